@@ -17,6 +17,7 @@ type HandlerOptions struct {
 	IBCKeeper         *keeper.Keeper
 	WasmConfig        *compute.WasmConfig
 	TXCounterStoreKey sdk.StoreKey
+	ComputeKeeper     *compute.Keeper
 }
 
 func NewAnteHandler(options HandlerOptions) (sdk.AnteHandler, error) {
@@ -32,6 +33,10 @@ func NewAnteHandler(options HandlerOptions) (sdk.AnteHandler, error) {
 		return nil, sdkerrors.Wrap(sdkerrors.ErrLogic, "sign mode handler is required for ante builder")
 	}
 
+	if options.ComputeKeeper == nil {
+		return nil, sdkerrors.Wrap(sdkerrors.ErrLogic, "compute keeper is required for ante builder")
+	}
+
 	sigGasConsumer := options.HandlerOptions.SigGasConsumer
 	if sigGasConsumer == nil {
 		sigGasConsumer = ante.DefaultSigVerificationGasConsumer
@@ -39,6 +44,7 @@ func NewAnteHandler(options HandlerOptions) (sdk.AnteHandler, error) {
 
 	anteDecorators := []sdk.AnteDecorator{
 		compute.NewCountTXDecorator(options.TXCounterStoreKey),
+		compute.NewFeeInfoDecorator(),
 		ante.NewSetUpContextDecorator(), // outermost AnteDecorator. SetUpContext must be called first
 		ante.NewRejectExtensionOptionsDecorator(),
 		ante.NewMempoolFeeDecorator(),
@@ -52,6 +58,7 @@ func NewAnteHandler(options HandlerOptions) (sdk.AnteHandler, error) {
 		ante.NewSigGasConsumeDecorator(options.HandlerOptions.AccountKeeper, sigGasConsumer),
 		ante.NewSigVerificationDecorator(options.HandlerOptions.AccountKeeper, options.HandlerOptions.SignModeHandler),
 		ante.NewIncrementSequenceDecorator(options.HandlerOptions.AccountKeeper),
+		compute.NewDecryptTxMsgsDecorator(options.ComputeKeeper),
 	}
 
 	return sdk.ChainAnteDecorators(anteDecorators...), nil
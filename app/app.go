@@ -35,6 +35,7 @@ import (
 	v1_10 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.10"
 	v1_11 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.11"
 	v1_12 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.12"
+	v1_13 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.13"
 	v1_3 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.3"
 	v1_4 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.4"
 	v1_5 "github.com/scrtlabs/SecretNetwork/app/upgrades/v1.5"
@@ -107,6 +108,7 @@ var (
 		v1_10.Upgrade,
 		v1_11.Upgrade,
 		v1_12.Upgrade,
+		v1_13.Upgrade,
 	}
 )
 
@@ -279,6 +281,7 @@ func NewSecretNetworkApp(
 		IBCKeeper:         app.AppKeepers.IbcKeeper,
 		WasmConfig:        computeConfig,
 		TXCounterStoreKey: app.AppKeepers.GetKey(compute.StoreKey),
+		ComputeKeeper:     app.AppKeepers.ComputeKeeper,
 	})
 	if err != nil {
 		panic(fmt.Errorf("failed to create AnteHandler: %s", err))
@@ -408,15 +411,33 @@ func (app *SecretNetworkApp) setupUpgradeHandlers() {
 	for _, upgradeDetails := range Upgrades {
 		app.AppKeepers.UpgradeKeeper.SetUpgradeHandler(
 			upgradeDetails.UpgradeName,
-			upgradeDetails.CreateUpgradeHandler(
+			app.withSeedRotation(upgradeDetails.CreateUpgradeHandler(
 				app.mm,
 				&app.AppKeepers,
 				app.configurator,
-			),
+			)),
 		)
 	}
 }
 
+// withSeedRotation activates the seed rotation carried by the info of the upgrade plan, if there is
+// one, before running the upgrade handler. A seed rotation takes effect at the height of the upgrade
+// that carries it, and the upgrade handler runs once, in the block at that height.
+func (app *SecretNetworkApp) withSeedRotation(handler upgradetypes.UpgradeHandler) upgradetypes.UpgradeHandler {
+	return func(ctx sdk.Context, plan upgradetypes.Plan, fromVM module.VersionMap) (module.VersionMap, error) {
+		restartRequired, err := app.AppKeepers.RegKeeper.ActivateSeedRotation(ctx, plan.Height, plan.Info)
+		if err != nil {
+			return nil, fmt.Errorf("failed to activate the seed rotation of upgrade %s: %w", plan.Name, err)
+		}
+		// The block isn't committed, so it's processed again with the new seed after the restart
+		if restartRequired {
+			panic(fmt.Sprintf("Activated the seed rotation of upgrade %s, restart the node to use the new seed", plan.Name))
+		}
+
+		return handler(ctx, plan, fromVM)
+	}
+}
+
 func (app *SecretNetworkApp) setupUpgradeStoreLoaders() {
 	upgradeInfo, err := app.AppKeepers.UpgradeKeeper.ReadUpgradeInfoFromDisk()
 	if err != nil {
@@ -35,6 +35,7 @@ import (
 
 	packetforwardrouter "github.com/cosmos/ibc-apps/middleware/packet-forward-middleware/v4/router"
 	"github.com/scrtlabs/SecretNetwork/x/compute"
+	computeclient "github.com/scrtlabs/SecretNetwork/x/compute/client"
 	icaauth "github.com/scrtlabs/SecretNetwork/x/mauth"
 	"github.com/scrtlabs/SecretNetwork/x/registration"
 )
@@ -56,6 +57,7 @@ var mbasics = module.NewBasicManager(
 			upgradeclient.CancelProposalHandler,
 			ibcclient.UpdateClientProposalHandler,
 			ibcclient.UpgradeProposalHandler,
+			computeclient.RemoveCodeProposalHandler,
 		),
 		params.AppModuleBasic{},
 		crisis.AppModuleBasic{},
@@ -73,6 +73,9 @@ import (
 	ibchookstypes "github.com/scrtlabs/SecretNetwork/x/ibc-hooks/types"
 )
 
+// ComputeSupportedFeatures are the features contracts can require, which the compute module offers
+const ComputeSupportedFeatures = "staking,stargate,ibc3,random"
+
 type SecretAppKeepers struct {
 	// keepers
 	AccountKeeper    *authkeeper.AccountKeeper
@@ -219,7 +222,11 @@ func (ak *SecretAppKeepers) InitSdkKeepers(
 		AddRoute(paramproposal.RouterKey, params.NewParamChangeProposalHandler(*ak.ParamsKeeper)).
 		AddRoute(distrtypes.RouterKey, distr.NewCommunityPoolSpendProposalHandler(*ak.DistrKeeper)).
 		AddRoute(upgradetypes.RouterKey, upgrade.NewSoftwareUpgradeProposalHandler(*ak.UpgradeKeeper)).
-		AddRoute(ibcclienttypes.RouterKey, ibcclient.NewClientProposalHandler(ak.IbcKeeper.ClientKeeper))
+		AddRoute(ibcclienttypes.RouterKey, ibcclient.NewClientProposalHandler(ak.IbcKeeper.ClientKeeper)).
+		AddRoute(compute.RouterKey, func(ctx sdk.Context, content govtypes.Content) error {
+			// the compute keeper needs the gov keeper, which seals this router, so it is created later
+			return compute.NewProposalHandler(*ak.ComputeKeeper)(ctx, content)
+		})
 
 	govKeeper := govkeeper.NewKeeper(
 		appCodec,
@@ -425,12 +432,13 @@ func (ak *SecretAppKeepers) InitCustomKeepers(
 	computeDir := filepath.Join(homePath, ".compute")
 	// The last arguments can contain custom message handlers, and custom query handlers,
 	// if we want to allow any custom callbacks
-	supportedFeatures := "staking,stargate,ibc3,random"
+	supportedFeatures := ComputeSupportedFeatures
 
 	computeKeeper := compute.NewKeeper(
 		appCodec,
 		*legacyAmino,
 		ak.keys[compute.StoreKey],
+		ak.GetSubspace(compute.ModuleName),
 		*ak.AccountKeeper,
 		ak.BankKeeper,
 		*ak.GovKeeper,
@@ -0,0 +1,47 @@
+package v1_13
+
+import (
+	"fmt"
+
+	store "github.com/cosmos/cosmos-sdk/store/types"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	"github.com/cosmos/cosmos-sdk/types/module"
+	upgradetypes "github.com/cosmos/cosmos-sdk/x/upgrade/types"
+	"github.com/scrtlabs/SecretNetwork/app/keepers"
+	"github.com/scrtlabs/SecretNetwork/app/upgrades"
+	"github.com/scrtlabs/SecretNetwork/x/compute"
+)
+
+const upgradeName = "v1.13"
+
+var Upgrade = upgrades.Upgrade{
+	UpgradeName:          upgradeName,
+	CreateUpgradeHandler: createUpgradeHandler,
+	StoreUpgrades:        store.StoreUpgrades{},
+}
+
+func createUpgradeHandler(mm *module.Manager, appKeepers *keepers.SecretAppKeepers, configurator module.Configurator,
+) upgradetypes.UpgradeHandler {
+	return func(ctx sdk.Context, _ upgradetypes.Plan, vm module.VersionMap) (module.VersionMap, error) {
+		ctx.Logger().Info(` _    _ _____   _____ _____            _____  ______ `)
+		ctx.Logger().Info(`| |  | |  __ \ / ____|  __ \     /\   |  __ \|  ____|`)
+		ctx.Logger().Info(`| |  | | |__) | |  __| |__) |   /  \  | |  | | |__   `)
+		ctx.Logger().Info(`| |  | |  ___/| | |_ |  _  /   / /\ \ | |  | |  __|  `)
+		ctx.Logger().Info(`| |__| | |    | |__| | | \ \  / ____ \| |__| | |____ `)
+		ctx.Logger().Info(` \____/|_|     \_____|_|  \_\/_/    \_\_____/|______|`)
+
+		// every node of the network runs this release from here on, record the consensus config
+		// they share so the ones started with another halt instead of forking off the network
+		appKeepers.ComputeKeeper.RecordConsensusConfigHash(ctx)
+
+		// compute moves from ConsensusVersion 5 to 6 in this release, its Migrate5to6 computes the
+		// storage statistics of every contract, which are kept up to date on every write from here on
+		if vm[compute.ModuleName] != 5 {
+			return nil, fmt.Errorf("%s expects compute at ConsensusVersion 5, got %d", upgradeName, vm[compute.ModuleName])
+		}
+		ctx.Logger().Info("Computing the storage statistics of every contract...")
+
+		ctx.Logger().Info(fmt.Sprintf("Running module migrations for %s...", upgradeName))
+		return mm.RunMigrations(ctx, configurator, vm)
+	}
+}
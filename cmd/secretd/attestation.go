@@ -261,7 +261,7 @@ func ConfigureSecret() *cobra.Command {
 
 			seed := args[1]
 			println(seed)
-			if (len(seed) != reg.LegacyEncryptedKeyLength && len(seed) != reg.EncryptedKeyLength) || !reg.IsHexString(seed) {
+			if !reg.IsValidEncryptedKeyLength(len(seed)) || !reg.IsHexString(seed) {
 				return fmt.Errorf("invalid encrypted seed format (requires hex string of length of at least 96 bytes without 0x prefix)")
 			}
 
@@ -516,7 +516,7 @@ Please report any issues with this command
 				seed = seed[2:]
 			}
 
-			if len(seed) != reg.EncryptedKeyLength || !reg.IsHexString(seed) {
+			if len(seed) < reg.EncryptedKeyLength || !reg.IsValidEncryptedKeyLength(len(seed)) || !reg.IsHexString(seed) {
 				return fmt.Errorf("invalid encrypted seed format (requires hex string of length 148 without 0x prefix)")
 			}
 
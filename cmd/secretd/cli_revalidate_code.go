@@ -0,0 +1,22 @@
+//go:build secretcli
+// +build secretcli
+
+package main
+
+import (
+	"github.com/spf13/cobra"
+)
+
+func RevalidateCode() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "revalidate-code",
+		Short: "Report the stored contracts that would fail validation after an upgrade",
+		Args:  cobra.ExactArgs(0),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			println("This is a secretd only function, yo")
+			return nil
+		},
+	}
+
+	return cmd
+}
@@ -0,0 +1,90 @@
+//go:build !secretcli
+// +build !secretcli
+
+package main
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"github.com/cosmos/cosmos-sdk/client/flags"
+	"github.com/spf13/cobra"
+
+	"github.com/scrtlabs/SecretNetwork/app/keepers"
+	"github.com/scrtlabs/SecretNetwork/go-cosmwasm/api"
+	"github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+)
+
+const (
+	flagSupportedFeatures = "supported-features"
+	flagValidationConfig  = "validation-config"
+)
+
+// RevalidateCode reports the contracts stored by the node that would no longer pass validation with
+// other supported features or another validation config, e.g. those of an upcoming upgrade.
+func RevalidateCode() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "revalidate-code",
+		Short: "Report the stored contracts that would fail validation after an upgrade",
+		Long: `Validate every contract stored by the node again, with the given supported features and
+validation config, and print a JSON report of the ones that would be rejected. The validation
+config is a JSON file in the format of go-cosmwasm's ValidationConfig, unset fields keep the
+defaults. Nothing is changed, the node keeps validating contracts with its own config.
+`,
+		Args: cobra.ExactArgs(0),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			homeDir, err := cmd.Flags().GetString(flags.FlagHome)
+			if err != nil {
+				return err
+			}
+			supportedFeatures, err := cmd.Flags().GetString(flagSupportedFeatures)
+			if err != nil {
+				return err
+			}
+			validationConfigPath, err := cmd.Flags().GetString(flagValidationConfig)
+			if err != nil {
+				return err
+			}
+
+			var validationConfig []byte
+			if validationConfigPath != "" {
+				bz, err := os.ReadFile(validationConfigPath)
+				if err != nil {
+					return err
+				}
+				var config types.ValidationConfig
+				if err := json.Unmarshal(bz, &config); err != nil {
+					return fmt.Errorf("invalid validation config %s: %w", validationConfigPath, err)
+				}
+				validationConfig, err = json.Marshal(config)
+				if err != nil {
+					return err
+				}
+			}
+
+			// the node validates with its own features, the report is made with the given ones
+			cache, err := api.InitCache(filepath.Join(homeDir, ".compute", "wasm"), keepers.ComputeSupportedFeatures, nil, nil, 0)
+			if err != nil {
+				return err
+			}
+			defer api.ReleaseCache(cache)
+
+			report, err := api.RevalidateStoredCode(cache, supportedFeatures, validationConfig)
+			if err != nil {
+				return err
+			}
+			out, err := json.MarshalIndent(report, "", "  ")
+			if err != nil {
+				return err
+			}
+			fmt.Println(string(out))
+			return nil
+		},
+	}
+	cmd.Flags().String(flagSupportedFeatures, keepers.ComputeSupportedFeatures, "Comma separated features contracts can require after the upgrade")
+	cmd.Flags().String(flagValidationConfig, "", "JSON file with the validation config after the upgrade, the defaults if unset")
+
+	return cmd
+}
@@ -2,6 +2,7 @@ package main
 
 import (
 	"context"
+	"encoding/json"
 	"fmt"
 	"io"
 	"os"
@@ -183,6 +184,7 @@ func initRootCmd(rootCmd *cobra.Command, encodingConfig app.EncodingConfig) {
 		HealthCheck(),
 		ResetEnclave(),
 		AutoRegisterNode(),
+		RevalidateCode(),
 		keys.Commands(app.DefaultNodeHome),
 		clientconfig.Cmd(),
 	)
@@ -288,12 +290,18 @@ func newApp(logger log.Logger, db dbm.DB, traceStore io.Writer, appOpts serverty
 
 	// fmt.Printf("bootstrap: %s\n", cast.ToString(bootstrap))
 
+	computeConfig := compute.GetConfig(appOpts)
+	computeConfig.ChainID, err = genesisChainID(cast.ToString(appOpts.Get(flags.FlagHome)))
+	if err != nil {
+		panic(err)
+	}
+
 	return app.NewSecretNetworkApp(logger, db, traceStore, true, skipUpgradeHeights,
 		cast.ToString(appOpts.Get(flags.FlagHome)),
 		cast.ToUint(appOpts.Get(server.FlagInvCheckPeriod)),
 		bootstrap,
 		appOpts,
-		compute.GetConfig(appOpts),
+		computeConfig,
 		baseapp.SetPruning(pruningOpts),
 		baseapp.SetMinGasPrices(cast.ToString(appOpts.Get(server.FlagMinGasPrices))),
 		baseapp.SetHaltHeight(cast.ToUint64(appOpts.Get(server.FlagHaltHeight))),
@@ -310,6 +318,23 @@ func newApp(logger log.Logger, db dbm.DB, traceStore io.Writer, appOpts serverty
 	)
 }
 
+// genesisChainID returns the chain-id of the genesis file of the node, without keeping its state
+func genesisChainID(homeDir string) (string, error) {
+	file, err := os.Open(filepath.Join(homeDir, "config", "genesis.json"))
+	if err != nil {
+		return "", err
+	}
+	defer file.Close()
+
+	var genesis struct {
+		ChainID string `json:"chain_id"`
+	}
+	if err := json.NewDecoder(file).Decode(&genesis); err != nil {
+		return "", fmt.Errorf("failed to read the chain-id of the genesis file: %w", err)
+	}
+	return genesis.ChainID, nil
+}
+
 func exportAppStateAndTMValidators(
 	logger log.Logger, db dbm.DB, traceStore io.Writer, height int64, forZeroHeight bool, jailWhiteList []string, appOpts servertypes.AppOptions, modulesToExport []string,
 ) (servertypes.ExportedApp, error) {
@@ -1449,6 +1449,33 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         }
         ExecuteMsg::Echo { data }   => {
             Ok(Response::new().set_data(data))}
+        ExecuteMsg::ReadYourWrites {} => read_your_writes(deps),
+        ExecuteMsg::ReadYourWritesWithSubmessage {} => {
+            let mut store = PrefixedStorage::new(deps.storage, b"my_prefix");
+            store.set(b"parent", b"1");
+            expect_state(deps.storage, b"parent", Some(b"1"))?;
+
+            Ok(Response::new().add_submessage(SubMsg::reply_always(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    code_hash: env.contract.code_hash,
+                    contract_addr: env.contract.address.into_string(),
+                    msg: Binary::from(r#"{"read_your_writes_in_submessage":{}}"#.as_bytes().to_vec()),
+                    funds: vec![],
+                }),
+                9400,
+            )))
+        }
+        ExecuteMsg::ReadYourWritesInSubmessage {} => {
+            expect_state(deps.storage, b"parent", Some(b"1"))?;
+
+            let mut store = PrefixedStorage::new(deps.storage, b"my_prefix");
+            store.set(b"child", b"2");
+            store.remove(b"parent");
+            expect_state(deps.storage, b"child", Some(b"2"))?;
+            expect_state(deps.storage, b"parent", None)?;
+
+            Ok(Response::default())
+        }
     }
 }
 
@@ -2505,6 +2532,20 @@ pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> StdResult<Response> {
                 })),
             )
         }
+        (9400, SubMsgResult::Ok(_)) => {
+            expect_state(deps.storage, b"child", Some(b"2"))?;
+            expect_state(deps.storage, b"parent", None)?;
+
+            let mut store = PrefixedStorage::new(deps.storage, b"my_prefix");
+            store.set(b"reply", b"3");
+            expect_state(deps.storage, b"reply", Some(b"3"))?;
+
+            Ok(Response::default())
+        }
+        (9400, SubMsgResult::Err(e)) => Err(StdError::generic_err(format!(
+            "read your writes in sub-message failed: {}",
+            e
+        ))),
         _ => Err(StdError::generic_err("invalid reply id or result")),
     }
 }
@@ -2855,6 +2896,49 @@ fn remove_state(deps: DepsMut, key: String) -> Response {
     Response::default()
 }
 
+fn expect_state(storage: &dyn Storage, key: &[u8], expected: Option<&[u8]>) -> StdResult<()> {
+    let store = cosmwasm_storage::ReadonlyPrefixedStorage::new(storage, b"my_prefix");
+    let value = store.get(key);
+    if value.as_deref() != expected {
+        return Err(StdError::generic_err(format!(
+            "read {:?} from key {:?} instead of {:?}",
+            value.as_deref().map(String::from_utf8_lossy),
+            String::from_utf8_lossy(key),
+            expected.map(String::from_utf8_lossy),
+        )));
+    }
+    Ok(())
+}
+
+/// Expects the key "committed" to hold "0", written by a previous call
+fn read_your_writes(deps: DepsMut) -> StdResult<Response> {
+    // a key the call writes first
+    expect_state(deps.storage, b"fresh", None)?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").set(b"fresh", b"1");
+    expect_state(deps.storage, b"fresh", Some(b"1"))?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").set(b"fresh", b"2");
+    expect_state(deps.storage, b"fresh", Some(b"2"))?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").remove(b"fresh");
+    expect_state(deps.storage, b"fresh", None)?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").set(b"fresh", b"3");
+    expect_state(deps.storage, b"fresh", Some(b"3"))?;
+
+    // a key read from the chain state first, which the enclave keeps in its read cache
+    expect_state(deps.storage, b"committed", Some(b"0"))?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").set(b"committed", b"1");
+    expect_state(deps.storage, b"committed", Some(b"1"))?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").remove(b"committed");
+    expect_state(deps.storage, b"committed", None)?;
+    PrefixedStorage::new(deps.storage, b"my_prefix").set(b"committed", b"2");
+    expect_state(deps.storage, b"committed", Some(b"2"))?;
+
+    // a key removed before it is ever read or written
+    PrefixedStorage::new(deps.storage, b"my_prefix").remove(b"never_written");
+    expect_state(deps.storage, b"never_written", None)?;
+
+    Ok(Response::default())
+}
+
 #[allow(invalid_value)]
 #[allow(unused_must_use)]
 fn pass_null_pointer_to_imports_should_throw(deps: DepsMut, pass_type: String) -> Response {
@@ -458,6 +458,12 @@ pub enum ExecuteMsg {
     Echo {
         data: Binary,
     },
+    /// Interleaves writes, removals and reads of the same keys and fails if a read doesn't see the
+    /// pending write
+    ReadYourWrites {},
+    /// Writes a key, then reads it back from a sub-message and its reply
+    ReadYourWritesWithSubmessage {},
+    ReadYourWritesInSubmessage {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -9,8 +9,16 @@ extern crate sgx_trts;
 extern crate sgx_types;
 
 use ctor::*;
+use enclave_utils::alloc_tracker::TrackingAllocator;
 use enclave_utils::logger::get_log_level;
 
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+// Decrypted state keys leak contract state, see `state_key_decryption`
+#[cfg(all(feature = "state-key-decryption", feature = "SGX_MODE_HW"))]
+compile_error!("state-key-decryption can only be enabled in software mode");
+
 // Force linking to all the ecalls/ocalls in this package
 pub use enclave_contract_engine;
 mod ecalls;
@@ -21,6 +29,12 @@ mod tests;
 #[cfg(feature = "SGX_MODE_HW")]
 use crate::registration::check_patch_level::ecall_check_patch_level;
 
+/// Lets contracts verify node attestations with the remote attestation code of this crate
+#[ctor]
+fn init_node_attestation_verifier() {
+    enclave_contract_engine::set_node_attestation_verifier(registration::verify_node_attestation);
+}
+
 #[cfg(feature = "production")]
 #[ctor]
 fn init_logger() {
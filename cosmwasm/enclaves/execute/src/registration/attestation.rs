@@ -41,6 +41,9 @@ use crate::registration::cert::verify_ra_cert;
 #[cfg(feature = "SGX_MODE_HW")]
 use enclave_crypto::consts::SIGNING_METHOD;
 
+#[cfg(feature = "SGX_MODE_HW")]
+use enclave_contract_engine::{node_signer_binding, NODE_SIGNER_BINDING_LENGTH};
+
 #[cfg(feature = "SGX_MODE_HW")]
 use enclave_crypto::consts::SigningMethod;
 
@@ -133,14 +136,20 @@ pub fn validate_enclave_version(
     let (prv_k, pub_k) = ecc_handle.create_key_pair().unwrap();
 
     // call create_report using the secp256k1 public key, and __not__ the P256 one
-    let signed_report =
-        match create_attestation_report(&kp.get_pubkey(), sign_type, api_key, challenge, true) {
-            Ok(r) => r,
-            Err(e) => {
-                error!("Error creating attestation report");
-                return Err(e);
-            }
-        };
+    let signed_report = match create_attestation_report(
+        &kp.get_pubkey(),
+        sign_type,
+        api_key,
+        challenge,
+        None,
+        true,
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            error!("Error creating attestation report");
+            return Err(e);
+        }
+    };
 
     let payload: String = serde_json::to_string(&signed_report).map_err(|_| {
         error!("Error serializing report. May be malformed, or badly encoded");
@@ -230,14 +239,22 @@ pub fn create_attestation_certificate(
     let (prv_k, pub_k) = ecc_handle.create_key_pair().unwrap();
 
     // call create_report using the secp256k1 public key, and __not__ the P256 one
-    let signed_report =
-        match create_attestation_report(&kp.get_pubkey(), sign_type, api_key, challenge, true) {
-            Ok(r) => r,
-            Err(e) => {
-                error!("Error creating attestation report");
-                return Err(e);
-            }
-        };
+    // commit to the node signing key, so that what it signs can be traced back to this report
+    let signer_binding = node_signer_binding(kp);
+    let signed_report = match create_attestation_report(
+        &kp.get_pubkey(),
+        sign_type,
+        api_key,
+        challenge,
+        Some(&signer_binding),
+        true,
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            error!("Error creating attestation report");
+            return Err(e);
+        }
+    };
 
     let payload: String = serde_json::to_string(&signed_report).map_err(|_| {
         error!("Error serializing report. May be malformed, or badly encoded");
@@ -296,6 +313,7 @@ pub fn create_attestation_report(
     sign_type: sgx_quote_sign_type_t,
     api_key_file: &[u8],
     challenge: Option<&[u8]>,
+    signer_binding: Option<&[u8; NODE_SIGNER_BINDING_LENGTH]>,
     early: bool,
 ) -> Result<EndorsedAttestationReport, sgx_status_t> {
     // Workflow:
@@ -356,6 +374,9 @@ pub fn create_attestation_report(
     if let Some(c) = challenge {
         report_data.d[32..36].copy_from_slice(c);
     }
+    if let Some(b) = signer_binding {
+        report_data.d[36..].copy_from_slice(b);
+    }
 
     let rep = match rsgx_create_report(&ti, &report_data) {
         Ok(r) => {
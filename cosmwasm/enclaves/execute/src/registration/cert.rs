@@ -17,6 +17,7 @@ use std::str;
 use std::time::{SystemTime, UNIX_EPOCH};
 use yasna::models::ObjectIdentifier;
 
+use enclave_contract_engine::NodeAttestation;
 use enclave_crypto::consts::{SigningMethod, CERTEXPIRYDAYS};
 #[cfg(feature = "SGX_MODE_HW")]
 use enclave_crypto::consts::{MRSIGNER, SIGNING_METHOD};
@@ -365,6 +366,31 @@ pub fn verify_ra_cert(
     Ok(report_public_key)
 }
 
+/// Verifies a node's attestation certificate for contracts, see `check_node_attestation`.
+/// Simulated enclaves have no TCB, so they are reported at level 0.
+#[cfg(not(feature = "SGX_MODE_HW"))]
+pub fn verify_node_attestation(cert_der: &[u8]) -> Result<NodeAttestation, NodeAuthResult> {
+    let public_key = verify_ra_cert(cert_der, None, false)?;
+
+    Ok(NodeAttestation {
+        public_key,
+        tcb_level: 0,
+    })
+}
+
+/// Verifies a node's attestation certificate for contracts, see `check_node_attestation`.
+/// The minimum TCB level is up to the contract, so it isn't enforced here.
+#[cfg(feature = "SGX_MODE_HW")]
+pub fn verify_node_attestation(cert_der: &[u8]) -> Result<NodeAttestation, NodeAuthResult> {
+    let public_key = verify_ra_cert(cert_der, None, false)?;
+    let report = AttestationReport::from_cert(cert_der).map_err(|_| NodeAuthResult::InvalidCert)?;
+
+    Ok(NodeAttestation {
+        public_key,
+        tcb_level: report.tcb_eval_data_number,
+    })
+}
+
 // fn transform_u32_to_array_of_u8(x: u32) -> [u8; 4] {
 //     let b1: u8 = ((x >> 24) & 0xff) as u8;
 //     let b2: u8 = ((x >> 16) & 0xff) as u8;
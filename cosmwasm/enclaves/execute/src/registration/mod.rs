@@ -1,4 +1,5 @@
 pub use attestation::create_attestation_certificate;
+pub use cert::verify_node_attestation;
 pub use offchain::{ecall_get_attestation_report, ecall_init_bootstrap, ecall_init_node};
 pub use onchain::ecall_authenticate_new_node;
 
@@ -10,6 +11,8 @@ mod onchain;
 mod persistency;
 mod report;
 mod seed_exchange;
+mod seed_rotation;
+mod seed_shares;
 
 #[cfg(feature = "SGX_MODE_HW")]
 mod ocalls;
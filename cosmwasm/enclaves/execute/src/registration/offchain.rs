@@ -12,12 +12,12 @@ use enclave_crypto::consts::{
     SEED_UPDATE_SAVE_PATH, SIGNATURE_TYPE,
 };
 
-use enclave_crypto::{KeyPair, Keychain, KEY_MANAGER, PUBLIC_KEY_SIZE};
+use enclave_crypto::{ct_eq, KeyPair, Keychain, Seed, KEY_MANAGER, PUBLIC_KEY_SIZE};
 
 use enclave_utils::pointers::validate_mut_slice;
 use enclave_utils::{validate_const_ptr, validate_mut_ptr};
 
-use enclave_ffi_types::SINGLE_ENCRYPTED_SEED_SIZE;
+use enclave_ffi_types::{MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE, SINGLE_ENCRYPTED_SEED_SIZE};
 
 use super::attestation::create_attestation_certificate;
 
@@ -249,6 +249,13 @@ pub unsafe extern "C" fn ecall_init_node(
 
     let mut key_manager = Keychain::new();
 
+    // The registration payload keeps the seed that was current when the node registered, so a node
+    // that activated a seed rotation since then must keep the seed it rotated to
+    let sealed_current_seed = key_manager
+        .get_consensus_seed()
+        .ok()
+        .map(|seeds| seeds.current);
+
     // even though key is overwritten later we still want to explicitly remove it in case we increase the security version
     // to make sure that it is resealed using the new svn
     if let Err(_e) = key_manager.reseal_registration_key() {
@@ -284,7 +291,13 @@ pub unsafe extern "C" fn ecall_init_node(
         );
         new_consensus_seed = match decrypt_seed(&key_manager, target_public_key, single_seed_bytes)
         {
-            Ok(result) => result,
+            Ok(result) => match sealed_current_seed {
+                Some(sealed) if is_previous_seed(&key_manager, &result) => {
+                    debug!("Keeping the seed this node rotated to since it registered");
+                    sealed
+                }
+                _ => result,
+            },
             Err(status) => return status,
         };
 
@@ -342,6 +355,110 @@ pub unsafe extern "C" fn ecall_init_node(
     sgx_status_t::SGX_SUCCESS
 }
 
+fn is_previous_seed(key_manager: &Keychain, seed: &Seed) -> bool {
+    key_manager
+        .get_previous_consensus_seeds()
+        .iter()
+        .any(|previous| ct_eq(previous.as_slice(), seed.as_slice()))
+}
+
+///
+/// `ecall_init_previous_seeds`
+///
+/// Seals the seeds that were current before each seed rotation, as shared with this node by
+/// [ecall_get_encrypted_previous_seeds] when it registered, after [ecall_init_node].
+///
+/// The seeds the node already has are kept if they are the same or more, e.g. when the node
+/// activated a rotation after it registered. Seeds that don't extend them are rejected.
+///
+/// This function happens off-chain
+///
+/// # Safety
+///  Something should go here
+///
+#[no_mangle]
+pub unsafe extern "C" fn ecall_init_previous_seeds(
+    master_key: *const u8,
+    master_key_len: u32,
+    encrypted_seeds: *const u8,
+    encrypted_seeds_len: u32,
+) -> sgx_status_t {
+    validate_const_ptr!(
+        master_key,
+        master_key_len as usize,
+        sgx_status_t::SGX_ERROR_UNEXPECTED,
+    );
+    validate_const_ptr!(
+        encrypted_seeds,
+        encrypted_seeds_len as usize,
+        sgx_status_t::SGX_ERROR_UNEXPECTED,
+    );
+
+    let key_slice = slice::from_raw_parts(master_key, master_key_len as usize);
+    let encrypted_seeds_slice =
+        slice::from_raw_parts(encrypted_seeds, encrypted_seeds_len as usize);
+
+    if key_slice.len() != PUBLIC_KEY_SIZE {
+        error!("Got public key with the wrong size: {:?}", key_slice.len());
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+    if encrypted_seeds_slice.len() % SINGLE_ENCRYPTED_SEED_SIZE != 0
+        || encrypted_seeds_slice.len() > MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE
+    {
+        error!(
+            "Encrypted previous seeds bad length: {}",
+            encrypted_seeds_slice.len()
+        );
+        return sgx_status_t::SGX_ERROR_INVALID_PARAMETER;
+    }
+
+    let mut target_public_key: [u8; PUBLIC_KEY_SIZE] = [0u8; PUBLIC_KEY_SIZE];
+    target_public_key.copy_from_slice(key_slice);
+
+    let result = panic::catch_unwind(|| -> Result<(), sgx_status_t> {
+        let mut key_manager = Keychain::new();
+
+        let mut seeds = vec![];
+        for chunk in encrypted_seeds_slice.chunks(SINGLE_ENCRYPTED_SEED_SIZE) {
+            let mut single_seed_bytes = [0u8; SINGLE_ENCRYPTED_SEED_SIZE];
+            single_seed_bytes.copy_from_slice(chunk);
+            seeds.push(decrypt_seed(
+                &key_manager,
+                target_public_key,
+                single_seed_bytes,
+            )?);
+        }
+
+        let sealed = key_manager.get_previous_consensus_seeds();
+        // One of them must start with the other
+        let same_history = seeds
+            .iter()
+            .zip(sealed.iter())
+            .all(|(a, b)| ct_eq(a.as_slice(), b.as_slice()));
+        if !same_history {
+            error!("Previous seeds from registration don't match the ones of this node");
+            return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+        }
+        if seeds.len() <= sealed.len() {
+            debug!("Previous seeds are already up to date");
+            return Ok(());
+        }
+
+        key_manager
+            .set_previous_consensus_seeds(seeds)
+            .map_err(|_| sgx_status_t::SGX_ERROR_UNEXPECTED)
+    });
+
+    match result {
+        Ok(Ok(())) => sgx_status_t::SGX_SUCCESS,
+        Ok(Err(status)) => status,
+        Err(_) => {
+            warn!("Enclave call ecall_init_previous_seeds panic!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
 #[no_mangle]
 /**
  * `ecall_get_attestation_report`
@@ -429,6 +546,34 @@ pub unsafe extern "C" fn ecall_key_gen(
     sgx_status_t::SGX_SUCCESS
 }
 
+///
+/// `ecall_get_mr_enclave`
+///
+/// Returns the MRENCLAVE of this enclave, the measurement of its code that its attestation reports
+/// carry, so that clients can tell which enclave build a node runs. Enclaves running in software
+/// mode have no meaningful measurement and don't support it.
+///
+/// # Safety
+/// Always use protection
+///
+#[no_mangle]
+pub unsafe extern "C" fn ecall_get_mr_enclave(mr_enclave: &mut [u8; 32]) -> sgx_status_t {
+    if let Err(_e) = validate_mut_slice(mr_enclave) {
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    #[cfg(feature = "SGX_MODE_HW")]
+    {
+        mr_enclave.copy_from_slice(&super::attestation::get_mr_enclave());
+        sgx_status_t::SGX_SUCCESS
+    }
+
+    #[cfg(not(feature = "SGX_MODE_HW"))]
+    {
+        sgx_status_t::SGX_ERROR_FEATURE_NOT_SUPPORTED
+    }
+}
+
 ///
 /// `ecall_get_genesis_seed
 ///
@@ -4,11 +4,11 @@
 use log::*;
 use std::panic;
 
-use enclave_ffi_types::NodeAuthResult;
+use enclave_ffi_types::{NodeAuthResult, MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE};
 
 use crate::registration::seed_exchange::SeedType;
 use enclave_crypto::consts::OUTPUT_ENCRYPTED_SEED_SIZE;
-use enclave_crypto::PUBLIC_KEY_SIZE;
+use enclave_crypto::{KEY_MANAGER, PUBLIC_KEY_SIZE};
 use enclave_utils::{
     oom_handler::{self, get_then_clear_oom_happened},
     validate_const_ptr, validate_mut_ptr,
@@ -20,6 +20,29 @@ use super::seed_exchange::encrypt_seed;
 #[cfg(feature = "light-client-validation")]
 use enclave_contract_engine::check_cert_in_current_block;
 
+/// Verifies the certificate of a registering node, and returns the public key in the extra data of
+/// its report
+fn registering_node_key(cert: &[u8]) -> Result<[u8; PUBLIC_KEY_SIZE], NodeAuthResult> {
+    let pk = verify_ra_cert(cert, None, true)?;
+
+    // just make sure the length isn't wrong for some reason (certificate may be malformed)
+    if pk.len() != PUBLIC_KEY_SIZE {
+        warn!(
+            "Got public key from certificate with the wrong size: {:?}",
+            pk.len()
+        );
+        return Err(NodeAuthResult::MalformedPublicKey);
+    }
+
+    let mut target_public_key: [u8; PUBLIC_KEY_SIZE] = [0u8; PUBLIC_KEY_SIZE];
+    target_public_key.copy_from_slice(&pk);
+    trace!(
+        "ecall_get_encrypted_seed target_public_key key pk: {:?}",
+        &target_public_key.to_vec()
+    );
+    Ok(target_public_key)
+}
+
 ///
 /// `ecall_authenticate_new_node`
 ///
@@ -57,24 +80,7 @@ pub unsafe extern "C" fn ecall_authenticate_new_node(
     }
 
     let result = panic::catch_unwind(|| -> Result<Vec<u8>, NodeAuthResult> {
-        // verify certificate, and return the public key in the extra data of the report
-        let pk = verify_ra_cert(cert_slice, None, true)?;
-
-        // just make sure the length isn't wrong for some reason (certificate may be malformed)
-        if pk.len() != PUBLIC_KEY_SIZE {
-            warn!(
-                "Got public key from certificate with the wrong size: {:?}",
-                pk.len()
-            );
-            return Err(NodeAuthResult::MalformedPublicKey);
-        }
-
-        let mut target_public_key: [u8; 32] = [0u8; 32];
-        target_public_key.copy_from_slice(&pk);
-        trace!(
-            "ecall_get_encrypted_seed target_public_key key pk: {:?}",
-            &target_public_key.to_vec()
-        );
+        let target_public_key = registering_node_key(cert_slice)?;
 
         let mut res: Vec<u8> = encrypt_seed(target_public_key, SeedType::Genesis, false)
             .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?;
@@ -113,3 +119,88 @@ pub unsafe extern "C" fn ecall_authenticate_new_node(
         NodeAuthResult::Panic
     }
 }
+
+///
+/// `ecall_get_encrypted_previous_seeds`
+///
+/// Shares the seeds that were current before each seed rotation with a registering node, encrypted
+/// like the seeds shared by [ecall_authenticate_new_node]. Nodes that register after a rotation
+/// need them to read state written before it.
+///
+/// `seeds` is filled with the encrypted seeds, oldest first, and `seeds_len` with their length.
+///
+/// This function happens on-chain, so any panic here might cause the chain to go boom
+///
+/// # Safety
+/// Safety first
+#[no_mangle]
+pub unsafe extern "C" fn ecall_get_encrypted_previous_seeds(
+    cert: *const u8,
+    cert_len: u32,
+    seeds: &mut [u8; MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE],
+    seeds_len: &mut u32,
+) -> NodeAuthResult {
+    if let Err(_err) = oom_handler::register_oom_handler() {
+        error!("Could not register OOM handler!");
+        return NodeAuthResult::MemorySafetyAllocationError;
+    }
+
+    validate_mut_ptr!(
+        seeds.as_mut_ptr(),
+        seeds.len(),
+        NodeAuthResult::InvalidInput
+    );
+    validate_mut_ptr!(
+        seeds_len as *mut u32 as *mut u8,
+        std::mem::size_of::<u32>(),
+        NodeAuthResult::InvalidInput
+    );
+    validate_const_ptr!(cert, cert_len as usize, NodeAuthResult::InvalidInput);
+
+    let cert_slice = std::slice::from_raw_parts(cert, cert_len as usize);
+
+    #[cfg(feature = "light-client-validation")]
+    if !check_cert_in_current_block(cert_slice) {
+        return NodeAuthResult::SignatureInvalid;
+    }
+
+    let result = panic::catch_unwind(|| -> Result<Vec<u8>, NodeAuthResult> {
+        let target_public_key = registering_node_key(cert_slice)?;
+
+        let mut res = vec![];
+        for index in 0..KEY_MANAGER.get_previous_consensus_seeds().len() {
+            res.extend(
+                encrypt_seed(target_public_key, SeedType::Previous(index), false)
+                    .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?,
+            );
+        }
+
+        Ok(res)
+    });
+
+    if let Err(_err) = oom_handler::restore_safety_buffer() {
+        error!("Could not restore OOM safety buffer!");
+        return NodeAuthResult::MemorySafetyAllocationError;
+    }
+
+    match result {
+        Ok(Ok(res)) => {
+            if res.len() > seeds.len() {
+                error!("Too many previous seeds to share: {} bytes", res.len());
+                return NodeAuthResult::SeedEncryptionFailed;
+            }
+            seeds[..res.len()].copy_from_slice(&res);
+            *seeds_len = res.len() as u32;
+            NodeAuthResult::Success
+        }
+        Ok(Err(e)) => {
+            trace!("error encrypting previous seeds {:?}", e);
+            e
+        }
+        Err(_) => {
+            get_then_clear_oom_happened();
+            warn!("Enclave call ecall_get_encrypted_previous_seeds panic!");
+            NodeAuthResult::Panic
+        }
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "pq-hybrid")]
+use enclave_crypto::consts::IO_KYBER_KEY_SAVE_PATH;
 use enclave_crypto::consts::{IO_KEY_SAVE_PATH, SEED_EXCH_KEY_SAVE_PATH};
 use enclave_crypto::{KeyPair, Keychain};
 use enclave_utils::storage::rewrite_on_untrusted;
@@ -18,5 +20,14 @@ pub fn write_master_pub_keys(key_manager: &Keychain) -> SgxResult<()> {
     let kp = key_manager.get_consensus_io_exchange_keypair().unwrap();
     write_public_key(&kp.current, IO_KEY_SAVE_PATH)?;
 
+    #[cfg(feature = "pq-hybrid")]
+    {
+        let kp = key_manager.get_consensus_io_kyber_keypair().unwrap();
+        rewrite_on_untrusted(
+            base64::encode(kp.get_pubkey()).as_bytes(),
+            IO_KYBER_KEY_SAVE_PATH,
+        )?;
+    }
+
     Ok(())
 }
@@ -10,6 +10,8 @@ use enclave_ffi_types::SINGLE_ENCRYPTED_SEED_SIZE;
 pub enum SeedType {
     Genesis,
     Current,
+    /// The seed that was current before the rotation at this index, oldest first
+    Previous(usize),
 }
 
 pub fn encrypt_seed(
@@ -26,6 +28,10 @@ pub fn encrypt_seed(
     let seed_to_share = match seed_type {
         SeedType::Genesis => KEY_MANAGER.get_consensus_seed().unwrap().genesis,
         SeedType::Current => KEY_MANAGER.get_consensus_seed().unwrap().current,
+        SeedType::Previous(index) => *KEY_MANAGER
+            .get_previous_consensus_seeds()
+            .get(index)
+            .ok_or(sgx_status_t::SGX_ERROR_INVALID_PARAMETER)?,
     };
 
     let shared_enc_key = base_seed.diffie_hellman(&new_node_pk);
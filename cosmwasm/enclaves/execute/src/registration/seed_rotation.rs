@@ -0,0 +1,489 @@
+//!
+/// Support for rotating the current consensus seed in a multi-party ceremony:
+///
+/// 1. A proposer generates a candidate seed (off-chain). The candidate is encrypted with a key
+///    derived from the current consensus seed, so only genuine enclaves of this network can read it,
+///    and is bound to the height at which it should become active.
+/// 2. Validators' enclaves approve the candidate (off-chain). An approval is a MAC over the
+///    candidate and activation height, keyed with the Diffie-Hellman secret of the node's
+///    registration key and the network's seed exchange key.
+/// 3. The candidate, the approvals and the attestation certificates of the approving nodes are put
+///    in a software upgrade plan, and governance votes on it.
+/// 4. When the upgrade is applied, at the upgrade height, every enclave verifies each approval
+///    against the attestation certificate it came with, so approvals can only count for attested
+///    enclaves, and activates the candidate as its new current seed if at least the planned number
+///    of distinct nodes approved it. Every replaced seed is kept to decrypt state written before the rotation,
+///    and shared with the nodes that register afterwards. The genesis seed is never rotated.
+///
+use log::*;
+use sgx_types::sgx_status_t;
+use std::panic;
+use std::slice;
+
+use enclave_crypto::consts::SEED_ROTATION_KEY_DERIVE_ORDER;
+use enclave_crypto::key_manager::KEY_MANAGER_LOADED;
+use enclave_crypto::{
+    ct_eq, sha_256, AESKey, Hmac, Kdf, KeyPair, Keychain, SIVEncryptable, Seed, HASH_SIZE,
+    HMAC_SIGNATURE_SIZE, KEY_MANAGER, PUBLIC_KEY_SIZE, SEED_KEY_SIZE,
+};
+use enclave_ffi_types::{NodeAuthResult, SEED_ROTATION_APPROVAL_SIZE, SINGLE_ENCRYPTED_SEED_SIZE};
+use enclave_utils::{validate_const_ptr, validate_mut_ptr};
+
+use super::cert::verify_ra_cert;
+use super::persistency::write_master_pub_keys;
+
+/// The fewest distinct nodes that must approve a candidate for it to be activated, whatever the
+/// upgrade plan asks for
+pub const MIN_SEED_ROTATION_APPROVALS: u32 = 2;
+
+/// The key used to encrypt candidates. Derived from the seed being replaced, so that a candidate can
+/// only be read by enclaves which already hold it.
+fn rotation_key(replaced_seed: &Seed) -> AESKey {
+    replaced_seed.derive_key_from_this(&SEED_ROTATION_KEY_DERIVE_ORDER.to_be_bytes())
+}
+
+fn current_seed(key_manager: &Keychain) -> Result<Seed, sgx_status_t> {
+    let seeds = key_manager.get_consensus_seed().map_err(|_| {
+        error!("Seed rotation requires an initialized node");
+        sgx_status_t::SGX_ERROR_INVALID_STATE
+    })?;
+    Ok(seeds.current)
+}
+
+fn decrypt_candidate(
+    replaced_seed: &Seed,
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+) -> Result<Seed, sgx_status_t> {
+    let height = activation_height.to_be_bytes();
+    let authenticated_data: Vec<&[u8]> = vec![&height];
+
+    let plaintext = rotation_key(replaced_seed)
+        .decrypt_siv(encrypted_candidate, Some(&authenticated_data))
+        .map_err(|_| {
+            warn!("Failed to decrypt seed rotation candidate");
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        })?;
+
+    if plaintext.len() != SEED_KEY_SIZE {
+        error!(
+            "Seed rotation candidate has invalid length - {:?}",
+            plaintext.len()
+        );
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    let mut candidate = Seed::default();
+    candidate.as_mut().copy_from_slice(&plaintext);
+    Ok(candidate)
+}
+
+/// The public commitment to a candidate, used to refer to it in approvals and logs
+fn candidate_hash(encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE]) -> [u8; HASH_SIZE] {
+    sha_256(encrypted_candidate)
+}
+
+fn approval_mac(
+    approval_key: &AESKey,
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+) -> [u8; HMAC_SIGNATURE_SIZE] {
+    let mut data = candidate_hash(encrypted_candidate).to_vec();
+    data.extend_from_slice(&activation_height.to_be_bytes());
+    approval_key.sign_sha_256(&data)
+}
+
+/// Verifies that `approval` was made for this candidate and activation height by the enclave the
+/// attestation certificate `cert` belongs to. Returns the registration key of the approving node.
+fn verify_approval(
+    network_key: &KeyPair,
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+    approval: &[u8],
+    cert: &[u8],
+) -> Result<[u8; PUBLIC_KEY_SIZE], NodeAuthResult> {
+    if approval.len() != SEED_ROTATION_APPROVAL_SIZE {
+        return Err(NodeAuthResult::InvalidInput);
+    }
+
+    let attested_key = verify_ra_cert(cert, None, true)?;
+    if attested_key.len() != PUBLIC_KEY_SIZE {
+        warn!(
+            "Got public key from certificate with the wrong size: {:?}",
+            attested_key.len()
+        );
+        return Err(NodeAuthResult::MalformedPublicKey);
+    }
+    if attested_key[..] != approval[..PUBLIC_KEY_SIZE] {
+        warn!("Seed rotation approval was not made by the node of the certificate");
+        return Err(NodeAuthResult::InvalidCert);
+    }
+
+    let mut node_public_key = [0u8; PUBLIC_KEY_SIZE];
+    node_public_key.copy_from_slice(&attested_key);
+
+    let approval_key = AESKey::new_from_slice(&network_key.diffie_hellman(&node_public_key));
+    let expected = approval_mac(&approval_key, encrypted_candidate, activation_height);
+    if !ct_eq(&expected, &approval[PUBLIC_KEY_SIZE..]) {
+        warn!("Seed rotation approval does not match the candidate");
+        return Err(NodeAuthResult::InvalidInput);
+    }
+
+    Ok(node_public_key)
+}
+
+/// Splits attestation certificates that are concatenated, each prefixed with its length as a big
+/// endian u32
+fn split_certs(certs: &[u8]) -> Result<Vec<&[u8]>, NodeAuthResult> {
+    let mut res = vec![];
+    let mut rest = certs;
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            return Err(NodeAuthResult::InvalidInput);
+        }
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&rest[..4]);
+        let len = u32::from_be_bytes(len) as usize;
+        if rest.len() - 4 < len {
+            return Err(NodeAuthResult::InvalidInput);
+        }
+        res.push(&rest[4..4 + len]);
+        rest = &rest[4 + len..];
+    }
+    Ok(res)
+}
+
+/// Whether the candidate was already activated by this node, i.e. it is the current seed and was
+/// encrypted with the seed it replaced. Activating is then a no-op, so that a node which restarts
+/// after activating but before the upgrade block was committed doesn't fail when replaying it.
+fn is_activated(
+    key_manager: &Keychain,
+    current: &Seed,
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+) -> bool {
+    let previous = match key_manager.get_previous_consensus_seeds().last() {
+        Some(previous) => previous,
+        None => return false,
+    };
+    match decrypt_candidate(previous, encrypted_candidate, activation_height) {
+        Ok(candidate) => ct_eq(candidate.as_slice(), current.as_slice()),
+        Err(_) => false,
+    }
+}
+
+///
+/// `ecall_seed_rotation_generate_candidate`
+///
+/// Generates a new candidate for the current consensus seed, to become active at
+/// `activation_height`. Returns the encrypted candidate and its hash.
+///
+/// This function happens off-chain
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_seed_rotation_generate_candidate(
+    activation_height: u64,
+    encrypted_candidate: &mut [u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    hash: &mut [u8; HASH_SIZE],
+) -> sgx_status_t {
+    validate_mut_ptr!(
+        encrypted_candidate.as_mut_ptr(),
+        encrypted_candidate.len(),
+        sgx_status_t::SGX_ERROR_UNEXPECTED
+    );
+    validate_mut_ptr!(
+        hash.as_mut_ptr(),
+        hash.len(),
+        sgx_status_t::SGX_ERROR_UNEXPECTED
+    );
+
+    let result = panic::catch_unwind(|| -> Result<Vec<u8>, sgx_status_t> {
+        let candidate = Seed::new().map_err(|_| sgx_status_t::SGX_ERROR_UNEXPECTED)?;
+
+        let height = activation_height.to_be_bytes();
+        let authenticated_data: Vec<&[u8]> = vec![&height];
+        let encrypted = rotation_key(&current_seed(&KEY_MANAGER)?)
+            .encrypt_siv(candidate.as_slice(), Some(&authenticated_data))
+            .map_err(|_| sgx_status_t::SGX_ERROR_UNEXPECTED)?;
+
+        if encrypted.len() != SINGLE_ENCRYPTED_SEED_SIZE {
+            error!(
+                "Seed rotation candidate encryption returned unexpected length: {:?}",
+                encrypted.len()
+            );
+            return Err(sgx_status_t::SGX_ERROR_UNEXPECTED);
+        }
+        Ok(encrypted)
+    });
+
+    match result {
+        Ok(Ok(encrypted)) => {
+            encrypted_candidate.copy_from_slice(&encrypted);
+            hash.copy_from_slice(&candidate_hash(encrypted_candidate));
+            info!(
+                "Generated seed rotation candidate {} for activation at height {}",
+                hex::encode(&hash),
+                activation_height
+            );
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Err(status)) => status,
+        Err(_) => {
+            warn!("Enclave call ecall_seed_rotation_generate_candidate panic!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
+///
+/// `ecall_seed_rotation_approve`
+///
+/// Checks that the candidate can be decrypted by this enclave, and returns this node's approval of
+/// it: the node's registration public key followed by the approval MAC. The approval only counts
+/// together with this node's attestation certificate, which binds the registration key to it.
+///
+/// This function happens off-chain
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_seed_rotation_approve(
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+    approval: &mut [u8; SEED_ROTATION_APPROVAL_SIZE],
+) -> sgx_status_t {
+    validate_mut_ptr!(
+        approval.as_mut_ptr(),
+        approval.len(),
+        sgx_status_t::SGX_ERROR_UNEXPECTED
+    );
+
+    let result = panic::catch_unwind(|| -> Result<Vec<u8>, sgx_status_t> {
+        decrypt_candidate(
+            &current_seed(&KEY_MANAGER)?,
+            encrypted_candidate,
+            activation_height,
+        )?;
+
+        let registration_key = KEY_MANAGER
+            .get_registration_key()
+            .map_err(|_| sgx_status_t::SGX_ERROR_INVALID_STATE)?;
+        let network_key = KEY_MANAGER
+            .seed_exchange_key()
+            .map_err(|_| sgx_status_t::SGX_ERROR_INVALID_STATE)?
+            .current
+            .get_pubkey();
+
+        let approval_key = AESKey::new_from_slice(&registration_key.diffie_hellman(&network_key));
+
+        let mut res = registration_key.get_pubkey().to_vec();
+        res.extend_from_slice(&approval_mac(
+            &approval_key,
+            encrypted_candidate,
+            activation_height,
+        ));
+        Ok(res)
+    });
+
+    match result {
+        Ok(Ok(res)) => {
+            approval.copy_from_slice(&res);
+            info!(
+                "Approved seed rotation candidate {} for activation at height {}",
+                hex::encode(candidate_hash(encrypted_candidate)),
+                activation_height
+            );
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Err(status)) => status,
+        Err(_) => {
+            warn!("Enclave call ecall_seed_rotation_approve panic!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
+///
+/// `ecall_seed_rotation_verify_approval`
+///
+/// Verifies that an approval was produced for this exact candidate and activation height by the
+/// enclave that `cert`, its attestation certificate, belongs to.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_seed_rotation_verify_approval(
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+    approval: &[u8; SEED_ROTATION_APPROVAL_SIZE],
+    cert: *const u8,
+    cert_len: u32,
+) -> NodeAuthResult {
+    validate_const_ptr!(cert, cert_len as usize, NodeAuthResult::InvalidInput);
+    let cert = slice::from_raw_parts(cert, cert_len as usize);
+
+    let result = panic::catch_unwind(|| -> Result<(), NodeAuthResult> {
+        let network_key = KEY_MANAGER
+            .seed_exchange_key()
+            .map_err(|_| NodeAuthResult::InvalidInput)?
+            .current;
+        verify_approval(
+            &network_key,
+            encrypted_candidate,
+            activation_height,
+            approval,
+            cert,
+        )?;
+        Ok(())
+    });
+
+    match result {
+        Ok(Ok(())) => NodeAuthResult::Success,
+        Ok(Err(e)) => e,
+        Err(_) => {
+            warn!("Enclave call ecall_seed_rotation_verify_approval panic!");
+            NodeAuthResult::Panic
+        }
+    }
+}
+
+///
+/// `ecall_seed_rotation_activate`
+///
+/// Replaces the current consensus seed with the candidate and seals it, once `current_height` has
+/// reached `activation_height`. The genesis seed is kept as is, and the replaced seed is added to
+/// the previous seeds so state written with it can still be read.
+///
+/// `approvals` are concatenated approvals, and `certs` the attestation certificates of the nodes
+/// that made them, in the same order, each prefixed with its length as a big endian u32. Approvals
+/// that don't verify are ignored. The candidate is only activated if at least `threshold` distinct
+/// attested nodes approved it, and `threshold` must be at least `MIN_SEED_ROTATION_APPROVALS`.
+///
+/// The enclave keeps using the seeds it loaded until it's restarted, so `restart_required` is set
+/// if they were already loaded when the candidate was activated.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_seed_rotation_activate(
+    encrypted_candidate: &[u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+    current_height: u64,
+    threshold: u32,
+    approvals: *const u8,
+    approvals_len: u32,
+    certs: *const u8,
+    certs_len: u32,
+    restart_required: &mut u8,
+) -> NodeAuthResult {
+    validate_const_ptr!(
+        approvals,
+        approvals_len as usize,
+        NodeAuthResult::InvalidInput
+    );
+    validate_const_ptr!(certs, certs_len as usize, NodeAuthResult::InvalidInput);
+    validate_mut_ptr!(restart_required as *mut u8, 1, NodeAuthResult::InvalidInput);
+
+    if current_height < activation_height {
+        warn!(
+            "Refusing to activate seed rotation at height {}, it is scheduled for height {}",
+            current_height, activation_height
+        );
+        return NodeAuthResult::InvalidInput;
+    }
+    if threshold < MIN_SEED_ROTATION_APPROVALS {
+        warn!(
+            "Refusing to activate seed rotation with a threshold of {}, it must be at least {}",
+            threshold, MIN_SEED_ROTATION_APPROVALS
+        );
+        return NodeAuthResult::InvalidInput;
+    }
+
+    let approvals = slice::from_raw_parts(approvals, approvals_len as usize);
+    let certs = slice::from_raw_parts(certs, certs_len as usize);
+
+    let result = panic::catch_unwind(|| -> Result<bool, NodeAuthResult> {
+        let certs = split_certs(certs)?;
+        if approvals.len() != certs.len() * SEED_ROTATION_APPROVAL_SIZE {
+            error!("Every seed rotation approval must come with a certificate");
+            return Err(NodeAuthResult::InvalidInput);
+        }
+
+        let mut key_manager = Keychain::new();
+        let current = current_seed(&key_manager).map_err(|_| NodeAuthResult::InvalidInput)?;
+        if is_activated(
+            &key_manager,
+            &current,
+            encrypted_candidate,
+            activation_height,
+        ) {
+            info!("Seed rotation candidate was already activated");
+            return Ok(false);
+        }
+
+        let candidate = decrypt_candidate(&current, encrypted_candidate, activation_height)
+            .map_err(|_| NodeAuthResult::InvalidInput)?;
+
+        let network_key = key_manager
+            .seed_exchange_key()
+            .map_err(|_| NodeAuthResult::InvalidInput)?
+            .current;
+        let mut approvers: Vec<[u8; PUBLIC_KEY_SIZE]> = vec![];
+        for (approval, cert) in approvals
+            .chunks(SEED_ROTATION_APPROVAL_SIZE)
+            .zip(certs.iter())
+        {
+            match verify_approval(
+                &network_key,
+                encrypted_candidate,
+                activation_height,
+                approval,
+                cert,
+            ) {
+                Ok(approver) => {
+                    if !approvers.contains(&approver) {
+                        approvers.push(approver);
+                    }
+                }
+                Err(e) => warn!("Ignoring seed rotation approval: {}", e),
+            }
+        }
+        if (approvers.len() as u32) < threshold {
+            error!(
+                "Seed rotation candidate only has {}/{} valid approvals",
+                approvers.len(),
+                threshold
+            );
+            return Err(NodeAuthResult::InvalidInput);
+        }
+
+        key_manager
+            .rotate_consensus_seed(candidate)
+            .map_err(|_| NodeAuthResult::CantWriteToStorage)?;
+        key_manager
+            .generate_consensus_master_keys()
+            .map_err(|_| NodeAuthResult::CantWriteToStorage)?;
+        write_master_pub_keys(&key_manager).map_err(|_| NodeAuthResult::CantWriteToStorage)?;
+
+        Ok(KEY_MANAGER_LOADED.load(std::sync::atomic::Ordering::SeqCst))
+    });
+
+    match result {
+        Ok(Ok(restart)) => {
+            *restart_required = restart as u8;
+            info!(
+                "Activated seed rotation candidate {} at height {}",
+                hex::encode(candidate_hash(encrypted_candidate)),
+                current_height
+            );
+            NodeAuthResult::Success
+        }
+        Ok(Err(e)) => e,
+        Err(_) => {
+            warn!("Enclave call ecall_seed_rotation_activate panic!");
+            NodeAuthResult::Panic
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//!
+/// Threshold mode for sharing the consensus seeds.
+///
+/// Instead of a new node receiving the seeds from a single registered node, the seeds are Shamir
+/// split across an approved validator set (at genesis or after a rotation). Each share is encrypted
+/// to one validator's registration key. A node joining the network must then obtain `threshold`
+/// shares, each re-encrypted for it by a share holder after checking the node's attestation
+/// certificate, and only reconstructs the seeds inside its enclave.
+///
+/// These functions run off chain
+///
+use log::*;
+use sgx_types::sgx_status_t;
+use std::panic;
+use std::slice;
+
+use enclave_crypto::consts::CONSENSUS_SEED_EXCHANGE_KEYPAIR_DERIVE_ORDER;
+use enclave_crypto::shamir::{combine_shares, split_secret, SecretShare};
+use enclave_crypto::{
+    AESKey, Kdf, KeyPair, Keychain, SIVEncryptable, Seed, KEY_MANAGER, PUBLIC_KEY_SIZE,
+    SEED_KEY_SIZE,
+};
+use enclave_ffi_types::{
+    NodeAuthResult, ENCRYPTED_SEED_SHARE_SIZE, RESHARED_SEED_SHARE_SIZE, SEED_SHARE_SECRET_SIZE,
+};
+use enclave_utils::{validate_const_ptr, validate_mut_ptr};
+
+use super::cert::verify_ra_cert;
+use super::persistency::write_master_pub_keys;
+
+/// The fewest shares the seeds can be split to be reconstructed from. A threshold must also be a
+/// majority of the recipients, so that no minority of them can reconstruct the seeds.
+pub const MIN_SEED_SHARE_THRESHOLD: u8 = 2;
+
+/// Checks that `threshold` of `count` shares is enough to keep the seeds safe
+fn is_safe_threshold(threshold: u8, count: usize) -> bool {
+    threshold >= MIN_SEED_SHARE_THRESHOLD && (threshold as usize) * 2 > count
+}
+
+/// Verifies the attestation certificates of the recipients of the shares, concatenated and each
+/// prefixed with its length as a big endian u32, and returns the registration keys found in them
+fn verify_recipient_certs(certs: &[u8]) -> Result<Vec<[u8; PUBLIC_KEY_SIZE]>, NodeAuthResult> {
+    let mut recipients: Vec<[u8; PUBLIC_KEY_SIZE]> = vec![];
+    let mut rest = certs;
+    while !rest.is_empty() {
+        if rest.len() < 4 {
+            return Err(NodeAuthResult::InvalidInput);
+        }
+        let mut len = [0u8; 4];
+        len.copy_from_slice(&rest[..4]);
+        let len = u32::from_be_bytes(len) as usize;
+        if rest.len() - 4 < len {
+            return Err(NodeAuthResult::InvalidInput);
+        }
+        let cert = &rest[4..4 + len];
+        rest = &rest[4 + len..];
+
+        let pk = verify_ra_cert(cert, None, true)?;
+        if pk.len() != PUBLIC_KEY_SIZE {
+            warn!(
+                "Got public key from certificate with the wrong size: {:?}",
+                pk.len()
+            );
+            return Err(NodeAuthResult::MalformedPublicKey);
+        }
+        let mut recipient = [0u8; PUBLIC_KEY_SIZE];
+        recipient.copy_from_slice(&pk);
+        if recipients.contains(&recipient) {
+            warn!("The same node can't receive more than one seed share");
+            return Err(NodeAuthResult::InvalidInput);
+        }
+        recipients.push(recipient);
+    }
+    Ok(recipients)
+}
+
+/// Encrypts a share of the seeds to `recipient`. The output is `index | threshold | ciphertext`.
+fn encrypt_share(
+    our_key: &KeyPair,
+    recipient: &[u8; PUBLIC_KEY_SIZE],
+    share: &SecretShare,
+) -> Result<Vec<u8>, sgx_status_t> {
+    let key = AESKey::new_from_slice(&our_key.diffie_hellman(recipient));
+    let header = [share.index, share.threshold];
+    let authenticated_data: Vec<&[u8]> = vec![recipient, &header];
+
+    let ciphertext = key
+        .encrypt_siv(&share.data, Some(&authenticated_data))
+        .map_err(|_| sgx_status_t::SGX_ERROR_UNEXPECTED)?;
+
+    let mut res = header.to_vec();
+    res.extend_from_slice(&ciphertext);
+    if res.len() != ENCRYPTED_SEED_SHARE_SIZE {
+        error!(
+            "Seed share encryption returned unexpected length: {}",
+            res.len()
+        );
+        return Err(sgx_status_t::SGX_ERROR_UNEXPECTED);
+    }
+    Ok(res)
+}
+
+/// Decrypts a share that `sender` encrypted to our registration key
+fn decrypt_share(
+    registration_key: &KeyPair,
+    sender: &[u8; PUBLIC_KEY_SIZE],
+    encrypted_share: &[u8],
+) -> Result<SecretShare, sgx_status_t> {
+    if encrypted_share.len() != ENCRYPTED_SEED_SHARE_SIZE {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    let key = AESKey::new_from_slice(&registration_key.diffie_hellman(sender));
+    let header = &encrypted_share[..2];
+    let our_public_key = registration_key.get_pubkey();
+    let authenticated_data: Vec<&[u8]> = vec![&our_public_key, header];
+
+    let data = key
+        .decrypt_siv(&encrypted_share[2..], Some(&authenticated_data))
+        .map_err(|_| {
+            warn!("Failed to decrypt seed share");
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        })?;
+
+    Ok(SecretShare {
+        index: header[0],
+        threshold: header[1],
+        data,
+    })
+}
+
+///
+/// `ecall_split_consensus_seed`
+///
+/// Splits the genesis and current seeds into one share per recipient, `threshold` of which are
+/// needed to reconstruct them. `recipient_certs` are the attestation certificates of the approved
+/// validators, each prefixed with its length as a big endian u32. Like in
+/// `ecall_reshare_seed_share`, each certificate is verified and its share is encrypted to, and
+/// authenticated with, the registration key found in it, so only attested enclaves can decrypt the
+/// shares. The threshold must be at least `MIN_SEED_SHARE_THRESHOLD` and a majority of the
+/// recipients.
+///
+/// Share `i` is written to
+/// `shares[i * ENCRYPTED_SEED_SHARE_SIZE..(i + 1) * ENCRYPTED_SEED_SHARE_SIZE]` and is encrypted
+/// with the network's seed exchange key, so the recipients decrypt it knowing only the public key.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_split_consensus_seed(
+    threshold: u8,
+    recipient_certs: *const u8,
+    recipient_certs_len: u32,
+    shares: *mut u8,
+    shares_len: u32,
+) -> NodeAuthResult {
+    validate_const_ptr!(
+        recipient_certs,
+        recipient_certs_len as usize,
+        NodeAuthResult::InvalidInput
+    );
+    validate_mut_ptr!(shares, shares_len as usize, NodeAuthResult::InvalidInput);
+
+    let recipient_certs = slice::from_raw_parts(recipient_certs, recipient_certs_len as usize);
+    let shares = slice::from_raw_parts_mut(shares, shares_len as usize);
+
+    let result = panic::catch_unwind(|| -> Result<Vec<u8>, NodeAuthResult> {
+        let recipients = verify_recipient_certs(recipient_certs)?;
+        if recipients.len() > u8::MAX as usize
+            || shares.len() != recipients.len() * ENCRYPTED_SEED_SHARE_SIZE
+        {
+            error!("Invalid seed share recipients or output buffer length");
+            return Err(NodeAuthResult::InvalidInput);
+        }
+        if !is_safe_threshold(threshold, recipients.len()) {
+            error!(
+                "A threshold of {} is unsafe for {} recipients, it must be at least {} and a majority",
+                threshold,
+                recipients.len(),
+                MIN_SEED_SHARE_THRESHOLD
+            );
+            return Err(NodeAuthResult::InvalidInput);
+        }
+
+        let seeds = KEY_MANAGER
+            .get_consensus_seed()
+            .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?;
+        let network_key = KEY_MANAGER
+            .seed_exchange_key()
+            .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?
+            .current;
+
+        let mut secret = seeds.genesis.as_slice().to_vec();
+        secret.extend_from_slice(seeds.current.as_slice());
+
+        let split = split_secret(&secret, threshold, recipients.len() as u8)
+            .map_err(|_| NodeAuthResult::InvalidInput)?;
+
+        let mut res = Vec::with_capacity(shares.len());
+        for (share, recipient) in split.iter().zip(recipients.iter()) {
+            res.extend(
+                encrypt_share(&network_key, recipient, share)
+                    .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?,
+            );
+        }
+        Ok(res)
+    });
+
+    match result {
+        Ok(Ok(res)) => {
+            shares.copy_from_slice(&res);
+            info!(
+                "Split the consensus seeds into {} shares with a threshold of {}",
+                res.len() / ENCRYPTED_SEED_SHARE_SIZE,
+                threshold
+            );
+            NodeAuthResult::Success
+        }
+        Ok(Err(e)) => {
+            trace!("error splitting the consensus seeds {:?}", e);
+            e
+        }
+        Err(_) => {
+            warn!("Enclave call ecall_split_consensus_seed panic!");
+            NodeAuthResult::Panic
+        }
+    }
+}
+
+///
+/// `ecall_reshare_seed_share`
+///
+/// Run by a share holder for a node that wants to join: verifies the node's attestation
+/// certificate, and re-encrypts our share to the registration key found in it. The output is our
+/// registration public key followed by the re-encrypted share.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_reshare_seed_share(
+    master_key: &[u8; PUBLIC_KEY_SIZE],
+    encrypted_share: &[u8; ENCRYPTED_SEED_SHARE_SIZE],
+    cert: *const u8,
+    cert_len: u32,
+    reshared: &mut [u8; RESHARED_SEED_SHARE_SIZE],
+) -> NodeAuthResult {
+    validate_const_ptr!(cert, cert_len as usize, NodeAuthResult::InvalidInput);
+    validate_mut_ptr!(
+        reshared.as_mut_ptr(),
+        reshared.len(),
+        NodeAuthResult::InvalidInput
+    );
+
+    let cert_slice = slice::from_raw_parts(cert, cert_len as usize);
+
+    let result = panic::catch_unwind(|| -> Result<Vec<u8>, NodeAuthResult> {
+        let pk = verify_ra_cert(cert_slice, None, true)?;
+        if pk.len() != PUBLIC_KEY_SIZE {
+            warn!(
+                "Got public key from certificate with the wrong size: {:?}",
+                pk.len()
+            );
+            return Err(NodeAuthResult::MalformedPublicKey);
+        }
+        let mut target_public_key = [0u8; PUBLIC_KEY_SIZE];
+        target_public_key.copy_from_slice(&pk);
+
+        let registration_key = KEY_MANAGER
+            .get_registration_key()
+            .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?;
+
+        let share = decrypt_share(&registration_key, master_key, encrypted_share)
+            .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?;
+
+        let mut res = registration_key.get_pubkey().to_vec();
+        res.extend(
+            encrypt_share(&registration_key, &target_public_key, &share)
+                .map_err(|_| NodeAuthResult::SeedEncryptionFailed)?,
+        );
+        Ok(res)
+    });
+
+    match result {
+        Ok(Ok(res)) => {
+            reshared.copy_from_slice(&res);
+            NodeAuthResult::Success
+        }
+        Ok(Err(e)) => {
+            trace!("error resharing seed share {:?}", e);
+            e
+        }
+        Err(_) => {
+            warn!("Enclave call ecall_reshare_seed_share panic!");
+            NodeAuthResult::Panic
+        }
+    }
+}
+
+///
+/// `ecall_combine_seed_shares`
+///
+/// Reconstructs the consensus seeds from shares re-encrypted for this node by
+/// `ecall_reshare_seed_share`, concatenated, and seals them.
+///
+/// Each holder and each share index may only appear once. The seeds are only sealed if the seed
+/// exchange key derived from them is `master_key`, the one registered on chain, so shares that
+/// combine into other seeds are rejected instead of leaving the node with seeds nobody else has.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_combine_seed_shares(
+    master_key: &[u8; PUBLIC_KEY_SIZE],
+    reshared: *const u8,
+    reshared_len: u32,
+) -> sgx_status_t {
+    validate_const_ptr!(
+        reshared,
+        reshared_len as usize,
+        sgx_status_t::SGX_ERROR_UNEXPECTED
+    );
+
+    let reshared = slice::from_raw_parts(reshared, reshared_len as usize);
+    if reshared.is_empty() || reshared.len() % RESHARED_SEED_SHARE_SIZE != 0 {
+        error!("Invalid seed shares length: {}", reshared.len());
+        return sgx_status_t::SGX_ERROR_INVALID_PARAMETER;
+    }
+
+    let result = panic::catch_unwind(|| -> Result<(), sgx_status_t> {
+        let mut key_manager = Keychain::new();
+        let registration_key = key_manager
+            .get_registration_key()
+            .map_err(|_| sgx_status_t::SGX_ERROR_INVALID_STATE)?;
+
+        let mut holders: Vec<[u8; PUBLIC_KEY_SIZE]> = vec![];
+        let mut shares: Vec<SecretShare> = vec![];
+        for chunk in reshared.chunks_exact(RESHARED_SEED_SHARE_SIZE) {
+            let mut holder = [0u8; PUBLIC_KEY_SIZE];
+            holder.copy_from_slice(&chunk[..PUBLIC_KEY_SIZE]);
+            if holders.contains(&holder) {
+                warn!("Got more than one seed share from the same holder");
+                return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+            }
+
+            let share = decrypt_share(&registration_key, &holder, &chunk[PUBLIC_KEY_SIZE..])?;
+            if shares.iter().any(|other| other.index == share.index) {
+                warn!("Got more than one seed share with index {}", share.index);
+                return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+            }
+
+            holders.push(holder);
+            shares.push(share);
+        }
+
+        let secret = combine_shares(&shares).map_err(|_| {
+            error!(
+                "Failed to reconstruct the consensus seeds from {} shares",
+                shares.len()
+            );
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        })?;
+        if secret.len() != SEED_SHARE_SECRET_SIZE {
+            return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+        }
+
+        let mut genesis = Seed::default();
+        genesis.as_mut().copy_from_slice(&secret[..SEED_KEY_SIZE]);
+        let mut current = Seed::default();
+        current.as_mut().copy_from_slice(&secret[SEED_KEY_SIZE..]);
+
+        let seed_exchange_key = KeyPair::from(
+            current
+                .derive_key_from_this(&CONSENSUS_SEED_EXCHANGE_KEYPAIR_DERIVE_ORDER.to_be_bytes()),
+        );
+        if seed_exchange_key.get_pubkey() != *master_key {
+            error!(
+                "The reconstructed consensus seeds don't match the registered seed exchange key"
+            );
+            return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+        }
+
+        key_manager
+            .set_consensus_seed(genesis, current)
+            .map_err(|_| sgx_status_t::SGX_ERROR_UNEXPECTED)?;
+        key_manager
+            .generate_consensus_master_keys()
+            .map_err(|_| sgx_status_t::SGX_ERROR_UNEXPECTED)?;
+
+        write_master_pub_keys(&key_manager)
+    });
+
+    match result {
+        Ok(Ok(())) => {
+            info!("Reconstructed the consensus seeds from seed shares");
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Err(status)) => status,
+        Err(_) => {
+            warn!("Enclave call ecall_combine_seed_shares panic!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! The messages of enclave errors as they appear in transaction results.
+//!
+//! The `Display` of `EnclaveError` is meant for logs and may be reworded between builds. Results
+//! are part of consensus though, so every node must produce the exact same message for the same
+//! error. Each version below is a fixed set of messages; a version must never change once
+//! released. Rewording a message, or changing how errors are grouped, means adding a new version
+//! and switching to it in a coordinated upgrade.
+
+use crate::types::EnclaveError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorMessagesVersion {
+    /// The messages as of the introduction of versioning, identical to what nodes reported before
+    V1,
+}
+
+impl ErrorMessagesVersion {
+    /// The version used for new results
+    pub const CURRENT: Self = ErrorMessagesVersion::V1;
+}
+
+impl EnclaveError {
+    /// The canonical message of this error in the given version.
+    ///
+    /// The match is exhaustive on purpose: a new variant can't be added without deciding on its
+    /// message.
+    pub fn canonical_message(&self, version: ErrorMessagesVersion) -> &'static str {
+        match version {
+            ErrorMessagesVersion::V1 => canonical_message_v1(self),
+        }
+    }
+}
+
+fn canonical_message_v1(error: &EnclaveError) -> &'static str {
+    match error {
+        EnclaveError::FailedOcall { .. } => "failed to execute ocall",
+        EnclaveError::ValidationFailure => "failed to validate transaction",
+        EnclaveError::InvalidWasm => "tried to load invalid wasm code",
+        EnclaveError::CannotInitializeWasmMemory => "failed to initialize wasm memory",
+        EnclaveError::WasmModuleWithStart => "WasmModuleWithStart",
+        EnclaveError::WasmModuleWithFP => "found floating point operation in module code",
+        EnclaveError::FailedGasMeteringInjection => "failed to inject gas metering",
+        EnclaveError::InternalError => "internal error during execution",
+        EnclaveError::OutOfGas => "execution ran out of gas",
+        EnclaveError::StackLimitExceeded => "execution exceeded the stack height limit",
+        EnclaveError::MemoryGrowthLimitExceeded => "execution exceeded the memory growth limit",
+        EnclaveError::FailedFunctionCall => {
+            "calling a function in the contract failed for an unexpected reason"
+        }
+        EnclaveError::ContractPanicUnreachable => "the contract panicked",
+        EnclaveError::ContractPanicMemoryAccessOutOfBounds => {
+            "the contract tried to access memory out of bounds"
+        }
+        EnclaveError::ContractPanicTableAccessOutOfBounds => {
+            "the contract tried to access a nonexistent resource"
+        }
+        EnclaveError::ContractPanicElemUninitialized => {
+            "the contract tried to access an uninitialized resource"
+        }
+        EnclaveError::ContractPanicDivisionByZero => "the contract tried to divide by zero",
+        EnclaveError::ContractPanicInvalidConversionToInt => {
+            "the contract tried to perform an invalid conversion to an integer"
+        }
+        EnclaveError::ContractPanicStackOverflow => {
+            "the contract has run out of space on the stack"
+        }
+        EnclaveError::ContractPanicIntegerOverflow => {
+            "the contract has run out of space on the stack"
+        }
+        EnclaveError::ContractPanicUnexpectedSignature => {
+            "the contract tried to call a function but expected an incorrect function signature"
+        }
+        EnclaveError::FailedSeal => "failed to seal data",
+        EnclaveError::FailedUnseal => "failed to unseal data",
+        EnclaveError::FailedContractAuthentication => "failed to authenticate secret contract",
+        EnclaveError::FailedToDeserialize => "failed to deserialize data",
+        EnclaveError::FailedToSerialize => "failed to serialize data",
+        EnclaveError::EncryptionError => "failed to encrypt data",
+        EnclaveError::DecryptionError => "failed to decrypt data",
+        EnclaveError::MemoryAllocationError => "failed to allocate memory",
+        EnclaveError::MemorySafetyAllocationError => "failed to allocate minimal safety buffer",
+        EnclaveError::MemoryReadError => "failed to read memory",
+        EnclaveError::MemoryWriteError => "failed to write memory",
+        EnclaveError::NotImplemented => "function not implemented",
+        EnclaveError::FailedTxVerification => "failed to verify transaction signature",
+        EnclaveError::UnauthorizedWrite => "contract tried to write to storage during a query",
+        EnclaveError::HostMisbehavior => "communication with the enclave's host failed",
+        EnclaveError::Panic => "panicked due to unexpected behavior",
+        EnclaveError::OutOfMemory => "enclave ran out of heap memory",
+        EnclaveError::MemorySoftLimitExceeded => "contract call reached the enclave heap limit",
+        EnclaveError::ResponseTooLarge => "contract response exceeds the configured size limit",
+        EnclaveError::InvalidEvent => {
+            "contract emitted an event with a reserved or too long type or attribute key"
+        }
+        EnclaveError::ExceededRecursionLimit => "depth of nested contract calls exceeded",
+        EnclaveError::ContractVersionDowngrade => {
+            "migration would downgrade the contract's cw2 version"
+        }
+        EnclaveError::InvalidFunds => "contract sent funds whose amounts of a denom overflow",
+        EnclaveError::AccessListViolation => {
+            "contract accessed a key outside the access list of the execution"
+        }
+        EnclaveError::Unknown => "unknown error",
+    }
+}
@@ -1,12 +1,17 @@
 #![no_std]
 #![allow(unused)]
 
+mod error_messages;
 mod types;
+pub mod wasm_abi;
+
+pub use error_messages::ErrorMessagesVersion;
 
 pub use types::{
-    Ctx, EnclaveBuffer, EnclaveError, HandleResult, HealthCheckResult, InitResult, MigrateResult,
-    NodeAuthResult, OcallReturn, QueryResult, RuntimeConfiguration, UntrustedVmError,
-    UpdateAdminResult, UserSpaceBuffer,
+    AllocatorStats, CallMemoryStats, Ctx, EnclaveBuffer, EnclaveError, ExecutionReceipt,
+    HandleResult, HealthCheckResult, InitResult, MigrateResult, ModuleCacheStats,
+    MsgDecryptionStatus, NodeAuthResult, OcallReturn, QueryResponseSignature, QueryResult,
+    RuntimeConfiguration, UntrustedVmError, UpdateAdminResult, UserSpaceBuffer,
 };
 
 // On input, the encrypted seed is expected to contain 3 values:
@@ -21,3 +26,21 @@ pub const SINGLE_ENCRYPTED_SEED_SIZE: usize = 48;
 pub const NEWLY_FORMED_SINGLE_ENCRYPTED_SEED_SIZE: usize = SINGLE_ENCRYPTED_SEED_SIZE + 1;
 pub const NEWLY_FORMED_DOUBLE_ENCRYPTED_SEED_SIZE: usize = (2 * SINGLE_ENCRYPTED_SEED_SIZE) + 1;
 pub const PUBLIC_KEY_SIZE: usize = 32;
+// Every seed that was current before a seed rotation is kept, and shared with registering nodes
+// after the genesis and current seeds, 48 bytes each. This bounds how often the seed can rotate.
+pub const MAX_PREVIOUS_CONSENSUS_SEEDS: usize = 16;
+pub const MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE: usize =
+    MAX_PREVIOUS_CONSENSUS_SEEDS * SINGLE_ENCRYPTED_SEED_SIZE;
+// A seed rotation approval is the approving node's public key followed by a 32 byte MAC
+pub const SEED_ROTATION_APPROVAL_SIZE: usize = PUBLIC_KEY_SIZE + 32;
+// In threshold mode the genesis and current seeds are split together, 32 bytes each.
+// An encrypted share is 1 byte index | 1 byte threshold | the AES-SIV encrypted share data,
+// and a share re-encrypted for a new node is prefixed with the holder's public key
+pub const SEED_SHARE_SECRET_SIZE: usize = 64;
+pub const ENCRYPTED_SEED_SHARE_SIZE: usize = 2 + SEED_SHARE_SECRET_SIZE + 16;
+pub const RESHARED_SEED_SHARE_SIZE: usize = PUBLIC_KEY_SIZE + ENCRYPTED_SEED_SHARE_SIZE;
+
+// The version of the interface between the node and the enclave, the EDL and the types in this
+// crate. An enclave is only loaded by a node built with the same version, bump it whenever either
+// changes
+pub const ENCLAVE_ABI_VERSION: u32 = 3;
@@ -41,6 +41,128 @@ pub struct RuntimeConfiguration {
     /// This speeds up the execution of recently used modules, but has a significant
     /// memory overhead.
     pub module_cache_size: u32,
+    /// Sign a receipt of every instantiate, execute and migrate and report it to the host, so
+    /// the node can prove to off-chain parties that it executed them.
+    pub sign_execution_receipts: bool,
+    /// Sign the responses to queries sent directly to this node, so clients that don't run a
+    /// node can trust them. Uses the same key as execution receipts.
+    pub sign_query_responses: bool,
+}
+
+/// A receipt of a contract execution, signed by the enclave of the node that ran it.
+/// Reported to the host via ocall, before the call returns, when
+/// `RuntimeConfiguration::sign_execution_receipts` is set.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecutionReceipt {
+    /// The height of the last block the enclave verified, which the execution is part of
+    pub block_height: u64,
+    pub code_hash: [u8; 32],
+    /// sha256 of the message as the enclave received it, i.e. encrypted
+    pub msg_hash: [u8; 32],
+    /// sha256 of the output the enclave returned to the host
+    pub result_hash: [u8; 32],
+    pub gas_used: u64,
+    /// The ed25519 key of this node that signed the receipt
+    pub signer_public_key: [u8; 32],
+    /// ed25519 signature over the sha256 of the sha256 of the chain-id the enclave verified
+    /// followed by every other field, in order, with the integers as 8 big endian bytes
+    pub signature: [u8; 64],
+}
+
+/// A signature binding a query, the block height it ran at and its result, returned with the
+/// result of `ecall_query` when `RuntimeConfiguration::sign_query_responses` is set or the contract
+/// opted in to MACed query responses.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryResponseSignature {
+    /// False if the response is not signed, in which case the fields of the signature are zeroed
+    pub signed: bool,
+    /// The height of the last block the enclave verified when it served the query
+    pub block_height: u64,
+    /// sha256 of the canonical address of the contract followed by the query as the enclave
+    /// received it, i.e. encrypted
+    pub query_hash: [u8; 32],
+    /// sha256 of the output the enclave returned to the host
+    pub result_hash: [u8; 32],
+    /// The ed25519 key of this node that signed the response
+    pub signer_public_key: [u8; 32],
+    /// ed25519 signature over the sha256 of the sha256 of the chain-id the enclave verified,
+    /// `block_height` as 8 big endian bytes, `query_hash`, `result_hash` and `signer_public_key`
+    pub signature: [u8; 64],
+    /// True if the contract opted in to MACed query responses. `block_height`, `query_hash` and
+    /// `result_hash` are then set even if the response isn't signed.
+    pub has_contract_mac: bool,
+    /// HMAC-SHA256 over the sha256 of the chain-id the enclave verified, `block_height` as 8 big
+    /// endian bytes, `query_hash` and `result_hash`, with a key derived from the query encryption
+    /// key, which the querier can check without trusting the node
+    pub contract_mac: [u8; 32],
+}
+
+impl QueryResponseSignature {
+    pub fn unsigned() -> Self {
+        Self {
+            signed: false,
+            block_height: 0,
+            query_hash: [0; 32],
+            result_hash: [0; 32],
+            signer_public_key: [0; 32],
+            signature: [0; 64],
+            has_contract_mac: false,
+            contract_mac: [0; 32],
+        }
+    }
+}
+
+/// Heap usage statistics collected by the enclave's allocator, reported to the host via ocall.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocatorStats {
+    /// Bytes currently allocated inside the enclave.
+    pub current_bytes: u64,
+    /// The most bytes that were ever allocated at once.
+    pub high_watermark_bytes: u64,
+    /// Allocations the underlying allocator could not satisfy.
+    pub failed_allocations: u64,
+    /// Contract calls that allocated more heap at once than the soft limit.
+    pub soft_limit_hits: u64,
+    /// Pages (64kb) contracts grew their wasm memory by, over all calls.
+    pub wasm_memory_grown_pages: u64,
+    /// Contract calls that failed because they exceeded the memory growth limit.
+    pub wasm_memory_growth_limit_hits: u64,
+}
+
+/// The memory footprint of a single contract call, reported to the host via ocall when the
+/// contract returns.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CallMemoryStats {
+    /// Size of the contract's wasm memory when it returned. Wasm memory never shrinks, so this
+    /// is also its peak size during the call.
+    pub wasm_memory_bytes: u64,
+    /// The most enclave heap the call had allocated at once, including its wasm memory.
+    pub heap_high_watermark_bytes: u64,
+}
+
+/// Usage statistics of the enclave's module cache, reported to the host via ocall after the calls
+/// that looked a module up in it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// The most modules the cache keeps, as configured when the runtime was set up.
+    pub capacity: u64,
+    /// Modules currently in the cache.
+    pub size: u64,
+    /// Modules kept outside of the cache because their code hash is pinned.
+    pub pinned: u64,
+    /// Lookups that found the module in the cache.
+    pub hits: u64,
+    /// Lookups that found the module pinned.
+    pub pinned_hits: u64,
+    /// Lookups that had to build the module, including all lookups while the cache is disabled.
+    pub misses: u64,
+    /// Modules dropped from the cache to make room for others, or because the cache shrunk.
+    pub evictions: u64,
 }
 
 /// This struct holds a pointer to memory in userspace, that contains the storage
@@ -92,6 +214,12 @@ pub enum EnclaveError {
     /// Ran out of gas
     #[display(fmt = "execution ran out of gas")]
     OutOfGas,
+    /// Calls nested deeper than the stack height limit
+    #[display(fmt = "execution exceeded the stack height limit")]
+    StackLimitExceeded,
+    /// Grew the wasm memory by more pages than a single call is allowed to
+    #[display(fmt = "execution exceeded the memory growth limit")]
+    MemoryGrowthLimitExceeded,
     /// Calling a function in the contract failed.
     #[display(fmt = "calling a function in the contract failed for an unexpected reason")]
     FailedFunctionCall,
@@ -166,8 +294,21 @@ pub enum EnclaveError {
     Panic,
     #[display(fmt = "enclave ran out of heap memory")]
     OutOfMemory,
+    /// Not returned anymore, the heap soft limit of a contract call is only a metric
+    #[display(fmt = "contract call reached the enclave heap limit")]
+    MemorySoftLimitExceeded,
+    #[display(fmt = "contract response exceeds the configured size limit")]
+    ResponseTooLarge,
+    #[display(fmt = "contract emitted an event with a reserved or too long type or attribute key")]
+    InvalidEvent,
     #[display(fmt = "depth of nested contract calls exceeded")]
     ExceededRecursionLimit,
+    #[display(fmt = "migration would downgrade the contract's cw2 version")]
+    ContractVersionDowngrade,
+    #[display(fmt = "contract sent funds whose amounts of a denom overflow")]
+    InvalidFunds,
+    #[display(fmt = "contract accessed a key outside the access list of the execution")]
+    AccessListViolation,
     /// Unexpected Error happened, no more details available
     #[display(fmt = "unknown error")]
     Unknown,
@@ -240,6 +381,28 @@ impl Default for HealthCheckResult {
     }
 }
 
+/// What `ecall_decrypt_tx_msgs` did with each message of a transaction, one byte per message.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+pub enum MsgDecryptionStatus {
+    Decrypted,
+    /// The message is too short to be an encrypted message
+    Malformed,
+    /// The message can't be decrypted with the key of its nonce and public key
+    DecryptionFailed,
+}
+
+impl MsgDecryptionStatus {
+    pub fn from_u8(status: u8) -> Option<Self> {
+        match status {
+            0 => Some(MsgDecryptionStatus::Decrypted),
+            1 => Some(MsgDecryptionStatus::Malformed),
+            2 => Some(MsgDecryptionStatus::DecryptionFailed),
+            _ => None,
+        }
+    }
+}
+
 /// This type holds a pointer to a VmError that is boxed on the untrusted side
 // `VmError` is the standard error type for the `cosmwasm-sgx-vm` layer.
 // During an ocall, we call into the original implementation of `db_read`, `db_write`, and `db_remove`.
@@ -356,6 +519,7 @@ pub enum QueryResult {
     Success {
         /// A pointer to the output of the calculation
         output: UserSpaceBuffer,
+        signature: QueryResponseSignature,
     },
     Failure {
         /// The error that happened in the enclave
@@ -0,0 +1,129 @@
+//! Parser for `wasm_abi.schema`, used by the build scripts that generate the lists of supported
+//! imports and required exports (cosmwasm-sgx-vm) and the host function registration
+//! (contract-engine) from it.
+
+use core::fmt;
+
+pub const WASM_ABI_SCHEMA: &str = include_str!("../wasm_abi.schema");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbiVersion {
+    V010,
+    V1,
+    /// Exports that make a contract IBC enabled, not a version of its own
+    Ibc,
+}
+
+impl AbiVersion {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "v010" => Some(AbiVersion::V010),
+            "v1" => Some(AbiVersion::V1),
+            "ibc" => Some(AbiVersion::Ibc),
+            _ => None,
+        }
+    }
+}
+
+/// A comma separated list of versions, as written in the schema
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiVersions<'a>(&'a str);
+
+impl<'a> AbiVersions<'a> {
+    pub fn contains(&self, version: AbiVersion) -> bool {
+        self.0
+            .split(',')
+            .any(|v| AbiVersion::parse(v) == Some(version))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiImport<'a> {
+    pub name: &'a str,
+    pub versions: AbiVersions<'a>,
+    /// `None` if the enclave doesn't link this import
+    pub host_function: Option<&'a str>,
+    /// The cargo feature of cosmwasm-sgx-vm that must be enabled for the import to be accepted
+    pub feature: Option<&'a str>,
+    pub no_args: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiExport<'a> {
+    pub name: &'a str,
+    pub versions: AbiVersions<'a>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbiEntry<'a> {
+    Import(AbiImport<'a>),
+    Export(AbiExport<'a>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbiSchemaError<'a> {
+    /// 1-based
+    pub line: usize,
+    pub content: &'a str,
+}
+
+impl<'a> fmt::Display for AbiSchemaError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Wasm ABI schema entry on line {}: {:?}",
+            self.line, self.content
+        )
+    }
+}
+
+/// The entries of `schema`, in order. Blank lines and `#` comments are skipped.
+pub fn parse_schema(
+    schema: &str,
+) -> impl Iterator<Item = Result<AbiEntry<'_>, AbiSchemaError<'_>>> {
+    schema
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.split('#').next().unwrap_or("").trim()))
+        .filter(|(_, content)| !content.is_empty())
+        .map(|(line, content)| parse_entry(content).ok_or(AbiSchemaError { line, content }))
+}
+
+fn parse_entry(content: &str) -> Option<AbiEntry<'_>> {
+    let mut fields = content.split_whitespace();
+    let kind = fields.next()?;
+    let name = fields.next()?;
+    let versions = fields.next()?;
+    if versions.split(',').any(|v| AbiVersion::parse(v).is_none()) {
+        return None;
+    }
+    let versions = AbiVersions(versions);
+
+    match kind {
+        "import" => {
+            let host_function = match fields.next()? {
+                "-" => None,
+                host_function => Some(host_function),
+            };
+            let mut import = AbiImport {
+                name,
+                versions,
+                host_function,
+                feature: None,
+                no_args: false,
+            };
+            for flag in fields {
+                if flag == "no_args" {
+                    import.no_args = true;
+                } else if let Some(feature) = flag.strip_prefix("feature=") {
+                    import.feature = Some(feature);
+                } else {
+                    return None;
+                }
+            }
+            Some(AbiEntry::Import(import))
+        }
+        "export" if fields.next().is_none() => Some(AbiEntry::Export(AbiExport { name, versions })),
+        _ => None,
+    }
+}
@@ -16,6 +16,7 @@ pub struct VerifiedBlockMessages {
     messages: VecDeque<Vec<u8>>,
     height: u64,
     time: i128,
+    chain_id: String,
 }
 
 impl VerifiedBlockMessages {
@@ -33,9 +34,10 @@ impl VerifiedBlockMessages {
         }
     }
 
-    pub fn set_block_info(&mut self, height: u64, time: i128) {
+    pub fn set_block_info(&mut self, height: u64, time: i128, chain_id: &str) {
         self.height = height;
         self.time = time;
+        self.chain_id = chain_id.to_string();
     }
 
     pub fn height(&self) -> u64 {
@@ -44,6 +46,9 @@ impl VerifiedBlockMessages {
     pub fn time(&self) -> i128 {
         self.time
     }
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
 
     pub fn clear(&mut self) {
         self.messages.clear()
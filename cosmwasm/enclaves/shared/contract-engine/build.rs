@@ -0,0 +1,49 @@
+//! Generates the registration of the host functions in the wasm3 engine from the Wasm ABI schema in
+//! enclave-ffi-types. See `link_host_functions` in `src/wasm3/mod.rs`.
+
+use std::collections::HashSet;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use enclave_ffi_types::wasm_abi::{parse_schema, AbiEntry, WASM_ABI_SCHEMA};
+
+fn main() {
+    let mut code = String::new();
+    writeln!(
+        code,
+        "fn link_abi_host_functions(instance: &mut wasm3::Instance<Context>) -> Wasm3RsResult<()> {{"
+    )
+    .unwrap();
+    // An import can be listed once per version
+    let mut linked = HashSet::new();
+    for entry in parse_schema(WASM_ABI_SCHEMA) {
+        let import = match entry.unwrap_or_else(|err| panic!("{}", err)) {
+            AbiEntry::Import(import) => import,
+            AbiEntry::Export(_) => continue,
+        };
+        let host_function = match import.host_function {
+            Some(host_function) => host_function,
+            None => continue,
+        };
+        if !linked.insert(import.name) {
+            continue;
+        }
+        let link = if import.no_args {
+            "link_fn_no_args"
+        } else {
+            "link_fn"
+        };
+        writeln!(
+            code,
+            "    {}(instance, {:?}, {})?;",
+            link, import.name, host_function
+        )
+        .unwrap();
+    }
+    writeln!(code, "    Ok(())\n}}").unwrap();
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("host_functions.rs"), code).unwrap();
+}
@@ -0,0 +1,71 @@
+//! Access lists let an execute message declare the prefixes of the keys of its contract's store it
+//! will read or write, so that it can be scheduled next to executions it can't conflict with.
+//!
+//! The node passes the declared prefixes in the env, and the enclave fails the execution when the
+//! contract touches a key outside of them. The keys are checked as the contract sees them, before
+//! they are encrypted, since the encrypted keys can't be predicted by the sender. The prefixes are
+//! part of the tx though, so declaring them reveals which keys of its store the execution touches.
+
+use log::*;
+use serde::Deserialize;
+
+use cw_types_v010::encoding::Binary;
+use enclave_ffi_types::EnclaveError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessList {
+    prefixes: Vec<Vec<u8>>,
+}
+
+impl AccessList {
+    /// Whether `key` starts with one of the declared prefixes
+    pub fn allows(&self, key: &[u8]) -> bool {
+        self.prefixes.iter().any(|prefix| key.starts_with(prefix))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EnvWithAccessList {
+    #[serde(default)]
+    access_list: Option<Vec<Binary>>,
+}
+
+/// Extract the access list from the env parameter, like the query_depth. An env without one, as
+/// sent for every call but executions that declared one, doesn't constrain the contract.
+pub fn extract_access_list(env: &[u8]) -> Result<Option<AccessList>, EnclaveError> {
+    let env = serde_json::from_slice::<EnvWithAccessList>(env).map_err(|err| {
+        warn!(
+            "error while deserializing env into json {:?}: {}",
+            String::from_utf8_lossy(env),
+            err
+        );
+        EnclaveError::FailedToDeserialize
+    })?;
+
+    Ok(env
+        .access_list
+        .filter(|prefixes| !prefixes.is_empty())
+        .map(|prefixes| AccessList {
+            prefixes: prefixes.into_iter().map(|prefix| prefix.0).collect(),
+        }))
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_extract_access_list() {
+        assert_eq!(extract_access_list(br#"{"query_depth":1}"#).unwrap(), None);
+        assert_eq!(extract_access_list(br#"{"access_list":[]}"#).unwrap(), None);
+
+        // "a" and "bc"
+        let access_list = extract_access_list(br#"{"access_list":["YQ==","YmM="]}"#)
+            .unwrap()
+            .unwrap();
+        assert!(access_list.allows(b"a"));
+        assert!(access_list.allows(b"abc"));
+        assert!(access_list.allows(b"bcd"));
+        assert!(!access_list.allows(b"b"));
+        assert!(!access_list.allows(b"cab"));
+    }
+}
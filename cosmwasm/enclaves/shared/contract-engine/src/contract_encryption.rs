@@ -0,0 +1,103 @@
+//! Encryption of data from one contract to another.
+//!
+//! Every contract has an x25519 keypair derived from the genesis consensus seed and its canonical
+//! address, so it exists without being stored anywhere and only the enclave knows the secret half.
+//! Data encrypted to a contract stays in its state or in flight across seed rotations, so the
+//! keypair must not change with the current seed, like the contract keys.
+//! A contract can encrypt data to another contract's public key and pass the result through public
+//! state, a message or an IBC packet, and only the recipient contract can decrypt it.
+//!
+//! The ciphertext is `ephemeral public key | AES-SIV ciphertext`. The ephemeral key is derived from
+//! the sender, the recipient and the plaintext rather than generated randomly, so every node
+//! produces the same ciphertext. As with the rest of AES-SIV, this means encrypting the same data
+//! twice from the same sender gives the same result.
+
+use cw_types_v010::types::CanonicalAddr;
+use enclave_crypto::{sha_256, AESKey, Kdf, KeyPair, SIVEncryptable, KEY_MANAGER, PUBLIC_KEY_SIZE};
+use enclave_ffi_types::EnclaveError;
+use log::*;
+
+use crate::contract_validation::ContractKey;
+
+const EPHEMERAL_KEY_DOMAIN: &[u8] = b"contract_encryption_ephemeral";
+
+fn contract_encryption_secret() -> Result<AESKey, EnclaveError> {
+    KEY_MANAGER
+        .get_contract_encryption_secret()
+        .map_err(|_| EnclaveError::FailedUnseal)
+}
+
+/// The keypair of the contract at `contract_address`
+pub fn contract_encryption_keypair(
+    contract_address: &CanonicalAddr,
+) -> Result<KeyPair, EnclaveError> {
+    let secret = contract_encryption_secret()?.derive_key_from_this(contract_address.as_slice());
+    Ok(KeyPair::from(secret))
+}
+
+/// Encrypts `plaintext` from the contract with key `sender` to the contract at `recipient`
+pub fn encrypt_to_contract(
+    sender: &ContractKey,
+    recipient: &CanonicalAddr,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, EnclaveError> {
+    encrypt_from(sender, recipient, plaintext)
+}
+
+/// Encrypts `plaintext` from the contract at `contract_address` to itself, e.g. for attributes
+/// only the contract can read back
+pub fn encrypt_to_self(
+    contract_address: &CanonicalAddr,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, EnclaveError> {
+    encrypt_from(contract_address.as_slice(), contract_address, plaintext)
+}
+
+/// `sender_id` only makes the ephemeral key unique to the sender
+fn encrypt_from(
+    sender_id: &[u8],
+    recipient: &CanonicalAddr,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, EnclaveError> {
+    let recipient_public_key = contract_encryption_keypair(recipient)?.get_pubkey();
+
+    let mut ephemeral_input = EPHEMERAL_KEY_DOMAIN.to_vec();
+    ephemeral_input.extend_from_slice(sender_id);
+    ephemeral_input.extend_from_slice(recipient.as_slice());
+    ephemeral_input.extend_from_slice(&sha_256(plaintext));
+    let ephemeral_key =
+        KeyPair::from(contract_encryption_secret()?.derive_key_from_this(&ephemeral_input));
+
+    let key = AESKey::new_from_slice(&ephemeral_key.diffie_hellman(&recipient_public_key));
+    let ad: Vec<&[u8]> = vec![recipient.as_slice()];
+    let ciphertext = key
+        .encrypt_siv(plaintext, Some(&ad))
+        .map_err(|_| EnclaveError::EncryptionError)?;
+
+    let mut res = ephemeral_key.get_pubkey().to_vec();
+    res.extend(ciphertext);
+    Ok(res)
+}
+
+/// Decrypts data that another contract encrypted to the contract at `recipient`
+pub fn decrypt_from_contract(
+    recipient: &CanonicalAddr,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, EnclaveError> {
+    if ciphertext.len() < PUBLIC_KEY_SIZE {
+        debug!(
+            "contract ciphertext is too short to hold the ephemeral key: {}",
+            ciphertext.len()
+        );
+        return Err(EnclaveError::DecryptionError);
+    }
+
+    let mut ephemeral_public_key = [0u8; PUBLIC_KEY_SIZE];
+    ephemeral_public_key.copy_from_slice(&ciphertext[..PUBLIC_KEY_SIZE]);
+
+    let keypair = contract_encryption_keypair(recipient)?;
+    let key = AESKey::new_from_slice(&keypair.diffie_hellman(&ephemeral_public_key));
+    let ad: Vec<&[u8]> = vec![recipient.as_slice()];
+    key.decrypt_siv(&ciphertext[PUBLIC_KEY_SIZE..], Some(&ad))
+        .map_err(|_| EnclaveError::DecryptionError)
+}
@@ -0,0 +1,192 @@
+//! Caches the contract keys the enclave validated, and the state encryption keys it derived from
+//! them, so that calls to recently used contracts skip these derivations.
+//!
+//! Validated keys are cached per contract, code hash and consensus seed id, and are only used if
+//! the host sent the very same keys again. Migrating a contract, or rotating the consensus seed,
+//! can't make the enclave use a stale entry, and migrations drop the entries of the contract.
+
+use std::sync::SgxMutex;
+
+use lazy_static::lazy_static;
+use log::*;
+use lru::LruCache;
+
+use cw_types_generic::CONTRACT_KEY_PROOF_LENGTH;
+use cw_types_v010::types::CanonicalAddr;
+use enclave_crypto::{AESKey, HASH_SIZE, KEY_MANAGER};
+
+use crate::contract_validation::ContractKey;
+
+/// The number of contracts whose keys are cached
+const CONTRACT_KEY_CACHE_SIZE: usize = 1024;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ContractKeyId {
+    contract_address: Vec<u8>,
+    code_hash: [u8; HASH_SIZE],
+    seed_id: u16,
+}
+
+/// The keys the host sent for a contract
+#[derive(Clone, PartialEq, Eq)]
+pub struct ContractKeys {
+    pub og_contract_key: ContractKey,
+    /// The current key of a migrated contract, and its proof
+    pub current_contract_key: Option<(ContractKey, [u8; CONTRACT_KEY_PROOF_LENGTH])>,
+}
+
+lazy_static! {
+    static ref VALIDATED_KEYS: SgxMutex<LruCache<ContractKeyId, ContractKeys>> =
+        SgxMutex::new(LruCache::new(CONTRACT_KEY_CACHE_SIZE));
+    static ref STATE_KEYS: SgxMutex<LruCache<(ContractKey, u16), AESKey>> =
+        SgxMutex::new(LruCache::new(CONTRACT_KEY_CACHE_SIZE));
+}
+
+fn current_seed_id() -> u16 {
+    KEY_MANAGER.get_consensus_seed_id()
+}
+
+/// Whether `keys` were already validated for this contract and code
+pub fn is_validated(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    keys: &ContractKeys,
+) -> bool {
+    is_validated_with_seed(contract_address, code_hash, keys, current_seed_id())
+}
+
+pub fn set_validated(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    keys: ContractKeys,
+) {
+    set_validated_with_seed(contract_address, code_hash, keys, current_seed_id())
+}
+
+/// Drops the validated keys of a contract, e.g. because it was migrated
+pub fn invalidate_contract(contract_address: &CanonicalAddr) {
+    let mut cache = VALIDATED_KEYS.lock().unwrap();
+    let stale: Vec<ContractKeyId> = cache
+        .iter()
+        .map(|(id, _)| id)
+        .filter(|id| id.contract_address == contract_address.as_slice())
+        .cloned()
+        .collect();
+    trace!("dropping {} cached keys of a contract", stale.len());
+    for id in stale {
+        cache.pop(&id);
+    }
+}
+
+/// The key encrypting the state of the contract with `og_contract_key` under the current
+/// consensus seed, which `derive` is called to compute if it isn't cached
+pub fn state_key<F: FnOnce() -> AESKey>(og_contract_key: &ContractKey, derive: F) -> AESKey {
+    state_key_with_seed(og_contract_key, current_seed_id(), derive)
+}
+
+fn is_validated_with_seed(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    keys: &ContractKeys,
+    seed_id: u16,
+) -> bool {
+    let id = ContractKeyId {
+        contract_address: contract_address.as_slice().to_vec(),
+        code_hash: *code_hash,
+        seed_id,
+    };
+    VALIDATED_KEYS.lock().unwrap().get(&id) == Some(keys)
+}
+
+fn set_validated_with_seed(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    keys: ContractKeys,
+    seed_id: u16,
+) {
+    let id = ContractKeyId {
+        contract_address: contract_address.as_slice().to_vec(),
+        code_hash: *code_hash,
+        seed_id,
+    };
+    VALIDATED_KEYS.lock().unwrap().put(id, keys);
+}
+
+fn state_key_with_seed<F: FnOnce() -> AESKey>(
+    og_contract_key: &ContractKey,
+    seed_id: u16,
+    derive: F,
+) -> AESKey {
+    let id = (*og_contract_key, seed_id);
+    if let Some(key) = STATE_KEYS.lock().unwrap().get(&id) {
+        return *key;
+    }
+
+    let key = derive();
+    STATE_KEYS.lock().unwrap().put(id, key);
+    key
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn keys(og: u8, current: Option<u8>) -> ContractKeys {
+        ContractKeys {
+            og_contract_key: [og; 64],
+            current_contract_key: current.map(|current| ([current; 64], [current; 32])),
+        }
+    }
+
+    fn validated(address: u8, code_hash: u8, keys: &ContractKeys, seed_id: u16) -> bool {
+        let address = CanonicalAddr::from_vec(vec![address; 20]);
+        is_validated_with_seed(&address, &[code_hash; HASH_SIZE], keys, seed_id)
+    }
+
+    fn set_validated(address: u8, code_hash: u8, keys: ContractKeys) {
+        let address = CanonicalAddr::from_vec(vec![address; 20]);
+        set_validated_with_seed(&address, &[code_hash; HASH_SIZE], keys, 0)
+    }
+
+    pub fn test_validated_keys_must_match() {
+        assert!(!validated(1, 1, &keys(1, None), 0));
+        set_validated(1, 1, keys(1, None));
+        assert!(validated(1, 1, &keys(1, None), 0));
+
+        assert!(!validated(1, 1, &keys(2, None), 0));
+        assert!(!validated(1, 1, &keys(1, Some(3)), 0));
+        assert!(!validated(1, 2, &keys(1, None), 0));
+        assert!(!validated(1, 1, &keys(1, None), 1));
+        assert!(!validated(2, 1, &keys(1, None), 0));
+    }
+
+    pub fn test_invalidate_contract() {
+        set_validated(3, 1, keys(1, None));
+        set_validated(3, 2, keys(1, Some(2)));
+        set_validated(4, 1, keys(3, None));
+
+        invalidate_contract(&CanonicalAddr::from_vec(vec![3; 20]));
+        assert!(!validated(3, 1, &keys(1, None), 0));
+        assert!(!validated(3, 2, &keys(1, Some(2)), 0));
+        assert!(validated(4, 1, &keys(3, None), 0));
+    }
+
+    pub fn test_state_key_is_derived_once_per_seed() {
+        let derivations = Cell::new(0);
+        let derive = || {
+            derivations.set(derivations.get() + 1);
+            AESKey::new_from_slice(&[derivations.get(); 32])
+        };
+
+        let first = state_key_with_seed(&[5; 64], 0, derive);
+        let second = state_key_with_seed(&[5; 64], 0, derive);
+        assert_eq!(derivations.get(), 1);
+        assert_eq!(first.get(), second.get());
+
+        state_key_with_seed(&[5; 64], 1, derive);
+        state_key_with_seed(&[6; 64], 0, derive);
+        assert_eq!(derivations.get(), 3);
+    }
+}
@@ -0,0 +1,168 @@
+//! Proofs that the keys of a contract were derived by a genuine enclave, for clients that fetch
+//! them from a node they don't trust.
+//!
+//! The enclave validates the contract key against the contract address and code hash, exactly as
+//! before running the contract, then signs the contract key and the contract's encryption public
+//! key with the node signing key. A client verifies the chain back to the registration authority:
+//!
+//! 1. The attestation certificate of `node_public_key`, which the registration module accepted
+//!    when the node registered, is signed by Intel and commits to the node signing key in its
+//!    report data (see `execution_receipts::node_signer_binding`).
+//! 2. The node signing key signed the proof.
+//!
+//! The encryption public key is derived from the genesis consensus seed, so it doesn't change when
+//! the seed is rotated.
+
+use log::*;
+
+use cw_types_generic::CONTRACT_KEY_PROOF_LENGTH;
+use cw_types_v010::types::CanonicalAddr;
+use enclave_crypto::{sha_256, HASH_SIZE, KEY_MANAGER, PUBLIC_KEY_SIZE};
+use enclave_ffi_types::EnclaveError;
+
+use crate::contract_encryption::contract_encryption_keypair;
+use crate::contract_key_cache::ContractKeys;
+use crate::contract_validation::{validate_contract_keys, ContractKey};
+use crate::execution_receipts::node_signing_key;
+
+pub const CONTRACT_KEY_PROOF_VERSION: u8 = 1;
+
+/// The enclave's part of a contract key proof. The host adds the fields it already knows.
+pub struct ContractKeyProof {
+    pub node_public_key: [u8; PUBLIC_KEY_SIZE],
+    pub encryption_public_key: [u8; PUBLIC_KEY_SIZE],
+    pub signer_public_key: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// The bytes covered by the signature of a proof. The contract address is the only field of
+/// variable length, so it goes last.
+fn signing_bytes(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    contract_key: &ContractKey,
+    encryption_public_key: &[u8; PUBLIC_KEY_SIZE],
+    node_public_key: &[u8; PUBLIC_KEY_SIZE],
+    signer_public_key: &[u8; 32],
+) -> [u8; 32] {
+    let mut data = vec![CONTRACT_KEY_PROOF_VERSION];
+    data.extend_from_slice(code_hash);
+    data.extend_from_slice(contract_key);
+    data.extend_from_slice(encryption_public_key);
+    data.extend_from_slice(node_public_key);
+    data.extend_from_slice(signer_public_key);
+    data.extend_from_slice(contract_address.as_slice());
+    sha_256(&data)
+}
+
+fn sign_proof(
+    signing_key: &ed25519_zebra::SigningKey,
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    contract_key: &ContractKey,
+    encryption_public_key: [u8; PUBLIC_KEY_SIZE],
+    node_public_key: [u8; PUBLIC_KEY_SIZE],
+) -> ContractKeyProof {
+    let signer_public_key: [u8; 32] = ed25519_zebra::VerificationKey::from(signing_key).into();
+    let signature = signing_key.sign(&signing_bytes(
+        contract_address,
+        code_hash,
+        contract_key,
+        &encryption_public_key,
+        &node_public_key,
+        &signer_public_key,
+    ));
+
+    ContractKeyProof {
+        node_public_key,
+        encryption_public_key,
+        signer_public_key,
+        signature: signature.into(),
+    }
+}
+
+/// Validates the keys of the contract and signs a proof of its current contract key.
+///
+/// `current_contract_key` is the current key of a migrated contract and its migration proof.
+pub fn prove_contract_key(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    og_contract_key: ContractKey,
+    current_contract_key: Option<(ContractKey, [u8; CONTRACT_KEY_PROOF_LENGTH])>,
+) -> Result<ContractKeyProof, EnclaveError> {
+    let keys = ContractKeys {
+        og_contract_key,
+        current_contract_key,
+    };
+    validate_contract_keys(contract_address, code_hash, &keys)?;
+
+    let contract_key = match &keys.current_contract_key {
+        Some((current_contract_key, _)) => current_contract_key,
+        None => &keys.og_contract_key,
+    };
+
+    let signing_key = node_signing_key().ok_or(EnclaveError::FailedUnseal)?;
+    let node_public_key = KEY_MANAGER
+        .get_registration_key()
+        .map_err(|_| EnclaveError::FailedUnseal)?
+        .get_pubkey();
+    let encryption_public_key = contract_encryption_keypair(contract_address)?.get_pubkey();
+
+    trace!(
+        "proving contract key {:?} of code hash {:?}",
+        hex::encode(contract_key),
+        hex::encode(code_hash)
+    );
+
+    Ok(sign_proof(
+        &signing_key,
+        contract_address,
+        code_hash,
+        contract_key,
+        encryption_public_key,
+        node_public_key,
+    ))
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    pub fn test_contract_key_proof_verifies() {
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let contract_address = CanonicalAddr::from_vec(vec![1u8; 20]);
+        let code_hash = [2u8; HASH_SIZE];
+        let contract_key = [3u8; 64];
+
+        let proof = sign_proof(
+            &signing_key,
+            &contract_address,
+            &code_hash,
+            &contract_key,
+            [4u8; PUBLIC_KEY_SIZE],
+            [5u8; PUBLIC_KEY_SIZE],
+        );
+
+        let verification_key =
+            ed25519_zebra::VerificationKey::try_from(proof.signer_public_key).unwrap();
+        let signature = ed25519_zebra::Signature::from(proof.signature);
+        let signed = |code_hash: &[u8; HASH_SIZE]| {
+            signing_bytes(
+                &contract_address,
+                code_hash,
+                &contract_key,
+                &proof.encryption_public_key,
+                &proof.node_public_key,
+                &proof.signer_public_key,
+            )
+        };
+        verification_key
+            .verify(&signature, &signed(&code_hash))
+            .unwrap();
+        assert!(verification_key
+            .verify(&signature, &signed(&[9u8; HASH_SIZE]))
+            .is_err());
+    }
+}
@@ -10,10 +10,25 @@ use cw_types_v010::types::CanonicalAddr;
 
 use enclave_cosmos_types::types::{ContractCode, HandleType, SigInfo, VerifyParamsType};
 use enclave_crypto::Ed25519PublicKey;
-use enclave_ffi_types::{Ctx, EnclaveError};
+use enclave_ffi_types::{Ctx, EnclaveError, QueryResponseSignature};
 use log::*;
 
+use crate::access_list::extract_access_list;
+use crate::contract_key_cache;
 use crate::cosmwasm_config::ContractOperation;
+use crate::cw2::{
+    attach_contract_version, check_version_upgrade, parse_contract_version, ContractVersion,
+    CONTRACT_INFO_KEY,
+};
+use crate::execution_receipts::{
+    add_contract_query_mac, query_response_signature, report_execution_receipt,
+};
+use crate::feature_gates::{
+    extract_feature_gates, FeatureGates, BULK_MEMORY, CONTRACT_VERSION, GAS_FORWARDING,
+    MEMORY_GROWTH_LIMIT, STACK_HEIGHT_LIMIT, STATE_COMPRESSION,
+};
+#[cfg(feature = "observer-mode")]
+use crate::observers::report_observed_events;
 
 #[cfg(feature = "light-client-validation")]
 use crate::contract_validation::verify_block_info;
@@ -25,9 +40,14 @@ use crate::external::results::{
     HandleSuccess, InitSuccess, MigrateSuccess, QuerySuccess, UpdateAdminSuccess,
 };
 use crate::message::{is_ibc_msg, parse_message};
+use crate::query_rewrap::strip_rewrap_opt_in;
 use crate::types::ParsedMessage;
 
+use crate::event_validation::validate_events;
 use crate::random::update_msg_counter;
+use crate::response_limits::{
+    enforce_response_limits, extract_response_limits, TOP_LEVEL_QUERY_DEPTH,
+};
 
 #[cfg(feature = "random")]
 use crate::random::derive_random;
@@ -37,12 +57,14 @@ use crate::wasm3::Engine;
 use crate::hardcoded_admins::is_hardcoded_contract_admin;
 
 use super::contract_validation::{
-    generate_contract_key, validate_contract_key, validate_msg, verify_params, ContractKey,
+    generate_contract_key, validate_contract_key, validate_msg, verify_params,
+    verify_predictable_contract_address, ContractKey,
 };
+use super::envelope::Envelope;
 use super::gas::WasmCosts;
 use super::io::{
-    finalize_raw_output, manipulate_callback_sig_for_plaintext, post_process_output,
-    set_all_logs_to_plaintext,
+    deserialize_output, finalize_raw_output, manipulate_callback_sig_for_plaintext,
+    post_process_output, set_all_logs_to_plaintext,
 };
 use super::types::{IoNonce, SecretMessage};
 
@@ -93,6 +115,8 @@ pub fn init(
     // let duration = start.elapsed();
     // trace!("Time elapsed in extract_base_env is: {:?}", duration);
     let query_depth = extract_query_depth(env)?;
+    let response_limits = extract_response_limits(env)?;
+    let feature_gates = extract_feature_gates(env)?;
 
     //let start = Instant::now();
     let (sender, contract_address, block_height, sent_funds) = base_env.get_verification_params();
@@ -103,6 +127,15 @@ pub fn init(
     let canonical_sender_address = to_canonical(sender)?;
     let canonical_admin_address = CanonicalAddr::from_vec(admin.to_vec());
 
+    if let Some(salt) = base_env.get_instantiate_salt() {
+        verify_predictable_contract_address(
+            &canonical_contract_address,
+            &contract_hash,
+            &canonical_sender_address,
+            salt,
+        )?;
+    }
+
     // contract_key is a unique key for each contract
     // it's used in state encryption to prevent the same
     // encryption keys from being used for different contracts
@@ -159,12 +192,15 @@ pub fn init(
         gas_limit,
         &contract_code,
         &og_contract_key,
+        &canonical_contract_address,
         ContractOperation::Init,
         query_depth,
         secret_msg.nonce,
         secret_msg.user_public_key,
         base_env.0.block.time,
+        &feature_gates,
     )?;
+    engine.set_simulation(extract_simulation(env)?);
     // let duration = start.elapsed();
     // trace!("Time elapsed in start_engine: {:?}", duration);
 
@@ -191,6 +227,17 @@ pub fn init(
     *used_gas = engine.gas_used();
 
     let output = result?;
+    let contract_version = read_contract_version(&mut engine, &feature_gates)?;
+    *used_gas = engine.gas_used();
+
+    #[cfg(feature = "observer-mode")]
+    report_observed_events(
+        &canonical_contract_address,
+        block_height,
+        &output,
+        env,
+        engine.observer(),
+    );
 
     #[cfg(not(feature = "random"))]
     let random: Option<Binary> = None;
@@ -206,8 +253,10 @@ pub fn init(
     // TODO: ref: https://github.com/CosmWasm/cosmwasm/blob/b971c037a773bf6a5f5d08a88485113d9b9e8e7b/packages/std/src/query.rs#L13
     //let start = Instant::now();
 
+    let raw_output = deserialize_output(output)?;
+    enforce_response_limits(&raw_output, &response_limits, query_depth)?;
     let output = post_process_output(
-        output,
+        raw_output,
         &secret_msg,
         &canonical_contract_address,
         versioned_env.get_contract_hash(),
@@ -215,7 +264,9 @@ pub fn init(
         &canonical_sender_address,
         false,
         false,
+        &feature_gates,
     )?;
+    let output = attach_contract_version(output, public_version(&engine, contract_version))?;
 
     // let duration = start.elapsed();
     // trace!("Time elapsed in encrypt_output: {:?}", duration);
@@ -224,6 +275,8 @@ pub fn init(
 
     let admin_proof = generate_admin_proof(&canonical_admin_address.0 .0, &og_contract_key);
 
+    report_execution_receipt(&contract_hash, msg, &output, *used_gas);
+
     Ok(InitSuccess {
         output,
         contract_key: og_contract_key,
@@ -291,6 +344,8 @@ pub fn migrate(
     // let duration = start.elapsed();
     // trace!("Time elapsed in extract_base_env is: {:?}", duration);
     let query_depth = extract_query_depth(env)?;
+    let response_limits = extract_response_limits(env)?;
+    let feature_gates = extract_feature_gates(env)?;
 
     //let start = Instant::now();
     let (sender, contract_address, block_height, sent_funds) = base_env.get_verification_params();
@@ -365,12 +420,15 @@ pub fn migrate(
         gas_limit,
         &contract_code,
         &og_contract_key,
+        &canonical_contract_address,
         ContractOperation::Migrate,
         query_depth,
         secret_msg.nonce,
         secret_msg.user_public_key,
         base_env.0.block.time,
+        &feature_gates,
     )?;
+    engine.set_simulation(extract_simulation(env)?);
     // let duration = start.elapsed();
     // trace!("Time elapsed in start_engine: {:?}", duration);
 
@@ -394,12 +452,17 @@ pub fn migrate(
         &mut versioned_env,
     );
 
+    let old_contract_version = read_contract_version(&mut engine, &feature_gates)?;
+
     update_msg_counter(block_height);
     let result = engine.migrate(&versioned_env, validated_msg);
 
     *used_gas = engine.gas_used();
 
     let output = result?;
+    let contract_version = read_contract_version(&mut engine, &feature_gates)?;
+    *used_gas = engine.gas_used();
+    check_version_upgrade(old_contract_version.as_ref(), contract_version.as_ref())?;
 
     let random = versioned_env.get_random();
 
@@ -407,8 +470,10 @@ pub fn migrate(
         .flush_cache(random)
         .map_err(|_| EnclaveError::FailedFunctionCall)?;
 
+    let raw_output = deserialize_output(output)?;
+    enforce_response_limits(&raw_output, &response_limits, query_depth)?;
     let output = post_process_output(
-        output,
+        raw_output,
         &secret_msg,
         &canonical_contract_address,
         versioned_env.get_contract_hash(),
@@ -416,7 +481,9 @@ pub fn migrate(
         &canonical_sender_address,
         false,
         false,
+        &feature_gates,
     )?;
+    let output = attach_contract_version(output, public_version(&engine, contract_version))?;
 
     // let duration = start.elapsed();
     // trace!("Time elapsed in encrypt_output: {:?}", duration);
@@ -435,6 +502,10 @@ pub fn migrate(
         new_contract_key, new_contract_key_proof
     );
 
+    report_execution_receipt(&contract_hash, msg, &output, *used_gas);
+
+    contract_key_cache::invalidate_contract(&canonical_contract_address);
+
     Ok(MigrateSuccess {
         output,
         new_contract_key,
@@ -496,6 +567,7 @@ pub fn update_admin(
             nonce: [0; 32],
             user_public_key: [0; 32],
             msg: vec![], // must be empty vec for callback_sig verification
+            envelope: Envelope::V1,
         },
         true,
         true,
@@ -538,6 +610,9 @@ pub fn handle(
     verify_block_info(&base_env)?;
 
     let query_depth = extract_query_depth(env)?;
+    let response_limits = extract_response_limits(env)?;
+    let feature_gates = extract_feature_gates(env)?;
+    let access_list = extract_access_list(env)?;
 
     let (sender, contract_address, block_height, sent_funds) = base_env.get_verification_params();
 
@@ -611,12 +686,16 @@ pub fn handle(
         gas_limit,
         &contract_code,
         &og_contract_key,
+        &canonical_contract_address,
         ContractOperation::Handle,
         query_depth,
         secret_msg.nonce,
         secret_msg.user_public_key,
         base_env.0.block.time,
+        &feature_gates,
     )?;
+    engine.set_simulation(extract_simulation(env)?);
+    engine.set_access_list(access_list);
 
     let mut versioned_env = base_env
         .clone()
@@ -664,7 +743,16 @@ pub fn handle(
 
     *used_gas = engine.gas_used();
 
-    let mut output = result?;
+    let output = result?;
+
+    #[cfg(feature = "observer-mode")]
+    report_observed_events(
+        &canonical_contract_address,
+        block_height,
+        &output,
+        env,
+        engine.observer(),
+    );
 
     let random = versioned_env.get_random();
 
@@ -678,9 +766,11 @@ pub fn handle(
         "(2) nonce just before encrypt_output: nonce = {:x?} pubkey = {:x?}",
         secret_msg.nonce, secret_msg.user_public_key
     );
-    if should_encrypt_output {
-        output = post_process_output(
-            output,
+    let raw_output = deserialize_output(output)?;
+    enforce_response_limits(&raw_output, &response_limits, query_depth)?;
+    let output = if should_encrypt_output {
+        post_process_output(
+            raw_output,
             &secret_msg,
             &canonical_contract_address,
             versioned_env.get_contract_hash(),
@@ -688,14 +778,21 @@ pub fn handle(
             &canonical_sender_address,
             false,
             is_ibc_msg(parsed_handle_type),
-        )?;
+            &feature_gates,
+        )?
     } else {
-        let mut raw_output =
-            manipulate_callback_sig_for_plaintext(&canonical_contract_address, output)?;
+        let mut raw_output = manipulate_callback_sig_for_plaintext(
+            &canonical_contract_address,
+            raw_output,
+            &feature_gates,
+        )?;
         set_all_logs_to_plaintext(&mut raw_output);
+        validate_events(&raw_output, &feature_gates)?;
 
-        output = finalize_raw_output(raw_output, false, is_ibc_msg(parsed_handle_type), false)?;
-    }
+        finalize_raw_output(raw_output, false, is_ibc_msg(parsed_handle_type), false)?
+    };
+
+    report_execution_receipt(&contract_hash, msg, &output, *used_gas);
 
     Ok(HandleSuccess { output })
 }
@@ -714,6 +811,7 @@ fn set_random_in_env(
         {
             debug!("random is enabled by contract");
             update_random_with_msg_counter(block_height, contract_key, versioned_env);
+            engine.set_random(versioned_env.get_random());
         } else {
             versioned_env.set_random(None);
         }
@@ -746,6 +844,8 @@ pub fn query(
 
     let base_env: BaseEnv = extract_base_env(env)?;
     let query_depth = extract_query_depth(env)?;
+    let response_limits = extract_response_limits(env)?;
+    let feature_gates = extract_feature_gates(env)?;
 
     let (_, contract_address, _, _) = base_env.get_verification_params();
 
@@ -758,7 +858,7 @@ pub fn query(
 
     let ValidatedMessage { validated_msg, .. } = validate_msg(
         &canonical_contract_address,
-        &decrypted_msg,
+        strip_rewrap_opt_in(&decrypted_msg),
         &contract_hash,
         None,
         None,
@@ -771,11 +871,13 @@ pub fn query(
         gas_limit,
         &contract_code,
         &og_contract_key,
+        &canonical_contract_address,
         ContractOperation::Query,
         query_depth,
         secret_msg.nonce,
         secret_msg.user_public_key,
         base_env.0.block.time,
+        &feature_gates,
     )?;
 
     let mut versioned_env = base_env
@@ -788,8 +890,10 @@ pub fn query(
     *used_gas = engine.gas_used();
     let output = result?;
 
+    let raw_output = deserialize_output(output)?;
+    enforce_response_limits(&raw_output, &response_limits, query_depth)?;
     let output = post_process_output(
-        output,
+        raw_output,
         &secret_msg,
         &CanonicalAddr(Binary(Vec::new())), // Not used for queries (can't init a new contract from a query)
         "",   // Not used for queries (can't call a sub-message from a query),
@@ -797,9 +901,30 @@ pub fn query(
         &CanonicalAddr(Binary(Vec::new())), // Not used for queries (used only for replies)
         true,
         false,
+        &feature_gates,
     )?;
 
-    Ok(QuerySuccess { output })
+    // Queries made by contracts are part of a call whose own result is what matters
+    let mut signature = if query_depth == TOP_LEVEL_QUERY_DEPTH {
+        query_response_signature(&canonical_contract_address.0 .0, msg, &output)
+    } else {
+        QueryResponseSignature::unsigned()
+    };
+    if query_depth == TOP_LEVEL_QUERY_DEPTH
+        && engine
+            .supported_features()
+            .contains(&ContractFeature::QueryResponseMac)
+    {
+        add_contract_query_mac(
+            &mut signature,
+            &secret_msg.encryption_key()?,
+            &canonical_contract_address.0 .0,
+            msg,
+            &output,
+        );
+    }
+
+    Ok(QuerySuccess { output, signature })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -808,24 +933,80 @@ fn start_engine(
     gas_limit: u64,
     contract_code: &ContractCode,
     og_contract_key: &ContractKey,
+    contract_address: &CanonicalAddr,
     operation: ContractOperation,
     query_depth: u32,
     nonce: IoNonce,
     user_public_key: Ed25519PublicKey,
     timestamp: u64,
+    feature_gates: &FeatureGates,
 ) -> Result<crate::wasm3::Engine, EnclaveError> {
-    crate::wasm3::Engine::new(
+    let mut engine = crate::wasm3::Engine::new(
         context,
         gas_limit,
         WasmCosts::default(),
         contract_code,
         *og_contract_key,
+        contract_address.clone(),
         operation,
         nonce,
         user_public_key,
         query_depth,
         timestamp,
-    )
+    )?;
+    if engine
+        .supported_features()
+        .contains(&ContractFeature::BulkMemory)
+        && !feature_gates.is_active(BULK_MEMORY)
+    {
+        debug!("contract uses bulk memory instructions, which governance didn't activate");
+        return Err(EnclaveError::InvalidWasm);
+    }
+    if !feature_gates.is_active(STATE_COMPRESSION) {
+        engine.disable_state_compression();
+    }
+    if !feature_gates.is_active(STACK_HEIGHT_LIMIT) {
+        engine.disable_stack_height_limit();
+    }
+    if !feature_gates.is_active(MEMORY_GROWTH_LIMIT) {
+        engine.disable_memory_growth_limit();
+    }
+    if !feature_gates.is_active(GAS_FORWARDING) {
+        engine.disable_gas_forwarding();
+    }
+    Ok(engine)
+}
+
+/// Reads the cw2 version of the contract, once the feature is active. A value that can't be read
+/// counts as no version, so that contracts not following the convention never fail because of it,
+/// unless the read runs out of gas.
+fn read_contract_version(
+    engine: &mut crate::wasm3::Engine,
+    feature_gates: &FeatureGates,
+) -> Result<Option<ContractVersion>, EnclaveError> {
+    if !feature_gates.is_active(CONTRACT_VERSION) {
+        return Ok(None);
+    }
+    match engine.read_state(CONTRACT_INFO_KEY) {
+        Ok(value) => Ok(parse_contract_version(value.as_deref())),
+        Err(EnclaveError::OutOfGas) => Err(EnclaveError::OutOfGas),
+        Err(err) => {
+            debug!("failed to read contract_info: {:?}", err);
+            Ok(None)
+        }
+    }
+}
+
+/// The version is part of the encrypted state, so it is only published for contracts opting in
+fn public_version(
+    engine: &crate::wasm3::Engine,
+    version: Option<ContractVersion>,
+) -> Option<ContractVersion> {
+    version.filter(|_| {
+        engine
+            .supported_features()
+            .contains(&ContractFeature::PublicContractVersion)
+    })
 }
 
 fn extract_base_env(env: &[u8]) -> Result<BaseEnv, EnclaveError> {
@@ -849,6 +1030,28 @@ struct EnvWithQD {
     query_depth: u32,
 }
 
+#[derive(Debug, Deserialize)]
+struct EnvWithSimulation {
+    #[serde(default)]
+    simulation: bool,
+}
+
+/// Extract whether the call is made during CheckTx or a simulation from the env parameter, like
+/// the query_depth. The host can't gain anything from lying about it: it only makes sampling use a
+/// fixed placeholder instead of the block random.
+fn extract_simulation(env: &[u8]) -> Result<bool, EnclaveError> {
+    serde_json::from_slice::<EnvWithSimulation>(env)
+        .map_err(|err| {
+            warn!(
+                "error while deserializing env into json {:?}: {}",
+                String::from_utf8_lossy(env),
+                err
+            );
+            EnclaveError::FailedToDeserialize
+        })
+        .map(|env| env.simulation)
+}
+
 /// Extract the query_depth from the env parameter.
 ///
 /// This is done in a separate method and type definition in order
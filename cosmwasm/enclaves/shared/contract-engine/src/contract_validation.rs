@@ -15,6 +15,7 @@ use enclave_crypto::{sha_256, AESKey, Hmac, Kdf, HASH_SIZE, KEY_MANAGER};
 use enclave_ffi_types::EnclaveError;
 use protobuf::Message;
 
+use crate::contract_key_cache::{self, ContractKeys};
 use crate::hardcoded_admins::is_code_hash_allowed;
 use crate::input_validation::contract_address_validation::verify_contract_address;
 use crate::input_validation::msg_validation::verify_and_get_sdk_msg;
@@ -217,6 +218,54 @@ pub fn generate_contract_key(
     Ok(contract_key)
 }
 
+/// Separates predictable contract addresses from other hashes
+const PREDICTABLE_ADDRESS_DOMAIN: &[u8] = b"secret_instantiate2";
+const PREDICTABLE_ADDRESS_LENGTH: usize = 20;
+pub const MAX_INSTANTIATE_SALT_LENGTH: usize = 64;
+
+/// The address of a contract instantiated with a salt. It only depends on the code hash, the
+/// creator and the salt, so factories can compute it before deploying the contract.
+///
+/// The address is the first 20 bytes of the sha256 of `"secret_instantiate2"` followed by the
+/// code hash, the creator and the salt, each prefixed with its length as a big endian u64.
+/// x/compute derives addresses the same way.
+pub fn predictable_contract_address(
+    code_hash: &[u8; HASH_SIZE],
+    creator: &CanonicalAddr,
+    salt: &[u8],
+) -> CanonicalAddr {
+    let mut input_data = PREDICTABLE_ADDRESS_DOMAIN.to_vec();
+    for part in [&code_hash[..], creator.as_slice(), salt] {
+        input_data.extend_from_slice(&(part.len() as u64).to_be_bytes());
+        input_data.extend_from_slice(part);
+    }
+    CanonicalAddr::from_vec(sha_256(&input_data)[..PREDICTABLE_ADDRESS_LENGTH].to_vec())
+}
+
+/// Checks that a contract instantiated with a salt got the address derived from it.
+///
+/// The contract key authenticates the contract address, so once the address is checked the key is
+/// bound to the code hash, creator and salt as well: the host can't instantiate other code, or
+/// the same code for someone else, at an address a factory predicted.
+pub fn verify_predictable_contract_address(
+    contract_address: &CanonicalAddr,
+    code_hash: &[u8; HASH_SIZE],
+    creator: &CanonicalAddr,
+    salt: &[u8],
+) -> Result<(), EnclaveError> {
+    if salt.is_empty() || salt.len() > MAX_INSTANTIATE_SALT_LENGTH {
+        warn!("Got an instantiate salt of invalid length {}", salt.len());
+        return Err(EnclaveError::ValidationFailure);
+    }
+
+    if predictable_contract_address(code_hash, creator, salt) != *contract_address {
+        warn!("Contract address doesn't match its instantiate salt");
+        return Err(EnclaveError::ValidationFailure);
+    }
+
+    Ok(())
+}
+
 pub fn generate_sender_id(msg_sender: &[u8], block_height: &u64) -> [u8; HASH_SIZE] {
     let mut input_data = msg_sender.to_vec();
     input_data.extend_from_slice(&block_height.to_be_bytes());
@@ -246,7 +295,7 @@ pub fn generate_contract_id(
 pub fn validate_current_contract_key(
     contract_key: &[u8; CONTRACT_KEY_LENGTH],
     contract_address: &CanonicalAddr,
-    contract_code: &ContractCode,
+    code_hash: &[u8; HASH_SIZE],
     og_contract_key: Option<&[u8; CONTRACT_KEY_LENGTH]>,
 ) -> Result<(), EnclaveError> {
     // parse contract key -> < signer_id || authentication_code >
@@ -270,7 +319,7 @@ pub fn validate_current_contract_key(
     let calculated_authentication_id = generate_contract_id(
         &enclave_key,
         &signer_id,
-        &contract_code.hash(),
+        code_hash,
         contract_address.as_slice(),
         og_contract_key,
     );
@@ -292,25 +341,48 @@ pub fn validate_contract_key(
 ) -> Result<(), EnclaveError> {
     let og_contract_key: [u8; CONTRACT_KEY_LENGTH] = base_env.get_og_contract_key()?;
 
-    if base_env.was_migrated() {
-        trace!("Contract was migrated, validating proof");
+    let current_contract_key = if base_env.was_migrated() {
+        Some((
+            base_env.get_current_contract_key()?,
+            base_env.get_current_contract_key_proof()?,
+        ))
+    } else {
+        None
+    };
+    let keys = ContractKeys {
+        og_contract_key,
+        current_contract_key,
+    };
+
+    validate_contract_keys(canonical_contract_address, &contract_code.hash(), &keys)
+}
+
+/// Validates the keys of a contract against its address and code hash, and the proof of the
+/// current key of a migrated contract against its original key.
+pub fn validate_contract_keys(
+    canonical_contract_address: &CanonicalAddr,
+    contract_hash: &[u8; HASH_SIZE],
+    keys: &ContractKeys,
+) -> Result<(), EnclaveError> {
+    if contract_key_cache::is_validated(canonical_contract_address, contract_hash, keys) {
+        trace!("Contract keys were already validated");
+        return Ok(());
+    }
 
-        let current_contract_key: [u8; CONTRACT_KEY_LENGTH] =
-            base_env.get_current_contract_key()?;
+    if let Some((current_contract_key, sent_contract_key_proof)) = keys.current_contract_key {
+        trace!("Contract was migrated, validating proof");
 
         validate_current_contract_key(
             &current_contract_key,
             canonical_contract_address,
-            contract_code,
-            Some(&og_contract_key),
+            contract_hash,
+            Some(&keys.og_contract_key),
         )?;
 
-        let sent_contract_key_proof = base_env.get_current_contract_key_proof()?;
-
         let contract_key_proof = generate_contract_key_proof(
             &canonical_contract_address.0 .0,
-            &contract_code.hash(),
-            &og_contract_key,
+            contract_hash,
+            &keys.og_contract_key,
             &current_contract_key, // this is already validated
         );
 
@@ -318,20 +390,20 @@ pub fn validate_contract_key(
             error!("Failed to validate contract key proof for a migrated contract");
             return Err(EnclaveError::ValidationFailure);
         }
-
-        Ok(())
     } else {
         trace!("Contract still has original code, validating contract_key");
 
         validate_current_contract_key(
-            &og_contract_key,
+            &keys.og_contract_key,
             canonical_contract_address,
-            contract_code,
+            contract_hash,
             None,
         )?;
-
-        Ok(())
     }
+
+    contract_key_cache::set_validated(canonical_contract_address, contract_hash, keys.clone());
+
+    Ok(())
 }
 
 pub fn generate_admin_proof(admin: &[u8], contract_key: &[u8]) -> [u8; enclave_crypto::HASH_SIZE] {
@@ -921,3 +993,36 @@ fn verify_input_params(
 
     Ok(true)
 }
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use cw_types_v010::types::CanonicalAddr;
+    use enclave_ffi_types::EnclaveError;
+
+    use super::{predictable_contract_address, verify_predictable_contract_address};
+
+    pub fn test_predictable_contract_address() {
+        let creator = CanonicalAddr::from_vec(vec![2u8; 20]);
+        let address = predictable_contract_address(&[1u8; 32], &creator, b"salt");
+
+        // Must match the address derived by x/compute for the same inputs
+        assert_eq!(
+            hex::encode(address.as_slice()),
+            "a7c05addb72eefb6e2ab807e6335a76426c2eb79"
+        );
+        verify_predictable_contract_address(&address, &[1u8; 32], &creator, b"salt").unwrap();
+
+        assert!(matches!(
+            verify_predictable_contract_address(&address, &[1u8; 32], &creator, b"other"),
+            Err(EnclaveError::ValidationFailure)
+        ));
+        assert!(matches!(
+            verify_predictable_contract_address(&address, &[3u8; 32], &creator, b"salt"),
+            Err(EnclaveError::ValidationFailure)
+        ));
+        assert!(matches!(
+            verify_predictable_contract_address(&address, &[1u8; 32], &creator, b""),
+            Err(EnclaveError::ValidationFailure)
+        ));
+    }
+}
@@ -1,3 +1,8 @@
+use core::fmt;
+
+use cw_types_generic::CosmWasmApiVersion;
+use enclave_cosmos_types::types::HandleType;
+
 /// api_marker is based on this compatibility chart:
 /// https://github.com/CosmWasm/cosmwasm/blob/v1.0.0-beta5/packages/vm/README.md#compatibility
 pub mod api_marker {
@@ -37,4 +42,95 @@ impl ContractOperation {
     }
 }
 
+/// The exports of a contract the enclave calls into.
+/// New entry points are added here, so every match on them has to handle the addition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryPoint {
+    Instantiate,
+    Execute,
+    Query,
+    Migrate,
+    Sudo,
+    Reply,
+    IbcChannelOpen,
+    IbcChannelConnect,
+    IbcChannelClose,
+    IbcPacketReceive,
+    IbcPacketAck,
+    IbcPacketTimeout,
+}
+
+impl EntryPoint {
+    pub fn from_handle_type(handle_type: &HandleType) -> Self {
+        match handle_type {
+            HandleType::HANDLE_TYPE_EXECUTE => EntryPoint::Execute,
+            HandleType::HANDLE_TYPE_REPLY => EntryPoint::Reply,
+            HandleType::HANDLE_TYPE_IBC_CHANNEL_OPEN => EntryPoint::IbcChannelOpen,
+            HandleType::HANDLE_TYPE_IBC_CHANNEL_CONNECT => EntryPoint::IbcChannelConnect,
+            HandleType::HANDLE_TYPE_IBC_CHANNEL_CLOSE => EntryPoint::IbcChannelClose,
+            HandleType::HANDLE_TYPE_IBC_PACKET_RECEIVE => EntryPoint::IbcPacketReceive,
+            HandleType::HANDLE_TYPE_IBC_PACKET_ACK => EntryPoint::IbcPacketAck,
+            HandleType::HANDLE_TYPE_IBC_PACKET_TIMEOUT => EntryPoint::IbcPacketTimeout,
+            HandleType::HANDLE_TYPE_IBC_WASM_HOOKS_INCOMING_TRANSFER => EntryPoint::Execute,
+            HandleType::HANDLE_TYPE_IBC_WASM_HOOKS_OUTGOING_TRANSFER_ACK => EntryPoint::Sudo,
+            HandleType::HANDLE_TYPE_IBC_WASM_HOOKS_OUTGOING_TRANSFER_TIMEOUT => EntryPoint::Sudo,
+        }
+    }
+
+    /// The name of the export in contracts of `api_version`
+    pub fn export_name(&self, api_version: CosmWasmApiVersion) -> &'static str {
+        match (self, api_version) {
+            (EntryPoint::Instantiate, CosmWasmApiVersion::V010) => "init",
+            (EntryPoint::Instantiate, _) => "instantiate",
+            (EntryPoint::Execute, CosmWasmApiVersion::V010) => "handle",
+            (EntryPoint::Execute, _) => "execute",
+            (EntryPoint::Query, _) => "query",
+            (EntryPoint::Migrate, _) => "migrate",
+            (EntryPoint::Sudo, _) => "sudo",
+            (EntryPoint::Reply, _) => "reply",
+            (EntryPoint::IbcChannelOpen, _) => "ibc_channel_open",
+            (EntryPoint::IbcChannelConnect, _) => "ibc_channel_connect",
+            (EntryPoint::IbcChannelClose, _) => "ibc_channel_close",
+            (EntryPoint::IbcPacketReceive, _) => "ibc_packet_receive",
+            (EntryPoint::IbcPacketAck, _) => "ibc_packet_ack",
+            (EntryPoint::IbcPacketTimeout, _) => "ibc_packet_timeout",
+        }
+    }
+
+    /// Whether the export takes an env region. Only v0.10 queries don't.
+    pub fn takes_env(&self, api_version: CosmWasmApiVersion) -> bool {
+        !(api_version == CosmWasmApiVersion::V010 && *self == EntryPoint::Query)
+    }
+
+    /// Whether the export takes a `MessageInfo` region between the env and the msg
+    pub fn takes_msg_info(&self, api_version: CosmWasmApiVersion) -> bool {
+        api_version == CosmWasmApiVersion::V1
+            && matches!(self, EntryPoint::Instantiate | EntryPoint::Execute)
+    }
+
+    /// IBC protocol entry points, which are not encrypted
+    pub fn is_ibc(&self) -> bool {
+        match self {
+            EntryPoint::IbcChannelOpen
+            | EntryPoint::IbcChannelConnect
+            | EntryPoint::IbcChannelClose
+            | EntryPoint::IbcPacketReceive
+            | EntryPoint::IbcPacketAck
+            | EntryPoint::IbcPacketTimeout => true,
+            EntryPoint::Instantiate
+            | EntryPoint::Execute
+            | EntryPoint::Query
+            | EntryPoint::Migrate
+            | EntryPoint::Sudo
+            | EntryPoint::Reply => false,
+        }
+    }
+}
+
+impl fmt::Display for EntryPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.export_name(CosmWasmApiVersion::V1))
+    }
+}
+
 //pub const MAX_LOG_LENGTH: usize = 8192;
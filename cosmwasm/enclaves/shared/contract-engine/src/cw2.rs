@@ -0,0 +1,224 @@
+//! Support for the cw2 convention: contracts store their name and version as JSON under the
+//! `contract_info` key of their state, e.g. `{"contract":"crates.io:cw20-base","version":"1.1.0"}`.
+//!
+//! The state of a contract is encrypted, so the node can't read the key the way vanilla CosmWasm
+//! chains do. Instead, the enclave reads it after instantiate and migrate, and rejects a migration
+//! that would lower the version of the same contract. The version is part of the encrypted state
+//! like any other value, so it is only returned to the node in plaintext for contracts that opt in
+//! with a `cw2_public` custom section.
+//!
+//! Both are only done once governance activates `feature_gates::CONTRACT_VERSION`.
+
+use std::cmp::Ordering;
+
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use enclave_ffi_types::EnclaveError;
+
+use crate::io::WasmOutput;
+
+pub const CONTRACT_INFO_KEY: &[u8] = b"contract_info";
+/// The custom section a contract opts in to publishing its version with
+pub const PUBLIC_CONTRACT_VERSION_SECTION: &str = "cw2_public";
+/// Longer values are not returned to the node, which stores them in plaintext
+const MAX_CONTRACT_INFO_LENGTH: usize = 256;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContractVersion {
+    pub contract: String,
+    pub version: String,
+}
+
+/// Parses the value of the `contract_info` key. `None` if it is missing or malformed, which
+/// contracts that don't follow the convention are free to do.
+pub fn parse_contract_version(value: Option<&[u8]>) -> Option<ContractVersion> {
+    let value = value?;
+    if value.len() > MAX_CONTRACT_INFO_LENGTH {
+        debug!(
+            "contract_info of {} bytes exceeds the limit of {} bytes",
+            value.len(),
+            MAX_CONTRACT_INFO_LENGTH
+        );
+        return None;
+    }
+    match serde_json::from_slice(value) {
+        Ok(version) => Some(version),
+        Err(err) => {
+            debug!("contract_info is not a cw2 version: {}", err);
+            None
+        }
+    }
+}
+
+/// Rejects a migration from `old` to `new` that lowers the version of the same contract. Versions
+/// are only compared when both are semver and the contract name didn't change.
+pub fn check_version_upgrade(
+    old: Option<&ContractVersion>,
+    new: Option<&ContractVersion>,
+) -> Result<(), EnclaveError> {
+    let (old, new) = match (old, new) {
+        (Some(old), Some(new)) if old.contract == new.contract => (old, new),
+        _ => return Ok(()),
+    };
+    match compare_versions(&old.version, &new.version) {
+        Some(Ordering::Greater) => {
+            warn!(
+                "migration would downgrade {} from {} to {}",
+                new.contract, old.version, new.version
+            );
+            Err(EnclaveError::ContractVersionDowngrade)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Adds `version` to a serialized `WasmOutput` if the contract succeeded. A failed call is
+/// reverted by the node, so its output is left as is.
+pub fn attach_contract_version(
+    output: Vec<u8>,
+    version: Option<ContractVersion>,
+) -> Result<Vec<u8>, EnclaveError> {
+    if version.is_none() {
+        return Ok(output);
+    }
+    let mut wasm_output: WasmOutput = serde_json::from_slice(&output).map_err(|err| {
+        warn!(
+            "failed to deserialize the output to attach contract_info: {}",
+            err
+        );
+        EnclaveError::FailedToDeserialize
+    })?;
+    let succeeded = wasm_output.v010.as_ref().map_or(false, |o| o.ok.is_some())
+        || wasm_output.v1.as_ref().map_or(false, |o| o.ok.is_some());
+    if !succeeded {
+        return Ok(output);
+    }
+
+    wasm_output.contract_info = version;
+    serde_json::to_vec(&wasm_output).map_err(|err| {
+        warn!("failed to serialize the output with contract_info: {}", err);
+        EnclaveError::FailedToSerialize
+    })
+}
+
+/// Major, minor, patch and pre-release identifiers of a semver version. Build metadata is ignored,
+/// as semver does for precedence.
+fn parse_semver(version: &str) -> Option<([u64; 3], Vec<&str>)> {
+    let version = version.split('+').next()?;
+    let (core, pre_release) = match version.find('-') {
+        Some(i) => (&version[..i], version[i + 1..].split('.').collect()),
+        None => (version, vec![]),
+    };
+
+    let mut numbers = [0u64; 3];
+    let mut parts = core.split('.');
+    for number in numbers.iter_mut() {
+        *number = parts.next()?.parse().ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((numbers, pre_release))
+}
+
+/// Compares versions by semver precedence. `None` if either isn't semver.
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a_core, a_pre) = parse_semver(a)?;
+    let (b_core, b_pre) = parse_semver(b)?;
+    let ordering = a_core.cmp(&b_core).then_with(|| {
+        // a release follows its pre-releases
+        match (a_pre.is_empty(), b_pre.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => compare_pre_releases(&a_pre, &b_pre),
+        }
+    });
+    Some(ordering)
+}
+
+fn compare_pre_releases(a: &[&str], b: &[&str]) -> Ordering {
+    for (x, y) in a.iter().zip(b.iter()) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            // numeric identifiers come first
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    fn version(contract: &str, version: &str) -> ContractVersion {
+        ContractVersion {
+            contract: contract.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    pub fn test_parse_contract_version() {
+        assert_eq!(
+            parse_contract_version(Some(br#"{"contract":"crates.io:cw20","version":"1.0.0"}"#)),
+            Some(version("crates.io:cw20", "1.0.0"))
+        );
+        assert_eq!(parse_contract_version(None), None);
+        assert_eq!(parse_contract_version(Some(b"not json")), None);
+        assert_eq!(
+            parse_contract_version(Some(br#"{"contract":"cw20"}"#)),
+            None
+        );
+        let long = format!(
+            r#"{{"contract":"{}","version":"1.0.0"}}"#,
+            "a".repeat(MAX_CONTRACT_INFO_LENGTH)
+        );
+        assert_eq!(parse_contract_version(Some(long.as_bytes())), None);
+    }
+
+    pub fn test_compare_versions() {
+        let ordered = [
+            "0.9.9",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.2.0",
+            "1.10.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(compare_versions(pair[0], pair[1]), Some(Ordering::Less));
+            assert_eq!(compare_versions(pair[1], pair[0]), Some(Ordering::Greater));
+        }
+        assert_eq!(
+            compare_versions("1.0.0+build.1", "1.0.0"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(compare_versions("v1.0.0", "1.0.0"), None);
+        assert_eq!(compare_versions("1.0", "1.0.0"), None);
+    }
+
+    pub fn test_check_version_upgrade() {
+        let old = version("cw20", "1.1.0");
+        check_version_upgrade(Some(&old), Some(&version("cw20", "1.2.0"))).unwrap();
+        check_version_upgrade(Some(&old), Some(&version("cw20", "1.1.0"))).unwrap();
+        check_version_upgrade(Some(&old), Some(&version("other", "0.1.0"))).unwrap();
+        check_version_upgrade(Some(&old), Some(&version("cw20", "latest"))).unwrap();
+        check_version_upgrade(None, Some(&old)).unwrap();
+        check_version_upgrade(Some(&old), None).unwrap();
+        assert!(matches!(
+            check_version_upgrade(Some(&old), Some(&version("cw20", "1.0.9"))),
+            Err(EnclaveError::ContractVersionDowngrade)
+        ));
+    }
+}
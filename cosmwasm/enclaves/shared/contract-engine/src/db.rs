@@ -10,7 +10,9 @@ use enclave_ffi_types::{Ctx, EnclaveBuffer, OcallReturn, UntrustedVmError};
 
 use enclave_crypto::{sha_256, AESKey, Kdf, SIVEncryptable, KEY_MANAGER};
 
+use crate::contract_key_cache;
 use crate::external::{ecalls, ocalls};
+use crate::state_compression::{compress_state_value, decompress_state_value};
 
 use enclave_utils::kv_cache::KvCache;
 
@@ -38,6 +40,14 @@ struct EncryptedValue {
     pub data: Vec<u8>,
 }
 
+/// The optional storage features a contract opted in to with custom sections of its code
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StateOptions {
+    /// Whether values are compressed when written, see the `state_compression` module. Compressed
+    /// values are read whether it is set or not.
+    pub compression: bool,
+}
+
 pub fn write_multiple_keys(
     context: &Ctx,
     keys: Vec<(Vec<u8>, Vec<u8>)>,
@@ -82,6 +92,7 @@ fn write_to_encrypted_state(
     context: &Ctx,
     contract_key: &ContractKey,
     encryption_salt: &[u8],
+    state_options: &StateOptions,
 ) -> Result<u64, WasmEngineError> {
     // Get the state key from the key manager
 
@@ -91,6 +102,7 @@ fn write_to_encrypted_state(
         context,
         contract_key,
         encryption_salt,
+        state_options,
     )?;
 
     // Write the new data as concat(ad, encrypted_val)
@@ -112,6 +124,7 @@ pub fn create_encrypted_key_value(
     context: &Ctx,
     contract_key: &ContractKey,
     encryption_salt: &[u8],
+    state_options: &StateOptions,
 ) -> Result<(Vec<u8>, u64, Vec<u8>), WasmEngineError> {
     let scrambled_field_name = field_name_digest(plaintext_key, contract_key);
     let gas_used_remove = remove_db(context, &scrambled_field_name).map_err(|err| {
@@ -130,14 +143,17 @@ pub fn create_encrypted_key_value(
     };
     let encrypted_key_bytes = bincode2::serialize(&encrypted_key).unwrap();
 
+    let (encryption_salt, plaintext_value) =
+        compress_state_value(plaintext_value, encryption_salt, state_options.compression);
+
     let encrypted_value = EncryptedValue {
-        salt: encryption_salt.to_vec(),
         data: encrypt_value_new(
             &encrypted_key.data,
-            plaintext_value,
+            &plaintext_value,
             contract_key,
-            encryption_salt,
+            &encryption_salt,
         )?,
+        salt: encryption_salt,
     };
     let encrypted_value_bytes = bincode2::serialize(&encrypted_value).unwrap();
 
@@ -149,6 +165,12 @@ pub fn create_encrypted_key_value(
     Ok((encrypted_key_bytes, gas_used_remove, encrypted_value_bytes))
 }
 
+/// Returns the value at `plaintext_key`, the gas the node charged for reading it and the number of
+/// bytes that were decompressed to read it, which the caller charges for.
+///
+/// Values in older formats are rewritten in the current one if `has_write_permissions` is set,
+/// without compressing them, so that compression is only ever charged to the write that asked for
+/// it.
 pub fn read_from_encrypted_state(
     plaintext_key: &[u8],
     context: &Ctx,
@@ -156,50 +178,57 @@ pub fn read_from_encrypted_state(
     has_write_permissions: bool,
     kv_cache: &mut KvCache,
     encryption_salt: &[u8],
-) -> Result<(Option<Vec<u8>>, u64), WasmEngineError> {
+) -> Result<(Option<Vec<u8>>, u64, usize), WasmEngineError> {
     // Try reading with the new encryption format
-    let encrypted_key = EncryptedKey {
-        magic_bytes: ENCRYPTED_KEY_MAGIC_BYTES.to_vec(),
-        consensus_seed_version: CONSENSUS_SEED_VERSION,
-        state_encryption_version: STATE_ENCRYPTION_VERSION,
-        data: encrypt_key_new(plaintext_key, contract_key)?,
-    };
-    let encrypted_key_bytes = bincode2::serialize(&encrypted_key).unwrap();
+    let (mut maybe_plaintext_value, gas_used_first_read, mut decompressed_size) =
+        read_encrypted_value(
+            plaintext_key,
+            context,
+            &get_symmetrical_key_new(contract_key),
+        )?;
 
-    let mut maybe_plaintext_value: Option<Vec<u8>>;
-    let gas_used_first_read: u64;
-    (maybe_plaintext_value, gas_used_first_read) = match read_db(context, &encrypted_key_bytes) {
-        Ok((maybe_encrypted_value_bytes, gas_used)) => match maybe_encrypted_value_bytes {
-            Some(encrypted_value_bytes) => {
-                let encrypted_value: EncryptedValue = bincode2::deserialize(&encrypted_value_bytes).map_err(|err| {
-                    warn!(
-                        "read_db() got an error while trying to read_from_encrypted_state the value {:?} for key {:?}, stopping wasm: {:?}",
-                        encrypted_value_bytes,
-                        encrypted_key_bytes,
-                        err.to_string()
-                    );
-                    WasmEngineError::DecryptionError
-                })?;
-
-                match decrypt_value_new(
-                    &encrypted_key.data,
-                    &encrypted_value.data,
+    if let Some(plaintext_value) = maybe_plaintext_value {
+        return Ok((
+            Some(plaintext_value),
+            gas_used_first_read,
+            decompressed_size,
+        ));
+    }
+
+    let rewrite_options = StateOptions { compression: false };
+
+    // Key doesn't exist, try reading with the seeds that were current before each rotation, the
+    // most recent first
+    let mut gas_used_previous_reads = 0;
+    for previous_key in get_previous_symmetrical_keys(contract_key) {
+        let gas_used_previous_read;
+        (
+            maybe_plaintext_value,
+            gas_used_previous_read,
+            decompressed_size,
+        ) = read_encrypted_value(plaintext_key, context, &previous_key)?;
+        gas_used_previous_reads += gas_used_previous_read;
+
+        if let Some(plaintext_value) = maybe_plaintext_value {
+            let mut gas_used_write = 0;
+            if has_write_permissions {
+                // Key exists encrypted with the previous seed, rewriting with the current one
+                gas_used_write += remove_encrypted_value(plaintext_key, context, &previous_key)?;
+                gas_used_write += write_to_encrypted_state(
+                    plaintext_key,
+                    &plaintext_value,
+                    context,
                     contract_key,
-                    &encrypted_value.salt,
-                ) {
-                    Ok(plaintext_value) => Ok((Some(plaintext_value), gas_used)),
-                    // This error case is why we have all the matches here.
-                    // If we successfully collected a value, but failed to decrypt it, then we propagate that error.
-                    Err(err) => Err(err),
-                }
+                    encryption_salt,
+                    &rewrite_options,
+                )?;
             }
-            None => Ok((None, gas_used)),
-        },
-        Err(err) => Err(err),
-    }?;
-
-    if let Some(plaintext_value) = maybe_plaintext_value {
-        return Ok((Some(plaintext_value), gas_used_first_read));
+            return Ok((
+                Some(plaintext_value),
+                gas_used_first_read + gas_used_previous_reads + gas_used_write,
+                decompressed_size,
+            ));
+        }
     }
 
     // Key doesn't exist, try reading with the old encryption format
@@ -239,16 +268,73 @@ pub fn read_from_encrypted_state(
                 context,
                 contract_key,
                 encryption_salt,
+                &rewrite_options,
             )?;
         }
     }
 
     Ok((
         maybe_plaintext_value,
-        gas_used_first_read + gas_used_second_read + gas_used_write,
+        gas_used_first_read + gas_used_previous_reads + gas_used_second_read + gas_used_write,
+        0,
     ))
 }
 
+/// Reads the value at `plaintext_key` stored in the new format with `state_key`
+fn read_encrypted_value(
+    plaintext_key: &[u8],
+    context: &Ctx,
+    state_key: &AESKey,
+) -> Result<(Option<Vec<u8>>, u64, usize), WasmEngineError> {
+    let encrypted_key = encrypt_key_with(plaintext_key, state_key)?;
+    let encrypted_key_bytes = serialize_encrypted_key(encrypted_key.clone());
+
+    let (maybe_encrypted_value_bytes, gas_used) = read_db(context, &encrypted_key_bytes)?;
+    let encrypted_value_bytes = match maybe_encrypted_value_bytes {
+        Some(encrypted_value_bytes) => encrypted_value_bytes,
+        None => return Ok((None, gas_used, 0)),
+    };
+
+    let encrypted_value: EncryptedValue = bincode2::deserialize(&encrypted_value_bytes).map_err(|err| {
+        warn!(
+            "read_db() got an error while trying to read_from_encrypted_state the value {:?} for key {:?}, stopping wasm: {:?}",
+            encrypted_value_bytes,
+            encrypted_key_bytes,
+            err.to_string()
+        );
+        WasmEngineError::DecryptionError
+    })?;
+
+    // If we successfully collected a value, but failed to decrypt it, then we propagate that error.
+    let (plaintext_value, decompressed) =
+        decrypt_stored_value(&encrypted_key, &encrypted_value, state_key)?;
+    let decompressed_size = if decompressed {
+        plaintext_value.len()
+    } else {
+        0
+    };
+    Ok((Some(plaintext_value), gas_used, decompressed_size))
+}
+
+/// Removes the value at `plaintext_key` stored in the new format with `state_key`
+fn remove_encrypted_value(
+    plaintext_key: &[u8],
+    context: &Ctx,
+    state_key: &AESKey,
+) -> Result<u64, WasmEngineError> {
+    let encrypted_key_bytes = serialize_encrypted_key(encrypt_key_with(plaintext_key, state_key)?);
+
+    let gas_used_remove = remove_db(context, &encrypted_key_bytes).map_err(|err| {
+        warn!(
+            "remove_db() got an error from ocall_remove_db on new key remove, stopping wasm: {:?}",
+            err
+        );
+        err
+    })?;
+
+    Ok(gas_used_remove)
+}
+
 pub fn remove_from_encrypted_state(
     plaintext_key: &[u8],
     context: &Ctx,
@@ -271,23 +357,69 @@ pub fn remove_from_encrypted_state(
     })?;
 
     // Remove key with new format
+    let gas_used_second_remove = remove_encrypted_value(
+        plaintext_key,
+        context,
+        &get_symmetrical_key_new(contract_key),
+    )?;
+
+    // Remove keys encrypted with the seeds that were current before each rotation, so that they
+    // don't show through on the next read
+    let mut gas_used_previous_remove = 0;
+    for previous_key in get_previous_symmetrical_keys(contract_key) {
+        gas_used_previous_remove += remove_encrypted_value(plaintext_key, context, &previous_key)?;
+    }
+
+    Ok(gas_used_first_remove + gas_used_second_remove + gas_used_previous_remove)
+}
+
+/// Decrypts a key of the state of the contract with `contract_key`, as it is stored. Returns `None`
+/// for keys in the old format, which are hashes of the plaintext key.
+pub fn decrypt_state_key(stored_key: &[u8], contract_key: &ContractKey) -> Option<Vec<u8>> {
+    let encrypted_key: EncryptedKey = bincode2::deserialize(stored_key).ok()?;
+    if encrypted_key.magic_bytes != ENCRYPTED_KEY_MAGIC_BYTES {
+        return None;
+    }
+
+    get_symmetrical_key_new(contract_key)
+        .decrypt_siv(&encrypted_key.data, Some(&[]))
+        .ok()
+        .or_else(|| {
+            get_previous_symmetrical_keys(contract_key)
+                .iter()
+                .find_map(|previous_key| {
+                    previous_key
+                        .decrypt_siv(&encrypted_key.data, Some(&[]))
+                        .ok()
+                })
+        })
+}
+
+fn serialize_encrypted_key(data: Vec<u8>) -> Vec<u8> {
     let encrypted_key = EncryptedKey {
         magic_bytes: ENCRYPTED_KEY_MAGIC_BYTES.to_vec(),
         consensus_seed_version: CONSENSUS_SEED_VERSION,
         state_encryption_version: STATE_ENCRYPTION_VERSION,
-        data: encrypt_key_new(plaintext_key, contract_key)?,
+        data,
     };
-    let encrypted_key_bytes = bincode2::serialize(&encrypted_key).unwrap();
+    bincode2::serialize(&encrypted_key).unwrap()
+}
 
-    let gas_used_second_remove = remove_db(context, &encrypted_key_bytes).map_err(|err| {
-        warn!(
-            "remove_db() got an error from ocall_remove_db on new key remove, stopping wasm: {:?}",
-            err
-        );
-        err
-    })?;
+/// Decrypts a value stored in the new format, decompressing it as needed. Also returns whether it
+/// was decompressed.
+fn decrypt_stored_value(
+    encrypted_key: &[u8],
+    encrypted_value: &EncryptedValue,
+    state_key: &AESKey,
+) -> Result<(Vec<u8>, bool), WasmEngineError> {
+    let plaintext_value = decrypt_value_new(
+        encrypted_key,
+        &encrypted_value.data,
+        state_key,
+        &encrypted_value.salt,
+    )?;
 
-    Ok(gas_used_first_remove + gas_used_second_remove)
+    decompress_state_value(plaintext_value, &encrypted_value.salt)
 }
 
 fn field_name_digest(field_name: &[u8], contract_key: &ContractKey) -> [u8; 32] {
@@ -427,10 +559,23 @@ fn get_symmetrical_key_old(field_name: &[u8], contract_key: &ContractKey) -> AES
 }
 
 fn get_symmetrical_key_new(contract_key: &ContractKey) -> AESKey {
-    let consensus_state_ikm: SeedsHolder<AESKey> = KEY_MANAGER.get_consensus_state_ikm().unwrap();
-    consensus_state_ikm
-        .current
-        .derive_key_from_this(contract_key)
+    contract_key_cache::state_key(contract_key, || {
+        let consensus_state_ikm: SeedsHolder<AESKey> =
+            KEY_MANAGER.get_consensus_state_ikm().unwrap();
+        consensus_state_ikm
+            .current
+            .derive_key_from_this(contract_key)
+    })
+}
+
+/// The state keys of the contract under the seeds that were current before each seed rotation,
+/// the most recent first. Values written before a rotation are encrypted with one of them.
+fn get_previous_symmetrical_keys(contract_key: &ContractKey) -> Vec<AESKey> {
+    KEY_MANAGER
+        .get_previous_consensus_state_ikms()
+        .iter()
+        .map(|ikm| ikm.derive_key_from_this(contract_key))
+        .collect()
 }
 
 fn encrypt_value_new(
@@ -457,11 +602,9 @@ fn encrypt_value_new(
 fn decrypt_value_new(
     encrypted_key: &[u8],
     encrypted_value: &[u8],
-    contract_key: &ContractKey,
+    decryption_key: &AESKey,
     encryption_salt: &[u8],
 ) -> Result<Vec<u8>, WasmEngineError> {
-    let decryption_key = get_symmetrical_key_new(contract_key);
-
     decryption_key.decrypt_siv(encrypted_value, Some(&[encrypted_key, encryption_salt])).map_err(|err| {
         warn!(
             "read_db() got an error while trying to decrypt_value_new the value {:?} for key {:?}, stopping wasm: {:?}",
@@ -477,8 +620,13 @@ fn encrypt_key_new(
     plaintext_state_key: &[u8],
     contract_key: &ContractKey,
 ) -> Result<Vec<u8>, WasmEngineError> {
-    let encryption_key = get_symmetrical_key_new(contract_key);
+    encrypt_key_with(plaintext_state_key, &get_symmetrical_key_new(contract_key))
+}
 
+fn encrypt_key_with(
+    plaintext_state_key: &[u8],
+    encryption_key: &AESKey,
+) -> Result<Vec<u8>, WasmEngineError> {
     encryption_key
         .encrypt_siv(plaintext_state_key, Some(&[]))
         .map_err(|err| {
@@ -0,0 +1,134 @@
+//! Selective disclosure of contract state to an auditor.
+//!
+//! A contract can decide, in its own code, to disclose some of its state keys to an auditor. The
+//! enclave reads the plaintext values, encrypts them to the auditor's public key and signs the
+//! result, so the auditor can prove the values came from the contract's real state at that time
+//! without the network ever decrypting anything else.
+//!
+//! The values are encrypted with AES-SIV under a key derived with HKDF-SHA256, with the same salt
+//! as transaction encryption keys and `DISCLOSURE_ENCRYPTION_KEY_LABEL` as info, from the
+//! Diffie-Hellman secret of the current consensus io exchange keypair and the auditor's key. The
+//! auditor derives the same secret from its private key and the io exchange public key, which is
+//! already public.
+//!
+//! The bundle is signed with an ed25519 key derived from the current consensus seed, so every
+//! node produces the same signature for the same disclosure. Nodes publish its public key in their
+//! vm capabilities, so auditors can check the signer of a bundle.
+
+use cw_types_v010::encoding::Binary;
+use enclave_crypto::{hkdf_sha_256, sha_256, SIVEncryptable, KEY_MANAGER, PUBLIC_KEY_SIZE};
+use enclave_ffi_types::EnclaveError;
+use log::*;
+use serde::Serialize;
+
+use crate::contract_validation::ContractKey;
+
+pub const DISCLOSURE_BUNDLE_VERSION: u8 = 1;
+
+/// Separates the key disclosed entries are encrypted with from other uses of the Diffie-Hellman
+/// secret of the io exchange keypair
+const DISCLOSURE_ENCRYPTION_KEY_LABEL: &[u8] = b"secret_disclosure_encryption_key";
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DisclosedEntry {
+    pub key: Binary,
+    /// `None` if the key is not set in the contract's state
+    pub value: Option<Binary>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DisclosureBundle {
+    pub version: u8,
+    pub contract_key: Binary,
+    pub timestamp: u64,
+    pub auditor_public_key: Binary,
+    /// The consensus io exchange public key the entries were encrypted with
+    pub encryption_public_key: Binary,
+    /// AES-SIV encrypted json of `Vec<DisclosedEntry>`
+    pub encrypted_entries: Binary,
+    pub signer_public_key: Binary,
+    /// ed25519 signature over `signing_bytes()`
+    pub signature: Binary,
+}
+
+impl DisclosureBundle {
+    /// The bytes covered by the enclave signature: the sha256 of every other field, in order
+    fn signing_bytes(&self) -> [u8; 32] {
+        let mut data = vec![self.version];
+        data.extend_from_slice(self.contract_key.as_slice());
+        data.extend_from_slice(&self.timestamp.to_be_bytes());
+        data.extend_from_slice(self.auditor_public_key.as_slice());
+        data.extend_from_slice(self.encryption_public_key.as_slice());
+        data.extend_from_slice(self.encrypted_entries.as_slice());
+        data.extend_from_slice(self.signer_public_key.as_slice());
+        sha_256(&data)
+    }
+}
+
+fn disclosure_signing_key() -> Result<ed25519_zebra::SigningKey, EnclaveError> {
+    let secret = KEY_MANAGER
+        .get_disclosure_signing_secret()
+        .map_err(|_| EnclaveError::FailedUnseal)?;
+    Ok(ed25519_zebra::SigningKey::from(*secret.get()))
+}
+
+/// The public key disclosure bundles are signed with, the same on every node
+pub fn disclosure_signer_public_key() -> Result<[u8; 32], EnclaveError> {
+    Ok(ed25519_zebra::VerificationKey::from(&disclosure_signing_key()?).into())
+}
+
+/// Encrypts `entries` to `auditor_public_key` and returns the signed bundle, serialized as json.
+pub fn create_disclosure_bundle(
+    contract_key: &ContractKey,
+    timestamp: u64,
+    auditor_public_key: &[u8; PUBLIC_KEY_SIZE],
+    entries: Vec<DisclosedEntry>,
+) -> Result<Vec<u8>, EnclaveError> {
+    let io_keypair = KEY_MANAGER
+        .get_consensus_io_exchange_keypair()
+        .map_err(|_| EnclaveError::FailedUnseal)?
+        .current;
+    let encryption_key = hkdf_sha_256(
+        &io_keypair.diffie_hellman(auditor_public_key),
+        &[DISCLOSURE_ENCRYPTION_KEY_LABEL],
+    );
+
+    let plaintext = serde_json::to_vec(&entries).map_err(|err| {
+        warn!(
+            "got an error while serializing disclosed entries: {:?}",
+            err
+        );
+        EnclaveError::FailedToSerialize
+    })?;
+
+    // Bind the ciphertext to the contract and time of the disclosure
+    let timestamp_bytes = timestamp.to_be_bytes();
+    let ad: Vec<&[u8]> = vec![&contract_key[..], &timestamp_bytes];
+    let encrypted_entries = encryption_key
+        .encrypt_siv(&plaintext, Some(&ad))
+        .map_err(|_| EnclaveError::EncryptionError)?;
+
+    let signing_key = disclosure_signing_key()?;
+    let signer_public_key: [u8; 32] = ed25519_zebra::VerificationKey::from(&signing_key).into();
+
+    let mut bundle = DisclosureBundle {
+        version: DISCLOSURE_BUNDLE_VERSION,
+        contract_key: Binary(contract_key.to_vec()),
+        timestamp,
+        auditor_public_key: Binary(auditor_public_key.to_vec()),
+        encryption_public_key: Binary(io_keypair.get_pubkey().to_vec()),
+        encrypted_entries: Binary(encrypted_entries),
+        signer_public_key: Binary(signer_public_key.to_vec()),
+        signature: Binary(vec![]),
+    };
+    let signature: [u8; 64] = signing_key.sign(&bundle.signing_bytes()).into();
+    bundle.signature = Binary(signature.to_vec());
+
+    serde_json::to_vec(&bundle).map_err(|err| {
+        warn!(
+            "got an error while serializing disclosure bundle: {:?}",
+            err
+        );
+        EnclaveError::FailedToSerialize
+    })
+}
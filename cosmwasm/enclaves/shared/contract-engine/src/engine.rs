@@ -0,0 +1,123 @@
+//! The interface between the contract engine and the interpreter that runs contracts inside the
+//! enclave, so that other interpreters can be tried behind a feature flag, as `wasm3` is.
+//!
+//! Contract code is instrumented for gas metering before it reaches the interpreter (see
+//! `wasm3::gas`), so the gas left is kept in globals of the contract and every interpreter meters
+//! it the same way. Host functions are still linked with the API of each interpreter.
+//!
+//! `tests` holds a conformance suite that every implementation has to pass.
+
+use enclave_ffi_types::EnclaveError;
+
+use crate::errors::WasmEngineResult;
+
+/// An interpreter able to instantiate contracts.
+pub trait WasmEngine {
+    /// The error returned by a failed or trapped call
+    type Error;
+
+    /// Instantiates `code` and runs `func` with the instance, which lives until `func` returns.
+    fn with_instance<C, R, F>(&self, code: &[u8], func: F) -> Result<R, EnclaveError>
+    where
+        C: 'static,
+        F: FnOnce(&mut dyn WasmInstance<C, Error = Self::Error>) -> R;
+}
+
+/// A contract instantiated by a `WasmEngine`, passing `C` to the host functions it calls.
+pub trait WasmInstance<C> {
+    type Error;
+
+    /// Calls the export `name` with up to three `i32` arguments, returning an `i32` as contract
+    /// entry points do.
+    fn call(&self, context: &mut C, name: &str, args: &[u32]) -> Result<u32, Self::Error>;
+
+    /// The size of the memory of the contract, in bytes
+    fn memory_size(&self) -> usize;
+
+    fn read_memory(&self, offset: u32, len: usize) -> WasmEngineResult<Vec<u8>>;
+
+    fn write_memory(&self, offset: u32, data: &[u8]) -> WasmEngineResult<()>;
+
+    fn set_gas_limit(&self, gas_limit: u64) -> Result<(), EnclaveError>;
+
+    fn remaining_gas(&self) -> u64;
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::WasmEngine;
+
+    /// A module with one page of memory, exporting `add(a, b)` and `load(address)`, which returns
+    /// the byte at `address`:
+    ///
+    /// (memory (export "memory") 1)
+    /// (func (export "add") (param i32 i32) (result i32)
+    ///   local.get 0
+    ///   local.get 1
+    ///   i32.add)
+    /// (func (export "load") (param i32) (result i32)
+    ///   local.get 0
+    ///   i32.load8_u)
+    #[rustfmt::skip]
+    pub const CONFORMANCE_WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic bytes, binary version
+        // type section: (i32, i32) -> i32, (i32) -> i32
+        0x01, 0x0c, 0x02, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, 0x60, 0x01, 0x7f, 0x01, 0x7f,
+        0x03, 0x03, 0x02, 0x00, 0x01, // function section: functions of type 0 and 1
+        0x05, 0x03, 0x01, 0x00, 0x01, // memory section: one memory of at least 1 page
+        0x07, 0x17, 0x03, // export section: 3 exports
+        0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00,
+        0x03, b'a', b'd', b'd', 0x00, 0x00,
+        0x04, b'l', b'o', b'a', b'd', 0x00, 0x01,
+        0x0a, 0x11, 0x02, // code section: 2 bodies
+        0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b, // local.get 0, local.get 1, i32.add
+        0x07, 0x00, 0x20, 0x00, 0x2d, 0x00, 0x00, 0x0b, // local.get 0, i32.load8_u
+    ];
+
+    pub fn calls_exports<E: WasmEngine>(engine: &E) {
+        engine
+            .with_instance::<(), _, _>(CONFORMANCE_WASM, |instance| {
+                assert!(matches!(instance.call(&mut (), "add", &[2, 3]), Ok(5)));
+                assert!(instance.call(&mut (), "missing", &[]).is_err());
+                assert!(instance.call(&mut (), "add", &[1, 2, 3, 4]).is_err());
+            })
+            .unwrap();
+    }
+
+    pub fn reads_and_writes_memory<E: WasmEngine>(engine: &E) {
+        engine
+            .with_instance::<(), _, _>(CONFORMANCE_WASM, |instance| {
+                assert_eq!(instance.memory_size(), 64 * 1024);
+
+                instance.write_memory(100, &[7, 8]).unwrap();
+                assert_eq!(instance.read_memory(100, 2).unwrap(), vec![7, 8]);
+                assert!(matches!(instance.call(&mut (), "load", &[101]), Ok(8)));
+
+                assert!(instance.write_memory(64 * 1024 - 1, &[1, 2]).is_err());
+                assert!(instance.read_memory(64 * 1024 - 1, 2).is_err());
+            })
+            .unwrap();
+    }
+
+    /// `metered_wasm` is `CONFORMANCE_WASM` instrumented for gas metering
+    pub fn meters_gas<E: WasmEngine>(engine: &E, metered_wasm: &[u8]) {
+        engine
+            .with_instance::<(), _, _>(metered_wasm, |instance| {
+                instance.set_gas_limit(1_000_000).unwrap();
+                assert!(matches!(instance.call(&mut (), "add", &[2, 3]), Ok(5)));
+                let remaining = instance.remaining_gas();
+                assert!(remaining < 1_000_000);
+
+                assert!(matches!(instance.call(&mut (), "add", &[2, 3]), Ok(5)));
+                assert_eq!(
+                    1_000_000 - remaining,
+                    remaining - instance.remaining_gas(),
+                    "the same call must use the same gas"
+                );
+
+                instance.set_gas_limit(0).unwrap();
+                assert!(instance.call(&mut (), "add", &[2, 3]).is_err());
+            })
+            .unwrap();
+    }
+}
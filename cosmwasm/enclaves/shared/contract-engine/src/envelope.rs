@@ -0,0 +1,224 @@
+//! The header of encrypted messages, which tells which algorithms encrypted them.
+//!
+//! v1 messages have no header: they're `nonce(32) || user_public_key(32) || ciphertext`, with a
+//! key agreed using X25519 and encrypted with AES-SIV. Old clients keep sending them.
+//!
+//! v2 messages start with `ENVELOPE_V2_MAGIC || key_agreement(1) || aead(1) || encapsulated_key`,
+//! followed by the same fields as v1. `encapsulated_key` is only set by key agreements that use a
+//! KEM. A v1 message is only mistaken for a v2 message if its random nonce starts with the 8 bytes
+//! of the magic, which is negligible.
+//!
+//! A new cipher suite is rolled out by adding its algorithm identifiers here, with the consensus
+//! seed epoch from which the enclave accepts it.
+
+use log::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "pq-hybrid")]
+use enclave_crypto::kyber::KYBER_CIPHERTEXTBYTES;
+use enclave_crypto::KEY_MANAGER;
+use enclave_ffi_types::EnclaveError;
+
+/// Starts the header of v2 messages. The last byte is the version.
+pub const ENVELOPE_V2_MAGIC: [u8; 8] = *b"SCRTENV\x02";
+const V2_HEADER_LENGTH: usize = ENVELOPE_V2_MAGIC.len() + 2;
+
+/// How the encryption key of a message is agreed between the user and the enclave
+#[repr(u8)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyAgreement {
+    /// X25519 with the consensus io exchange key, then HKDF-SHA256 with the nonce
+    X25519 = 1,
+    /// Experimental: X25519 as above, and a Kyber768 secret encapsulated for the consensus io
+    /// Kyber key, both fed to HKDF-SHA256 with the nonce
+    #[cfg(feature = "pq-hybrid")]
+    X25519Kyber768 = 2,
+}
+
+impl KeyAgreement {
+    fn encapsulated_key_length(&self) -> usize {
+        match self {
+            KeyAgreement::X25519 => 0,
+            #[cfg(feature = "pq-hybrid")]
+            KeyAgreement::X25519Kyber768 => KYBER_CIPHERTEXTBYTES,
+        }
+    }
+}
+
+/// How a message is encrypted with the agreed key
+#[repr(u8)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Aead {
+    AesSiv = 1,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CipherSuite {
+    pub key_agreement: KeyAgreement,
+    pub aead: Aead,
+}
+
+impl CipherSuite {
+    /// The cipher suite of v1 messages
+    pub const V1: CipherSuite = CipherSuite {
+        key_agreement: KeyAgreement::X25519,
+        aead: Aead::AesSiv,
+    };
+
+    fn from_ids(key_agreement: u8, aead: u8) -> Option<Self> {
+        let key_agreement = match key_agreement {
+            1 => KeyAgreement::X25519,
+            #[cfg(feature = "pq-hybrid")]
+            2 => KeyAgreement::X25519Kyber768,
+            _ => return None,
+        };
+        let aead = match aead {
+            1 => Aead::AesSiv,
+            _ => return None,
+        };
+        Some(CipherSuite {
+            key_agreement,
+            aead,
+        })
+    }
+
+    /// The first consensus seed epoch in which the enclave accepts this cipher suite
+    fn activation_seed_id(&self) -> u16 {
+        match (self.key_agreement, self.aead) {
+            (KeyAgreement::X25519, Aead::AesSiv) => 0,
+            #[cfg(feature = "pq-hybrid")]
+            (KeyAgreement::X25519Kyber768, Aead::AesSiv) => 0,
+        }
+    }
+}
+
+/// The version and cipher suite of an encrypted message
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Envelope {
+    V1,
+    V2 {
+        cipher_suite: CipherSuite,
+        /// The secret encapsulated by the user, for key agreements that use a KEM
+        encapsulated_key: Vec<u8>,
+    },
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Envelope::V1
+    }
+}
+
+impl Envelope {
+    pub fn cipher_suite(&self) -> CipherSuite {
+        match self {
+            Envelope::V1 => CipherSuite::V1,
+            Envelope::V2 { cipher_suite, .. } => *cipher_suite,
+        }
+    }
+
+    pub fn encapsulated_key(&self) -> &[u8] {
+        match self {
+            Envelope::V1 => &[],
+            Envelope::V2 {
+                encapsulated_key, ..
+            } => encapsulated_key,
+        }
+    }
+
+    /// Splits the header off `msg`, and checks that its cipher suite is accepted in the current
+    /// consensus seed epoch
+    pub fn parse(msg: &[u8]) -> Result<(Self, &[u8]), EnclaveError> {
+        Self::parse_with_seed(msg, KEY_MANAGER.get_consensus_seed_id())
+    }
+
+    fn parse_with_seed(msg: &[u8], seed_id: u16) -> Result<(Self, &[u8]), EnclaveError> {
+        if !msg.starts_with(&ENVELOPE_V2_MAGIC) {
+            return Ok((Envelope::V1, msg));
+        }
+
+        if msg.len() < V2_HEADER_LENGTH {
+            warn!("Encrypted message header is truncated");
+            return Err(EnclaveError::DecryptionError);
+        }
+        let key_agreement = msg[ENVELOPE_V2_MAGIC.len()];
+        let aead = msg[ENVELOPE_V2_MAGIC.len() + 1];
+
+        let cipher_suite = CipherSuite::from_ids(key_agreement, aead).ok_or_else(|| {
+            warn!(
+                "Unknown cipher suite in encrypted message header: key agreement {} aead {}",
+                key_agreement, aead
+            );
+            EnclaveError::DecryptionError
+        })?;
+        if seed_id < cipher_suite.activation_seed_id() {
+            warn!(
+                "Cipher suite {:?} isn't enabled in consensus seed epoch {}",
+                cipher_suite, seed_id
+            );
+            return Err(EnclaveError::DecryptionError);
+        }
+
+        let header_length = V2_HEADER_LENGTH + cipher_suite.key_agreement.encapsulated_key_length();
+        if msg.len() < header_length {
+            warn!("Encrypted message encapsulated key is truncated");
+            return Err(EnclaveError::DecryptionError);
+        }
+
+        let envelope = Envelope::V2 {
+            cipher_suite,
+            encapsulated_key: msg[V2_HEADER_LENGTH..header_length].to_vec(),
+        };
+        Ok((envelope, &msg[header_length..]))
+    }
+
+    pub fn header(&self) -> Vec<u8> {
+        match self {
+            Envelope::V1 => vec![],
+            Envelope::V2 {
+                cipher_suite,
+                encapsulated_key,
+            } => {
+                let mut header = ENVELOPE_V2_MAGIC.to_vec();
+                header.push(cipher_suite.key_agreement as u8);
+                header.push(cipher_suite.aead as u8);
+                header.extend_from_slice(encapsulated_key);
+                header
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_envelope_header_round_trip() {
+        let body = [7u8; 80];
+        let v2 = Envelope::V2 {
+            cipher_suite: CipherSuite::V1,
+            encapsulated_key: vec![],
+        };
+        for envelope in &[Envelope::V1, v2] {
+            let mut msg = envelope.header();
+            msg.extend_from_slice(&body);
+
+            let (parsed, rest) = Envelope::parse_with_seed(&msg, 0).unwrap();
+            assert_eq!(&parsed, envelope);
+            assert_eq!(rest, &body[..]);
+        }
+    }
+
+    pub fn test_unknown_envelope_algorithms_are_rejected() {
+        let mut msg = ENVELOPE_V2_MAGIC.to_vec();
+        msg.extend_from_slice(&[1, 2]);
+        msg.extend_from_slice(&[7u8; 80]);
+        assert!(Envelope::parse_with_seed(&msg, 0).is_err());
+
+        msg[ENVELOPE_V2_MAGIC.len()] = 0;
+        msg[ENVELOPE_V2_MAGIC.len() + 1] = 1;
+        assert!(Envelope::parse_with_seed(&msg, 0).is_err());
+
+        assert!(Envelope::parse_with_seed(&ENVELOPE_V2_MAGIC, 0).is_err());
+    }
+}
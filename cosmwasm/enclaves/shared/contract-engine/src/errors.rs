@@ -34,6 +34,8 @@ pub enum WasmEngineError {
     MemoryWriteError,
     /// The contract attempted to write to storage during a query
     UnauthorizedWrite,
+    /// The contract touched a key of its store outside the access list of the execution
+    AccessListViolation,
 
     /// The contract tried calling an unrecognized function
     NonExistentImportFunction,
@@ -58,6 +60,7 @@ impl From<WasmEngineError> for EnclaveError {
             MemoryReadError => EnclaveError::MemoryReadError,
             MemoryWriteError => EnclaveError::MemoryWriteError,
             UnauthorizedWrite => EnclaveError::UnauthorizedWrite,
+            AccessListViolation => EnclaveError::AccessListViolation,
             HostMisbehavior => EnclaveError::HostMisbehavior,
             // Unexpected WasmEngineError variant
             _other => EnclaveError::Unknown,
@@ -0,0 +1,195 @@
+//! Checks of the events and attributes v1 contracts emit, before they are encrypted.
+//!
+//! Indexers and wallets trust the event types and attribute keys the chain itself emits. A contract
+//! could otherwise emit an event or a plaintext attribute that reads like one of them, e.g. an
+//! `ibc_transfer` event or a second `contract_address` attribute. Keys and types are compared after
+//! trimming whitespace and ignoring case, the way the node and most indexers normalize them.
+//!
+//! The key of an encrypted attribute only leaves the enclave as ciphertext, so it can't pass for a
+//! system attribute and only its length is checked. Types and keys were already checked to be UTF-8
+//! when the output was deserialized.
+
+use log::*;
+
+use cw_types_v010::types::LogAttribute;
+use cw_types_v1::results::Event;
+use enclave_ffi_types::EnclaveError;
+
+use crate::feature_gates::{FeatureGates, EVENT_VALIDATION};
+use crate::io::RawWasmOutput;
+
+/// Event types of the node and of IBC
+const RESERVED_EVENT_TYPES: &[&str] = &["wasm", "ibc"];
+/// Contract event types get a `wasm-` prefix from the node, but indexers matching on prefixes would
+/// still be fooled by e.g. `wasm-wasm`. Types like `wasmswap` or `ibcx` are left to contracts.
+const RESERVED_EVENT_TYPE_PREFIXES: &[&str] = &["wasm-", "ibc_"];
+/// Keys starting with `_` are reserved for the node
+const RESERVED_ATTRIBUTE_KEY_PREFIX: &str = "_";
+/// The attributes the node adds to contract events
+const SYSTEM_ATTRIBUTE_KEYS: &[&str] = &["contract_address", "code_id", "signer"];
+
+pub const MAX_EVENT_TYPE_LENGTH: usize = 128;
+pub const MAX_ATTRIBUTE_KEY_LENGTH: usize = 128;
+
+/// Checks the events and attributes of a v1 contract response, from the height governance activates
+/// the check at.
+///
+/// v0.10 contracts only emit attributes of the `wasm` event, whose `contract_address` the node
+/// already drops, so their output is left as is.
+pub fn validate_events(
+    output: &RawWasmOutput,
+    feature_gates: &FeatureGates,
+) -> Result<(), EnclaveError> {
+    if !feature_gates.is_active(EVENT_VALIDATION) {
+        return Ok(());
+    }
+
+    match output {
+        RawWasmOutput::OkV1 { ok, .. } => validate_response_events(&ok.attributes, &ok.events),
+        RawWasmOutput::OkIBCPacketReceive { ok } => {
+            validate_response_events(&ok.attributes, &ok.events)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_response_events(
+    attributes: &[LogAttribute],
+    events: &[Event],
+) -> Result<(), EnclaveError> {
+    for event in events {
+        validate_event_type(&event.ty)?;
+        event.attributes.iter().try_for_each(validate_attribute)?;
+    }
+    attributes.iter().try_for_each(validate_attribute)
+}
+
+fn validate_event_type(ty: &str) -> Result<(), EnclaveError> {
+    let ty = ty.trim();
+    if ty.len() > MAX_EVENT_TYPE_LENGTH {
+        debug!(
+            "event type of {} bytes exceeds the limit of {} bytes",
+            ty.len(),
+            MAX_EVENT_TYPE_LENGTH
+        );
+        return Err(EnclaveError::InvalidEvent);
+    }
+
+    let ty = ty.to_ascii_lowercase();
+    if RESERVED_EVENT_TYPES.contains(&&*ty) {
+        debug!("event type {:?} is reserved", ty);
+        return Err(EnclaveError::InvalidEvent);
+    }
+    if let Some(prefix) = RESERVED_EVENT_TYPE_PREFIXES
+        .iter()
+        .find(|prefix| ty.starts_with(*prefix))
+    {
+        debug!(
+            "event type {:?} starts with reserved prefix {:?}",
+            ty, prefix
+        );
+        return Err(EnclaveError::InvalidEvent);
+    }
+    Ok(())
+}
+
+fn validate_attribute(attribute: &LogAttribute) -> Result<(), EnclaveError> {
+    let key = attribute.key.trim();
+    if key.len() > MAX_ATTRIBUTE_KEY_LENGTH {
+        debug!(
+            "attribute key of {} bytes exceeds the limit of {} bytes",
+            key.len(),
+            MAX_ATTRIBUTE_KEY_LENGTH
+        );
+        return Err(EnclaveError::InvalidEvent);
+    }
+    if attribute.encrypted {
+        return Ok(());
+    }
+
+    let key = key.to_ascii_lowercase();
+    if key.starts_with(RESERVED_ATTRIBUTE_KEY_PREFIX) || SYSTEM_ATTRIBUTE_KEYS.contains(&&*key) {
+        debug!("plaintext attribute key {:?} is reserved", key);
+        return Err(EnclaveError::InvalidEvent);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+    use crate::feature_gates::extract_feature_gates;
+
+    fn attribute(key: &str, encrypted: bool) -> LogAttribute {
+        LogAttribute {
+            key: key.to_string(),
+            value: "value".to_string(),
+            encrypted,
+            ..Default::default()
+        }
+    }
+
+    pub fn test_reserved_event_types_are_rejected() {
+        validate_event_type("transfer").unwrap();
+        validate_event_type("wasmswap").unwrap();
+        validate_event_type("ibcx").unwrap();
+        validate_event_type(&"a".repeat(MAX_EVENT_TYPE_LENGTH)).unwrap();
+
+        for ty in &["wasm", " WASM ", "wasm-transfer", " IBC_transfer", "ibc"] {
+            assert!(matches!(
+                validate_event_type(ty),
+                Err(EnclaveError::InvalidEvent)
+            ));
+        }
+        assert!(matches!(
+            validate_event_type(&"a".repeat(MAX_EVENT_TYPE_LENGTH + 1)),
+            Err(EnclaveError::InvalidEvent)
+        ));
+    }
+
+    pub fn test_event_validation_is_gated() {
+        let mut ok = cw_types_v1::results::Response::default();
+        ok.events.push(Event {
+            ty: "wasm".to_string(),
+            attributes: vec![],
+        });
+        let output = RawWasmOutput::OkV1 {
+            ok,
+            internal_reply_enclave_sig: None,
+            internal_msg_id: None,
+        };
+
+        let inactive = extract_feature_gates(b"{}").unwrap();
+        validate_events(&output, &inactive).unwrap();
+
+        let active = extract_feature_gates(br#"{"active_features":["event_validation"]}"#).unwrap();
+        assert!(matches!(
+            validate_events(&output, &active),
+            Err(EnclaveError::InvalidEvent)
+        ));
+    }
+
+    pub fn test_reserved_plaintext_attribute_keys_are_rejected() {
+        validate_attribute(&attribute("amount", false)).unwrap();
+
+        for key in &[
+            "_contract_address",
+            " Contract_Address ",
+            "code_id",
+            "signer",
+        ] {
+            assert!(matches!(
+                validate_attribute(&attribute(key, false)),
+                Err(EnclaveError::InvalidEvent)
+            ));
+            // the key of encrypted attributes is never seen in plaintext
+            validate_attribute(&attribute(key, true)).unwrap();
+        }
+
+        let long_key = "a".repeat(MAX_ATTRIBUTE_KEY_LENGTH + 1);
+        assert!(matches!(
+            validate_attribute(&attribute(&long_key, true)),
+            Err(EnclaveError::InvalidEvent)
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::envelope::Envelope;
 use crate::message_utils::try_get_decrypted_secret_msg;
 use crate::types::{ParsedMessage, SecretMessage};
 use enclave_ffi_types::EnclaveError;
@@ -27,6 +28,7 @@ pub fn parse_execute_message(message: &[u8]) -> Result<ParsedMessage, EnclaveErr
         nonce: [0; 32],
         user_public_key: [0; 32],
         msg: message.into(),
+        envelope: Envelope::V1,
     };
 
     let decrypted_msg = secret_msg.msg.clone();
@@ -0,0 +1,412 @@
+//! Receipts of contract executions and signatures of query responses, signed by the enclave.
+//!
+//! Receipts let a node prove to off-chain parties that it executed a given message with a given
+//! result. Query response signatures let light clients trust the result of a query served by a
+//! node they don't run. Unlike disclosure bundles, both are signed with a key unique to the node:
+//! it is derived from the node's registration key, which never leaves the enclave.
+//!
+//! The node's attestation certificate commits to the public half of this key (see
+//! `node_signer_binding`), so its signatures can be traced back to a genuine registered enclave.
+//!
+//! Both are bound to the chain-id and the height of the last block the enclave verified, never to
+//! ones the host passes, so a node can't replay them across chains or claim another height. An
+//! enclave that doesn't verify blocks (built without `light-client-validation`) signs nothing.
+//!
+//! Contracts can also opt in to MACed query responses, by embedding an empty custom wasm section
+//! named `query_response_mac` in their code. The encryption of the response already authenticates
+//! it, but not when it was produced, so a node could serve an old response to the same query. The
+//! MAC binds the response to the chain-id and height of the last block the enclave verified, with
+//! a key the enclave derives from the key the query was encrypted with, which only the querier
+//! knows. The querier can so check how fresh the response is without trusting the node or the RPC
+//! infrastructure in between, and without verifying attestation certificates.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::*;
+use sgx_types::sgx_status_t;
+
+use enclave_crypto::{sha_256, AESKey, Hmac, KeyPair, KEY_MANAGER};
+use enclave_ffi_types::{ExecutionReceipt, QueryResponseSignature};
+
+use crate::external::ocalls;
+
+#[cfg(feature = "light-client-validation")]
+use block_verifier::VERIFIED_BLOCK_MESSAGES;
+
+/// Separates the receipt signing key from other keys derived from the registration key
+const RECEIPT_SIGNING_KEY_LABEL: &[u8] = b"secret_execution_receipt_signing_key";
+/// Separates the query response MAC key from other uses of the query encryption key
+const QUERY_RESPONSE_MAC_KEY_LABEL: &[u8] = b"secret_query_response_mac_key";
+
+pub const QUERY_RESPONSE_MAC_SECTION: &str = "query_response_mac";
+
+/// The length of the commitment to the node signing key in the attestation report data, which
+/// only has room for it after the registration key and the 4 byte challenge
+pub const NODE_SIGNER_BINDING_LENGTH: usize = 28;
+
+static SIGN_EXECUTION_RECEIPTS: AtomicBool = AtomicBool::new(false);
+static SIGN_QUERY_RESPONSES: AtomicBool = AtomicBool::new(false);
+
+pub fn configure_execution_receipts(sign_receipts: bool, sign_query_responses: bool) {
+    debug!(
+        "configuring execution receipts: {}, query response signatures: {}",
+        sign_receipts, sign_query_responses
+    );
+    SIGN_EXECUTION_RECEIPTS.store(sign_receipts, Ordering::SeqCst);
+    SIGN_QUERY_RESPONSES.store(sign_query_responses, Ordering::SeqCst);
+}
+
+/// The last block the enclave verified
+struct VerifiedBlock {
+    chain_id: String,
+    height: u64,
+}
+
+impl VerifiedBlock {
+    /// The bytes every signature starts with, binding it to the block
+    fn signing_prefix(&self, block_height: u64) -> Vec<u8> {
+        let mut data = sha_256(self.chain_id.as_bytes()).to_vec();
+        data.extend_from_slice(&block_height.to_be_bytes());
+        data
+    }
+}
+
+#[cfg(feature = "light-client-validation")]
+fn verified_block() -> Option<VerifiedBlock> {
+    let verified = VERIFIED_BLOCK_MESSAGES.lock().unwrap();
+    if verified.height() == 0 {
+        debug!("Not signing before the enclave verified a block");
+        return None;
+    }
+    Some(VerifiedBlock {
+        chain_id: verified.chain_id().to_string(),
+        height: verified.height(),
+    })
+}
+
+#[cfg(not(feature = "light-client-validation"))]
+fn verified_block() -> Option<VerifiedBlock> {
+    None
+}
+
+fn signing_key_of(registration_key: &KeyPair) -> ed25519_zebra::SigningKey {
+    let mut data = RECEIPT_SIGNING_KEY_LABEL.to_vec();
+    data.extend_from_slice(registration_key.get_privkey());
+    ed25519_zebra::SigningKey::from(sha_256(&data))
+}
+
+/// The key this node signs receipts, query responses and contract key proofs with
+pub(crate) fn node_signing_key() -> Option<ed25519_zebra::SigningKey> {
+    let registration_key = KEY_MANAGER
+        .get_registration_key()
+        .map_err(|err| warn!("Can't get the node signing key: {:?}", err))
+        .ok()?;
+    Some(signing_key_of(&registration_key))
+}
+
+/// The commitment to the node signing key that goes in the attestation report of
+/// `registration_key`: the first bytes of the sha256 of its public key
+pub fn node_signer_binding(registration_key: &KeyPair) -> [u8; NODE_SIGNER_BINDING_LENGTH] {
+    let public_key: [u8; 32] =
+        ed25519_zebra::VerificationKey::from(&signing_key_of(registration_key)).into();
+
+    let mut binding = [0u8; NODE_SIGNER_BINDING_LENGTH];
+    binding.copy_from_slice(&sha_256(&public_key)[..NODE_SIGNER_BINDING_LENGTH]);
+    binding
+}
+
+/// The bytes covered by the signature of a receipt
+fn signing_bytes(block: &VerifiedBlock, receipt: &ExecutionReceipt) -> [u8; 32] {
+    let mut data = block.signing_prefix(receipt.block_height);
+    data.extend_from_slice(&receipt.code_hash);
+    data.extend_from_slice(&receipt.msg_hash);
+    data.extend_from_slice(&receipt.result_hash);
+    data.extend_from_slice(&receipt.gas_used.to_be_bytes());
+    data.extend_from_slice(&receipt.signer_public_key);
+    sha_256(&data)
+}
+
+fn sign_receipt(
+    signing_key: &ed25519_zebra::SigningKey,
+    block: &VerifiedBlock,
+    code_hash: &[u8; 32],
+    msg: &[u8],
+    output: &[u8],
+    gas_used: u64,
+) -> ExecutionReceipt {
+    let mut receipt = ExecutionReceipt {
+        block_height: block.height,
+        code_hash: *code_hash,
+        msg_hash: sha_256(msg),
+        result_hash: sha_256(output),
+        gas_used,
+        signer_public_key: ed25519_zebra::VerificationKey::from(signing_key).into(),
+        signature: [0; 64],
+    };
+    receipt.signature = signing_key.sign(&signing_bytes(block, &receipt)).into();
+    receipt
+}
+
+/// Signs a receipt of an execution and sends it to the host, if the node enabled receipts.
+///
+/// Failing to produce a receipt doesn't fail the execution, since receipts are not part of
+/// consensus.
+pub fn report_execution_receipt(code_hash: &[u8; 32], msg: &[u8], output: &[u8], gas_used: u64) {
+    if !SIGN_EXECUTION_RECEIPTS.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let (signing_key, block) = match (node_signing_key(), verified_block()) {
+        (Some(signing_key), Some(block)) => (signing_key, block),
+        _ => return,
+    };
+    let receipt = sign_receipt(&signing_key, &block, code_hash, msg, output, gas_used);
+
+    let status = unsafe { ocalls::ocall_report_execution_receipt(receipt) };
+    if status != sgx_status_t::SGX_SUCCESS {
+        warn!("Failed to report execution receipt to the host: {}", status);
+    }
+}
+
+/// The bytes covered by the signature of a query response
+fn query_signing_bytes(block: &VerifiedBlock, signature: &QueryResponseSignature) -> [u8; 32] {
+    let mut data = block.signing_prefix(signature.block_height);
+    data.extend_from_slice(&signature.query_hash);
+    data.extend_from_slice(&signature.result_hash);
+    data.extend_from_slice(&signature.signer_public_key);
+    sha_256(&data)
+}
+
+fn sign_query_response(
+    signing_key: &ed25519_zebra::SigningKey,
+    block: &VerifiedBlock,
+    contract_address: &[u8],
+    query: &[u8],
+    output: &[u8],
+) -> QueryResponseSignature {
+    let mut signature = QueryResponseSignature {
+        signed: true,
+        block_height: block.height,
+        query_hash: query_hash(contract_address, query),
+        result_hash: sha_256(output),
+        signer_public_key: ed25519_zebra::VerificationKey::from(signing_key).into(),
+        signature: [0; 64],
+        has_contract_mac: false,
+        contract_mac: [0; 32],
+    };
+    signature.signature = signing_key
+        .sign(&query_signing_bytes(block, &signature))
+        .into();
+    signature
+}
+
+fn query_hash(contract_address: &[u8], query: &[u8]) -> [u8; 32] {
+    let mut query_data = contract_address.to_vec();
+    query_data.extend_from_slice(query);
+    sha_256(&query_data)
+}
+
+/// Signs the response to a query sent directly to the node, if the node enabled it.
+///
+/// The height signed is the one of the last block the enclave verified. The enclave can't tell
+/// which height the state the query read is from, so that state may be older.
+///
+/// As with receipts, failing to sign returns an unsigned response rather than failing the query.
+pub fn query_response_signature(
+    contract_address: &[u8],
+    query: &[u8],
+    output: &[u8],
+) -> QueryResponseSignature {
+    if !SIGN_QUERY_RESPONSES.load(Ordering::SeqCst) {
+        return QueryResponseSignature::unsigned();
+    }
+
+    match (node_signing_key(), verified_block()) {
+        (Some(signing_key), Some(block)) => {
+            sign_query_response(&signing_key, &block, contract_address, query, output)
+        }
+        _ => QueryResponseSignature::unsigned(),
+    }
+}
+
+fn mac_query_response(
+    signature: &mut QueryResponseSignature,
+    block: &VerifiedBlock,
+    encryption_key: &AESKey,
+    contract_address: &[u8],
+    query: &[u8],
+    output: &[u8],
+) {
+    if !signature.signed {
+        signature.block_height = block.height;
+        signature.query_hash = query_hash(contract_address, query);
+        signature.result_hash = sha_256(output);
+    }
+
+    let mut key_data = QUERY_RESPONSE_MAC_KEY_LABEL.to_vec();
+    key_data.extend_from_slice(encryption_key.get());
+    let mac_key = AESKey::new_from_slice(&sha_256(&key_data));
+
+    let mut mac_data = block.signing_prefix(signature.block_height);
+    mac_data.extend_from_slice(&signature.query_hash);
+    mac_data.extend_from_slice(&signature.result_hash);
+    signature.contract_mac = mac_key.sign_sha_256(&mac_data);
+    signature.has_contract_mac = true;
+}
+
+/// Adds the MAC of the contract to the response of a query sent directly to the node.
+///
+/// The MAC is HMAC-SHA256 over `sha256(chain_id) || block_height || query_hash || result_hash`,
+/// keyed with the sha256 of `QUERY_RESPONSE_MAC_KEY_LABEL || encryption_key`. Like signatures, it
+/// is left out before the enclave verified a block.
+pub fn add_contract_query_mac(
+    signature: &mut QueryResponseSignature,
+    encryption_key: &AESKey,
+    contract_address: &[u8],
+    query: &[u8],
+    output: &[u8],
+) {
+    if let Some(block) = verified_block() {
+        mac_query_response(
+            signature,
+            &block,
+            encryption_key,
+            contract_address,
+            query,
+            output,
+        );
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use std::convert::TryFrom;
+
+    use enclave_crypto::AESKey;
+    use enclave_ffi_types::QueryResponseSignature;
+
+    use super::{
+        mac_query_response, query_signing_bytes, sign_query_response, sign_receipt, signing_bytes,
+        VerifiedBlock,
+    };
+
+    fn block_of(chain_id: &str) -> VerifiedBlock {
+        VerifiedBlock {
+            chain_id: chain_id.to_string(),
+            height: 42,
+        }
+    }
+
+    pub fn test_receipt_signature_verifies() {
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let block = block_of("secret-4");
+        let receipt = sign_receipt(&signing_key, &block, &[1u8; 32], b"msg", b"output", 1234);
+
+        assert_eq!(receipt.code_hash, [1u8; 32]);
+        assert_eq!(receipt.gas_used, 1234);
+        assert_eq!(receipt.block_height, 42);
+
+        let verification_key =
+            ed25519_zebra::VerificationKey::try_from(receipt.signer_public_key).unwrap();
+        let signature = ed25519_zebra::Signature::from(receipt.signature);
+        verification_key
+            .verify(&signature, &signing_bytes(&block, &receipt))
+            .unwrap();
+
+        let mut tampered = receipt;
+        tampered.gas_used += 1;
+        assert!(verification_key
+            .verify(&signature, &signing_bytes(&block, &tampered))
+            .is_err());
+
+        let mut tampered = receipt;
+        tampered.block_height += 1;
+        assert!(verification_key
+            .verify(&signature, &signing_bytes(&block, &tampered))
+            .is_err());
+
+        // not valid for another chain
+        assert!(verification_key
+            .verify(&signature, &signing_bytes(&block_of("pulsar-3"), &receipt))
+            .is_err());
+    }
+
+    pub fn test_query_response_signature_verifies() {
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let block = block_of("secret-4");
+        let response = sign_query_response(&signing_key, &block, b"contract", b"query", b"output");
+
+        assert!(response.signed);
+        assert_eq!(response.block_height, 42);
+        assert_ne!(
+            response.query_hash,
+            sign_query_response(&signing_key, &block, b"other", b"query", b"output").query_hash
+        );
+
+        let verification_key =
+            ed25519_zebra::VerificationKey::try_from(response.signer_public_key).unwrap();
+        let signature = ed25519_zebra::Signature::from(response.signature);
+        verification_key
+            .verify(&signature, &query_signing_bytes(&block, &response))
+            .unwrap();
+
+        let mut tampered = response;
+        tampered.block_height += 1;
+        assert!(verification_key
+            .verify(&signature, &query_signing_bytes(&block, &tampered))
+            .is_err());
+
+        assert!(verification_key
+            .verify(
+                &signature,
+                &query_signing_bytes(&block_of("pulsar-3"), &response)
+            )
+            .is_err());
+    }
+
+    pub fn test_contract_query_mac_depends_on_keys() {
+        let mac_with = |encryption_key: [u8; 32], block: &VerifiedBlock, output: &[u8]| {
+            let mut signature = QueryResponseSignature::unsigned();
+            mac_query_response(
+                &mut signature,
+                block,
+                &AESKey::new_from_slice(&encryption_key),
+                b"contract",
+                b"query",
+                output,
+            );
+            assert!(signature.has_contract_mac);
+            assert!(!signature.signed);
+            assert_eq!(signature.block_height, block.height);
+            signature.contract_mac
+        };
+
+        let block = block_of("secret-4");
+        let mac = mac_with([1; 32], &block, b"output");
+        assert_eq!(mac, mac_with([1; 32], &block, b"output"));
+        assert_ne!(mac, mac_with([3; 32], &block, b"output"));
+        assert_ne!(mac, mac_with([1; 32], &block, b"tampered"));
+        // bound to the chain and the height
+        assert_ne!(mac, mac_with([1; 32], &block_of("pulsar-3"), b"output"));
+        let later = VerifiedBlock {
+            chain_id: "secret-4".to_string(),
+            height: 43,
+        };
+        assert_ne!(mac, mac_with([1; 32], &later, b"output"));
+
+        // the MAC covers the same height and hashes as the node signature
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let mut signed =
+            sign_query_response(&signing_key, &block, b"contract", b"query", b"output");
+        mac_query_response(
+            &mut signed,
+            &block,
+            &AESKey::new_from_slice(&[1; 32]),
+            b"contract",
+            b"query",
+            b"output",
+        );
+        assert!(signed.signed);
+        assert_eq!(signed.contract_mac, mac);
+    }
+}
@@ -7,18 +7,26 @@ use log::*;
 
 use sgx_types::sgx_status_t;
 
+use cw_types_v010::types::CanonicalAddr;
+use enclave_cosmos_types::types::ContractCode;
+
 use enclave_ffi_types::{
     Ctx, EnclaveBuffer, EnclaveError, HandleResult, HealthCheckResult, InitResult, MigrateResult,
-    QueryResult, RuntimeConfiguration, UpdateAdminResult,
+    QueryResult, RuntimeConfiguration, UpdateAdminResult, ENCLAVE_ABI_VERSION,
 };
 
-use enclave_utils::{oom_handler, validate_const_ptr, validate_input_length, validate_mut_ptr};
+use enclave_utils::{
+    alloc_tracker, oom_handler, validate_const_ptr, validate_input_length, validate_mut_ptr,
+};
 
+use crate::contract_key_cache::ContractKeys;
+use crate::external::ocalls;
 use crate::external::results::{
     result_handle_success_to_handleresult, result_init_success_to_initresult,
     result_migrate_success_to_result, result_query_success_to_queryresult,
     result_update_admin_success_to_result,
 };
+use crate::feature_gates::{extract_feature_gates, STATE_KEY_DECRYPTION};
 
 lazy_static! {
     static ref ECALL_ALLOCATE_STACK: SgxMutex<Vec<EnclaveBuffer>> = SgxMutex::new(Vec::new());
@@ -27,6 +35,7 @@ lazy_static! {
 const MAX_ENV_LENGTH: usize = 10_240; // 10 KiB
 const MAX_SIG_INFO_LENGTH: usize = 5_120_000; // 5 MiB, includes tx_bytes and sign_bytes
 const MAX_MSG_LENGTH: usize = 2_048_000; // 2 MiB
+const MAX_TX_MSGS_LENGTH: usize = 5_120_000; // 5 MiB, the msgs of a tx and their u32 length prefixes
 const MAX_ADDRESS_LENGTH: usize = 65; // canonical can be 20 or 32 bytes, humanized can be 45 or 65
 const MAX_PROOF_LENGTH: usize = 32; // output of sha256
 const MAX_WASM_LENGHT: usize = 3_145_728; // 3 MiB, larger Wasm ATM is 1,990,361 bytes (1.6 MiB)
@@ -105,9 +114,119 @@ fn ecall_configure_runtime_impl(config: RuntimeConfiguration) -> sgx_status_t {
         config.module_cache_size
     );
     crate::wasm3::module_cache::configure_module_cache(config.module_cache_size as usize);
+    crate::execution_receipts::configure_execution_receipts(
+        config.sign_execution_receipts,
+        config.sign_query_responses,
+    );
+    sgx_status_t::SGX_SUCCESS
+}
+
+/// Keeps the module of a code hash in the enclave across executions, see `pin_module`.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_pin_module(code_hash: *const u8) -> sgx_status_t {
+    validate_const_ptr!(code_hash, 32, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(std::slice::from_raw_parts(code_hash, 32));
+
+    if crate::wasm3::module_cache::pin_module(hash) {
+        sgx_status_t::SGX_SUCCESS
+    } else {
+        sgx_status_t::SGX_ERROR_OUT_OF_MEMORY
+    }
+}
+
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_unpin_module(code_hash: *const u8) -> sgx_status_t {
+    validate_const_ptr!(code_hash, 32, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(std::slice::from_raw_parts(code_hash, 32));
+
+    crate::wasm3::module_cache::unpin_module(&hash);
     sgx_status_t::SGX_SUCCESS
 }
 
+/// Drops the module of a code hash that was removed, see `evict_module`.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_evict_module(code_hash: *const u8) -> sgx_status_t {
+    validate_const_ptr!(code_hash, 32, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(std::slice::from_raw_parts(code_hash, 32));
+
+    crate::wasm3::module_cache::evict_module(&hash);
+    sgx_status_t::SGX_SUCCESS
+}
+
+/// Builds the module of a stored contract before it first runs, see `prewarm_module`.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_prewarm_module(
+    contract: *const u8,
+    contract_len: usize,
+) -> sgx_status_t {
+    validate_const_ptr!(
+        contract,
+        contract_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_input_length!(
+        contract_len,
+        "contract",
+        MAX_WASM_LENGHT,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    let contract = std::slice::from_raw_parts(contract, contract_len);
+
+    let result = panic::catch_unwind(|| {
+        let contract_code = ContractCode::new(contract);
+        crate::wasm3::module_cache::prewarm_module(&contract_code)
+    });
+    report_module_cache_stats();
+    match result {
+        Ok(Ok(())) => sgx_status_t::SGX_SUCCESS,
+        Ok(Err(err)) => {
+            debug!("could not prewarm module: {}", err);
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        }
+        Err(_err) => {
+            warn!("prewarming a module panicked");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
+/// Sends the allocator statistics to the host, if they changed in a way worth reporting.
+fn report_allocator_stats() {
+    if let Some(stats) = alloc_tracker::take_unreported_stats() {
+        let status = unsafe { ocalls::ocall_report_allocator_stats(stats) };
+        if status != sgx_status_t::SGX_SUCCESS {
+            warn!("Failed to report allocator stats to the host: {}", status);
+        }
+    }
+}
+
+/// Sends the module cache statistics to the host, if a module was looked up since they were last sent.
+fn report_module_cache_stats() {
+    if let Some(stats) = crate::wasm3::module_cache::take_unreported_stats() {
+        let status = unsafe { ocalls::ocall_report_module_cache_stats(stats) };
+        if status != sgx_status_t::SGX_SUCCESS {
+            warn!(
+                "Failed to report module cache stats to the host: {}",
+                status
+            );
+        }
+    }
+}
+
 /// Take a pointer as returned by `ecall_allocate` and recover the Vec<u8> inside of it.
 /// # Safety
 ///  This is a text
@@ -193,7 +312,11 @@ pub unsafe extern "C" fn ecall_init(
         result_init_success_to_initresult(result)
     });
 
-    if let Err(err) = oom_handler::restore_safety_buffer() {
+    // Reported whether the call failed or not, it may have built and cached a module either way
+    let restored = oom_handler::restore_safety_buffer();
+    report_allocator_stats();
+    report_module_cache_stats();
+    if let Err(err) = restored {
         error!("Could not restore OOM safety buffer!");
         return InitResult::Failure { err };
     }
@@ -272,7 +395,10 @@ pub unsafe extern "C" fn ecall_handle(
         result_handle_success_to_handleresult(result)
     });
 
-    if let Err(err) = oom_handler::restore_safety_buffer() {
+    let restored = oom_handler::restore_safety_buffer();
+    report_allocator_stats();
+    report_module_cache_stats();
+    if let Err(err) = restored {
         error!("Could not restore OOM safety buffer!");
         return HandleResult::Failure { err };
     }
@@ -369,7 +495,10 @@ unsafe fn ecall_query_impl(
         result_query_success_to_queryresult(result)
     });
 
-    if let Err(err) = oom_handler::restore_safety_buffer() {
+    let restored = oom_handler::restore_safety_buffer();
+    report_allocator_stats();
+    report_module_cache_stats();
+    if let Err(err) = restored {
         error!("Could not restore OOM safety buffer!");
         return QueryResult::Failure { err };
     }
@@ -464,7 +593,10 @@ pub unsafe extern "C" fn ecall_migrate(
         result_migrate_success_to_result(result)
     });
 
-    if let Err(err) = oom_handler::restore_safety_buffer() {
+    let restored = oom_handler::restore_safety_buffer();
+    report_allocator_stats();
+    report_module_cache_stats();
+    if let Err(err) = restored {
         error!("Could not restore OOM safety buffer!");
         return MigrateResult::Failure { err };
     }
@@ -557,6 +689,7 @@ pub unsafe extern "C" fn ecall_update_admin(
         error!("Could not restore OOM safety buffer!");
         return UpdateAdminResult::UpdateAdminFailure { err };
     }
+    report_allocator_stats();
 
     if let Ok(res) = result {
         res
@@ -580,6 +713,510 @@ pub unsafe extern "C" fn ecall_health_check() -> HealthCheckResult {
     HealthCheckResult::Success
 }
 
+/// Returns the `ENCLAVE_ABI_VERSION` this enclave was built with, so the node can refuse to load
+/// an enclave it can't talk to.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_abi_version() -> u32 {
+    ENCLAVE_ABI_VERSION
+}
+
+/// Writes the public key disclosure bundles are signed with, see `disclosure`. Fails before the
+/// node has the consensus seed.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_get_disclosure_signer(public_key: *mut u8) -> sgx_status_t {
+    validate_mut_ptr!(public_key, 32, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+
+    match crate::disclosure::disclosure_signer_public_key() {
+        Ok(signer) => {
+            std::slice::from_raw_parts_mut(public_key, 32).copy_from_slice(&signer);
+            sgx_status_t::SGX_SUCCESS
+        }
+        Err(_err) => sgx_status_t::SGX_ERROR_UNEXPECTED,
+    }
+}
+
+/// Decrypts the messages of a transaction, each prefixed by its length as a big endian u32, and
+/// writes a `MsgDecryptionStatus` per message to `statuses`. Their plaintext is kept for their
+/// execution if `keep_plaintexts` isn't zero. See `tx_decryption`.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_decrypt_tx_msgs(
+    msgs: *const u8,
+    msgs_len: usize,
+    statuses: *mut u8,
+    statuses_len: u32,
+    keep_plaintexts: u8,
+) -> sgx_status_t {
+    if let Err(_err) = oom_handler::register_oom_handler() {
+        error!("Could not register OOM handler!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    validate_const_ptr!(msgs, msgs_len, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    validate_mut_ptr!(
+        statuses,
+        statuses_len as usize,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_input_length!(
+        msgs_len,
+        "msgs",
+        MAX_TX_MSGS_LENGTH,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+
+    let msgs = std::slice::from_raw_parts(msgs, msgs_len);
+    let statuses = std::slice::from_raw_parts_mut(statuses, statuses_len as usize);
+
+    let result =
+        panic::catch_unwind(|| crate::tx_decryption::decrypt_tx_msgs(msgs, keep_plaintexts != 0));
+
+    if let Err(_err) = oom_handler::restore_safety_buffer() {
+        error!("Could not restore OOM safety buffer!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+    report_allocator_stats();
+
+    match result {
+        Ok(Ok(decrypted)) if decrypted.len() == statuses.len() => {
+            for (status, decrypted) in statuses.iter_mut().zip(decrypted) {
+                *status = decrypted as u8;
+            }
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Ok(decrypted)) => {
+            error!(
+                "Got {} messages to decrypt but room for {} statuses",
+                decrypted.len(),
+                statuses.len()
+            );
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        }
+        Ok(Err(_err)) => sgx_status_t::SGX_ERROR_INVALID_PARAMETER,
+        Err(_err) => {
+            oom_handler::get_then_clear_oom_happened();
+            error!("Call ecall_decrypt_tx_msgs panicked unexpectedly!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
+/// Decrypts the stored state keys of the contract with `og_contract_key`, see
+/// `state_key_decryption`. `keys` and `decrypted_keys` have the same length. `features` holds the
+/// active features like the env of a call.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_decrypt_state_keys(
+    og_contract_key: *const u8,
+    keys: *const u8,
+    keys_len: usize,
+    features: *const u8,
+    features_len: usize,
+    decrypted_keys: *mut u8,
+) -> sgx_status_t {
+    if !cfg!(feature = "state-key-decryption") {
+        warn!("Refusing to decrypt state keys, this enclave keeps contract state private");
+        return sgx_status_t::SGX_ERROR_FEATURE_NOT_SUPPORTED;
+    }
+
+    validate_const_ptr!(
+        features,
+        features_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_input_length!(
+        features_len,
+        "features",
+        MAX_ENV_LENGTH,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    match extract_feature_gates(std::slice::from_raw_parts(features, features_len)) {
+        Ok(feature_gates) if feature_gates.is_active(STATE_KEY_DECRYPTION) => {}
+        Ok(_) => {
+            warn!("Refusing to decrypt state keys, governance did not activate it");
+            return sgx_status_t::SGX_ERROR_FEATURE_NOT_SUPPORTED;
+        }
+        Err(_err) => return sgx_status_t::SGX_ERROR_INVALID_PARAMETER,
+    }
+
+    if let Err(_err) = oom_handler::register_oom_handler() {
+        error!("Could not register OOM handler!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    validate_const_ptr!(
+        og_contract_key,
+        64,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(keys, keys_len, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    validate_mut_ptr!(
+        decrypted_keys,
+        keys_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+
+    let mut og_contract_key_bytes = [0u8; 64];
+    og_contract_key_bytes.copy_from_slice(std::slice::from_raw_parts(og_contract_key, 64));
+    let keys = std::slice::from_raw_parts(keys, keys_len);
+    let decrypted_keys = std::slice::from_raw_parts_mut(decrypted_keys, keys_len);
+
+    let result = panic::catch_unwind(|| {
+        crate::state_key_decryption::decrypt_state_keys(&og_contract_key_bytes, keys)
+    });
+
+    if let Err(_err) = oom_handler::restore_safety_buffer() {
+        error!("Could not restore OOM safety buffer!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+    report_allocator_stats();
+
+    match result {
+        // Plaintext keys are shorter than their encryption
+        Ok(Some(decrypted)) if decrypted.len() <= keys_len => {
+            decrypted_keys[..decrypted.len()].copy_from_slice(&decrypted);
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Some(decrypted)) => {
+            error!(
+                "Decrypted state keys have length {}, more than the {} of their encryption",
+                decrypted.len(),
+                keys_len
+            );
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+        Ok(None) => sgx_status_t::SGX_ERROR_INVALID_PARAMETER,
+        Err(_err) => {
+            oom_handler::get_then_clear_oom_happened();
+            error!("Call ecall_decrypt_state_keys panicked unexpectedly!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
+/// Parses the current contract key and migration proof of a migrated contract, or nothing if
+/// `current_contract_key_len` is 0
+unsafe fn parse_current_contract_key(
+    current_contract_key: *const u8,
+    current_contract_key_len: usize,
+) -> Result<Option<([u8; 64], [u8; 32])>, sgx_status_t> {
+    match current_contract_key_len {
+        0 => Ok(None),
+        96 => {
+            validate_const_ptr!(
+                current_contract_key,
+                current_contract_key_len,
+                Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER)
+            );
+            let current = std::slice::from_raw_parts(current_contract_key, 96);
+            let mut key = [0u8; 64];
+            key.copy_from_slice(&current[..64]);
+            let mut proof = [0u8; 32];
+            proof.copy_from_slice(&current[64..]);
+            Ok(Some((key, proof)))
+        }
+        _ => {
+            warn!(
+                "Got a current contract key of invalid length {}",
+                current_contract_key_len
+            );
+            Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER)
+        }
+    }
+}
+
+/// Validates the keys of a contract and signs a proof of its current contract key. See
+/// `contract_key_proofs`.
+///
+/// `current_contract_key` is empty unless the contract was migrated, in which case it is the
+/// current contract key followed by its migration proof.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_prove_contract_key(
+    contract_address: *const u8,
+    contract_address_len: usize,
+    code_hash: *const u8,
+    og_contract_key: *const u8,
+    current_contract_key: *const u8,
+    current_contract_key_len: usize,
+    node_public_key: *mut u8,
+    encryption_public_key: *mut u8,
+    signer_public_key: *mut u8,
+    signature: *mut u8,
+) -> sgx_status_t {
+    if let Err(_err) = oom_handler::register_oom_handler() {
+        error!("Could not register OOM handler!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    validate_const_ptr!(
+        contract_address,
+        contract_address_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(code_hash, 32, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    validate_const_ptr!(
+        og_contract_key,
+        64,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_mut_ptr!(
+        node_public_key,
+        32,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_mut_ptr!(
+        encryption_public_key,
+        32,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_mut_ptr!(
+        signer_public_key,
+        32,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_mut_ptr!(signature, 64, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    validate_input_length!(
+        contract_address_len,
+        "contract_address",
+        MAX_ADDRESS_LENGTH,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+
+    let contract_address = CanonicalAddr::from_vec(
+        std::slice::from_raw_parts(contract_address, contract_address_len).to_vec(),
+    );
+
+    let mut code_hash_bytes = [0u8; 32];
+    code_hash_bytes.copy_from_slice(std::slice::from_raw_parts(code_hash, 32));
+
+    let mut og_contract_key_bytes = [0u8; 64];
+    og_contract_key_bytes.copy_from_slice(std::slice::from_raw_parts(og_contract_key, 64));
+
+    let current_contract_key =
+        match parse_current_contract_key(current_contract_key, current_contract_key_len) {
+            Ok(current_contract_key) => current_contract_key,
+            Err(status) => return status,
+        };
+
+    let result = panic::catch_unwind(|| {
+        crate::contract_key_proofs::prove_contract_key(
+            &contract_address,
+            &code_hash_bytes,
+            og_contract_key_bytes,
+            current_contract_key,
+        )
+    });
+
+    if let Err(_err) = oom_handler::restore_safety_buffer() {
+        error!("Could not restore OOM safety buffer!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    match result {
+        Ok(Ok(proof)) => {
+            std::slice::from_raw_parts_mut(node_public_key, 32)
+                .copy_from_slice(&proof.node_public_key);
+            std::slice::from_raw_parts_mut(encryption_public_key, 32)
+                .copy_from_slice(&proof.encryption_public_key);
+            std::slice::from_raw_parts_mut(signer_public_key, 32)
+                .copy_from_slice(&proof.signer_public_key);
+            std::slice::from_raw_parts_mut(signature, 64).copy_from_slice(&proof.signature);
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Err(err)) => {
+            warn!("Failed to prove contract key: {}", err);
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        }
+        Err(_err) => {
+            oom_handler::get_then_clear_oom_happened();
+            error!("Call ecall_prove_contract_key panicked unexpectedly!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
+/// Re-wraps a query addressed to the previous code of a migrated contract for its current code.
+/// See `query_rewrap`.
+///
+/// `previous_current_contract_key` is empty unless the contract had already been migrated before
+/// it ran the previous code, in which case it is the contract key of that code followed by its
+/// migration proof. `current_contract_key` is the contract key `migrator` generated at
+/// `migration_height`, followed by its migration proof. The re-wrapped query has the length of
+/// `msg`.
+///
+/// # Safety
+/// Always use protection
+#[no_mangle]
+pub unsafe extern "C" fn ecall_rewrap_query(
+    contract_address: *const u8,
+    contract_address_len: usize,
+    previous_code_hash: *const u8,
+    og_contract_key: *const u8,
+    previous_current_contract_key: *const u8,
+    previous_current_contract_key_len: usize,
+    current_code_hash: *const u8,
+    current_contract_key: *const u8,
+    migrator: *const u8,
+    migrator_len: usize,
+    migration_height: u64,
+    current_height: u64,
+    grace_blocks: u64,
+    msg: *const u8,
+    msg_len: usize,
+    rewrapped_msg: *mut u8,
+) -> sgx_status_t {
+    if let Err(_err) = oom_handler::register_oom_handler() {
+        error!("Could not register OOM handler!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    validate_const_ptr!(
+        contract_address,
+        contract_address_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(
+        previous_code_hash,
+        32,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(
+        og_contract_key,
+        64,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(
+        current_code_hash,
+        32,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(
+        migrator,
+        migrator_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_const_ptr!(msg, msg_len, sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    validate_mut_ptr!(
+        rewrapped_msg,
+        msg_len,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_input_length!(
+        contract_address_len,
+        "contract_address",
+        MAX_ADDRESS_LENGTH,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_input_length!(
+        migrator_len,
+        "migrator",
+        MAX_ADDRESS_LENGTH,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+    validate_input_length!(
+        msg_len,
+        "msg",
+        MAX_MSG_LENGTH,
+        sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+    );
+
+    let contract_address = CanonicalAddr::from_vec(
+        std::slice::from_raw_parts(contract_address, contract_address_len).to_vec(),
+    );
+
+    let mut previous_code_hash_bytes = [0u8; 32];
+    previous_code_hash_bytes.copy_from_slice(std::slice::from_raw_parts(previous_code_hash, 32));
+
+    let mut current_code_hash_bytes = [0u8; 32];
+    current_code_hash_bytes.copy_from_slice(std::slice::from_raw_parts(current_code_hash, 32));
+
+    let mut og_contract_key_bytes = [0u8; 64];
+    og_contract_key_bytes.copy_from_slice(std::slice::from_raw_parts(og_contract_key, 64));
+
+    let previous_current_contract_key = match parse_current_contract_key(
+        previous_current_contract_key,
+        previous_current_contract_key_len,
+    ) {
+        Ok(current_contract_key) => current_contract_key,
+        Err(status) => return status,
+    };
+    let previous_keys = ContractKeys {
+        og_contract_key: og_contract_key_bytes,
+        current_contract_key: previous_current_contract_key,
+    };
+
+    let current_contract_key = match parse_current_contract_key(current_contract_key, 96) {
+        Ok(current_contract_key) => current_contract_key,
+        Err(status) => return status,
+    };
+    let current_keys = ContractKeys {
+        og_contract_key: og_contract_key_bytes,
+        current_contract_key,
+    };
+
+    let migrator =
+        CanonicalAddr::from_vec(std::slice::from_raw_parts(migrator, migrator_len).to_vec());
+
+    let msg = std::slice::from_raw_parts(msg, msg_len);
+
+    let result = panic::catch_unwind(|| {
+        crate::query_rewrap::rewrap_query(
+            &contract_address,
+            &previous_code_hash_bytes,
+            &previous_keys,
+            &current_code_hash_bytes,
+            &current_keys,
+            &migrator,
+            migration_height,
+            current_height,
+            grace_blocks,
+            msg,
+        )
+    });
+
+    if let Err(_err) = oom_handler::restore_safety_buffer() {
+        error!("Could not restore OOM safety buffer!");
+        return sgx_status_t::SGX_ERROR_UNEXPECTED;
+    }
+
+    match result {
+        Ok(Ok(rewrapped)) if rewrapped.len() == msg_len => {
+            std::slice::from_raw_parts_mut(rewrapped_msg, msg_len).copy_from_slice(&rewrapped);
+            sgx_status_t::SGX_SUCCESS
+        }
+        Ok(Ok(rewrapped)) => {
+            error!(
+                "Re-wrapped query has length {}, expected {}",
+                rewrapped.len(),
+                msg_len
+            );
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+        Ok(Err(err)) => {
+            debug!("Did not re-wrap query: {}", err);
+            sgx_status_t::SGX_ERROR_INVALID_PARAMETER
+        }
+        Err(_err) => {
+            oom_handler::get_then_clear_oom_happened();
+            error!("Call ecall_rewrap_query panicked unexpectedly!");
+            sgx_status_t::SGX_ERROR_UNEXPECTED
+        }
+    }
+}
+
 #[cfg(feature = "test")]
 pub mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 //! This file should be autogenerated based on the headers created from the .edl file.
 
-use enclave_ffi_types::{Ctx, EnclaveBuffer, OcallReturn, UntrustedVmError, UserSpaceBuffer};
+use enclave_ffi_types::{
+    AllocatorStats, CallMemoryStats, Ctx, EnclaveBuffer, ExecutionReceipt, ModuleCacheStats,
+    OcallReturn, UntrustedVmError, UserSpaceBuffer,
+};
 use sgx_types::*;
 
 extern "C" {
@@ -60,4 +63,14 @@ extern "C" {
         keys: *const u8,
         keys_len: usize,
     ) -> sgx_status_t;
+
+    pub fn ocall_report_allocator_stats(stats: AllocatorStats) -> sgx_status_t;
+
+    pub fn ocall_report_call_memory(stats: CallMemoryStats) -> sgx_status_t;
+
+    pub fn ocall_report_module_cache_stats(stats: ModuleCacheStats) -> sgx_status_t;
+
+    pub fn ocall_report_execution_receipt(receipt: ExecutionReceipt) -> sgx_status_t;
+
+    pub fn ocall_report_observed_events(record: *const u8, record_len: usize) -> sgx_status_t;
 }
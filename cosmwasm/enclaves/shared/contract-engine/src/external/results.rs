@@ -1,8 +1,8 @@
 use sgx_types::sgx_status_t;
 
 use enclave_ffi_types::{
-    EnclaveError, HandleResult, InitResult, MigrateResult, QueryResult, UntrustedVmError,
-    UpdateAdminResult, UserSpaceBuffer,
+    EnclaveError, HandleResult, InitResult, MigrateResult, QueryResponseSignature, QueryResult,
+    UntrustedVmError, UpdateAdminResult, UserSpaceBuffer,
 };
 
 use crate::external::ocalls::ocall_allocate;
@@ -141,13 +141,14 @@ pub fn result_update_admin_success_to_result(
 pub struct QuerySuccess {
     /// The output of the calculation
     pub output: Vec<u8>,
+    pub signature: QueryResponseSignature,
 }
 
 pub fn result_query_success_to_queryresult(
     result: Result<QuerySuccess, EnclaveError>,
 ) -> QueryResult {
     match result {
-        Ok(QuerySuccess { output }) => {
+        Ok(QuerySuccess { output, signature }) => {
             let user_buffer = unsafe {
                 let mut user_buffer = std::mem::MaybeUninit::<UserSpaceBuffer>::uninit();
                 match ocall_allocate(user_buffer.as_mut_ptr(), output.as_ptr(), output.len()) {
@@ -164,6 +165,7 @@ pub fn result_query_success_to_queryresult(
             };
             QueryResult::Success {
                 output: user_buffer,
+                signature,
             }
         }
         Err(err) => QueryResult::Failure { err },
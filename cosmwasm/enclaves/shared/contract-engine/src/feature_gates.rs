@@ -0,0 +1,69 @@
+//! Changes of the contract engine that alter the results of transactions are activated by
+//! governance at a height, through the params of the compute module, so that every node switches
+//! at the same block. The node passes the features active at the height of the block in the env
+//! of every call.
+
+use log::*;
+use serde::Deserialize;
+
+use enclave_ffi_types::EnclaveError;
+
+/// Reading the cw2 version of contracts on instantiate and migrate, see the `cw2` module
+pub const CONTRACT_VERSION: &str = "contract_version";
+/// Signing callbacks over normalized funds, see `send_funds_validations::funds_to_sign`
+pub const FUNDS_NORMALIZATION: &str = "funds_normalization";
+/// Rejecting events with reserved types or attribute keys, see the `event_validation` module
+pub const EVENT_VALIDATION: &str = "event_validation";
+/// Compressing the state of contracts that opted in, see the `state_compression` module
+pub const STATE_COMPRESSION: &str = "state_compression";
+/// Trapping contracts that recurse deeper than the limit, see the `wasm3::stack_height` module
+pub const STACK_HEIGHT_LIMIT: &str = "stack_height_limit";
+/// Trapping calls that grow their memory by more than the limit, see `wasm3::gas`
+pub const MEMORY_GROWTH_LIMIT: &str = "memory_growth_limit";
+/// Forwarding 63/64 of the remaining gas to queried contracts, see `gas::GasForwarding`
+pub const GAS_FORWARDING: &str = "gas_forwarding";
+/// Publishing the revert reasons contracts fail with next to their encrypted errors, see `revert`
+pub const REVERT_REASONS: &str = "revert_reasons";
+/// Running contracts using the bulk memory proposal, see `wasm3::validation::uses_bulk_memory`
+pub const BULK_MEMORY: &str = "bulk_memory";
+/// Decrypting the state keys of contracts for migration tooling, see `state_key_decryption`
+pub const STATE_KEY_DECRYPTION: &str = "state_key_decryption";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FeatureGates {
+    #[serde(default)]
+    active_features: Vec<String>,
+}
+
+impl FeatureGates {
+    pub fn is_active(&self, feature: &str) -> bool {
+        self.active_features.iter().any(|active| active == feature)
+    }
+}
+
+/// Extract the active features from the env parameter, like the query_depth. An env without them,
+/// as sent before they existed, activates none.
+pub fn extract_feature_gates(env: &[u8]) -> Result<FeatureGates, EnclaveError> {
+    serde_json::from_slice::<FeatureGates>(env).map_err(|err| {
+        warn!(
+            "error while deserializing env into json {:?}: {}",
+            String::from_utf8_lossy(env),
+            err
+        );
+        EnclaveError::FailedToDeserialize
+    })
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_extract_feature_gates() {
+        let gates = extract_feature_gates(br#"{"active_features":["contract_version"]}"#).unwrap();
+        assert!(gates.is_active(CONTRACT_VERSION));
+        assert!(!gates.is_active("other"));
+
+        let gates = extract_feature_gates(br#"{"query_depth":1}"#).unwrap();
+        assert!(!gates.is_active(CONTRACT_VERSION));
+    }
+}
@@ -23,10 +23,15 @@ pub struct WasmCosts {
     pub initial_mem: u32,
     /// Grow memory cost, per page (64kb)
     pub grow_mem: u32,
-    // /// Memory copy cost, per byte
-    // pub memcpy: u32,
-    // /// Max stack height (native WebAssembly stack limiter)
-    // pub max_stack_height: u32,
+    /// Bulk memory cost, per byte copied, filled or initialized and per table element copied or
+    /// initialized
+    pub memcpy: u32,
+    /// Max stack height (native WebAssembly stack limiter), in approximate stack slots.
+    /// See `wasm3::stack_height`.
+    pub max_stack_height: u32,
+    /// Max number of pages (64kb) a single call can grow its memory by, on top of the memory the
+    /// contract starts with. Growing is refused before the static memory limit is reached.
+    pub max_memory_grow_pages: u32,
     // /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` / `opcodes_div`
     // pub opcodes_mul: u32,
     // /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` / `opcodes_div`
@@ -51,8 +56,57 @@ pub struct WasmCosts {
     pub external_secp256k1_sign: u32,
     /// Cost invoking ed25519_sign from WASM
     pub external_ed25519_sign: u32,
+    /// Cost invoking disclose_state from WASM, on top of reading the disclosed keys
+    pub external_disclose_state: u32,
+    /// Cost per byte of the disclosure bundle, which is encrypted, hashed and copied to the contract
+    pub external_disclose_state_per_byte: u32,
+    /// Cost invoking check_node_attestation from WASM
+    pub external_check_node_attestation: u32,
+    /// Cost invoking ct_eq from WASM
+    pub external_ct_eq_base: u32,
+    /// Cost per compared byte in ct_eq
+    pub external_ct_eq_per_byte: u32,
+    /// Cost invoking encrypt_to_contract from WASM
+    pub external_encrypt_to_contract: u32,
+    /// Cost invoking decrypt_from_contract from WASM
+    pub external_decrypt_from_contract: u32,
+    /// Cost invoking shuffle from WASM
+    pub external_shuffle_base: u32,
+    /// Cost invoking weighted_sample from WASM
+    pub external_weighted_sample_base: u32,
+    /// Cost per item handled by shuffle, and per weight and pick in weighted_sample
+    pub external_sample_per_item: u32,
+    /// Cost per byte written by contracts whose state is compressed
+    pub external_state_compression_per_byte: u32,
+    /// Cost per byte of the compressed values read
+    pub external_state_decompression_per_byte: u32,
     pub external_check_gas_used: u32,
     pub external_minimum_gas_evaporate: u32,
+    /// How much of its remaining gas a contract forwards to the contracts it queries
+    pub gas_forwarding: GasForwarding,
+}
+
+/// The gas a contract hands to a nested call, out of the gas it has left.
+///
+/// If a call could use all of its caller's gas, a deep enough chain of calls could exhaust the gas
+/// at the leaf and leave every contract above it without gas to handle the failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GasForwarding {
+    /// The callee gets all the remaining gas of the caller
+    All,
+    /// The caller keeps 1/n of its remaining gas and forwards the rest. With n = 64 this is the
+    /// 63/64 rule of EIP-150: at any depth, the callers keep enough gas to record the result.
+    AllButOneNth(u64),
+}
+
+impl GasForwarding {
+    pub fn forwarded_gas(&self, remaining_gas: u64) -> u64 {
+        match *self {
+            GasForwarding::All => remaining_gas,
+            GasForwarding::AllButOneNth(0) => remaining_gas,
+            GasForwarding::AllButOneNth(n) => remaining_gas - remaining_gas / n,
+        }
+    }
 }
 
 impl Default for WasmCosts {
@@ -66,8 +120,12 @@ impl Default for WasmCosts {
             // static_address: 40,
             initial_mem: 8192,
             grow_mem: 8192,
-            // memcpy: 1,
-            // max_stack_height: 64 * 1024,
+            memcpy: 1,
+            // Half of the 60 KiB interpreter stack, in 8 byte slots. Operand stacks are not
+            // counted, so this leaves them the other half.
+            max_stack_height: 60 * 1024 / 8 / 2,
+            // 8 MiB, two thirds of the static memory limit
+            max_memory_grow_pages: 128,
             // opcodes_mul: 3,
             // opcodes_div: 8,
             external_humanize_address: 8192,
@@ -80,8 +138,21 @@ impl Default for WasmCosts {
             external_ed25519_batch_verify_each: 70000,
             external_secp256k1_sign: 100000,
             external_ed25519_sign: 75000,
+            external_disclose_state: 150000,
+            external_disclose_state_per_byte: 4,
+            external_check_node_attestation: 200000,
+            external_ct_eq_base: 1000,
+            external_ct_eq_per_byte: 2,
+            external_encrypt_to_contract: 100000,
+            external_decrypt_from_contract: 75000,
+            external_shuffle_base: 5000,
+            external_weighted_sample_base: 5000,
+            external_sample_per_item: 20,
+            external_state_compression_per_byte: 8,
+            external_state_decompression_per_byte: 2,
             external_check_gas_used: 8192,
             external_minimum_gas_evaporate: 8000,
+            gas_forwarding: GasForwarding::AllButOneNth(64),
         }
     }
 }
@@ -141,3 +212,22 @@ pub struct RuntimeGas {
     pub refund: u64,
     pub costs: RuntimeWasmCosts,
 }
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::GasForwarding;
+
+    pub fn test_gas_forwarding() {
+        assert_eq!(GasForwarding::All.forwarded_gas(6400), 6400);
+        assert_eq!(GasForwarding::AllButOneNth(64).forwarded_gas(6400), 6300);
+        assert_eq!(GasForwarding::AllButOneNth(64).forwarded_gas(63), 63);
+        assert_eq!(GasForwarding::AllButOneNth(0).forwarded_gas(6400), 6400);
+
+        // The gas a chain of calls can reach shrinks with depth
+        let mut gas = 1_000_000;
+        for _ in 0..10 {
+            gas = GasForwarding::AllButOneNth(64).forwarded_gas(gas);
+        }
+        assert_eq!(gas, 854_295);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::envelope::Envelope;
 use crate::message_utils::try_get_decrypted_secret_msg;
 use crate::types::{ParsedMessage, SecretMessage};
 use cw_types_v1::ibc::IbcPacketReceiveMsg;
@@ -16,6 +17,7 @@ pub fn parse_plaintext_ibc_protocol_message(
             nonce: [0; 32],
             user_public_key: [0; 32],
             msg: plaintext_message.into(),
+            envelope: Envelope::V1,
         },
         decrypted_msg: plaintext_message.into(),
         data_for_validation: None,
@@ -62,6 +64,7 @@ pub fn parse_ibc_receive_message(message: &[u8]) -> Result<ParsedMessage, Enclav
                         nonce: [0; 32],
                         user_public_key: [0; 32],
                         msg: message.into(),
+                        envelope: Envelope::V1,
                     },
                 )
             }
@@ -98,6 +101,7 @@ pub fn parse_plaintext_ibc_validated_message(
             nonce: [0; 32],
             user_public_key: [0; 32],
             msg: plaintext_message.into(),
+            envelope: Envelope::V1,
         },
         decrypted_msg: plaintext_message.into(),
         data_for_validation: None,
@@ -1,6 +1,8 @@
+use crate::feature_gates::{FeatureGates, FUNDS_NORMALIZATION};
 use crate::ibc_denom_utils::{get_denom_prefix, parse_denom_trace, receiver_chain_is_source};
 use cw_types_v010::types::Coin;
 use enclave_cosmos_types::types::{DirectSdkMsg, FungibleTokenPacketData, Packet};
+use enclave_ffi_types::EnclaveError;
 use log::*;
 
 /// Check that the funds listed in the cosmwasm message matches the ones in env
@@ -48,6 +50,43 @@ pub fn verify_sent_funds(msg: &DirectSdkMsg, sent_funds_msg: &[Coin]) -> bool {
     }
 }
 
+/// Sorts funds by denom, merges coins of the same denom and drops zero amounts, the same way the
+/// node normalizes funds that contracts send before calling the receiving contract. Fails if the
+/// amounts of a denom add up to more than a u128.
+pub fn normalize_funds(funds: &[Coin]) -> Result<Vec<Coin>, EnclaveError> {
+    let mut normalized: Vec<Coin> = vec![];
+    let mut sorted = funds.to_vec();
+    sorted.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    for coin in sorted {
+        match normalized.last_mut() {
+            Some(last) if last.denom == coin.denom => {
+                last.amount.0 = last.amount.0.checked_add(coin.amount.0).ok_or_else(|| {
+                    warn!("funds of denom {} overflow", coin.denom);
+                    EnclaveError::InvalidFunds
+                })?;
+            }
+            _ => normalized.push(coin),
+        }
+    }
+    normalized.retain(|coin| coin.amount.0 != 0);
+    Ok(normalized)
+}
+
+/// The funds the callback of a message a contract sends is signed over. Once funds normalization
+/// is active the node normalizes the funds before calling the receiving contract, so they are
+/// signed normalized. Before, the node only accepted them sorted and unique, as sent.
+pub fn funds_to_sign(
+    funds: &[Coin],
+    feature_gates: &FeatureGates,
+) -> Result<Vec<Coin>, EnclaveError> {
+    if feature_gates.is_active(FUNDS_NORMALIZATION) {
+        normalize_funds(funds)
+    } else {
+        Ok(funds.to_vec())
+    }
+}
+
 fn verify_sent_funds_ibc_wasm_hooks_incoming_transfer(
     sent_funds_msg: &[Coin],
     data: &Vec<u8>,
@@ -136,3 +175,44 @@ fn verify_sent_funds_ibc_wasm_hooks_incoming_transfer(
 
     true
 }
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use cw_types_v010::math::Uint128;
+    use cw_types_v010::types::Coin;
+
+    use super::{funds_to_sign, normalize_funds};
+    use crate::feature_gates::extract_feature_gates;
+
+    fn coin(amount: u128, denom: &str) -> Coin {
+        Coin {
+            denom: denom.to_string(),
+            amount: Uint128(amount),
+        }
+    }
+
+    pub fn test_normalize_funds() {
+        let sorted = vec![coin(1, "a"), coin(2, "b")];
+        assert_eq!(normalize_funds(&sorted).unwrap(), sorted);
+        assert_eq!(normalize_funds(&[]).unwrap(), vec![]);
+
+        let unsorted = [coin(2, "b"), coin(1, "a"), coin(3, "b"), coin(0, "c")];
+        assert_eq!(
+            normalize_funds(&unsorted).unwrap(),
+            vec![coin(1, "a"), coin(5, "b")]
+        );
+        assert!(normalize_funds(&[coin(u128::MAX, "a"), coin(1, "a")]).is_err());
+
+        let inactive = extract_feature_gates(br#"{}"#).unwrap();
+        assert_eq!(
+            funds_to_sign(&unsorted, &inactive).unwrap(),
+            unsorted.to_vec()
+        );
+        let active =
+            extract_feature_gates(br#"{"active_features":["funds_normalization"]}"#).unwrap();
+        assert_eq!(
+            funds_to_sign(&unsorted, &active).unwrap(),
+            vec![coin(1, "a"), coin(5, "b")]
+        );
+    }
+}
@@ -1,13 +1,20 @@
+use crate::contract_encryption::encrypt_to_self;
 use crate::contract_validation::ReplyParams;
+use crate::cw2::ContractVersion;
+use crate::event_validation::validate_events;
+use crate::feature_gates::{FeatureGates, REVERT_REASONS};
+use crate::input_validation::send_funds_validations::funds_to_sign;
+use crate::revert::{parse_revert, Revert};
 use core::fmt;
 
 /// This contains all the user-facing functions. In these functions we will be using
 /// the consensus_io_exchange_keypair and a user-generated key to create a symmetric key
 /// that is unique to the user and the enclave
 ///
+use super::envelope::Envelope;
 use super::types::{IoNonce, SecretMessage};
 use cw_types_v010::encoding::Binary;
-use cw_types_v010::types::{CanonicalAddr, Coin, LogAttribute};
+use cw_types_v010::types::{AttributeEncryption, CanonicalAddr, Coin, LogAttribute};
 use cw_types_v1::results::{Event, Reply, ReplyOn, SubMsg, SubMsgResponse, SubMsgResult};
 
 use enclave_ffi_types::EnclaveError;
@@ -121,6 +128,9 @@ pub struct WasmOutput {
     pub query: Option<QueryOutput>,
     pub internal_reply_enclave_sig: Option<Binary>,
     pub internal_msg_id: Option<Binary>,
+    /// The cw2 version of the contract after a successful instantiate or migrate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_info: Option<ContractVersion>,
 }
 
 pub fn calc_encryption_key(nonce: &IoNonce, user_public_key: &Ed25519PublicKey) -> AESKey {
@@ -131,6 +141,29 @@ pub fn calc_encryption_key(nonce: &IoNonce, user_public_key: &Ed25519PublicKey)
     AESKey::new_from_slice(&tx_encryption_ikm).derive_key_from_this(nonce)
 }
 
+/// Like `calc_encryption_key`, with a Kyber768 secret encapsulated by the user for the consensus
+/// io Kyber key mixed in, so that the key stays secret if X25519 is broken
+#[cfg(feature = "pq-hybrid")]
+pub fn calc_hybrid_encryption_key(
+    nonce: &IoNonce,
+    user_public_key: &Ed25519PublicKey,
+    encapsulated_key: &[u8],
+) -> Result<AESKey, EnclaveError> {
+    let enclave_io_key = KEY_MANAGER.get_consensus_io_exchange_keypair().unwrap();
+    let enclave_kyber_key = KEY_MANAGER
+        .get_consensus_io_kyber_keypair()
+        .map_err(|_| EnclaveError::DecryptionError)?;
+
+    let tx_encryption_ikm = enclave_io_key.current.diffie_hellman(user_public_key);
+    let kyber_shared_secret = enclave_kyber_key
+        .decapsulate(encapsulated_key)
+        .map_err(|_| EnclaveError::DecryptionError)?;
+
+    let mut data = kyber_shared_secret.to_vec();
+    data.extend_from_slice(nonce);
+    Ok(AESKey::new_from_slice(&tx_encryption_ikm).derive_key_from_this(&data))
+}
+
 fn encrypt_serializable<T>(
     key: &AESKey,
     val: &T,
@@ -194,7 +227,7 @@ fn b64_encode(data: &[u8]) -> String {
 
 #[allow(clippy::too_many_arguments)]
 pub fn post_process_output(
-    output: Vec<u8>,
+    mut raw_output: RawWasmOutput,
     secret_msg: &SecretMessage,
     contract_addr: &CanonicalAddr,
     contract_hash: &str,
@@ -202,8 +235,9 @@ pub fn post_process_output(
     sender_addr: &CanonicalAddr,
     is_query_output: bool,
     is_ibc_output: bool,
+    feature_gates: &FeatureGates,
 ) -> Result<Vec<u8>, EnclaveError> {
-    let mut raw_output = deserialize_output(output)?;
+    validate_events(&raw_output, feature_gates)?;
     raw_output = attach_reply_headers_to_submsgs(raw_output, contract_hash, &reply_params)?;
     raw_output = encrypt_output(
         raw_output,
@@ -211,8 +245,9 @@ pub fn post_process_output(
         contract_addr,
         &reply_params,
         is_ibc_output,
+        feature_gates,
     )?;
-    raw_output = create_callback_sig_for_submsgs(raw_output, contract_addr)?;
+    raw_output = create_callback_sig_for_submsgs(raw_output, contract_addr, feature_gates)?;
     raw_output = adapt_output_for_reply(raw_output, &reply_params, secret_msg, sender_addr)?;
 
     let output = finalize_raw_output(raw_output, is_query_output, is_ibc_output, true)?;
@@ -339,14 +374,9 @@ pub fn finalize_raw_output(
 
 pub fn manipulate_callback_sig_for_plaintext(
     contract_addr: &CanonicalAddr,
-    output: Vec<u8>,
+    mut raw_output: RawWasmOutput,
+    feature_gates: &FeatureGates,
 ) -> Result<RawWasmOutput, EnclaveError> {
-    let mut raw_output: RawWasmOutput = serde_json::from_slice(&output).map_err(|err| {
-        warn!("got an error while trying to deserialize output bytes into json");
-        trace!("output: {:?} error: {:?}", output, err);
-        EnclaveError::FailedToDeserialize
-    })?;
-
     match &mut raw_output {
         RawWasmOutput::OkV1 { ok, .. } => {
             for sub_msg in &mut ok.messages {
@@ -367,13 +397,18 @@ pub fn manipulate_callback_sig_for_plaintext(
                             *callback_sig = Some(create_callback_signature(
                                 contract_addr,
                                 &msg.0,
-                                &funds
-                                    .iter()
-                                    .map(|coin| cw_types_v010::types::Coin {
-                                        denom: coin.denom.clone(),
-                                        amount: cw_types_v010::math::Uint128(coin.amount.u128()),
-                                    })
-                                    .collect::<Vec<cw_types_v010::types::Coin>>()[..],
+                                &funds_to_sign(
+                                    &funds
+                                        .iter()
+                                        .map(|coin| cw_types_v010::types::Coin {
+                                            denom: coin.denom.clone(),
+                                            amount: cw_types_v010::math::Uint128(
+                                                coin.amount.u128(),
+                                            ),
+                                        })
+                                        .collect::<Vec<cw_types_v010::types::Coin>>(),
+                                    feature_gates,
+                                )?,
                             ));
                         }
                         cw_types_v1::results::WasmMsg::Migrate {
@@ -413,13 +448,18 @@ pub fn manipulate_callback_sig_for_plaintext(
                             *callback_sig = Some(create_callback_signature(
                                 contract_addr,
                                 &msg.as_slice().to_vec(),
-                                &funds
-                                    .iter()
-                                    .map(|coin| Coin {
-                                        denom: coin.denom.clone(),
-                                        amount: cw_types_v010::math::Uint128(coin.amount.u128()),
-                                    })
-                                    .collect::<Vec<Coin>>()[..],
+                                &funds_to_sign(
+                                    &funds
+                                        .iter()
+                                        .map(|coin| Coin {
+                                            denom: coin.denom.clone(),
+                                            amount: cw_types_v010::math::Uint128(
+                                                coin.amount.u128(),
+                                            ),
+                                        })
+                                        .collect::<Vec<Coin>>(),
+                                    feature_gates,
+                                )?,
                             ));
                         }
                         cw_types_v1::results::WasmMsg::Migrate {
@@ -470,7 +510,7 @@ pub fn set_all_logs_to_plaintext(raw_output: &mut RawWasmOutput) {
     }
 }
 
-fn deserialize_output(output: Vec<u8>) -> Result<RawWasmOutput, EnclaveError> {
+pub fn deserialize_output(output: Vec<u8>) -> Result<RawWasmOutput, EnclaveError> {
     trace!(
         "output as received from contract: {:?}",
         String::from_utf8_lossy(&output)
@@ -505,11 +545,12 @@ fn encrypt_output(
     contract_addr: &CanonicalAddr,
     reply_params: &Option<Vec<ReplyParams>>,
     is_ibc_output: bool,
+    feature_gates: &FeatureGates,
 ) -> Result<RawWasmOutput, EnclaveError> {
     // The output we receive from a contract could be a reply to a caller contract (via the "reply" endpoint).
     // Therefore if reply_recipient_contract_hash is "Some", we append it to any encrypted data besides submessages that are irrelevant for replies.
     // More info in: https://github.com/CosmWasm/cosmwasm/blob/v1.0.0/packages/std/src/results/submessages.rs#L192-L198
-    let encryption_key = calc_encryption_key(&secret_msg.nonce, &secret_msg.user_public_key);
+    let encryption_key = secret_msg.encryption_key()?;
     trace!(
         "message nonce and public key for encryption: {:?} {:?}",
         secret_msg.nonce,
@@ -518,8 +559,13 @@ fn encrypt_output(
 
     match &mut output {
         RawWasmOutput::Err { err, .. } => {
+            let revert = if feature_gates.is_active(REVERT_REASONS) {
+                parse_revert(err)
+            } else {
+                None
+            };
             let encrypted_err = encrypt_serializable(&encryption_key, err, reply_params, false)?;
-            *err = format_generic_error_message(Value::String(encrypted_err));
+            *err = format_encrypted_error_message(Value::String(encrypted_err), revert);
         }
         RawWasmOutput::QueryOkV010 { ok } | RawWasmOutput::QueryOkV1 { ok } => {
             *ok = encrypt_serializable(&encryption_key, ok, reply_params, false)?;
@@ -532,16 +578,16 @@ fn encrypt_output(
                         wasm_msg,
                         secret_msg.nonce,
                         secret_msg.user_public_key,
+                        secret_msg.envelope.clone(),
                         contract_addr,
+                        feature_gates,
                     )?;
                 }
             }
 
             // v0.10: The logs that will be emitted as part of a "wasm" event.
             for log in ok.log.iter_mut().filter(|log| log.encrypted) {
-                log.key = encrypt_preserialized_string(&encryption_key, &log.key, &None, false)?;
-                log.value =
-                    encrypt_preserialized_string(&encryption_key, &log.value, &None, false)?;
+                encrypt_attribute(log, &encryption_key, secret_msg, contract_addr)?;
             }
 
             if let Some(data) = &mut ok.data {
@@ -559,6 +605,7 @@ fn encrypt_output(
                 &mut ok.attributes,
                 &mut ok.events,
                 secret_msg,
+                contract_addr,
             )?;
             if let Some(data) = &mut ok.data {
                 if is_ibc_output {
@@ -580,6 +627,7 @@ fn encrypt_output(
                 &mut ok.attributes,
                 &mut ok.events,
                 secret_msg,
+                contract_addr,
             )?;
 
             ok.acknowledgement = Binary::from_base64(&encrypt_serializable(
@@ -600,8 +648,9 @@ fn encrypt_v1_non_result_fields<T: Clone + fmt::Debug + PartialEq>(
     attributes: &mut [LogAttribute],
     events: &mut [Event],
     secret_msg: &SecretMessage,
+    contract_addr: &CanonicalAddr,
 ) -> Result<(), EnclaveError> {
-    let encryption_key = calc_encryption_key(&secret_msg.nonce, &secret_msg.user_public_key);
+    let encryption_key = secret_msg.encryption_key()?;
 
     for sub_msg in messages.iter_mut() {
         encrypt_wasm_submsg(sub_msg, secret_msg)?;
@@ -609,15 +658,55 @@ fn encrypt_v1_non_result_fields<T: Clone + fmt::Debug + PartialEq>(
 
     // v1: The attributes that will be emitted as part of a "wasm" event.
     for attr in attributes.iter_mut().filter(|attr| attr.encrypted) {
-        attr.key = encrypt_preserialized_string(&encryption_key, &attr.key, &None, false)?;
-        attr.value = encrypt_preserialized_string(&encryption_key, &attr.value, &None, false)?;
+        encrypt_attribute(attr, &encryption_key, secret_msg, contract_addr)?;
     }
 
     // v1: Extra, custom events separate from the main wasm one. These will have "wasm-"" prepended to the type.
     for event in events.iter_mut() {
         for attr in event.attributes.iter_mut().filter(|attr| attr.encrypted) {
-            attr.key = encrypt_preserialized_string(&encryption_key, &attr.key, &None, false)?;
-            attr.value = encrypt_preserialized_string(&encryption_key, &attr.value, &None, false)?;
+            encrypt_attribute(attr, &encryption_key, secret_msg, contract_addr)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Encrypts the key and value of an encrypted attribute with the key the contract picked for it.
+///
+/// `sender_key` is the key of the sender of the transaction, which the rest of the output is
+/// encrypted with. Attributes for a recipient are encrypted with the key the recipient would share
+/// with the enclave had it sent the transaction, so it decrypts them like its own outputs, using the
+/// nonce of the transaction. Contract-private attributes can only be decrypted by the contract,
+/// with `decrypt_from_contract`.
+fn encrypt_attribute(
+    attr: &mut LogAttribute,
+    sender_key: &AESKey,
+    secret_msg: &SecretMessage,
+    contract_addr: &CanonicalAddr,
+) -> Result<(), EnclaveError> {
+    match &attr.encryption {
+        AttributeEncryption::Sender => {
+            attr.key = encrypt_preserialized_string(sender_key, &attr.key, &None, false)?;
+            attr.value = encrypt_preserialized_string(sender_key, &attr.value, &None, false)?;
+        }
+        AttributeEncryption::Recipient(recipient_public_key) => {
+            let mut public_key: Ed25519PublicKey = [0u8; 32];
+            if recipient_public_key.len() != public_key.len() {
+                warn!(
+                    "Got an attribute encrypted to a public key of invalid length: {}",
+                    recipient_public_key.len()
+                );
+                return Err(EnclaveError::EncryptionError);
+            }
+            public_key.copy_from_slice(recipient_public_key.as_slice());
+
+            let recipient_key = calc_encryption_key(&secret_msg.nonce, &public_key);
+            attr.key = encrypt_preserialized_string(&recipient_key, &attr.key, &None, false)?;
+            attr.value = encrypt_preserialized_string(&recipient_key, &attr.value, &None, false)?;
+        }
+        AttributeEncryption::Contract => {
+            attr.key = b64_encode(&encrypt_to_self(contract_addr, attr.key.as_bytes())?);
+            attr.value = b64_encode(&encrypt_to_self(contract_addr, attr.value.as_bytes())?);
         }
     }
 
@@ -638,6 +727,7 @@ fn encrypt_wasm_submsg<T: Clone + fmt::Debug + PartialEq>(
                     msg: msg.as_slice().to_vec(),
                     nonce: secret_msg.nonce,
                     user_public_key: secret_msg.user_public_key,
+                    envelope: secret_msg.envelope.clone(),
                 };
                 msg_to_encrypt.encrypt_in_place()?;
                 *msg = Binary::from(msg_to_encrypt.to_vec().as_slice());
@@ -704,6 +794,7 @@ fn attach_reply_headers_to_submsgs(
 fn create_callback_sig_for_submsgs(
     mut output: RawWasmOutput,
     contract_addr: &CanonicalAddr,
+    feature_gates: &FeatureGates,
 ) -> Result<RawWasmOutput, EnclaveError> {
     let sub_msgs = match &mut output {
         RawWasmOutput::OkV1 { ok, .. } => &mut ok.messages,
@@ -729,13 +820,16 @@ fn create_callback_sig_for_submsgs(
                     *callback_sig = Some(create_callback_signature(
                         contract_addr,
                         &SecretMessage::from_slice(msg.as_slice())?.msg,
-                        &funds
-                            .iter()
-                            .map(|coin| Coin {
-                                denom: coin.denom.clone(),
-                                amount: cw_types_v010::math::Uint128(coin.amount.u128()),
-                            })
-                            .collect::<Vec<Coin>>()[..],
+                        &funds_to_sign(
+                            &funds
+                                .iter()
+                                .map(|coin| Coin {
+                                    denom: coin.denom.clone(),
+                                    amount: cw_types_v010::math::Uint128(coin.amount.u128()),
+                                })
+                                .collect::<Vec<Coin>>(),
+                            feature_gates,
+                        )?,
                     ));
                 }
                 cw_types_v1::results::WasmMsg::Migrate {
@@ -783,7 +877,7 @@ fn adapt_output_for_reply(
         return Ok(output);
     }
 
-    let encryption_key = calc_encryption_key(&secret_msg.nonce, &secret_msg.user_public_key);
+    let encryption_key = secret_msg.encryption_key()?;
 
     let output_result;
     let should_append_reply_params;
@@ -895,7 +989,9 @@ fn encrypt_v010_wasm_msg(
     wasm_msg: &mut cw_types_v010::types::WasmMsg,
     nonce: IoNonce,
     user_public_key: Ed25519PublicKey,
+    envelope: Envelope,
     contract_addr: &CanonicalAddr,
+    feature_gates: &FeatureGates,
 ) -> Result<(), EnclaveError> {
     match wasm_msg {
         cw_types_v010::types::WasmMsg::Execute {
@@ -919,6 +1015,7 @@ fn encrypt_v010_wasm_msg(
                 Binary(hash_appended_msg).to_base64(),
                 nonce,
                 user_public_key,
+                envelope,
             )?;
 
             msg_to_pass.encrypt_in_place()?;
@@ -927,7 +1024,7 @@ fn encrypt_v010_wasm_msg(
             *callback_sig = Some(create_callback_signature(
                 contract_addr,
                 &msg_to_pass.msg,
-                send,
+                &funds_to_sign(send, feature_gates)?,
             ));
         }
         cw_types_v010::types::WasmMsg::Migrate {
@@ -943,6 +1040,7 @@ fn encrypt_v010_wasm_msg(
                 Binary(hash_appended_msg).to_base64(),
                 nonce,
                 user_public_key,
+                envelope,
             )?;
 
             msg_to_pass.encrypt_in_place()?;
@@ -1035,3 +1133,35 @@ pub fn create_callback_signature(
 pub fn format_generic_error_message(encrypted_err: Value) -> Value {
     json!({"generic_err":{"msg":encrypted_err}})
 }
+
+/// Like `format_generic_error_message`, with the `revert` the contract failed with, if any, next
+/// to the message that only the tx sender can decrypt
+fn format_encrypted_error_message(encrypted_err: Value, revert: Option<Revert>) -> Value {
+    let mut err = format_generic_error_message(encrypted_err);
+    if let Some(revert) = revert {
+        err["generic_err"]["revert"] = json!(revert);
+    }
+    err
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_format_encrypted_error_message() {
+        let formatted = format_encrypted_error_message(Value::String("c2VjcmV0".to_string()), None);
+        // the same as before reverts existed, the kind of the error is only in the ciphertext
+        assert_eq!(formatted, json!({"generic_err":{"msg":"c2VjcmV0"}}));
+
+        let revert = Revert {
+            code: 42,
+            data: Binary(vec![0, 1, 2]),
+        };
+        let formatted =
+            format_encrypted_error_message(Value::String("c2VjcmV0".to_string()), Some(revert));
+        assert_eq!(
+            formatted["generic_err"]["revert"],
+            json!({"code":42,"data":"AAEC"})
+        );
+    }
+}
@@ -8,13 +8,24 @@ extern crate sgx_tstd as std;
 extern crate sgx_rand;
 extern crate sgx_types;
 
+mod access_list;
+mod contract_encryption;
+mod contract_key_cache;
+mod contract_key_proofs;
 mod contract_operations;
 mod contract_validation;
 mod cosmwasm_config;
+mod cw2;
 mod db;
+mod disclosure;
+mod engine;
+mod envelope;
 mod errors;
+mod event_validation;
 mod execute_message;
+mod execution_receipts;
 pub mod external;
+mod feature_gates;
 mod gas;
 mod ibc_denom_utils;
 mod ibc_message;
@@ -22,21 +33,43 @@ mod input_validation;
 mod io;
 mod message;
 mod message_utils;
+mod node_attestation;
+#[cfg(feature = "observer-mode")]
+mod observers;
 mod query_chain;
+mod query_rewrap;
 mod random;
 mod reply_message;
+mod response_limits;
+mod revert;
+mod sampling;
+mod state_compression;
+mod state_key_decryption;
+mod tx_decryption;
 mod hardcoded_admins;
 pub(crate) mod types;
 #[cfg(feature = "wasm3")]
 pub mod wasm3;
 
 pub use contract_operations::{handle, init, query};
+pub use execution_receipts::{node_signer_binding, NODE_SIGNER_BINDING_LENGTH};
+pub use node_attestation::{
+    set_node_attestation_verifier, NodeAttestation, NodeAttestationVerifier,
+};
 #[cfg(feature = "light-client-validation")]
 pub use contract_validation::{check_cert_in_current_block, check_tx_in_current_block};
 
 #[cfg(feature = "test")]
 pub mod tests {
-    use crate::types;
+    use crate::input_validation::send_funds_validations;
+    use crate::{
+        access_list, contract_key_cache, contract_key_proofs, contract_validation, cw2, envelope,
+        event_validation, execution_receipts, feature_gates, gas, io, query_rewrap, revert,
+        sampling, state_compression, state_key_decryption, tx_decryption, types,
+    };
+
+    #[cfg(feature = "observer-mode")]
+    use crate::observers;
 
     /// Catch failures like the standard test runner, and print similar information per test.
     /// Tests can only fail by panicking, not by returning a `Result` type.
@@ -62,6 +95,46 @@ pub mod tests {
 
         count_failures!(failures, {
             types::tests::test_new_from_slice();
+            envelope::tests::test_envelope_header_round_trip();
+            envelope::tests::test_unknown_envelope_algorithms_are_rejected();
+            sampling::tests::test_shuffle_is_deterministic_permutation();
+            sampling::tests::test_sampling_rng_depends_on_counter();
+            sampling::tests::test_weighted_sample();
+            sampling::tests::test_encode_sections();
+            execution_receipts::tests::test_receipt_signature_verifies();
+            execution_receipts::tests::test_query_response_signature_verifies();
+            execution_receipts::tests::test_contract_query_mac_depends_on_keys();
+            contract_validation::tests::test_predictable_contract_address();
+            contract_key_cache::tests::test_validated_keys_must_match();
+            contract_key_cache::tests::test_invalidate_contract();
+            contract_key_cache::tests::test_state_key_is_derived_once_per_seed();
+            contract_key_proofs::tests::test_contract_key_proof_verifies();
+            query_rewrap::tests::test_swap_code_hash();
+            query_rewrap::tests::test_check_grace_window();
+            query_rewrap::tests::test_check_migration_height();
+            state_compression::tests::test_state_compression_roundtrip();
+            state_compression::tests::test_state_compression_skips_small_values();
+            state_key_decryption::tests::test_decrypt_keys_with();
+            event_validation::tests::test_reserved_event_types_are_rejected();
+            event_validation::tests::test_event_validation_is_gated();
+            event_validation::tests::test_reserved_plaintext_attribute_keys_are_rejected();
+            io::tests::test_format_encrypted_error_message();
+            revert::tests::test_parse_revert();
+            cw2::tests::test_parse_contract_version();
+            cw2::tests::test_compare_versions();
+            cw2::tests::test_check_version_upgrade();
+            feature_gates::tests::test_extract_feature_gates();
+            access_list::tests::test_extract_access_list();
+            send_funds_validations::tests::test_normalize_funds();
+            gas::tests::test_gas_forwarding();
+            tx_decryption::tests::test_split_msgs();
+            tx_decryption::tests::test_malformed_msgs_are_reported();
+        });
+
+        #[cfg(feature = "observer-mode")]
+        count_failures!(failures, {
+            observers::tests::test_extract_events_drops_data_and_messages();
+            observers::tests::test_observer_must_be_approved_and_named_by_the_contract();
         });
 
         if failures != 0 {
@@ -3,6 +3,7 @@ use log::trace;
 use enclave_cosmos_types::types::HandleType;
 use enclave_ffi_types::EnclaveError;
 
+use crate::cosmwasm_config::EntryPoint;
 use crate::execute_message::parse_execute_message;
 use crate::ibc_message::{
     parse_ibc_receive_message, parse_plaintext_ibc_protocol_message,
@@ -24,7 +25,7 @@ pub fn parse_message(
         | HandleType::HANDLE_TYPE_IBC_CHANNEL_CLOSE => {
             trace!(
                 "parsing {} msg (Should always be plaintext): {:?}",
-                HandleType::get_export_name(handle_type),
+                EntryPoint::from_handle_type(handle_type),
                 base64::encode(message)
             );
 
@@ -42,13 +43,5 @@ pub fn parse_message(
 }
 
 pub fn is_ibc_msg(handle_type: HandleType) -> bool {
-    matches!(
-        handle_type,
-        HandleType::HANDLE_TYPE_IBC_CHANNEL_OPEN
-            | HandleType::HANDLE_TYPE_IBC_CHANNEL_CONNECT
-            | HandleType::HANDLE_TYPE_IBC_CHANNEL_CLOSE
-            | HandleType::HANDLE_TYPE_IBC_PACKET_RECEIVE
-            | HandleType::HANDLE_TYPE_IBC_PACKET_ACK
-            | HandleType::HANDLE_TYPE_IBC_PACKET_TIMEOUT
-    )
+    EntryPoint::from_handle_type(&handle_type).is_ibc()
 }
@@ -1,3 +1,4 @@
+use crate::envelope::Envelope;
 use crate::types::{DecryptedSecretMessage, SecretMessage};
 use log::trace;
 
@@ -14,6 +15,7 @@ pub fn get_secret_msg(message: &[u8]) -> SecretMessage {
                 nonce: [0; 32],
                 user_public_key: [0; 32],
                 msg: message.into(),
+                envelope: Envelope::V1,
             }
         }
     }
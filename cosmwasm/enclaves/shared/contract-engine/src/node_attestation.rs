@@ -0,0 +1,38 @@
+use std::sync::SgxRwLock;
+
+use enclave_ffi_types::NodeAuthResult;
+use lazy_static::lazy_static;
+use log::*;
+
+/// What a contract learns about a node from its attestation certificate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeAttestation {
+    /// The node's registration public key, as found in the attestation report
+    pub public_key: Vec<u8>,
+    /// The TCB evaluation data number the platform was attested against
+    pub tcb_level: u16,
+}
+
+/// Verifies an attestation certificate. Remote attestation lives in the enclave crate that links
+/// this engine, which registers its verifier when the enclave is loaded.
+pub type NodeAttestationVerifier = fn(&[u8]) -> Result<NodeAttestation, NodeAuthResult>;
+
+lazy_static! {
+    static ref NODE_ATTESTATION_VERIFIER: SgxRwLock<Option<NodeAttestationVerifier>> =
+        SgxRwLock::new(None);
+}
+
+pub fn set_node_attestation_verifier(verifier: NodeAttestationVerifier) {
+    *NODE_ATTESTATION_VERIFIER.write().unwrap() = Some(verifier);
+}
+
+pub fn verify_node_attestation(cert: &[u8]) -> Result<NodeAttestation, NodeAuthResult> {
+    let verifier = *NODE_ATTESTATION_VERIFIER.read().unwrap();
+    match verifier {
+        Some(verify) => verify(cert),
+        None => {
+            error!("No node attestation verifier was registered");
+            Err(NodeAuthResult::InvalidCert)
+        }
+    }
+}
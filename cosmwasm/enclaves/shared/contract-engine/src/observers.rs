@@ -0,0 +1,207 @@
+//! Observer mode: streaming the events of a contract to a party authorized to see them.
+//!
+//! Some contract operators need to see the events of their own contract, e.g. to run a private
+//! dashboard, without the network decrypting anything for anyone else. An enclave built with the
+//! `observer-mode` feature encrypts the attributes and events of every execution of an observed
+//! contract to that contract's observer, and hands the result to the host, which streams it. The
+//! rest of the output of the execution, and the executions of other contracts, are untouched.
+//!
+//! A contract is observed when two parties agree on its observer: governance, which approves it in
+//! the `contract_observers` param of the compute module, passed to the enclave in the env of every
+//! call, and the contract itself, which names it as the 32 raw bytes of a custom wasm section named
+//! `observer` in its code. The host can pass any env, so it can't pick an observer on its own, and
+//! a contract can't be observed without governance knowing.
+//!
+//! The events are encrypted with AES-SIV under a key derived with HKDF, with
+//! `OBSERVED_EVENTS_ENCRYPTION_KEY_LABEL` as info, from the Diffie-Hellman secret of the current
+//! consensus io exchange keypair and the observer's key, as disclosure bundles are. They are
+//! streamed whether or not the transaction succeeds in the end.
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use cw_types_v010::encoding::Binary;
+use cw_types_v010::types::{CanonicalAddr, HumanAddr};
+use enclave_crypto::{ct_eq, hkdf_sha_256, SIVEncryptable, KEY_MANAGER, PUBLIC_KEY_SIZE};
+use enclave_ffi_types::EnclaveError;
+use sgx_types::sgx_status_t;
+
+use crate::external::ocalls;
+
+pub const OBSERVED_EVENTS_VERSION: u8 = 1;
+
+pub const OBSERVER_SECTION: &str = "observer";
+
+const OBSERVED_EVENTS_ENCRYPTION_KEY_LABEL: &[u8] = b"secret_observed_events_encryption_key";
+
+/// The fields of a contract response that are its events, in v0.10 and v1 responses
+const EVENT_FIELDS: [&str; 3] = ["log", "attributes", "events"];
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ObservedEvents {
+    pub version: u8,
+    pub contract_address: HumanAddr,
+    pub block_height: u64,
+    pub observer_public_key: Binary,
+    /// The consensus io exchange public key the events were encrypted with
+    pub encryption_public_key: Binary,
+    /// AES-SIV encrypted json of the event fields of the response
+    pub encrypted_events: Binary,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvWithObserver {
+    #[serde(default)]
+    observer: Option<Binary>,
+}
+
+/// Extract the observer governance approved for the contract from the env parameter, like the
+/// query_depth
+fn extract_approved_observer(env: &[u8]) -> Option<Vec<u8>> {
+    serde_json::from_slice::<EnvWithObserver>(env)
+        .map_err(|err| warn!("error while deserializing env into json: {}", err))
+        .ok()?
+        .observer
+        .map(|observer| observer.0)
+}
+
+/// The observer of the contract, if the one its code names is the one governance approved
+fn observer_of(
+    env: &[u8],
+    contract_observer: Option<[u8; PUBLIC_KEY_SIZE]>,
+) -> Option<[u8; PUBLIC_KEY_SIZE]> {
+    let contract_observer = contract_observer?;
+    let approved_observer = extract_approved_observer(env)?;
+    if !ct_eq(&approved_observer, &contract_observer) {
+        debug!("the observer of the contract is not the one governance approved");
+        return None;
+    }
+    Some(contract_observer)
+}
+
+/// Keeps the event fields of the raw output of an execution, dropping its data and messages
+fn extract_events(output: &[u8]) -> Result<Value, EnclaveError> {
+    let output: Value = serde_json::from_slice(output).map_err(|err| {
+        warn!(
+            "got an error while parsing the output for observers: {:?}",
+            err
+        );
+        EnclaveError::FailedToDeserialize
+    })?;
+
+    let mut events = serde_json::Map::new();
+    if let Some(Value::Object(response)) = output.get("ok") {
+        for field in EVENT_FIELDS.iter() {
+            if let Some(value) = response.get(*field) {
+                events.insert(field.to_string(), value.clone());
+            }
+        }
+    }
+    Ok(Value::Object(events))
+}
+
+fn encrypt_events(
+    contract_address: &CanonicalAddr,
+    block_height: u64,
+    observer_public_key: &[u8; PUBLIC_KEY_SIZE],
+    output: &[u8],
+) -> Result<ObservedEvents, EnclaveError> {
+    let io_keypair = KEY_MANAGER
+        .get_consensus_io_exchange_keypair()
+        .map_err(|_| EnclaveError::FailedUnseal)?
+        .current;
+    let encryption_key = hkdf_sha_256(
+        &io_keypair.diffie_hellman(observer_public_key),
+        &[OBSERVED_EVENTS_ENCRYPTION_KEY_LABEL],
+    );
+
+    let plaintext = serde_json::to_vec(&extract_events(output)?).map_err(|err| {
+        warn!("got an error while serializing observed events: {:?}", err);
+        EnclaveError::FailedToSerialize
+    })?;
+
+    // Bind the ciphertext to the contract and block of the execution
+    let block_height_bytes = block_height.to_be_bytes();
+    let ad: Vec<&[u8]> = vec![contract_address.as_slice(), &block_height_bytes];
+    let encrypted_events = encryption_key
+        .encrypt_siv(&plaintext, Some(&ad))
+        .map_err(|_| EnclaveError::EncryptionError)?;
+
+    Ok(ObservedEvents {
+        version: OBSERVED_EVENTS_VERSION,
+        contract_address: HumanAddr::from_canonical(contract_address)
+            .map_err(|_| EnclaveError::FailedToSerialize)?,
+        block_height,
+        observer_public_key: Binary(observer_public_key.to_vec()),
+        encryption_public_key: Binary(io_keypair.get_pubkey().to_vec()),
+        encrypted_events: Binary(encrypted_events),
+    })
+}
+
+/// Encrypts the events in `output`, the raw output of an execution of the contract, to the
+/// contract's observer and sends them to the host, if the contract has one. `contract_observer` is
+/// the observer the code of the contract names, `env` the env of the execution.
+///
+/// As with receipts, failing to do so doesn't fail the execution.
+pub fn report_observed_events(
+    contract_address: &CanonicalAddr,
+    block_height: u64,
+    output: &[u8],
+    env: &[u8],
+    contract_observer: Option<[u8; PUBLIC_KEY_SIZE]>,
+) {
+    let observer_public_key = match observer_of(env, contract_observer) {
+        Some(observer_public_key) => observer_public_key,
+        None => return,
+    };
+
+    let record = encrypt_events(contract_address, block_height, &observer_public_key, output)
+        .and_then(|observed| {
+            serde_json::to_vec(&observed).map_err(|_| EnclaveError::FailedToSerialize)
+        });
+    let record = match record {
+        Ok(record) => record,
+        Err(err) => {
+            warn!("Failed to encrypt observed events: {}", err);
+            return;
+        }
+    };
+
+    let status = unsafe { ocalls::ocall_report_observed_events(record.as_ptr(), record.len()) };
+    if status != sgx_status_t::SGX_SUCCESS {
+        warn!("Failed to report observed events to the host: {}", status);
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_extract_events_drops_data_and_messages() {
+        let output = br#"{"ok":{"messages":[{"id":1}],"attributes":[{"key":"a","value":"b"}],"events":[],"data":"ZGF0YQ=="}}"#;
+
+        let events = extract_events(output).unwrap();
+        assert_eq!(
+            events,
+            serde_json::json!({"attributes":[{"key":"a","value":"b"}],"events":[]})
+        );
+
+        let failed = extract_events(br#"{"err":{"generic_err":{"msg":"x"}}}"#).unwrap();
+        assert_eq!(failed, serde_json::json!({}));
+    }
+
+    pub fn test_observer_must_be_approved_and_named_by_the_contract() {
+        let observer = [7u8; PUBLIC_KEY_SIZE];
+        let env = format!(r#"{{"observer":"{}"}}"#, base64::encode(observer));
+
+        assert_eq!(observer_of(env.as_bytes(), Some(observer)), Some(observer));
+        // not named by the contract, or not approved
+        assert_eq!(observer_of(env.as_bytes(), None), None);
+        assert_eq!(observer_of(b"{}", Some(observer)), None);
+        assert_eq!(
+            observer_of(env.as_bytes(), Some([8u8; PUBLIC_KEY_SIZE])),
+            None
+        );
+    }
+}
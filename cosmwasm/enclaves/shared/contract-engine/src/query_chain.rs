@@ -8,6 +8,7 @@ use enclave_crypto::Ed25519PublicKey;
 use enclave_utils::recursion_depth;
 
 use super::errors::WasmEngineError;
+use crate::envelope::Envelope;
 use crate::external::{ecalls, ocalls};
 use crate::types::{IoNonce, SecretMessage};
 
@@ -279,6 +280,7 @@ fn encrypt_query_request(
             msg: hash_appended_msg,
             user_public_key,
             nonce,
+            envelope: Envelope::V1,
         };
         encrypted_msg.encrypt_in_place().map_err(|err| {
             debug!(
@@ -308,6 +310,7 @@ fn decrypt_query_response(
         nonce,
         user_public_key,
         msg: response,
+        envelope: Envelope::V1,
     };
 
     let b64_decrypted = as_secret_msg.decrypt().map_err(|err| {
@@ -338,6 +341,7 @@ fn decrypt_query_response_error(
         nonce,
         user_public_key,
         msg: error,
+        envelope: Envelope::V1,
     };
 
     error_msg.decrypt().map_err(|err| {
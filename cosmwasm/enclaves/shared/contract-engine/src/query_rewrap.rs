@@ -0,0 +1,221 @@
+//! Re-wrapping of queries sent to a contract just before it was migrated.
+//!
+//! Users prepend the code hash of the contract to their encrypted messages, and the enclave rejects
+//! messages with any other code hash. A migration changes the code hash and the contract key, so
+//! queries built for the previous code fail until clients learn about the migration.
+//!
+//! For `QueryRewrapGraceBlocks` blocks after a migration, a param of the compute module, the host
+//! can have the enclave re-wrap such a query, if the user opted in by starting the plaintext of the
+//! query with `REWRAP_OPT_IN`: the enclave checks that the previous code hash and contract key were
+//! really those of the contract, decrypts the query, swaps the previous code hash for the current
+//! one and encrypts it again for the same user, so the response still decrypts with the user's key.
+//! Queries run with the opt-in stripped, so it can always be sent.
+//!
+//! The height of the migration is checked against the current key of the contract, which starts
+//! with the hash of the migrator and the height it was generated at, and the current height is the
+//! one of the last block the enclave verified, so the host can't move the window.
+
+use log::*;
+
+use cw_types_v010::types::CanonicalAddr;
+use enclave_crypto::HASH_SIZE;
+use enclave_ffi_types::EnclaveError;
+
+use crate::contract_key_cache::ContractKeys;
+use crate::contract_validation::{generate_sender_id, validate_contract_keys};
+use crate::types::SecretMessage;
+
+#[cfg(feature = "light-client-validation")]
+use block_verifier::VERIFIED_BLOCK_MESSAGES;
+
+const HEX_ENCODED_HASH_SIZE: usize = HASH_SIZE * 2;
+
+/// Prepended to the plaintext of a query, before the code hash, to allow re-wrapping it
+pub const REWRAP_OPT_IN: &[u8] = b"rewrap:";
+
+/// Strips the opt-in to re-wrapping from the plaintext of a query
+pub fn strip_rewrap_opt_in(plaintext: &[u8]) -> &[u8] {
+    plaintext.strip_prefix(REWRAP_OPT_IN).unwrap_or(plaintext)
+}
+
+/// The height of the last block the enclave verified. Enclaves that don't verify blocks trust the
+/// host for it, like for the rest of the env.
+fn verified_height(host_height: u64) -> u64 {
+    #[cfg(feature = "light-client-validation")]
+    {
+        let _ = host_height;
+        VERIFIED_BLOCK_MESSAGES.lock().unwrap().height()
+    }
+
+    #[cfg(not(feature = "light-client-validation"))]
+    host_height
+}
+
+/// Checks that `migration_height` is at most `grace_blocks` before `current_height`
+fn check_grace_window(
+    migration_height: u64,
+    current_height: u64,
+    grace_blocks: u64,
+) -> Result<(), EnclaveError> {
+    if migration_height > current_height || current_height - migration_height > grace_blocks {
+        debug!(
+            "Migration at height {} is outside of the grace window at height {}",
+            migration_height, current_height
+        );
+        return Err(EnclaveError::ValidationFailure);
+    }
+    Ok(())
+}
+
+/// Checks that `migrator` generated `current_contract_key` at `migration_height`. The key must be
+/// validated already.
+fn check_migration_height(
+    current_contract_key: &[u8],
+    migrator: &CanonicalAddr,
+    migration_height: u64,
+) -> Result<(), EnclaveError> {
+    if current_contract_key[..HASH_SIZE] != generate_sender_id(&(migrator.0).0, &migration_height) {
+        warn!("Contract key was not generated by the migrator at the migration height");
+        return Err(EnclaveError::ValidationFailure);
+    }
+    Ok(())
+}
+
+/// Replaces `previous_code_hash` after the opt-in at the start of a decrypted message with
+/// `current_code_hash`
+fn swap_code_hash(
+    plaintext: &[u8],
+    previous_code_hash: &[u8; HASH_SIZE],
+    current_code_hash: &[u8; HASH_SIZE],
+) -> Result<Vec<u8>, EnclaveError> {
+    let plaintext = plaintext.strip_prefix(REWRAP_OPT_IN).ok_or_else(|| {
+        debug!("Message did not opt in to be re-wrapped");
+        EnclaveError::ValidationFailure
+    })?;
+    if plaintext.len() < HEX_ENCODED_HASH_SIZE {
+        warn!("Malformed message - expected contract code hash to be prepended to the msg");
+        return Err(EnclaveError::ValidationFailure);
+    }
+
+    // hex::decode accepts both cases, like the validation of messages
+    let received_code_hash = hex::decode(&plaintext[..HEX_ENCODED_HASH_SIZE]).map_err(|_| {
+        warn!("Got message with malformed contract hash");
+        EnclaveError::ValidationFailure
+    })?;
+    if received_code_hash != previous_code_hash {
+        debug!("Message is not addressed to the previous code of the contract");
+        return Err(EnclaveError::ValidationFailure);
+    }
+
+    let mut rewrapped = REWRAP_OPT_IN.to_vec();
+    rewrapped.extend_from_slice(hex::encode(current_code_hash).as_bytes());
+    rewrapped.extend_from_slice(&plaintext[HEX_ENCODED_HASH_SIZE..]);
+    Ok(rewrapped)
+}
+
+/// Re-wraps `msg`, an encrypted query addressed to the previous code of a migrated contract, for
+/// its current code.
+///
+/// `previous_keys` are the keys of the contract when it ran `previous_code_hash`, and
+/// `current_keys` the ones `migrator` generated when it migrated it at `migration_height`.
+/// `current_height` is only used by enclaves that don't verify blocks.
+#[allow(clippy::too_many_arguments)]
+pub fn rewrap_query(
+    contract_address: &CanonicalAddr,
+    previous_code_hash: &[u8; HASH_SIZE],
+    previous_keys: &ContractKeys,
+    current_code_hash: &[u8; HASH_SIZE],
+    current_keys: &ContractKeys,
+    migrator: &CanonicalAddr,
+    migration_height: u64,
+    current_height: u64,
+    grace_blocks: u64,
+    msg: &[u8],
+) -> Result<Vec<u8>, EnclaveError> {
+    validate_contract_keys(contract_address, previous_code_hash, previous_keys)?;
+    validate_contract_keys(contract_address, current_code_hash, current_keys)?;
+    if previous_keys.og_contract_key != current_keys.og_contract_key {
+        warn!("Previous and current keys are of different contracts");
+        return Err(EnclaveError::ValidationFailure);
+    }
+    let (current_contract_key, _) = current_keys.current_contract_key.ok_or_else(|| {
+        warn!("Got the keys of a contract that was never migrated");
+        EnclaveError::ValidationFailure
+    })?;
+    check_migration_height(&current_contract_key, migrator, migration_height)?;
+    check_grace_window(
+        migration_height,
+        verified_height(current_height),
+        grace_blocks,
+    )?;
+
+    let mut secret_msg = SecretMessage::from_slice(msg)?;
+    let plaintext = secret_msg
+        .try_decrypt_uncached()
+        .ok_or(EnclaveError::DecryptionError)?;
+
+    secret_msg.msg = swap_code_hash(&plaintext, previous_code_hash, current_code_hash)?;
+    secret_msg.encrypt_in_place()?;
+
+    trace!(
+        "re-wrapped query from code hash {:?} to {:?}",
+        hex::encode(previous_code_hash),
+        hex::encode(current_code_hash)
+    );
+
+    Ok(secret_msg.to_vec())
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_swap_code_hash() {
+        let previous = [1u8; HASH_SIZE];
+        let current = [2u8; HASH_SIZE];
+
+        let mut plaintext = REWRAP_OPT_IN.to_vec();
+        plaintext.extend_from_slice(hex::encode_upper(previous).as_bytes());
+        plaintext.extend_from_slice(b"{\"balance\":{}}");
+
+        let mut expected = REWRAP_OPT_IN.to_vec();
+        expected.extend_from_slice(hex::encode(current).as_bytes());
+        expected.extend_from_slice(b"{\"balance\":{}}");
+        assert_eq!(
+            swap_code_hash(&plaintext, &previous, &current).unwrap(),
+            expected
+        );
+
+        // already addressed to the current code
+        assert!(swap_code_hash(&expected, &previous, &current).is_err());
+        // did not opt in
+        assert!(swap_code_hash(&plaintext[REWRAP_OPT_IN.len()..], &previous, &current).is_err());
+        assert!(swap_code_hash(b"rewrap:{}", &previous, &current).is_err());
+
+        assert_eq!(
+            strip_rewrap_opt_in(&expected),
+            &expected[REWRAP_OPT_IN.len()..]
+        );
+        assert_eq!(strip_rewrap_opt_in(b"{}"), b"{}");
+    }
+
+    pub fn test_check_grace_window() {
+        assert!(check_grace_window(1000, 1000, 100).is_ok());
+        assert!(check_grace_window(1000, 1100, 100).is_ok());
+        assert!(check_grace_window(1000, 1101, 100).is_err());
+        assert!(check_grace_window(1000, 1001, 0).is_err());
+        // a migration in the future
+        assert!(check_grace_window(1001, 1000, 100).is_err());
+    }
+
+    pub fn test_check_migration_height() {
+        let migrator = CanonicalAddr::from_vec(vec![7u8; 20]);
+        let mut current_contract_key = [0u8; 64];
+        current_contract_key[..HASH_SIZE].copy_from_slice(&generate_sender_id(&[7u8; 20], &1000));
+
+        assert!(check_migration_height(&current_contract_key, &migrator, 1000).is_ok());
+        assert!(check_migration_height(&current_contract_key, &migrator, 1001).is_err());
+        let other = CanonicalAddr::from_vec(vec![8u8; 20]);
+        assert!(check_migration_height(&current_contract_key, &other, 1000).is_err());
+    }
+}
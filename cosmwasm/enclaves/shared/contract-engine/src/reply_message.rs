@@ -54,6 +54,7 @@ fn get_data_from_reply(
             let tmp_secret_msg_data = SecretMessage {
                 nonce: input_msg.nonce,
                 user_public_key: input_msg.user_public_key,
+                envelope: input_msg.envelope.clone(),
                 msg: data.as_slice().to_vec(),
             };
 
@@ -84,6 +85,7 @@ fn parse_message_id_of_encrypted_reply(
     let tmp_secret_msg_id = SecretMessage {
         nonce: input_msg.nonce,
         user_public_key: input_msg.user_public_key,
+        envelope: input_msg.envelope.clone(),
         msg: parsed_reply.id.as_slice().to_vec(),
     };
 
@@ -152,6 +154,7 @@ fn wrap_results_as_parsed_message(
     let reply_secret_msg = SecretMessage {
         nonce: input_msg.nonce,
         user_public_key: input_msg.user_public_key,
+        envelope: input_msg.envelope.clone(),
         msg: serialized_encrypted_reply,
     };
 
@@ -195,6 +198,7 @@ fn parse_encrypted_error_reply(
     let secret_msg = SecretMessage {
         nonce: input_msg.nonce,
         user_public_key: input_msg.user_public_key,
+        envelope: input_msg.envelope.clone(),
         msg: base64::decode(response.clone()).map_err(|err| {
             warn!(
                 "got an error while trying to serialize err reply from base64 {:?}: {}",
@@ -271,6 +275,7 @@ fn parse_plaintext_reply_message(
     let reply_secret_msg = SecretMessage {
         nonce: input_msg.nonce,
         user_public_key: input_msg.user_public_key,
+        envelope: input_msg.envelope.clone(),
         msg: serialized_reply,
     };
 
@@ -0,0 +1,95 @@
+//! Size limits on what contracts return, set by the params of the compute module and passed to
+//! the enclave in the env of every call.
+//!
+//! They protect nodes from contracts returning tens of MB of data, which would otherwise be
+//! encrypted, copied out of the enclave and kept in memory by the node.
+
+use log::*;
+use serde::Deserialize;
+
+use enclave_ffi_types::EnclaveError;
+
+use crate::io::RawWasmOutput;
+
+/// The depth of queries sent directly to the node, as opposed to by a contract
+pub const TOP_LEVEL_QUERY_DEPTH: u32 = 1;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ResponseLimits {
+    /// Largest `data` of instantiate, execute and migrate responses. Zero means unlimited.
+    #[serde(default)]
+    max_response_data_size: u64,
+    /// Largest response to a query sent directly to the node. Zero means unlimited.
+    #[serde(default)]
+    max_query_response_size: u64,
+}
+
+/// Extract the response limits from the env parameter, like the query_depth. An env without
+/// them, as sent before the params existed, has no limits.
+pub fn extract_response_limits(env: &[u8]) -> Result<ResponseLimits, EnclaveError> {
+    serde_json::from_slice::<ResponseLimits>(env).map_err(|err| {
+        warn!(
+            "error while deserializing env into json {:?}: {}",
+            String::from_utf8_lossy(env),
+            err
+        );
+        EnclaveError::FailedToDeserialize
+    })
+}
+
+fn check_size(size: usize, limit: u64, what: &str) -> Result<(), EnclaveError> {
+    if limit != 0 && size as u64 > limit {
+        debug!(
+            "{} of {} bytes exceeds the limit of {} bytes",
+            what, size, limit
+        );
+        return Err(EnclaveError::ResponseTooLarge);
+    }
+    Ok(())
+}
+
+/// Checks the output of a contract, before it is encrypted, against the limits, failing with
+/// `EnclaveError::ResponseTooLarge` when it exceeds one.
+///
+/// Queries contracts make to each other, including during transactions, are part of the call
+/// that made them and are never limited, only the response the node gets back is.
+pub fn enforce_response_limits(
+    output: &RawWasmOutput,
+    limits: &ResponseLimits,
+    query_depth: u32,
+) -> Result<(), EnclaveError> {
+    match output {
+        RawWasmOutput::QueryOkV010 { ok } | RawWasmOutput::QueryOkV1 { ok } => {
+            if query_depth != TOP_LEVEL_QUERY_DEPTH {
+                return Ok(());
+            }
+            let limit = limits.max_query_response_size;
+            // `ok` is base64, so its length is an upper bound of the response size
+            if limit == 0 || (ok.len() * 3 / 4) as u64 <= limit {
+                return Ok(());
+            }
+
+            let response = base64::decode(ok.as_bytes()).map_err(|err| {
+                warn!("query response is not valid base64: {:?}", err);
+                EnclaveError::FailedToDeserialize
+            })?;
+            check_size(response.len(), limit, "query response")
+        }
+        RawWasmOutput::OkV010 { ok, .. } => {
+            check_data_size(ok.data.as_ref().map(|d| d.len()), limits)
+        }
+        RawWasmOutput::OkV1 { ok, .. } => {
+            check_data_size(ok.data.as_ref().map(|d| d.len()), limits)
+        }
+        RawWasmOutput::Err { .. }
+        | RawWasmOutput::OkIBCPacketReceive { .. }
+        | RawWasmOutput::OkIBCOpenChannel { .. } => Ok(()),
+    }
+}
+
+fn check_data_size(data_size: Option<usize>, limits: &ResponseLimits) -> Result<(), EnclaveError> {
+    match data_size {
+        Some(size) => check_size(size, limits.max_response_data_size, "response data"),
+        None => Ok(()),
+    }
+}
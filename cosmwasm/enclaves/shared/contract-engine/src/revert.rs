@@ -0,0 +1,90 @@
+//! Structured revert reasons: a contract that fails with an error message of the form
+//! `{"revert":{"code":42,"data":"<base64>"}}` gives clients a machine-readable reason, e.g. an
+//! ABI-encoded payload a frontend can decode, instead of a string to parse.
+//!
+//! The error of a contract is encrypted for the tx sender. The revert is returned next to it in
+//! plaintext, so the node can emit it in events. Contracts opt into publishing it by using the
+//! convention; any other error message stays private. Nothing is published until governance
+//! activates `feature_gates::REVERT_REASONS`, as existing contracts may already fail with messages
+//! of that form.
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use cw_types_v010::encoding::Binary;
+
+/// Longer reverts are not made public, the error is only returned encrypted
+pub const MAX_REVERT_DATA_SIZE: usize = 1024;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Revert {
+    pub code: u32,
+    #[serde(default)]
+    pub data: Binary,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RevertMessage {
+    revert: Revert,
+}
+
+/// The revert a contract failed with, found in the message of its error: the error itself for v1
+/// contracts, the message of a `generic_err` for v0.10 ones. `None` if it doesn't follow the
+/// convention.
+pub fn parse_revert(err: &Value) -> Option<Revert> {
+    let msg = match err {
+        Value::String(msg) => msg.as_str(),
+        _ => err.get("generic_err")?.get("msg")?.as_str()?,
+    };
+    let revert = serde_json::from_str::<RevertMessage>(msg).ok()?.revert;
+    if revert.data.len() > MAX_REVERT_DATA_SIZE {
+        debug!(
+            "revert data of {} bytes exceeds the limit of {} bytes",
+            revert.data.len(),
+            MAX_REVERT_DATA_SIZE
+        );
+        return None;
+    }
+    Some(revert)
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+    use serde_json::json;
+
+    pub fn test_parse_revert() {
+        let expected = Some(Revert {
+            code: 42,
+            data: Binary(vec![0, 1, 2]),
+        });
+        let msg = r#"{"revert":{"code":42,"data":"AAEC"}}"#;
+        assert_eq!(parse_revert(&json!(msg)), expected);
+        assert_eq!(parse_revert(&json!({"generic_err":{"msg":msg}})), expected);
+        assert_eq!(
+            parse_revert(&json!(r#"{"revert":{"code":7}}"#)),
+            Some(Revert {
+                code: 7,
+                data: Binary(vec![]),
+            })
+        );
+
+        assert_eq!(parse_revert(&json!("insufficient funds")), None);
+        assert_eq!(parse_revert(&json!({"not_found":{"kind":msg}})), None);
+        assert_eq!(
+            parse_revert(&json!(r#"{"revert":{"code":-1,"data":""}}"#)),
+            None
+        );
+        assert_eq!(
+            parse_revert(&json!(r#"{"revert":{"code":1},"other":1}"#)),
+            None
+        );
+        let long = format!(
+            r#"{{"revert":{{"code":1,"data":"{}"}}}}"#,
+            base64::encode(vec![0u8; MAX_REVERT_DATA_SIZE + 1])
+        );
+        assert_eq!(parse_revert(&json!(long)), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! Deterministic shuffling and weighted sampling for contracts, seeded from the block random the
+//! contract received in its env.
+//!
+//! Every call draws from its own ChaCha stream, derived from the random and the number of previous
+//! calls in the same execution, so two shuffles in one message don't return the same permutation
+//! while every node still computes the same result.
+
+use cw_types_v010::encoding::Binary;
+use log::*;
+use rand_chacha::ChaChaRng;
+use rand_core::{RngCore, SeedableRng};
+
+const SAMPLING_RNG_INFO: &[u8] = b"contract_sampling";
+
+/// Error codes returned to the contract in the high half of the result
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SamplingError {
+    /// The contract did not receive a block random, e.g. in a query or a v0.10 contract
+    RandomUnavailable = 1,
+    InvalidInput = 2,
+}
+
+pub fn sampling_rng(random: &Binary, call_counter: u64) -> ChaChaRng {
+    let counter_bytes = call_counter.to_be_bytes();
+    let seed = enclave_crypto::hkdf_sha_256(
+        random.as_slice(),
+        &[SAMPLING_RNG_INFO, counter_bytes.as_slice()],
+    );
+    ChaChaRng::from_seed(*seed.get())
+}
+
+/// A uniform number in `0..bound`, without the bias of a plain modulo
+fn uniform_below(rng: &mut ChaChaRng, bound: u128) -> u128 {
+    debug_assert!(bound > 0);
+    // Draw 64 bits when they are enough, so small bounds consume the stream like a u64 would
+    let wide = bound > u64::MAX as u128;
+    let max = if wide { u128::MAX } else { u64::MAX as u128 };
+    // Anything at or above the largest multiple of `bound` is rejected
+    let zone = max - (max % bound);
+    loop {
+        let value = if wide {
+            ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128
+        } else {
+            rng.next_u64() as u128
+        };
+        if value < zone {
+            return value % bound;
+        }
+    }
+}
+
+/// Fisher-Yates shuffle
+pub fn shuffle<T>(items: &mut [T], rng: &mut ChaChaRng) {
+    for i in (1..items.len()).rev() {
+        let j = uniform_below(rng, i as u128 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Picks `count` distinct indices, each draw choosing among the remaining indices with probability
+/// proportional to its weight. Indices with a weight of zero are never picked.
+pub fn weighted_sample(
+    weights: &[u64],
+    count: usize,
+    rng: &mut ChaChaRng,
+) -> Result<Vec<u32>, SamplingError> {
+    if weights.len() > u32::MAX as usize {
+        return Err(SamplingError::InvalidInput);
+    }
+
+    let mut remaining: Vec<(u32, u64)> = weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .map(|(index, weight)| (index as u32, *weight))
+        .collect();
+
+    if count > remaining.len() {
+        debug!(
+            "weighted_sample() asked for {} samples out of {} non zero weights",
+            count,
+            remaining.len()
+        );
+        return Err(SamplingError::InvalidInput);
+    }
+
+    let mut total: u128 = remaining.iter().map(|(_, weight)| *weight as u128).sum();
+    let mut picked = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut target = uniform_below(rng, total);
+        let position = remaining
+            .iter()
+            .position(|(_, weight)| {
+                if target < *weight as u128 {
+                    true
+                } else {
+                    target -= *weight as u128;
+                    false
+                }
+            })
+            .ok_or(SamplingError::InvalidInput)?;
+
+        let (index, weight) = remaining.remove(position);
+        total -= weight as u128;
+        picked.push(index);
+    }
+
+    Ok(picked)
+}
+
+/// The inverse of `decode_sections`: every item followed by its length as a big endian u32
+pub fn encode_sections(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(items.iter().map(|item| item.len() + 4).sum());
+    for item in items {
+        result.extend_from_slice(item);
+        result.extend_from_slice(&(item.len() as u32).to_be_bytes());
+    }
+    result
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    fn test_rng() -> ChaChaRng {
+        sampling_rng(&Binary(vec![7u8; 32]), 0)
+    }
+
+    pub fn test_shuffle_is_deterministic_permutation() {
+        let mut a: Vec<u32> = (0..100).collect();
+        let mut b = a.clone();
+        shuffle(&mut a, &mut test_rng());
+        shuffle(&mut b, &mut test_rng());
+        assert_eq!(a, b);
+
+        let mut sorted = a.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..100).collect::<Vec<u32>>());
+        assert_ne!(a, sorted);
+    }
+
+    pub fn test_sampling_rng_depends_on_counter() {
+        let random = Binary(vec![7u8; 32]);
+        assert_ne!(
+            sampling_rng(&random, 0).next_u64(),
+            sampling_rng(&random, 1).next_u64()
+        );
+    }
+
+    pub fn test_weighted_sample() {
+        let weights = [5, 0, 1, u64::MAX, 0, 3];
+        let picked = weighted_sample(&weights, 4, &mut test_rng()).unwrap();
+        assert_eq!(
+            picked,
+            weighted_sample(&weights, 4, &mut test_rng()).unwrap()
+        );
+
+        let mut sorted = picked.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 2, 3, 5]);
+
+        assert_eq!(
+            weighted_sample(&weights, 5, &mut test_rng()),
+            Err(SamplingError::InvalidInput)
+        );
+        assert_eq!(weighted_sample(&[], 0, &mut test_rng()), Ok(vec![]));
+    }
+
+    pub fn test_encode_sections() {
+        let encoded = encode_sections(&[b"ab".to_vec(), vec![]]);
+        assert_eq!(encoded, vec![b'a', b'b', 0, 0, 0, 2, 0, 0, 0, 0]);
+    }
+}
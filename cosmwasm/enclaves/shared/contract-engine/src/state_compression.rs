@@ -0,0 +1,177 @@
+//! Compression of contract state values before they are encrypted.
+//!
+//! Contracts storing large json values can opt in by embedding an empty custom wasm section named
+//! `state_compression` in their code. Compression is activated by governance at a height, see
+//! `feature_gates::STATE_COMPRESSION`, and charged per byte compressed or decompressed.
+//!
+//! Every node must produce the same ciphertext for the same write, so values are compressed at a
+//! fixed level with a pinned zstd version and a dictionary shipped in the enclave, and only kept
+//! compressed when that makes them smaller.
+//!
+//! Compressed values are marked in their encryption salt, which is stored in plaintext and
+//! authenticated with the value, as `marker | dictionary version | salt`. Regular salts start with
+//! the block timestamp, so they never start with the marker. Dictionaries are never changed or
+//! removed, a new one gets the next version, so values stay readable whatever the code of the
+//! contract is migrated to.
+
+use log::*;
+
+use crate::errors::WasmEngineError;
+
+pub const STATE_COMPRESSION_SECTION: &str = "state_compression";
+
+/// Never change this, or nodes with different versions will write different state
+const COMPRESSION_LEVEL: i32 = 3;
+const COMPRESSED_SALT_MARKER: &[u8; 4] = b"zstd";
+const COMPRESSED_SALT_HEADER_SIZE: usize = COMPRESSED_SALT_MARKER.len() + 1;
+
+/// The raw zstd dictionaries values were ever compressed with, by version
+const DICTIONARIES: &[(u8, &[u8])] = &[(1, include_bytes!("state_compression/dictionary_v1.json"))];
+/// The version of the dictionary new values are compressed with
+const CURRENT_DICTIONARY_VERSION: u8 = 1;
+
+fn dictionary(version: u8) -> Option<&'static [u8]> {
+    DICTIONARIES
+        .iter()
+        .find(|(dictionary_version, _)| *dictionary_version == version)
+        .map(|(_, dictionary)| *dictionary)
+}
+
+fn compress(value: &[u8], dictionary: &[u8]) -> Option<Vec<u8>> {
+    let mut compressed = vec![0u8; zstd_safe::compress_bound(value.len())];
+    let size = zstd_safe::CCtx::create()
+        .compress_using_dict(&mut compressed[..], value, dictionary, COMPRESSION_LEVEL)
+        .map_err(|code| {
+            warn!(
+                "failed to compress a state value: {}",
+                zstd_safe::get_error_name(code)
+            )
+        })
+        .ok()?;
+    compressed.truncate(size);
+    Some(compressed)
+}
+
+fn decompress(compressed: &[u8], dictionary: &[u8]) -> Result<Vec<u8>, WasmEngineError> {
+    let size = match zstd_safe::get_frame_content_size(compressed) {
+        Ok(Some(size)) => size as usize,
+        _ => {
+            warn!("compressed state value doesn't hold its size");
+            return Err(WasmEngineError::DecryptionError);
+        }
+    };
+
+    let mut value = vec![0u8; size];
+    let written = zstd_safe::DCtx::create()
+        .decompress_using_dict(&mut value[..], compressed, dictionary)
+        .map_err(|code| {
+            warn!(
+                "failed to decompress a state value: {}",
+                zstd_safe::get_error_name(code)
+            );
+            WasmEngineError::DecryptionError
+        })?;
+    value.truncate(written);
+    Ok(value)
+}
+
+/// Returns the salt and the value to encrypt for a write of `value`.
+///
+/// The value is compressed with the current dictionary if `compression` is set and that makes it
+/// smaller, in which case the salt is marked.
+pub fn compress_state_value(
+    value: &[u8],
+    encryption_salt: &[u8],
+    compression: bool,
+) -> (Vec<u8>, Vec<u8>) {
+    let compressed = if compression {
+        dictionary(CURRENT_DICTIONARY_VERSION).and_then(|dictionary| {
+            let compressed = compress(value, dictionary)?;
+            if compressed.len() + COMPRESSED_SALT_HEADER_SIZE >= value.len() {
+                return None;
+            }
+
+            let mut salt = COMPRESSED_SALT_MARKER.to_vec();
+            salt.push(CURRENT_DICTIONARY_VERSION);
+            salt.extend_from_slice(encryption_salt);
+            Some((salt, compressed))
+        })
+    } else {
+        None
+    };
+
+    compressed.unwrap_or_else(|| (encryption_salt.to_vec(), value.to_vec()))
+}
+
+/// Decompresses `value`, as decrypted with `encryption_salt`, if the salt says it is compressed.
+/// Returns the value and whether it was decompressed.
+pub fn decompress_state_value(
+    value: Vec<u8>,
+    encryption_salt: &[u8],
+) -> Result<(Vec<u8>, bool), WasmEngineError> {
+    if encryption_salt.len() <= COMPRESSED_SALT_HEADER_SIZE
+        || !encryption_salt.starts_with(COMPRESSED_SALT_MARKER)
+    {
+        return Ok((value, false));
+    }
+
+    let version = encryption_salt[COMPRESSED_SALT_MARKER.len()];
+    match dictionary(version) {
+        Some(dictionary) => Ok((decompress(&value, dictionary)?, true)),
+        None => {
+            warn!(
+                "state value was compressed with unknown dictionary version {}",
+                version
+            );
+            Err(WasmEngineError::DecryptionError)
+        }
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_state_compression_roundtrip() {
+        let value = br#"{"owner":"secret1abc","balance":"100","history":[{"amount":"100"},{"amount":"100"},{"amount":"100"},{"amount":"100"}]}"#;
+        let salt = [7u8; 24];
+
+        let (compressed_salt, compressed) = compress_state_value(value, &salt, true);
+        assert!(compressed.len() < value.len());
+        assert_eq!(compressed_salt[COMPRESSED_SALT_MARKER.len()], 1);
+        assert_eq!(&compressed_salt[COMPRESSED_SALT_HEADER_SIZE..], &salt);
+        // deterministic
+        assert_eq!(
+            compress_state_value(value, &salt, true),
+            (compressed_salt.clone(), compressed.clone())
+        );
+
+        assert_eq!(
+            decompress_state_value(compressed.clone(), &compressed_salt).unwrap(),
+            (value.to_vec(), true)
+        );
+
+        let mut unknown_version_salt = compressed_salt;
+        unknown_version_salt[COMPRESSED_SALT_MARKER.len()] = 0;
+        assert!(decompress_state_value(compressed, &unknown_version_salt).is_err());
+    }
+
+    pub fn test_state_compression_skips_small_values() {
+        let salt = [7u8; 24];
+
+        assert_eq!(
+            compress_state_value(b"1", &salt, true),
+            (salt.to_vec(), b"1".to_vec())
+        );
+        // not opted in, or not active yet
+        let repetitive = [b'1'; 40];
+        assert_eq!(
+            compress_state_value(&repetitive, &salt, false),
+            (salt.to_vec(), repetitive.to_vec())
+        );
+        assert_eq!(
+            decompress_state_value(b"1".to_vec(), &salt).unwrap(),
+            (b"1".to_vec(), false)
+        );
+    }
+}
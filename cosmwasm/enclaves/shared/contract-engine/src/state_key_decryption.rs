@@ -0,0 +1,86 @@
+//! Decryption of the keys of a contract's state, for state migration and analytics tooling that
+//! must walk a contract's state in the order of its plaintext keys rather than of their encryption.
+//!
+//! Plaintext keys reveal e.g. which addresses hold a balance in a token contract, so this is only
+//! built into enclaves running in software mode, which don't keep contract state private in the
+//! first place (see the `state-key-decryption` feature), and only once governance activates
+//! `feature_gates::STATE_KEY_DECRYPTION`. Other enclaves refuse the ecall.
+//!
+//! Keys are passed in and out prefixed by their length as a big endian u32. A key that can't be
+//! decrypted, i.e. a key in the old format, which is a hash, is returned with the length
+//! `UNDECRYPTABLE_KEY`. A plaintext key is always shorter than its encryption, so the output never
+//! exceeds the input.
+
+use log::*;
+
+use crate::contract_validation::ContractKey;
+use crate::db::decrypt_state_key;
+
+pub const UNDECRYPTABLE_KEY: u32 = u32::MAX;
+
+/// Decrypts the length prefixed stored keys of the contract with `contract_key`. Returns `None`
+/// if `keys` is truncated.
+pub fn decrypt_state_keys(contract_key: &ContractKey, keys: &[u8]) -> Option<Vec<u8>> {
+    let decrypted = decrypt_keys_with(keys, |key| decrypt_state_key(key, contract_key))?;
+    debug!("Decrypted the state keys of a contract");
+    Some(decrypted)
+}
+
+fn decrypt_keys_with(
+    mut keys: &[u8],
+    decrypt: impl Fn(&[u8]) -> Option<Vec<u8>>,
+) -> Option<Vec<u8>> {
+    let mut decrypted = vec![];
+    while !keys.is_empty() {
+        if keys.len() < 4 {
+            return None;
+        }
+        let (len, rest) = keys.split_at(4);
+        let mut len_bytes = [0u8; 4];
+        len_bytes.copy_from_slice(len);
+        let len = u32::from_be_bytes(len_bytes) as usize;
+        if rest.len() < len {
+            return None;
+        }
+        let (key, rest) = rest.split_at(len);
+        keys = rest;
+
+        match decrypt(key) {
+            Some(plaintext) => {
+                decrypted.extend_from_slice(&(plaintext.len() as u32).to_be_bytes());
+                decrypted.extend_from_slice(&plaintext);
+            }
+            None => decrypted.extend_from_slice(&UNDECRYPTABLE_KEY.to_be_bytes()),
+        }
+    }
+    Some(decrypted)
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    fn prefixed(keys: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = vec![];
+        for key in keys {
+            bytes.extend_from_slice(&(key.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(key);
+        }
+        bytes
+    }
+
+    pub fn test_decrypt_keys_with() {
+        let decrypt = |key: &[u8]| key.strip_prefix(b"enc:").map(|key| key.to_vec());
+
+        let decrypted = decrypt_keys_with(&prefixed(&[b"enc:b", b"hash", b"enc:a"]), decrypt);
+        let mut expected = prefixed(&[b"b"]);
+        expected.extend_from_slice(&UNDECRYPTABLE_KEY.to_be_bytes());
+        expected.extend_from_slice(&prefixed(&[b"a"]));
+        assert_eq!(decrypted, Some(expected));
+
+        let mut truncated = prefixed(&[b"enc:a"]);
+        truncated.pop();
+        assert_eq!(decrypt_keys_with(&truncated, decrypt), None);
+        assert_eq!(decrypt_keys_with(&[], decrypt), Some(vec![]));
+    }
+}
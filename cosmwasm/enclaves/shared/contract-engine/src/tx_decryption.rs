@@ -0,0 +1,137 @@
+//! Decrypts all the messages of a transaction in a single ecall, before they're executed, and keeps
+//! their plaintext for the ecalls executing them, which then skip the key exchange.
+//!
+//! The host sends the messages each prefixed by its length as a big endian u32, and gets back a
+//! `MsgDecryptionStatus` per message, so it can reject a transaction with a message that can't be
+//! decrypted without executing any of them.
+//!
+//! Plaintexts are only kept for the transaction being delivered. CheckTx doesn't execute the
+//! messages so it keeps none, and each batch first drops what the previous transaction didn't
+//! take, e.g. because one of its messages failed before the others ran.
+
+use std::convert::TryInto;
+use std::sync::SgxMutex;
+
+use lazy_static::lazy_static;
+use log::*;
+use lru::LruCache;
+
+use enclave_crypto::{sha_256, HASH_SIZE};
+use enclave_ffi_types::{EnclaveError, MsgDecryptionStatus};
+
+use crate::types::SecretMessage;
+
+/// The number of decrypted messages kept until they're executed
+const DECRYPTED_MSGS_CACHE_SIZE: usize = 256;
+const SIZE_OF_U32: usize = 4;
+
+lazy_static! {
+    static ref DECRYPTED_MSGS: SgxMutex<LruCache<[u8; HASH_SIZE], Vec<u8>>> =
+        SgxMutex::new(LruCache::new(DECRYPTED_MSGS_CACHE_SIZE));
+}
+
+pub fn decrypt_tx_msgs(
+    msgs: &[u8],
+    keep_plaintexts: bool,
+) -> Result<Vec<MsgDecryptionStatus>, EnclaveError> {
+    let msgs = split_msgs(msgs)?;
+    if keep_plaintexts {
+        DECRYPTED_MSGS.lock().unwrap().clear();
+    }
+
+    let statuses = msgs
+        .into_iter()
+        .map(|msg| decrypt_and_keep(msg, keep_plaintexts))
+        .collect();
+    Ok(statuses)
+}
+
+/// The plaintext of `secret_msg`, if it was decrypted by `decrypt_tx_msgs` and not taken yet
+pub fn take_decrypted(secret_msg: &SecretMessage) -> Option<Vec<u8>> {
+    DECRYPTED_MSGS.lock().unwrap().pop(&msg_id(secret_msg))
+}
+
+fn decrypt_and_keep(msg: &[u8], keep_plaintext: bool) -> MsgDecryptionStatus {
+    let secret_msg = match SecretMessage::from_slice(msg) {
+        Ok(secret_msg) => secret_msg,
+        Err(_) => return MsgDecryptionStatus::Malformed,
+    };
+
+    match secret_msg.try_decrypt_uncached() {
+        Some(plaintext) => {
+            if keep_plaintext {
+                DECRYPTED_MSGS
+                    .lock()
+                    .unwrap()
+                    .put(msg_id(&secret_msg), plaintext);
+            }
+            MsgDecryptionStatus::Decrypted
+        }
+        None => {
+            debug!("a message of the transaction can't be decrypted");
+            MsgDecryptionStatus::DecryptionFailed
+        }
+    }
+}
+
+fn split_msgs(mut msgs: &[u8]) -> Result<Vec<&[u8]>, EnclaveError> {
+    let mut split = vec![];
+    while !msgs.is_empty() {
+        if msgs.len() < SIZE_OF_U32 {
+            warn!("Got a truncated message length");
+            return Err(EnclaveError::FailedToDeserialize);
+        }
+        let (len, rest) = msgs.split_at(SIZE_OF_U32);
+        let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+
+        if rest.len() < len {
+            warn!("Got a truncated message");
+            return Err(EnclaveError::FailedToDeserialize);
+        }
+        let (msg, rest) = rest.split_at(len);
+        split.push(msg);
+        msgs = rest;
+    }
+    Ok(split)
+}
+
+fn msg_id(secret_msg: &SecretMessage) -> [u8; HASH_SIZE] {
+    sha_256(&secret_msg.to_vec())
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    fn length_prefixed(msgs: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = vec![];
+        for msg in msgs {
+            bytes.extend_from_slice(&(msg.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(msg);
+        }
+        bytes
+    }
+
+    pub fn test_split_msgs() {
+        let msgs: &[&[u8]] = &[b"first", b"", b"third"];
+        assert_eq!(split_msgs(&length_prefixed(msgs)).unwrap(), msgs);
+        assert!(split_msgs(&[]).unwrap().is_empty());
+
+        let mut truncated = length_prefixed(msgs);
+        truncated.pop();
+        assert!(split_msgs(&truncated).is_err());
+        assert!(split_msgs(&[0, 0, 1]).is_err());
+    }
+
+    pub fn test_malformed_msgs_are_reported() {
+        let msgs: &[&[u8]] = &[b"too short", b""];
+        let statuses = decrypt_tx_msgs(&length_prefixed(msgs), true).unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                MsgDecryptionStatus::Malformed,
+                MsgDecryptionStatus::Malformed
+            ]
+        );
+    }
+}
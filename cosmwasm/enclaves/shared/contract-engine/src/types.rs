@@ -4,7 +4,11 @@ use serde::{Deserialize, Serialize};
 use enclave_crypto::{AESKey, Ed25519PublicKey, SIVEncryptable};
 use enclave_ffi_types::EnclaveError;
 
+use super::envelope::{Aead, Envelope, KeyAgreement};
 use super::io::calc_encryption_key;
+#[cfg(feature = "pq-hybrid")]
+use super::io::calc_hybrid_encryption_key;
+use super::tx_decryption;
 
 pub type IoNonce = [u8; 32];
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
@@ -12,6 +16,8 @@ pub struct SecretMessage {
     pub nonce: IoNonce,
     pub user_public_key: Ed25519PublicKey,
     pub msg: Vec<u8>,
+    /// The format the message was sent in, which its outputs are encrypted with too
+    pub envelope: Envelope,
 }
 
 pub struct ParsedMessage {
@@ -32,22 +38,36 @@ pub struct DecryptedSecretMessage {
 
 impl SecretMessage {
     pub fn encrypt_in_place(&mut self) -> Result<(), EnclaveError> {
-        self.msg = self
-            .encryption_key()
-            .encrypt_siv(self.msg.as_slice(), None)
-            .map_err(|err| {
-                error!("got an error while trying to encrypt the msg: {:?}", err);
-                EnclaveError::EncryptionError
-            })?;
+        let key = self.encryption_key()?;
+        let encrypted = match self.envelope.cipher_suite().aead {
+            Aead::AesSiv => key.encrypt_siv(self.msg.as_slice(), None),
+        };
+        self.msg = encrypted.map_err(|err| {
+            error!("got an error while trying to encrypt the msg: {:?}", err);
+            EnclaveError::EncryptionError
+        })?;
 
         Ok(())
     }
 
     pub fn try_decrypt(&self) -> Option<Vec<u8>> {
+        if let Some(msg) = tx_decryption::take_decrypted(self) {
+            trace!("input was decrypted with the rest of the transaction");
+            return Some(msg);
+        }
+
+        self.try_decrypt_uncached()
+    }
+
+    /// Like `try_decrypt`, but ignores the messages decrypted by `tx_decryption`
+    pub fn try_decrypt_uncached(&self) -> Option<Vec<u8>> {
         trace!("input before decryption: {:?}", base64::encode(&self.msg));
-        let key = self.encryption_key();
+        let key = self.encryption_key().ok()?;
+        let decrypted = match self.envelope.cipher_suite().aead {
+            Aead::AesSiv => key.decrypt_siv(self.msg.as_slice(), None),
+        };
 
-        if let Ok(msg) = key.decrypt_siv(self.msg.as_slice(), None) {
+        if let Ok(msg) = decrypted {
             trace!(
                 "input after decryption: {:?}",
                 String::from_utf8_lossy(&msg)
@@ -69,14 +89,24 @@ impl SecretMessage {
         }
     }
 
-    pub fn encryption_key(&self) -> AESKey {
-        calc_encryption_key(&self.nonce, &self.user_public_key)
+    /// The key encrypting this message and the outputs for its sender
+    pub fn encryption_key(&self) -> Result<AESKey, EnclaveError> {
+        match self.envelope.cipher_suite().key_agreement {
+            KeyAgreement::X25519 => Ok(calc_encryption_key(&self.nonce, &self.user_public_key)),
+            #[cfg(feature = "pq-hybrid")]
+            KeyAgreement::X25519Kyber768 => calc_hybrid_encryption_key(
+                &self.nonce,
+                &self.user_public_key,
+                self.envelope.encapsulated_key(),
+            ),
+        }
     }
 
     pub fn from_base64(
         msg_b64: String,
         nonce: IoNonce,
         user_public_key: Ed25519PublicKey,
+        envelope: Envelope,
     ) -> Result<Self, EnclaveError> {
         let msg = base64::decode(&msg_b64.to_owned().into_bytes()).map_err(|err| {
             error!(
@@ -90,14 +120,18 @@ impl SecretMessage {
             msg,
             nonce,
             user_public_key,
+            envelope,
         })
     }
 
     pub fn from_slice(msg: &[u8]) -> Result<Self, EnclaveError> {
+        // the header of v2 messages, see `envelope`
         // 32 bytes of nonce
         // 32 bytes of 25519 compressed public key
         // 16+ bytes of encrypted data
 
+        let (envelope, msg) = Envelope::parse(msg)?;
+
         if msg.len() < 82 {
             error!(
                 "Encrypted message length {:?} is too short. Cannot parse",
@@ -122,11 +156,13 @@ impl SecretMessage {
             nonce,
             user_public_key: user_pubkey,
             msg: msg[64..].to_vec(),
+            envelope,
         })
     }
 
     pub fn to_vec(&self) -> Vec<u8> {
-        let mut packed_msg: Vec<u8> = self.nonce.to_vec();
+        let mut packed_msg: Vec<u8> = self.envelope.header();
+        packed_msg.extend_from_slice(&self.nonce);
         packed_msg.extend_from_slice(&self.user_public_key);
         packed_msg.extend_from_slice(self.msg.as_slice());
         packed_msg
@@ -152,6 +188,7 @@ pub mod tests {
             nonce,
             user_public_key,
             msg: msg.as_bytes().to_vec(),
+            envelope: Envelope::V1,
         };
 
         let msg_from_slice = SecretMessage::from_slice(&slice).unwrap();
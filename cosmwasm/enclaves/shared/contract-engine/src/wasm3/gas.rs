@@ -1,4 +1,13 @@
 //! Gas metering instrumentation.
+//!
+//! Every `memory.grow` is also preceded by a call to a meter that charges gas for the pages and
+//! caps how many pages a single call can grow its memory by, independently of the static memory
+//! limit. The cap keeps a contract from taking a large part of the enclave heap at runtime, even
+//! when it could pay for it. The cap is held in an exported global, so that it can be lifted for
+//! each instance until governance activates it, see `feature_gates::MEMORY_GROWTH_LIMIT`.
+//!
+//! The bulk memory instructions are preceded by a call to a meter charging gas for their length,
+//! since a single one can copy or fill the whole memory.
 
 use log::*;
 
@@ -14,6 +23,12 @@ use enclave_ffi_types::EnclaveError;
 pub const EXPORT_GAS_LIMIT: &str = "gas_limit";
 /// Name of the exported global that holds the gas limit exhausted flag.
 pub const EXPORT_GAS_LIMIT_EXHAUSTED: &str = "gas_limit_exhausted";
+/// Name of the exported global that holds the number of pages the memory was grown by.
+pub const EXPORT_MEMORY_GROWN_PAGES: &str = "memory_grown_pages";
+/// Name of the exported global that is set when the memory growth limit was exceeded.
+pub const EXPORT_MEMORY_GROWTH_LIMIT_EXCEEDED: &str = "memory_growth_limit_exceeded";
+/// Name of the exported global that holds the memory growth limit, in pages.
+pub const EXPORT_MEMORY_GROWTH_LIMIT: &str = "memory_growth_limit";
 
 /// Configures the gas limit on the given instance.
 pub fn set_gas_limit<C>(instance: &wasm3::Instance<C>, gas_limit: u64) -> Result<(), EnclaveError> {
@@ -22,6 +37,16 @@ pub fn set_gas_limit<C>(instance: &wasm3::Instance<C>, gas_limit: u64) -> Result
         .map_err(|_err| EnclaveError::FailedGasMeteringInjection)
 }
 
+/// Configures the memory growth limit on the given instance. `u32::MAX` never traps.
+pub fn set_memory_growth_limit<C>(
+    instance: &wasm3::Instance<C>,
+    max_memory_grow_pages: u32,
+) -> Result<(), EnclaveError> {
+    instance
+        .set_global(EXPORT_MEMORY_GROWTH_LIMIT, max_memory_grow_pages)
+        .map_err(|_err| EnclaveError::FailedGasMeteringInjection)
+}
+
 /// Returns the remaining gas.
 pub fn get_remaining_gas<C>(instance: &wasm3::Instance<C>) -> u64 {
     instance.get_global(EXPORT_GAS_LIMIT).unwrap_or_default()
@@ -34,6 +59,21 @@ pub fn get_exhausted_amount<C>(instance: &wasm3::Instance<C>) -> u64 {
         .unwrap_or_default()
 }
 
+/// Returns the number of pages the memory was grown by since the instance was created.
+pub fn get_memory_grown_pages<C>(instance: &wasm3::Instance<C>) -> u32 {
+    instance
+        .get_global(EXPORT_MEMORY_GROWN_PAGES)
+        .unwrap_or_default()
+}
+
+/// Returns whether the last call trapped because it exceeded the memory growth limit.
+pub fn memory_growth_limit_exceeded<C>(instance: &wasm3::Instance<C>) -> bool {
+    instance
+        .get_global::<u32>(EXPORT_MEMORY_GROWTH_LIMIT_EXCEEDED)
+        .unwrap_or_default()
+        != 0
+}
+
 /// Attempts to use the given amount of gas.
 pub fn use_gas<C>(instance: &wasm3::Instance<C>, amount: u64) -> WasmEngineResult<()> {
     debug!("external service used gas: {}", amount);
@@ -65,7 +105,43 @@ pub fn add_metering(module: &mut Module, gas_costs: &WasmCosts) {
         .exports
         .add(EXPORT_GAS_LIMIT_EXHAUSTED, gas_limit_exhausted_global);
 
+    let grown_pages_global =
+        module
+            .globals
+            .add_local(ValType::I32, true, InitExpr::Value(Value::I32(0)));
+    let growth_limit_exceeded_global =
+        module
+            .globals
+            .add_local(ValType::I32, true, InitExpr::Value(Value::I32(0)));
+    let growth_limit_global = module.globals.add_local(
+        ValType::I32,
+        true,
+        InitExpr::Value(Value::I32(gas_costs.max_memory_grow_pages as i32)),
+    );
+    module
+        .exports
+        .add(EXPORT_MEMORY_GROWN_PAGES, grown_pages_global);
+    module.exports.add(
+        EXPORT_MEMORY_GROWTH_LIMIT_EXCEEDED,
+        growth_limit_exceeded_global,
+    );
+    module
+        .exports
+        .add(EXPORT_MEMORY_GROWTH_LIMIT, growth_limit_global);
+
     let memory_grow_meter = create_memory_grow_meter(
+        module,
+        gas_costs,
+        MemoryGrowGlobals {
+            gas_limit: gas_limit_global,
+            gas_limit_exhausted: gas_limit_exhausted_global,
+            grown_pages: grown_pages_global,
+            growth_limit_exceeded: growth_limit_exceeded_global,
+            growth_limit: growth_limit_global,
+        },
+    );
+
+    let bulk_memory_meter = create_bulk_memory_meter(
         module,
         gas_costs,
         gas_limit_global,
@@ -78,11 +154,35 @@ pub fn add_metering(module: &mut Module, gas_costs: &WasmCosts) {
             gas_costs,
             gas_limit_global,
             gas_limit_exhausted_global,
-            memory_grow_meter,
+            Meters {
+                memory_grow: memory_grow_meter,
+                bulk_memory: bulk_memory_meter,
+            },
         );
     }
 }
 
+/// The functions called before the instructions whose cost depends on their operands
+#[derive(Clone, Copy)]
+struct Meters {
+    memory_grow: FunctionId,
+    bulk_memory: FunctionId,
+}
+
+/// The meter of an instruction, which takes its last operand and returns it as is
+fn instruction_meter(instr: &Instr, meters: Meters) -> Option<FunctionId> {
+    match instr {
+        Instr::MemoryGrow { .. } => Some(meters.memory_grow),
+        // the last operand of these is their length
+        Instr::MemoryCopy { .. }
+        | Instr::MemoryFill { .. }
+        | Instr::MemoryInit { .. }
+        | Instr::TableCopy { .. }
+        | Instr::TableInit { .. } => Some(meters.bulk_memory),
+        _ => None,
+    }
+}
+
 // todo copy from pwasm_utils
 /// Instruction cost function.
 fn instruction_cost(_instr: &Instr, _gas_costs: &WasmCosts) -> u64 {
@@ -95,7 +195,7 @@ fn transform_function(
     gas_costs: &WasmCosts,
     gas_limit_global: GlobalId,
     gas_limit_exhausted_global: GlobalId,
-    memory_grow_meter: FunctionId,
+    meters: Meters,
 ) {
     // get the list of "original" blocks before we start adding more.
     let block_ids: Vec<_> = func.blocks().map(|(block_id, _block)| block_id).collect();
@@ -107,7 +207,7 @@ fn transform_function(
             gas_costs,
             gas_limit_global,
             gas_limit_exhausted_global,
-            memory_grow_meter,
+            meters,
         );
     }
 }
@@ -121,7 +221,7 @@ fn inject_metering(
     gas_costs: &WasmCosts,
     gas_limit_global: GlobalId,
     gas_limit_exhausted_global: GlobalId,
-    memory_grow_meter: FunctionId,
+    meters: Meters,
 ) {
     let block = func.block_mut(block_id);
     let block_instrs = &mut block.instrs;
@@ -132,24 +232,22 @@ fn inject_metering(
         .sum();
     let block_cost = block_cost as i64;
 
-    // find all location in the block that use Instr::MemoryGrow
-    let mut grow_locations = vec![];
+    // find all location in the block that use Instr::MemoryGrow or a bulk memory instruction
+    let mut metered_locations = vec![];
     for (loc, (instr, _)) in block_instrs.iter().enumerate() {
-        if let Instr::MemoryGrow { .. } = instr {
-            grow_locations.push(loc);
+        if let Some(meter) = instruction_meter(instr, meters) {
+            metered_locations.push((loc, meter));
         }
     }
 
-    // Prepend instances of Instr::MemoryGrow with a call to the memory grow meter.
+    // Prepend these instructions with a call to their meter.
     // This is done in reverse because the indices are locations in the
     // underlying instruction array. Doing this in order would invalidate the
     // indices.
-    for loc in grow_locations.into_iter().rev() {
-        let call_grow_meter = Instr::from(Call {
-            func: memory_grow_meter,
-        });
+    for (loc, meter) in metered_locations.into_iter().rev() {
+        let call_meter = Instr::from(Call { func: meter });
         // using Default is fine - it's the same as what `InstrSeqBuilder::instr_at` does.
-        block_instrs.insert(loc, (call_grow_meter, Default::default()));
+        block_instrs.insert(loc, (call_meter, Default::default()));
     }
 
     let builder = func.builder_mut();
@@ -182,12 +280,27 @@ fn inject_metering(
     block.instrs = new_instrs;
 }
 
+struct MemoryGrowGlobals {
+    gas_limit: GlobalId,
+    gas_limit_exhausted: GlobalId,
+    grown_pages: GlobalId,
+    growth_limit_exceeded: GlobalId,
+    growth_limit: GlobalId,
+}
+
 fn create_memory_grow_meter(
     module: &mut Module,
     gas_costs: &WasmCosts,
-    gas_limit_global: GlobalId,
-    gas_limit_exhausted_global: GlobalId,
+    globals: MemoryGrowGlobals,
 ) -> FunctionId {
+    let MemoryGrowGlobals {
+        gas_limit: gas_limit_global,
+        gas_limit_exhausted: gas_limit_exhausted_global,
+        grown_pages: grown_pages_global,
+        growth_limit_exceeded: growth_limit_exceeded_global,
+        growth_limit: growth_limit_global,
+    } = globals;
+
     // function input
     let num_pages = module.locals.add(ValType::I32);
     // cache cost of memory grow
@@ -196,6 +309,32 @@ fn create_memory_grow_meter(
     let mut func = FunctionBuilder::new(&mut module.types, &[ValType::I32], &[ValType::I32]);
 
     func.func_body()
+        // if unsigned(globals[grown_pages]) + unsigned(num_pages)
+        //     > unsigned(globals[growth_limit]) {
+        //     globals[growth_limit_exceeded] = 1; throw();
+        // }
+        .global_get(grown_pages_global)
+        .unop(UnaryOp::I64ExtendUI32)
+        .local_get(num_pages)
+        .unop(UnaryOp::I64ExtendUI32)
+        .binop(BinaryOp::I64Add)
+        .global_get(growth_limit_global)
+        .unop(UnaryOp::I64ExtendUI32)
+        .binop(BinaryOp::I64GtU)
+        .if_else(
+            None,
+            |then| {
+                then.i32_const(1)
+                    .global_set(growth_limit_exceeded_global)
+                    .unreachable();
+            },
+            |_else| {},
+        )
+        // globals[grown_pages] += num_pages;
+        .global_get(grown_pages_global)
+        .local_get(num_pages)
+        .binop(BinaryOp::I32Add)
+        .global_set(grown_pages_global)
         // multiply the number of pages by the grow cost
         .local_get(num_pages)
         // num_pages as i64
@@ -230,3 +369,184 @@ fn create_memory_grow_meter(
     // register the function
     func.finish(vec![num_pages], &mut module.funcs)
 }
+
+/// Creates a function charging `gas_costs.memcpy` per unit of the length it is called with, which
+/// it returns for the bulk memory instruction called right after it
+fn create_bulk_memory_meter(
+    module: &mut Module,
+    gas_costs: &WasmCosts,
+    gas_limit_global: GlobalId,
+    gas_limit_exhausted_global: GlobalId,
+) -> FunctionId {
+    // function input
+    let length = module.locals.add(ValType::I32);
+    let cost = module.locals.add(ValType::I64);
+
+    let mut func = FunctionBuilder::new(&mut module.types, &[ValType::I32], &[ValType::I32]);
+
+    func.func_body()
+        // cost = unsigned(length) * memcpy, which can't overflow
+        .local_get(length)
+        .unop(UnaryOp::I64ExtendUI32)
+        .i64_const(gas_costs.memcpy as i64)
+        .binop(BinaryOp::I64Mul)
+        .local_set(cost)
+        // if unsigned(globals[gas_limit]) < unsigned(cost) { throw(); }
+        .global_get(gas_limit_global)
+        .local_get(cost)
+        .binop(BinaryOp::I64LtU)
+        .if_else(
+            None,
+            |then| {
+                then.local_get(cost)
+                    .global_set(gas_limit_exhausted_global)
+                    .unreachable();
+            },
+            |_else| {},
+        )
+        // globals[gas_limit] -= cost;
+        .global_get(gas_limit_global)
+        .local_get(cost)
+        .binop(BinaryOp::I64Sub)
+        .global_set(gas_limit_global)
+        // return the length for the bulk memory instruction
+        .local_get(length);
+
+    func.finish(vec![length], &mut module.funcs)
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use walrus::{Module, ModuleConfig};
+
+    use super::{
+        add_metering, get_exhausted_amount, get_memory_grown_pages, get_remaining_gas,
+        memory_growth_limit_exceeded, set_gas_limit, set_memory_growth_limit,
+    };
+    use crate::gas::WasmCosts;
+
+    /// A module with one page of memory, exporting `grow(n)`, which grows the memory by `n` pages
+    /// and returns the previous size:
+    ///
+    /// (memory 1)
+    /// (func (export "grow") (param i32) (result i32)
+    ///   local.get 0
+    ///   memory.grow)
+    #[rustfmt::skip]
+    const GROW_WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic bytes, binary version
+        0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f, // type section: (i32) -> i32
+        0x03, 0x02, 0x01, 0x00, // function section: one function of type 0
+        0x05, 0x03, 0x01, 0x00, 0x01, // memory section: one memory of at least 1 page
+        0x07, 0x08, 0x01, 0x04, b'g', b'r', b'o', b'w', 0x00, 0x00, // exports
+        0x0a, 0x08, 0x01, 0x06, 0x00, // code section: one body of 6 bytes, no locals
+        0x20, 0x00, 0x40, 0x00, // local.get 0, memory.grow
+        0x0b, // end
+    ];
+
+    /// A module with one page of memory, exporting `fill(n)`, which zeroes its first `n` bytes:
+    ///
+    /// (memory 1)
+    /// (func (export "fill") (param i32)
+    ///   i32.const 0
+    ///   i32.const 0
+    ///   local.get 0
+    ///   memory.fill)
+    #[rustfmt::skip]
+    const FILL_WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic bytes, binary version
+        0x01, 0x05, 0x01, 0x60, 0x01, 0x7f, 0x00, // type section: (i32) -> ()
+        0x03, 0x02, 0x01, 0x00, // function section: one function of type 0
+        0x05, 0x03, 0x01, 0x00, 0x01, // memory section: one memory of at least 1 page
+        0x07, 0x08, 0x01, 0x04, b'f', b'i', b'l', b'l', 0x00, 0x00, // exports
+        0x0a, 0x0d, 0x01, 0x0b, 0x00, // code section: one body of 11 bytes, no locals
+        0x41, 0x00, 0x41, 0x00, 0x20, 0x00, // i32.const 0, i32.const 0, local.get 0
+        0xfc, 0x0b, 0x00, // memory.fill
+        0x0b, // end
+    ];
+
+    /// Instruments `wasm` for gas metering with the default costs
+    pub fn with_metering(wasm: &[u8]) -> Vec<u8> {
+        let mut module: Module = ModuleConfig::new().parse(wasm).unwrap();
+        add_metering(&mut module, &WasmCosts::default());
+        module.emit_wasm()
+    }
+
+    pub fn memory_growth_is_capped_per_call() {
+        let mut module: Module = ModuleConfig::new().parse(GROW_WASM).unwrap();
+        let costs = WasmCosts {
+            max_memory_grow_pages: 4,
+            ..WasmCosts::default()
+        };
+        add_metering(&mut module, &costs);
+        let code = module.emit_wasm();
+
+        let environment = wasm3::Environment::new().unwrap();
+        let runtime = environment.new_runtime::<()>(1024 * 60, Some(16)).unwrap();
+        let instance = runtime
+            .load_module(environment.parse_module(&code).unwrap())
+            .unwrap();
+        set_gas_limit(&instance, 1_000_000_000).unwrap();
+        let grow = instance.find_function::<u32, u32>("grow").unwrap();
+
+        assert_eq!(grow.call_with_context(&mut (), 3).unwrap(), 1);
+        assert_eq!(grow.call_with_context(&mut (), 1).unwrap(), 4);
+        assert_eq!(get_memory_grown_pages(&instance), 4);
+        assert!(!memory_growth_limit_exceeded(&instance));
+
+        let result = grow.call_with_context(&mut (), 1);
+        assert!(matches!(result, Err(wasm3::Error::UnreachableExecuted)));
+        assert!(memory_growth_limit_exceeded(&instance));
+        assert_eq!(get_memory_grown_pages(&instance), 4);
+    }
+
+    pub fn memory_growth_limit_can_be_lifted() {
+        let mut module: Module = ModuleConfig::new().parse(GROW_WASM).unwrap();
+        let costs = WasmCosts {
+            max_memory_grow_pages: 4,
+            ..WasmCosts::default()
+        };
+        add_metering(&mut module, &costs);
+        let code = module.emit_wasm();
+
+        let environment = wasm3::Environment::new().unwrap();
+        let runtime = environment.new_runtime::<()>(1024 * 60, Some(16)).unwrap();
+        let instance = runtime
+            .load_module(environment.parse_module(&code).unwrap())
+            .unwrap();
+        set_gas_limit(&instance, 1_000_000_000).unwrap();
+        set_memory_growth_limit(&instance, u32::MAX).unwrap();
+        let grow = instance.find_function::<u32, u32>("grow").unwrap();
+
+        assert_eq!(grow.call_with_context(&mut (), 8).unwrap(), 1);
+        assert!(!memory_growth_limit_exceeded(&instance));
+        assert_eq!(get_memory_grown_pages(&instance), 8);
+    }
+
+    pub fn bulk_memory_is_metered_by_length() {
+        let mut module: Module = ModuleConfig::new().parse(FILL_WASM).unwrap();
+        let costs = WasmCosts {
+            memcpy: 3,
+            ..WasmCosts::default()
+        };
+        add_metering(&mut module, &costs);
+        let code = module.emit_wasm();
+
+        let environment = wasm3::Environment::new().unwrap();
+        let runtime = environment.new_runtime::<()>(1024 * 60, Some(16)).unwrap();
+        let instance = runtime
+            .load_module(environment.parse_module(&code).unwrap())
+            .unwrap();
+        let fill = instance.find_function::<u32, ()>("fill").unwrap();
+
+        set_gas_limit(&instance, 100_000).unwrap();
+        fill.call_with_context(&mut (), 1_000).unwrap();
+        let used = 100_000 - get_remaining_gas(&instance);
+        assert!(used >= 3_000, "used {} gas", used);
+
+        set_gas_limit(&instance, 100_000).unwrap();
+        let result = fill.call_with_context(&mut (), 40_000);
+        assert!(matches!(result, Err(wasm3::Error::UnreachableExecuted)));
+        assert_eq!(get_exhausted_amount(&instance), 120_000);
+    }
+}
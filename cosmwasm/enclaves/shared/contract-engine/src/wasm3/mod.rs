@@ -2,6 +2,7 @@ use core::cmp::max;
 use std::convert::{TryFrom, TryInto};
 
 use log::*;
+use sgx_types::sgx_status_t;
 
 use bech32::{FromBase32, ToBase32};
 use cw_types_generic::{ContractFeature, CosmWasmApiVersion, CwEnv};
@@ -13,32 +14,48 @@ use wasm3::{Instance, Memory, Trap};
 
 use cw_types_v010::consts::BECH32_PREFIX_ACC_ADDR;
 use cw_types_v010::encoding::Binary;
+use cw_types_v010::types::{CanonicalAddr, HumanAddr};
 use enclave_cosmos_types::types::{ContractCode, HandleType};
-use enclave_crypto::{sha_256, Ed25519PublicKey, WasmApiCryptoError};
-use enclave_ffi_types::{Ctx, EnclaveError};
+use enclave_crypto::{ct_eq, sha_256, Ed25519PublicKey, WasmApiCryptoError, PUBLIC_KEY_SIZE};
+use enclave_ffi_types::{CallMemoryStats, Ctx, EnclaveError, NodeAuthResult};
 
+use crate::access_list::AccessList;
+use crate::contract_encryption::{decrypt_from_contract, encrypt_to_contract};
 use crate::contract_validation::ContractKey;
-use crate::cosmwasm_config::ContractOperation;
+use crate::cosmwasm_config::{ContractOperation, EntryPoint};
 use crate::db::read_from_encrypted_state;
-use crate::db::{remove_from_encrypted_state, write_multiple_keys};
+use crate::db::{remove_from_encrypted_state, write_multiple_keys, StateOptions};
+use crate::disclosure::{create_disclosure_bundle, DisclosedEntry};
+use crate::engine::WasmInstance;
 use crate::errors::{ToEnclaveError, ToEnclaveResult, WasmEngineError, WasmEngineResult};
-use crate::gas::{WasmCosts, READ_BASE_GAS, WRITE_BASE_GAS};
+use crate::external::ocalls;
+use crate::gas::{GasForwarding, WasmCosts, READ_BASE_GAS, WRITE_BASE_GAS};
+use crate::node_attestation::verify_node_attestation;
 use crate::query_chain::encrypt_and_query_chain;
 use crate::random::MSG_COUNTER;
+use crate::sampling::{encode_sections, sampling_rng, shuffle, weighted_sample, SamplingError};
 use crate::types::IoNonce;
 
-use gas::{get_exhausted_amount, get_remaining_gas, use_gas};
+use gas::{
+    get_exhausted_amount, get_memory_grown_pages, get_remaining_gas, memory_growth_limit_exceeded,
+    set_memory_growth_limit, use_gas,
+};
 use module_cache::create_module_instance;
+use stack_height::{set_stack_height_limit, stack_limit_exceeded};
+use wasm_engine::Wasm3Engine;
 
 mod gas;
 pub mod module_cache;
+pub mod stack_height;
 mod validation;
+pub mod wasm_engine;
 // use std::time::Instant;
 
 type Wasm3RsError = wasm3::Error;
 type Wasm3RsResult<T> = Result<T, wasm3::Error>;
 
 use crate::wasm3::gas::EXPORT_GAS_LIMIT;
+use enclave_utils::alloc_tracker;
 use enclave_utils::kv_cache::KvCache;
 
 macro_rules! debug_err {
@@ -115,11 +132,22 @@ pub struct Context {
     query_depth: u32,
     operation: ContractOperation,
     og_contract_key: ContractKey,
+    contract_address: CanonicalAddr,
     user_nonce: IoNonce,
     user_public_key: Ed25519PublicKey,
     kv_cache: KvCache,
     last_error: Option<WasmEngineError>,
     timestamp: u64,
+    /// The block random derived for this contract, if it received one in its env
+    random: Option<Binary>,
+    /// How many shuffle or sampling calls were made so far, so each one gets fresh randomness
+    sampling_calls: u64,
+    /// The storage features the contract opted in to
+    state_options: StateOptions,
+    /// Set during CheckTx and simulations, where sampling uses `SIMULATION_RANDOM`
+    simulation: bool,
+    /// The keys of its store the contract may touch, if the execution declared an access list
+    access_list: Option<AccessList>,
 }
 
 impl Context {
@@ -191,6 +219,27 @@ where
         .allow_missing_import()
 }
 
+/// Calls `entry_point` with the region pointers in `args`, in the order the export takes them.
+/// Fails if the contract is of an invalid api version or doesn't have the export.
+fn call_entry_point(
+    instance: &Instance<Context>,
+    context: &mut Context,
+    entry_point: EntryPoint,
+    api_version: CosmWasmApiVersion,
+    args: &[u32],
+) -> Result<Result<u32, wasm3::Error>, EnclaveError> {
+    if api_version == CosmWasmApiVersion::Invalid {
+        return Err(EnclaveError::InvalidWasm);
+    }
+
+    let name = entry_point.export_name(api_version);
+    trace!("calling {} with {} arguments", name, args.len());
+    Ok(instance.call(context, name, args))
+}
+
+// Generated by build.rs from the Wasm ABI schema
+include!(concat!(env!("OUT_DIR"), "/host_functions.rs"));
+
 fn check_execution_result<T>(
     instance: &Instance<Context>,
     context: &mut Context,
@@ -207,6 +256,16 @@ fn check_execution_result<T>(
             );
             EnclaveError::OutOfGas
         }
+        // Likewise, the stack height instrumentation sets a flag before trapping.
+        wasm3::Error::UnreachableExecuted if stack_limit_exceeded(instance) => {
+            debug!("Detected stack height limit exceeded");
+            EnclaveError::StackLimitExceeded
+        }
+        // And so does the memory growth meter.
+        wasm3::Error::UnreachableExecuted if memory_growth_limit_exceeded(instance) => {
+            debug!("Detected memory growth limit exceeded");
+            EnclaveError::MemoryGrowthLimitExceeded
+        }
         // Otherwise, check if a hook set an error, in which case we propagate it.
         err => match context.take_last_error() {
             Some(err) => err.into(),
@@ -219,11 +278,15 @@ pub struct Engine {
     context: Context,
     gas_limit: u64,
     used_gas: u64,
-    environment: wasm3::Environment,
+    /// Gas of the reads of `read_state`, outside of the calls of the contract
+    read_state_gas: u64,
+    interpreter: Wasm3Engine,
     code: Vec<u8>,
     api_version: CosmWasmApiVersion,
     #[allow(dead_code)]
     features: Vec<ContractFeature>,
+    #[allow(dead_code)]
+    observer: Option<[u8; PUBLIC_KEY_SIZE]>,
 }
 
 impl Engine {
@@ -234,6 +297,7 @@ impl Engine {
         gas_costs: WasmCosts,
         contract_code: &ContractCode,
         og_contract_key: ContractKey,
+        contract_address: CanonicalAddr,
         operation: ContractOperation,
         user_nonce: IoNonce,
         user_public_key: Ed25519PublicKey,
@@ -250,17 +314,23 @@ impl Engine {
             gas_costs,
             operation,
             og_contract_key,
+            contract_address,
             user_nonce,
             user_public_key,
             kv_cache,
             last_error: None,
             timestamp,
+            random: None,
+            sampling_calls: 0,
+            state_options: versioned_code.state_options,
+            simulation: false,
+            access_list: None,
         };
 
         debug!("setting up runtime");
         // let start = Instant::now();
 
-        let environment = wasm3::Environment::new().to_enclave_result()?;
+        let interpreter = Wasm3Engine::new()?;
         // let duration = start.elapsed();
         // trace!("Time elapsed in Environment::new() is: {:?}", duration);
         debug!("initialized environment");
@@ -269,10 +339,12 @@ impl Engine {
             context,
             gas_limit,
             used_gas: 0,
-            environment,
+            read_state_gas: 0,
+            interpreter,
             code: versioned_code.code,
             api_version: versioned_code.version,
             features: versioned_code.features,
+            observer: versioned_code.observer,
         })
     }
 
@@ -280,113 +352,72 @@ impl Engine {
     where
         F: FnOnce(&mut wasm3::Instance<Context>, &mut Context) -> Result<Vec<u8>, EnclaveError>,
     {
-        // let start = Instant::now();
-        let runtime = self
-            .environment
-            .new_runtime::<Context>(1024 * 60, Some(192 /* 12 MiB */))
-            .to_enclave_result()?;
-        // let duration = start.elapsed();
-        // trace!("Time elapsed in environment.new_runtime is: {:?}", duration);
-        trace!("initialized runtime");
+        let heap_tracker = alloc_tracker::CallHeapTracker::start();
+        let Self {
+            context,
+            gas_limit,
+            used_gas,
+            interpreter,
+            code,
+            ..
+        } = self;
 
         // let start = Instant::now();
-        let module = self
-            .environment
-            .parse_module(&self.code)
-            .to_enclave_result()?;
-        // let duration = start.elapsed();
-        // trace!(
-        // "Time elapsed in environment.parse_module is: {:?}",
-        // duration
-        // );
-        trace!("parsed module");
+        interpreter.with_wasm3_instance(code, |instance| {
+            // let duration = start.elapsed();
+            // trace!("Time elapsed in loading the instance is: {:?}", duration);
+            trace!("created instance");
 
-        // let start = Instant::now();
-        let mut instance = runtime.load_module(module).to_enclave_result()?;
-        // let duration = start.elapsed();
-        // trace!("Time elapsed in runtime.load_module is: {:?}", duration);
-        trace!("created instance");
+            // let start = Instant::now();
+            instance.set_gas_limit(*gas_limit)?;
+            // let duration = start.elapsed();
+            // trace!("Time elapsed in set_gas_limit is: {:?}", duration);
+            trace!("set gas limit");
 
-        // let start = Instant::now();
-        gas::set_gas_limit(&instance, self.gas_limit)?;
-        // let duration = start.elapsed();
-        // trace!("Time elapsed in set_gas_limit is: {:?}", duration);
-        trace!("set gas limit");
+            set_stack_height_limit(instance, context.gas_costs.max_stack_height)?;
+            set_memory_growth_limit(instance, context.gas_costs.max_memory_grow_pages)?;
 
-        // let start = Instant::now();
-        Self::link_host_functions(&mut instance).to_enclave_result()?;
-        // let duration = start.elapsed();
-        // trace!("Time elapsed in link_host_functions is: {:?}", duration);
-        trace!("linked functions");
+            // let start = Instant::now();
+            Self::link_host_functions(instance).to_enclave_result()?;
+            // let duration = start.elapsed();
+            // trace!("Time elapsed in link_host_functions is: {:?}", duration);
+            trace!("linked functions");
 
-        // let start = Instant::now();
-        let result = func(&mut instance, &mut self.context);
-        // let duration = start.elapsed();
-        // trace!("Instance: elapsed time for running func is: {:?}", duration);
-        trace!("function returned {:?}", result);
+            // let start = Instant::now();
+            let result = func(instance, context);
+            // let duration = start.elapsed();
+            // trace!("Instance: elapsed time for running func is: {:?}", duration);
+            trace!("function returned {:?}", result);
+
+            *used_gas = gas_limit
+                .saturating_sub(instance.remaining_gas())
+                .saturating_sub(context.get_gas_used_externally())
+                .saturating_add(get_exhausted_amount(instance));
+
+            alloc_tracker::record_wasm_memory_growth(
+                get_memory_grown_pages(instance),
+                memory_growth_limit_exceeded(instance),
+            );
 
-        self.used_gas = self
-            .gas_limit
-            .saturating_sub(get_remaining_gas(&instance))
-            .saturating_sub(self.context.get_gas_used_externally())
-            .saturating_add(get_exhausted_amount(&instance));
+            report_call_memory(CallMemoryStats {
+                wasm_memory_bytes: instance.memory_size() as u64,
+                heap_high_watermark_bytes: heap_tracker.high_watermark_bytes(),
+            });
 
-        result
+            result
+        })?
     }
 
+    /// Links every import of the Wasm ABI schema (enclaves/ffi-types/wasm_abi.schema) that has a
+    /// host function. New imports are added there.
     fn link_host_functions(instance: &mut wasm3::Instance<Context>) -> Wasm3RsResult<()> {
-        link_fn(instance, "db_read", host_read_db)?;
-        link_fn(instance, "db_write", host_write_db)?;
-        link_fn(instance, "db_remove", host_remove_db)?;
-        link_fn(instance, "canonicalize_address", host_canonicalize_address)?;
-        link_fn(instance, "humanize_address", host_humanize_address)?;
-        link_fn(instance, "query_chain", host_query_chain)?;
-
-        link_fn(instance, "addr_canonicalize", host_addr_canonicalize)?;
-        link_fn(instance, "addr_humanize", host_humanize_address)?;
-        link_fn(instance, "addr_validate", host_addr_validate)?;
-        link_fn(instance, "debug_print", host_debug_print)?;
-
-        link_fn(instance, "debug", host_debug_print)?;
-
-        link_fn(instance, "secp256k1_verify", host_secp256k1_verify)?;
-        #[rustfmt::skip]
-        link_fn(instance, "secp256k1_recover_pubkey", host_secp256k1_recover_pubkey)?;
-        link_fn(instance, "ed25519_verify", host_ed25519_verify)?;
-        link_fn(instance, "ed25519_batch_verify", host_ed25519_batch_verify)?;
-        link_fn(instance, "secp256k1_sign", host_secp256k1_sign)?;
-        link_fn(instance, "ed25519_sign", host_ed25519_sign)?;
-        link_fn_no_args(instance, "check_gas", host_check_gas_used)?;
-        link_fn(instance, "gas_evaporate", host_gas_evaporate)?;
-
-        //    DbReadIndex = 0,
-        //     DbWriteIndex = 1,
-        //     DbRemoveIndex = 2,
-        //     CanonicalizeAddressIndex = 3,
-        //     HumanizeAddressIndex = 4,
-        //     GasIndex = 5,
-        //     QueryChainIndex = 6,
-        //     AddrValidateIndex = 7,
-        //     AddrCanonicalizeIndex = 8,
-        //     AddrHumanizeIndex = 9,
-        //     Secp256k1VerifyIndex = 10,
-        //     Secp256k1RecoverPubkeyIndex = 11,
-        //     Ed25519VerifyIndex = 12,
-        //     Ed25519BatchVerifyIndex = 13,
-        //     Secp256k1SignIndex = 14,
-        //     Ed25519SignIndex = 15,
-        //     DebugIndex = 16,
-        //     DebugPrintIndex = 254,
-        //     Unknown,
-
-        Ok(())
+        link_abi_host_functions(instance)
     }
 
-    /// get the amount of gas used by the last contract execution
+    /// get the amount of gas used by the last contract execution, and by `read_state`
     pub fn gas_used(&self) -> u64 {
-        self.used_gas
+        self.used_gas.saturating_add(self.read_state_gas)
     }
-
     pub fn get_api_version(&self) -> CosmWasmApiVersion {
         self.api_version
     }
@@ -396,6 +427,63 @@ impl Engine {
         &self.features
     }
 
+    /// The observer the code of the contract names, see `observers`
+    #[allow(dead_code)]
+    pub fn observer(&self) -> Option<[u8; PUBLIC_KEY_SIZE]> {
+        self.observer
+    }
+
+    /// Makes the sampling host functions use a placeholder instead of the block random, for calls
+    /// made during CheckTx and simulations
+    pub fn set_simulation(&mut self, simulation: bool) {
+        self.context.simulation = simulation;
+    }
+
+    /// Fails the execution when the contract touches a key of its store outside `access_list`
+    pub fn set_access_list(&mut self, access_list: Option<AccessList>) {
+        self.context.access_list = access_list;
+    }
+
+    /// Makes the block random sent to the contract available to the sampling host functions
+    #[allow(dead_code)]
+    pub fn set_random(&mut self, random: Option<Binary>) {
+        self.context.random = random;
+    }
+
+    /// Reads a key of the state of the contract as the contract would, its pending writes
+    /// included. The read is charged to the contract like its own reads.
+    pub fn read_state(&mut self, key: &[u8]) -> Result<Option<Vec<u8>>, EnclaveError> {
+        self.read_state_gas = self.read_state_gas.saturating_add(READ_BASE_GAS);
+        if self.gas_used() > self.gas_limit {
+            return Err(EnclaveError::OutOfGas);
+        }
+
+        if let Some(value) = self.context.kv_cache.read(key) {
+            return Ok(Some(value));
+        }
+        // the gas of the storage read itself is charged by the node
+        let (value, _, decompressed_size) = read_from_encrypted_state(
+            key,
+            &self.context.context,
+            &self.context.og_contract_key,
+            false,
+            &mut self.context.kv_cache,
+            &get_encryption_salt(self.context.timestamp),
+        )
+        .map_err(EnclaveError::from)?;
+
+        // like `use_gas_for_decompression`, without an instance to charge
+        self.read_state_gas = self.read_state_gas.saturating_add(
+            (self.context.gas_costs.external_state_decompression_per_byte as u64)
+                .saturating_mul(decompressed_size as u64),
+        );
+        if self.gas_used() > self.gas_limit {
+            return Err(EnclaveError::OutOfGas);
+        }
+
+        Ok(value)
+    }
+
     pub fn migrate(&mut self, env: &CwEnv, msg: Vec<u8>) -> Result<Vec<u8>, EnclaveError> {
         let api_version = self.get_api_version();
 
@@ -417,34 +505,13 @@ impl Engine {
             // let duration = start.elapsed();
             // trace!("Time elapsed in msg write_to_memory is: {:?}", duration);
 
-            let result = match api_version {
-                CosmWasmApiVersion::V010 => {
-                    let (migrate, args) = (
-                        instance
-                            .find_function::<(u32, u32), u32>("migrate")
-                            .to_enclave_result()?,
-                        (env_ptr, msg_ptr),
-                    );
-                    migrate.call_with_context(context, args)
-                }
-                CosmWasmApiVersion::V1 => {
-                    let (migrate, args) = (
-                        instance
-                            .find_function::<(u32, u32), u32>("migrate")
-                            .to_enclave_result()?,
-                        (env_ptr, msg_ptr),
-                    );
-                    // let start = Instant::now();
-                    // let res =
-                    migrate.call_with_context(context, args)
-                    // let duration = start.elapsed();
-                    // trace!("Time elapsed in call_with_context is: {:?}", duration);
-                    // res
-                }
-                CosmWasmApiVersion::Invalid => {
-                    return Err(EnclaveError::InvalidWasm);
-                }
-            };
+            let result = call_entry_point(
+                instance,
+                context,
+                EntryPoint::Migrate,
+                api_version,
+                &[env_ptr, msg_ptr],
+            )?;
             // let start = Instant::now();
             let output_ptr = check_execution_result(instance, context, result)?;
             // let duration = start.elapsed();
@@ -480,35 +547,24 @@ impl Engine {
             // let duration = start.elapsed();
             // trace!("Time elapsed in msg write_to_memory is: {:?}", duration);
 
-            let result = match api_version {
-                CosmWasmApiVersion::V010 => {
-                    let (init, args) = (
-                        instance
-                            .find_function::<(u32, u32), u32>("init")
-                            .to_enclave_result()?,
-                        (env_ptr, msg_ptr),
-                    );
-                    init.call_with_context(context, args)
-                }
-                CosmWasmApiVersion::V1 => {
-                    let msg_info_ptr = write_to_memory(instance, &msg_info_bytes)?;
-
-                    let (init, args) = (
-                        instance
-                            .find_function::<(u32, u32, u32), u32>("instantiate")
-                            .to_enclave_result()?,
-                        (env_ptr, msg_info_ptr, msg_ptr),
-                    );
-                    // let start = Instant::now();
-                    // let res =
-                    init.call_with_context(context, args)
-                    // let duration = start.elapsed();
-                    // trace!("Time elapsed in call_with_context is: {:?}", duration);
-                    // res
-                }
-                CosmWasmApiVersion::Invalid => {
-                    return Err(EnclaveError::InvalidWasm);
-                }
+            let entry_point = EntryPoint::Instantiate;
+            let result = if entry_point.takes_msg_info(api_version) {
+                let msg_info_ptr = write_to_memory(instance, &msg_info_bytes)?;
+                call_entry_point(
+                    instance,
+                    context,
+                    entry_point,
+                    api_version,
+                    &[env_ptr, msg_info_ptr, msg_ptr],
+                )?
+            } else {
+                call_entry_point(
+                    instance,
+                    context,
+                    entry_point,
+                    api_version,
+                    &[env_ptr, msg_ptr],
+                )?
             };
             // let start = Instant::now();
             let output_ptr = check_execution_result(instance, context, result)?;
@@ -541,41 +597,28 @@ impl Engine {
             let env_ptr = write_to_memory(instance, &env_bytes)?;
             trace!("handle written env");
 
-            let result = match api_version {
-                CosmWasmApiVersion::V010 => {
-                    let (handle, args) = (
-                        instance
-                            .find_function::<(u32, u32), u32>("handle")
-                            .to_enclave_result()?,
-                        (env_ptr, msg_ptr),
-                    );
-                    handle.call_with_context(context, args)
-                }
-                CosmWasmApiVersion::V1 => {
-                    let export_name = HandleType::get_export_name(handle_type);
-
-                    if export_name == "execute" {
-                        let msg_info_ptr = write_to_memory(instance, &msg_info_bytes)?;
-                        let (handle, args) = (
-                            instance
-                                .find_function::<(u32, u32, u32), u32>(export_name)
-                                .to_enclave_result()?,
-                            (env_ptr, msg_info_ptr, msg_ptr),
-                        );
-                        handle.call_with_context(context, args)
-                    } else {
-                        let (handle, args) = (
-                            instance
-                                .find_function::<(u32, u32), u32>(export_name)
-                                .to_enclave_result()?,
-                            (env_ptr, msg_ptr),
-                        );
-                        handle.call_with_context(context, args)
-                    }
-                }
-                CosmWasmApiVersion::Invalid => {
-                    return Err(EnclaveError::InvalidWasm);
-                }
+            let entry_point = match api_version {
+                // v0.10 contracts only have `handle`
+                CosmWasmApiVersion::V010 => EntryPoint::Execute,
+                _ => EntryPoint::from_handle_type(handle_type),
+            };
+            let result = if entry_point.takes_msg_info(api_version) {
+                let msg_info_ptr = write_to_memory(instance, &msg_info_bytes)?;
+                call_entry_point(
+                    instance,
+                    context,
+                    entry_point,
+                    api_version,
+                    &[env_ptr, msg_info_ptr, msg_ptr],
+                )?
+            } else {
+                call_entry_point(
+                    instance,
+                    context,
+                    entry_point,
+                    api_version,
+                    &[env_ptr, msg_ptr],
+                )?
             };
 
             trace!("found handle");
@@ -596,33 +639,19 @@ impl Engine {
         self.with_instance(|instance, context| {
             let msg_ptr = write_to_memory(instance, &msg)?;
 
-            let result = match api_version {
-                CosmWasmApiVersion::V010 => {
-                    let (query, args) = (
-                        instance
-                            .find_function::<u32, u32>("query")
-                            .to_enclave_result()?,
-                        (msg_ptr),
-                    );
-
-                    query.call_with_context(context, args)
-                }
-
-                CosmWasmApiVersion::V1 => {
-                    let (env_bytes, _) = env.get_wasm_ptrs()?;
-                    let env_ptr = write_to_memory(instance, &env_bytes)?;
-                    let (query, args) = (
-                        instance
-                            .find_function::<(u32, u32), u32>("query")
-                            .to_enclave_result()?,
-                        (env_ptr, msg_ptr),
-                    );
-
-                    query.call_with_context(context, args)
-                }
-                CosmWasmApiVersion::Invalid => {
-                    return Err(EnclaveError::InvalidWasm);
-                }
+            let entry_point = EntryPoint::Query;
+            let result = if entry_point.takes_env(api_version) {
+                let (env_bytes, _) = env.get_wasm_ptrs()?;
+                let env_ptr = write_to_memory(instance, &env_bytes)?;
+                call_entry_point(
+                    instance,
+                    context,
+                    entry_point,
+                    api_version,
+                    &[env_ptr, msg_ptr],
+                )?
+            } else {
+                call_entry_point(instance, context, entry_point, api_version, &[msg_ptr])?
             };
 
             debug!("starting query");
@@ -635,6 +664,30 @@ impl Engine {
         })
     }
 
+    /// Writes values uncompressed even if the contract opted in, until governance activates state
+    /// compression
+    pub fn disable_state_compression(&mut self) {
+        self.context.state_options.compression = false;
+    }
+
+    /// Lets contracts recurse as deep as the interpreter's stack allows, like before the limit
+    /// existed, until governance activates the stack height limit
+    pub fn disable_stack_height_limit(&mut self) {
+        self.context.gas_costs.max_stack_height = u32::MAX;
+    }
+
+    /// Lets a call grow its memory up to the static memory limit, like before the cap existed,
+    /// until governance activates the memory growth limit
+    pub fn disable_memory_growth_limit(&mut self) {
+        self.context.gas_costs.max_memory_grow_pages = u32::MAX;
+    }
+
+    /// Forwards all the remaining gas to queried contracts, like before the 63/64 rule, until
+    /// governance activates it
+    pub fn disable_gas_forwarding(&mut self) {
+        self.context.gas_costs.gas_forwarding = GasForwarding::All;
+    }
+
     pub fn flush_cache(&mut self, random: Option<Binary>) -> Result<u64, EnclaveError> {
         use crate::db::create_encrypted_key_value;
 
@@ -654,6 +707,7 @@ impl Engine {
                     &self.context.context,
                     &self.context.og_contract_key,
                     &get_encryption_salt(self.context.timestamp),
+                    &self.context.state_options,
                 )
                 .unwrap();
 
@@ -869,6 +923,15 @@ fn write_to_allocated_memory<C>(
         })?
 }
 
+/// Sends the memory footprint of a contract call to the host, which returns it with the gas
+/// report of the call.
+fn report_call_memory(stats: CallMemoryStats) {
+    let status = unsafe { ocalls::ocall_report_call_memory(stats) };
+    if status != sgx_status_t::SGX_SUCCESS {
+        warn!("Failed to report call memory to the host: {}", status);
+    }
+}
+
 fn show_bytes(bytes: &[u8]) -> String {
     format!(
         "{:?} ({})",
@@ -910,6 +973,7 @@ fn host_read_db(
     )?;
 
     debug!("db_read reading key {}", show_bytes(&state_key_name));
+    check_access_list(context, &state_key_name)?;
 
     let value = context.kv_cache.read(&state_key_name);
 
@@ -927,7 +991,7 @@ fn host_read_db(
     }
 
     debug!("Missed value in cache");
-    let (value, used_gas) = read_from_encrypted_state(
+    let (value, used_gas, decompressed_size) = read_from_encrypted_state(
         &state_key_name,
         &context.context,
         &context.og_contract_key,
@@ -942,6 +1006,7 @@ fn host_read_db(
     )
     .map_err(debug_err!("db_read failed to read key from storage"))?;
     context.use_gas_externally(used_gas);
+    use_gas_for_decompression(context, instance, decompressed_size)?;
 
     debug!(
         "db_read received value {:?}",
@@ -974,6 +1039,7 @@ fn host_remove_db(
     )?;
 
     debug!("db_remove removing key {}", show_bytes(&state_key_name));
+    check_access_list(context, &state_key_name)?;
 
     // Also remove the key from the cache to avoid rewriting it
     context.kv_cache.remove(&state_key_name);
@@ -1010,12 +1076,45 @@ fn host_write_db(
         show_bytes(&value)
     );
 
+    check_access_list(context, &state_key_name)?;
+
+    // Whether the value ends up compressed or not, all of it goes through the compressor
+    if context.state_options.compression {
+        use_gas(
+            instance,
+            context.gas_costs.external_state_compression_per_byte as u64 * value.len() as u64,
+        )?;
+    }
+
     let (_, pseudo_cost_for_write) = context.kv_cache.write(&state_key_name, &value);
     use_gas(instance, pseudo_cost_for_write)?; // Use gas now, refund later
 
     Ok(())
 }
 
+/// Fails if the execution declared an access list that doesn't allow `key`
+fn check_access_list(context: &Context, key: &[u8]) -> WasmEngineResult<()> {
+    match &context.access_list {
+        Some(access_list) if !access_list.allows(key) => {
+            debug!("key {} is outside the access list", show_bytes(key));
+            Err(WasmEngineError::AccessListViolation)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Charges for the `decompressed_size` bytes of compressed values a read returned
+fn use_gas_for_decompression(
+    context: &Context,
+    instance: &wasm3::Instance<Context>,
+    decompressed_size: usize,
+) -> WasmEngineResult<()> {
+    use_gas(
+        instance,
+        context.gas_costs.external_state_decompression_per_byte as u64 * decompressed_size as u64,
+    )
+}
+
 fn host_canonicalize_address(
     context: &mut Context,
     instance: &wasm3::Instance<Context>,
@@ -1279,7 +1378,10 @@ fn host_query_chain(
         context.user_nonce,
         context.user_public_key,
         &mut used_gas,
-        get_remaining_gas(instance),
+        context
+            .gas_costs
+            .gas_forwarding
+            .forwarded_gas(get_remaining_gas(instance)),
     )?;
 
     context.use_gas_externally(used_gas);
@@ -1863,6 +1965,361 @@ fn host_ed25519_sign(
     Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
 }
 
+/// Lets a contract disclose some of its own state keys to an auditor. Returns a pointer to a
+/// signed disclosure bundle, see the `disclosure` module.
+fn host_disclose_state(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    (keys_ptr, auditor_public_key_ptr): (i32, i32),
+) -> WasmEngineResult<i64> {
+    let used_gas = context.gas_costs.external_disclose_state as u64;
+    use_gas(instance, used_gas)?;
+
+    let keys = decode_sections_from_memory(instance, keys_ptr as u32).map_err(
+        debug_err!(err => "disclose_state error while trying to read keys from wasm memory: {err}"),
+    )?;
+    let auditor_public_key_data = read_from_memory(instance, auditor_public_key_ptr as u32).map_err(
+        debug_err!(err => "disclose_state error while trying to read auditor public key from wasm memory: {err}"),
+    )?;
+
+    let auditor_public_key: [u8; PUBLIC_KEY_SIZE] =
+        match auditor_public_key_data.as_slice().try_into() {
+            Ok(pk) => pk,
+            Err(_) => {
+                debug!(
+                    "disclose_state() was called with an auditor public key of length {:?}",
+                    auditor_public_key_data.len()
+                );
+                return Ok(to_high_half(WasmApiCryptoError::InvalidPubkeyFormat as u32) as i64);
+            }
+        };
+
+    let mut entries = Vec::with_capacity(keys.len());
+    for key in keys {
+        use_gas(instance, READ_BASE_GAS)?;
+        check_access_list(context, &key)?;
+
+        let value = match context.kv_cache.read(&key) {
+            Some(value) => Some(value),
+            None => {
+                let (value, used_gas, decompressed_size) = read_from_encrypted_state(
+                    &key,
+                    &context.context,
+                    &context.og_contract_key,
+                    !context.operation.is_query(),
+                    &mut context.kv_cache,
+                    &get_encryption_salt(context.timestamp),
+                )
+                .map_err(debug_err!("disclose_state failed to read key from storage"))?;
+                context.use_gas_externally(used_gas);
+                use_gas_for_decompression(context, instance, decompressed_size)?;
+                value
+            }
+        };
+
+        entries.push(DisclosedEntry {
+            key: Binary(key),
+            value: value.map(Binary),
+        });
+    }
+
+    debug!(
+        "disclose_state() disclosing {} keys to auditor {}",
+        entries.len(),
+        hex::encode(auditor_public_key)
+    );
+
+    let bundle = create_disclosure_bundle(
+        &context.og_contract_key,
+        context.timestamp,
+        &auditor_public_key,
+        entries,
+    )
+    .map_err(|err| {
+        debug!("disclose_state() failed to create the bundle: {:?}", err);
+        WasmEngineError::EncryptionError
+    })?;
+    use_gas(
+        instance,
+        context.gas_costs.external_disclose_state_per_byte as u64 * bundle.len() as u64,
+    )?;
+
+    let ptr_to_region_in_wasm_vm = write_to_memory(instance, &bundle).map_err(|err| {
+        debug!(
+            "disclose_state() error while trying to allocate {} bytes for the bundle",
+            bundle.len(),
+        );
+        err
+    })?;
+
+    // Return pointer to the allocated buffer with the value written to it
+    Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
+}
+
+/// Verifies the attestation certificate of a node, typically the one that relayed the input to
+/// the contract, and that it was attested at `min_tcb_level` or above. Returns a pointer to the
+/// node's registration public key, which the contract can match against its input, or a
+/// `NodeAuthResult` error code in the high half.
+fn host_check_node_attestation(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    (cert_ptr, min_tcb_level): (i32, i32),
+) -> WasmEngineResult<i64> {
+    let used_gas = context.gas_costs.external_check_node_attestation as u64;
+    use_gas(instance, used_gas)?;
+
+    let cert = read_from_memory(instance, cert_ptr as u32).map_err(
+        debug_err!(err => "check_node_attestation error while trying to read cert from wasm memory: {err}"),
+    )?;
+
+    let attestation = match verify_node_attestation(&cert) {
+        Ok(attestation) => attestation,
+        Err(err) => {
+            debug!(
+                "check_node_attestation() got an invalid certificate: {}",
+                err
+            );
+            return Ok(to_high_half(err as u32) as i64);
+        }
+    };
+
+    if (attestation.tcb_level as i32) < min_tcb_level {
+        debug!(
+            "check_node_attestation() node was attested at tcb level {}, required {}",
+            attestation.tcb_level, min_tcb_level
+        );
+        return Ok(to_high_half(NodeAuthResult::GroupOutOfDate as u32) as i64);
+    }
+
+    let ptr_to_region_in_wasm_vm =
+        write_to_memory(instance, &attestation.public_key).map_err(|err| {
+            debug!(
+                "check_node_attestation() error while trying to allocate and write the public key {:?} to the WASM VM",
+                &attestation.public_key,
+            );
+            err
+        })?;
+
+    // Return pointer to the allocated buffer with the value written to it
+    Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
+}
+
+/// Compares two regions in constant time, so that contracts checking viewing keys or passwords
+/// don't leak how many leading bytes matched. Returns 1 if they are equal and 0 otherwise.
+fn host_ct_eq(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    (a_ptr, b_ptr): (i32, i32),
+) -> WasmEngineResult<i32> {
+    let a = read_from_memory(instance, a_ptr as u32)
+        .map_err(debug_err!(err => "ct_eq error while trying to read a from wasm memory: {err}"))?;
+    let b = read_from_memory(instance, b_ptr as u32)
+        .map_err(debug_err!(err => "ct_eq error while trying to read b from wasm memory: {err}"))?;
+
+    let used_gas = context.gas_costs.external_ct_eq_base as u64
+        + context.gas_costs.external_ct_eq_per_byte as u64 * a.len().max(b.len()) as u64;
+    use_gas(instance, used_gas)?;
+
+    Ok(ct_eq(&a, &b) as i32)
+}
+
+/// Encrypts data to the contract at the bech32 address `addr`, see the `contract_encryption`
+/// module. Returns a pointer to the ciphertext, or an error code in the high half.
+fn host_encrypt_to_contract(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    (addr_ptr, data_ptr): (i32, i32),
+) -> WasmEngineResult<i64> {
+    let used_gas = context.gas_costs.external_encrypt_to_contract as u64;
+    use_gas(instance, used_gas)?;
+
+    let addr = read_from_memory(instance, addr_ptr as u32).map_err(
+        debug_err!(err => "encrypt_to_contract error while trying to read addr from wasm memory: {err}"),
+    )?;
+    let data = read_from_memory(instance, data_ptr as u32).map_err(
+        debug_err!(err => "encrypt_to_contract error while trying to read data from wasm memory: {err}"),
+    )?;
+
+    let recipient = match std::str::from_utf8(&addr)
+        .ok()
+        .and_then(|addr| CanonicalAddr::from_human(&HumanAddr(addr.to_string())).ok())
+    {
+        Some(recipient) => recipient,
+        None => {
+            debug!(
+                "encrypt_to_contract() was called with an invalid address: {}",
+                show_bytes(&addr)
+            );
+            return Ok(to_high_half(WasmApiCryptoError::InvalidPubkeyFormat as u32) as i64);
+        }
+    };
+
+    let ciphertext =
+        encrypt_to_contract(&context.og_contract_key, &recipient, &data).map_err(|err| {
+            debug!("encrypt_to_contract() failed to encrypt: {:?}", err);
+            WasmEngineError::EncryptionError
+        })?;
+
+    let ptr_to_region_in_wasm_vm = write_to_memory(instance, &ciphertext).map_err(|err| {
+        debug!(
+            "encrypt_to_contract() error while trying to allocate {} bytes for the ciphertext",
+            ciphertext.len(),
+        );
+        err
+    })?;
+
+    // Return pointer to the allocated buffer with the value written to it
+    Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
+}
+
+/// Decrypts data that another contract encrypted to this one with `encrypt_to_contract`.
+/// Returns a pointer to the plaintext, or an error code in the high half if it was not encrypted
+/// to this contract.
+fn host_decrypt_from_contract(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    ciphertext_ptr: i32,
+) -> WasmEngineResult<i64> {
+    let used_gas = context.gas_costs.external_decrypt_from_contract as u64;
+    use_gas(instance, used_gas)?;
+
+    let ciphertext = read_from_memory(instance, ciphertext_ptr as u32).map_err(
+        debug_err!(err => "decrypt_from_contract error while trying to read ciphertext from wasm memory: {err}"),
+    )?;
+
+    let plaintext = match decrypt_from_contract(&context.contract_address, &ciphertext) {
+        Ok(plaintext) => plaintext,
+        Err(err) => {
+            debug!("decrypt_from_contract() failed to decrypt: {:?}", err);
+            return Ok(to_high_half(WasmApiCryptoError::GenericErr as u32) as i64);
+        }
+    };
+
+    let ptr_to_region_in_wasm_vm = write_to_memory(instance, &plaintext).map_err(|err| {
+        debug!(
+            "decrypt_from_contract() error while trying to allocate {} bytes for the plaintext",
+            plaintext.len(),
+        );
+        err
+    })?;
+
+    // Return pointer to the allocated buffer with the value written to it
+    Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
+}
+
+/// The random sampling uses during CheckTx and simulations instead of the block random, which
+/// depends on the block the tx ends up in. Gas estimates match delivery, the results don't.
+const SIMULATION_RANDOM: [u8; 32] = [0u8; 32];
+
+/// Returns the rng for the next sampling call, or `None` if the contract has no block random
+fn next_sampling_rng(context: &mut Context) -> Option<ChaChaRng> {
+    let random = context.random.as_ref()?;
+    let rng = if context.simulation {
+        sampling_rng(&Binary(SIMULATION_RANDOM.to_vec()), context.sampling_calls)
+    } else {
+        sampling_rng(random, context.sampling_calls)
+    };
+    context.sampling_calls += 1;
+    Some(rng)
+}
+
+/// Shuffles the items in `items_ptr`, encoded as sections, using the block random. Returns a
+/// pointer to the shuffled items in the same encoding, or a `SamplingError` in the high half.
+fn host_shuffle(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    items_ptr: i32,
+) -> WasmEngineResult<i64> {
+    let mut items = decode_sections_from_memory(instance, items_ptr as u32).map_err(
+        debug_err!(err => "shuffle error while trying to read items from wasm memory: {err}"),
+    )?;
+
+    let used_gas = context.gas_costs.external_shuffle_base as u64
+        + context.gas_costs.external_sample_per_item as u64 * items.len() as u64;
+    use_gas(instance, used_gas)?;
+
+    let mut rng = match next_sampling_rng(context) {
+        Some(rng) => rng,
+        None => {
+            debug!("shuffle() was called without a block random");
+            return Ok(to_high_half(SamplingError::RandomUnavailable as u32) as i64);
+        }
+    };
+    shuffle(&mut items, &mut rng);
+
+    let shuffled = encode_sections(&items);
+    let ptr_to_region_in_wasm_vm = write_to_memory(instance, &shuffled).map_err(|err| {
+        debug!(
+            "shuffle() error while trying to allocate {} bytes for the shuffled items",
+            shuffled.len(),
+        );
+        err
+    })?;
+
+    // Return pointer to the allocated buffer with the value written to it
+    Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
+}
+
+/// Picks `count` distinct indices out of `weights_ptr`, a list of big endian u64 weights, with
+/// probability proportional to their weight, using the block random. Returns a pointer to the
+/// picked indices as big endian u32s in the order they were drawn, or a `SamplingError` in the
+/// high half.
+fn host_weighted_sample(
+    context: &mut Context,
+    instance: &wasm3::Instance<Context>,
+    (weights_ptr, count): (i32, i32),
+) -> WasmEngineResult<i64> {
+    let weights_data = read_from_memory(instance, weights_ptr as u32).map_err(
+        debug_err!(err => "weighted_sample error while trying to read weights from wasm memory: {err}"),
+    )?;
+
+    let weights_len = weights_data.len() / 8;
+    let used_gas = context.gas_costs.external_weighted_sample_base as u64
+        + context.gas_costs.external_sample_per_item as u64
+            * (weights_len as u64).saturating_mul(count.max(1) as u64);
+    use_gas(instance, used_gas)?;
+
+    if weights_data.len() % 8 != 0 || count < 0 {
+        debug!(
+            "weighted_sample() was called with {} bytes of weights and count {}",
+            weights_data.len(),
+            count
+        );
+        return Ok(to_high_half(SamplingError::InvalidInput as u32) as i64);
+    }
+    let weights: Vec<u64> = weights_data
+        .chunks_exact(8)
+        .map(|chunk| u64::from_be_bytes(chunk.try_into().unwrap()))
+        .collect();
+
+    let mut rng = match next_sampling_rng(context) {
+        Some(rng) => rng,
+        None => {
+            debug!("weighted_sample() was called without a block random");
+            return Ok(to_high_half(SamplingError::RandomUnavailable as u32) as i64);
+        }
+    };
+    let picked = match weighted_sample(&weights, count as usize, &mut rng) {
+        Ok(picked) => picked,
+        Err(err) => return Ok(to_high_half(err as u32) as i64),
+    };
+
+    let picked: Vec<u8> = picked
+        .iter()
+        .flat_map(|index| index.to_be_bytes().to_vec())
+        .collect();
+    let ptr_to_region_in_wasm_vm = write_to_memory(instance, &picked).map_err(|err| {
+        debug!(
+            "weighted_sample() error while trying to allocate {} bytes for the picked indices",
+            picked.len(),
+        );
+        err
+    })?;
+
+    // Return pointer to the allocated buffer with the value written to it
+    Ok(to_low_half(ptr_to_region_in_wasm_vm) as i64)
+}
+
 fn get_encryption_salt(timestamp: u64) -> Vec<u8> {
     let mut encryption_salt: Vec<u8> = vec![];
 
@@ -1920,8 +2377,10 @@ fn host_check_gas_used(
 
 #[cfg(feature = "test")]
 pub mod tests {
-    use super::shuffle_cache;
+    use super::wasm_engine::Wasm3Engine;
+    use super::{gas, module_cache, shuffle_cache, stack_height};
     use crate::count_failures;
+    use crate::engine;
     use crate::wasm3::Binary;
 
     pub fn run_tests() {
@@ -1930,6 +2389,20 @@ pub mod tests {
 
         count_failures!(failures, {
             cache_shuffle_works();
+            stack_height::tests::stack_limit_allows_shallow_recursion();
+            stack_height::tests::stack_limit_stops_deep_recursion();
+            stack_height::tests::stack_limit_can_be_lifted();
+            stack_height::tests::stack_height_is_restored_after_calls();
+            module_cache::tests::custom_sections_are_stripped();
+            gas::tests::memory_growth_is_capped_per_call();
+            gas::tests::memory_growth_limit_can_be_lifted();
+            gas::tests::bulk_memory_is_metered_by_length();
+            engine::tests::calls_exports(&Wasm3Engine::new().unwrap());
+            engine::tests::reads_and_writes_memory(&Wasm3Engine::new().unwrap());
+            engine::tests::meters_gas(
+                &Wasm3Engine::new().unwrap(),
+                &gas::tests::with_metering(engine::tests::CONFORMANCE_WASM),
+            );
         });
 
         // The test doesn't work for some reason
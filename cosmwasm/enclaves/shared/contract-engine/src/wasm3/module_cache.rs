@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::SgxRwLock;
 
 use lazy_static::lazy_static;
@@ -6,40 +8,180 @@ use lru::LruCache;
 
 use cw_types_generic::{ContractFeature, CosmWasmApiVersion};
 
-use enclave_ffi_types::EnclaveError;
+use enclave_ffi_types::{EnclaveError, ModuleCacheStats};
 
 use enclave_cosmos_types::types::ContractCode;
-use enclave_crypto::HASH_SIZE;
+use enclave_crypto::{HASH_SIZE, PUBLIC_KEY_SIZE};
 
-use super::{gas, validation};
+use super::{gas, stack_height, validation};
 use crate::cosmwasm_config::ContractOperation;
 use crate::cosmwasm_config::{api_marker, features};
+use crate::cw2::PUBLIC_CONTRACT_VERSION_SECTION;
+use crate::db::StateOptions;
+use crate::execution_receipts::QUERY_RESPONSE_MAC_SECTION;
 use crate::gas::WasmCosts;
+use crate::observers::OBSERVER_SECTION;
+use crate::state_compression::STATE_COMPRESSION_SECTION;
 
+#[derive(Clone)]
 pub struct VersionedCode {
     pub code: Vec<u8>,
     pub version: CosmWasmApiVersion,
     pub features: Vec<ContractFeature>,
+    /// The storage features the contract opted in to
+    pub state_options: StateOptions,
+    /// The x25519 public key of the observer the contract names, if any
+    pub observer: Option<[u8; PUBLIC_KEY_SIZE]>,
 }
 
 impl VersionedCode {
-    pub fn new(code: Vec<u8>, version: CosmWasmApiVersion, features: Vec<ContractFeature>) -> Self {
+    pub fn new(
+        code: Vec<u8>,
+        version: CosmWasmApiVersion,
+        features: Vec<ContractFeature>,
+        state_options: StateOptions,
+        observer: Option<[u8; PUBLIC_KEY_SIZE]>,
+    ) -> Self {
         Self {
             code,
             version,
             features,
+            state_options,
+            observer,
         }
     }
 }
 
+/// Pinned modules are never evicted, this keeps them from exhausting the enclave memory
+pub const MAX_PINNED_MODULES: usize = 64;
+
 lazy_static! {
     static ref MODULE_CACHE: SgxRwLock<LruCache<[u8; HASH_SIZE], VersionedCode>> =
         SgxRwLock::new(LruCache::new(0));
+    /// The code hashes the node pinned, with their module once one of their contracts ran. These
+    /// are kept across executions whatever the size of `MODULE_CACHE`.
+    static ref PINNED_MODULES: SgxRwLock<HashMap<[u8; HASH_SIZE], Option<VersionedCode>>> =
+        SgxRwLock::new(HashMap::new());
+}
+
+static HITS: AtomicU64 = AtomicU64::new(0);
+static PINNED_HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+static EVICTIONS: AtomicU64 = AtomicU64::new(0);
+/// Set when the statistics changed since they were last reported to the host
+static UNREPORTED: AtomicBool = AtomicBool::new(false);
+
+fn count(counter: &AtomicU64) {
+    counter.fetch_add(1, Ordering::Relaxed);
+    UNREPORTED.store(true, Ordering::Relaxed);
 }
 
 pub fn configure_module_cache(cap: usize) {
     debug!("configuring module cache: {}", cap);
-    MODULE_CACHE.write().unwrap().resize(cap)
+    let mut cache = MODULE_CACHE.write().unwrap();
+    let evicted = cache.len().saturating_sub(cap);
+    if evicted > 0 {
+        EVICTIONS.fetch_add(evicted as u64, Ordering::Relaxed);
+        UNREPORTED.store(true, Ordering::Relaxed);
+    }
+    cache.resize(cap)
+}
+
+pub fn module_cache_stats() -> ModuleCacheStats {
+    let cache = MODULE_CACHE.read().unwrap();
+    ModuleCacheStats {
+        capacity: cache.cap() as u64,
+        size: cache.len() as u64,
+        pinned: PINNED_MODULES.read().unwrap().len() as u64,
+        hits: HITS.load(Ordering::Relaxed),
+        pinned_hits: PINNED_HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+        evictions: EVICTIONS.load(Ordering::Relaxed),
+    }
+}
+
+/// Returns the current statistics if they changed since the last time this returned something,
+/// which is after every call that looked a module up
+pub fn take_unreported_stats() -> Option<ModuleCacheStats> {
+    if UNREPORTED.swap(false, Ordering::Relaxed) {
+        Some(module_cache_stats())
+    } else {
+        None
+    }
+}
+
+/// Keeps the module of `hash` once built until it is unpinned. Returns false if
+/// `MAX_PINNED_MODULES` are pinned already.
+pub fn pin_module(hash: [u8; HASH_SIZE]) -> bool {
+    let mut pinned = PINNED_MODULES.write().unwrap();
+    if !pinned.contains_key(&hash) && pinned.len() >= MAX_PINNED_MODULES {
+        warn!("can't pin more than {} modules", MAX_PINNED_MODULES);
+        return false;
+    }
+    pinned.entry(hash).or_insert(None);
+    true
+}
+
+pub fn unpin_module(hash: &[u8; HASH_SIZE]) {
+    PINNED_MODULES.write().unwrap().remove(hash);
+}
+
+/// Drops the module of `hash`, pinned or cached, once its code was removed. Unlike `unpin_module`,
+/// the module isn't left in the LRU cache.
+pub fn evict_module(hash: &[u8; HASH_SIZE]) {
+    PINNED_MODULES.write().unwrap().remove(hash);
+    if MODULE_CACHE.write().unwrap().pop(hash).is_some() {
+        debug!("evicted module from cache");
+    }
+}
+
+/// Builds the module of `contract_code` ahead of its first execution, keeping it like an execution
+/// would: in its pinned slot if it is pinned, in the LRU cache otherwise. Does nothing if the
+/// module is already built or the cache is disabled.
+///
+/// The module is built as for an instantiation, which is the strictest, so a prewarmed module is
+/// one any execution would accept. Code an instantiation rejects fails here and is built on its
+/// first execution instead.
+pub fn prewarm_module(contract_code: &ContractCode) -> Result<(), EnclaveError> {
+    let hash = contract_code.hash();
+    let pinned = PINNED_MODULES
+        .read()
+        .unwrap()
+        .get(&hash)
+        .map(Option::is_some);
+    match pinned {
+        Some(true) => return Ok(()),
+        Some(false) => {
+            let versioned_code = analyze_module(
+                contract_code,
+                &WasmCosts::default(),
+                ContractOperation::Init,
+            )?;
+            if let Some(entry) = PINNED_MODULES.write().unwrap().get_mut(&hash) {
+                *entry = Some(versioned_code);
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    {
+        let cache = MODULE_CACHE.read().unwrap();
+        if cache.cap() == 0 || cache.contains(&hash) {
+            return Ok(());
+        }
+    }
+    let versioned_code = analyze_module(
+        contract_code,
+        &WasmCosts::default(),
+        ContractOperation::Init,
+    )?;
+    let mut cache = MODULE_CACHE.write().unwrap();
+    if !cache.contains(&hash) && cache.len() >= cache.cap() {
+        count(&EVICTIONS);
+    }
+    cache.put(hash, versioned_code);
+    Ok(())
 }
 
 pub fn create_module_instance(
@@ -47,12 +189,41 @@ pub fn create_module_instance(
     gas_costs: &WasmCosts,
     operation: ContractOperation,
 ) -> Result<VersionedCode, EnclaveError> {
+    let pinned = PINNED_MODULES
+        .read()
+        .unwrap()
+        .get(&contract_code.hash())
+        .cloned();
+    match pinned {
+        Some(Some(versioned_code)) => {
+            trace!("found pinned module");
+            count(&PINNED_HITS);
+            return Ok(versioned_code);
+        }
+        Some(None) => {
+            trace!("module is pinned but wasn't built yet, analyzing now");
+            count(&MISSES);
+            let versioned_code = analyze_module(contract_code, gas_costs, operation)?;
+            // unless it was unpinned meanwhile
+            if let Some(entry) = PINNED_MODULES
+                .write()
+                .unwrap()
+                .get_mut(&contract_code.hash())
+            {
+                *entry = Some(versioned_code.clone());
+            }
+            return Ok(versioned_code);
+        }
+        None => {}
+    }
+
     trace!("fetching module from cache");
     let cache = MODULE_CACHE.read().unwrap();
 
     // If the cache is disabled, don't try to use it and just compile the module.
     if cache.cap() == 0 {
         trace!("cache is disabled, building module");
+        count(&MISSES);
         return analyze_module(contract_code, gas_costs, operation);
     }
     trace!("cache is enabled");
@@ -61,18 +232,25 @@ pub fn create_module_instance(
     let mut code = None;
     let mut api_version = CosmWasmApiVersion::Invalid;
     let mut features = vec![];
+    let mut state_options = StateOptions::default();
+    let mut observer = None;
     trace!("peeking in cache");
     let peek_result = cache.peek(&contract_code.hash());
     if let Some(VersionedCode {
         code: cached_code,
         version: cached_ver,
         features: cached_features,
+        state_options: cached_state_options,
+        observer: cached_observer,
     }) = peek_result
     {
         trace!("found instance in cache!");
+        count(&HITS);
         code = Some(cached_code.clone());
         api_version = *cached_ver;
         features = cached_features.clone();
+        state_options = cached_state_options.clone();
+        observer = *cached_observer;
     }
 
     drop(cache); // Release read lock
@@ -80,10 +258,13 @@ pub fn create_module_instance(
     // if we couldn't find the code in the cache, analyze it now
     if code.is_none() {
         trace!("code not found in cache! analyzing now");
+        count(&MISSES);
         let versioned_code = analyze_module(contract_code, gas_costs, operation)?;
         code = Some(versioned_code.code);
         api_version = versioned_code.version;
         features = versioned_code.features;
+        state_options = versioned_code.state_options;
+        observer = versioned_code.observer;
     }
 
     // If we analyzed the code in the previous step, insert it to the LRU cache
@@ -91,9 +272,18 @@ pub fn create_module_instance(
     let mut cache = MODULE_CACHE.write().unwrap();
     if let Some(code) = code.clone() {
         trace!("storing code in cache");
+        if !cache.contains(&contract_code.hash()) && cache.len() >= cache.cap() {
+            count(&EVICTIONS);
+        }
         cache.put(
             contract_code.hash(),
-            VersionedCode::new(code, api_version, features.clone()),
+            VersionedCode::new(
+                code,
+                api_version,
+                features.clone(),
+                state_options.clone(),
+                observer,
+            ),
         );
     } else {
         // Touch the cache to update the LRU value
@@ -104,7 +294,13 @@ pub fn create_module_instance(
     let code = code.unwrap();
 
     trace!("returning built instance");
-    Ok(VersionedCode::new(code, api_version, features))
+    Ok(VersionedCode::new(
+        code,
+        api_version,
+        features,
+        state_options,
+        observer,
+    ))
 }
 
 pub fn analyze_module(
@@ -114,6 +310,7 @@ pub fn analyze_module(
 ) -> Result<VersionedCode, EnclaveError> {
     let mut module = walrus::ModuleConfig::new()
         .generate_producers_section(false)
+        .generate_name_section(cfg!(feature = "debug-print"))
         .parse(contract_code.code())
         .map_err(|_| EnclaveError::InvalidWasm)?;
 
@@ -143,7 +340,7 @@ pub fn analyze_module(
         .iter()
         .any(|exp| exp.name == features::RANDOM);
 
-    let features = if random_enabled {
+    let mut features = if random_enabled {
         debug!("Found supported features: random");
         vec![ContractFeature::Random]
     } else {
@@ -151,8 +348,55 @@ pub fn analyze_module(
     };
     drop(exports);
 
+    if module
+        .customs
+        .remove_raw(QUERY_RESPONSE_MAC_SECTION)
+        .is_some()
+    {
+        debug!("Found supported features: query response mac");
+        features.push(ContractFeature::QueryResponseMac);
+    }
+
+    if module
+        .customs
+        .remove_raw(PUBLIC_CONTRACT_VERSION_SECTION)
+        .is_some()
+    {
+        debug!("Found supported features: public contract version");
+        features.push(ContractFeature::PublicContractVersion);
+    }
+
+    let state_options = StateOptions {
+        compression: module
+            .customs
+            .remove_raw(STATE_COMPRESSION_SECTION)
+            .is_some(),
+    };
+
+    let observer = module
+        .customs
+        .remove_raw(OBSERVER_SECTION)
+        .and_then(|section| {
+            let mut observer = [0u8; PUBLIC_KEY_SIZE];
+            if section.data.len() != PUBLIC_KEY_SIZE {
+                debug!("observer section is not a public key, ignoring it");
+                return None;
+            }
+            observer.copy_from_slice(&section.data);
+            Some(observer)
+        });
+
+    // The code hash was taken over the uploaded Wasm, only the module built from it is stripped
+    #[cfg(not(feature = "debug-print"))]
+    strip_custom_sections(&mut module);
+
     validation::validate_memory(&mut module)?;
 
+    if validation::uses_bulk_memory(&module) {
+        debug!("contract was found to use bulk memory instructions");
+        features.push(ContractFeature::BulkMemory);
+    }
+
     if let ContractOperation::Init = operation {
         if module.has_floats() {
             debug!("contract was found to contain floating point operations");
@@ -161,8 +405,61 @@ pub fn analyze_module(
     }
 
     gas::add_metering(&mut module, gas_costs);
+    stack_height::add_stack_limit(&mut module, gas_costs.max_stack_height);
 
     let code = module.emit_wasm();
 
-    Ok(VersionedCode::new(code, cosmwasm_api_version, features))
+    Ok(VersionedCode::new(
+        code,
+        cosmwasm_api_version,
+        features,
+        state_options,
+        observer,
+    ))
+}
+
+/// Drops the custom sections left once the ones the enclave reads were taken out, like DWARF debug
+/// info, which often make up most of a contract and are never read when it runs. Together with the
+/// `name` section, they are kept by `debug-print` builds.
+fn strip_custom_sections(module: &mut walrus::Module) {
+    let names: Vec<String> = module
+        .customs
+        .iter()
+        .map(|(_, section)| section.name().to_string())
+        .collect();
+    for name in names {
+        trace!("stripping custom section {}", name);
+        module.customs.remove_raw(&name);
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use walrus::ModuleConfig;
+
+    use super::strip_custom_sections;
+
+    fn custom_section(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut payload = vec![name.len() as u8];
+        payload.extend_from_slice(name.as_bytes());
+        payload.extend_from_slice(data);
+        let mut section = vec![0, payload.len() as u8];
+        section.extend(payload);
+        section
+    }
+
+    pub fn custom_sections_are_stripped() {
+        let wasm = [
+            &[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00][..], // magic bytes, binary version
+            &custom_section("build_info", &[0xaa; 20]),
+            &custom_section("sourceMappingURL", b"contract.wasm.map"),
+        ]
+        .concat();
+        let mut module = ModuleConfig::new().parse(&wasm).unwrap();
+        assert_eq!(module.customs.iter().count(), 2);
+
+        strip_custom_sections(&mut module);
+        let stripped = ModuleConfig::new().parse(&module.emit_wasm()).unwrap();
+        assert_eq!(stripped.customs.iter().count(), 0);
+    }
 }
@@ -0,0 +1,284 @@
+//! Stack height limiting instrumentation.
+//!
+//! Gas metering bounds how long a contract runs, but not how deep it recurses, and deep recursion
+//! overflows the interpreter's stack. Every call to a function of the contract is wrapped with
+//! code that adds the callee's frame size to a global counter before the call, traps when the
+//! counter goes above the limit, and subtracts the frame size again after the call returns.
+//!
+//! The frame size of a function is approximated as one slot for the frame itself plus one per
+//! local (parameters included). Indirect calls are charged the largest frame size in the module.
+//! This runs after gas metering so it doesn't change the gas cost of contracts.
+//!
+//! The limit is held in an exported global, so that it can be lifted for each instance until
+//! governance activates it, see `feature_gates::STACK_HEIGHT_LIMIT`.
+
+use std::collections::HashMap;
+
+use log::*;
+
+use walrus::{ir::*, FunctionId, GlobalId, InitExpr, LocalFunction, Module, ValType};
+
+use enclave_ffi_types::EnclaveError;
+
+/// Name of the exported global that is set when the stack height limit was exceeded.
+pub const EXPORT_STACK_LIMIT_EXCEEDED: &str = "stack_limit_exceeded";
+/// Name of the exported global that holds the stack height limit.
+pub const EXPORT_STACK_HEIGHT_LIMIT: &str = "stack_height_limit";
+
+/// Configures the stack height limit on the given instance. `u32::MAX` never traps.
+pub fn set_stack_height_limit<C>(
+    instance: &wasm3::Instance<C>,
+    max_stack_height: u32,
+) -> Result<(), EnclaveError> {
+    instance
+        .set_global(EXPORT_STACK_HEIGHT_LIMIT, max_stack_height)
+        .map_err(|_err| EnclaveError::FailedGasMeteringInjection)
+}
+
+/// Returns whether the last call trapped because it exceeded the stack height limit.
+pub fn stack_limit_exceeded<C>(instance: &wasm3::Instance<C>) -> bool {
+    instance
+        .get_global::<u32>(EXPORT_STACK_LIMIT_EXCEEDED)
+        .unwrap_or_default()
+        != 0
+}
+
+/// Inject stack height limiting instrumentation into the module.
+pub fn add_stack_limit(module: &mut Module, max_stack_height: u32) {
+    let frame_sizes: HashMap<FunctionId, i32> = module
+        .funcs
+        .iter_local()
+        .map(|(id, func)| (id, frame_size(func)))
+        .collect();
+    let indirect_frame_size = frame_sizes.values().copied().max().unwrap_or(1);
+    trace!(
+        "limiting stack height to {}, largest frame is {}",
+        max_stack_height,
+        indirect_frame_size
+    );
+
+    let stack_height_global =
+        module
+            .globals
+            .add_local(ValType::I32, true, InitExpr::Value(Value::I32(0)));
+    let stack_limit_exceeded_global =
+        module
+            .globals
+            .add_local(ValType::I32, true, InitExpr::Value(Value::I32(0)));
+    let stack_height_limit_global = module.globals.add_local(
+        ValType::I32,
+        true,
+        InitExpr::Value(Value::I32(max_stack_height as i32)),
+    );
+    module
+        .exports
+        .add(EXPORT_STACK_LIMIT_EXCEEDED, stack_limit_exceeded_global);
+    module
+        .exports
+        .add(EXPORT_STACK_HEIGHT_LIMIT, stack_height_limit_global);
+
+    let globals = StackGlobals {
+        height: stack_height_global,
+        limit_exceeded: stack_limit_exceeded_global,
+        max_height: stack_height_limit_global,
+    };
+
+    for (_, func) in module.funcs.iter_local_mut() {
+        // get the list of "original" blocks before we start adding more.
+        let block_ids: Vec<_> = func.blocks().map(|(block_id, _block)| block_id).collect();
+        for block_id in block_ids {
+            inject_stack_limit(func, block_id, &frame_sizes, indirect_frame_size, &globals);
+        }
+    }
+}
+
+struct StackGlobals {
+    height: GlobalId,
+    limit_exceeded: GlobalId,
+    max_height: GlobalId,
+}
+
+fn frame_size(func: &LocalFunction) -> i32 {
+    let mut locals: Vec<LocalId> = func.args.clone();
+    for (_, block) in func.blocks() {
+        for (instr, _) in block.instrs.iter() {
+            let local = match instr {
+                Instr::LocalGet(LocalGet { local })
+                | Instr::LocalSet(LocalSet { local })
+                | Instr::LocalTee(LocalTee { local }) => *local,
+                _ => continue,
+            };
+            if !locals.contains(&local) {
+                locals.push(local);
+            }
+        }
+    }
+    1 + locals.len() as i32
+}
+
+fn inject_stack_limit(
+    func: &mut LocalFunction,
+    block_id: InstrSeqId,
+    frame_sizes: &HashMap<FunctionId, i32>,
+    indirect_frame_size: i32,
+    globals: &StackGlobals,
+) {
+    let calls: Vec<(usize, i32)> = func
+        .block(block_id)
+        .instrs
+        .iter()
+        .enumerate()
+        .filter_map(|(loc, (instr, _))| match instr {
+            // Imported functions aren't in `frame_sizes`, they don't use the contract's stack
+            Instr::Call(Call { func }) => frame_sizes.get(func).map(|size| (loc, *size)),
+            Instr::CallIndirect(_) => Some((loc, indirect_frame_size)),
+            _ => None,
+        })
+        .collect();
+
+    // This is done in reverse because the indices are locations in the underlying instruction
+    // array. Doing this in order would invalidate the indices.
+    for (loc, frame_size) in calls.into_iter().rev() {
+        let builder = func.builder_mut();
+        let mut before = builder.dangling_instr_seq(None);
+        before
+            // globals[stack_height] += frame_size;
+            .global_get(globals.height)
+            .i32_const(frame_size)
+            .binop(BinaryOp::I32Add)
+            .global_set(globals.height)
+            // if unsigned(globals[stack_height]) > unsigned(globals[max_height]) { throw(); }
+            .global_get(globals.height)
+            .global_get(globals.max_height)
+            .binop(BinaryOp::I32GtU)
+            .if_else(
+                None,
+                |then| {
+                    then.i32_const(1)
+                        .global_set(globals.limit_exceeded)
+                        .unreachable();
+                },
+                |_else| {},
+            );
+        let before: Vec<_> = before.instrs_mut().drain(..).collect();
+
+        let builder = func.builder_mut();
+        let mut after = builder.dangling_instr_seq(None);
+        after
+            // globals[stack_height] -= frame_size;
+            .global_get(globals.height)
+            .i32_const(frame_size)
+            .binop(BinaryOp::I32Sub)
+            .global_set(globals.height);
+        let after: Vec<_> = after.instrs_mut().drain(..).collect();
+
+        let block_instrs = &mut func.block_mut(block_id).instrs;
+        block_instrs.splice(loc + 1..loc + 1, after);
+        block_instrs.splice(loc..loc, before);
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use walrus::{Module, ModuleConfig};
+
+    use super::{add_stack_limit, set_stack_height_limit, stack_limit_exceeded};
+
+    /// A module exporting `recurse(n)`, which calls itself `n` times and returns 0:
+    ///
+    /// (func (export "recurse") (param i32) (result i32)
+    ///   local.get 0
+    ///   if (result i32)
+    ///     local.get 0 i32.const 1 i32.sub call 0
+    ///   else
+    ///     i32.const 0
+    ///   end)
+    #[rustfmt::skip]
+    const RECURSIVE_WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic bytes, binary version
+        0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f, // type section: (i32) -> i32
+        0x03, 0x02, 0x01, 0x00, // function section: one function of type 0
+        0x07, 0x0b, 0x01, 0x07, b'r', b'e', b'c', b'u', b'r', b's', b'e', 0x00, 0x00, // exports
+        0x0a, 0x13, 0x01, 0x11, 0x00, // code section: one body of 17 bytes, no locals
+        0x20, 0x00, 0x04, 0x7f, // local.get 0, if (result i32)
+        0x20, 0x00, 0x41, 0x01, 0x6b, 0x10, 0x00, // local.get 0, i32.const 1, i32.sub, call 0
+        0x05, 0x41, 0x00, 0x0b, // else, i32.const 0, end
+        0x0b, // end
+    ];
+
+    fn recursive_module() -> Module {
+        ModuleConfig::new().parse(RECURSIVE_WASM).unwrap()
+    }
+
+    fn call_recurse(
+        module: &mut Module,
+        depth: u32,
+        max_stack_height: Option<u32>,
+    ) -> (Result<u32, wasm3::Error>, bool) {
+        let code = module.emit_wasm();
+        let environment = wasm3::Environment::new().unwrap();
+        let runtime = environment.new_runtime::<()>(1024 * 60, Some(1)).unwrap();
+        let module = environment.parse_module(&code).unwrap();
+        let instance = runtime.load_module(module).unwrap();
+        if let Some(max_stack_height) = max_stack_height {
+            set_stack_height_limit(&instance, max_stack_height).unwrap();
+        }
+
+        let result = instance
+            .find_function::<u32, u32>("recurse")
+            .unwrap()
+            .call_with_context(&mut (), depth);
+        (result, stack_limit_exceeded(&instance))
+    }
+
+    pub fn stack_limit_allows_shallow_recursion() {
+        let mut module = recursive_module();
+        // Each frame of `recurse` has a size of 2, and the call from the host isn't counted
+        add_stack_limit(&mut module, 200);
+
+        let (result, exceeded) = call_recurse(&mut module, 100, None);
+        assert_eq!(result.unwrap(), 0);
+        assert!(!exceeded);
+    }
+
+    pub fn stack_limit_stops_deep_recursion() {
+        let mut module = recursive_module();
+        add_stack_limit(&mut module, 200);
+
+        let (result, exceeded) = call_recurse(&mut module, 101, None);
+        assert!(matches!(result, Err(wasm3::Error::UnreachableExecuted)));
+        assert!(exceeded);
+    }
+
+    pub fn stack_limit_can_be_lifted() {
+        let mut module = recursive_module();
+        add_stack_limit(&mut module, 200);
+
+        let (result, exceeded) = call_recurse(&mut module, 101, Some(u32::MAX));
+        assert_eq!(result.unwrap(), 0);
+        assert!(!exceeded);
+
+        // and lowered
+        let (result, exceeded) = call_recurse(&mut module, 50, Some(100));
+        assert!(matches!(result, Err(wasm3::Error::UnreachableExecuted)));
+        assert!(exceeded);
+    }
+
+    pub fn stack_height_is_restored_after_calls() {
+        let mut module = recursive_module();
+        add_stack_limit(&mut module, 200);
+        let code = module.emit_wasm();
+
+        let environment = wasm3::Environment::new().unwrap();
+        let runtime = environment.new_runtime::<()>(1024 * 60, Some(1)).unwrap();
+        let instance = runtime
+            .load_module(environment.parse_module(&code).unwrap())
+            .unwrap();
+        let recurse = instance.find_function::<u32, u32>("recurse").unwrap();
+
+        // The limit would be exceeded on the second call if the first didn't release its frames
+        assert_eq!(recurse.call_with_context(&mut (), 100).unwrap(), 0);
+        assert_eq!(recurse.call_with_context(&mut (), 100).unwrap(), 0);
+        assert!(!stack_limit_exceeded(&instance));
+    }
+}
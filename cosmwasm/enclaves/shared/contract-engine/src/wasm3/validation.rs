@@ -1,6 +1,6 @@
 use log::*;
 
-use walrus::Module;
+use walrus::{ir::Instr, Module};
 
 use enclave_ffi_types::EnclaveError;
 
@@ -32,3 +32,24 @@ pub fn validate_memory(module: &mut Module) -> Result<(), EnclaveError> {
 
     Ok(())
 }
+
+/// Whether the module uses the bulk memory proposal, which only runs once governance activates it,
+/// see `feature_gates::BULK_MEMORY`
+pub fn uses_bulk_memory(module: &Module) -> bool {
+    module.funcs.iter_local().any(|(_, func)| {
+        func.blocks().any(|(_, block)| {
+            block.instrs.iter().any(|(instr, _)| {
+                matches!(
+                    instr,
+                    Instr::MemoryCopy { .. }
+                        | Instr::MemoryFill { .. }
+                        | Instr::MemoryInit { .. }
+                        | Instr::DataDrop { .. }
+                        | Instr::TableCopy { .. }
+                        | Instr::TableInit { .. }
+                        | Instr::ElemDrop { .. }
+                )
+            })
+        })
+    })
+}
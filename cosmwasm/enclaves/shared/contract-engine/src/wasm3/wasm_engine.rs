@@ -0,0 +1,114 @@
+//! The `WasmEngine` implementation of wasm3.
+
+use enclave_ffi_types::EnclaveError;
+
+use crate::engine::{WasmEngine, WasmInstance};
+use crate::errors::{ToEnclaveResult, WasmEngineError, WasmEngineResult};
+
+use super::gas;
+use super::Wasm3RuntimeEx;
+
+/// The stack of a contract, in bytes
+const STACK_SIZE: u32 = 1024 * 60;
+/// The most pages (64kb) the memory of a contract can have
+const MEMORY_LIMIT_PAGES: u32 = 192; // 12 MiB
+
+pub struct Wasm3Engine {
+    environment: wasm3::Environment,
+}
+
+impl Wasm3Engine {
+    pub fn new() -> Result<Self, EnclaveError> {
+        let environment = wasm3::Environment::new().to_enclave_result()?;
+        Ok(Self { environment })
+    }
+
+    /// Like `WasmEngine::with_instance`, but gives `func` the wasm3 instance itself, so that it
+    /// can link host functions to it.
+    pub fn with_wasm3_instance<C, R, F>(&self, code: &[u8], func: F) -> Result<R, EnclaveError>
+    where
+        C: 'static,
+        F: FnOnce(&mut wasm3::Instance<C>) -> R,
+    {
+        let runtime = self
+            .environment
+            .new_runtime::<C>(STACK_SIZE, Some(MEMORY_LIMIT_PAGES))
+            .to_enclave_result()?;
+        let module = self.environment.parse_module(code).to_enclave_result()?;
+        let mut instance = runtime.load_module(module).to_enclave_result()?;
+        Ok(func(&mut instance))
+    }
+}
+
+impl WasmEngine for Wasm3Engine {
+    type Error = wasm3::Error;
+
+    fn with_instance<C, R, F>(&self, code: &[u8], func: F) -> Result<R, EnclaveError>
+    where
+        C: 'static,
+        F: FnOnce(&mut dyn WasmInstance<C, Error = Self::Error>) -> R,
+    {
+        self.with_wasm3_instance(code, |instance| func(instance))
+    }
+}
+
+impl<C> WasmInstance<C> for wasm3::Instance<'_, C> {
+    type Error = wasm3::Error;
+
+    fn call(&self, context: &mut C, name: &str, args: &[u32]) -> Result<u32, Self::Error> {
+        match *args {
+            [] => self
+                .find_function::<(), u32>(name)?
+                .call_with_context(context, ()),
+            [a] => self
+                .find_function::<u32, u32>(name)?
+                .call_with_context(context, a),
+            [a, b] => self
+                .find_function::<(u32, u32), u32>(name)?
+                .call_with_context(context, (a, b)),
+            [a, b, c] => self
+                .find_function::<(u32, u32, u32), u32>(name)?
+                .call_with_context(context, (a, b, c)),
+            _ => Err(wasm3::Error::ArgumentCountMismatch),
+        }
+    }
+
+    fn memory_size(&self) -> usize {
+        self.runtime()
+            .try_with_memory_or((), |memory| memory.as_slice().len())
+            .unwrap_or_default()
+    }
+
+    fn read_memory(&self, offset: u32, len: usize) -> WasmEngineResult<Vec<u8>> {
+        let start = offset as usize;
+        self.runtime()
+            .try_with_memory_or(WasmEngineError::MemoryReadError, |memory| {
+                memory
+                    .as_slice()
+                    .get(start..start.saturating_add(len))
+                    .map(|slice| slice.to_vec())
+                    .ok_or(WasmEngineError::MemoryReadError)
+            })?
+    }
+
+    fn write_memory(&self, offset: u32, data: &[u8]) -> WasmEngineResult<()> {
+        let start = offset as usize;
+        self.runtime()
+            .try_with_memory_or(WasmEngineError::MemoryWriteError, |mut memory| {
+                memory
+                    .as_slice_mut()
+                    .get_mut(start..start.saturating_add(data.len()))
+                    .ok_or(WasmEngineError::MemoryWriteError)?
+                    .copy_from_slice(data);
+                Ok(())
+            })?
+    }
+
+    fn set_gas_limit(&self, gas_limit: u64) -> Result<(), EnclaveError> {
+        gas::set_gas_limit(self, gas_limit)
+    }
+
+    fn remaining_gas(&self) -> u64 {
+        gas::get_remaining_gas(self)
+    }
+}
@@ -184,22 +184,6 @@ impl HandleType {
             }
         }
     }
-
-    pub fn get_export_name(h: &HandleType) -> &'static str {
-        match h {
-            HandleType::HANDLE_TYPE_EXECUTE => "execute",
-            HandleType::HANDLE_TYPE_REPLY => "reply",
-            HandleType::HANDLE_TYPE_IBC_CHANNEL_OPEN => "ibc_channel_open",
-            HandleType::HANDLE_TYPE_IBC_CHANNEL_CONNECT => "ibc_channel_connect",
-            HandleType::HANDLE_TYPE_IBC_CHANNEL_CLOSE => "ibc_channel_close",
-            HandleType::HANDLE_TYPE_IBC_PACKET_RECEIVE => "ibc_packet_receive",
-            HandleType::HANDLE_TYPE_IBC_PACKET_ACK => "ibc_packet_ack",
-            HandleType::HANDLE_TYPE_IBC_PACKET_TIMEOUT => "ibc_packet_timeout",
-            HandleType::HANDLE_TYPE_IBC_WASM_HOOKS_INCOMING_TRANSFER => "execute",
-            HandleType::HANDLE_TYPE_IBC_WASM_HOOKS_OUTGOING_TRANSFER_ACK => "sudo",
-            HandleType::HANDLE_TYPE_IBC_WASM_HOOKS_OUTGOING_TRANSFER_TIMEOUT => "sudo",
-        }
-    }
 }
 
 #[allow(non_camel_case_types)]
@@ -29,6 +29,13 @@ pub enum CosmWasmApiVersion {
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
 pub enum ContractFeature {
     Random,
+    /// MAC the responses to queries, opted in to with a custom section rather than an export
+    QueryResponseMac,
+    /// Publish the cw2 version of the contract to the node, opted in to with a custom section
+    PublicContractVersion,
+    /// Uses the bulk memory proposal (memory.copy, memory.fill...), which only runs once governance
+    /// activates it
+    BulkMemory,
 }
 
 pub type BaseAddr = HumanAddr;
@@ -137,6 +144,10 @@ impl BaseEnv {
         }
     }
 
+    pub fn get_instantiate_salt(&self) -> Option<&[u8]> {
+        self.0.instantiate_salt.as_ref().map(|salt| salt.as_slice())
+    }
+
     pub fn get_verification_params(&self) -> (&BaseAddr, &BaseAddr, u64, &Vec<BaseCoin>) {
         (
             &self.0.message.sender,
@@ -178,6 +189,9 @@ impl BaseEnv {
                 message: v010types::MessageInfo {
                     sender: self.0.message.sender,
                     sent_funds: self.0.message.sent_funds,
+                    // like `transaction`, only v1 contracts get to know about fees
+                    fee_payer: None,
+                    fee_granter: None,
                 },
                 contract: v010types::ContractInfo {
                     address: self.0.contract.address,
@@ -187,6 +201,7 @@ impl BaseEnv {
                 contract_key: None,
                 contract_code_hash: self.0.contract_code_hash,
                 transaction: None,
+                instantiate_salt: None,
             },
         }
     }
@@ -219,6 +234,16 @@ impl BaseEnv {
                     .into_iter()
                     .map(|x| x.into())
                     .collect(),
+                fee_payer: self
+                    .0
+                    .message
+                    .fee_payer
+                    .map(|addr| v1types::Addr::unchecked(addr.0)),
+                fee_granter: self
+                    .0
+                    .message
+                    .fee_granter
+                    .map(|addr| v1types::Addr::unchecked(addr.0)),
             },
         }
     }
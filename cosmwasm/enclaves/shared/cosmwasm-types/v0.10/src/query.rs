@@ -19,6 +19,7 @@ pub enum QueryRequest {
     Gov(GovQuery),
     Ibc(IbcQuery),
     Stargate { path: String, data: Binary },
+    TokenFactory(TokenFactoryQuery),
 }
 
 /// These are queries to the various IBC modules to see the state of the contract's
@@ -57,6 +58,25 @@ pub enum MintQuery {
     BondedRatio {},
 }
 
+/// Queries about denoms created the token factory way, named `factory/{creator}/{subdenom}`.
+/// These are regular bank denoms, so their metadata and supply come from the bank module.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenFactoryQuery {
+    /// Returns the full name of the denom `creator_addr` creates with `subdenom`.
+    /// Return value is FullDenomResponse.
+    FullDenom {
+        creator_addr: HumanAddr,
+        subdenom: String,
+    },
+    /// Returns the bank metadata of a denom, if it has any.
+    /// Return value is DenomMetadataResponse.
+    DenomMetadata { denom: String },
+    /// Returns the total supply of a denom.
+    /// Return value is DenomSupplyResponse.
+    Supply { denom: String },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum BankQuery {
@@ -147,6 +167,12 @@ impl From<DistQuery> for QueryRequest {
     }
 }
 
+impl From<TokenFactoryQuery> for QueryRequest {
+    fn from(msg: TokenFactoryQuery) -> Self {
+        QueryRequest::TokenFactory(msg)
+    }
+}
+
 impl From<BankQuery> for QueryRequest {
     fn from(msg: BankQuery) -> Self {
         QueryRequest::Bank(msg)
@@ -123,6 +123,10 @@ pub struct Env {
     pub contract_code_hash: String,
     #[serde(default)]
     pub transaction: Option<TransactionInfo>,
+    /// Set when the contract is instantiated at an address derived from this salt, its code hash
+    /// and its creator, instead of from an instance counter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instantiate_salt: Option<Binary>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -168,6 +172,12 @@ pub struct MessageInfo {
     /// if you have a specific need for that feature: https://github.com/CosmWasm/cosmwasm/issues/293
     pub sender: HumanAddr,
     pub sent_funds: Vec<Coin>,
+    /// The account that paid the fees of the transaction, as reported by the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<HumanAddr>,
+    /// The account that granted the fees through a fee grant, if one was used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_granter: Option<HumanAddr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
@@ -379,6 +389,29 @@ pub struct LogAttribute {
     #[serde(default = "bool_true")]
     #[serde(skip_serializing)]
     pub encrypted: bool,
+    /// nonstandard, optional and only used in deserialization, like `encrypted`.
+    /// Who an encrypted attribute is encrypted to. Ignored for plaintext attributes.
+    #[serde(default)]
+    #[serde(skip_serializing)]
+    pub encryption: AttributeEncryption,
+}
+
+/// The key an encrypted log attribute is encrypted with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeEncryption {
+    /// The key of the sender of the transaction, like the rest of the output
+    Sender,
+    /// The key a recipient with this x25519 public key derives, as if it had sent the transaction
+    Recipient(Binary),
+    /// The contract's own encryption key, so only the contract can decrypt it
+    Contract,
+}
+
+impl Default for AttributeEncryption {
+    fn default() -> Self {
+        AttributeEncryption::Sender
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -408,6 +441,7 @@ pub fn log<K: ToString, V: ToString>(key: K, value: V) -> LogAttribute {
         key: key.to_string(),
         value: value.to_string(),
         encrypted: true,
+        encryption: AttributeEncryption::Sender,
     }
 }
 
@@ -417,5 +451,36 @@ pub fn plaintext_log<K: ToString, V: ToString>(key: K, value: V) -> LogAttribute
         key: key.to_string(),
         value: value.to_string(),
         encrypted: false,
+        encryption: AttributeEncryption::Sender,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_attribute_encryption_defaults_to_sender() {
+        let attr: LogAttribute = serde_json::from_str(r#"{"key":"a","value":"b"}"#).unwrap();
+        assert_eq!(attr, log("a", "b"));
+
+        let attr: LogAttribute =
+            serde_json::from_str(r#"{"key":"a","value":"b","encryption":{"recipient":"AQID"}}"#)
+                .unwrap();
+        assert!(attr.encrypted);
+        assert_eq!(
+            attr.encryption,
+            AttributeEncryption::Recipient(Binary(vec![1, 2, 3]))
+        );
+
+        let attr: LogAttribute =
+            serde_json::from_str(r#"{"key":"a","value":"b","encryption":"contract"}"#).unwrap();
+        assert_eq!(attr.encryption, AttributeEncryption::Contract);
+
+        // the encryption is not serialized, like `encrypted`
+        assert_eq!(
+            serde_json::to_string(&attr).unwrap(),
+            r#"{"key":"a","value":"b"}"#
+        );
     }
 }
@@ -30,6 +30,7 @@ where
     Wasm(WasmMsg),
     Gov(GovMsg),
     FinalizeTx(Empty),
+    Secret(SecretMsg),
 }
 
 /// The message types of the bank module.
@@ -232,3 +233,23 @@ pub enum VoteOption {
     Abstain,
     NoWithVeto,
 }
+
+/// Operations of the chain's own privacy features, as opposed to those of contracts.
+///
+/// A variant can be added here before the chain supports it. Until a node dispatches it, the
+/// message fails, like any other message the node doesn't know how to handle.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretMsg {
+    /// Moves the given coins from the contract's bank balance to its shielded balance.
+    Shield { amount: Vec<Coin> },
+    /// Moves the given coins from the contract's shielded balance back to its bank balance.
+    Unshield { amount: Vec<Coin> },
+    /// Sends the given coins from the contract's shielded balance to the shielded balance of
+    /// `to_address`, without revealing the amount on chain.
+    ShieldedSend {
+        to_address: String,
+        amount: Vec<Coin>,
+    },
+}
@@ -91,6 +91,14 @@ pub struct MessageInfo {
     /// or `MsgExecuteContract`. The transfer is processed in bank before the contract
     /// is executed such that the new balance is visible during contract execution.
     pub funds: Vec<Coin>,
+    /// The account that paid the fees of the transaction. It is reported by the node and not
+    /// verified by the enclave, so it must not be used for authorization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<Addr>,
+    /// The account that granted the fees of the transaction, if a fee grant was used. Like
+    /// `fee_payer`, it is not verified by the enclave.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_granter: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
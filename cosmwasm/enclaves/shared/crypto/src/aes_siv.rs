@@ -14,12 +14,10 @@
 /// would expect it to be. 256/512 bit for Aes128/256 respectively.
 ///
 /// The result of encrypted data will be the size of the data + 16 bytes, same as in GCM mode
+use crate::backend::backend;
 use crate::keys::{AESKey, SymmetricKey};
 use crate::traits::SIVEncryptable;
 use crate::CryptoError;
-use aes_siv::aead::generic_array::GenericArray;
-use aes_siv::siv::Aes128Siv;
-use log::*;
 
 impl SIVEncryptable for AESKey {
     fn encrypt_siv(&self, plaintext: &[u8], ad: Option<&[&[u8]]>) -> Result<Vec<u8>, CryptoError> {
@@ -38,11 +36,7 @@ fn aes_siv_encrypt(
 ) -> Result<Vec<u8>, CryptoError> {
     let ad = ad.unwrap_or(&[&[]]);
 
-    let mut cipher = Aes128Siv::new(GenericArray::clone_from_slice(key));
-    cipher.encrypt(ad, plaintext).map_err(|e| {
-        warn!("aes_siv_encrypt error: {:?}", e);
-        CryptoError::EncryptionError
-    })
+    backend().aes_siv_encrypt(key, plaintext, ad)
 }
 
 fn aes_siv_decrypt(
@@ -52,11 +46,7 @@ fn aes_siv_decrypt(
 ) -> Result<Vec<u8>, CryptoError> {
     let ad = ad.unwrap_or(&[&[]]);
 
-    let mut cipher = Aes128Siv::new(GenericArray::clone_from_slice(key));
-    cipher.decrypt(ad, ciphertext).map_err(|e| {
-        warn!("aes_siv_decrypt error: {:?}", e);
-        CryptoError::DecryptionError
-    })
+    backend().aes_siv_decrypt(key, ciphertext, ad)
 }
 
 #[cfg(feature = "test")]
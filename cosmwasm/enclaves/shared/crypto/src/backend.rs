@@ -0,0 +1,234 @@
+//! The primitives every other module of this crate is built on.
+//!
+//! All in-enclave crypto (AES-SIV, HKDF, HMAC, SHA-256 and X25519) goes through the
+//! `CryptoBackend` returned by `backend()`. `DefaultBackend` is the implementation the network
+//! has always used. An alternative implementation, such as a formally verified library, only has to
+//! implement this trait, and can be differential-tested against the default one with
+//! `tests::assert_backends_agree` before being swapped in.
+//!
+//! Every implementation must produce bit-for-bit identical output to `DefaultBackend`, since the
+//! results are part of consensus (encrypted state, derived keys).
+
+use aes_siv::aead::generic_array::GenericArray;
+use aes_siv::siv::Aes128Siv;
+use log::*;
+use ring::{digest, hkdf, hmac};
+
+use crate::hash::sha::HASH_SIZE;
+use crate::keys::SymmetricKey;
+use crate::traits::HMAC_SIGNATURE_SIZE;
+use crate::CryptoError;
+
+pub const X25519_KEY_SIZE: usize = 32;
+
+pub trait CryptoBackend: Sync {
+    /// A short name, used in logs and test output
+    fn name(&self) -> &'static str;
+
+    /// AES-128-SIV, with `key` holding both the MAC and the encryption halves
+    fn aes_siv_encrypt(
+        &self,
+        key: &SymmetricKey,
+        plaintext: &[u8],
+        ad: &[&[u8]],
+    ) -> Result<Vec<u8>, CryptoError>;
+
+    fn aes_siv_decrypt(
+        &self,
+        key: &SymmetricKey,
+        ciphertext: &[u8],
+        ad: &[&[u8]],
+    ) -> Result<Vec<u8>, CryptoError>;
+
+    /// HKDF-SHA256 extract and expand, filling all of `output`
+    fn hkdf_sha_256(
+        &self,
+        salt: &[u8],
+        input: &[u8],
+        info: &[&[u8]],
+        output: &mut [u8],
+    ) -> Result<(), CryptoError>;
+
+    fn hmac_sha_256(&self, key: &[u8], data: &[u8]) -> [u8; HMAC_SIGNATURE_SIZE];
+
+    fn sha_256(&self, data: &[u8]) -> [u8; HASH_SIZE];
+
+    fn x25519_public_key(&self, secret: &[u8; X25519_KEY_SIZE]) -> [u8; X25519_KEY_SIZE];
+
+    fn x25519_diffie_hellman(
+        &self,
+        secret: &[u8; X25519_KEY_SIZE],
+        their_public: &[u8; X25519_KEY_SIZE],
+    ) -> [u8; X25519_KEY_SIZE];
+}
+
+/// aes-siv, ring and x25519-dalek
+pub struct DefaultBackend;
+
+static DEFAULT_BACKEND: DefaultBackend = DefaultBackend;
+
+/// The backend used by the rest of the crate
+pub fn backend() -> &'static dyn CryptoBackend {
+    &DEFAULT_BACKEND
+}
+
+impl CryptoBackend for DefaultBackend {
+    fn name(&self) -> &'static str {
+        "default"
+    }
+
+    fn aes_siv_encrypt(
+        &self,
+        key: &SymmetricKey,
+        plaintext: &[u8],
+        ad: &[&[u8]],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let mut cipher = Aes128Siv::new(GenericArray::clone_from_slice(key));
+        cipher.encrypt(ad, plaintext).map_err(|e| {
+            warn!("aes_siv_encrypt error: {:?}", e);
+            CryptoError::EncryptionError
+        })
+    }
+
+    fn aes_siv_decrypt(
+        &self,
+        key: &SymmetricKey,
+        ciphertext: &[u8],
+        ad: &[&[u8]],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let mut cipher = Aes128Siv::new(GenericArray::clone_from_slice(key));
+        cipher.decrypt(ad, ciphertext).map_err(|e| {
+            warn!("aes_siv_decrypt error: {:?}", e);
+            CryptoError::DecryptionError
+        })
+    }
+
+    fn hkdf_sha_256(
+        &self,
+        salt: &[u8],
+        input: &[u8],
+        info: &[&[u8]],
+        output: &mut [u8],
+    ) -> Result<(), CryptoError> {
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(input);
+        let okm = prk
+            .expand(info, OkmLength(output.len()))
+            .map_err(|_| CryptoError::DerivingKeyError)?;
+        okm.fill(output).map_err(|_| CryptoError::DerivingKeyError)
+    }
+
+    fn hmac_sha_256(&self, key: &[u8], data: &[u8]) -> [u8; HMAC_SIGNATURE_SIZE] {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+        let tag = hmac::sign(&key, data);
+
+        let mut result = [0u8; HMAC_SIGNATURE_SIZE];
+        result.copy_from_slice(tag.as_ref());
+        result
+    }
+
+    fn sha_256(&self, data: &[u8]) -> [u8; HASH_SIZE] {
+        let hash = digest::digest(&digest::SHA256, data);
+
+        let mut result = [0u8; HASH_SIZE];
+        result.copy_from_slice(hash.as_ref());
+        result
+    }
+
+    fn x25519_public_key(&self, secret: &[u8; X25519_KEY_SIZE]) -> [u8; X25519_KEY_SIZE] {
+        let secret = x25519_dalek::StaticSecret::from(*secret);
+        *x25519_dalek::PublicKey::from(&secret).as_bytes()
+    }
+
+    fn x25519_diffie_hellman(
+        &self,
+        secret: &[u8; X25519_KEY_SIZE],
+        their_public: &[u8; X25519_KEY_SIZE],
+    ) -> [u8; X25519_KEY_SIZE] {
+        let secret = x25519_dalek::StaticSecret::from(*secret);
+        let their_public = x25519_dalek::PublicKey::from(*their_public);
+        *secret.diffie_hellman(&their_public).as_bytes()
+    }
+}
+
+/// ring wants the output length of hkdf as a `KeyType`
+struct OkmLength(usize);
+
+impl hkdf::KeyType for OkmLength {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    /// Runs both backends over the same inputs and panics on the first difference
+    pub fn assert_backends_agree(a: &dyn CryptoBackend, b: &dyn CryptoBackend) {
+        let key = [7u8; 32];
+        let ad: &[&[u8]] = &[b"first", b"second"];
+        for plaintext in [&b""[..], b"a", &[0xabu8; 100][..]].iter() {
+            let ciphertext = a.aes_siv_encrypt(&key, plaintext, ad).unwrap();
+            assert_eq!(
+                ciphertext,
+                b.aes_siv_encrypt(&key, plaintext, ad).unwrap(),
+                "aes_siv_encrypt differs between {} and {}",
+                a.name(),
+                b.name()
+            );
+            assert_eq!(
+                b.aes_siv_decrypt(&key, &ciphertext, ad).unwrap(),
+                *plaintext
+            );
+        }
+
+        let mut okm_a = [0u8; 32];
+        let mut okm_b = [0u8; 32];
+        a.hkdf_sha_256(b"salt", b"input", &[b"info"], &mut okm_a)
+            .unwrap();
+        b.hkdf_sha_256(b"salt", b"input", &[b"info"], &mut okm_b)
+            .unwrap();
+        assert_eq!(okm_a, okm_b, "hkdf_sha_256 differs");
+
+        assert_eq!(
+            a.hmac_sha_256(&key, b"data"),
+            b.hmac_sha_256(&key, b"data"),
+            "hmac_sha_256 differs"
+        );
+        assert_eq!(a.sha_256(b"data"), b.sha_256(b"data"), "sha_256 differs");
+
+        let secret_a = [1u8; 32];
+        let secret_b = [2u8; 32];
+        let public_a = a.x25519_public_key(&secret_a);
+        let public_b = b.x25519_public_key(&secret_b);
+        assert_eq!(public_a, b.x25519_public_key(&secret_a), "x25519 differs");
+        assert_eq!(
+            a.x25519_diffie_hellman(&secret_a, &public_b),
+            b.x25519_diffie_hellman(&secret_b, &public_a),
+            "x25519_diffie_hellman differs"
+        );
+    }
+
+    pub fn test_default_backend_known_answers() {
+        // sha256("abc") from FIPS 180-2
+        assert_eq!(
+            hex::encode(DefaultBackend.sha_256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        // RFC 7748, section 6.1
+        let mut alice_secret = [0u8; 32];
+        alice_secret.copy_from_slice(
+            &hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+                .unwrap(),
+        );
+        assert_eq!(
+            hex::encode(DefaultBackend.x25519_public_key(&alice_secret)),
+            "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+        );
+    }
+
+    pub fn test_default_backend_agrees_with_itself() {
+        assert_backends_agree(backend(), &DefaultBackend);
+    }
+}
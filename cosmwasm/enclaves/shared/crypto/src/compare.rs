@@ -0,0 +1,32 @@
+//! Comparison of secret byte strings, such as viewing keys or MACs, in time that does not depend
+//! on where the first differing byte is.
+
+/// Returns whether `a` and `b` are equal. Only their lengths, which are assumed to be public,
+/// affect the running time.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y));
+
+    // Keep the optimizer from turning the fold back into an early-exit comparison
+    unsafe { core::ptr::read_volatile(&diff) == 0 }
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_ct_eq() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"viewing key", b"viewing key"));
+        assert!(!ct_eq(b"viewing key", b"viewing kez"));
+        assert!(!ct_eq(b"viewing key", b"Viewing key"));
+        assert!(!ct_eq(b"viewing key", b"viewing ke"));
+        assert!(!ct_eq(b"", b"a"));
+    }
+}
@@ -19,11 +19,15 @@ pub const ATTESTATION_CERTIFICATE_SAVE_PATH: &str = "attestation_cert.der";
 
 pub const SEED_EXCH_KEY_SAVE_PATH: &str = "node-master-key.txt";
 pub const IO_KEY_SAVE_PATH: &str = "io-master-key.txt";
+pub const IO_KYBER_KEY_SAVE_PATH: &str = "io-master-kyber-key.txt";
 pub const SEED_UPDATE_SAVE_PATH: &str = "seed.txt";
 
 pub const NODE_EXCHANGE_KEY_FILE: &str = "new_node_seed_exchange_keypair.sealed";
 pub const NODE_ENCRYPTED_SEED_KEY_GENESIS_FILE: &str = "consensus_seed.sealed";
 pub const NODE_ENCRYPTED_SEED_KEY_CURRENT_FILE: &str = "consensus_seed_current.sealed";
+/// Every seed that was current before a seed rotation, oldest first, kept so state written with them
+/// can still be read
+pub const NODE_ENCRYPTED_SEED_KEY_PREVIOUS_FILE: &str = "consensus_seeds_previous.sealed";
 
 #[cfg(feature = "random")]
 pub const REK_SEALED_FILE_NAME: &str = "rek.sealed";
@@ -72,6 +76,13 @@ lazy_static! {
     .to_str()
     .unwrap_or(DEFAULT_SGX_SECRET_PATH)
     .to_string();
+    pub static ref PREVIOUS_CONSENSUS_SEEDS_SEALING_PATH: String = path::Path::new(
+        &env::var(SCRT_SGX_STORAGE_ENV_VAR).unwrap_or_else(|_| DEFAULT_SGX_SECRET_PATH.to_string())
+    )
+    .join(NODE_ENCRYPTED_SEED_KEY_PREVIOUS_FILE)
+    .to_str()
+    .unwrap_or(DEFAULT_SGX_SECRET_PATH)
+    .to_string();
     pub static ref REGISTRATION_KEY_SEALING_PATH: String = path::Path::new(
         &env::var(SCRT_SGX_STORAGE_ENV_VAR).unwrap_or_else(|_| DEFAULT_SGX_SECRET_PATH.to_string())
     )
@@ -114,6 +125,10 @@ pub const RANDOMNESS_ENCRYPTION_KEY_SECRET_DERIVE_ORDER: u32 = 5;
 pub const INITIAL_RANDOMNESS_SEED_SECRET_DERIVE_ORDER: u32 = 6;
 pub const ADMIN_PROOF_SECRET_DERIVE_ORDER: u32 = 7;
 pub const CONTRACT_KEY_PROOF_SECRET_DERIVE_ORDER: u32 = 8;
+pub const SEED_ROTATION_KEY_DERIVE_ORDER: u32 = 9;
+pub const DISCLOSURE_SIGNING_SECRET_DERIVE_ORDER: u32 = 10;
+pub const CONTRACT_ENCRYPTION_SECRET_DERIVE_ORDER: u32 = 11;
+pub const CONSENSUS_IO_KYBER_KEYPAIR_DERIVE_ORDER: u32 = 12;
 
 pub const ENCRYPTED_KEY_MAGIC_BYTES: &[u8; 6] = b"secret";
 pub const CONSENSUS_SEED_VERSION: u16 = 2;
@@ -5,6 +5,7 @@ use sgx_types::sgx_align_ec256_private_t;
 
 // use x25519_dalek;
 
+use crate::backend::backend;
 use crate::CryptoError;
 
 use super::rng::rand_slice;
@@ -49,22 +50,16 @@ impl KeyPair {
         let mut secret_key = Ed25519PrivateKey::default();
         rand_slice(secret_key.get_mut())?;
 
-        let sk = x25519_dalek::StaticSecret::from(secret_key.to_owned().key.r as [u8; 32]);
-        let pk = x25519_dalek::PublicKey::from(&sk);
+        let public_key = backend().x25519_public_key(secret_key.key_ref());
 
         Ok(Self {
             secret_key,
-            public_key: *pk.as_bytes(),
+            public_key,
         })
     }
 
     pub fn diffie_hellman(&self, your_public: &[u8; SECRET_KEY_SIZE]) -> DhKey {
-        let my_secret =
-            x25519_dalek::StaticSecret::from(self.secret_key.to_owned().key.r as [u8; 32]);
-        let pk = x25519_dalek::PublicKey::from(*your_public);
-        let ss = my_secret.diffie_hellman(&pk);
-
-        *ss.as_bytes()
+        backend().x25519_diffie_hellman(self.secret_key.key_ref(), your_public)
     }
     pub fn get_privkey(&self) -> &[u8; SECRET_KEY_SIZE] {
         self.secret_key.key_ref()
@@ -85,11 +80,10 @@ impl<T: AlignedMemory + ExportECKey> From<T> for KeyPair {
         let mut secret_key = Ed25519PrivateKey::default();
         secret_key.get_mut().copy_from_slice(value.key_ref());
 
-        let my_secret = x25519_dalek::StaticSecret::from(secret_key.to_owned().key.r as [u8; 32]);
-        let pk = x25519_dalek::PublicKey::from(&my_secret);
+        let public_key = backend().x25519_public_key(secret_key.key_ref());
         Self {
             secret_key,
-            public_key: *pk.as_bytes(),
+            public_key,
         }
     }
 }
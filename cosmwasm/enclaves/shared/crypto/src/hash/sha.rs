@@ -1,15 +1,11 @@
+use crate::backend::backend;
 pub use ring::digest;
 pub use ring::digest::SHA256;
 
 pub const HASH_SIZE: usize = 32;
 
 pub fn sha_256(data: &[u8]) -> [u8; HASH_SIZE] {
-    let hash = digest::digest(&digest::SHA256, data);
-
-    let mut result = [0u8; HASH_SIZE];
-    result.copy_from_slice(hash.as_ref());
-
-    result
+    backend().sha_256(data)
 }
 
 // #[cfg(feature = "test")]
@@ -1,19 +1,12 @@
 use super::{AESKey, Hmac, HMAC_SIGNATURE_SIZE};
-use ring::hmac;
+use crate::backend::backend;
 
 impl Hmac for AESKey {
     fn sign_sha_256(&self, to_sign: &[u8]) -> [u8; HMAC_SIGNATURE_SIZE] {
         // let mut input_bytes: Vec<u8> = self.get().to_vec();
         // input_bytes.extend_from_slice(extra_key_info);
 
-        let key = hmac::Key::new(hmac::HMAC_SHA256, self.get());
-        let tag = hmac::sign(&key, to_sign);
-
-        let mut result: [u8; HMAC_SIGNATURE_SIZE] = [0u8; HMAC_SIGNATURE_SIZE];
-
-        result.copy_from_slice(tag.as_ref());
-
-        result
+        backend().hmac_sha_256(self.get(), to_sign)
     }
 }
 
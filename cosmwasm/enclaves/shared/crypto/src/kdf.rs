@@ -1,7 +1,6 @@
+use crate::backend::backend;
 use crate::traits::Kdf;
-use crate::{AESKey, Seed, SECRET_KEY_SIZE};
-
-use ring::hkdf;
+use crate::{AESKey, Seed};
 
 // Bitcoin halving block hash https://www.blockchain.com/btc/block/000000000000000000024bead8df69990852c202db0e0097c1a12ea637d7e96d
 const KDF_SALT: [u8; 32] = [
@@ -29,39 +28,15 @@ impl Kdf<AESKey> for Seed {
 }
 
 pub fn hkdf_sha_256(input_bytes: &[u8], info: &[&[u8]]) -> AESKey {
-    let salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &KDF_SALT);
-
-    let prk = salt.extract(input_bytes);
-
-    let okm = prk.expand(info, My(SECRET_KEY_SIZE)).unwrap();
-
     let mut result: AESKey = AESKey::default();
 
-    let _ = okm.fill(result.as_mut());
+    backend()
+        .hkdf_sha_256(&KDF_SALT, input_bytes, info, result.as_mut())
+        .unwrap();
 
     result
 }
 
-/// https://github.com/briansmith/ring/blob/master/tests/hkdf_tests.rs
-/// Generic newtype wrapper that lets us implement traits for externally-defined
-/// types.
-#[derive(Debug, PartialEq)]
-struct My<T: std::fmt::Debug + PartialEq>(T);
-
-impl hkdf::KeyType for My<usize> {
-    fn len(&self) -> usize {
-        self.0
-    }
-}
-
-impl From<hkdf::Okm<'_, My<usize>>> for My<Vec<u8>> {
-    fn from(okm: hkdf::Okm<My<usize>>) -> Self {
-        let mut r = vec![0u8; okm.len().0];
-        okm.fill(&mut r).unwrap();
-        My(r)
-    }
-}
-
 #[cfg(feature = "test")]
 pub mod tests {
     // use crate::crypto::CryptoError;
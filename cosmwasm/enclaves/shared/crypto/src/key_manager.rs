@@ -1,10 +1,14 @@
 use crate::consts::*;
 use crate::traits::{Kdf, SealedKey};
 use crate::CryptoError;
-use crate::{AESKey, KeyPair, Seed};
-use enclave_ffi_types::EnclaveError;
+use crate::{seal_seeds, unseal_seeds, AESKey, KeyPair, Seed};
+
+#[cfg(feature = "pq-hybrid")]
+use crate::kyber::KyberKeyPair;
+use enclave_ffi_types::{EnclaveError, MAX_PREVIOUS_CONSENSUS_SEEDS};
 use lazy_static::lazy_static;
 use log::*;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 // For phase 1 of the seed rotation, all consensus secrets come in two parts:
 // 1. The genesis seed generated on 15 September 2020
@@ -30,6 +34,10 @@ pub struct Keychain {
     consensus_seed_id: u16,
     consensus_seed: Option<SeedsHolder<Seed>>,
     consensus_state_ikm: Option<SeedsHolder<AESKey>>,
+    /// Every seed that was current before a seed rotation, oldest first
+    previous_consensus_seeds: Vec<Seed>,
+    /// The state ikms of `previous_consensus_seeds`, newest first
+    previous_consensus_state_ikms: Vec<AESKey>,
     consensus_seed_exchange_keypair: Option<SeedsHolder<KeyPair>>,
     consensus_io_exchange_keypair: Option<SeedsHolder<KeyPair>>,
     consensus_callback_secret: Option<SeedsHolder<AESKey>>,
@@ -40,6 +48,12 @@ pub struct Keychain {
     registration_key: Option<KeyPair>,
     admin_proof_secret: Option<AESKey>,
     contract_key_proof_secret: Option<AESKey>,
+    disclosure_signing_secret: Option<AESKey>,
+    /// Derived from the genesis seed, as data encrypted to contracts outlives seed rotations
+    contract_encryption_secret: Option<AESKey>,
+    /// Only derived from the current seed, as the hybrid tx encryption is newer than it
+    #[cfg(feature = "pq-hybrid")]
+    consensus_io_kyber_keypair: Option<KyberKeyPair>,
 }
 
 #[derive(Clone, Copy, Default)]
@@ -48,8 +62,15 @@ pub struct SeedsHolder<T> {
     pub current: T,
 }
 
+/// Whether `KEY_MANAGER` was already loaded from the sealed seeds. Seeds sealed after that only take
+/// effect once the enclave is restarted.
+pub static KEY_MANAGER_LOADED: AtomicBool = AtomicBool::new(false);
+
 lazy_static! {
-    pub static ref KEY_MANAGER: Keychain = Keychain::new();
+    pub static ref KEY_MANAGER: Keychain = {
+        KEY_MANAGER_LOADED.store(true, Ordering::SeqCst);
+        Keychain::new()
+    };
 }
 
 #[allow(clippy::new_without_default)]
@@ -79,11 +100,17 @@ impl Keychain {
 
         let registration_key = Self::unseal_registration_key();
 
+        let previous_consensus_seeds =
+            unseal_seeds(PREVIOUS_CONSENSUS_SEEDS_SEALING_PATH.as_str()).unwrap_or_default();
+        let previous_consensus_state_ikms = Self::derive_state_ikms(&previous_consensus_seeds);
+
         let mut x = Keychain {
             consensus_seed_id: CONSENSUS_SEED_VERSION,
             consensus_seed,
             registration_key,
             consensus_state_ikm: None,
+            previous_consensus_seeds,
+            previous_consensus_state_ikms,
             consensus_seed_exchange_keypair: None,
             consensus_io_exchange_keypair: None,
             consensus_callback_secret: None,
@@ -93,6 +120,10 @@ impl Keychain {
             random_encryption_key: None,
             admin_proof_secret: None,
             contract_key_proof_secret: None,
+            disclosure_signing_secret: None,
+            contract_encryption_secret: None,
+            #[cfg(feature = "pq-hybrid")]
+            consensus_io_kyber_keypair: None,
         };
 
         let _ = x.generate_consensus_master_keys();
@@ -100,6 +131,14 @@ impl Keychain {
         x
     }
 
+    fn derive_state_ikms(seeds: &[Seed]) -> Vec<AESKey> {
+        seeds
+            .iter()
+            .rev()
+            .map(|seed| seed.derive_key_from_this(&CONSENSUS_STATE_IKM_DERIVE_ORDER.to_be_bytes()))
+            .collect()
+    }
+
     fn unseal_registration_key() -> Option<KeyPair> {
         match KeyPair::unseal(REGISTRATION_KEY_SEALING_PATH.as_str()) {
             Ok(k) => Some(k),
@@ -157,6 +196,16 @@ impl Keychain {
         })
     }
 
+    /// The seeds that were current before each seed rotation, oldest first
+    pub fn get_previous_consensus_seeds(&self) -> &[Seed] {
+        &self.previous_consensus_seeds
+    }
+
+    /// The state ikms of the seeds that were current before each seed rotation, newest first
+    pub fn get_previous_consensus_state_ikms(&self) -> &[AESKey] {
+        &self.previous_consensus_state_ikms
+    }
+
     pub fn get_consensus_seed_id(&self) -> u16 {
         self.consensus_seed_id
     }
@@ -214,6 +263,28 @@ impl Keychain {
         })
     }
 
+    pub fn get_disclosure_signing_secret(&self) -> Result<AESKey, CryptoError> {
+        self.disclosure_signing_secret.ok_or_else(|| {
+            error!("Error accessing disclosure_signing_secret (does not exist, or was not initialized)");
+            CryptoError::ParsingError
+        })
+    }
+
+    pub fn get_contract_encryption_secret(&self) -> Result<AESKey, CryptoError> {
+        self.contract_encryption_secret.ok_or_else(|| {
+            error!("Error accessing contract_encryption_secret (does not exist, or was not initialized)");
+            CryptoError::ParsingError
+        })
+    }
+
+    #[cfg(feature = "pq-hybrid")]
+    pub fn get_consensus_io_kyber_keypair(&self) -> Result<KyberKeyPair, CryptoError> {
+        self.consensus_io_kyber_keypair.ok_or_else(|| {
+            error!("Error accessing consensus_io_kyber_keypair (does not exist, or was not initialized)");
+            CryptoError::ParsingError
+        })
+    }
+
     pub fn reseal_registration_key(&mut self) -> Result<(), EnclaveError> {
         match Self::unseal_registration_key() {
             Some(kp) => {
@@ -309,6 +380,47 @@ impl Keychain {
         Ok(())
     }
 
+    /// Seals the seeds that were current before each seed rotation, oldest first, replacing the
+    /// ones sealed so far. Used by nodes that register after a rotation.
+    pub fn set_previous_consensus_seeds(&mut self, seeds: Vec<Seed>) -> Result<(), EnclaveError> {
+        if seeds.len() > MAX_PREVIOUS_CONSENSUS_SEEDS {
+            error!(
+                "Cannot keep {} previous consensus seeds, the maximum is {}",
+                seeds.len(),
+                MAX_PREVIOUS_CONSENSUS_SEEDS
+            );
+            return Err(EnclaveError::FailedSeal);
+        }
+
+        debug!(
+            "Sealing {} previous consensus seeds in {}",
+            seeds.len(),
+            *PREVIOUS_CONSENSUS_SEEDS_SEALING_PATH
+        );
+        if let Err(e) = seal_seeds(&seeds, PREVIOUS_CONSENSUS_SEEDS_SEALING_PATH.as_str()) {
+            error!("Error sealing previous consensus seeds - error code 0xC14");
+            return Err(e);
+        }
+        self.previous_consensus_state_ikms = Self::derive_state_ikms(&seeds);
+        self.previous_consensus_seeds = seeds;
+        Ok(())
+    }
+
+    /// Replaces the current seed with `new_current`. The replaced seed is added to the previous
+    /// seeds first, so that state encrypted with it can still be decrypted after the rotation.
+    pub fn rotate_consensus_seed(&mut self, new_current: Seed) -> Result<(), EnclaveError> {
+        let seeds = self.get_consensus_seed().map_err(|_| {
+            error!("Cannot rotate the consensus seed before it is set");
+            EnclaveError::FailedUnseal
+        })?;
+
+        let mut previous_seeds = self.previous_consensus_seeds.clone();
+        previous_seeds.push(seeds.current);
+        self.set_previous_consensus_seeds(previous_seeds)?;
+
+        self.set_consensus_seed(seeds.genesis, new_current)
+    }
+
     pub fn generate_consensus_master_keys(&mut self) -> Result<(), EnclaveError> {
         if !self.is_consensus_seed_set() {
             trace!("Seed not initialized, skipping derivation of enclave keys");
@@ -479,6 +591,48 @@ impl Keychain {
             hex::encode(contract_key_proof_secret.get())
         );
 
+        let disclosure_signing_secret = self
+            .consensus_seed
+            .unwrap()
+            .current
+            .derive_key_from_this(&DISCLOSURE_SIGNING_SECRET_DERIVE_ORDER.to_be_bytes());
+
+        self.disclosure_signing_secret = Some(disclosure_signing_secret);
+
+        trace!(
+            "disclosure_signing_secret: {:?}",
+            hex::encode(disclosure_signing_secret.get())
+        );
+
+        let contract_encryption_secret = self
+            .consensus_seed
+            .unwrap()
+            .genesis
+            .derive_key_from_this(&CONTRACT_ENCRYPTION_SECRET_DERIVE_ORDER.to_be_bytes());
+
+        self.contract_encryption_secret = Some(contract_encryption_secret);
+
+        trace!(
+            "contract_encryption_secret: {:?}",
+            hex::encode(contract_encryption_secret.get())
+        );
+
+        #[cfg(feature = "pq-hybrid")]
+        {
+            let consensus_io_kyber_keypair = KyberKeyPair::derive_from_seed(
+                &self.consensus_seed.unwrap().current,
+                CONSENSUS_IO_KYBER_KEYPAIR_DERIVE_ORDER,
+            )
+            .map_err(|_| EnclaveError::InternalError)?;
+
+            trace!(
+                "consensus_io_kyber_keypair: {:?}",
+                hex::encode(consensus_io_kyber_keypair.get_pubkey())
+            );
+
+            self.consensus_io_kyber_keypair = Some(consensus_io_kyber_keypair);
+        }
+
         Ok(())
     }
 
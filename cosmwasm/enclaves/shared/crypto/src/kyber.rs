@@ -0,0 +1,54 @@
+//! Kyber768 key encapsulation, for the experimental hybrid X25519+Kyber768 tx encryption.
+//!
+//! The key pair is derived from the consensus seed like the X25519 io exchange key pair, so every
+//! node holds the same one.
+
+use log::*;
+use pqc_kyber::{KYBER_PUBLICKEYBYTES, KYBER_SECRETKEYBYTES};
+
+use crate::traits::Kdf;
+use crate::{CryptoError, Seed};
+
+pub use pqc_kyber::{KYBER_CIPHERTEXTBYTES, KYBER_SSBYTES};
+
+pub type KyberPublicKey = [u8; KYBER_PUBLICKEYBYTES];
+pub type KyberSharedSecret = [u8; KYBER_SSBYTES];
+
+#[derive(Clone, Copy)]
+pub struct KyberKeyPair {
+    public: KyberPublicKey,
+    secret: [u8; KYBER_SECRETKEYBYTES],
+}
+
+impl KyberKeyPair {
+    /// Derives the key pair from `seed`, using the two halves of the 64 byte seed Kyber expects
+    pub fn derive_from_seed(seed: &Seed, derive_order: u32) -> Result<Self, CryptoError> {
+        let mut kyber_seed = [0u8; 64];
+        for (i, half) in kyber_seed.chunks_mut(32).enumerate() {
+            let mut data = derive_order.to_be_bytes().to_vec();
+            data.push(i as u8);
+            half.copy_from_slice(seed.derive_key_from_this(&data).get());
+        }
+
+        let keypair = pqc_kyber::derive(&kyber_seed).map_err(|err| {
+            error!("Failed to derive a Kyber key pair: {:?}", err);
+            CryptoError::KeyError
+        })?;
+
+        Ok(KyberKeyPair {
+            public: keypair.public,
+            secret: keypair.secret,
+        })
+    }
+
+    pub fn get_pubkey(&self) -> &KyberPublicKey {
+        &self.public
+    }
+
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> Result<KyberSharedSecret, CryptoError> {
+        pqc_kyber::decapsulate(ciphertext, &self.secret).map_err(|err| {
+            warn!("Failed to decapsulate a Kyber ciphertext: {:?}", err);
+            CryptoError::DecryptionError
+        })
+    }
+}
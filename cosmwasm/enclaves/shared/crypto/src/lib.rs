@@ -14,17 +14,23 @@ mod errors;
 pub(crate) mod kdf;
 pub mod key_manager;
 mod keys;
+#[cfg(feature = "pq-hybrid")]
+pub mod kyber;
 mod storage;
 pub mod traits;
 
 // mod aes_gcm;
 mod aes_siv;
+pub mod backend;
+mod compare;
 pub mod ed25519;
 mod hmac;
 pub mod secp256k1;
 
 mod rng;
 
+pub mod shamir;
+
 pub mod hash;
 
 pub use errors::{CryptoError, WasmApiCryptoError};
@@ -34,11 +40,16 @@ pub use keys::{AESKey, Seed, SymmetricKey, SEED_KEY_SIZE};
 
 pub use ed25519::{Ed25519PublicKey, KeyPair, PUBLIC_KEY_SIZE, SECRET_KEY_SIZE};
 
+pub use compare::ct_eq;
 pub use hash::sha::{sha_256, HASH_SIZE};
 pub use traits::{Encryptable, Hmac, Kdf, SIVEncryptable, SealedKey, HMAC_SIGNATURE_SIZE};
 
 pub use kdf::hkdf_sha_256;
 
+pub use storage::{seal_seeds, unseal_seeds};
+
+pub use backend::{backend, CryptoBackend, DefaultBackend};
+
 #[cfg(feature = "test")]
 pub mod tests {
     /// Catch failures like the standard test runner, and print similar information per test.
@@ -60,10 +71,18 @@ pub mod tests {
     }
 
     pub fn run_tests() {
-        let failures = 0;
+        let mut failures = 0;
 
         count_failures!(failures, {
             // todo: add encryption and other tests here
+            crate::shamir::tests::test_gf_inv();
+            crate::shamir::tests::test_split_and_combine();
+            crate::shamir::tests::test_combine_not_enough_shares();
+            crate::shamir::tests::test_combine_duplicate_shares();
+            crate::shamir::tests::test_split_invalid_parameters();
+            crate::backend::tests::test_default_backend_known_answers();
+            crate::backend::tests::test_default_backend_agrees_with_itself();
+            crate::compare::tests::test_ct_eq();
         });
 
         if failures != 0 {
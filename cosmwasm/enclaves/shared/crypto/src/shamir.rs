@@ -0,0 +1,185 @@
+//! Shamir secret sharing over GF(2^8), one polynomial per byte of the secret.
+//!
+//! Used to split the consensus seed across a set of validators, so that it can only be
+//! reconstructed from `threshold` of the shares.
+
+use crate::rng::rand_slice;
+use crate::CryptoError;
+use log::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecretShare {
+    /// The x coordinate of the share. Never zero, since the secret lives at x = 0.
+    pub index: u8,
+    pub threshold: u8,
+    pub data: Vec<u8>,
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+/// Written without data-dependent branches or lookup tables.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0u8;
+    for _ in 0..8 {
+        result ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    result
+}
+
+/// a^254 == a^-1 for every non zero a
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent != 0 {
+        if exponent & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Splits `secret` into `count` shares, any `threshold` of which can reconstruct it.
+pub fn split_secret(
+    secret: &[u8],
+    threshold: u8,
+    count: u8,
+) -> Result<Vec<SecretShare>, CryptoError> {
+    if threshold == 0 || count < threshold {
+        warn!(
+            "Invalid secret sharing parameters: threshold {} of {} shares",
+            threshold, count
+        );
+        return Err(CryptoError::KeyError);
+    }
+
+    // coefficients[i] holds the random coefficients of the polynomial for byte i, the
+    // constant term being the secret byte itself
+    let degree = threshold as usize - 1;
+    let mut coefficients = vec![0u8; secret.len() * degree];
+    rand_slice(&mut coefficients)?;
+
+    let shares = (1..=count)
+        .map(|x| {
+            let data = secret
+                .iter()
+                .enumerate()
+                .map(|(i, secret_byte)| {
+                    // Horner's method, from the highest degree coefficient down to the secret
+                    let poly = &coefficients[i * degree..(i + 1) * degree];
+                    let acc = poly
+                        .iter()
+                        .rev()
+                        .fold(0u8, |acc, coefficient| gf_mul(acc, x) ^ coefficient);
+                    gf_mul(acc, x) ^ secret_byte
+                })
+                .collect();
+
+            SecretShare {
+                index: x,
+                threshold,
+                data,
+            }
+        })
+        .collect();
+
+    Ok(shares)
+}
+
+/// Reconstructs the secret from at least `threshold` shares with distinct indices.
+pub fn combine_shares(shares: &[SecretShare]) -> Result<Vec<u8>, CryptoError> {
+    let first = shares.first().ok_or(CryptoError::MissingKeyError)?;
+    let threshold = first.threshold as usize;
+    let secret_len = first.data.len();
+
+    if shares.len() < threshold {
+        warn!(
+            "Not enough shares to reconstruct the secret: got {}, need {}",
+            shares.len(),
+            threshold
+        );
+        return Err(CryptoError::MissingKeyError);
+    }
+
+    let shares = &shares[..threshold];
+    for (i, share) in shares.iter().enumerate() {
+        if share.index == 0
+            || share.threshold != first.threshold
+            || share.data.len() != secret_len
+            || shares[..i].iter().any(|other| other.index == share.index)
+        {
+            warn!("Inconsistent secret share with index {}", share.index);
+            return Err(CryptoError::KeyError);
+        }
+    }
+
+    // Lagrange interpolation at x = 0. In GF(2^8) subtraction is xor, so
+    // basis_j(0) = prod_{m != j} x_m / (x_m ^ x_j)
+    let basis: Vec<u8> = shares
+        .iter()
+        .map(|share_j| {
+            shares
+                .iter()
+                .filter(|share_m| share_m.index != share_j.index)
+                .fold(1u8, |acc, share_m| {
+                    gf_mul(
+                        acc,
+                        gf_mul(share_m.index, gf_inv(share_m.index ^ share_j.index)),
+                    )
+                })
+        })
+        .collect();
+
+    let secret = (0..secret_len)
+        .map(|i| {
+            shares
+                .iter()
+                .zip(basis.iter())
+                .fold(0u8, |acc, (share, b)| acc ^ gf_mul(share.data[i], *b))
+        })
+        .collect();
+
+    Ok(secret)
+}
+
+#[cfg(feature = "test")]
+pub mod tests {
+    use super::*;
+
+    pub fn test_gf_inv() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    pub fn test_split_and_combine() {
+        let secret = b"consensus seed consensus seed 32".to_vec();
+        let shares = split_secret(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        assert_eq!(combine_shares(&shares[..3]).unwrap(), secret);
+        assert_eq!(combine_shares(&shares[2..]).unwrap(), secret);
+        let picked = vec![shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(combine_shares(&picked).unwrap(), secret);
+    }
+
+    pub fn test_combine_not_enough_shares() {
+        let shares = split_secret(b"secret", 3, 5).unwrap();
+        assert!(combine_shares(&shares[..2]).is_err());
+    }
+
+    pub fn test_combine_duplicate_shares() {
+        let shares = split_secret(b"secret", 2, 3).unwrap();
+        let duplicated = vec![shares[1].clone(), shares[1].clone()];
+        assert!(combine_shares(&duplicated).is_err());
+    }
+
+    pub fn test_split_invalid_parameters() {
+        assert!(split_secret(b"secret", 0, 3).is_err());
+        assert!(split_secret(b"secret", 4, 3).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::ed25519::Ed25519PrivateKey;
 use crate::traits::SealedKey;
-use crate::{AESKey, KeyPair, Seed, SECRET_KEY_SIZE};
+use crate::{AESKey, KeyPair, Seed, SECRET_KEY_SIZE, SEED_KEY_SIZE};
 use enclave_ffi_types::EnclaveError;
 use log::*;
 use std::io::{Read, Write};
@@ -40,6 +40,51 @@ impl SealedKey for KeyPair {
     }
 }
 
+/// Seals `seeds` in a single file, in order
+pub fn seal_seeds(seeds: &[Seed], filepath: &str) -> Result<(), EnclaveError> {
+    let mut file = SgxFile::create(filepath).map_err(|_err| {
+        error!("error creating file {}: {:?}", filepath, _err);
+        EnclaveError::FailedSeal
+    })?;
+
+    for seed in seeds {
+        file.write_all(seed.as_slice()).map_err(|_err| {
+            error!("error writing to path {}: {:?}", filepath, _err);
+            EnclaveError::FailedSeal
+        })?;
+    }
+    Ok(())
+}
+
+/// Unseals the seeds sealed by `seal_seeds`, in the order they were sealed in
+pub fn unseal_seeds(filepath: &str) -> Result<Vec<Seed>, EnclaveError> {
+    let mut file = SgxFile::open(filepath).map_err(|err| {
+        error!("failed to unseal file! {:?}", err);
+        EnclaveError::FailedUnseal
+    })?;
+
+    let mut buf = vec![];
+    file.read_to_end(&mut buf)
+        .map_err(|_err| EnclaveError::FailedUnseal)?;
+
+    if buf.len() % SEED_KEY_SIZE != 0 {
+        error!(
+            "[Enclave] Sealed seeds in {} have an invalid length {}",
+            filepath,
+            buf.len()
+        );
+        return Err(EnclaveError::FailedUnseal);
+    }
+    Ok(buf
+        .chunks(SEED_KEY_SIZE)
+        .map(|chunk| {
+            let mut seed = Seed::default();
+            seed.as_mut().copy_from_slice(chunk);
+            seed
+        })
+        .collect())
+}
+
 fn seal(data: &[u8; 32], filepath: &str) -> Result<(), EnclaveError> {
     let mut file = SgxFile::create(filepath).map_err(|_err| {
         error!("error creating file {}: {:?}", filepath, _err);
@@ -0,0 +1,192 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use enclave_ffi_types::AllocatorStats;
+
+/// TrackingAllocator wraps the SGX system allocator and keeps count of the heap the enclave
+/// is using.
+pub struct TrackingAllocator;
+
+/// Contract calls, including the calls they make to other contracts, that allocated more heap
+/// than this at once are counted in `soft_limit_hits`. It's a metric only: how much a call
+/// allocates also depends on the state of the node, like its module cache, so it can't decide
+/// whether a transaction fails.
+pub const CALL_HEAP_SOFT_LIMIT_BYTES: i64 = 256 * 1024 * 1024;
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static HIGH_WATERMARK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static FAILED_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static SOFT_LIMIT_HITS: AtomicU64 = AtomicU64::new(0);
+static WASM_MEMORY_GROWN_PAGES: AtomicU64 = AtomicU64::new(0);
+static WASM_MEMORY_GROWTH_LIMIT_HITS: AtomicU64 = AtomicU64::new(0);
+
+/// Snapshot of the statistics that were last sent to the host
+static LAST_REPORTED_WATERMARK: AtomicUsize = AtomicUsize::new(0);
+static LAST_REPORTED_FAILURES: AtomicU64 = AtomicU64::new(0);
+static LAST_REPORTED_SOFT_LIMIT_HITS: AtomicU64 = AtomicU64::new(0);
+static LAST_REPORTED_GROWTH_LIMIT_HITS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Bytes this thread allocated and didn't free yet. It goes negative when the thread frees
+    /// memory another thread allocated, so only differences between two readings are meaningful.
+    static THREAD_BYTES: Cell<i64> = Cell::new(0);
+    /// The most `THREAD_BYTES` reached since the innermost `CallHeapTracker` started
+    static THREAD_PEAK_BYTES: Cell<i64> = Cell::new(0);
+    /// Whether a `CallHeapTracker` is running on this thread
+    static IN_CALL: Cell<bool> = Cell::new(false);
+}
+
+impl TrackingAllocator {
+    fn reserve(size: usize) {
+        let current = CURRENT_BYTES
+            .fetch_add(size, Ordering::Relaxed)
+            .saturating_add(size);
+        HIGH_WATERMARK_BYTES.fetch_max(current, Ordering::Relaxed);
+        THREAD_BYTES.with(|bytes| {
+            let current = bytes.get().saturating_add(size as i64);
+            bytes.set(current);
+            THREAD_PEAK_BYTES.with(|peak| peak.set(peak.get().max(current)));
+        });
+    }
+
+    fn release(size: usize) {
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+        THREAD_BYTES.with(|bytes| bytes.set(bytes.get().saturating_sub(size as i64)));
+    }
+
+    fn failed(size: usize) {
+        Self::release(size);
+        FAILED_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::reserve(layout.size());
+        let ptr = System.alloc(layout);
+        if ptr.is_null() {
+            Self::failed(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::reserve(layout.size());
+        let ptr = System.alloc_zeroed(layout);
+        if ptr.is_null() {
+            Self::failed(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::release(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let old_size = layout.size();
+        if new_size > old_size {
+            Self::reserve(new_size - old_size);
+        }
+
+        let new_ptr = System.realloc(ptr, layout, new_size);
+
+        if new_ptr.is_null() {
+            if new_size > old_size {
+                Self::failed(new_size - old_size);
+            } else {
+                FAILED_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            }
+        } else if new_size < old_size {
+            Self::release(old_size - new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Records the pages a contract call grew its wasm memory by, and whether the call hit the
+/// memory growth limit. The wasm memory of a contract lives on the enclave heap.
+pub fn record_wasm_memory_growth(pages: u32, limit_exceeded: bool) {
+    WASM_MEMORY_GROWN_PAGES.fetch_add(pages as u64, Ordering::Relaxed);
+    if limit_exceeded {
+        WASM_MEMORY_GROWTH_LIMIT_HITS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Measures the most heap a contract call had allocated at once, counting the allocations of the
+/// thread running it. A contract querying another one runs the query on the same thread, so the
+/// peak of the outer call includes the peak of the inner one, and only the outermost call is
+/// compared to `CALL_HEAP_SOFT_LIMIT_BYTES`.
+pub struct CallHeapTracker {
+    start_bytes: i64,
+    outer_peak_bytes: i64,
+    outermost: bool,
+}
+
+impl CallHeapTracker {
+    pub fn start() -> Self {
+        let start_bytes = THREAD_BYTES.with(Cell::get);
+        let outer_peak_bytes = THREAD_PEAK_BYTES.with(|peak| peak.replace(start_bytes));
+        let outermost = !IN_CALL.with(|in_call| in_call.replace(true));
+        Self {
+            start_bytes,
+            outer_peak_bytes,
+            outermost,
+        }
+    }
+
+    /// The most bytes the call had allocated at once since the tracker started
+    pub fn high_watermark_bytes(&self) -> u64 {
+        let peak = THREAD_PEAK_BYTES.with(Cell::get);
+        peak.saturating_sub(self.start_bytes).max(0) as u64
+    }
+}
+
+impl Drop for CallHeapTracker {
+    fn drop(&mut self) {
+        if self.outermost {
+            if self.high_watermark_bytes() > CALL_HEAP_SOFT_LIMIT_BYTES as u64 {
+                SOFT_LIMIT_HITS.fetch_add(1, Ordering::Relaxed);
+            }
+            IN_CALL.with(|in_call| in_call.set(false));
+        }
+        // Hand the peak back to the call this one is nested in
+        THREAD_PEAK_BYTES.with(|peak| peak.set(peak.get().max(self.outer_peak_bytes)));
+    }
+}
+
+pub fn stats() -> AllocatorStats {
+    AllocatorStats {
+        current_bytes: CURRENT_BYTES.load(Ordering::Relaxed) as u64,
+        high_watermark_bytes: HIGH_WATERMARK_BYTES.load(Ordering::Relaxed) as u64,
+        failed_allocations: FAILED_ALLOCATIONS.load(Ordering::Relaxed),
+        soft_limit_hits: SOFT_LIMIT_HITS.load(Ordering::Relaxed),
+        wasm_memory_grown_pages: WASM_MEMORY_GROWN_PAGES.load(Ordering::Relaxed),
+        wasm_memory_growth_limit_hits: WASM_MEMORY_GROWTH_LIMIT_HITS.load(Ordering::Relaxed),
+    }
+}
+
+/// Returns the current statistics if the high-watermark moved, allocations failed or a contract
+/// hit the memory growth limit since the last time this returned something. This keeps the host from being pinged on every call.
+pub fn take_unreported_stats() -> Option<AllocatorStats> {
+    let stats = stats();
+
+    let watermark = stats.high_watermark_bytes as usize;
+    let watermark_moved = LAST_REPORTED_WATERMARK.swap(watermark, Ordering::Relaxed) != watermark;
+    let new_failures = LAST_REPORTED_FAILURES.swap(stats.failed_allocations, Ordering::Relaxed)
+        != stats.failed_allocations;
+    let new_soft_limit_hits = LAST_REPORTED_SOFT_LIMIT_HITS
+        .swap(stats.soft_limit_hits, Ordering::Relaxed)
+        != stats.soft_limit_hits;
+    let new_growth_limit_hits = LAST_REPORTED_GROWTH_LIMIT_HITS
+        .swap(stats.wasm_memory_growth_limit_hits, Ordering::Relaxed)
+        != stats.wasm_memory_growth_limit_hits;
+
+    if watermark_moved || new_failures || new_soft_limit_hits || new_growth_limit_hits {
+        Some(stats)
+    } else {
+        None
+    }
+}
@@ -7,6 +7,7 @@ extern crate core;
 #[cfg(not(target_env = "sgx"))]
 extern crate sgx_tstd as std;
 
+pub mod alloc_tracker;
 pub mod kv_cache;
 pub mod logger;
 pub mod macros;
@@ -0,0 +1,79 @@
+//! Generates the lists of supported imports and required exports checked on upload from the Wasm
+//! ABI schema in enclave-ffi-types. See `compatability.rs`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use enclave_ffi_types::wasm_abi::{parse_schema, AbiEntry, AbiVersion, WASM_ABI_SCHEMA};
+
+fn main() {
+    let entries: Vec<AbiEntry> = parse_schema(WASM_ABI_SCHEMA)
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|err| panic!("{}", err));
+
+    let mut code = String::new();
+    write_imports(
+        &mut code,
+        "const SUPPORTED_IMPORTS_V010",
+        AbiVersion::V010,
+        &entries,
+    );
+    write_imports(
+        &mut code,
+        "const SUPPORTED_IMPORTS_V1",
+        AbiVersion::V1,
+        &entries,
+    );
+    write_exports(
+        &mut code,
+        "const REQUIRED_EXPORTS_V010",
+        AbiVersion::V010,
+        &entries,
+    );
+    write_exports(
+        &mut code,
+        "const REQUIRED_EXPORTS_V1",
+        AbiVersion::V1,
+        &entries,
+    );
+    write_exports(
+        &mut code,
+        "pub const REQUIRED_IBC_EXPORTS",
+        AbiVersion::Ibc,
+        &entries,
+    );
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("wasm_abi.rs"), code).unwrap();
+}
+
+/// `declaration` is everything before the colon, e.g. `const NAME`
+fn write_imports(code: &mut String, declaration: &str, version: AbiVersion, entries: &[AbiEntry]) {
+    writeln!(code, "{}: &[&str] = &[", declaration).unwrap();
+    for entry in entries {
+        if let AbiEntry::Import(import) = entry {
+            if !import.versions.contains(version) {
+                continue;
+            }
+            if let Some(feature) = import.feature {
+                writeln!(code, "    #[cfg(feature = {:?})]", feature).unwrap();
+            }
+            writeln!(code, "    \"env.{}\",", import.name).unwrap();
+        }
+    }
+    writeln!(code, "];").unwrap();
+}
+
+fn write_exports(code: &mut String, declaration: &str, version: AbiVersion, entries: &[AbiEntry]) {
+    writeln!(code, "{}: &[&str] = &[", declaration).unwrap();
+    for entry in entries {
+        if let AbiEntry::Export(export) = entry {
+            if export.versions.contains(version) {
+                writeln!(code, "    {:?},", export.name).unwrap();
+            }
+        }
+    }
+    writeln!(code, "];").unwrap();
+}
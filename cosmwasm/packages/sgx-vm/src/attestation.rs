@@ -7,7 +7,10 @@ use log::*;
 use sgx_types::*;
 use sgx_types::{sgx_status_t, SgxResult};
 
-use enclave_ffi_types::{NodeAuthResult, OUTPUT_ENCRYPTED_SEED_SIZE, SINGLE_ENCRYPTED_SEED_SIZE};
+use enclave_ffi_types::{
+    NodeAuthResult, MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE, OUTPUT_ENCRYPTED_SEED_SIZE,
+    SINGLE_ENCRYPTED_SEED_SIZE,
+};
 
 use crate::enclave::ENCLAVE_DOORBELL;
 
@@ -32,6 +35,14 @@ extern "C" {
         pk_len: u32,
         seed: &mut [u8; SINGLE_ENCRYPTED_SEED_SIZE as usize],
     ) -> sgx_status_t;
+    pub fn ecall_get_encrypted_previous_seeds(
+        eid: sgx_enclave_id_t,
+        retval: *mut NodeAuthResult,
+        cert: *const u8,
+        cert_len: u32,
+        seeds: &mut [u8; MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE],
+        seeds_len: *mut u32,
+    ) -> sgx_status_t;
 }
 
 #[no_mangle]
@@ -213,6 +224,47 @@ pub fn untrusted_get_encrypted_seed(
     Ok(Ok(seed))
 }
 
+/// The seeds that were current before each seed rotation, oldest first, encrypted for the node of
+/// `cert` like the seeds returned by `untrusted_get_encrypted_seed`. Empty if the seed was never
+/// rotated.
+pub fn untrusted_get_encrypted_previous_seeds(
+    cert: &[u8],
+) -> SgxResult<Result<Vec<u8>, NodeAuthResult>> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+    let eid = enclave.geteid();
+    let mut retval = NodeAuthResult::Success;
+
+    let mut seeds = [0u8; MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE];
+    let mut seeds_len = 0u32;
+    let status = unsafe {
+        ecall_get_encrypted_previous_seeds(
+            eid,
+            &mut retval,
+            cert.as_ptr(),
+            cert.len() as u32,
+            &mut seeds,
+            &mut seeds_len,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        debug!("Error from get encrypted previous seeds");
+        return Err(status);
+    }
+
+    if retval != NodeAuthResult::Success {
+        debug!("Error from get encrypted previous seeds, bad NodeAuthResult");
+        return Ok(Err(retval));
+    }
+
+    Ok(Ok(seeds[..seeds_len as usize].to_vec()))
+}
+
 pub fn untrusted_get_encrypted_genesis_seed(
     pk: &[u8],
 ) -> SgxResult<[u8; SINGLE_ENCRYPTED_SEED_SIZE as usize]> {
@@ -0,0 +1,191 @@
+//! Pins the code hashes whose contracts were executed the most in the last epoch, so that operators
+//! get a warm cache for the hot contracts of their network without maintaining a pin list. At the
+//! end of each epoch, the top `top_k` code hashes of the epoch are pinned and the ones that dropped
+//! out, including the dormant ones, are unpinned.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::checksum::Checksum;
+
+/// Disabled by default, with `top_k` of zero
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AutoPinConfig {
+    /// The number of code hashes pinned each epoch
+    pub top_k: usize,
+    /// In blocks
+    pub epoch_length: u64,
+}
+
+impl AutoPinConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.top_k > 0 && self.epoch_length > 0
+    }
+}
+
+/// What auto pinning did since the node started
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AutoPinMetrics {
+    /// The epoch executions are currently counted in
+    pub epoch: u64,
+    /// The code hashes pinned by auto pinning now
+    pub pinned: usize,
+    /// Cumulative
+    pub pins: u64,
+    /// Cumulative
+    pub unpins: u64,
+}
+
+/// The pins and unpins at the end of an epoch
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct EpochChanges {
+    pub pin: Vec<Checksum>,
+    pub unpin: Vec<Checksum>,
+}
+
+#[derive(Default)]
+pub(crate) struct AutoPinner {
+    config: AutoPinConfig,
+    epoch: u64,
+    /// Of the current epoch
+    executions: HashMap<Checksum, u64>,
+    pinned: HashSet<Checksum>,
+    pins: u64,
+    unpins: u64,
+}
+
+impl AutoPinner {
+    /// Changing the config starts over, unpinning everything auto pinning pinned
+    pub fn configure(&mut self, config: AutoPinConfig) -> EpochChanges {
+        let changes = EpochChanges {
+            pin: vec![],
+            unpin: self.pinned.drain().collect(),
+        };
+        self.unpins += changes.unpin.len() as u64;
+        self.config = config;
+        self.epoch = 0;
+        self.executions.clear();
+        changes
+    }
+
+    /// Counts an execution of `checksum` at `height`. Returns the changes to apply if it is the first
+    /// execution of a new epoch, which ends the previous one.
+    pub fn record(&mut self, checksum: &Checksum, height: u64) -> Option<EpochChanges> {
+        if !self.config.is_enabled() {
+            return None;
+        }
+
+        let epoch = height / self.config.epoch_length;
+        let changes = if epoch > self.epoch {
+            let changes = self.end_epoch();
+            self.epoch = epoch;
+            Some(changes)
+        } else {
+            None
+        };
+        *self.executions.entry(*checksum).or_insert(0) += 1;
+        changes
+    }
+
+    fn end_epoch(&mut self) -> EpochChanges {
+        let mut ranked: Vec<(Checksum, u64)> = self.executions.drain().collect();
+        // ties are broken by checksum, for all nodes to pin the same code hashes
+        ranked.sort_by(|(a_checksum, a_count), (b_checksum, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| a_checksum.to_hex().cmp(&b_checksum.to_hex()))
+        });
+        let top: HashSet<Checksum> = ranked
+            .into_iter()
+            .take(self.config.top_k)
+            .map(|(checksum, _)| checksum)
+            .collect();
+
+        let mut changes = EpochChanges {
+            pin: top.difference(&self.pinned).copied().collect(),
+            unpin: self.pinned.difference(&top).copied().collect(),
+        };
+        changes.pin.sort_by_key(|checksum| checksum.to_hex());
+        changes.unpin.sort_by_key(|checksum| checksum.to_hex());
+        self.pins += changes.pin.len() as u64;
+        self.unpins += changes.unpin.len() as u64;
+        self.pinned = top;
+        changes
+    }
+
+    /// Stops tracking a code hash that was removed, it was unpinned by the caller
+    pub fn forget(&mut self, checksum: &Checksum) {
+        self.executions.remove(checksum);
+        self.pinned.remove(checksum);
+    }
+
+    pub fn metrics(&self) -> AutoPinMetrics {
+        AutoPinMetrics {
+            epoch: self.epoch,
+            pinned: self.pinned.len(),
+            pins: self.pins,
+            unpins: self.unpins,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn auto_pinner_pins_top_k_of_each_epoch() {
+        let hot = Checksum::generate(b"hot");
+        let warm = Checksum::generate(b"warm");
+        let cold = Checksum::generate(b"cold");
+        let mut pinner = AutoPinner::default();
+        // disabled
+        assert_eq!(pinner.record(&hot, 100), None);
+
+        pinner.configure(AutoPinConfig {
+            top_k: 2,
+            epoch_length: 10,
+        });
+        for _ in 0..3 {
+            assert_eq!(pinner.record(&hot, 1), None);
+        }
+        pinner.record(&warm, 5);
+        pinner.record(&warm, 9);
+        pinner.record(&cold, 9);
+
+        let mut expected = vec![hot, warm];
+        expected.sort_by_key(|checksum| checksum.to_hex());
+        assert_eq!(
+            pinner.record(&cold, 12),
+            Some(EpochChanges {
+                pin: expected,
+                unpin: vec![],
+            })
+        );
+        pinner.record(&cold, 13);
+        pinner.record(&hot, 19);
+
+        // warm went dormant
+        assert_eq!(
+            pinner.record(&hot, 35),
+            Some(EpochChanges {
+                pin: vec![cold],
+                unpin: vec![warm],
+            })
+        );
+        assert_eq!(
+            pinner.metrics(),
+            AutoPinMetrics {
+                epoch: 3,
+                pinned: 2,
+                pins: 3,
+                unpins: 1,
+            }
+        );
+
+        let changes = pinner.configure(AutoPinConfig::default());
+        assert_eq!(changes.unpin.len(), 2);
+        assert_eq!(pinner.metrics().pinned, 0);
+    }
+}
@@ -1,25 +1,57 @@
-use parity_wasm::elements::deserialize_buffer;
-use std::collections::HashSet;
-use std::fs::{create_dir_all, File, OpenOptions};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, create_dir_all, File, OpenOptions};
 use std::io::{Read, Write};
 use std::marker::PhantomData;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
+use log::*;
+use serde::Serialize;
+
+use crate::auto_pinning::{AutoPinConfig, AutoPinMetrics, AutoPinner, EpochChanges};
 /*
 use crate::backends::{backend, compile};
 */
+use crate::capabilities::{
+    untrusted_get_disclosure_signer, untrusted_get_mr_enclave, vm_capabilities, VmCapabilities,
+};
 use crate::checksum::Checksum;
-use crate::compatability::{check_wasm, check_wasm_exports, REQUIRED_IBC_EXPORTS};
-use crate::errors::{VmError, VmResult};
+use crate::compatability::{
+    check_stored_wasm, check_wasm, ibc_capabilities, interface_version, IbcCapabilities,
+    WasmValidationConfig,
+};
+use crate::developer_signature::{check_developer_signature, DeveloperSignature};
+use crate::enclave_stats::{enclave_module_cache_stats, EnclaveModuleCacheStats};
+use crate::errors::{EnclaveError, VmError, VmResult};
 use crate::features::required_features_from_module;
 use crate::instance::Instance;
+use crate::instance_stats::{InstanceStats, InstanceStatsStore};
+use crate::module_pinning::{
+    untrusted_evict_module, untrusted_prewarm_module, untrusted_set_module_pinned,
+};
+use crate::parsed_modules::{forget_parsed_module, parse_module_with_checksum};
+use crate::store_migrations::store_migrations;
 /*
 use crate::modules::FileSystemCache;
 */
 use crate::traits::{Api, Extern, Querier, Storage};
 
 const WASM_DIR: &str = "wasm";
+/// Holds the validation stamp of every Wasm in `WASM_DIR`, under the same file name
+const VALIDATION_DIR: &str = "validation";
+/// Holds the execution counters of every contract, see `InstanceStats`
+const INSTANCE_STATS_DIR: &str = "instance_stats";
+/// Holds the Wasm blobs found corrupted when they were loaded, for the operator to inspect
+const QUARANTINE_DIR: &str = "quarantine";
+/// The version of the static validation done by `check_wasm`. Bump it whenever that validation
+/// changes, so contracts validated by an older node are validated again before they run. Rules
+/// stored contracts can fail must be enforced on upload only, see `check_stored_wasm`.
+const VALIDATION_VERSION: u32 = 3;
+/// Custom section in which contracts declare the SNIP standards they implement,
+/// as a comma separated list, e.g. `snip20,snip24`
+pub(crate) const SNIP_STANDARDS_SECTION: &str = "snip_standards";
 /*
 const MODULES_DIR: &str = "modules";
 */
@@ -27,21 +59,57 @@ const MODULES_DIR: &str = "modules";
 #[allow(dead_code)]
 #[derive(Debug, Default, Clone)]
 struct Stats {
-    hits_module: u32,
-    misses: u32,
+    hits_pinned: u64,
+    hits_module: u64,
+    misses: u64,
+    /// Loading stored Wasm and checking its integrity
+    unseal_time: Duration,
+    /// Validating and parsing Wasm
+    parse_time: Duration,
+}
+
+/// How the cache performed since it was created, for the node to export
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Instances built from pinned Wasm, kept in memory
+    pub hits_pinned: u64,
+    /// Instances of code that isn't pinned, whose Wasm is loaded from disk, including those that
+    /// failed to load
+    pub misses: u64,
+    /// The number of pinned code hashes
+    pub elements_pinned: u64,
+    /// In nanoseconds, loading stored Wasm from disk and checking its integrity
+    pub unseal_time_ns: u64,
+    /// In nanoseconds, validating Wasm when it is stored or revalidated, and parsing it for
+    /// analysis. Parsing for execution happens in the enclave.
+    pub parse_time_ns: u64,
+    /// The module cache of the enclave, where the modules executed are built and kept
+    pub enclave_module_cache: EnclaveModuleCacheStats,
 }
 
 struct CosmCacheImpl {
     wasm_path: PathBuf,
+    validation_path: PathBuf,
+    quarantine_path: PathBuf,
+    instance_stats: InstanceStatsStore,
     supported_features: HashSet<String>,
+    validation_config: WasmValidationConfig,
+    /// See `validation_stamp`
+    validation_stamp: String,
     /*
     modules: FileSystemCache,
     */
+    /// The Wasm of the pinned code hashes, kept in memory. Pinned code was validated when pinned.
+    pinned: HashMap<Checksum, Arc<[u8]>>,
+    /// Pinned with `pin`, rather than by auto pinning
+    manual_pins: HashSet<Checksum>,
+    auto_pinner: AutoPinner,
     stats: Stats,
 }
 
 pub struct CosmCache<S: Storage + 'static, A: Api + 'static, Q: Querier + 'static> {
-    inner: Mutex<CosmCacheImpl>,
+    /// Shared with background threads, see [`CosmCache::prewarm`]
+    inner: Arc<Mutex<CosmCacheImpl>>,
     // Those two don't store data but only fix type information
     type_storage: PhantomData<S>,
     type_api: PhantomData<A>,
@@ -51,9 +119,37 @@ pub struct CosmCache<S: Storage + 'static, A: Api + 'static, Q: Querier + 'stati
 #[derive(PartialEq, Debug)]
 pub struct AnalysisReport {
     pub has_ibc_entry_points: bool,
+    pub ibc_capabilities: IbcCapabilities,
     pub required_features: HashSet<String>,
 }
 
+/// Public information about a contract, for wallets and explorers to discover what it supports
+#[derive(PartialEq, Debug)]
+pub struct IntrospectionReport {
+    /// `"v1"`, `"v0.10"` or `None` if unknown
+    pub interface_version: Option<&'static str>,
+    pub has_ibc_entry_points: bool,
+    /// As declared by the contract, in the order it declared them. These are not verified.
+    pub snip_standards: Vec<String>,
+}
+
+/// The stored contracts that would no longer pass `check_wasm` under other supported features or
+/// another validation config, see [`CosmCache::revalidate_stored_code`]
+#[derive(Serialize, PartialEq, Eq, Debug, Default)]
+pub struct RevalidationReport {
+    /// The number of stored Wasm blobs checked
+    pub checked: usize,
+    /// Sorted by checksum
+    pub failures: Vec<RevalidationFailure>,
+}
+
+#[derive(Serialize, PartialEq, Eq, Debug)]
+pub struct RevalidationFailure {
+    /// Hex encoded
+    pub checksum: String,
+    pub error: String,
+}
+
 impl<S, A, Q> CosmCache<S, A, Q>
 where
     S: Storage + 'static,
@@ -72,35 +168,100 @@ where
     pub unsafe fn new<P: Into<PathBuf>>(
         base_dir: P,
         supported_features: HashSet<String>,
+        validation_config: WasmValidationConfig,
     ) -> VmResult<Self> {
         let base = base_dir.into();
         let wasm_path = base.join(WASM_DIR);
         create_dir_all(&wasm_path)
             .map_err(|e| VmError::cache_err(format!("Error creating Wasm dir for cache: {}", e)))?;
+        let validation_path = base.join(VALIDATION_DIR);
+        create_dir_all(&validation_path).map_err(|e| {
+            VmError::cache_err(format!("Error creating validation dir for cache: {}", e))
+        })?;
+        let instance_stats_path = base.join(INSTANCE_STATS_DIR);
+        create_dir_all(&instance_stats_path).map_err(|e| {
+            VmError::cache_err(format!(
+                "Error creating instance stats dir for cache: {}",
+                e
+            ))
+        })?;
+        store_migrations().run(&base)?;
+        // the chain already decided whether the stored contracts could use the bulk memory proposal
+        let validation_config = WasmValidationConfig {
+            bulk_memory: true,
+            ..validation_config
+        };
+        let validation_stamp = validation_stamp(&supported_features, &validation_config);
+        discard_stale_validation_stamps(&wasm_path, &validation_path, &validation_stamp)?;
 
         /*
         let modules = FileSystemCache::new(base.join(MODULES_DIR))
             .map_err(|e| VmError::cache_err(format!("Error file system cache: {}", e)))?;
         */
         Ok(CosmCache {
-            inner: Mutex::new(CosmCacheImpl {
+            inner: Arc::new(Mutex::new(CosmCacheImpl {
                 wasm_path,
+                validation_path,
+                quarantine_path: base.join(QUARANTINE_DIR),
+                instance_stats: InstanceStatsStore::new(instance_stats_path),
                 supported_features,
+                validation_config,
+                validation_stamp,
                 /*
                 modules,
                 */
+                pinned: HashMap::new(),
+                manual_pins: HashSet::new(),
+                auto_pinner: AutoPinner::default(),
                 stats: Stats::default(),
-            }),
+            })),
             type_storage: PhantomData::<S>,
             type_api: PhantomData::<A>,
             type_querier: PhantomData::<Q>,
         })
     }
 
+    /// Validates and stores a Wasm as uploaded, and returns its checksum. The checksum is the code
+    /// hash the enclave computes over the same bytes, so they must never be rewritten here.
+    ///
+    /// Contracts using the bulk memory proposal are rejected, see `save_wasm_with_bulk_memory`.
     pub fn save_wasm(&mut self, wasm: &[u8]) -> VmResult<Checksum> {
-        let inner = self.inner.lock().unwrap();
-        check_wasm(wasm, &inner.supported_features)?;
+        self.save_wasm_with_bulk_memory(wasm, false)
+    }
+
+    /// Like `save_wasm`, only accepting the bulk memory proposal if `bulk_memory` is set. The caller
+    /// passes whether the chain activated it at the current height, so that all of its nodes accept
+    /// the same contracts.
+    pub fn save_wasm_with_bulk_memory(
+        &mut self,
+        wasm: &[u8],
+        bulk_memory: bool,
+    ) -> VmResult<Checksum> {
+        self.save_signed_wasm(wasm, bulk_memory, None)
+    }
+
+    /// Like `save_wasm_with_bulk_memory`, also checking that `developer_signature`, if any, is a
+    /// signature of `wasm` as given, before the enclave strips anything from it.
+    pub fn save_signed_wasm(
+        &mut self,
+        wasm: &[u8],
+        bulk_memory: bool,
+        developer_signature: Option<DeveloperSignature>,
+    ) -> VmResult<Checksum> {
+        let mut inner = self.inner.lock().unwrap();
+        let started = Instant::now();
+        let validation_config = WasmValidationConfig {
+            bulk_memory,
+            ..inner.validation_config.clone()
+        };
+        check_wasm(wasm, &inner.supported_features, &validation_config)?;
+        if let Some(developer_signature) = developer_signature {
+            check_developer_signature(wasm, &developer_signature)?;
+        }
+        let parse_time = started.elapsed();
         let checksum = save_wasm_to_disk(&inner.wasm_path, wasm)?;
+        save_validation_stamp(&inner.validation_path, &checksum, &inner.validation_stamp)?;
+        inner.stats.parse_time += parse_time;
         /*
         let module = compile(wasm)?;
         self.modules.store(&checksum, module)?;
@@ -114,14 +275,35 @@ where
     ///
     /// If the given ID is not found or the content does not match the hash (=ID), an error is returned.
     pub fn load_wasm(&self, checksum: &Checksum) -> VmResult<Vec<u8>> {
-        let inner = self.inner.lock().unwrap();
-        let code = load_wasm_from_disk(&inner.wasm_path, checksum)?;
-        // verify hash matches (integrity check)
-        if Checksum::generate(&code) != *checksum {
-            Err(VmError::integrity_err())
-        } else {
-            Ok(code)
-        }
+        unseal_wasm(&self.inner, checksum)
+    }
+
+    /// Deletes a Wasm blob stored via save_wasm, along with its validation stamp, to reclaim
+    /// disk space once no contract uses it anymore, e.g. once its code was removed from the chain.
+    /// The enclave drops the module it built from it, pinned or cached, the module parsed from it
+    /// is dropped and auto pinning forgets it. The Wasm can't be loaded or instantiated afterwards,
+    /// unless it is saved again.
+    ///
+    /// Code pinned with [`pin`] is still referenced by the pin, and isn't removed: unpin it first.
+    /// Returns false if no Wasm with this checksum was stored.
+    pub fn remove_wasm(&mut self, checksum: &Checksum) -> VmResult<bool> {
+        let removed = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.manual_pins.contains(checksum) {
+                return Err(VmError::cache_err(format!(
+                    "Wasm {} is pinned, unpin it before removing it",
+                    checksum
+                )));
+            }
+            inner.pinned.remove(checksum);
+            inner.auto_pinner.forget(checksum);
+            forget_parsed_module(checksum);
+            let removed = remove_file_if_exists(&inner.wasm_path.join(checksum.to_hex()))?;
+            remove_file_if_exists(&inner.validation_path.join(checksum.to_hex()))?;
+            removed
+        };
+        untrusted_evict_module(checksum).map_err(EnclaveError::sdk_err)?;
+        Ok(removed)
     }
 
     /// Performs static anlyzation on this Wasm without compiling or instantiating it.
@@ -132,27 +314,87 @@ where
         // Here we could use a streaming deserializer to slightly improve performance. However, this way it is DRYer.
         let wasm = self.load_wasm(checksum)?;
 
-        let module = match deserialize_buffer(&wasm) {
-            Ok(deserialized) => deserialized,
-            Err(err) => {
-                return Err(VmError::static_validation_err(format!(
-                    "Wasm bytecode could not be deserialized. Deserialization error: \"{}\"",
-                    err
-                )));
-            }
-        };
+        let module = self.timed_parse(|| parse_module_with_checksum(checksum, &wasm))?;
 
-        let has_ibc_entry_points = match check_wasm_exports(&module, REQUIRED_IBC_EXPORTS) {
-            Ok(_) => true,
-            Err(_) => false,
-        };
+        let ibc_capabilities = ibc_capabilities(&module);
 
         Ok(AnalysisReport {
-            has_ibc_entry_points,
+            has_ibc_entry_points: ibc_capabilities.is_enabled(),
+            ibc_capabilities,
             required_features: required_features_from_module(&module),
         })
     }
 
+    /// Reads the public information of a contract from its Wasm, without running it.
+    ///
+    /// Like [`analyze`], this doesn't depend on any caching of the contract and uses no gas.
+    pub fn introspect(&self, checksum: &Checksum) -> VmResult<IntrospectionReport> {
+        let wasm = self.load_wasm(checksum)?;
+        let module = self.timed_parse(|| parse_module_with_checksum(checksum, &wasm))?;
+
+        let snip_standards = module
+            .custom_section(SNIP_STANDARDS_SECTION)
+            .map(|section| {
+                String::from_utf8_lossy(section)
+                    .split(',')
+                    .map(|standard| standard.trim().to_string())
+                    .filter(|standard| !standard.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(IntrospectionReport {
+            interface_version: interface_version(&module),
+            has_ibc_entry_points: ibc_capabilities(&module).is_enabled(),
+            snip_standards,
+        })
+    }
+
+    /// Runs `check_stored_wasm` on every stored Wasm with the given supported features and validation
+    /// config, and reports the ones that would fail, so upgrades changing them can be assessed
+    /// before they activate. Nothing is changed: the cache keeps validating with its own config.
+    ///
+    /// This reads every stored Wasm and is meant for maintenance tooling, not for serving queries.
+    pub fn revalidate_stored_code(
+        &self,
+        supported_features: &HashSet<String>,
+        validation_config: &WasmValidationConfig,
+    ) -> VmResult<RevalidationReport> {
+        let wasm_path = self.inner.lock().unwrap().wasm_path.clone();
+        let mut checksums = checksums_in_dir(&wasm_path)?;
+        checksums.sort_by_key(|checksum| checksum.to_hex());
+
+        let mut report = RevalidationReport::default();
+        for checksum in checksums {
+            let result = self
+                .load_wasm(&checksum)
+                .and_then(|wasm| check_stored_wasm(&wasm, supported_features, validation_config));
+            if let Err(e) = result {
+                report.failures.push(RevalidationFailure {
+                    checksum: checksum.to_hex(),
+                    error: e.to_string(),
+                });
+            }
+            report.checked += 1;
+        }
+        Ok(report)
+    }
+
+    /// Describes the contracts this node accepts and what it offers them, for clients and contract
+    /// tooling. Asks the enclave for its MRENCLAVE and its disclosure signer, which are left out if
+    /// it can't report them.
+    pub fn vm_capabilities(&self) -> VmCapabilities {
+        let mr_enclave = untrusted_get_mr_enclave().ok();
+        let disclosure_signer = untrusted_get_disclosure_signer().ok();
+        let inner = self.inner.lock().unwrap();
+        vm_capabilities(
+            &inner.supported_features,
+            &inner.validation_config,
+            mr_enclave,
+            disclosure_signer,
+        )
+    }
+
     /// Returns an Instance tied to a previously saved Wasm.
     /// Depending on availability, this is either generated from a cached instance, a cached module or Wasm code.
     pub fn get_instance(
@@ -170,11 +412,324 @@ where
         }
         */
 
+        let pinned = self.inner.lock().unwrap().pinned.get(checksum).cloned();
+        if let Some(wasm) = pinned {
+            self.inner.lock().unwrap().stats.hits_pinned += 1;
+            return Instance::from_code(&wasm, deps, gas_limit);
+        }
+
         // fall back to wasm cache (and re-compiling) - this is for backends that don't support serialization
-        let wasm = self.load_wasm(checksum)?;
         self.inner.lock().unwrap().stats.misses += 1;
+        let wasm = self.load_wasm(checksum)?;
+        revalidate_if_stale(&self.inner, checksum, &wasm)?;
         Instance::from_code(&wasm, deps, gas_limit)
     }
+
+    /// Counts an execution, be it an init, handle or migrate, of the contract `env` was given, of
+    /// the code `checksum`, see [`InstanceStats`]. `env` is the env of the call as passed to the
+    /// enclave, simulations aren't counted. This is also what auto pinning ranks code hashes by.
+    pub fn record_execution(&self, checksum: &Checksum, env: &[u8], gas_used: u64) -> VmResult<()> {
+        let changes = {
+            let mut inner = self.inner.lock().unwrap();
+            match inner.instance_stats.record(env, gas_used)? {
+                Some(height) => inner.auto_pinner.record(checksum, height),
+                None => None,
+            }
+        };
+        if let Some(changes) = changes {
+            self.apply_pin_changes(changes);
+        }
+        Ok(())
+    }
+
+    /// Sets how many code hashes are pinned by auto pinning and how often, see [`AutoPinConfig`].
+    /// Auto pinning starts over, unpinning what it pinned so far.
+    pub fn configure_auto_pinning(&self, config: AutoPinConfig) {
+        let changes = self.inner.lock().unwrap().auto_pinner.configure(config);
+        self.apply_pin_changes(changes);
+    }
+
+    pub fn auto_pin_metrics(&self) -> AutoPinMetrics {
+        self.inner.lock().unwrap().auto_pinner.metrics()
+    }
+
+    /// Returns the hits, misses and time spent loading and parsing Wasm since the cache was
+    /// created, see [`Metrics`]
+    pub fn metrics(&self) -> Metrics {
+        let inner = self.inner.lock().unwrap();
+        Metrics {
+            hits_pinned: inner.stats.hits_pinned,
+            misses: inner.stats.misses,
+            elements_pinned: inner.pinned.len() as u64,
+            unseal_time_ns: inner.stats.unseal_time.as_nanos() as u64,
+            parse_time_ns: inner.stats.parse_time.as_nanos() as u64,
+            enclave_module_cache: enclave_module_cache_stats(),
+        }
+    }
+
+    /// Runs `parse`, counting the time it took as parse time
+    fn timed_parse<T>(&self, parse: impl FnOnce() -> VmResult<T>) -> VmResult<T> {
+        let started = Instant::now();
+        let result = parse();
+        self.inner.lock().unwrap().stats.parse_time += started.elapsed();
+        result
+    }
+
+    /// Keeps a stored Wasm in memory and has the enclave keep the module it builds from it across
+    /// executions, so the contracts of hot code skip loading and parsing it on every call.
+    ///
+    /// Auto pinning doesn't unpin what is pinned here, only [`unpin`] does. Pins don't survive
+    /// restarts, and the enclave refuses to pin more than a few dozen modules.
+    pub fn pin(&self, checksum: &Checksum) -> VmResult<()> {
+        pin_wasm(&self.inner, checksum)?;
+        self.inner.lock().unwrap().manual_pins.insert(*checksum);
+        Ok(())
+    }
+
+    /// Undoes [`pin`], or a pin of auto pinning. Unpinning code that isn't pinned does nothing.
+    pub fn unpin(&self, checksum: &Checksum) -> VmResult<()> {
+        self.inner.lock().unwrap().manual_pins.remove(checksum);
+        self.unpin_wasm(checksum)
+    }
+
+    /// Loads and validates the stored Wasm of `checksums` on a background thread, pins it like
+    /// [`pin`] if `pin` is set, and has the enclave build its module, so that the first block after
+    /// a restart doesn't pay for loading and building the hot contracts of the network. Code that
+    /// can't be loaded or pinned is skipped with a warning.
+    ///
+    /// Returns once the thread is started. Without pinning, the modules are kept in the module
+    /// cache of the enclave, so prewarming more code than it holds only keeps the last of it.
+    pub fn prewarm(&self, checksums: &[Checksum], pin: bool) -> VmResult<JoinHandle<()>> {
+        let inner = Arc::clone(&self.inner);
+        let checksums = checksums.to_vec();
+        thread::Builder::new()
+            .name("wasm-prewarm".to_string())
+            .spawn(move || {
+                let started = Instant::now();
+                let mut prewarmed = 0;
+                for checksum in checksums {
+                    let result = if pin {
+                        pin_wasm(&inner, &checksum).map(|()| {
+                            let mut inner = inner.lock().unwrap();
+                            inner.manual_pins.insert(checksum);
+                            inner.pinned[&checksum].to_vec()
+                        })
+                    } else {
+                        unseal_wasm(&inner, &checksum).and_then(|wasm| {
+                            revalidate_if_stale(&inner, &checksum, &wasm).map(|()| wasm)
+                        })
+                    };
+                    let result = result.and_then(|wasm| {
+                        untrusted_prewarm_module(&checksum, &wasm)
+                            .map_err(|e| EnclaveError::sdk_err(e).into())
+                    });
+                    match result {
+                        Ok(()) => prewarmed += 1,
+                        Err(e) => warn!("Failed to prewarm Wasm {}: {}", checksum, e),
+                    }
+                }
+                info!(
+                    "Prewarmed {} Wasm blobs in {:?}",
+                    prewarmed,
+                    started.elapsed()
+                );
+            })
+            .map_err(|e| VmError::cache_err(format!("Error starting prewarm thread: {}", e)))
+    }
+
+    fn unpin_wasm(&self, checksum: &Checksum) -> VmResult<()> {
+        if self.inner.lock().unwrap().pinned.remove(checksum).is_some() {
+            untrusted_set_module_pinned(checksum, false).map_err(EnclaveError::sdk_err)?;
+        }
+        Ok(())
+    }
+
+    /// Code that can't be pinned, e.g. because it isn't stored, is left out and its instances are
+    /// built as usual. Code pinned with [`pin`] stays pinned.
+    fn apply_pin_changes(&self, changes: EpochChanges) {
+        for checksum in changes.unpin {
+            if self.inner.lock().unwrap().manual_pins.contains(&checksum) {
+                continue;
+            }
+            if let Err(e) = self.unpin_wasm(&checksum) {
+                warn!("Failed to unpin Wasm {}: {}", checksum, e);
+            }
+        }
+        for checksum in changes.pin {
+            if let Err(e) = pin_wasm(&self.inner, &checksum) {
+                warn!("Failed to pin Wasm {}: {}", checksum, e);
+            }
+        }
+        debug!(
+            "{} Wasm blobs are pinned",
+            self.inner.lock().unwrap().pinned.len()
+        );
+    }
+
+    /// Returns the executions of a contract recorded by this node, `None` if it never executed it
+    pub fn instance_stats(&self, contract_address: &str) -> VmResult<Option<InstanceStats>> {
+        self.inner
+            .lock()
+            .unwrap()
+            .instance_stats
+            .get(contract_address)
+    }
+}
+
+// The following take the inner cache rather than the cache, for background threads to use them
+
+/// Loads a stored Wasm and checks its integrity, see [`CosmCache::load_wasm`]. A Wasm that
+/// doesn't match its checksum, e.g. after a partial write or a disk failure, is moved to the
+/// quarantine dir for the operator to restore it from another node, and other contracts keep
+/// running in the meantime.
+fn unseal_wasm(inner: &Mutex<CosmCacheImpl>, checksum: &Checksum) -> VmResult<Vec<u8>> {
+    let mut inner = inner.lock().unwrap();
+    let started = Instant::now();
+    let code = load_wasm_from_disk(&inner.wasm_path, checksum)?;
+    // verify hash matches (integrity check)
+    let valid = Checksum::generate(&code) == *checksum;
+    inner.stats.unseal_time += started.elapsed();
+    if !valid {
+        quarantine_wasm(&inner, checksum)?;
+        Err(VmError::integrity_err())
+    } else {
+        Ok(code)
+    }
+}
+
+/// Moves a corrupted Wasm to the quarantine dir and removes its validation stamp, so that saving
+/// it again validates it again
+fn quarantine_wasm(inner: &CosmCacheImpl, checksum: &Checksum) -> VmResult<()> {
+    error!(
+        "Wasm {} of the cache doesn't match its checksum, moving it to {}. Restore it from \
+         another node",
+        checksum,
+        inner.quarantine_path.display()
+    );
+    create_dir_all(&inner.quarantine_path).map_err(|e| {
+        VmError::cache_err(format!("Error creating quarantine dir for cache: {}", e))
+    })?;
+    fs::rename(
+        inner.wasm_path.join(checksum.to_hex()),
+        inner.quarantine_path.join(checksum.to_hex()),
+    )
+    .map_err(|e| VmError::cache_err(format!("Error quarantining Wasm {}: {}", checksum, e)))?;
+    remove_file_if_exists(&inner.validation_path.join(checksum.to_hex()))?;
+    Ok(())
+}
+
+/// Validates a Wasm again if it was validated with other supported features, another validation
+/// config or by another version of the validation than this node's, e.g. before a chain upgrade.
+/// The Wasm was accepted by the chain, so the rules only enforced on upload don't apply.
+fn revalidate_if_stale(
+    inner: &Mutex<CosmCacheImpl>,
+    checksum: &Checksum,
+    wasm: &[u8],
+) -> VmResult<()> {
+    let mut inner = inner.lock().unwrap();
+    let stamp = load_validation_stamp(&inner.validation_path, checksum);
+    if stamp.as_deref() == Some(inner.validation_stamp.as_str()) {
+        return Ok(());
+    }
+
+    info!(
+        "Validating Wasm {} again, its validation stamp {:?} is stale",
+        checksum, stamp
+    );
+    let started = Instant::now();
+    let result = check_stored_wasm(wasm, &inner.supported_features, &inner.validation_config);
+    inner.stats.parse_time += started.elapsed();
+    result?;
+    save_validation_stamp(&inner.validation_path, checksum, &inner.validation_stamp)
+}
+
+fn pin_wasm(inner: &Mutex<CosmCacheImpl>, checksum: &Checksum) -> VmResult<()> {
+    if inner.lock().unwrap().pinned.contains_key(checksum) {
+        return Ok(());
+    }
+    let wasm = unseal_wasm(inner, checksum)?;
+    revalidate_if_stale(inner, checksum, &wasm)?;
+    untrusted_set_module_pinned(checksum, true).map_err(EnclaveError::sdk_err)?;
+    inner
+        .lock()
+        .unwrap()
+        .pinned
+        .insert(*checksum, Arc::from(wasm));
+    Ok(())
+}
+
+/// Identifies the validation a Wasm passed: the version of the validation and a hash of the
+/// features the node supported and of its validation config. The hash doesn't depend on the order
+/// of the features.
+fn validation_stamp(
+    supported_features: &HashSet<String>,
+    validation_config: &WasmValidationConfig,
+) -> String {
+    let mut features: Vec<&str> = supported_features.iter().map(String::as_str).collect();
+    features.sort_unstable();
+    let features_hash =
+        Checksum::generate(format!("{};{:?}", features.join(","), validation_config).as_bytes());
+    format!("{}:{}", VALIDATION_VERSION, features_hash.to_hex())
+}
+
+/// Returns `None` if the Wasm has no stamp, like those stored before stamps existed
+fn load_validation_stamp(dir: &Path, checksum: &Checksum) -> Option<String> {
+    fs::read_to_string(dir.join(checksum.to_hex())).ok()
+}
+
+fn save_validation_stamp(dir: &Path, checksum: &Checksum, stamp: &str) -> VmResult<()> {
+    fs::write(dir.join(checksum.to_hex()), stamp)
+        .map_err(|e| VmError::cache_err(format!("Error writing validation stamp: {}", e)))
+}
+
+/// Removes the stamps of another validation or of a Wasm that isn't stored, so nothing mismatched
+/// is trusted. Runs when the cache is opened, the Wasm themselves are checked when they are
+/// loaded, see `unseal_wasm`.
+///
+/// A Wasm whose stamp was removed is validated again before it runs. Files whose name isn't a
+/// checksum aren't ours and are left alone.
+fn discard_stale_validation_stamps(
+    wasm_path: &Path,
+    validation_path: &Path,
+    validation_stamp: &str,
+) -> VmResult<()> {
+    let mut discarded_stamps = 0;
+    for checksum in checksums_in_dir(validation_path)? {
+        let stamp = load_validation_stamp(validation_path, &checksum);
+        if stamp.as_deref() != Some(validation_stamp)
+            || !wasm_path.join(checksum.to_hex()).is_file()
+        {
+            remove_file_if_exists(&validation_path.join(checksum.to_hex()))?;
+            discarded_stamps += 1;
+        }
+    }
+
+    if discarded_stamps > 0 {
+        info!(
+            "Discarded {} stale validation stamps from the cache",
+            discarded_stamps
+        );
+    }
+    Ok(())
+}
+
+/// The checksums of the files in a directory of the cache, named by the hex of their checksum
+fn checksums_in_dir(dir: &Path) -> VmResult<Vec<Checksum>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| VmError::cache_err(format!("Error reading {}: {}", dir.display(), e)))?;
+    let mut checksums = vec![];
+    for entry in entries {
+        let entry = entry
+            .map_err(|e| VmError::cache_err(format!("Error reading {}: {}", dir.display(), e)))?;
+        if let Some(checksum) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| Checksum::from_hex(name).ok())
+        {
+            checksums.push(checksum);
+        }
+    }
+    Ok(checksums)
 }
 
 /// save stores the wasm code in the given directory and returns an ID for lookup.
@@ -212,6 +767,18 @@ fn load_wasm_from_disk<P: Into<PathBuf>>(dir: P, checksum: &Checksum) -> VmResul
     Ok(wasm)
 }
 
+fn remove_file_if_exists(path: &Path) -> VmResult<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(VmError::cache_err(format!(
+            "Error removing {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -235,8 +802,14 @@ mod test {
     #[test]
     fn save_wasm_works() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> =
-            unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         cache.save_wasm(CONTRACT).unwrap();
     }
 
@@ -244,8 +817,14 @@ mod test {
     // This property is required when the same bytecode is uploaded multiple times
     fn save_wasm_allows_saving_multiple_times() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> =
-            unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         cache.save_wasm(CONTRACT).unwrap();
         cache.save_wasm(CONTRACT).unwrap();
     }
@@ -265,8 +844,14 @@ mod test {
         .unwrap();
 
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> =
-            unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let save_result = cache.save_wasm(&wasm);
         match save_result.unwrap_err() {
             VmError::StaticValidationErr { msg, .. } => {
@@ -279,8 +864,14 @@ mod test {
     #[test]
     fn load_wasm_works() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> =
-            unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
 
         let restored = cache.load_wasm(&id).unwrap();
@@ -294,14 +885,26 @@ mod test {
         let id: Checksum;
 
         {
-            let mut cache1: CosmCache<MockStorage, MockApi, MockQuerier> =
-                unsafe { CosmCache::new(tmp_path, default_features()).unwrap() };
+            let mut cache1: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+                CosmCache::new(
+                    tmp_path,
+                    default_features(),
+                    WasmValidationConfig::default(),
+                )
+                .unwrap()
+            };
             id = cache1.save_wasm(CONTRACT).unwrap();
         }
 
         {
-            let cache2: CosmCache<MockStorage, MockApi, MockQuerier> =
-                unsafe { CosmCache::new(tmp_path, default_features()).unwrap() };
+            let cache2: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+                CosmCache::new(
+                    tmp_path,
+                    default_features(),
+                    WasmValidationConfig::default(),
+                )
+                .unwrap()
+            };
             let restored = cache2.load_wasm(&id).unwrap();
             assert_eq!(restored, CONTRACT);
         }
@@ -310,8 +913,14 @@ mod test {
     #[test]
     fn load_wasm_errors_for_non_existent_id() {
         let tmp_dir = TempDir::new().unwrap();
-        let cache: CosmCache<MockStorage, MockApi, MockQuerier> =
-            unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let checksum = Checksum::from([
             5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5,
             5, 5, 5,
@@ -326,11 +935,77 @@ mod test {
         }
     }
 
+    #[test]
+    fn remove_wasm_works() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        let id = cache.save_wasm(CONTRACT).unwrap();
+
+        assert!(cache.remove_wasm(&id).unwrap());
+        let stamp_path = tmp_dir.path().join(VALIDATION_DIR).join(id.to_hex());
+        assert!(!stamp_path.exists());
+        match cache.load_wasm(&id) {
+            Err(VmError::CacheErr { msg, .. }) => {
+                assert!(msg.starts_with("Error opening Wasm file for reading:"))
+            }
+            Err(e) => panic!("Unexpected error: {:?}", e),
+            Ok(_) => panic!("This must not succeed"),
+        }
+
+        // Removing again is a no-op
+        assert!(!cache.remove_wasm(&id).unwrap());
+
+        // The Wasm can be saved again
+        assert_eq!(cache.save_wasm(CONTRACT).unwrap(), id);
+        assert_eq!(cache.load_wasm(&id).unwrap(), CONTRACT);
+    }
+
+    #[test]
+    fn remove_wasm_unpins() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        let id = cache.save_wasm(CONTRACT).unwrap();
+
+        // still referenced by the pin
+        cache.pin(&id).unwrap();
+        match cache.remove_wasm(&id) {
+            Err(VmError::CacheErr { msg, .. }) => assert!(msg.contains("is pinned")),
+            Err(e) => panic!("Unexpected error: {:?}", e),
+            Ok(_) => panic!("This must not succeed"),
+        }
+        assert_eq!(cache.load_wasm(&id).unwrap(), CONTRACT);
+
+        cache.unpin(&id).unwrap();
+        pin_wasm(&cache.inner, &id).unwrap();
+        assert!(cache.remove_wasm(&id).unwrap());
+        assert!(cache.inner.lock().unwrap().pinned.is_empty());
+    }
+
     #[test]
     fn load_wasm_errors_for_corrupted_wasm() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> =
-            unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let checksum = cache.save_wasm(CONTRACT).unwrap();
 
         // Corrupt cache file
@@ -346,10 +1021,351 @@ mod test {
         }
     }
 
+    #[test]
+    fn new_discards_stale_validation_stamps() {
+        let tmp_dir = TempDir::new().unwrap();
+        let new_cache = || -> CosmCache<MockStorage, MockApi, MockQuerier> {
+            unsafe {
+                CosmCache::new(
+                    tmp_dir.path(),
+                    default_features(),
+                    WasmValidationConfig::default(),
+                )
+                .unwrap()
+            }
+        };
+        let mut cache = new_cache();
+        let checksum = cache.save_wasm(CONTRACT).unwrap();
+        drop(cache);
+
+        // an intact Wasm with its stamp is kept
+        let cache = new_cache();
+        assert!(cache.load_wasm(&checksum).is_ok());
+        let stamp_path = tmp_dir.path().join(VALIDATION_DIR).join(checksum.to_hex());
+        assert!(stamp_path.is_file());
+        drop(cache);
+
+        // stamps of another validation and of missing Wasm are removed
+        fs::write(&stamp_path, "0:outdated").unwrap();
+        let orphan = Checksum::generate(b"not stored");
+        let orphan_path = tmp_dir.path().join(VALIDATION_DIR).join(orphan.to_hex());
+        fs::write(&orphan_path, "0:outdated").unwrap();
+        let unrelated_path = tmp_dir.path().join(WASM_DIR).join("README");
+        fs::write(&unrelated_path, "not a Wasm").unwrap();
+        let cache = new_cache();
+        assert!(cache.load_wasm(&checksum).is_ok());
+        assert!(!stamp_path.exists());
+        assert!(!orphan_path.exists());
+        assert!(unrelated_path.is_file());
+    }
+
+    #[test]
+    fn load_wasm_quarantines_corrupted_wasm() {
+        let tmp_dir = TempDir::new().unwrap();
+        let new_cache = || -> CosmCache<MockStorage, MockApi, MockQuerier> {
+            unsafe {
+                CosmCache::new(
+                    tmp_dir.path(),
+                    default_features(),
+                    WasmValidationConfig::default(),
+                )
+                .unwrap()
+            }
+        };
+        let mut cache = new_cache();
+        let checksum = cache.save_wasm(CONTRACT).unwrap();
+        drop(cache);
+
+        // a corrupted Wasm doesn't keep the cache from opening
+        let wasm_path = tmp_dir.path().join(WASM_DIR).join(checksum.to_hex());
+        let stamp_path = tmp_dir.path().join(VALIDATION_DIR).join(checksum.to_hex());
+        fs::write(&wasm_path, b"broken data").unwrap();
+        let mut cache = new_cache();
+        assert!(wasm_path.is_file());
+
+        // it is quarantined and its stamp removed once loaded
+        match cache.load_wasm(&checksum) {
+            Err(VmError::IntegrityErr { .. }) => {}
+            res => panic!("Unexpected result: {:?}", res),
+        }
+        assert!(!wasm_path.exists());
+        assert!(!stamp_path.exists());
+        let quarantined = tmp_dir.path().join(QUARANTINE_DIR).join(checksum.to_hex());
+        assert_eq!(fs::read(quarantined).unwrap(), b"broken data");
+
+        // the operator restores it
+        assert!(cache.load_wasm(&checksum).is_err());
+        cache.save_wasm(CONTRACT).unwrap();
+        assert!(cache.load_wasm(&checksum).is_ok());
+    }
+
+    #[test]
+    fn revalidate_stored_code_works() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        let checksum = cache.save_wasm(CONTRACT).unwrap();
+
+        let report = cache
+            .revalidate_stored_code(&default_features(), &WasmValidationConfig::default())
+            .unwrap();
+        assert_eq!(
+            report,
+            RevalidationReport {
+                checked: 1,
+                failures: vec![],
+            }
+        );
+
+        let stricter = WasmValidationConfig {
+            max_code_size: Some(CONTRACT.len() - 1),
+            ..WasmValidationConfig::default()
+        };
+        let report = cache
+            .revalidate_stored_code(&default_features(), &stricter)
+            .unwrap();
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].checksum, checksum.to_hex());
+        assert!(report.failures[0].error.contains("max_code_size"));
+
+        // the cache still validates with its own config
+        cache.load_wasm(&checksum).unwrap();
+    }
+
+    #[test]
+    fn validation_stamp_depends_on_features_and_config_only() {
+        let config = WasmValidationConfig::default();
+        assert_eq!(
+            validation_stamp(&features_from_csv("staking,iterator"), &config),
+            validation_stamp(&features_from_csv("iterator,staking"), &config)
+        );
+        assert_ne!(
+            validation_stamp(&features_from_csv("staking"), &config),
+            validation_stamp(&features_from_csv("staking,iterator"), &config)
+        );
+        assert_ne!(
+            validation_stamp(&default_features(), &config),
+            validation_stamp(
+                &default_features(),
+                &WasmValidationConfig {
+                    allow_memory_maximum: true,
+                    ..WasmValidationConfig::default()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn get_instance_revalidates_when_features_change() {
+        let tmp_dir = TempDir::new().unwrap();
+        let id = {
+            let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+                CosmCache::new(
+                    tmp_dir.path(),
+                    default_features(),
+                    WasmValidationConfig::default(),
+                )
+                .unwrap()
+            };
+            cache.save_wasm(CONTRACT).unwrap()
+        };
+        let stamp_path = tmp_dir.path().join(VALIDATION_DIR).join(id.to_hex());
+        assert_eq!(
+            fs::read_to_string(&stamp_path).unwrap(),
+            validation_stamp(&default_features(), &WasmValidationConfig::default())
+        );
+
+        let features = features_from_csv("staking,iterator");
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                features.clone(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        let deps = mock_dependencies(20, &[]);
+        let _instance = cache.get_instance(&id, deps, TESTING_GAS_LIMIT).unwrap();
+        assert_eq!(
+            fs::read_to_string(&stamp_path).unwrap(),
+            validation_stamp(&features, &WasmValidationConfig::default())
+        );
+    }
+
+    #[test]
+    fn get_instance_rejects_wasm_failing_revalidation() {
+        // Invalid because it doesn't contain required memory and exports
+        let wasm = wat2wasm(
+            r#"(module
+            (type $t0 (func (param i32) (result i32)))
+            (func $add_one (export "add_one") (type $t0) (param $p0 i32) (result i32)
+              get_local $p0
+              i32.const 1
+              i32.add))
+            "#,
+        )
+        .unwrap();
+
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        // Stored without a stamp, like a Wasm validated before stamps existed
+        let id = save_wasm_to_disk(tmp_dir.path().join(WASM_DIR), &wasm).unwrap();
+
+        let deps = mock_dependencies(20, &[]);
+        match cache.get_instance(&id, deps, TESTING_GAS_LIMIT) {
+            Err(VmError::StaticValidationErr { .. }) => {}
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Expected the Wasm to fail validation"),
+        }
+    }
+
+    #[test]
+    fn record_execution_auto_pins_hot_code() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        let id = cache.save_wasm(CONTRACT).unwrap();
+        cache.configure_auto_pinning(AutoPinConfig {
+            top_k: 1,
+            epoch_length: 10,
+        });
+        let env = |height: u64| {
+            format!(
+                r#"{{"block":{{"height":{}}},"contract":{{"address":"secret1contract"}}}}"#,
+                height
+            )
+        };
+
+        cache.record_execution(&id, env(5).as_bytes(), 100).unwrap();
+        assert!(cache.inner.lock().unwrap().pinned.is_empty());
+        cache
+            .record_execution(&id, env(12).as_bytes(), 100)
+            .unwrap();
+        assert!(cache.inner.lock().unwrap().pinned.contains_key(&id));
+        assert_eq!(cache.auto_pin_metrics().pinned, 1);
+
+        let deps = mock_dependencies(20, &[]);
+        let _instance = cache.get_instance(&id, deps, TESTING_GAS_LIMIT).unwrap();
+        assert_eq!(cache.inner.lock().unwrap().stats.hits_pinned, 1);
+
+        // dormant for an epoch, the code taking its place isn't stored so it can't be pinned
+        let other = Checksum::generate(b"other");
+        cache
+            .record_execution(&other, env(25).as_bytes(), 100)
+            .unwrap();
+        assert!(cache.inner.lock().unwrap().pinned.contains_key(&id));
+        cache
+            .record_execution(&other, env(35).as_bytes(), 100)
+            .unwrap();
+        assert!(cache.inner.lock().unwrap().pinned.is_empty());
+        assert_eq!(cache.auto_pin_metrics().unpins, 1);
+    }
+
+    #[test]
+    fn metrics_works() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        assert_eq!(cache.metrics(), Metrics::default());
+
+        let id = cache.save_wasm(CONTRACT).unwrap();
+        let metrics = cache.metrics();
+        assert!(metrics.parse_time_ns > 0);
+        assert_eq!(metrics.unseal_time_ns, 0);
+
+        let deps = mock_dependencies(20, &[]);
+        let _instance = cache.get_instance(&id, deps, TESTING_GAS_LIMIT).unwrap();
+        let metrics = cache.metrics();
+        assert_eq!(metrics.misses, 1);
+        assert_eq!(metrics.hits_pinned, 0);
+        assert!(metrics.unseal_time_ns > 0);
+
+        cache
+            .inner
+            .lock()
+            .unwrap()
+            .pinned
+            .insert(id, Arc::from(CONTRACT));
+        let deps = mock_dependencies(20, &[]);
+        let _instance = cache.get_instance(&id, deps, TESTING_GAS_LIMIT).unwrap();
+        let metrics = cache.metrics();
+        assert_eq!(metrics.misses, 1);
+        assert_eq!(metrics.hits_pinned, 1);
+        assert_eq!(metrics.elements_pinned, 1);
+
+        // code that fails to load is a miss too
+        let deps = mock_dependencies(20, &[]);
+        let missing = Checksum::generate(b"missing");
+        assert!(cache
+            .get_instance(&missing, deps, TESTING_GAS_LIMIT)
+            .is_err());
+        assert_eq!(cache.metrics().misses, 2);
+    }
+
+    #[test]
+    fn prewarm_works() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut cache: CosmCache<MockStorage, MockApi, MockQuerier> = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
+        let id = cache.save_wasm(CONTRACT).unwrap();
+        let stamp_path = tmp_dir.path().join(VALIDATION_DIR).join(id.to_hex());
+        fs::remove_file(&stamp_path).unwrap();
+
+        // code that isn't stored is skipped
+        let missing = Checksum::generate(b"missing");
+        cache
+            .prewarm(&[missing, id], false)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert!(stamp_path.is_file());
+        assert!(cache.metrics().unseal_time_ns > 0);
+        assert_eq!(cache.metrics().elements_pinned, 0);
+    }
+
     #[test]
     fn get_instance_finds_cached_module() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
         let deps = mock_dependencies(20, &[]);
         let _instance = cache.get_instance(&id, deps, TESTING_GAS_LIMIT).unwrap();
@@ -360,7 +1376,14 @@ mod test {
     #[test]
     fn get_instance_finds_cached_instance() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
         let deps1 = mock_dependencies(20, &[]);
         let deps2 = mock_dependencies(20, &[]);
@@ -375,7 +1398,14 @@ mod test {
     #[test]
     fn init_cached_contract() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
         let deps = mock_dependencies(20, &[]);
         let mut instance = cache.get_instance(&id, deps, TESTING_GAS_LIMIT).unwrap();
@@ -393,7 +1423,14 @@ mod test {
     #[test]
     fn run_cached_contract() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
         // TODO: contract balance
         let deps = mock_dependencies(20, &[]);
@@ -417,7 +1454,14 @@ mod test {
     #[test]
     fn use_multiple_cached_instances_of_same_contract() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
 
         // these differentiate the two instances of the same contract
@@ -463,7 +1507,14 @@ mod test {
     #[cfg(feature = "default-singlepass")]
     fn resets_gas_when_reusing_instance() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
 
         let deps1 = mock_dependencies(20, &[]);
@@ -494,7 +1545,14 @@ mod test {
     #[cfg(feature = "default-singlepass")]
     fn recovers_from_out_of_gas() {
         let tmp_dir = TempDir::new().unwrap();
-        let mut cache = unsafe { CosmCache::new(tmp_dir.path(), default_features()).unwrap() };
+        let mut cache = unsafe {
+            CosmCache::new(
+                tmp_dir.path(),
+                default_features(),
+                WasmValidationConfig::default(),
+            )
+            .unwrap()
+        };
         let id = cache.save_wasm(CONTRACT).unwrap();
 
         let deps1 = mock_dependencies(20, &[]);
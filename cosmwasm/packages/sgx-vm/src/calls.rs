@@ -11,6 +11,7 @@ use crate::instance::{Func, Instance};
 use crate::instance::Instance;
 // use crate::serde::{from_slice, to_vec};
 use crate::traits::{Api, Querier, Storage};
+use enclave_ffi_types::QueryResponseSignature;
 // use schemars::JsonSchema;
 
 /*
@@ -152,6 +153,17 @@ pub fn call_query_raw<S: Storage + 'static, A: Api + 'static, Q: Querier + 'stat
     instance.call_query(env, msg)
 }
 
+/// Calls Wasm export "query" like `call_query_raw`, and also returns the enclave's signature of
+/// the response, if any.
+pub fn call_query_raw_signed<S: Storage + 'static, A: Api + 'static, Q: Querier + 'static>(
+    instance: &mut Instance<S, A, Q>,
+    env: &[u8],
+    msg: &[u8],
+) -> VmResult<(Vec<u8>, Option<QueryResponseSignature>)> {
+    instance.set_storage_readonly(true);
+    instance.call_query_signed(env, msg)
+}
+
 #[cfg(not(feature = "default-enclave"))]
 fn call_raw<S: Storage + 'static, A: Api + 'static, Q: Querier + 'static>(
     instance: &mut Instance<S, A, Q>,
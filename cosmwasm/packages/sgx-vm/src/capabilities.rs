@@ -0,0 +1,198 @@
+//! A description of what this node accepts from and offers to contracts, for clients and contract
+//! tooling to adapt to the node they talk to.
+
+use std::collections::HashSet;
+
+use log::debug;
+use serde::Serialize;
+use sgx_types::*;
+
+use crate::compatability::WasmValidationConfig;
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_get_mr_enclave(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        mr_enclave: &mut [u8; 32],
+    ) -> sgx_status_t;
+
+    pub fn ecall_get_disclosure_signer(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        public_key: &mut [u8; 32],
+    ) -> sgx_status_t;
+}
+
+/// The version of the gas the enclave charges for instructions and host functions. It changes
+/// whenever they do, so that tooling estimating gas knows when its numbers are stale.
+pub const GAS_SCHEDULE_VERSION: u32 = 2;
+
+/// The interface versions of the contracts this node runs, as `IntrospectionReport` names them
+const INTERFACE_VERSIONS: &[&str] = &["v0.10", "v1"];
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VmCapabilities {
+    pub interface_versions: Vec<String>,
+    /// The imports contracts of each interface version may use, as `module.name`, including the
+    /// ones the chain adds
+    pub host_functions_v010: Vec<String>,
+    pub host_functions_v1: Vec<String>,
+    /// Sorted
+    pub features: Vec<String>,
+    pub limits: VmLimits,
+    pub gas_schedule_version: u32,
+    /// Hex encoded. `None` if the enclave can't report it, e.g. in software mode.
+    pub mr_enclave: Option<String>,
+    /// The hex encoded ed25519 public key disclosure bundles are signed with, the same on every
+    /// node of the network. `None` before the node has the consensus seed.
+    pub disclosure_signer: Option<String>,
+}
+
+/// The limits contracts must stay within to be stored, `None` if unlimited
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VmLimits {
+    /// In pages
+    pub memory_limit: u32,
+    pub max_functions: Option<usize>,
+    /// In bytes
+    pub max_function_body_size: Option<usize>,
+    /// In elements
+    pub max_table_size: Option<u32>,
+    /// In bytes
+    pub max_code_size: Option<usize>,
+    pub max_data_segments: Option<usize>,
+    /// In bytes
+    pub max_data_size: Option<usize>,
+}
+
+pub(crate) fn vm_capabilities(
+    supported_features: &HashSet<String>,
+    config: &WasmValidationConfig,
+    mr_enclave: Option<[u8; 32]>,
+    disclosure_signer: Option<[u8; 32]>,
+) -> VmCapabilities {
+    let with_extra = |imports: &[String]| {
+        imports
+            .iter()
+            .chain(config.extra_supported_imports.iter())
+            .cloned()
+            .collect()
+    };
+    let mut features: Vec<String> = supported_features.iter().cloned().collect();
+    features.sort_unstable();
+
+    VmCapabilities {
+        interface_versions: INTERFACE_VERSIONS.iter().map(|v| v.to_string()).collect(),
+        host_functions_v010: with_extra(&config.supported_imports_v010),
+        host_functions_v1: with_extra(&config.supported_imports_v1),
+        features,
+        limits: VmLimits {
+            memory_limit: config.memory_limit,
+            max_functions: config.max_functions,
+            max_function_body_size: config.max_function_body_size,
+            max_table_size: config.max_table_size,
+            max_code_size: config.max_code_size,
+            max_data_segments: config.max_data_segments,
+            max_data_size: config.max_data_size,
+        },
+        gas_schedule_version: GAS_SCHEDULE_VERSION,
+        mr_enclave: mr_enclave.map(hex::encode),
+        disclosure_signer: disclosure_signer.map(hex::encode),
+    }
+}
+
+/// Returns the MRENCLAVE of the enclave. Enclaves in software mode fail with
+/// `SGX_ERROR_FEATURE_NOT_SUPPORTED`.
+pub fn untrusted_get_mr_enclave() -> SgxResult<[u8; 32]> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut mr_enclave = [0u8; 32];
+    let status = unsafe { ecall_get_mr_enclave(eid, &mut retval, &mut mr_enclave) };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        debug!("The enclave didn't report its MRENCLAVE: {}", retval);
+        return Err(retval);
+    }
+
+    Ok(mr_enclave)
+}
+
+/// Returns the public key the enclave signs disclosure bundles with
+pub fn untrusted_get_disclosure_signer() -> SgxResult<[u8; 32]> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut public_key = [0u8; 32];
+    let status = unsafe { ecall_get_disclosure_signer(eid, &mut retval, &mut public_key) };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        debug!(
+            "The enclave didn't report its disclosure signer: {}",
+            retval
+        );
+        return Err(retval);
+    }
+
+    Ok(public_key)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vm_capabilities_works() {
+        let features: HashSet<String> = ["staking", "iterator", "random"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        let config = WasmValidationConfig {
+            extra_supported_imports: vec!["env.chain_specific".to_string()],
+            max_code_size: Some(800 * 1024),
+            ..WasmValidationConfig::default()
+        };
+
+        let capabilities = vm_capabilities(&features, &config, Some([0xab; 32]), Some([0xcd; 32]));
+        assert_eq!(capabilities.interface_versions, vec!["v0.10", "v1"]);
+        assert_eq!(capabilities.features, vec!["iterator", "random", "staking"]);
+        assert_eq!(
+            capabilities.host_functions_v1.len(),
+            config.supported_imports_v1.len() + 1
+        );
+        assert!(capabilities
+            .host_functions_v010
+            .contains(&"env.chain_specific".to_string()));
+        assert_eq!(capabilities.limits.memory_limit, config.memory_limit);
+        assert_eq!(capabilities.limits.max_code_size, Some(800 * 1024));
+        assert_eq!(capabilities.limits.max_functions, None);
+        assert_eq!(capabilities.gas_schedule_version, GAS_SCHEDULE_VERSION);
+        assert_eq!(capabilities.mr_enclave, Some("ab".repeat(32)));
+        assert_eq!(capabilities.disclosure_signer, Some("cd".repeat(32)));
+
+        let capabilities = vm_capabilities(&features, &config, None, None);
+        assert_eq!(capabilities.mr_enclave, None);
+        assert_eq!(capabilities.disclosure_signer, None);
+    }
+}
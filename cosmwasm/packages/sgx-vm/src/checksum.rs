@@ -1,15 +1,21 @@
 use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use sha2::{Digest, Sha256};
 
-use crate::errors::VmError;
+use crate::errors::{VmError, VmResult};
 
 /// A SHA-256 checksum of a Wasm blob, used to identify a Wasm code.
 /// This must remain stable since this checksum is stored in the blockchain state.
 ///
 /// This is often referred to as "code ID" in go-cosmwasm, even if code ID
 /// usually refers to an auto-incrementing number.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///
+/// Comparisons run in constant time, since checksums are compared against
+/// values that come from the chain and from disk.
+#[derive(Debug, Copy, Clone)]
 pub struct Checksum([u8; 32]);
 
 impl Checksum {
@@ -17,10 +23,60 @@ impl Checksum {
         Checksum(Sha256::digest(wasm).into())
     }
 
+    /// Parses a hex encoded checksum, as produced by `to_hex`.
+    /// Both lowercase and uppercase input is accepted.
+    pub fn from_hex(input: &str) -> VmResult<Self> {
+        let data = hex::decode(input)
+            .map_err(|e| VmError::cache_err(format!("Invalid hex checksum: {}", e)))?;
+        Checksum::try_from(data.as_slice())
+    }
+
     /// Creates a lowercase hex encoded copy of this checksum
     pub fn to_hex(&self) -> String {
         hex::encode(self.0)
     }
+
+    /// Creates a standard base64 encoded copy of this checksum, the encoding
+    /// used for code hashes in JSON
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0)
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq for Checksum {
+    fn eq(&self, other: &Self) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        diff == 0
+    }
+}
+
+impl Eq for Checksum {}
+
+// Implemented by hand to stay consistent with the PartialEq implementation above
+impl Hash for Checksum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl AsRef<[u8]> for Checksum {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 impl From<[u8; 32]> for Checksum {
@@ -78,6 +134,52 @@ mod test {
         );
     }
 
+    #[test]
+    fn from_hex_works() {
+        let checksum = Checksum::generate(&[0x68, 0x69, 0x6a]);
+        assert_eq!(Checksum::from_hex(&checksum.to_hex()).unwrap(), checksum);
+        assert_eq!(
+            Checksum::from_hex(&checksum.to_hex().to_uppercase()).unwrap(),
+            checksum
+        );
+
+        match Checksum::from_hex("722c8c").unwrap_err() {
+            VmError::CacheErr { msg, .. } => assert_eq!(msg, "Checksum not of length 32"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        match Checksum::from_hex("not hex").unwrap_err() {
+            VmError::CacheErr { msg, .. } => assert!(msg.starts_with("Invalid hex checksum")),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn to_base64_works() {
+        let checksum = Checksum::generate(&[0x68, 0x69, 0x6a]);
+        // echo -n "hij" | sha256sum | xxd -r -p | base64
+        assert_eq!(
+            checksum.to_base64(),
+            "ciyMmT/XWnYn1p7ZQTRP4qFCOj5179Pmd4oUKIQicQQ="
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        let checksum = Checksum::generate(&[0x68, 0x69, 0x6a]);
+        assert_eq!(format!("{}", checksum), checksum.to_hex());
+    }
+
+    #[test]
+    fn eq_works() {
+        let checksum = Checksum::generate(&[0x68, 0x69, 0x6a]);
+        let mut other = [0u8; 32];
+        other.copy_from_slice(checksum.as_slice());
+        assert_eq!(checksum, Checksum::from(other));
+
+        other[31] ^= 1;
+        assert_ne!(checksum, Checksum::from(other));
+    }
+
     #[test]
     fn into_vec_works() {
         let checksum = Checksum::generate(&vec![12u8; 17]);
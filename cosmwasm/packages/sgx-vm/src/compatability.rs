@@ -1,223 +1,687 @@
-use parity_wasm::elements::{deserialize_buffer, External, ImportEntry, Module};
-use std::collections::BTreeSet;
 use std::collections::HashSet;
+use std::fmt::Debug;
 use std::iter::FromIterator;
 
-use crate::errors::{VmError, VmResult};
-use crate::features::required_features_from_module;
-
-/// Lists all v0.10 imports we provide upon instantiating the instance in Instance::from_module()
-/// This should be updated when new imports are added
-const SUPPORTED_IMPORTS_V010: &[&str] = &[
-    "env.db_read",
-    "env.db_write",
-    "env.db_remove",
-    "env.canonicalize_address",
-    "env.humanize_address",
-    "env.query_chain",
-    "env.secp256k1_verify",
-    "env.secp256k1_recover_pubkey",
-    "env.secp256k1_sign",
-    "env.ed25519_verify",
-    "env.ed25519_batch_verify",
-    "env.ed25519_sign",
-    #[cfg(feature = "iterator")]
-    "env.db_scan",
-    #[cfg(feature = "iterator")]
-    "env.db_next",
-    #[cfg(feature = "debug-print")]
-    "env.debug_print",
-];
-
-/// Lists all v1 imports we provide upon instantiating the instance in Instance::from_module()
-/// This should be updated when new imports are added
-const SUPPORTED_IMPORTS_V1: &[&str] = &[
-    "env.db_read",
-    "env.db_write",
-    "env.db_remove",
-    "env.addr_validate",
-    "env.addr_canonicalize",
-    "env.addr_humanize",
-    "env.secp256k1_verify",
-    "env.secp256k1_recover_pubkey",
-    "env.secp256k1_sign",
-    "env.ed25519_verify",
-    "env.ed25519_batch_verify",
-    "env.ed25519_sign",
-    "env.debug",
-    "env.query_chain",
-    #[cfg(feature = "iterator")]
-    "env.db_scan",
-    #[cfg(feature = "iterator")]
-    "env.db_next",
-    "env.gas_evaporate",
-    "env.check_gas"
-];
-
-/// Lists all entry points we expect to be present when calling a v0.10 contract.
-/// Basically, anything that is used in calls.rs
-/// This is unlikely to change much, must be frozen at 1.0 to avoid breaking existing contracts
-const REQUIRED_EXPORTS_V010: &[&str] = &[
-    "cosmwasm_vm_version_3",
-    "query",
-    "init",
-    "handle",
-    "allocate",
-    "deallocate",
-];
-
-/// Lists all entry points we expect to be present when calling a v1 contract.
-/// Basically, anything that is used in calls.rs
-/// This is unlikely to change much, must be frozen at 1.0 to avoid breaking existing contracts
-const REQUIRED_EXPORTS_V1: &[&str] = &[
-    "interface_version_8",
-    // IO
-    "allocate",
-    "deallocate",
-    // Required entry points
-    "instantiate",
-];
-
-pub const REQUIRED_IBC_EXPORTS: &[&str] = &[
-    "ibc_channel_open",
-    "ibc_channel_connect",
-    "ibc_channel_close",
-    "ibc_packet_receive",
-    "ibc_packet_ack",
-    "ibc_packet_timeout",
-];
+use log::*;
+use serde::Deserialize;
+
+use crate::entry_points::EntryPoint;
+use crate::errors::{ValidationFailure, VmError, VmResult};
+use crate::features::{required_features_from_module, REQUIRES_PREFIX};
+use crate::parsed_modules::parse_module;
+use crate::wasm_module::{WasmFunctionType, WasmModule};
+
+// The imports we provide upon instantiating the instance (SUPPORTED_IMPORTS_V010,
+// SUPPORTED_IMPORTS_V1) and the entry points we expect to be present when calling a contract
+// (REQUIRED_EXPORTS_V010, REQUIRED_EXPORTS_V1, REQUIRED_IBC_EXPORTS).
+// Generated by build.rs from enclaves/ffi-types/wasm_abi.schema, which is where new imports
+// should be added.
+include!(concat!(env!("OUT_DIR"), "/wasm_abi.rs"));
 
 const MEMORY_LIMIT: u32 = 512; // in pages
 
-/// Checks if the data is valid wasm and compatibility with the CosmWasm API (imports and exports)
-pub fn check_wasm(wasm_code: &[u8], supported_features: &HashSet<String>) -> VmResult<()> {
-    let module = match deserialize_buffer(&wasm_code) {
-        Ok(deserialized) => deserialized,
-        Err(err) => {
-            return Err(VmError::static_validation_err(format!(
-                "Wasm bytecode could not be deserialized. Deserialization error: \"{}\"",
-                err
-            )));
+const INTERFACE_VERSION_PREFIX: &str = "interface_version_";
+/// The features cosmwasm-std requires from contracts using API added after 1.0
+pub(crate) const COSMWASM_1_X_FEATURE_PREFIX: &str = "cosmwasm_1_";
+/// Exports the enclave uses when present, on top of the required ones and the entry points
+const OPTIONAL_EXPORTS: &[&str] = &["memory", "__data_end", "__heap_base"];
+
+/// The limits and the interface `check_wasm` validates contracts against.
+///
+/// The defaults are those of Secret Network. Chains forking it can pass their own from
+/// go-cosmwasm, as JSON in which missing fields keep their default.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WasmValidationConfig {
+    /// The largest initial memory of a contract, in pages
+    pub memory_limit: u32,
+    /// Accept a memory maximum of at most `memory_limit` pages, which newer toolchains always emit,
+    /// instead of requiring it to be unset. The enclave sets the maximum either way.
+    pub allow_memory_maximum: bool,
+    pub supported_imports_v010: Vec<String>,
+    pub supported_imports_v1: Vec<String>,
+    pub required_exports_v010: Vec<String>,
+    pub required_exports_v1: Vec<String>,
+    /// Imports accepted from contracts of both versions on top of `supported_imports_v010` and
+    /// `supported_imports_v1`, for chains whose enclave provides extra host functions. They are
+    /// validated like the others: only function imports are accepted.
+    pub extra_supported_imports: Vec<String>,
+    /// What to do with contracts using floating point instructions. The enclave refuses to
+    /// instantiate them, but they can still be stored and migrated to.
+    pub float_instructions: FloatPolicy,
+    /// The most functions a contract may define, unlimited if unset
+    pub max_functions: Option<usize>,
+    /// The largest function body a contract may contain, in bytes, unlimited if unset
+    pub max_function_body_size: Option<usize>,
+    /// The largest initial size of a table of a contract, in elements, unlimited if unset
+    pub max_table_size: Option<u32>,
+    /// The largest contract, in bytes, unlimited if unset. Checked before deserializing it.
+    pub max_code_size: Option<usize>,
+    /// The most data segments a contract may have, unlimited if unset
+    pub max_data_segments: Option<usize>,
+    /// The largest total size of the data segments of a contract, in bytes, unlimited if unset
+    pub max_data_size: Option<usize>,
+    /// Report, and log a warning for, exports the enclave never calls, which are usually a
+    /// misspelled entry point. They are accepted either way.
+    pub strict_exports: bool,
+    /// Accept contracts using the bulk memory proposal (`memory.copy`, `memory.fill`...), which
+    /// newer Rust versions emit. It can't be configured, since it decides what contracts the chain
+    /// accepts: `CosmCache` sets it for stored contracts, and for new ones when the chain activated
+    /// the proposal, see `CosmCache::save_wasm_with_bulk_memory`.
+    #[serde(skip)]
+    pub bulk_memory: bool,
+}
+
+/// What `check_wasm_with_report` found in a valid contract that is worth telling its developer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The exports that are neither required nor optional entry points, nor feature or interface
+    /// markers, in the order of the export section. Only filled in with `strict_exports`.
+    pub unknown_exports: Vec<String>,
+}
+
+/// How `check_wasm` treats contracts using f32 or f64 instructions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FloatPolicy {
+    /// Accept them and log which functions use floats
+    Flag,
+    /// Reject them, listing the functions using floats in the error
+    Reject,
+}
+
+impl Default for WasmValidationConfig {
+    fn default() -> Self {
+        let to_strings = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            memory_limit: MEMORY_LIMIT,
+            allow_memory_maximum: false,
+            supported_imports_v010: to_strings(SUPPORTED_IMPORTS_V010),
+            supported_imports_v1: to_strings(SUPPORTED_IMPORTS_V1),
+            required_exports_v010: to_strings(REQUIRED_EXPORTS_V010),
+            required_exports_v1: to_strings(REQUIRED_EXPORTS_V1),
+            extra_supported_imports: vec![],
+            float_instructions: FloatPolicy::Flag,
+            // Unlimited, since contracts stored before the limits existed are validated again with
+            // them when loaded
+            max_functions: None,
+            max_function_body_size: None,
+            max_table_size: None,
+            max_code_size: None,
+            max_data_segments: None,
+            max_data_size: None,
+            strict_exports: false,
+            bulk_memory: false,
         }
-    };
-    check_wasm_memories(&module)?;
+    }
+}
+
+impl WasmValidationConfig {
+    /// Parses a config from JSON. Empty input is the default config.
+    pub fn from_json(json: &[u8]) -> VmResult<Self> {
+        if json.is_empty() {
+            return Ok(Self::default());
+        }
+        serde_json::from_slice(json)
+            .map_err(|e| VmError::parse_err("WasmValidationConfig", e.to_string()))
+    }
+}
+
+/// The rules `check_wasm` applies
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Rules {
+    /// All of them, for contracts being uploaded
+    Upload,
+    /// Leaves out the rules added after the chain started storing contracts, for contracts it
+    /// already stored, which it accepted without them. See `check_stored_wasm`.
+    Stored,
+}
+
+/// Checks if the data is valid wasm and compatibility with the CosmWasm API (imports and exports)
+pub fn check_wasm(
+    wasm_code: &[u8],
+    supported_features: &HashSet<String>,
+    config: &WasmValidationConfig,
+) -> VmResult<()> {
+    check_wasm_with_report(wasm_code, supported_features, config).map(|_| ())
+}
+
+/// Like `check_wasm`, for a contract the chain already stored, e.g. validated again after the node
+/// was upgraded. Rules only enforced on upload are left out, so that contracts stored before they
+/// existed keep running.
+pub fn check_stored_wasm(
+    wasm_code: &[u8],
+    supported_features: &HashSet<String>,
+    config: &WasmValidationConfig,
+) -> VmResult<()> {
+    check_wasm_rules(wasm_code, supported_features, config, Rules::Stored).map(|_| ())
+}
+
+/// Like `check_wasm`, also returning what was found in a valid contract
+pub fn check_wasm_with_report(
+    wasm_code: &[u8],
+    supported_features: &HashSet<String>,
+    config: &WasmValidationConfig,
+) -> VmResult<ValidationReport> {
+    check_wasm_rules(wasm_code, supported_features, config, Rules::Upload)
+}
+
+fn check_wasm_rules(
+    wasm_code: &[u8],
+    supported_features: &HashSet<String>,
+    config: &WasmValidationConfig,
+    rules: Rules,
+) -> VmResult<ValidationReport> {
+    check_wasm_code_size(wasm_code, config)?;
+    let module = parse_module(wasm_code)?;
+    check_wasm_memories(&module, config)?;
+    if rules == Rules::Upload {
+        check_wasm_start(&module)?;
+    }
+    check_wasm_size_limits(&module, config)?;
     check_wasm_features(&module, supported_features)?;
+    check_wasm_floats(&module, config)?;
+    check_wasm_mixed_imports(&module, config)?;
 
-    let check_v010_exports_result = check_wasm_exports(&module, REQUIRED_EXPORTS_V010);
-    let check_v010_imports_result = check_wasm_imports(&module, SUPPORTED_IMPORTS_V010);
+    let check_v010_exports_result = check_wasm_exports(&module, &config.required_exports_v010);
+    let check_v010_imports_result = check_wasm_imports(
+        &module,
+        &config.supported_imports_v010,
+        &config.extra_supported_imports,
+    );
     let is_v010 = check_v010_exports_result.is_ok() && check_v010_imports_result.is_ok();
 
-    let check_v1_exports_result = check_wasm_exports(&module, REQUIRED_EXPORTS_V1);
-    let check_v1_imports_result = check_wasm_imports(&module, SUPPORTED_IMPORTS_V1);
+    let check_v1_exports_result = check_wasm_exports(&module, &config.required_exports_v1);
+    let check_v1_imports_result = check_wasm_imports(
+        &module,
+        &config.supported_imports_v1,
+        &config.extra_supported_imports,
+    );
     let is_v1 = check_v1_exports_result.is_ok() && check_v1_imports_result.is_ok();
 
     if !is_v010 && !is_v1 {
-        let errors = vec![
-            check_v010_exports_result,
-            check_v010_imports_result,
-            check_v1_exports_result,
-            check_v1_imports_result,
-        ];
+        check_wasm_interface_version(&module, config)?;
+
+        let failures = |results: Vec<VmResult<()>>| -> Vec<ValidationFailure> {
+            results
+                .iter()
+                .filter_map(|result| result.as_ref().err())
+                .filter_map(|err| err.validation_failure().cloned())
+                .collect()
+        };
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::NotCosmWasm {
+                v010_failures: failures(vec![check_v010_exports_result, check_v010_imports_result]),
+                v1_failures: failures(vec![check_v1_exports_result, check_v1_imports_result]),
+            },
+        ));
+    }
 
-        return Err(VmError::static_validation_err(format!("Contract is not CosmWasm v0.10 or v1. To support v0.10 please fix the former two errors, to supports v1 please fix the latter two errors: ${:?}", errors)));
+    module.validate(wasm_code, config.bulk_memory)?;
+    if rules == Rules::Upload {
+        check_wasm_export_signatures(&module, is_v1)?;
     }
 
-    Ok(())
+    let mut report = ValidationReport::default();
+    if config.strict_exports {
+        report.unknown_exports = unknown_exports(&module, config);
+        if !report.unknown_exports.is_empty() {
+            warn!(
+                "Wasm contract has exports that are never called: {:?}",
+                report.unknown_exports
+            );
+        }
+    }
+    Ok(report)
+}
+
+fn unknown_exports(module: &WasmModule, config: &WasmValidationConfig) -> Vec<String> {
+    let is_known = |name: &str| {
+        config.required_exports_v010.iter().any(|e| e == name)
+            || config.required_exports_v1.iter().any(|e| e == name)
+            || EntryPoint::from_export_name(name).is_some()
+            || OPTIONAL_EXPORTS.contains(&name)
+            || name.starts_with(REQUIRES_PREFIX)
+            || name.starts_with(INTERFACE_VERSION_PREFIX)
+    };
+    module
+        .exports()
+        .iter()
+        .filter(|export| !is_known(&export.name))
+        .map(|export| export.name.clone())
+        .collect()
 }
 
-fn check_wasm_memories(module: &Module) -> VmResult<()> {
-    let section = match module.memory_section() {
-        Some(section) => section,
+fn check_wasm_memories(module: &WasmModule, config: &WasmValidationConfig) -> VmResult<()> {
+    let memories = match module.memories() {
+        Some(memories) => memories,
         None => {
-            return Err(VmError::static_validation_err(
-                "Wasm contract doesn't have a memory section",
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::MissingMemorySection,
             ));
         }
     };
 
-    let memories = section.entries();
-    if memories.len() != 1 {
-        return Err(VmError::static_validation_err(
-            "Wasm contract must contain exactly one memory",
+    if memories.len() > 1 {
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::TooManyMemories {
+                count: memories.len(),
+            },
+        ));
+    }
+    if memories.is_empty() {
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::NoMemory,
         ));
     }
 
     let memory = memories[0];
     // println!("Memory: {:?}", memory);
-    let limits = memory.limits();
 
-    if limits.initial() > MEMORY_LIMIT {
-        return Err(VmError::static_validation_err(format!(
-            "Wasm contract memory's minimum must not exceed {} pages.",
-            MEMORY_LIMIT
-        )));
+    if memory.initial > config.memory_limit as u64 {
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::MemoryInitialTooLarge {
+                limit: config.memory_limit,
+            },
+        ));
+    }
+
+    match memory.maximum {
+        None => {}
+        Some(maximum) if config.allow_memory_maximum => {
+            if maximum > config.memory_limit as u64 {
+                return Err(VmError::static_validation_failure(
+                    ValidationFailure::MemoryMaximumTooLarge {
+                        limit: config.memory_limit,
+                    },
+                ));
+            }
+        }
+        Some(_) => {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::MemoryMaximumSet,
+            ));
+        }
     }
+    Ok(())
+}
 
-    if limits.maximum() != None {
-        return Err(VmError::static_validation_err(
-            "Wasm contract memory's maximum must be unset. The host will set it for you.",
+/// A start function would run when the contract is instantiated, before and outside of the entry
+/// points the enclave calls. Only enforced on upload.
+fn check_wasm_start(module: &WasmModule) -> VmResult<()> {
+    if module.start_function().is_some() {
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::StartFunction,
         ));
     }
     Ok(())
 }
 
-pub fn check_wasm_exports(module: &Module, required_exports: &[&str]) -> VmResult<()> {
-    let available_exports: Vec<String> = module.export_section().map_or(vec![], |export_section| {
-        export_section
-            .entries()
+fn check_wasm_code_size(wasm_code: &[u8], config: &WasmValidationConfig) -> VmResult<()> {
+    if let Some(max_code_size) = config.max_code_size {
+        if wasm_code.len() > max_code_size {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::CodeTooLarge {
+                    size: wasm_code.len(),
+                    limit: max_code_size,
+                },
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_wasm_size_limits(module: &WasmModule, config: &WasmValidationConfig) -> VmResult<()> {
+    let function_count = module.function_body_sizes().len();
+    if let Some(max_functions) = config.max_functions {
+        if function_count > max_functions {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::TooManyFunctions {
+                    count: function_count,
+                    limit: max_functions,
+                },
+            ));
+        }
+    }
+
+    if let Some(max_function_body_size) = config.max_function_body_size {
+        let largest_body = module
+            .function_body_sizes()
             .iter()
-            .map(|entry| entry.field().to_string())
-            .collect()
-    });
+            .enumerate()
+            .max_by_key(|(_, &size)| size);
+        if let Some((index, &size)) = largest_body {
+            if size > max_function_body_size {
+                // in the index space of functions, which starts with the imported ones
+                let imported_functions = module.imports().iter().filter(|i| i.is_function).count();
+                return Err(VmError::static_validation_failure(
+                    ValidationFailure::FunctionBodyTooLarge {
+                        function: imported_functions + index,
+                        size,
+                        limit: max_function_body_size,
+                    },
+                ));
+            }
+        }
+    }
+
+    if let Some(max_table_size) = config.max_table_size {
+        if let Some(&size) = module.table_sizes().iter().max() {
+            if size > max_table_size {
+                return Err(VmError::static_validation_failure(
+                    ValidationFailure::TableTooLarge {
+                        size,
+                        limit: max_table_size,
+                    },
+                ));
+            }
+        }
+    }
+
+    let data_segment_count = module.data_segment_sizes().len();
+    if let Some(max_data_segments) = config.max_data_segments {
+        if data_segment_count > max_data_segments {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::TooManyDataSegments {
+                    count: data_segment_count,
+                    limit: max_data_segments,
+                },
+            ));
+        }
+    }
+
+    if let Some(max_data_size) = config.max_data_size {
+        let data_size: usize = module.data_segment_sizes().iter().sum();
+        if data_size > max_data_size {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::DataTooLarge {
+                    size: data_size,
+                    limit: max_data_size,
+                },
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_wasm_floats(module: &WasmModule, config: &WasmValidationConfig) -> VmResult<()> {
+    if module.float_functions().is_empty() {
+        return Ok(());
+    }
+
+    let failure = ValidationFailure::FloatInstructions {
+        functions: module
+            .float_functions()
+            .iter()
+            .map(|&index| (index, module.function_export_name(index).map(String::from)))
+            .collect(),
+    };
+
+    match config.float_instructions {
+        FloatPolicy::Flag => {
+            warn!("{} It can't be instantiated.", failure);
+            Ok(())
+        }
+        FloatPolicy::Reject => Err(VmError::static_validation_failure(failure)),
+    }
+}
+
+pub fn check_wasm_exports<S: AsRef<str> + Debug>(
+    module: &WasmModule,
+    required_exports: &[S],
+) -> VmResult<()> {
+    let available_exports = module.exports();
 
     for required_export in required_exports {
-        if !available_exports.iter().any(|x| x == required_export) {
-            return Err(VmError::static_validation_err(format!(
-                "Wasm contract doesn't have required export: \"{}\". Exports required by VM: {:?}.",
-                required_export, required_exports
-            )));
+        let required_export = required_export.as_ref();
+        if !available_exports.iter().any(|x| x.name == required_export) {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::MissingExport {
+                    export: required_export.to_string(),
+                    required_exports: to_strings(required_exports),
+                },
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The type the enclave calls an export with, for contracts of v1 if `is_v1` or of v0.10. `None`
+/// for exports it doesn't call.
+fn export_signature(name: &str, is_v1: bool) -> Option<WasmFunctionType> {
+    let (params, results) = match name {
+        "allocate" => (1, 1),
+        "deallocate" => (1, 0),
+        name => {
+            let entry_point = EntryPoint::from_export_name(name).filter(|entry_point| {
+                (is_v1 || entry_point.in_v010()) && entry_point.export_name(is_v1) == name
+            })?;
+            (entry_point.param_count(is_v1), 1)
+        }
+    };
+    Some(WasmFunctionType::i32s(params, results))
+}
+
+/// Rejects contracts exporting an entry point, `allocate` or `deallocate` with another type than
+/// the one the enclave calls it with, which would only fail when the contract is called. Only
+/// enforced on upload.
+fn check_wasm_export_signatures(module: &WasmModule, is_v1: bool) -> VmResult<()> {
+    for export in module.exports() {
+        let expected = match export_signature(&export.name, is_v1) {
+            Some(expected) => expected,
+            None => continue,
+        };
+        let actual = if export.is_function {
+            module.function_type(export.index)
+        } else {
+            None
+        };
+        if actual != Some(&expected) {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::WrongExportSignature {
+                    export: export.name.clone(),
+                    expected: expected.to_string(),
+                    actual: actual.map(|actual| actual.to_string()),
+                },
+            ));
         }
     }
     Ok(())
 }
 
+/// The CosmWasm version a contract was built for, judged by its exports alone.
+/// Returns `"v1"`, `"v0.10"` or `None` if the contract exports neither version marker.
+pub fn interface_version(module: &WasmModule) -> Option<&'static str> {
+    if check_wasm_exports(module, REQUIRED_EXPORTS_V1).is_ok() {
+        Some("v1")
+    } else if check_wasm_exports(module, REQUIRED_EXPORTS_V010).is_ok() {
+        Some("v0.10")
+    } else {
+        None
+    }
+}
+
+/// Which of the IBC entry points a contract exports. A contract takes part in IBC only if it
+/// exports all of them, see `REQUIRED_IBC_EXPORTS`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IbcCapabilities {
+    /// In the order of `REQUIRED_IBC_EXPORTS`
+    pub present: Vec<String>,
+    /// In the order of `REQUIRED_IBC_EXPORTS`
+    pub missing: Vec<String>,
+}
+
+impl IbcCapabilities {
+    /// Whether the contract exports all the IBC entry points
+    pub fn is_enabled(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Whether the contract exports some of the IBC entry points but not all, which is most likely
+    /// a mistake since it can't take part in IBC
+    pub fn is_partial(&self) -> bool {
+        !self.present.is_empty() && !self.missing.is_empty()
+    }
+}
+
+pub(crate) fn ibc_capabilities(module: &WasmModule) -> IbcCapabilities {
+    let (present, missing): (Vec<&str>, Vec<&str>) =
+        REQUIRED_IBC_EXPORTS.iter().partition(|&&required| {
+            module
+                .exports()
+                .iter()
+                .any(|export| export.name == required)
+        });
+    IbcCapabilities {
+        present: to_strings(&present),
+        missing: to_strings(&missing),
+    }
+}
+
+/// Reports which of the IBC entry points a contract exports, without validating or running it
+pub fn has_ibc_entry_points(wasm_code: &[u8]) -> VmResult<IbcCapabilities> {
+    let module = parse_module(wasm_code)?;
+    Ok(ibc_capabilities(&module))
+}
+
+/// The capabilities of a contract, read from its Wasm without validating or running it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractAnalysis {
+    /// `"v1"`, `"v0.10"` or `None` if unknown
+    pub interface_version: Option<&'static str>,
+    pub required_features: HashSet<String>,
+    pub has_ibc_entry_points: bool,
+    pub has_migrate: bool,
+    pub has_reply: bool,
+    pub has_sudo: bool,
+    /// The initial size of the first memory in pages, `None` if the contract has no memory
+    pub memory_initial_pages: Option<u64>,
+    /// The maximum size of the first memory in pages, if it declares one
+    pub memory_maximum_pages: Option<u64>,
+}
+
+/// Reads the capabilities of a contract from its Wasm, e.g. to describe a contract before it is
+/// instantiated. This doesn't check the contract is valid, see `check_wasm` for that.
+pub fn analyze_wasm(wasm_code: &[u8]) -> VmResult<ContractAnalysis> {
+    let module = parse_module(wasm_code)?;
+    let exports_function = |name: &str| {
+        module
+            .exports()
+            .iter()
+            .any(|e| e.is_function && e.name == name)
+    };
+    let memory = module
+        .memories()
+        .and_then(|memories| memories.first().copied());
+
+    Ok(ContractAnalysis {
+        interface_version: interface_version(&module),
+        required_features: required_features_from_module(&module),
+        has_ibc_entry_points: ibc_capabilities(&module).is_enabled(),
+        has_migrate: exports_function(EntryPoint::Migrate.export_name(true)),
+        has_reply: exports_function(EntryPoint::Reply.export_name(true)),
+        has_sudo: exports_function(EntryPoint::Sudo.export_name(true)),
+        memory_initial_pages: memory.map(|memory| memory.initial),
+        memory_maximum_pages: memory.and_then(|memory| memory.maximum),
+    })
+}
+
+/// Rejects contracts exporting an `interface_version_<n>` marker other than the v1 one with an
+/// explicit error. cosmwasm-std exports `interface_version_8` in all its 1.x releases and gates the
+/// API it added since 1.0 behind `requires_cosmwasm_1_<minor>` features instead, see
+/// `check_wasm_features`.
+fn check_wasm_interface_version(
+    module: &WasmModule,
+    config: &WasmValidationConfig,
+) -> VmResult<()> {
+    let unsupported = module.exports().iter().find(|export| {
+        export.is_function
+            && export.name.starts_with(INTERFACE_VERSION_PREFIX)
+            && !config.required_exports_v1.contains(&export.name)
+    });
+    match unsupported {
+        Some(export) => Err(VmError::static_validation_failure(
+            ValidationFailure::UnsupportedInterfaceVersion {
+                export: export.name.clone(),
+            },
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Rejects contracts importing both host functions only v0.10 provides and host functions only v1
+/// provides, which could not be instantiated with either version.
+fn check_wasm_mixed_imports(module: &WasmModule, config: &WasmValidationConfig) -> VmResult<()> {
+    let imports_only = |own: &[String], other: &[String]| -> Vec<String> {
+        module
+            .imports()
+            .iter()
+            .map(|import| import.full_name())
+            .filter(|name| own.contains(name) && !other.contains(name))
+            .collect()
+    };
+    let v010_imports = imports_only(&config.supported_imports_v010, &config.supported_imports_v1);
+    let v1_imports = imports_only(&config.supported_imports_v1, &config.supported_imports_v010);
+
+    if !v010_imports.is_empty() && !v1_imports.is_empty() {
+        return Err(VmError::mixed_interface_version(v010_imports, v1_imports));
+    }
+    Ok(())
+}
+
 /// Checks if the import requirements of the contract are satisfied.
 /// When this is not the case, we either have an incompatibility between contract and VM
 /// or a error in the contract.
-fn check_wasm_imports(module: &Module, supported_imports: &[&str]) -> VmResult<()> {
-    let required_imports: Vec<ImportEntry> = module
-        .import_section()
-        .map_or(vec![], |import_section| import_section.entries().to_vec());
-    for required_import in required_imports {
-        let full_name = format!("{}.{}", required_import.module(), required_import.field());
-        if !supported_imports.contains(&full_name.as_str()) {
-            return Err(VmError::static_validation_err(format!(
-                "Wasm contract requires unsupported import: \"{}\". Imports supported by VM: {:?}.",
-                full_name, supported_imports
-            )));
-        }
-
-        match required_import.external() {
-            External::Function(_) => {}, // ok
-            _ => return Err(VmError::static_validation_err(format!(
-                "Wasm contract requires non-function import: \"{}\". Right now, all supported imports are functions.",
-                full_name
-            ))),
-        };
+///
+/// `extra_supported_imports` are the host functions a chain adds to those of the interface version.
+fn check_wasm_imports<S: AsRef<str> + Debug, E: AsRef<str> + Debug>(
+    module: &WasmModule,
+    supported_imports: &[S],
+    extra_supported_imports: &[E],
+) -> VmResult<()> {
+    for required_import in module.imports() {
+        let full_name = required_import.full_name();
+        if !supported_imports
+            .iter()
+            .any(|import| import.as_ref() == full_name)
+            && !extra_supported_imports
+                .iter()
+                .any(|import| import.as_ref() == full_name)
+        {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::UnsupportedImport {
+                    import: full_name,
+                    supported_imports: to_strings(supported_imports),
+                    extra_supported_imports: to_strings(extra_supported_imports),
+                },
+            ));
+        }
+
+        if !required_import.is_function {
+            return Err(VmError::static_validation_failure(
+                ValidationFailure::NonFunctionImport { import: full_name },
+            ));
+        }
     }
 
     Ok(())
 }
 
-fn check_wasm_features(module: &Module, supported_features: &HashSet<String>) -> VmResult<()> {
+fn to_strings<S: AsRef<str>>(names: &[S]) -> Vec<String> {
+    names.iter().map(|name| name.as_ref().to_string()).collect()
+}
+
+fn check_wasm_features(module: &WasmModule, supported_features: &HashSet<String>) -> VmResult<()> {
     let required_features = required_features_from_module(module);
     if !required_features.is_subset(supported_features) {
-        // We switch to BTreeSet to get a sorted error message
-        let unsupported = BTreeSet::from_iter(required_features.difference(&supported_features));
-        return Err(VmError::static_validation_err(format!(
-            "Wasm contract requires unsupported features: {:?}",
-            unsupported
-        )));
+        let features = required_features
+            .difference(supported_features)
+            .cloned()
+            .collect();
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::UnsupportedFeatures { features },
+        ));
     }
     Ok(())
 }
@@ -233,6 +697,7 @@ mod test {
     static CONTRACT_0_7: &[u8] = include_bytes!("../testdata/contract_0.7.wasm");
     static CONTRACT: &[u8] = include_bytes!("../testdata/contract.wasm");
     static CORRUPTED: &[u8] = include_bytes!("../testdata/corrupted.wasm");
+    static NO_EXTRA_IMPORTS: &[&str] = &[];
 
     fn default_features() -> HashSet<String> {
         HashSet::from_iter(["staking".to_string()].iter().cloned())
@@ -241,12 +706,21 @@ mod test {
     #[test]
     fn test_check_wasm() {
         // this is our reference check, must pass
-        check_wasm(CONTRACT, &default_features()).unwrap();
+        check_wasm(
+            CONTRACT,
+            &default_features(),
+            &WasmValidationConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn test_check_wasm_old_contract() {
-        match check_wasm(CONTRACT_0_7, &default_features()) {
+        match check_wasm(
+            CONTRACT_0_7,
+            &default_features(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => assert!(msg.starts_with(
                 "Wasm contract doesn't have required export: \"cosmwasm_vm_version_3\""
             )),
@@ -254,7 +728,11 @@ mod test {
             Ok(_) => panic!("This must not succeeed"),
         };
 
-        match check_wasm(CONTRACT_0_6, &default_features()) {
+        match check_wasm(
+            CONTRACT_0_6,
+            &default_features(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => assert!(msg.starts_with(
                 "Wasm contract doesn't have required export: \"cosmwasm_vm_version_3\""
             )),
@@ -265,7 +743,11 @@ mod test {
 
     #[test]
     fn test_check_wasm_corrupted_data() {
-        match check_wasm(CORRUPTED, &default_features()) {
+        match check_wasm(
+            CORRUPTED,
+            &default_features(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with("Wasm bytecode could not be deserialized."))
             }
@@ -277,13 +759,20 @@ mod test {
     #[test]
     fn test_check_wasm_memories_ok() {
         let wasm = wat2wasm("(module (memory 1))").unwrap();
-        check_wasm_memories(&deserialize_buffer(&wasm).unwrap()).unwrap()
+        check_wasm_memories(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            &WasmValidationConfig::default(),
+        )
+        .unwrap()
     }
 
     #[test]
     fn test_check_wasm_memories_no_memory() {
         let wasm = wat2wasm("(module)").unwrap();
-        match check_wasm_memories(&deserialize_buffer(&wasm).unwrap()) {
+        match check_wasm_memories(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with("Wasm contract doesn't have a memory section"));
             }
@@ -307,7 +796,10 @@ mod test {
         ))
         .unwrap();
 
-        match check_wasm_memories(&deserialize_buffer(&wasm).unwrap()) {
+        match check_wasm_memories(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with("Wasm contract must contain exactly one memory"));
             }
@@ -328,7 +820,10 @@ mod test {
         ))
         .unwrap();
 
-        match check_wasm_memories(&deserialize_buffer(&wasm).unwrap()) {
+        match check_wasm_memories(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with("Wasm contract must contain exactly one memory"));
             }
@@ -337,13 +832,76 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_check_wasm_start_ok() {
+        let wasm = wat2wasm("(module (memory 1) (func $init))").unwrap();
+        check_wasm_start(&WasmModule::from_bytes(&wasm).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_check_wasm_start_with_start_function() {
+        let wasm = wat2wasm("(module (memory 1) (func $init) (start $init))").unwrap();
+        match check_wasm_start(&WasmModule::from_bytes(&wasm).unwrap()) {
+            Err(VmError::StaticValidationErr { msg, .. }) => {
+                assert!(msg.starts_with("Wasm contract must not have a start function"));
+            }
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with a start function"),
+        }
+    }
+
+    #[test]
+    fn test_check_stored_wasm_with_start_function() {
+        let wasm = wat2wasm(
+            r#"(module
+            (memory 1)
+            (func $init)
+            (start $init)
+            (func (export "interface_version_8"))
+            (func (export "allocate") (param i32) (result i32) i32.const 0)
+            (func (export "deallocate") (param i32))
+            (func (export "instantiate") (param i32 i32 i32) (result i32) i32.const 0)
+        )"#,
+        )
+        .unwrap();
+        let config = WasmValidationConfig::default();
+        check_wasm(&wasm, &default_features(), &config).unwrap_err();
+        // stored before the rule existed
+        check_stored_wasm(&wasm, &default_features(), &config).unwrap();
+    }
+
+    #[test]
+    fn test_check_stored_wasm_with_wrong_export_signature() {
+        let wasm = wat2wasm(
+            r#"(module
+            (memory 1)
+            (func (export "interface_version_8"))
+            (func (export "allocate") (param i32 i32) (result i32) get_local 0)
+            (func (export "deallocate") (param i32))
+            (func (export "instantiate") (param i32 i32 i32) (result i32) i32.const 0)
+        )"#,
+        )
+        .unwrap();
+        let config = WasmValidationConfig::default();
+        check_wasm(&wasm, &default_features(), &config).unwrap_err();
+        // stored before the rule existed
+        check_stored_wasm(&wasm, &default_features(), &config).unwrap();
+    }
+
     #[test]
     fn test_check_wasm_memories_initial_size() {
         let wasm_ok = wat2wasm("(module (memory 512))").unwrap();
-        check_wasm_memories(&deserialize_buffer(&wasm_ok).unwrap()).unwrap();
+        check_wasm_memories(
+            &WasmModule::from_bytes(&wasm_ok).unwrap(),
+            &WasmValidationConfig::default(),
+        )
+        .unwrap();
 
         let wasm_too_big = wat2wasm("(module (memory 513))").unwrap();
-        match check_wasm_memories(&deserialize_buffer(&wasm_too_big).unwrap()) {
+        match check_wasm_memories(
+            &WasmModule::from_bytes(&wasm_too_big).unwrap(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with("Wasm contract memory's minimum must not exceed 512 pages"));
             }
@@ -355,7 +913,10 @@ mod test {
     #[test]
     fn test_check_wasm_memories_maximum_size() {
         let wasm_max = wat2wasm("(module (memory 1 5))").unwrap();
-        match check_wasm_memories(&deserialize_buffer(&wasm_max).unwrap()) {
+        match check_wasm_memories(
+            &WasmModule::from_bytes(&wasm_max).unwrap(),
+            &WasmValidationConfig::default(),
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with("Wasm contract memory's maximum must be unset"));
             }
@@ -364,6 +925,298 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_check_wasm_memories_allowed_maximum_size() {
+        let config = WasmValidationConfig {
+            allow_memory_maximum: true,
+            ..WasmValidationConfig::default()
+        };
+
+        let wasm_max = wat2wasm("(module (memory 1 512))").unwrap();
+        check_wasm_memories(&WasmModule::from_bytes(&wasm_max).unwrap(), &config).unwrap();
+
+        let wasm_max_too_big = wat2wasm("(module (memory 1 513))").unwrap();
+        match check_wasm_memories(&WasmModule::from_bytes(&wasm_max_too_big).unwrap(), &config) {
+            Err(VmError::StaticValidationErr { msg, .. }) => {
+                assert!(msg.starts_with("Wasm contract memory's maximum must not exceed 512 pages"));
+            }
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with invalid api"),
+        }
+    }
+
+    #[test]
+    fn test_check_wasm_memories_custom_limit() {
+        let config = WasmValidationConfig::from_json(br#"{"memory_limit":16}"#).unwrap();
+        assert_eq!(config.required_exports_v1, REQUIRED_EXPORTS_V1);
+
+        let wasm_too_big = wat2wasm("(module (memory 17))").unwrap();
+        match check_wasm_memories(&WasmModule::from_bytes(&wasm_too_big).unwrap(), &config) {
+            Err(VmError::StaticValidationErr { msg, .. }) => {
+                assert!(msg.starts_with("Wasm contract memory's minimum must not exceed 16 pages"));
+            }
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with invalid api"),
+        }
+    }
+
+    #[test]
+    fn test_check_wasm_floats() {
+        let wasm = wat2wasm(
+            r#"(module
+            (func (param i32) (result i32) get_local 0)
+            (func (export "half") (param f64) (result f64)
+                get_local 0
+                f64.const 2
+                f64.div)
+            (func (result i32) f32.const 1 i32.trunc_s/f32)
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+
+        check_wasm_floats(&module, &WasmValidationConfig::default()).unwrap();
+
+        let config =
+            WasmValidationConfig::from_json(br#"{"float_instructions":"reject"}"#).unwrap();
+        match check_wasm_floats(&module, &config) {
+            Err(VmError::StaticValidationErr { msg, .. }) => assert_eq!(
+                msg,
+                "Wasm contract uses floating point instructions, which are not deterministic, in functions: #1 (export \"half\"), #2."
+            ),
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with floats"),
+        }
+
+        let wasm = wat2wasm("(module (func (param i32) (result i32) get_local 0))").unwrap();
+        check_wasm_floats(&WasmModule::from_bytes(&wasm).unwrap(), &config).unwrap();
+    }
+
+    #[test]
+    fn test_check_wasm_validation_failures() {
+        let failure = |wasm: &[u8], config: &WasmValidationConfig| {
+            check_wasm(wasm, &default_features(), config)
+                .unwrap_err()
+                .validation_failure()
+                .cloned()
+                .unwrap()
+        };
+        let config = WasmValidationConfig::default();
+
+        assert_eq!(failure(CONTRACT_0_7, &config).code(), "not_cosmwasm");
+        assert_eq!(failure(CORRUPTED, &config).code(), "deserialization");
+
+        let wasm = wat2wasm(
+            r#"(module
+            (memory 1)
+            (func (export "half") (param f64) (result f64)
+                get_local 0
+                f64.const 2
+                f64.div)
+        )"#,
+        )
+        .unwrap();
+        let config =
+            WasmValidationConfig::from_json(br#"{"float_instructions":"reject"}"#).unwrap();
+        assert_eq!(
+            failure(&wasm, &config),
+            ValidationFailure::FloatInstructions {
+                functions: vec![(0, Some("half".to_string()))],
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_wasm_size_limits() {
+        let wasm = wat2wasm(
+            r#"(module
+            (table 10 anyfunc)
+            (memory 1)
+            (func (param i32) (result i32) get_local 0)
+            (func (param i32) (result i32) get_local 0 i32.const 1 i32.add)
+            (data (i32.const 0) "abc")
+            (data (i32.const 8) "de")
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+
+        check_wasm_size_limits(&module, &WasmValidationConfig::default()).unwrap();
+        let config = WasmValidationConfig::from_json(
+            br#"{"max_functions":2,"max_function_body_size":7,"max_table_size":10,"max_data_segments":2,"max_data_size":5}"#,
+        )
+        .unwrap();
+        check_wasm_size_limits(&module, &config).unwrap();
+
+        let cases = [
+            (
+                WasmValidationConfig {
+                    max_functions: Some(1),
+                    ..config.clone()
+                },
+                "Wasm contract exceeds limit max_functions: it defines 2 functions, the limit is 1.",
+            ),
+            (
+                WasmValidationConfig {
+                    max_function_body_size: Some(6),
+                    ..config.clone()
+                },
+                "Wasm contract exceeds limit max_function_body_size: the body of its function #1 has 7 bytes, the limit is 6.",
+            ),
+            (
+                WasmValidationConfig {
+                    max_table_size: Some(9),
+                    ..config.clone()
+                },
+                "Wasm contract exceeds limit max_table_size: it has a table of 10 elements, the limit is 9.",
+            ),
+            (
+                WasmValidationConfig {
+                    max_data_segments: Some(1),
+                    ..config.clone()
+                },
+                "Wasm contract exceeds limit max_data_segments: it has 2 data segments, the limit is 1.",
+            ),
+            (
+                WasmValidationConfig {
+                    max_data_size: Some(4),
+                    ..config.clone()
+                },
+                "Wasm contract exceeds limit max_data_size: its data segments have 5 bytes, the limit is 4.",
+            ),
+        ];
+        for (config, expected) in cases.iter() {
+            match check_wasm_size_limits(&module, config) {
+                Err(VmError::StaticValidationErr { msg, .. }) => assert_eq!(&msg, expected),
+                Err(e) => panic!("Unexpected error {:?}", e),
+                Ok(_) => panic!("Didn't reject wasm exceeding a limit"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_wasm_code_size() {
+        let wasm = wat2wasm("(module)").unwrap();
+        check_wasm_code_size(&wasm, &WasmValidationConfig::default()).unwrap();
+
+        let config = WasmValidationConfig {
+            max_code_size: Some(wasm.len()),
+            ..WasmValidationConfig::default()
+        };
+        check_wasm_code_size(&wasm, &config).unwrap();
+
+        // checked before deserializing, so oversized garbage gets the same error
+        let mut oversized = wasm.clone();
+        oversized.push(0xff);
+        match check_wasm(&oversized, &default_features(), &config) {
+            Err(VmError::StaticValidationErr { msg, .. }) => assert_eq!(
+                msg,
+                format!(
+                    "Wasm contract exceeds limit max_code_size: it has {} bytes, the limit is {}.",
+                    wasm.len() + 1,
+                    wasm.len()
+                )
+            ),
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject oversized wasm"),
+        }
+    }
+
+    #[test]
+    fn test_analyze_wasm() {
+        let wasm = wat2wasm(
+            r#"(module
+            (memory 3 5)
+            (func (export "interface_version_8"))
+            (func (export "allocate") (param i32) (result i32) get_local 0)
+            (func (export "deallocate") (param i32))
+            (func (export "instantiate") (param i32 i32 i32) (result i32) i32.const 0)
+            (func (export "requires_staking"))
+            (func (export "migrate") (param i32 i32 i32) (result i32) i32.const 0)
+            (func (export "reply") (param i32 i32) (result i32) i32.const 0)
+            (global (export "sudo") i32 (i32.const 1))
+        )"#,
+        )
+        .unwrap();
+        assert_eq!(
+            analyze_wasm(&wasm).unwrap(),
+            ContractAnalysis {
+                interface_version: Some("v1"),
+                required_features: HashSet::from_iter(vec!["staking".to_string()]),
+                has_ibc_entry_points: false,
+                has_migrate: true,
+                has_reply: true,
+                // not a function
+                has_sudo: false,
+                memory_initial_pages: Some(3),
+                memory_maximum_pages: Some(5),
+            }
+        );
+
+        let analysis = analyze_wasm(&wat2wasm("(module)").unwrap()).unwrap();
+        assert_eq!(analysis.interface_version, None);
+        assert_eq!(analysis.memory_initial_pages, None);
+
+        assert!(analyze_wasm(b"\0asm").is_err());
+    }
+
+    #[test]
+    fn test_has_ibc_entry_points() {
+        let wasm = wat2wasm(
+            r#"(module
+            (func (export "ibc_channel_open") (param i32 i32) (result i32) i32.const 0)
+            (func (export "ibc_packet_receive") (param i32 i32) (result i32) i32.const 0)
+        )"#,
+        )
+        .unwrap();
+        let capabilities = has_ibc_entry_points(&wasm).unwrap();
+        assert_eq!(
+            capabilities.present,
+            ["ibc_channel_open", "ibc_packet_receive"]
+        );
+        assert_eq!(
+            capabilities.missing,
+            [
+                "ibc_channel_connect",
+                "ibc_channel_close",
+                "ibc_packet_ack",
+                "ibc_packet_timeout"
+            ]
+        );
+        assert!(!capabilities.is_enabled());
+        assert!(capabilities.is_partial());
+
+        let capabilities = has_ibc_entry_points(CONTRACT).unwrap();
+        assert!(capabilities.present.is_empty());
+        assert!(!capabilities.is_enabled());
+        assert!(!capabilities.is_partial());
+
+        let exports: String = REQUIRED_IBC_EXPORTS
+            .iter()
+            .map(|name| format!(r#"(func (export "{}"))"#, name))
+            .collect();
+        let wasm = wat2wasm(format!("(module {})", exports)).unwrap();
+        let capabilities = has_ibc_entry_points(&wasm).unwrap();
+        assert_eq!(capabilities.present, REQUIRED_IBC_EXPORTS);
+        assert!(capabilities.is_enabled());
+        assert!(!capabilities.is_partial());
+
+        assert!(has_ibc_entry_points(CORRUPTED).is_err());
+    }
+
+    #[test]
+    fn test_validation_config_from_json() {
+        assert_eq!(
+            WasmValidationConfig::from_json(b"").unwrap(),
+            WasmValidationConfig::default()
+        );
+        match WasmValidationConfig::from_json(b"{") {
+            Err(VmError::ParseErr { .. }) => {}
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject invalid json"),
+        }
+    }
+
     #[test]
     fn test_check_wasm_exports() {
         // this is invalid, as it doesn't contain all required exports
@@ -377,7 +1230,7 @@ mod test {
         "#;
         let wasm_missing_exports = wat2wasm(WAT_MISSING_EXPORTS).unwrap();
 
-        let module = deserialize_buffer(&wasm_missing_exports).unwrap();
+        let module = WasmModule::from_bytes(&wasm_missing_exports).unwrap();
         match check_wasm_exports(&module, REQUIRED_EXPORTS_V010) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with(
@@ -391,7 +1244,7 @@ mod test {
 
     #[test]
     fn test_check_wasm_exports_of_old_contract() {
-        let module = deserialize_buffer(CONTRACT_0_7).unwrap();
+        let module = WasmModule::from_bytes(CONTRACT_0_7).unwrap();
         match check_wasm_exports(&module, REQUIRED_EXPORTS_V010) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(msg.starts_with(
@@ -415,13 +1268,40 @@ mod test {
         )"#,
         )
         .unwrap();
-        check_wasm_imports(&deserialize_buffer(&wasm).unwrap(), SUPPORTED_IMPORTS_V010).unwrap();
+        check_wasm_imports(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            SUPPORTED_IMPORTS_V010,
+            NO_EXTRA_IMPORTS,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_check_wasm_imports_extra() {
+        let wasm = wat2wasm(
+            r#"(module
+            (import "env" "db_read" (func (param i32) (result i32)))
+            (import "env" "oracle_price" (func (param i32) (result i32)))
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        match check_wasm_imports(&module, SUPPORTED_IMPORTS_V1, NO_EXTRA_IMPORTS) {
+            Err(VmError::StaticValidationErr { msg, .. }) => {
+                assert!(msg.starts_with(
+                    "Wasm contract requires unsupported import: \"env.oracle_price\""
+                ));
+            }
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with unknown import"),
+        }
+        check_wasm_imports(&module, SUPPORTED_IMPORTS_V1, &["env.oracle_price"]).unwrap();
     }
 
     #[test]
     fn test_check_wasm_imports_of_old_contract() {
-        let module = deserialize_buffer(CONTRACT_0_7).unwrap();
-        match check_wasm_imports(&module, SUPPORTED_IMPORTS_V010) {
+        let module = WasmModule::from_bytes(CONTRACT_0_7).unwrap();
+        match check_wasm_imports(&module, SUPPORTED_IMPORTS_V010, NO_EXTRA_IMPORTS) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(
                     msg.starts_with("Wasm contract requires unsupported import: \"env.db_read\"")
@@ -435,7 +1315,11 @@ mod test {
     #[test]
     fn test_check_wasm_imports_wrong_type() {
         let wasm = wat2wasm(r#"(module (import "env" "db_read" (memory 1 1)))"#).unwrap();
-        match check_wasm_imports(&deserialize_buffer(&wasm).unwrap(), SUPPORTED_IMPORTS_V010) {
+        match check_wasm_imports(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            SUPPORTED_IMPORTS_V010,
+            NO_EXTRA_IMPORTS,
+        ) {
             Err(VmError::StaticValidationErr { msg, .. }) => {
                 assert!(
                     msg.starts_with("Wasm contract requires non-function import: \"env.db_read\"")
@@ -461,7 +1345,7 @@ mod test {
         )"#,
         )
         .unwrap();
-        let module = deserialize_buffer(&wasm).unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
         let supported = HashSet::from_iter(
             [
                 "water".to_string(),
@@ -475,6 +1359,176 @@ mod test {
         check_wasm_features(&module, &supported).unwrap();
     }
 
+    #[test]
+    fn check_wasm_features_explains_cosmwasm_1_x_features() {
+        let wasm = wat2wasm(
+            r#"(module
+            (func (export "requires_cosmwasm_1_2"))
+            (func (export "requires_staking"))
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        match check_wasm_features(&module, &default_features()).unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => assert_eq!(
+                msg,
+                "Wasm contract requires unsupported features: {\"cosmwasm_1_2\"} The cosmwasm_1_* features of cosmwasm-std enable messages and queries this chain doesn't support, build the contract without them."
+            ),
+            _ => panic!("Got unexpected error"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_exports() {
+        let wasm = wat2wasm(
+            r#"(module
+            (type (func))
+            (func (type 0) nop)
+            (memory 3)
+            (export "memory" (memory 0))
+            (export "interface_version_8" (func 0))
+            (export "allocate" (func 0))
+            (export "deallocate" (func 0))
+            (export "instantiate" (func 0))
+            (export "instantate" (func 0))
+            (export "execute" (func 0))
+            (export "requires_staking" (func 0))
+            (export "ibc_channel_open" (func 0))
+            (export "miggrate" (func 0))
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        assert_eq!(
+            unknown_exports(&module, &WasmValidationConfig::default()),
+            ["instantate", "miggrate"]
+        );
+    }
+
+    #[test]
+    fn test_check_wasm_mixed_imports() {
+        let wasm = wat2wasm(
+            r#"(module
+            (import "env" "canonicalize_address" (func (param i32 i32) (result i32)))
+            (import "env" "db_read" (func (param i32) (result i32)))
+            (import "env" "addr_validate" (func (param i32) (result i32)))
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        match check_wasm_mixed_imports(&module, &WasmValidationConfig::default()).unwrap_err() {
+            VmError::MixedInterfaceVersion {
+                v010_imports,
+                v1_imports,
+                ..
+            } => {
+                assert_eq!(v010_imports, ["env.canonicalize_address"]);
+                assert_eq!(v1_imports, ["env.addr_validate"]);
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        // db_read is provided to both versions
+        let wasm = wat2wasm(
+            r#"(module
+            (import "env" "db_read" (func (param i32) (result i32)))
+            (import "env" "addr_validate" (func (param i32) (result i32)))
+        )"#,
+        )
+        .unwrap();
+        check_wasm_mixed_imports(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            &WasmValidationConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_check_wasm_interface_version() {
+        let wasm = wat2wasm(
+            r#"(module
+            (memory 1)
+            (func (export "interface_version_9"))
+            (func (export "allocate") (param i32) (result i32) get_local 0)
+            (func (export "deallocate") (param i32))
+            (func (export "instantiate") (param i32 i32 i32) (result i32) i32.const 0)
+        )"#,
+        )
+        .unwrap();
+        match check_wasm(&wasm, &default_features(), &WasmValidationConfig::default()) {
+            Err(VmError::StaticValidationErr { msg, .. }) => assert_eq!(
+                msg,
+                "Wasm contract exports unsupported interface version \"interface_version_9\". Contracts built with cosmwasm-std 1.x export \"interface_version_8\"."
+            ),
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with an unsupported interface version"),
+        }
+
+        let wasm = wat2wasm(r#"(module (func (export "interface_version_8")))"#).unwrap();
+        check_wasm_interface_version(
+            &WasmModule::from_bytes(&wasm).unwrap(),
+            &WasmValidationConfig::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_check_wasm_export_signatures() {
+        let wasm = wat2wasm(
+            r#"(module
+            (memory 1)
+            (func (export "interface_version_8"))
+            (func (export "allocate") (param i32 i32) (result i32) get_local 0)
+            (func (export "deallocate") (param i32))
+            (func (export "instantiate") (param i32 i32 i32) (result i32) i32.const 0)
+        )"#,
+        )
+        .unwrap();
+        match check_wasm(&wasm, &default_features(), &WasmValidationConfig::default()) {
+            Err(VmError::StaticValidationErr { msg, failure, .. }) => {
+                assert_eq!(
+                    msg,
+                    "Wasm contract export \"allocate\" has type [i32, i32] -> [i32], the VM calls it as [i32] -> [i32]."
+                );
+                assert_eq!(failure.code(), "wrong_export_signature");
+            }
+            Err(e) => panic!("Unexpected error {:?}", e),
+            Ok(_) => panic!("Didn't reject wasm with a wrong allocate signature"),
+        }
+
+        // v0.10 queries take one argument less than v1 ones
+        let wasm = wat2wasm(
+            r#"(module
+            (func (export "query") (param i32) (result i32) i32.const 0)
+            (func (export "reply") (param i32) (result i32) i32.const 0)
+            (global (export "execute") i32 (i32.const 1))
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        check_wasm_export_signatures(&module, false).unwrap();
+        match check_wasm_export_signatures(&module, true).unwrap_err() {
+            VmError::StaticValidationErr { failure, .. } => assert_eq!(
+                failure,
+                ValidationFailure::WrongExportSignature {
+                    export: "query".to_string(),
+                    expected: "[i32, i32] -> [i32]".to_string(),
+                    actual: Some("[i32] -> [i32]".to_string()),
+                }
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let wasm = wat2wasm(r#"(module (global (export "execute") i32 (i32.const 1)))"#).unwrap();
+        match check_wasm_export_signatures(&WasmModule::from_bytes(&wasm).unwrap(), true) {
+            Err(VmError::StaticValidationErr { msg, .. }) => assert_eq!(
+                msg,
+                "Wasm contract export \"execute\" is not a function, the VM calls it as [i32, i32, i32] -> [i32]."
+            ),
+            r => panic!("Unexpected result: {:?}", r),
+        }
+    }
+
     #[test]
     fn check_wasm_features_fails_for_missing() {
         let wasm = wat2wasm(
@@ -490,7 +1544,7 @@ mod test {
         )"#,
         )
         .unwrap();
-        let module = deserialize_buffer(&wasm).unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
 
         // Support set 1
         let supported = HashSet::from_iter(
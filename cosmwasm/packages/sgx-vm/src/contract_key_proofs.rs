@@ -0,0 +1,96 @@
+use sgx_types::*;
+
+use log::debug;
+
+use crate::checksum::Checksum;
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_prove_contract_key(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        contract_address: *const u8,
+        contract_address_len: usize,
+        code_hash: *const u8,
+        og_contract_key: *const u8,
+        current_contract_key: *const u8,
+        current_contract_key_len: usize,
+        node_public_key: *mut u8,
+        encryption_public_key: *mut u8,
+        signer_public_key: *mut u8,
+        signature: *mut u8,
+    ) -> sgx_status_t;
+}
+
+/// What the enclave signs to prove the keys of a contract
+pub struct EnclaveContractKeyProof {
+    /// The registration key of this node, whose attestation certificate commits to the signer
+    pub node_public_key: [u8; 32],
+    /// The x25519 key other contracts encrypt data to the contract with
+    pub encryption_public_key: [u8; 32],
+    pub signer_public_key: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// Asks the enclave to validate the keys of a contract and sign a proof of its current contract
+/// key, which clients can verify without trusting this node.
+///
+/// `current_contract_key` is the current key of a migrated contract and its migration proof.
+pub fn untrusted_prove_contract_key(
+    contract_address: &[u8],
+    code_hash: &Checksum,
+    og_contract_key: &[u8; 64],
+    current_contract_key: Option<(&[u8; 64], &[u8; 32])>,
+) -> SgxResult<EnclaveContractKeyProof> {
+    let current = match current_contract_key {
+        Some((key, proof)) => [&key[..], &proof[..]].concat(),
+        None => vec![],
+    };
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut proof = EnclaveContractKeyProof {
+        node_public_key: [0; 32],
+        encryption_public_key: [0; 32],
+        signer_public_key: [0; 32],
+        signature: [0; 64],
+    };
+    let status = unsafe {
+        ecall_prove_contract_key(
+            eid,
+            &mut retval,
+            contract_address.as_ptr(),
+            contract_address.len(),
+            code_hash.as_slice().as_ptr(),
+            og_contract_key.as_ptr(),
+            current.as_ptr(),
+            current.len(),
+            proof.node_public_key.as_mut_ptr(),
+            proof.encryption_public_key.as_mut_ptr(),
+            proof.signer_public_key.as_mut_ptr(),
+            proof.signature.as_mut_ptr(),
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!(
+        "Enclave proved the contract key of {}",
+        hex::encode(contract_address)
+    );
+
+    Ok(proof)
+}
@@ -0,0 +1,78 @@
+//! The signature a developer can upload with their contract, for users to check who built the code
+//! they run.
+//!
+//! It is a secp256k1 signature, as made by the keys of a Cosmos keyring, over the Wasm as uploaded,
+//! which is the Wasm stored and hashed into the code hash. Checking it is part of checking the Wasm
+//! when it is stored.
+
+use k256::ecdsa::signature::Verifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+
+use crate::errors::{ValidationFailure, VmError, VmResult};
+
+/// The compressed public key of a developer and their signature over a Wasm
+pub struct DeveloperSignature<'a> {
+    pub public_key: &'a [u8],
+    pub signature: &'a [u8],
+}
+
+/// Fails with `ValidationFailure::InvalidDeveloperSignature` unless `developer` signed `wasm_code`.
+///
+/// Like the Cosmos SDK, only accepts signatures normalized to a low S.
+pub fn check_developer_signature(wasm_code: &[u8], developer: &DeveloperSignature) -> VmResult<()> {
+    let verified = match (
+        VerifyingKey::from_sec1_bytes(developer.public_key),
+        Signature::from_slice(developer.signature),
+    ) {
+        (Ok(key), Ok(signature)) => key.verify(wasm_code, &signature).is_ok(),
+        _ => false,
+    };
+    if !verified {
+        return Err(VmError::static_validation_failure(
+            ValidationFailure::InvalidDeveloperSignature {
+                public_key: hex::encode(developer.public_key),
+            },
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use k256::ecdsa::signature::Signer;
+    use k256::ecdsa::SigningKey;
+
+    #[test]
+    fn check_developer_signature_works() {
+        let wasm = b"\0asm signed";
+        let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let signature: Signature = key.sign(wasm);
+        let public_key = key.verifying_key().to_encoded_point(true);
+        let developer = DeveloperSignature {
+            public_key: public_key.as_bytes(),
+            signature: &signature.to_bytes(),
+        };
+        check_developer_signature(wasm, &developer).unwrap();
+
+        match check_developer_signature(b"\0asm other", &developer).unwrap_err() {
+            VmError::StaticValidationErr { failure, .. } => {
+                assert_eq!(failure.code(), "invalid_developer_signature")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let other_key = SigningKey::from_slice(&[8u8; 32]).unwrap();
+        let other_public_key = other_key.verifying_key().to_encoded_point(true);
+        let signed_by_other = DeveloperSignature {
+            public_key: other_public_key.as_bytes(),
+            ..developer
+        };
+        check_developer_signature(wasm, &signed_by_other).unwrap_err();
+        let malformed = DeveloperSignature {
+            public_key: &public_key.as_bytes()[1..],
+            ..developer
+        };
+        check_developer_signature(wasm, &malformed).unwrap_err();
+    }
+}
@@ -1,23 +1,35 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
 use std::ops::Deref;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{env, path::Path};
 
 use sgx_types::{
-    sgx_attributes_t, sgx_launch_token_t, sgx_misc_attribute_t, sgx_status_t, SgxResult,
+    sgx_attributes_t, sgx_enclave_id_t, sgx_launch_token_t, sgx_misc_attribute_t, sgx_status_t,
+    SgxResult,
 };
 use sgx_urts::SgxEnclave;
 
 use lazy_static::lazy_static;
 use log::*;
-use parking_lot::{Condvar, Mutex};
+use parking_lot::{Condvar, Mutex, RwLock};
+
+use enclave_ffi_types::ENCLAVE_ABI_VERSION;
+
+use crate::errors::{VmError, VmResult};
+use crate::upgrade_schedule::{upgrade_schedule, Behavior};
+
+extern "C" {
+    pub fn ecall_abi_version(eid: sgx_enclave_id_t, retval: *mut u32) -> sgx_status_t;
+}
 
 #[cfg(feature = "production")]
-const ENCLAVE_DEBUG: i32 = 0;
+pub(crate) const ENCLAVE_DEBUG: i32 = 0;
 
 #[cfg(not(feature = "production"))]
-const ENCLAVE_DEBUG: i32 = 1;
+pub(crate) const ENCLAVE_DEBUG: i32 = 1;
 
-fn init_enclave(enclave_file: &str) -> SgxResult<SgxEnclave> {
+fn init_enclave(enclave_file: &str) -> SgxResult<(SgxEnclave, sgx_misc_attribute_t)> {
     let mut launch_token: sgx_launch_token_t = [0; 1024];
     let mut launch_token_updated: i32 = 0;
     // call sgx_create_enclave to initialize an enclave instance
@@ -56,67 +68,320 @@ fn init_enclave(enclave_file: &str) -> SgxResult<SgxEnclave> {
         sgx_status_t::SGX_ERROR_INVALID_ENCLAVE
     })?;
 
-    SgxEnclave::create(
+    let enclave = SgxEnclave::create(
         enclave_file_path,
         debug,
         &mut launch_token,
         &mut launch_token_updated,
         &mut misc_attr,
-    )
+    )?;
+
+    Ok((enclave, misc_attr))
 }
 
 static ENCLAVE_FILE: &str = "librust_cosmwasm_enclave.signed.so";
 /// This const determines how many seconds we wait when trying to get access to the enclave
 /// before giving up.
 const ENCLAVE_LOCK_TIMEOUT: u64 = 6 * 5;
-const TCS_NUM: u8 = 8;
+/// The amount of TCS the enclave is signed with (`TCSNum` in Enclave.config.xml).
+/// Keep this in sync with the enclave configuration.
+pub const TCS_NUM: u8 = 8;
 lazy_static! {
-    pub static ref ENCLAVE_DOORBELL: EnclaveDoorbell = EnclaveDoorbell::new(ENCLAVE_FILE, TCS_NUM);
+    pub static ref ENCLAVE_DOORBELL: EnclaveDoorbell =
+        EnclaveDoorbell::new(ENCLAVE_FILE, ThreadPolicy::from_env());
+    static ref LEGACY_ENCLAVE_DOORBELL: RwLock<Option<&'static EnclaveDoorbell>> =
+        RwLock::new(None);
+}
+
+thread_local! {
+    /// The enclave this thread is currently inside of, so ocalls that need to call back into the
+    /// enclave (such as allocating buffers) reach the one that made them.
+    static CURRENT_DOORBELL: Cell<Option<&'static EnclaveDoorbell>> = Cell::new(None);
+}
+
+/// Loads the previous version of the enclave, from `enclave_file`, next to the current one during
+/// a coordinated upgrade. This must be done before the enclaves are configured.
+///
+/// Blocks before `Behavior::CurrentEnclave` activates in the `UpgradeSchedule` are executed by
+/// this enclave, so a node replaying them gets the results they originally had, while newer
+/// blocks use the current enclave.
+///
+/// The untrusted side of every ecall and ocall is shared between the enclaves, so the legacy
+/// enclave is refused unless it was built with the same `ENCLAVE_ABI_VERSION` as the node.
+pub fn load_legacy_enclave(enclave_file: &str) -> VmResult<()> {
+    let mut legacy_doorbell = LEGACY_ENCLAVE_DOORBELL.write();
+    if legacy_doorbell.is_some() {
+        return Err(VmError::generic_err("a legacy enclave is already loaded"));
+    }
+
+    info!("Loading legacy enclave {}", enclave_file);
+    // Never unloaded, like the current enclave
+    let doorbell: &'static EnclaveDoorbell = Box::leak(Box::new(EnclaveDoorbell::new(
+        enclave_file,
+        ThreadPolicy::from_env(),
+    )));
+
+    let abi_version = abi_version(doorbell).map_err(|status| {
+        VmError::generic_err(format!(
+            "could not load the legacy enclave {}: {}",
+            enclave_file, status
+        ))
+    })?;
+    check_abi_version(abi_version).map_err(|err| {
+        VmError::generic_err(format!(
+            "refusing the legacy enclave {}: {}",
+            enclave_file, err
+        ))
+    })?;
+
+    *legacy_doorbell = Some(doorbell);
+    Ok(())
+}
+
+fn abi_version(doorbell: &'static EnclaveDoorbell) -> SgxResult<u32> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = doorbell
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let mut abi_version = 0_u32;
+    // An enclave built before `ecall_abi_version` existed fails this with
+    // SGX_ERROR_INVALID_FUNCTION
+    let status = unsafe { ecall_abi_version(enclave.geteid(), &mut abi_version) };
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+    Ok(abi_version)
+}
+
+fn check_abi_version(abi_version: u32) -> Result<(), String> {
+    if abi_version != ENCLAVE_ABI_VERSION {
+        return Err(format!(
+            "it was built with ABI version {}, but this node uses version {}",
+            abi_version, ENCLAVE_ABI_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the enclave that executes calls made at `block_height`.
+///
+/// This is the current enclave unless a legacy enclave is loaded and the block precedes the
+/// activation of `Behavior::CurrentEnclave`. Calls that don't belong to a block go to
+/// `ENCLAVE_DOORBELL` directly.
+pub fn doorbell_for_height(block_height: u64) -> &'static EnclaveDoorbell {
+    match *LEGACY_ENCLAVE_DOORBELL.read() {
+        Some(legacy) if !upgrade_schedule().is_active(Behavior::CurrentEnclave, block_height) => {
+            trace!(
+                "Routing call at height {} to the legacy enclave",
+                block_height
+            );
+            legacy
+        }
+        _ => &*ENCLAVE_DOORBELL,
+    }
+}
+
+/// Returns every loaded enclave, the current one first. Node-wide settings apply to all of them.
+pub fn all_doorbells() -> Vec<&'static EnclaveDoorbell> {
+    let mut doorbells = vec![&*ENCLAVE_DOORBELL];
+    if let Some(legacy) = *LEGACY_ENCLAVE_DOORBELL.read() {
+        doorbells.push(legacy);
+    }
+    doorbells
+}
+
+/// Returns the enclave the current thread is inside of, or the current enclave if it isn't
+/// inside of any.
+pub fn current_doorbell() -> &'static EnclaveDoorbell {
+    CURRENT_DOORBELL
+        .with(|current| current.get())
+        .unwrap_or(&*ENCLAVE_DOORBELL)
+}
+
+/// Describes how host threads are mapped onto the enclave's thread control structures (TCS).
+///
+/// The enclave is signed with `TCSNum` = `TCS_NUM` and `TCSPolicy` = 1 (unbound). A TCS is
+/// therefore bound to a host thread only for the duration of an ecall, and nested ecalls made
+/// from inside an ocall run on the TCS of the thread that made the ocall. Only top-level calls
+/// need a TCS of their own, and at most `max_threads` of them may be inside the enclave at once.
+///
+/// Callers beyond that are queued in arrival order for up to `queue_timeout`, after which they
+/// get an error rather than the SDK failing with `SGX_ERROR_OUT_OF_TCS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThreadPolicy {
+    max_threads: u8,
+    queue_timeout: Duration,
+}
+
+impl ThreadPolicy {
+    /// `max_threads` is clamped to `1..=TCS_NUM`, as the enclave can never host more
+    /// threads than it was signed with.
+    pub fn new(max_threads: u8, queue_timeout: Duration) -> Self {
+        let clamped = max_threads.clamp(1, TCS_NUM);
+        if clamped != max_threads {
+            warn!(
+                "Requested {} enclave threads, but the enclave supports 1 to {}. Using {}",
+                max_threads, TCS_NUM, clamped
+            );
+        }
+        Self {
+            max_threads: clamped,
+            queue_timeout,
+        }
+    }
+
+    /// Reads the amount of threads from the SCRT_ENCLAVE_THREADS environment variable, so
+    /// operators can reserve fewer TCS for contract calls than the enclave provides.
+    pub fn from_env() -> Self {
+        let max_threads = match env::var("SCRT_ENCLAVE_THREADS") {
+            Ok(value) => value.parse::<u8>().unwrap_or_else(|_| {
+                warn!(
+                    "Could not parse SCRT_ENCLAVE_THREADS={:?}, using {} threads",
+                    value, TCS_NUM
+                );
+                TCS_NUM
+            }),
+            Err(_) => TCS_NUM,
+        };
+        Self::new(max_threads, Duration::from_secs(ENCLAVE_LOCK_TIMEOUT))
+    }
+
+    pub fn max_threads(&self) -> u8 {
+        self.max_threads
+    }
+
+    pub fn queue_timeout(&self) -> Duration {
+        self.queue_timeout
+    }
+}
+
+impl Default for ThreadPolicy {
+    fn default() -> Self {
+        Self::new(TCS_NUM, Duration::from_secs(ENCLAVE_LOCK_TIMEOUT))
+    }
+}
+
+/// A FIFO counting semaphore over the enclave threads allowed by a `ThreadPolicy`.
+struct ThreadGate {
+    policy: ThreadPolicy,
+    condvar: Condvar,
+    state: Mutex<GateState>,
+}
+
+struct GateState {
+    /// Amount of tasks that may still enter the enclave.
+    available: u8,
+    /// Tickets of the tasks waiting for a thread, in arrival order.
+    queue: VecDeque<u64>,
+    next_ticket: u64,
+}
+
+impl ThreadGate {
+    fn new(policy: ThreadPolicy) -> Self {
+        Self {
+            policy,
+            condvar: Condvar::new(),
+            state: Mutex::new(GateState {
+                available: policy.max_threads,
+                queue: VecDeque::new(),
+                next_ticket: 0,
+            }),
+        }
+    }
+
+    /// Waits in line for a thread. Returns false if none became available within `timeout`.
+    fn enter(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock();
+
+        let ticket = state.next_ticket;
+        state.next_ticket = state.next_ticket.wrapping_add(1);
+        state.queue.push_back(ticket);
+
+        while !(state.available > 0 && state.queue.front() == Some(&ticket)) {
+            if self.condvar.wait_until(&mut state, deadline).timed_out()
+                && !(state.available > 0 && state.queue.front() == Some(&ticket))
+            {
+                state.queue.retain(|queued| *queued != ticket);
+                drop(state);
+                // We may have been first in line, in which case the next one can go now
+                self.condvar.notify_all();
+                return false;
+            }
+        }
+
+        state.queue.pop_front();
+        state.available -= 1;
+        let others_can_enter = state.available > 0 && !state.queue.is_empty();
+        drop(state);
+        if others_can_enter {
+            self.condvar.notify_all();
+        }
+        true
+    }
+
+    fn leave(&self) {
+        let mut state = self.state.lock();
+        state.available += 1;
+        drop(state);
+        // Waiters check whether they are first in line, so all of them need to wake up
+        self.condvar.notify_all();
+    }
 }
 
 /// This struct manages the access to the enclave.
 ///
 /// It effectively works as a custom, non-generic Semaphore. We need to make sure that the enclave
-/// is not entered more than TCS_NUM times at once, except that entering it recursively from the
-/// same thread is always permitted.
+/// is not entered by more threads than its `ThreadPolicy` allows, except that entering it
+/// recursively from the same thread is always permitted.
 /// `EnclaveDoorbell` and `EnclaveAccessToken` help control this behavior.
 /// The depth of calls, which determines whether or not they are recursive, is managed by the
 /// `query_depth` parameter that is threaded through the context of each call.
 pub struct EnclaveDoorbell {
     enclave: SgxResult<SgxEnclave>,
-    condvar: Condvar,
-    /// Amount of tasks allowed to use the enclave at the same time.
-    count: Mutex<u8>,
+    /// The SECS attributes and misc select the enclave was actually launched with.
+    launch_attributes: SgxResult<sgx_misc_attribute_t>,
+    gate: ThreadGate,
 }
 
 impl EnclaveDoorbell {
-    fn new(enclave_file: &str, count: u8) -> Self {
-        info!("Setting up enclave doorbell for up to {} threads", count);
+    fn new(enclave_file: &str, policy: ThreadPolicy) -> Self {
+        info!(
+            "Setting up enclave doorbell for up to {} threads",
+            policy.max_threads
+        );
+        let (enclave, launch_attributes) = match init_enclave(enclave_file) {
+            Ok((enclave, misc_attr)) => (Ok(enclave), Ok(misc_attr)),
+            Err(status) => (Err(status), Err(status)),
+        };
         Self {
-            enclave: init_enclave(enclave_file),
-            condvar: Condvar::new(),
-            count: Mutex::new(count),
+            enclave,
+            launch_attributes,
+            gate: ThreadGate::new(policy),
         }
     }
 
     fn wait_for(&'static self, duration: Duration, query_depth: u32) -> Option<EnclaveAccessToken> {
-        if query_depth == 1 {
-            let mut count = self.count.lock();
-            if *count == 0 {
-                // try to wait for other tasks to complete
-                let wait = self.condvar.wait_for(&mut count, duration);
-                // double check that the count is nonzero, so there's an available slot in the enclave.
-                if wait.timed_out() || *count == 0 {
-                    return None;
-                }
-            }
-            *count -= 1;
+        if query_depth == 1 && !self.gate.enter(duration) {
+            return None;
         }
         Some(EnclaveAccessToken::new(self, query_depth))
     }
 
     pub fn get_access(&'static self, query_depth: u32) -> Option<EnclaveAccessToken> {
-        self.wait_for(Duration::from_secs(ENCLAVE_LOCK_TIMEOUT), query_depth)
+        self.wait_for(self.gate.policy.queue_timeout, query_depth)
+    }
+
+    pub fn policy(&self) -> ThreadPolicy {
+        self.gate.policy
+    }
+
+    /// Returns the attributes reported by the SDK when the enclave was created.
+    pub fn launch_attributes(&self) -> SgxResult<sgx_misc_attribute_t> {
+        self.launch_attributes
     }
 }
 
@@ -125,15 +390,19 @@ pub struct EnclaveAccessToken {
     doorbell: &'static EnclaveDoorbell,
     enclave: SgxResult<&'static SgxEnclave>,
     query_depth: u32,
+    /// The enclave the thread was inside of before this token was taken
+    previous_doorbell: Option<&'static EnclaveDoorbell>,
 }
 
 impl EnclaveAccessToken {
     fn new(doorbell: &'static EnclaveDoorbell, query_depth: u32) -> Self {
         let enclave = doorbell.enclave.as_ref().map_err(|status| *status);
+        let previous_doorbell = CURRENT_DOORBELL.with(|current| current.replace(Some(doorbell)));
         Self {
             doorbell,
             enclave,
             query_depth,
+            previous_doorbell,
         }
     }
 }
@@ -148,11 +417,81 @@ impl Deref for EnclaveAccessToken {
 
 impl Drop for EnclaveAccessToken {
     fn drop(&mut self) {
+        CURRENT_DOORBELL.with(|current| current.set(self.previous_doorbell));
         if self.query_depth == 1 {
-            let mut count = self.doorbell.count.lock();
-            *count += 1;
-            drop(count);
-            self.doorbell.condvar.notify_one();
+            self.doorbell.gate.leave();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::mpsc;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn thread_policy_clamps_max_threads() {
+        let timeout = Duration::from_secs(1);
+        assert_eq!(ThreadPolicy::new(0, timeout).max_threads(), 1);
+        assert_eq!(ThreadPolicy::new(4, timeout).max_threads(), 4);
+        assert_eq!(ThreadPolicy::new(200, timeout).max_threads(), TCS_NUM);
+        assert_eq!(ThreadPolicy::default().max_threads(), TCS_NUM);
+    }
+
+    #[test]
+    fn legacy_enclave_needs_the_same_abi_version() {
+        assert!(check_abi_version(ENCLAVE_ABI_VERSION).is_ok());
+        assert!(check_abi_version(ENCLAVE_ABI_VERSION + 1).is_err());
+        assert!(check_abi_version(0).is_err());
+    }
+
+    #[test]
+    fn thread_gate_times_out_when_full() {
+        let gate = ThreadGate::new(ThreadPolicy::new(1, Duration::from_secs(1)));
+        assert!(gate.enter(Duration::from_millis(10)));
+        assert!(!gate.enter(Duration::from_millis(10)));
+
+        // a timed out caller must not keep its place in line
+        assert!(gate.state.lock().queue.is_empty());
+
+        gate.leave();
+        assert!(gate.enter(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn thread_gate_serves_waiters_in_order() {
+        let gate = Arc::new(ThreadGate::new(ThreadPolicy::new(
+            1,
+            Duration::from_secs(5),
+        )));
+        assert!(gate.enter(Duration::from_secs(1)));
+
+        let (sender, receiver) = mpsc::channel();
+        let mut handles = vec![];
+        for id in 0..3 {
+            // Wait until the previous thread is queued, so arrival order is known
+            while gate.state.lock().queue.len() != id {
+                thread::yield_now();
+            }
+            let gate = Arc::clone(&gate);
+            let sender = sender.clone();
+            handles.push(thread::spawn(move || {
+                assert!(gate.enter(Duration::from_secs(5)));
+                sender.send(id).unwrap();
+                gate.leave();
+            }));
+        }
+        while gate.state.lock().queue.len() != 3 {
+            thread::yield_now();
+        }
+
+        gate.leave();
+        for handle in handles {
+            handle.join().unwrap();
         }
+        let order: Vec<usize> = receiver.try_iter().collect();
+        assert_eq!(order, vec![0, 1, 2]);
     }
 }
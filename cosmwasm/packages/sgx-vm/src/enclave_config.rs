@@ -5,7 +5,7 @@ use sgx_types::{sgx_enclave_id_t, sgx_status_t, SgxResult};
 
 use enclave_ffi_types::RuntimeConfiguration;
 
-use crate::enclave::ENCLAVE_DOORBELL;
+use crate::enclave::all_doorbells;
 
 lazy_static! {
     /// This variable indicates if the enclave configuration has already been set
@@ -22,12 +22,20 @@ extern "C" {
 
 pub struct EnclaveRuntimeConfig {
     pub module_cache_size: u32,
+    /// Have the enclave sign a receipt of every instantiate, execute and migrate.
+    /// See `take_execution_receipt`.
+    pub sign_execution_receipts: bool,
+    /// Have the enclave sign the responses to queries sent directly to the node.
+    /// See `call_query_raw_signed`.
+    pub sign_query_responses: bool,
 }
 
 impl EnclaveRuntimeConfig {
     fn to_ffi_type(&self) -> RuntimeConfiguration {
         RuntimeConfiguration {
             module_cache_size: self.module_cache_size,
+            sign_execution_receipts: self.sign_execution_receipts,
+            sign_query_responses: self.sign_query_responses,
         }
     }
 }
@@ -40,24 +48,27 @@ pub fn configure_enclave(config: EnclaveRuntimeConfig) -> SgxResult<()> {
     *configured = true;
     drop(configured);
 
-    // Bind the token to a local variable to ensure its
-    // destructor runs in the end of the function
-    let enclave_access_token = ENCLAVE_DOORBELL
-        .get_access(1) // This can never be recursive
-        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
-    let enclave = (*enclave_access_token)?;
+    // A legacy enclave executes blocks too, so it gets the same configuration
+    for doorbell in all_doorbells() {
+        // Bind the token to a local variable to ensure its
+        // destructor runs in the end of the function
+        let enclave_access_token = doorbell
+            .get_access(1) // This can never be recursive
+            .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+        let enclave = (*enclave_access_token)?;
 
-    let mut retval = sgx_status_t::SGX_SUCCESS;
+        let mut retval = sgx_status_t::SGX_SUCCESS;
 
-    let status =
-        unsafe { ecall_configure_runtime(enclave.geteid(), &mut retval, config.to_ffi_type()) };
+        let status =
+            unsafe { ecall_configure_runtime(enclave.geteid(), &mut retval, config.to_ffi_type()) };
 
-    if status != sgx_status_t::SGX_SUCCESS {
-        return Err(status);
-    }
+        if status != sgx_status_t::SGX_SUCCESS {
+            return Err(status);
+        }
 
-    if retval != sgx_status_t::SGX_SUCCESS {
-        return Err(retval);
+        if retval != sgx_status_t::SGX_SUCCESS {
+            return Err(retval);
+        }
     }
 
     Ok(())
@@ -0,0 +1,109 @@
+use std::cell::Cell;
+
+use lazy_static::lazy_static;
+use log::*;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use enclave_ffi_types::{AllocatorStats, CallMemoryStats, ModuleCacheStats};
+
+lazy_static! {
+    /// The last allocator statistics the enclave reported
+    static ref ALLOCATOR_STATS: Mutex<AllocatorStats> = Mutex::new(AllocatorStats::default());
+    /// The last module cache statistics the enclave reported
+    static ref MODULE_CACHE_STATS: Mutex<ModuleCacheStats> =
+        Mutex::new(ModuleCacheStats::default());
+}
+
+thread_local! {
+    /// The memory footprint of the last contract call that returned on this thread. Ocalls run on
+    /// the thread that made the ecall, so this is the footprint of that ecall once it returns.
+    static CALL_MEMORY: Cell<CallMemoryStats> = Cell::new(CallMemoryStats::default());
+}
+
+pub(crate) fn record_allocator_stats(stats: AllocatorStats) {
+    let mut recorded = ALLOCATOR_STATS.lock();
+    if stats.failed_allocations > recorded.failed_allocations {
+        warn!(
+            "Enclave allocator failed {} allocations so far (heap in use: {} bytes)",
+            stats.failed_allocations, stats.current_bytes
+        );
+    }
+    if stats.soft_limit_hits > recorded.soft_limit_hits {
+        warn!(
+            "Contract calls went over the enclave heap soft limit {} times so far",
+            stats.soft_limit_hits
+        );
+    }
+    if stats.wasm_memory_growth_limit_hits > recorded.wasm_memory_growth_limit_hits {
+        warn!(
+            "Contracts exceeded the memory growth limit {} times so far",
+            stats.wasm_memory_growth_limit_hits
+        );
+    }
+    debug!(
+        "Enclave heap high-watermark: {} bytes, wasm pages grown: {}",
+        stats.high_watermark_bytes, stats.wasm_memory_grown_pages
+    );
+    *recorded = stats;
+}
+
+/// Returns the most recent heap usage statistics reported by the enclave.
+/// The enclave only reports when its high-watermark moves or allocations fail, so
+/// `current_bytes` reflects the heap at the time of that report, not right now.
+pub fn enclave_allocator_stats() -> AllocatorStats {
+    *ALLOCATOR_STATS.lock()
+}
+
+pub(crate) fn record_module_cache_stats(stats: ModuleCacheStats) {
+    let mut recorded = MODULE_CACHE_STATS.lock();
+    if stats.evictions > recorded.evictions {
+        debug!(
+            "Enclave module cache evicted {} modules so far ({} of {} cached)",
+            stats.evictions, stats.size, stats.capacity
+        );
+    }
+    *recorded = stats;
+}
+
+/// The module cache statistics of the enclave, see `ModuleCacheStats`
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EnclaveModuleCacheStats {
+    pub capacity: u64,
+    pub size: u64,
+    pub pinned: u64,
+    pub hits: u64,
+    pub pinned_hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl From<ModuleCacheStats> for EnclaveModuleCacheStats {
+    fn from(stats: ModuleCacheStats) -> Self {
+        EnclaveModuleCacheStats {
+            capacity: stats.capacity,
+            size: stats.size,
+            pinned: stats.pinned,
+            hits: stats.hits,
+            pinned_hits: stats.pinned_hits,
+            misses: stats.misses,
+            evictions: stats.evictions,
+        }
+    }
+}
+
+/// Returns the most recent module cache statistics reported by the enclave. The enclave reports
+/// them after each contract call, failed or not, so they are as of the last call.
+pub fn enclave_module_cache_stats() -> EnclaveModuleCacheStats {
+    (*MODULE_CACHE_STATS.lock()).into()
+}
+
+pub(crate) fn record_call_memory(stats: CallMemoryStats) {
+    CALL_MEMORY.with(|call_memory| call_memory.set(stats));
+}
+
+/// Returns the memory footprint the enclave reported for the last contract call on this thread,
+/// and forgets it so that a call that doesn't report one isn't attributed the previous one's.
+pub(crate) fn take_call_memory() -> CallMemoryStats {
+    CALL_MEMORY.with(|call_memory| call_memory.take())
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+
+/// The exports of a contract the enclave calls into, as seen from the node when it validates and
+/// analyzes contracts. Mirrors `EntryPoint` of the enclave's contract engine, which calls them.
+/// New entry points are added to both, so every match on them has to handle the addition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EntryPoint {
+    Instantiate,
+    Execute,
+    Query,
+    Migrate,
+    Sudo,
+    Reply,
+    IbcChannelOpen,
+    IbcChannelConnect,
+    IbcChannelClose,
+    IbcPacketReceive,
+    IbcPacketAck,
+    IbcPacketTimeout,
+}
+
+impl EntryPoint {
+    pub const ALL: [EntryPoint; 12] = [
+        EntryPoint::Instantiate,
+        EntryPoint::Execute,
+        EntryPoint::Query,
+        EntryPoint::Migrate,
+        EntryPoint::Sudo,
+        EntryPoint::Reply,
+        EntryPoint::IbcChannelOpen,
+        EntryPoint::IbcChannelConnect,
+        EntryPoint::IbcChannelClose,
+        EntryPoint::IbcPacketReceive,
+        EntryPoint::IbcPacketAck,
+        EntryPoint::IbcPacketTimeout,
+    ];
+
+    /// The entry point exported as `name` by contracts of v1 or of v0.10, whose export names
+    /// don't overlap
+    pub fn from_export_name(name: &str) -> Option<Self> {
+        EntryPoint::ALL
+            .iter()
+            .find(|entry_point| {
+                entry_point.export_name(true) == name
+                    || (entry_point.in_v010() && entry_point.export_name(false) == name)
+            })
+            .copied()
+    }
+
+    /// The name of the export in contracts of v1 if `is_v1` or of v0.10
+    pub fn export_name(&self, is_v1: bool) -> &'static str {
+        match (self, is_v1) {
+            (EntryPoint::Instantiate, false) => "init",
+            (EntryPoint::Instantiate, true) => "instantiate",
+            (EntryPoint::Execute, false) => "handle",
+            (EntryPoint::Execute, true) => "execute",
+            (EntryPoint::Query, _) => "query",
+            (EntryPoint::Migrate, _) => "migrate",
+            (EntryPoint::Sudo, _) => "sudo",
+            (EntryPoint::Reply, _) => "reply",
+            (EntryPoint::IbcChannelOpen, _) => "ibc_channel_open",
+            (EntryPoint::IbcChannelConnect, _) => "ibc_channel_connect",
+            (EntryPoint::IbcChannelClose, _) => "ibc_channel_close",
+            (EntryPoint::IbcPacketReceive, _) => "ibc_packet_receive",
+            (EntryPoint::IbcPacketAck, _) => "ibc_packet_ack",
+            (EntryPoint::IbcPacketTimeout, _) => "ibc_packet_timeout",
+        }
+    }
+
+    /// Whether the enclave calls the entry point in contracts of v0.10
+    pub fn in_v010(&self) -> bool {
+        matches!(
+            self,
+            EntryPoint::Instantiate | EntryPoint::Execute | EntryPoint::Query | EntryPoint::Migrate
+        )
+    }
+
+    /// Whether the export takes an env region. Only v0.10 queries don't.
+    pub fn takes_env(&self, is_v1: bool) -> bool {
+        is_v1 || *self != EntryPoint::Query
+    }
+
+    /// Whether the export takes a `MessageInfo` region between the env and the msg
+    pub fn takes_msg_info(&self, is_v1: bool) -> bool {
+        is_v1 && matches!(self, EntryPoint::Instantiate | EntryPoint::Execute)
+    }
+
+    /// The number of regions the enclave calls the export with, the msg included
+    pub fn param_count(&self, is_v1: bool) -> usize {
+        1 + self.takes_env(is_v1) as usize + self.takes_msg_info(is_v1) as usize
+    }
+
+    /// IBC protocol entry points, which are not encrypted
+    pub fn is_ibc(&self) -> bool {
+        match self {
+            EntryPoint::IbcChannelOpen
+            | EntryPoint::IbcChannelConnect
+            | EntryPoint::IbcChannelClose
+            | EntryPoint::IbcPacketReceive
+            | EntryPoint::IbcPacketAck
+            | EntryPoint::IbcPacketTimeout => true,
+            EntryPoint::Instantiate
+            | EntryPoint::Execute
+            | EntryPoint::Query
+            | EntryPoint::Migrate
+            | EntryPoint::Sudo
+            | EntryPoint::Reply => false,
+        }
+    }
+}
+
+impl fmt::Display for EntryPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.export_name(true))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_export_name_works() {
+        assert_eq!(
+            EntryPoint::from_export_name("init"),
+            Some(EntryPoint::Instantiate)
+        );
+        assert_eq!(
+            EntryPoint::from_export_name("instantiate"),
+            Some(EntryPoint::Instantiate)
+        );
+        assert_eq!(
+            EntryPoint::from_export_name("handle"),
+            Some(EntryPoint::Execute)
+        );
+        assert_eq!(
+            EntryPoint::from_export_name("ibc_packet_ack"),
+            Some(EntryPoint::IbcPacketAck)
+        );
+        assert_eq!(EntryPoint::from_export_name("allocate"), None);
+
+        for entry_point in EntryPoint::ALL.iter() {
+            assert_eq!(
+                EntryPoint::from_export_name(entry_point.export_name(true)),
+                Some(*entry_point)
+            );
+        }
+    }
+
+    #[test]
+    fn param_count_works() {
+        assert_eq!(EntryPoint::Query.param_count(false), 1);
+        assert_eq!(EntryPoint::Query.param_count(true), 2);
+        assert_eq!(EntryPoint::Execute.param_count(false), 2);
+        assert_eq!(EntryPoint::Execute.param_count(true), 3);
+        assert_eq!(EntryPoint::Reply.param_count(true), 2);
+        assert_eq!(EntryPoint::IbcChannelOpen.param_count(true), 2);
+    }
+}
@@ -1,11 +1,13 @@
 use super::VmError;
+use enclave_ffi_types::ErrorMessagesVersion;
 use snafu::{Backtrace, Snafu};
 
 /// An error in the communication with the enclave
 #[derive(Debug, Snafu)]
 #[non_exhaustive]
 pub enum EnclaveError {
-    #[snafu(display("{}", error))]
+    /// Displays the canonical message of the error, since it ends up in transaction results
+    #[snafu(display("{}", error.canonical_message(ErrorMessagesVersion::CURRENT)))]
     EnclaveErr {
         error: enclave_ffi_types::EnclaveError,
         backtrace: Backtrace,
@@ -37,6 +39,14 @@ impl From<enclave_ffi_types::EnclaveError> for VmError {
     fn from(error: enclave_ffi_types::EnclaveError) -> Self {
         match error {
             enclave_ffi_types::EnclaveError::OutOfGas => VmError::GasDepletion,
+            enclave_ffi_types::EnclaveError::MemorySoftLimitExceeded => {
+                VmError::enclave_out_of_memory()
+            }
+            enclave_ffi_types::EnclaveError::ResponseTooLarge => VmError::response_too_large(),
+            enclave_ffi_types::EnclaveError::StackLimitExceeded => VmError::stack_limit_exceeded(),
+            enclave_ffi_types::EnclaveError::MemoryGrowthLimitExceeded => {
+                VmError::memory_growth_limit_exceeded()
+            }
             enclave_ffi_types::EnclaveError::FailedOcall { vm_error }
                 if !vm_error.ptr.is_null() =>
             // This error is boxed during ocalls.
@@ -45,3 +55,20 @@ impl From<enclave_ffi_types::EnclaveError> for VmError {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn enclave_err_displays_canonical_message() {
+        let error: VmError = enclave_ffi_types::EnclaveError::WasmModuleWithFP.into();
+        assert_eq!(
+            error.to_string(),
+            "Enclave: found floating point operation in module code"
+        );
+
+        let error: VmError = enclave_ffi_types::EnclaveError::Unknown.into();
+        assert_eq!(error.to_string(), "Enclave: unknown error");
+    }
+}
@@ -1,11 +1,13 @@
 mod communication_error;
 mod region_validation_error;
+mod validation_failure;
 mod vm_error;
 
 mod enclave;
 
 pub use communication_error::CommunicationError;
 pub use region_validation_error::RegionValidationError;
+pub use validation_failure::ValidationFailure;
 pub use vm_error::VmError;
 
 pub use enclave::EnclaveError;
@@ -0,0 +1,380 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::compatability::COSMWASM_1_X_FEATURE_PREFIX;
+
+/// Why `check_wasm` rejected a contract. Carried by `VmError::StaticValidationErr`, along with its
+/// message, for clients to tell failures apart without matching messages.
+///
+/// It is serialized with its `code` next to its fields, which is how it is passed to Go.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ValidationFailure {
+    /// The Wasm couldn't be parsed, or isn't valid with the proposals the enclave supports
+    Deserialization {
+        error: String,
+        /// The proposals it would be valid with, if any
+        unsupported_proposals: Vec<String>,
+        /// The wasm-opt flags enabling `unsupported_proposals`
+        wasm_opt_flags: Vec<String>,
+    },
+    MissingMemorySection,
+    NoMemory,
+    TooManyMemories {
+        count: usize,
+    },
+    /// In pages
+    MemoryInitialTooLarge {
+        limit: u32,
+    },
+    /// In pages
+    MemoryMaximumTooLarge {
+        limit: u32,
+    },
+    MemoryMaximumSet,
+    StartFunction,
+    /// In bytes
+    CodeTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    TooManyFunctions {
+        count: usize,
+        limit: usize,
+    },
+    /// In bytes. `function` is in the index space of functions, imports included.
+    FunctionBodyTooLarge {
+        function: usize,
+        size: usize,
+        limit: usize,
+    },
+    /// In elements
+    TableTooLarge {
+        size: u32,
+        limit: u32,
+    },
+    TooManyDataSegments {
+        count: usize,
+        limit: usize,
+    },
+    /// In bytes, of all the data segments
+    DataTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    /// The indices of the functions using floats, imports included, and their export names
+    FloatInstructions {
+        functions: Vec<(u32, Option<String>)>,
+    },
+    MissingExport {
+        export: String,
+        required_exports: Vec<String>,
+    },
+    /// The types are in the notation of the Wasm spec. `actual` is `None` if the export is not a
+    /// function.
+    WrongExportSignature {
+        export: String,
+        expected: String,
+        actual: Option<String>,
+    },
+    UnsupportedImport {
+        import: String,
+        supported_imports: Vec<String>,
+        extra_supported_imports: Vec<String>,
+    },
+    NonFunctionImport {
+        import: String,
+    },
+    UnsupportedFeatures {
+        features: BTreeSet<String>,
+    },
+    UnsupportedInterfaceVersion {
+        export: String,
+    },
+    /// The developer signature uploaded with the Wasm is not a signature of it by `public_key`
+    InvalidDeveloperSignature {
+        /// Hex encoded
+        public_key: String,
+    },
+    /// Why the contract is neither, for each interface version
+    #[serde(rename = "not_cosmwasm")]
+    NotCosmWasm {
+        v010_failures: Vec<ValidationFailure>,
+        v1_failures: Vec<ValidationFailure>,
+    },
+    /// A failure without a code of its own
+    Other {
+        msg: String,
+    },
+}
+
+impl ValidationFailure {
+    /// A stable identifier of the kind of failure
+    pub fn code(&self) -> &'static str {
+        use ValidationFailure::*;
+        match self {
+            Deserialization { .. } => "deserialization",
+            MissingMemorySection => "missing_memory_section",
+            NoMemory => "no_memory",
+            TooManyMemories { .. } => "too_many_memories",
+            MemoryInitialTooLarge { .. } => "memory_initial_too_large",
+            MemoryMaximumTooLarge { .. } => "memory_maximum_too_large",
+            MemoryMaximumSet => "memory_maximum_set",
+            StartFunction => "start_function",
+            CodeTooLarge { .. } => "code_too_large",
+            TooManyFunctions { .. } => "too_many_functions",
+            FunctionBodyTooLarge { .. } => "function_body_too_large",
+            TableTooLarge { .. } => "table_too_large",
+            TooManyDataSegments { .. } => "too_many_data_segments",
+            DataTooLarge { .. } => "data_too_large",
+            FloatInstructions { .. } => "float_instructions",
+            MissingExport { .. } => "missing_export",
+            WrongExportSignature { .. } => "wrong_export_signature",
+            UnsupportedImport { .. } => "unsupported_import",
+            NonFunctionImport { .. } => "non_function_import",
+            UnsupportedFeatures { .. } => "unsupported_features",
+            UnsupportedInterfaceVersion { .. } => "unsupported_interface_version",
+            InvalidDeveloperSignature { .. } => "invalid_developer_signature",
+            NotCosmWasm { .. } => "not_cosmwasm",
+            Other { .. } => "other",
+        }
+    }
+}
+
+fn join(failures: &[ValidationFailure]) -> String {
+    let messages: Vec<String> = failures.iter().map(|f| f.to_string()).collect();
+    messages.join(" ")
+}
+
+impl fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ValidationFailure::*;
+        match self {
+            Deserialization {
+                error,
+                unsupported_proposals,
+                wasm_opt_flags,
+            } => {
+                write!(
+                    f,
+                    "Wasm bytecode could not be deserialized. Deserialization error: \"{}\"",
+                    error
+                )?;
+                if !unsupported_proposals.is_empty() {
+                    write!(
+                        f,
+                        ". It uses Wasm proposals the enclave doesn't support: {}. Newer Rust \
+                        versions enable some of them by default, build it with \
+                        RUSTFLAGS=\"-C target-cpu=mvp\" and don't pass {} to wasm-opt.",
+                        unsupported_proposals.join(", "),
+                        wasm_opt_flags.join(" ")
+                    )?;
+                }
+                Ok(())
+            }
+            MissingMemorySection => write!(f, "Wasm contract doesn't have a memory section"),
+            NoMemory => write!(f, "Wasm contract must contain exactly one memory"),
+            TooManyMemories { .. } => write!(
+                f,
+                "Wasm contract must contain exactly one memory. It uses the multi-memory \
+                proposal, which the enclave doesn't support: don't pass --enable-multimemory to \
+                wasm-opt."
+            ),
+            MemoryInitialTooLarge { limit } => write!(
+                f,
+                "Wasm contract memory's minimum must not exceed {} pages.",
+                limit
+            ),
+            MemoryMaximumTooLarge { limit } => write!(
+                f,
+                "Wasm contract memory's maximum must not exceed {} pages.",
+                limit
+            ),
+            MemoryMaximumSet => write!(
+                f,
+                "Wasm contract memory's maximum must be unset. The host will set it for you."
+            ),
+            StartFunction => write!(f, "Wasm contract must not have a start function"),
+            CodeTooLarge { size, limit } => write!(
+                f,
+                "Wasm contract exceeds limit max_code_size: it has {} bytes, the limit is {}.",
+                size, limit
+            ),
+            TooManyFunctions { count, limit } => write!(
+                f,
+                "Wasm contract exceeds limit max_functions: it defines {} functions, the limit is {}.",
+                count, limit
+            ),
+            FunctionBodyTooLarge {
+                function,
+                size,
+                limit,
+            } => write!(
+                f,
+                "Wasm contract exceeds limit max_function_body_size: the body of its function #{} has {} bytes, the limit is {}.",
+                function, size, limit
+            ),
+            TableTooLarge { size, limit } => write!(
+                f,
+                "Wasm contract exceeds limit max_table_size: it has a table of {} elements, the limit is {}.",
+                size, limit
+            ),
+            TooManyDataSegments { count, limit } => write!(
+                f,
+                "Wasm contract exceeds limit max_data_segments: it has {} data segments, the limit is {}.",
+                count, limit
+            ),
+            DataTooLarge { size, limit } => write!(
+                f,
+                "Wasm contract exceeds limit max_data_size: its data segments have {} bytes, the limit is {}.",
+                size, limit
+            ),
+            FloatInstructions { functions } => {
+                let functions: Vec<String> = functions
+                    .iter()
+                    .map(|(index, export)| match export {
+                        Some(name) => format!("#{} (export \"{}\")", index, name),
+                        None => format!("#{}", index),
+                    })
+                    .collect();
+                write!(
+                    f,
+                    "Wasm contract uses floating point instructions, which are not deterministic, in functions: {}.",
+                    functions.join(", ")
+                )
+            }
+            MissingExport {
+                export,
+                required_exports,
+            } => write!(
+                f,
+                "Wasm contract doesn't have required export: \"{}\". Exports required by VM: {:?}.",
+                export, required_exports
+            ),
+            WrongExportSignature {
+                export,
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "Wasm contract export \"{}\" has type {}, the VM calls it as {}.",
+                export, actual, expected
+            ),
+            WrongExportSignature {
+                export,
+                expected,
+                actual: None,
+            } => write!(
+                f,
+                "Wasm contract export \"{}\" is not a function, the VM calls it as {}.",
+                export, expected
+            ),
+            UnsupportedImport {
+                import,
+                supported_imports,
+                extra_supported_imports,
+            } => write!(
+                f,
+                "Wasm contract requires unsupported import: \"{}\". Imports supported by VM: {:?}, extra imports supported by the chain: {:?}.",
+                import, supported_imports, extra_supported_imports
+            ),
+            NonFunctionImport { import } => write!(
+                f,
+                "Wasm contract requires non-function import: \"{}\". Right now, all supported imports are functions.",
+                import
+            ),
+            UnsupportedFeatures { features } => {
+                write!(f, "Wasm contract requires unsupported features: {:?}", features)?;
+                if features
+                    .iter()
+                    .any(|feature| feature.starts_with(COSMWASM_1_X_FEATURE_PREFIX))
+                {
+                    write!(f, " The cosmwasm_1_* features of cosmwasm-std enable messages and queries this chain doesn't support, build the contract without them.")?;
+                }
+                Ok(())
+            }
+            UnsupportedInterfaceVersion { export } => write!(
+                f,
+                "Wasm contract exports unsupported interface version \"{}\". Contracts built with cosmwasm-std 1.x export \"interface_version_8\".",
+                export
+            ),
+            InvalidDeveloperSignature { public_key } => write!(
+                f,
+                "Wasm contract is not signed by the developer key {}. Sign the uncompressed Wasm as uploaded.",
+                public_key
+            ),
+            NotCosmWasm {
+                v010_failures,
+                v1_failures,
+            } => write!(
+                f,
+                "Contract is not CosmWasm v0.10 or v1. To support v0.10 please fix: {} To support v1 please fix: {}",
+                join(v010_failures),
+                join(v1_failures)
+            ),
+            Other { msg } => write!(f, "{}", msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_works() {
+        assert_eq!(
+            ValidationFailure::MemoryInitialTooLarge { limit: 512 }.to_string(),
+            "Wasm contract memory's minimum must not exceed 512 pages."
+        );
+        assert_eq!(
+            ValidationFailure::FloatInstructions {
+                functions: vec![(2, Some("to_float".to_string())), (3, None)],
+            }
+            .to_string(),
+            "Wasm contract uses floating point instructions, which are not deterministic, in functions: #2 (export \"to_float\"), #3."
+        );
+        let features = ["staking", "cosmwasm_1_2"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        assert_eq!(
+            ValidationFailure::UnsupportedFeatures { features }.to_string(),
+            "Wasm contract requires unsupported features: {\"cosmwasm_1_2\", \"staking\"} The cosmwasm_1_* features of cosmwasm-std enable messages and queries this chain doesn't support, build the contract without them."
+        );
+    }
+
+    #[test]
+    fn code_works() {
+        assert_eq!(ValidationFailure::StartFunction.code(), "start_function");
+        assert_eq!(
+            ValidationFailure::NotCosmWasm {
+                v010_failures: vec![],
+                v1_failures: vec![],
+            }
+            .code(),
+            "not_cosmwasm"
+        );
+    }
+
+    #[test]
+    fn serialize_works() {
+        let failure = ValidationFailure::NotCosmWasm {
+            v010_failures: vec![ValidationFailure::TooManyMemories { count: 2 }],
+            v1_failures: vec![ValidationFailure::NonFunctionImport {
+                import: "env.memory".to_string(),
+            }],
+        };
+        assert_eq!(
+            serde_json::to_string(&failure).unwrap(),
+            r#"{"code":"not_cosmwasm","v010_failures":[{"code":"too_many_memories","count":2}],"v1_failures":[{"code":"non_function_import","import":"env.memory"}]}"#
+        );
+        assert_eq!(
+            serde_json::to_value(&ValidationFailure::StartFunction).unwrap()["code"],
+            ValidationFailure::StartFunction.code()
+        );
+    }
+}
@@ -6,6 +6,7 @@ use super::communication_error::CommunicationError;
 use crate::ffi::FfiError;
 
 use super::EnclaveError;
+use super::ValidationFailure;
 
 const MAX_ERR_LEN: usize = 4096;
 
@@ -52,6 +53,11 @@ pub enum VmError {
         id: u32,
         backtrace: snafu::Backtrace,
     },
+    #[snafu(display("Enclave launch configuration rejected: {}", msg))]
+    LaunchConfigErr {
+        msg: String,
+        backtrace: snafu::Backtrace,
+    },
     #[snafu(display("Error parsing into type {}: {}", target, msg))]
     ParseErr {
         /// the target type that was attempted
@@ -80,6 +86,17 @@ pub enum VmError {
     #[snafu(display("Error during static Wasm validation: {}", msg))]
     StaticValidationErr {
         msg: String,
+        failure: ValidationFailure,
+        backtrace: snafu::Backtrace,
+    },
+    #[snafu(display(
+        "Wasm contract mixes imports of CosmWasm v0.10: {:?} and v1: {:?}",
+        v010_imports,
+        v1_imports
+    ))]
+    MixedInterfaceVersion {
+        v010_imports: Vec<String>,
+        v1_imports: Vec<String>,
         backtrace: snafu::Backtrace,
     },
     #[snafu(display("Uninitialized Context Data: {}", kind))]
@@ -97,6 +114,18 @@ pub enum VmError {
     #[snafu(display("Must not call a writing storage function in this context."))]
     WriteAccessDenied { backtrace: snafu::Backtrace },
 
+    #[snafu(display("Contract call reached the enclave heap limit"))]
+    EnclaveOutOfMemory { backtrace: snafu::Backtrace },
+
+    #[snafu(display("Contract response exceeds the configured size limit"))]
+    ResponseTooLarge { backtrace: snafu::Backtrace },
+
+    #[snafu(display("Contract calls nested deeper than the stack height limit"))]
+    StackLimitExceeded { backtrace: snafu::Backtrace },
+
+    #[snafu(display("Contract grew its memory by more than the memory growth limit"))]
+    MemoryGrowthLimitExceeded { backtrace: snafu::Backtrace },
+
     #[snafu(display("Enclave: {}", source))]
     EnclaveErr {
         #[snafu(backtrace)]
@@ -133,6 +162,10 @@ impl VmError {
         .build()
     }
 
+    pub(crate) fn enclave_out_of_memory() -> Self {
+        EnclaveOutOfMemory {}.build()
+    }
+
     pub(crate) fn generic_err<S: Into<String>>(msg: S) -> Self {
         GenericErr {
             msg: &Self::truncate_input(msg),
@@ -156,6 +189,13 @@ impl VmError {
         IteratorDoesNotExist { id: iterator_id }.build()
     }
 
+    pub(crate) fn launch_config_err<S: Into<String>>(msg: S) -> Self {
+        LaunchConfigErr {
+            msg: &Self::truncate_input(msg),
+        }
+        .build()
+    }
+
     pub(crate) fn parse_err<T: Into<String>, M: Into<String>>(target: T, msg: M) -> Self {
         ParseErr {
             target: &Self::truncate_input(target),
@@ -186,9 +226,42 @@ impl VmError {
         .build()
     }
 
+    pub(crate) fn stack_limit_exceeded() -> Self {
+        StackLimitExceeded {}.build()
+    }
+
+    pub(crate) fn memory_growth_limit_exceeded() -> Self {
+        MemoryGrowthLimitExceeded {}.build()
+    }
+
+    #[allow(dead_code)]
     pub(crate) fn static_validation_err<S: Into<String>>(msg: S) -> Self {
+        Self::static_validation_failure(ValidationFailure::Other { msg: msg.into() })
+    }
+
+    pub(crate) fn static_validation_failure(failure: ValidationFailure) -> Self {
         StaticValidationErr {
-            msg: &Self::truncate_input(msg),
+            msg: &Self::truncate_input(failure.to_string()),
+            failure,
+        }
+        .build()
+    }
+
+    /// Why `check_wasm` rejected the contract, if this is a static validation error
+    pub fn validation_failure(&self) -> Option<&ValidationFailure> {
+        match self {
+            VmError::StaticValidationErr { failure, .. } => Some(failure),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn mixed_interface_version(
+        v010_imports: Vec<String>,
+        v1_imports: Vec<String>,
+    ) -> Self {
+        MixedInterfaceVersion {
+            v010_imports,
+            v1_imports,
         }
         .build()
     }
@@ -209,6 +282,10 @@ impl VmError {
         s
     }
 
+    pub(crate) fn response_too_large() -> Self {
+        ResponseTooLarge {}.build()
+    }
+
     pub(crate) fn write_access_denied() -> Self {
         WriteAccessDenied {}.build()
     }
@@ -273,6 +350,15 @@ mod test {
         }
     }
 
+    #[test]
+    fn enclave_out_of_memory_works() {
+        let error = VmError::enclave_out_of_memory();
+        match error {
+            VmError::EnclaveOutOfMemory { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn generic_err_works() {
         let guess = 7;
@@ -325,6 +411,15 @@ mod test {
         }
     }
 
+    #[test]
+    fn launch_config_err_works() {
+        let error = VmError::launch_config_err("something went wrong");
+        match error {
+            VmError::LaunchConfigErr { msg, .. } => assert_eq!(msg, "something went wrong"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn parse_err_works() {
         let error = VmError::parse_err("Book", "Missing field: title");
@@ -358,6 +453,15 @@ mod test {
         }
     }
 
+    #[test]
+    fn response_too_large_works() {
+        let error = VmError::response_too_large();
+        match error {
+            VmError::ResponseTooLarge { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn runtime_err_works() {
         let error = VmError::runtime_err("something went wrong");
@@ -367,6 +471,24 @@ mod test {
         }
     }
 
+    #[test]
+    fn stack_limit_exceeded_works() {
+        let error = VmError::stack_limit_exceeded();
+        match error {
+            VmError::StackLimitExceeded { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn memory_growth_limit_exceeded_works() {
+        let error = VmError::memory_growth_limit_exceeded();
+        match error {
+            VmError::MemoryGrowthLimitExceeded { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn static_validation_err_works() {
         let error = VmError::static_validation_err("export xy missing");
@@ -376,6 +498,45 @@ mod test {
         }
     }
 
+    #[test]
+    fn static_validation_failure_works() {
+        let error = VmError::static_validation_failure(ValidationFailure::StartFunction);
+        assert_eq!(
+            error.to_string(),
+            "Error during static Wasm validation: Wasm contract must not have a start function"
+        );
+        assert_eq!(
+            error.validation_failure(),
+            Some(&ValidationFailure::StartFunction)
+        );
+        match error {
+            VmError::StaticValidationErr { msg, failure, .. } => {
+                assert_eq!(msg, "Wasm contract must not have a start function");
+                assert_eq!(failure.code(), "start_function");
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn mixed_interface_version_works() {
+        let error = VmError::mixed_interface_version(
+            vec!["env.canonicalize_address".to_string()],
+            vec!["env.addr_validate".to_string()],
+        );
+        match error {
+            VmError::MixedInterfaceVersion {
+                v010_imports,
+                v1_imports,
+                ..
+            } => {
+                assert_eq!(v010_imports, ["env.canonicalize_address"]);
+                assert_eq!(v1_imports, ["env.addr_validate"]);
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     fn uninitialized_context_data_works() {
         let error = VmError::uninitialized_context_data("foo");
@@ -0,0 +1,33 @@
+use std::cell::Cell;
+
+use log::*;
+
+use enclave_ffi_types::ExecutionReceipt;
+
+use crate::checksum::Checksum;
+
+thread_local! {
+    /// The receipt of the instantiate, execute or migrate running on this thread. The enclave
+    /// reports it through an ocall on the thread that made the ecall, before the ecall returns, so
+    /// concurrent calls on other threads never see it.
+    static EXECUTION_RECEIPT: Cell<Option<ExecutionReceipt>> = Cell::new(None);
+}
+
+pub(crate) fn record_execution_receipt(receipt: ExecutionReceipt) {
+    trace!(
+        "Enclave signed an execution receipt for code {}",
+        Checksum::from(receipt.code_hash)
+    );
+    EXECUTION_RECEIPT.with(|cell| cell.set(Some(receipt)));
+}
+
+/// Takes the receipt the enclave signed for the instantiate, execute or migrate that last ran on
+/// this thread.
+///
+/// Call it on the thread that made the call: before the call, to discard a receipt no one took,
+/// and right after it returns, to get its own. `None` unless the enclave signed a receipt, which
+/// it only does when `EnclaveRuntimeConfig::sign_execution_receipts` is set and it verified the
+/// block the call is part of.
+pub fn take_execution_receipt() -> Option<ExecutionReceipt> {
+    EXECUTION_RECEIPT.with(|cell| cell.take())
+}
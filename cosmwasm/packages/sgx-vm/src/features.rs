@@ -1,9 +1,10 @@
-use parity_wasm::elements::{Internal, Module};
 use std::collections::HashSet;
 use std::iter::FromIterator;
+
+use crate::wasm_module::WasmModule;
 // use wasmer_runtime_core::{export::Export, Instance as WasmerInstance};
 
-const REQUIRES_PREFIX: &str = "requires_";
+pub(crate) const REQUIRES_PREFIX: &str = "requires_";
 
 /// Takes a comma-separated string, splits it by commas, removes empty elements and returns a set of features.
 /// This can be used e.g. to initialize the cache.
@@ -31,28 +32,22 @@ pub fn required_features_from_wasmer_instance(wasmer_instance: &WasmerInstance)
 
 /// Implementation for check_wasm, based on static analysis of the bytecode.
 /// This is used for code upload, to perform check before compiling the Wasm.
-pub fn required_features_from_module(module: &Module) -> HashSet<String> {
-    match module.export_section() {
-        None => HashSet::new(),
-        Some(export_section) => {
-            HashSet::from_iter(export_section.entries().iter().filter_map(|entry| {
-                if let Internal::Function(_) = entry.internal() {
-                    let name = entry.field();
-                    if name.starts_with(REQUIRES_PREFIX) && name.len() > REQUIRES_PREFIX.len() {
-                        let (_, required_feature) = name.split_at(REQUIRES_PREFIX.len());
-                        return Some(required_feature.to_string());
-                    }
-                }
-                None
-            }))
+pub fn required_features_from_module(module: &WasmModule) -> HashSet<String> {
+    HashSet::from_iter(module.exports().iter().filter_map(|entry| {
+        if entry.is_function {
+            let name = &entry.name;
+            if name.starts_with(REQUIRES_PREFIX) && name.len() > REQUIRES_PREFIX.len() {
+                let (_, required_feature) = name.split_at(REQUIRES_PREFIX.len());
+                return Some(required_feature.to_string());
+            }
         }
-    }
+        None
+    }))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use parity_wasm::elements::deserialize_buffer;
     use wabt::wat2wasm;
 
     #[test]
@@ -93,7 +88,7 @@ mod test {
             )"#,
         )
         .unwrap();
-        let module = deserialize_buffer(&wasm).unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
 
         let required_features = required_features_from_module(&module);
         assert_eq!(required_features.len(), 3);
@@ -105,7 +100,7 @@ mod test {
     #[test]
     fn required_features_from_module_works_without_exports_section() {
         let wasm = wat2wasm(r#"(module)"#).unwrap();
-        let module = deserialize_buffer(&wasm).unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
         let required_features = required_features_from_module(&module);
         assert_eq!(required_features.len(), 0);
     }
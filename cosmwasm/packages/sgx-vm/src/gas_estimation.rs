@@ -0,0 +1,413 @@
+//! Static gas estimates of the exports of a contract, for developers to see when they store code.
+//!
+//! The estimate of an export is the gas of its most expensive path that runs every loop body at
+//! most once, including the functions it calls and the base gas of the host functions it calls.
+//! It is in the gas units of the enclave, like the gas limits passed to it. Storage access is
+//! charged by the node depending on the data, and is not included.
+//!
+//! An export that loops, recurses or calls functions through a table can use more gas than its
+//! estimate, which is flagged in the estimate.
+
+use wasmparser::{Operator, Parser, Payload, TypeRef};
+
+use crate::entry_points::EntryPoint;
+use crate::errors::{ValidationFailure, VmError, VmResult};
+use crate::wasm_module::WasmModule;
+
+/// The gas the enclave meters for every instruction
+const INSTRUCTION_GAS: u64 = 2;
+
+/// The base gas the enclave charges for host functions, as set in its `WasmCosts`. Host functions
+/// that are not listed only cost the instruction calling them.
+const HOST_FUNCTION_GAS: &[(&str, u64)] = &[
+    ("canonicalize_address", 8192),
+    ("humanize_address", 8192),
+    ("addr_canonicalize", 8192),
+    ("addr_humanize", 8192),
+    ("addr_validate", 8192),
+    ("secp256k1_verify", 98304),
+    ("secp256k1_recover_pubkey", 98304),
+    ("ed25519_verify", 73728),
+    ("ed25519_batch_verify", 5000),
+    ("secp256k1_sign", 100000),
+    ("ed25519_sign", 75000),
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportGasEstimate {
+    pub name: String,
+    /// The entry point the export is, `None` for the other exports of the contract
+    pub entry_point: Option<EntryPoint>,
+    /// The gas of the most expensive path running every loop body at most once
+    pub gas: u64,
+    /// The export runs a loop, so it can use more gas than estimated
+    pub has_loops: bool,
+    /// The export can call a function it is already in, whose gas is not included
+    pub has_recursion: bool,
+    /// The export calls functions through a table, whose gas is not included
+    pub has_indirect_calls: bool,
+}
+
+impl ExportGasEstimate {
+    /// Whether no call of the export can use more gas than estimated
+    pub fn is_upper_bound(&self) -> bool {
+        !self.has_loops && !self.has_recursion && !self.has_indirect_calls
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasProfile {
+    /// The estimates of the exported functions, in the order of their exports
+    pub exports: Vec<ExportGasEstimate>,
+}
+
+/// A function body, reduced to what its gas depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Gas(u64),
+    Call(u32),
+    CallIndirect,
+    Block,
+    Loop,
+    If,
+    Else,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FunctionEstimate {
+    gas: u64,
+    has_loops: bool,
+    has_recursion: bool,
+    has_indirect_calls: bool,
+}
+
+impl FunctionEstimate {
+    fn add_flags(&mut self, other: &FunctionEstimate) {
+        self.has_loops |= other.has_loops;
+        self.has_recursion |= other.has_recursion;
+        self.has_indirect_calls |= other.has_indirect_calls;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    NotVisited,
+    InProgress,
+    Done,
+}
+
+/// Estimates the gas each exported function of `code` uses at most when it doesn't loop.
+pub fn estimate_gas_profile(code: &[u8]) -> VmResult<GasProfile> {
+    let module = WasmModule::from_bytes(code)?;
+    let (imported_functions, bodies) = parse_functions(code).map_err(|err| {
+        VmError::static_validation_failure(ValidationFailure::Deserialization {
+            error: err.message().to_string(),
+            unsupported_proposals: vec![],
+            wasm_opt_flags: vec![],
+        })
+    })?;
+
+    let mut estimates: Vec<FunctionEstimate> = imported_functions
+        .iter()
+        .map(|name| FunctionEstimate {
+            gas: host_function_gas(name),
+            ..Default::default()
+        })
+        .collect();
+    estimates.resize(imported_functions.len() + bodies.len(), Default::default());
+    let mut visits = vec![Visit::NotVisited; estimates.len()];
+    for visit in visits.iter_mut().take(imported_functions.len()) {
+        *visit = Visit::Done;
+    }
+
+    let callees: Vec<Vec<u32>> = bodies.iter().map(|body| callees(body)).collect();
+    let body_of = |index: u32| {
+        (index as usize)
+            .checked_sub(imported_functions.len())
+            .and_then(|index| bodies.get(index))
+    };
+
+    let mut profile = GasProfile::default();
+    for export in module.exports().iter().filter(|export| export.is_function) {
+        // Depth first over the call graph, without recursing, as contracts can nest calls deeper
+        // than the stack of the node
+        let mut stack = vec![(export.index, 0)];
+        while let Some((index, next_callee)) = stack.pop() {
+            let body = match body_of(index) {
+                Some(body) => body,
+                None => continue,
+            };
+            if next_callee == 0 {
+                if visits[index as usize] != Visit::NotVisited {
+                    continue;
+                }
+                visits[index as usize] = Visit::InProgress;
+            }
+
+            let callees = &callees[index as usize - imported_functions.len()];
+            if let Some(&callee) = callees.get(next_callee) {
+                stack.push((index, next_callee + 1));
+                if visits.get(callee as usize) == Some(&Visit::NotVisited) {
+                    stack.push((callee, 0));
+                }
+                continue;
+            }
+
+            estimates[index as usize] = estimate_function(body, &estimates, &visits);
+            visits[index as usize] = Visit::Done;
+        }
+
+        let estimate = estimates
+            .get(export.index as usize)
+            .copied()
+            .unwrap_or_default();
+        profile.exports.push(ExportGasEstimate {
+            name: export.name.clone(),
+            entry_point: EntryPoint::from_export_name(&export.name),
+            gas: estimate.gas,
+            has_loops: estimate.has_loops,
+            has_recursion: estimate.has_recursion,
+            has_indirect_calls: estimate.has_indirect_calls,
+        });
+    }
+
+    Ok(profile)
+}
+
+/// Returns the names of the imported functions and the steps of the functions defined by the
+/// module, in the order of their indices
+fn parse_functions(
+    code: &[u8],
+) -> Result<(Vec<String>, Vec<Vec<Step>>), wasmparser::BinaryReaderError> {
+    let mut imported_functions = vec![];
+    let mut bodies = vec![];
+
+    for payload in Parser::new(0).parse_all(code) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import?;
+                    if let TypeRef::Func(_) = import.ty {
+                        imported_functions.push(import.name.to_string());
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let mut steps = vec![];
+                let mut reader = body.get_operators_reader()?;
+                while !reader.eof() {
+                    let step = match reader.read()? {
+                        Operator::Block { .. } => Step::Block,
+                        Operator::Loop { .. } => Step::Loop,
+                        Operator::If { .. } => Step::If,
+                        Operator::Else => Step::Else,
+                        Operator::End => Step::End,
+                        Operator::Call { function_index } => Step::Call(function_index),
+                        Operator::CallIndirect { .. } => Step::CallIndirect,
+                        _ => Step::Gas(INSTRUCTION_GAS),
+                    };
+                    // Block, loop, if and call instructions are metered too
+                    if !matches!(step, Step::Else | Step::End | Step::Gas(_)) {
+                        push_gas(&mut steps, INSTRUCTION_GAS);
+                    }
+                    match step {
+                        Step::Gas(gas) => push_gas(&mut steps, gas),
+                        step => steps.push(step),
+                    }
+                }
+                bodies.push(steps);
+            }
+            _ => {}
+        }
+    }
+
+    Ok((imported_functions, bodies))
+}
+
+fn push_gas(steps: &mut Vec<Step>, gas: u64) {
+    match steps.last_mut() {
+        Some(Step::Gas(previous)) => *previous = previous.saturating_add(gas),
+        _ => steps.push(Step::Gas(gas)),
+    }
+}
+
+fn host_function_gas(name: &str) -> u64 {
+    HOST_FUNCTION_GAS
+        .iter()
+        .find(|(host_function, _)| *host_function == name)
+        .map_or(0, |(_, gas)| *gas)
+}
+
+fn callees(body: &[Step]) -> Vec<u32> {
+    let mut callees = vec![];
+    for step in body {
+        if let Step::Call(callee) = step {
+            if !callees.contains(callee) {
+                callees.push(*callee);
+            }
+        }
+    }
+    callees
+}
+
+/// Estimates a function whose callees are all estimated, except the ones it was called from.
+fn estimate_function(
+    body: &[Step],
+    estimates: &[FunctionEstimate],
+    visits: &[Visit],
+) -> FunctionEstimate {
+    let mut estimate = FunctionEstimate::default();
+    // The gas of the current path in each open block, and of the then branch of open ifs that
+    // reached their else
+    let mut blocks: Vec<(u64, Option<u64>)> = vec![(0, None)];
+
+    for step in body {
+        let (gas, then_gas) = match blocks.last_mut() {
+            Some(block) => block,
+            None => break,
+        };
+        match *step {
+            Step::Gas(step_gas) => *gas = gas.saturating_add(step_gas),
+            Step::Call(callee) => match visits.get(callee as usize) {
+                Some(Visit::Done) => {
+                    let callee = &estimates[callee as usize];
+                    *gas = gas.saturating_add(callee.gas);
+                    estimate.add_flags(callee);
+                }
+                Some(_) => estimate.has_recursion = true,
+                None => {}
+            },
+            Step::CallIndirect => estimate.has_indirect_calls = true,
+            Step::Block | Step::If => blocks.push((0, None)),
+            Step::Loop => {
+                estimate.has_loops = true;
+                blocks.push((0, None));
+            }
+            Step::Else => *then_gas = Some(std::mem::take(gas)),
+            Step::End => {
+                let (gas, then_gas) = blocks.pop().unwrap_or_default();
+                let block_gas = then_gas.map_or(gas, |then_gas| then_gas.max(gas));
+                match blocks.last_mut() {
+                    Some((outer_gas, _)) => *outer_gas = outer_gas.saturating_add(block_gas),
+                    None => estimate.gas = block_gas,
+                }
+            }
+        }
+    }
+
+    estimate
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wabt::wat2wasm;
+
+    #[test]
+    fn estimate_gas_profile_works() {
+        let wasm = wat2wasm(
+            r#"(module
+            (import "env" "secp256k1_verify" (func $verify (param i32 i32 i32) (result i32)))
+            (import "env" "db_read" (func $read (param i32) (result i32)))
+            (type $callback (func))
+            (memory 1)
+            (table 1 anyfunc)
+            (func $branch (param i32) (result i32)
+                get_local 0
+                if (result i32)
+                    i32.const 1
+                else
+                    i32.const 1
+                    i32.const 2
+                    i32.add
+                end)
+            (func (export "query") (param i32) (result i32)
+                get_local 0
+                call $branch)
+            (func (export "verify") (result i32)
+                i32.const 0
+                i32.const 0
+                i32.const 0
+                call $verify
+                call $read)
+            (func (export "execute")
+                loop
+                    br 0
+                end)
+            (func $recurse (export "recurse")
+                call $recurse)
+            (func (export "indirect")
+                i32.const 0
+                call_indirect (type $callback))
+            (export "memory" (memory 0))
+        )"#,
+        )
+        .unwrap();
+
+        let profile = estimate_gas_profile(&wasm).unwrap();
+        let estimate = |name: &str| {
+            profile
+                .exports
+                .iter()
+                .find(|export| export.name == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(profile.exports.len(), 5);
+
+        // get_local, call, and get_local, if and the else branch of $branch
+        let query = estimate("query");
+        assert_eq!(query.gas, 7 * INSTRUCTION_GAS);
+        assert!(query.is_upper_bound());
+        assert_eq!(query.entry_point, Some(EntryPoint::Query));
+
+        let verify = estimate("verify");
+        assert_eq!(verify.entry_point, None);
+        assert_eq!(verify.gas, 5 * INSTRUCTION_GAS + 98304);
+        assert!(verify.is_upper_bound());
+
+        let execute = estimate("execute");
+        assert_eq!(execute.gas, 2 * INSTRUCTION_GAS);
+        assert!(execute.has_loops);
+        assert!(!execute.is_upper_bound());
+
+        let recurse = estimate("recurse");
+        assert_eq!(recurse.gas, INSTRUCTION_GAS);
+        assert!(recurse.has_recursion);
+
+        let indirect = estimate("indirect");
+        assert_eq!(indirect.gas, 2 * INSTRUCTION_GAS);
+        assert!(indirect.has_indirect_calls);
+        assert!(!indirect.has_loops);
+    }
+
+    #[test]
+    fn estimate_gas_profile_propagates_flags_of_callees() {
+        let wasm = wat2wasm(
+            r#"(module
+            (func $spin loop br 0 end)
+            (func (export "execute") call $spin call $spin)
+        )"#,
+        )
+        .unwrap();
+
+        let profile = estimate_gas_profile(&wasm).unwrap();
+        assert_eq!(
+            profile.exports,
+            vec![ExportGasEstimate {
+                name: "execute".to_string(),
+                entry_point: Some(EntryPoint::Execute),
+                gas: 6 * INSTRUCTION_GAS,
+                has_loops: true,
+                has_recursion: false,
+                has_indirect_calls: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn estimate_gas_profile_rejects_invalid_code() {
+        assert!(estimate_gas_profile(b"\0asm").is_err());
+    }
+}
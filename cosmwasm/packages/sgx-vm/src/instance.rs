@@ -25,7 +25,7 @@ use crate::context::{
 /*
 use crate::conversion::to_u32;
 */
-use crate::errors::VmResult;
+use crate::errors::{VmError, VmResult};
 /*
 use crate::features::required_features_from_wasmer_instance;
 use crate::imports::{
@@ -35,10 +35,13 @@ use crate::imports::{
 use crate::imports::{do_next, do_scan};
 use crate::memory::{get_memory_info, read_region, write_region};
 */
+use crate::panic_telemetry::record_contract_panic;
 use crate::traits::{Api, Extern, Querier, Storage};
 
 use crate::wasmi::Module;
 
+use enclave_ffi_types::QueryResponseSignature;
+
 /*
 const WASM_PAGE_SIZE: u64 = 64 * 1024;
 */
@@ -54,6 +57,11 @@ pub struct GasReport {
     /// The amount of gas that was spend and metered internally (i.e. by executing Wasm and calling
     /// API methods which are not metered externally)
     pub used_internally: u64,
+    /// The size of the contract's wasm memory at the end of the last call, which is also its
+    /// peak size as wasm memory never shrinks
+    pub wasm_memory_bytes: u64,
+    /// The most enclave heap the last call had allocated at once, including the wasm memory
+    pub heap_high_watermark_bytes: u64,
 }
 
 pub struct Instance<S: Storage + 'static, A: Api + 'static, Q: Querier + 'static> {
@@ -239,6 +247,8 @@ where
             remaining: gas_left,
             used_externally: state.externally_used_gas,
             used_internally: state.get_gas_used_in_wasmer(gas_left),
+            wasm_memory_bytes: self.get_memory_size(),
+            heap_high_watermark_bytes: 0,
         }
     }
 
@@ -253,11 +263,14 @@ where
         let used_externally = limit
             .saturating_sub(remaining)
             .saturating_sub(used_internally);
+        let call_memory = self.inner.call_memory();
         GasReport {
             limit,
             remaining,
             used_externally,
             used_internally,
+            wasm_memory_bytes: call_memory.wasm_memory_bytes,
+            heap_high_watermark_bytes: call_memory.heap_high_watermark_bytes,
         }
     }
 
@@ -326,6 +339,13 @@ where
         Ok(function)
     }
 
+    /// Counts the error against the code hash of this contract if it is a trap, see
+    /// `contract_panic_stats`
+    fn record_panic(&self, error: VmError) -> VmError {
+        record_contract_panic(self.inner.bytecode(), &error);
+        error
+    }
+
     pub fn call_migrate(
         &mut self,
         env: &[u8],
@@ -334,7 +354,10 @@ where
         admin: &[u8],
         admin_proof: &[u8],
     ) -> VmResult<Vec<u8>> {
-        let result = self.inner.migrate(env, msg, sig_info, admin, admin_proof)?;
+        let result = self
+            .inner
+            .migrate(env, msg, sig_info, admin, admin_proof)
+            .map_err(|e| self.record_panic(e))?;
         Ok(result.into_output())
     }
 
@@ -346,13 +369,10 @@ where
         current_admin_proof: &[u8],
         new_admin: &[u8],
     ) -> VmResult<Vec<u8>> {
-        let result = self.inner.update_admin(
-            env,
-            sig_info,
-            current_admin,
-            current_admin_proof,
-            new_admin,
-        )?;
+        let result = self
+            .inner
+            .update_admin(env, sig_info, current_admin, current_admin_proof, new_admin)
+            .map_err(|e| self.record_panic(e))?;
         Ok(result.into_output())
     }
 
@@ -363,7 +383,10 @@ where
         sig_info: &[u8],
         admin: &[u8],
     ) -> VmResult<Vec<u8>> {
-        let result = self.inner.init(env, msg, sig_info, admin)?;
+        let result = self
+            .inner
+            .init(env, msg, sig_info, admin)
+            .map_err(|e| self.record_panic(e))?;
         Ok(result.into_output())
     }
 
@@ -374,14 +397,34 @@ where
         sig_info: &[u8],
         handle_type: u8,
     ) -> VmResult<Vec<u8>> {
-        let result = self.inner.handle(env, msg, sig_info, handle_type)?;
+        let result = self
+            .inner
+            .handle(env, msg, sig_info, handle_type)
+            .map_err(|e| self.record_panic(e))?;
         Ok(result.into_output())
     }
 
     pub fn call_query(&mut self, env: &[u8], msg: &[u8]) -> VmResult<Vec<u8>> {
-        let result = self.inner.query(env, msg)?;
+        let result = self
+            .inner
+            .query(env, msg)
+            .map_err(|e| self.record_panic(e))?;
         Ok(result.into_output())
     }
+
+    /// Like `call_query`, but also returns the enclave's signature of the response, if the node
+    /// enabled `EnclaveRuntimeConfig::sign_query_responses` and this is not a nested query.
+    pub fn call_query_signed(
+        &mut self,
+        env: &[u8],
+        msg: &[u8],
+    ) -> VmResult<(Vec<u8>, Option<QueryResponseSignature>)> {
+        let result = self
+            .inner
+            .query(env, msg)
+            .map_err(|e| self.record_panic(e))?;
+        Ok(result.into_signed_output())
+    }
 }
 
 #[cfg(test)]
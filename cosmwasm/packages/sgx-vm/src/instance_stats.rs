@@ -0,0 +1,234 @@
+//! Counts the executions of each contract, for activity based features like pinning hot contracts
+//! or reporting dormant ones. The counters are kept in memory and regularly written to the cache
+//! dir, one file per contract, so they survive restarts. They are local to the node and not part
+//! of consensus.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{VmError, VmResult};
+
+/// How long executions are only counted in memory before being written
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstanceStats {
+    pub executions: u64,
+    pub last_executed_height: u64,
+    pub cumulative_gas: u64,
+}
+
+/// The fields of the env of an execution the stats are keyed and dated by
+#[derive(Deserialize)]
+struct ExecutionEnv {
+    block: ExecutionBlock,
+    contract: ExecutionContract,
+    #[serde(default)]
+    simulation: bool,
+}
+
+#[derive(Deserialize)]
+struct ExecutionBlock {
+    height: u64,
+}
+
+#[derive(Deserialize)]
+struct ExecutionContract {
+    address: String,
+}
+
+/// Contract addresses are bech32, anything else doesn't name a file of ours
+fn stats_file_name(contract_address: &str) -> VmResult<&str> {
+    if contract_address.is_empty() || !contract_address.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(VmError::cache_err(format!(
+            "Invalid contract address for instance stats: {:?}",
+            contract_address
+        )));
+    }
+    Ok(contract_address)
+}
+
+/// Returns `None` if the contract was never executed by this node
+fn load_instance_stats(dir: &Path, contract_address: &str) -> VmResult<Option<InstanceStats>> {
+    let path = dir.join(stats_file_name(contract_address)?);
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(VmError::cache_err(format!(
+                "Error reading instance stats: {}",
+                e
+            )))
+        }
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| VmError::cache_err(format!("Error parsing instance stats: {}", e)))
+}
+
+/// Writes to a temporary file renamed over the stats, so a crash never leaves them half written
+fn save_instance_stats(dir: &Path, contract_address: &str, stats: &InstanceStats) -> VmResult<()> {
+    let path = dir.join(stats_file_name(contract_address)?);
+    let tmp_path = path.with_extension("tmp");
+    let bytes = serde_json::to_vec(stats)
+        .map_err(|e| VmError::cache_err(format!("Error serializing instance stats: {}", e)))?;
+    fs::write(&tmp_path, bytes)
+        .and_then(|_| fs::rename(&tmp_path, &path))
+        .map_err(|e| VmError::cache_err(format!("Error writing instance stats: {}", e)))
+}
+
+/// The stats of the contracts executed since the node started, in memory, written to the stats
+/// dir at most every `FLUSH_INTERVAL` and when dropped. A crash loses the executions since the
+/// last flush, which is fine for local stats.
+pub(crate) struct InstanceStatsStore {
+    dir: PathBuf,
+    stats: HashMap<String, InstanceStats>,
+    /// The contracts with executions that aren't written yet
+    dirty: HashSet<String>,
+    last_flush: Instant,
+}
+
+impl InstanceStatsStore {
+    pub fn new(dir: PathBuf) -> Self {
+        InstanceStatsStore {
+            dir,
+            stats: HashMap::new(),
+            dirty: HashSet::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Returns `None` if the contract was never executed by this node
+    pub fn get(&self, contract_address: &str) -> VmResult<Option<InstanceStats>> {
+        match self.stats.get(contract_address) {
+            Some(stats) => Ok(Some(stats.clone())),
+            None => load_instance_stats(&self.dir, contract_address),
+        }
+    }
+
+    /// Counts an execution, be it an init, handle or migrate, of the contract `env` was given,
+    /// which used `gas_used`, and returns the height it happened at. Simulations and CheckTx don't
+    /// count, the execution happens again when the tx is included in a block: `None` is returned
+    /// for them.
+    pub fn record(&mut self, env: &[u8], gas_used: u64) -> VmResult<Option<u64>> {
+        let env: ExecutionEnv = serde_json::from_slice(env).map_err(|e| {
+            VmError::cache_err(format!("Error parsing env for instance stats: {}", e))
+        })?;
+        if env.simulation {
+            return Ok(None);
+        }
+
+        let address = env.contract.address;
+        if !self.stats.contains_key(&address) {
+            let loaded = load_instance_stats(&self.dir, &address)?.unwrap_or_default();
+            self.stats.insert(address.clone(), loaded);
+        }
+        let stats = self.stats.get_mut(&address).unwrap();
+        stats.executions += 1;
+        stats.last_executed_height = stats.last_executed_height.max(env.block.height);
+        stats.cumulative_gas = stats.cumulative_gas.saturating_add(gas_used);
+        self.dirty.insert(address);
+
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush()?;
+        }
+        Ok(Some(env.block.height))
+    }
+
+    /// Writes the stats of the contracts executed since the last flush
+    pub fn flush(&mut self) -> VmResult<()> {
+        self.last_flush = Instant::now();
+        for address in std::mem::take(&mut self.dirty) {
+            save_instance_stats(&self.dir, &address, &self.stats[&address])?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for InstanceStatsStore {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!("Failed to write the instance stats: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn env(address: &str, height: u64, simulation: bool) -> Vec<u8> {
+        format!(
+            r#"{{"block":{{"height":{},"time":0,"chain_id":"test"}},"contract":{{"address":"{}"}},"simulation":{}}}"#,
+            height, address, simulation
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn record_works() {
+        let tmp_dir = TempDir::new().unwrap();
+        let dir = tmp_dir.path();
+        let address = "secret1contract";
+        let mut store = InstanceStatsStore::new(dir.to_path_buf());
+        assert_eq!(store.get(address).unwrap(), None);
+
+        assert_eq!(
+            store.record(&env(address, 10, false), 100).unwrap(),
+            Some(10)
+        );
+        store.record(&env(address, 12, false), 50).unwrap();
+        // not counted
+        assert_eq!(store.record(&env(address, 13, true), 70).unwrap(), None);
+        let expected = InstanceStats {
+            executions: 2,
+            last_executed_height: 12,
+            cumulative_gas: 150,
+        };
+        assert_eq!(store.get(address).unwrap(), Some(expected.clone()));
+        assert_eq!(store.get("secret1other").unwrap(), None);
+        // not written yet
+        assert_eq!(load_instance_stats(dir, address).unwrap(), None);
+
+        store.flush().unwrap();
+        assert_eq!(load_instance_stats(dir, address).unwrap(), Some(expected));
+        assert!(!dir.join("secret1contract.tmp").exists());
+    }
+
+    #[test]
+    fn stats_survive_restarts() {
+        let tmp_dir = TempDir::new().unwrap();
+        let dir = tmp_dir.path();
+        let address = "secret1contract";
+        let mut store = InstanceStatsStore::new(dir.to_path_buf());
+        store.record(&env(address, 10, false), 100).unwrap();
+        drop(store);
+
+        let mut store = InstanceStatsStore::new(dir.to_path_buf());
+        store.record(&env(address, 11, false), 100).unwrap();
+        assert_eq!(
+            store.get(address).unwrap(),
+            Some(InstanceStats {
+                executions: 2,
+                last_executed_height: 11,
+                cumulative_gas: 200,
+            })
+        );
+    }
+
+    #[test]
+    fn record_rejects_invalid_input() {
+        let tmp_dir = TempDir::new().unwrap();
+        let mut store = InstanceStatsStore::new(tmp_dir.path().to_path_buf());
+        store.record(b"not json", 1).unwrap_err();
+        store.record(&env("../escape", 1, false), 1).unwrap_err();
+        store.get("").unwrap_err();
+    }
+}
@@ -0,0 +1,161 @@
+use log::*;
+use sgx_types::{sgx_misc_attribute_t, SGX_FLAGS_DEBUG, SGX_XFRM_LEGACY};
+
+use crate::enclave::{ENCLAVE_DEBUG, ENCLAVE_DOORBELL};
+use crate::errors::{VmError, VmResult};
+
+/// Chain-ids of Secret Network mainnets. A debug-signed enclave offers no confidentiality
+/// (its memory can be inspected by the host), so consensus on these chains must never run on one.
+pub const MAINNET_CHAIN_IDS: &[&str] = &["secret-1", "secret-2", "secret-3", "secret-4"];
+
+/// The MiscSelect value both Enclave.config.xml and Enclave.config.prod.xml sign the enclave with.
+const EXPECTED_MISC_SELECT: u32 = 0;
+
+pub fn is_mainnet_chain_id(chain_id: &str) -> bool {
+    MAINNET_CHAIN_IDS.contains(&chain_id)
+}
+
+/// Validates the attributes the enclave was launched with against what this build expects.
+///
+/// `allow_debug_enclave` lets testnets that use a mainnet-like chain-id start with a
+/// debug-signed enclave anyway. It has no effect on the other checks.
+pub fn validate_enclave_launch(chain_id: &str, allow_debug_enclave: bool) -> VmResult<()> {
+    let attributes = ENCLAVE_DOORBELL.launch_attributes().map_err(|status| {
+        VmError::launch_config_err(format!("enclave failed to launch: {}", status))
+    })?;
+
+    check_launch_attributes(&attributes, chain_id, allow_debug_enclave)
+}
+
+fn check_launch_attributes(
+    attributes: &sgx_misc_attribute_t,
+    chain_id: &str,
+    allow_debug_enclave: bool,
+) -> VmResult<()> {
+    let debug = attributes.secs_attr.flags & SGX_FLAGS_DEBUG != 0;
+    let expected_debug = ENCLAVE_DEBUG != 0;
+
+    if debug != expected_debug {
+        return Err(VmError::launch_config_err(format!(
+            "enclave debug mode is {} but this node was built for {} mode",
+            debug,
+            if expected_debug {
+                "debug"
+            } else {
+                "production"
+            }
+        )));
+    }
+
+    if attributes.secs_attr.xfrm & SGX_XFRM_LEGACY != SGX_XFRM_LEGACY {
+        return Err(VmError::launch_config_err(format!(
+            "unexpected xfrm {:#x}, x87 and SSE state must be enabled",
+            attributes.secs_attr.xfrm
+        )));
+    }
+
+    if attributes.misc_select != EXPECTED_MISC_SELECT {
+        return Err(VmError::launch_config_err(format!(
+            "unexpected misc select {:#x}, expected {:#x}",
+            attributes.misc_select, EXPECTED_MISC_SELECT
+        )));
+    }
+
+    if debug && is_mainnet_chain_id(chain_id) {
+        if !allow_debug_enclave {
+            return Err(VmError::launch_config_err(format!(
+                "refusing to run a debug enclave on mainnet chain {}",
+                chain_id
+            )));
+        }
+        warn!(
+            "Running a debug enclave on chain {}. This must never happen on a production network",
+            chain_id
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sgx_types::sgx_attributes_t;
+
+    fn attributes(debug: bool, xfrm: u64, misc_select: u32) -> sgx_misc_attribute_t {
+        sgx_misc_attribute_t {
+            secs_attr: sgx_attributes_t {
+                flags: if debug { SGX_FLAGS_DEBUG } else { 0 },
+                xfrm,
+            },
+            misc_select,
+        }
+    }
+
+    fn build_mode_attributes() -> sgx_misc_attribute_t {
+        attributes(ENCLAVE_DEBUG != 0, SGX_XFRM_LEGACY, 0)
+    }
+
+    #[test]
+    fn is_mainnet_chain_id_works() {
+        assert!(is_mainnet_chain_id("secret-4"));
+        assert!(!is_mainnet_chain_id("pulsar-3"));
+        assert!(!is_mainnet_chain_id("secretdev-1"));
+    }
+
+    #[test]
+    fn check_launch_attributes_accepts_build_mode() {
+        check_launch_attributes(&build_mode_attributes(), "secretdev-1", false).unwrap();
+    }
+
+    #[test]
+    fn check_launch_attributes_rejects_mode_mismatch() {
+        let attrs = attributes(ENCLAVE_DEBUG == 0, SGX_XFRM_LEGACY, 0);
+        match check_launch_attributes(&attrs, "secretdev-1", true).unwrap_err() {
+            VmError::LaunchConfigErr { msg, .. } => {
+                assert!(msg.starts_with("enclave debug mode is"))
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn check_launch_attributes_rejects_missing_xfrm() {
+        let mut attrs = build_mode_attributes();
+        attrs.secs_attr.xfrm = 0x1;
+        match check_launch_attributes(&attrs, "secretdev-1", false).unwrap_err() {
+            VmError::LaunchConfigErr { msg, .. } => assert!(msg.starts_with("unexpected xfrm")),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn check_launch_attributes_rejects_misc_select() {
+        let mut attrs = build_mode_attributes();
+        attrs.misc_select = 0x1;
+        match check_launch_attributes(&attrs, "secretdev-1", false).unwrap_err() {
+            VmError::LaunchConfigErr { msg, .. } => {
+                assert!(msg.starts_with("unexpected misc select"))
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[cfg(not(feature = "production"))]
+    #[test]
+    fn check_launch_attributes_debug_enclave_on_mainnet() {
+        let attrs = build_mode_attributes();
+        match check_launch_attributes(&attrs, "secret-4", false).unwrap_err() {
+            VmError::LaunchConfigErr { msg, .. } => {
+                assert_eq!(
+                    msg,
+                    "refusing to run a debug enclave on mainnet chain secret-4"
+                )
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        // the override flag lets testnets through
+        check_launch_attributes(&attrs, "secret-4", true).unwrap();
+    }
+}
@@ -1,59 +1,120 @@
+mod auto_pinning;
 mod backends;
 mod cache;
 mod calls;
+mod capabilities;
 mod checksum;
 mod compatability;
 mod context;
 mod conversion;
+mod developer_signature;
+mod entry_points;
 mod errors;
 mod features;
 mod ffi;
+mod gas_estimation;
 // mod imports;
 mod instance;
+mod instance_stats;
 // mod memory;
+mod panic_telemetry;
+mod parsed_modules;
 // mod middleware;
 // mod modules;
 mod serde;
+mod store_migrations;
 pub mod testing;
 mod traits;
+mod wasm_module;
 
 // Secret Network specific modules
 mod attestation;
+mod contract_key_proofs;
 mod enclave;
 mod enclave_config;
+mod enclave_stats;
+mod execution_receipts;
+mod launch_config;
+mod module_pinning;
+mod observed_events;
+mod query_rewrap;
 mod seed;
+mod seed_rotation;
+mod seed_shares;
+mod state_key_decryption;
+mod tx_decryption;
+mod upgrade_schedule;
 mod wasmi;
 
 mod random;
 
-pub use crate::cache::CosmCache;
+pub use crate::auto_pinning::{AutoPinConfig, AutoPinMetrics};
+pub use crate::cache::{
+    CosmCache, IntrospectionReport, Metrics, RevalidationFailure, RevalidationReport,
+};
 pub use crate::calls::{
-    call_handle_raw, call_init_raw, call_migrate_raw, call_query_raw, call_update_admin_raw,
+    call_handle_raw, call_init_raw, call_migrate_raw, call_query_raw, call_query_raw_signed,
+    call_update_admin_raw,
 };
+pub use crate::capabilities::{VmCapabilities, VmLimits, GAS_SCHEDULE_VERSION};
 pub use crate::checksum::Checksum;
+pub use crate::compatability::{
+    analyze_wasm, check_wasm_with_report, has_ibc_entry_points, ContractAnalysis, FloatPolicy,
+    IbcCapabilities, ValidationReport, WasmValidationConfig,
+};
+pub use crate::developer_signature::DeveloperSignature;
+pub use crate::entry_points::EntryPoint;
 pub use crate::errors::{
     CommunicationError, CommunicationResult, RegionValidationError, RegionValidationResult,
-    VmError, VmResult,
+    ValidationFailure, VmError, VmResult,
 };
 pub use crate::features::features_from_csv;
 pub use crate::ffi::{FfiError, FfiResult, GasInfo};
+pub use crate::gas_estimation::{estimate_gas_profile, ExportGasEstimate, GasProfile};
 pub use crate::instance::{GasReport, Instance};
+pub use crate::instance_stats::InstanceStats;
+pub use crate::panic_telemetry::{contract_panic_stats, ContractPanicStats};
+pub use enclave::load_legacy_enclave;
 pub use enclave_config::{configure_enclave, EnclaveRuntimeConfig};
+pub use enclave_stats::{
+    enclave_allocator_stats, enclave_module_cache_stats, EnclaveModuleCacheStats,
+};
+pub use execution_receipts::take_execution_receipt;
+pub use launch_config::{is_mainnet_chain_id, validate_enclave_launch, MAINNET_CHAIN_IDS};
+pub use observed_events::take_observed_events;
+pub use upgrade_schedule::{
+    configure_upgrade_schedule, Behavior, UpgradeSchedule, UPGRADE_SCHEDULE_VERSION,
+};
 /*
 pub use crate::modules::FileSystemCache;
 */
 pub use crate::serde::{from_slice, to_vec};
-pub use crate::traits::{Api, Extern, Querier, Storage};
+pub use crate::store_migrations::{store_migrations, MigrationRegistry, StoreMigration};
+pub use crate::traits::{Api, Extern, Querier, Storage, StorageTier};
 
 #[cfg(feature = "iterator")]
 pub use crate::traits::StorageIterator;
 
 // Secret Network specific exports
 pub use crate::attestation::{
-    create_attestation_report_u, untrusted_get_encrypted_genesis_seed, untrusted_get_encrypted_seed,
+    create_attestation_report_u, untrusted_get_encrypted_genesis_seed,
+    untrusted_get_encrypted_previous_seeds, untrusted_get_encrypted_seed,
 };
 pub use crate::seed::{
-    untrusted_health_check, untrusted_init_bootstrap, untrusted_init_node, untrusted_key_gen,
+    untrusted_health_check, untrusted_init_bootstrap, untrusted_init_node,
+    untrusted_init_previous_seeds, untrusted_key_gen,
+};
+pub use crate::seed_rotation::{
+    untrusted_seed_rotation_activate, untrusted_seed_rotation_approve,
+    untrusted_seed_rotation_generate_candidate, untrusted_seed_rotation_verify_approval,
+    SeedRotationCeremony,
+};
+pub use crate::seed_shares::{
+    untrusted_combine_seed_shares, untrusted_reshare_seed_share, untrusted_split_consensus_seed,
 };
 
+pub use crate::contract_key_proofs::{untrusted_prove_contract_key, EnclaveContractKeyProof};
+pub use crate::query_rewrap::untrusted_rewrap_query;
 pub use crate::random::untrusted_submit_block_signatures;
+pub use crate::state_key_decryption::untrusted_decrypt_state_keys;
+pub use crate::tx_decryption::untrusted_decrypt_tx_msgs;
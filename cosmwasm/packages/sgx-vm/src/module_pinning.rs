@@ -0,0 +1,124 @@
+use sgx_types::*;
+
+use log::debug;
+
+use crate::checksum::Checksum;
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_pin_module(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        code_hash: *const u8,
+    ) -> sgx_status_t;
+
+    pub fn ecall_unpin_module(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        code_hash: *const u8,
+    ) -> sgx_status_t;
+
+    pub fn ecall_evict_module(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        code_hash: *const u8,
+    ) -> sgx_status_t;
+
+    pub fn ecall_prewarm_module(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        contract: *const u8,
+        contract_len: usize,
+    ) -> sgx_status_t;
+}
+
+/// Asks the enclave to keep the module it builds for `checksum` across executions, or to stop
+/// keeping it, whatever the size of its module cache. The enclave refuses to pin more than a few
+/// dozen modules with `SGX_ERROR_OUT_OF_MEMORY`.
+///
+/// Only the current enclave is asked, a legacy enclave runs old blocks and doesn't need it.
+pub(crate) fn untrusted_set_module_pinned(checksum: &Checksum, pinned: bool) -> SgxResult<()> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let status = unsafe {
+        if pinned {
+            ecall_pin_module(eid, &mut retval, checksum.as_slice().as_ptr())
+        } else {
+            ecall_unpin_module(eid, &mut retval, checksum.as_slice().as_ptr())
+        }
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!("Enclave set module {} pinned: {}", checksum, pinned);
+
+    Ok(())
+}
+
+/// Asks the enclave to drop the module it built for `checksum`, whether it is pinned or only in
+/// its module cache, once the code was removed.
+pub(crate) fn untrusted_evict_module(checksum: &Checksum) -> SgxResult<()> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let status = unsafe { ecall_evict_module(eid, &mut retval, checksum.as_slice().as_ptr()) };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!("Enclave evicted module {}", checksum);
+
+    Ok(())
+}
+
+/// Asks the enclave to build the module of `wasm`, the stored Wasm of `checksum`, and keep it like
+/// the first execution of one of its contracts would, pinned or in its module cache. The enclave
+/// fails with `SGX_ERROR_INVALID_PARAMETER` for code it only builds when it runs.
+pub(crate) fn untrusted_prewarm_module(checksum: &Checksum, wasm: &[u8]) -> SgxResult<()> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let status = unsafe { ecall_prewarm_module(eid, &mut retval, wasm.as_ptr(), wasm.len()) };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!("Enclave prewarmed module {}", checksum);
+
+    Ok(())
+}
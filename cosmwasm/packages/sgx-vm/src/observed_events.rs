@@ -0,0 +1,34 @@
+use std::collections::VecDeque;
+
+use lazy_static::lazy_static;
+use log::*;
+use parking_lot::Mutex;
+
+/// Records beyond this are dropped, oldest first, when the node doesn't drain the queue
+const MAX_QUEUED_OBSERVED_EVENTS: usize = 1024;
+
+lazy_static! {
+    /// The events the enclave encrypted to contract observers, as json records
+    static ref OBSERVED_EVENTS: Mutex<VecDeque<Vec<u8>>> = Mutex::new(VecDeque::new());
+}
+
+pub(crate) fn record_observed_events(record: Vec<u8>) {
+    trace!(
+        "Enclave encrypted {} bytes of events to an observer",
+        record.len()
+    );
+    let mut queue = OBSERVED_EVENTS.lock();
+    if queue.len() >= MAX_QUEUED_OBSERVED_EVENTS {
+        warn!("Dropping observed events, the node doesn't stream them");
+        queue.pop_front();
+    }
+    queue.push_back(record);
+}
+
+/// Takes the events the enclave encrypted to contract observers since the last call.
+///
+/// The enclave only encrypts events when it is built with the `observer-mode` feature, for the
+/// contracts it has an observer for. Each record is a json object.
+pub fn take_observed_events() -> Vec<Vec<u8>> {
+    OBSERVED_EVENTS.lock().drain(..).collect()
+}
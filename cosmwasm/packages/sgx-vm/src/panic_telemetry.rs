@@ -0,0 +1,176 @@
+//! Counts the traps of contracts per code hash, so that operators can spot a newly stored contract
+//! that keeps failing.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use log::*;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::checksum::Checksum;
+use crate::errors::{EnclaveError, VmError};
+
+/// Code hashes beyond this evict the one whose contracts trapped the longest ago
+const MAX_TRACKED_CODE_HASHES: usize = 256;
+/// The message and the log of a code hash are updated at most this often, its count always is
+const MESSAGE_INTERVAL: Duration = Duration::from_secs(10);
+/// In chars
+const MAX_MESSAGE_LENGTH: usize = 256;
+
+struct PanicEntry {
+    count: u64,
+    last_message: String,
+    last_panic: Instant,
+    /// `None` until the first message is recorded
+    last_message_update: Option<Instant>,
+}
+
+lazy_static! {
+    static ref CONTRACT_PANICS: Mutex<HashMap<Checksum, PanicEntry>> = Mutex::new(HashMap::new());
+}
+
+/// The traps of the contracts of a code hash since the node started
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContractPanicStats {
+    /// Hex encoded
+    pub code_hash: String,
+    pub count: u64,
+    /// A recent trap message, truncated and without control characters
+    pub last_message: String,
+}
+
+fn is_contract_panic(error: &enclave_ffi_types::EnclaveError) -> bool {
+    use enclave_ffi_types::EnclaveError::*;
+    matches!(
+        error,
+        ContractPanicUnreachable
+            | ContractPanicMemoryAccessOutOfBounds
+            | ContractPanicTableAccessOutOfBounds
+            | ContractPanicElemUninitialized
+            | ContractPanicDivisionByZero
+            | ContractPanicInvalidConversionToInt
+            | ContractPanicStackOverflow
+            | ContractPanicIntegerOverflow
+            | ContractPanicUnexpectedSignature
+    )
+}
+
+fn sanitize(message: &str) -> String {
+    message
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_MESSAGE_LENGTH)
+        .collect()
+}
+
+/// Counts `error` against the code hash of `wasm` if it is a trap of the contract. Other errors,
+/// like running out of gas, are not the contract malfunctioning.
+pub(crate) fn record_contract_panic(wasm: &[u8], error: &VmError) {
+    match error {
+        VmError::EnclaveErr {
+            source: EnclaveError::EnclaveErr { error, .. },
+        } if is_contract_panic(error) => {}
+        _ => return,
+    }
+
+    let code_hash = Checksum::generate(wasm);
+    let now = Instant::now();
+    let mut panics = CONTRACT_PANICS.lock();
+    if !panics.contains_key(&code_hash) && panics.len() >= MAX_TRACKED_CODE_HASHES {
+        let oldest = panics
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_panic)
+            .map(|(code_hash, _)| *code_hash);
+        if let Some(oldest) = oldest {
+            panics.remove(&oldest);
+        }
+    }
+
+    let entry = panics.entry(code_hash).or_insert_with(|| PanicEntry {
+        count: 0,
+        last_message: String::new(),
+        last_panic: now,
+        last_message_update: None,
+    });
+    entry.count += 1;
+    entry.last_panic = now;
+    let message_due = entry.last_message_update.map_or(true, |updated| {
+        now.duration_since(updated) >= MESSAGE_INTERVAL
+    });
+    if message_due {
+        entry.last_message = sanitize(&error.to_string());
+        entry.last_message_update = Some(now);
+        warn!(
+            "Contracts of code hash {} trapped {} times so far, last: {}",
+            code_hash, entry.count, entry.last_message
+        );
+    }
+}
+
+/// Returns the traps of contracts per code hash since the node started, most frequent first.
+/// Only the code hashes whose contracts trapped most recently are kept.
+pub fn contract_panic_stats() -> Vec<ContractPanicStats> {
+    let mut stats: Vec<ContractPanicStats> = CONTRACT_PANICS
+        .lock()
+        .iter()
+        .map(|(code_hash, entry)| ContractPanicStats {
+            code_hash: code_hash.to_hex(),
+            count: entry.count,
+            last_message: entry.last_message.clone(),
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.code_hash.cmp(&b.code_hash))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record_contract_panic_works() {
+        let wasm = b"record_contract_panic_works";
+        let code_hash = Checksum::generate(wasm).to_hex();
+        let stats_of = || {
+            contract_panic_stats()
+                .into_iter()
+                .find(|stats| stats.code_hash == code_hash)
+        };
+
+        // not traps
+        record_contract_panic(wasm, &VmError::GasDepletion);
+        record_contract_panic(
+            wasm,
+            &enclave_ffi_types::EnclaveError::FailedToDeserialize.into(),
+        );
+        assert_eq!(stats_of(), None);
+
+        let unreachable: VmError = enclave_ffi_types::EnclaveError::ContractPanicUnreachable.into();
+        record_contract_panic(wasm, &unreachable);
+        record_contract_panic(
+            wasm,
+            &enclave_ffi_types::EnclaveError::ContractPanicDivisionByZero.into(),
+        );
+        // the message is only updated once per interval
+        assert_eq!(
+            stats_of(),
+            Some(ContractPanicStats {
+                code_hash: code_hash.clone(),
+                count: 2,
+                last_message: unreachable.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn sanitize_works() {
+        assert_eq!(sanitize("trap\n\u{1b}[31mred"), "trap[31mred");
+        assert_eq!(sanitize(&"a".repeat(1000)).len(), MAX_MESSAGE_LENGTH);
+    }
+}
@@ -0,0 +1,143 @@
+//! Keeps the recently parsed Wasm modules by code hash. The same code is parsed when it is stored,
+//! analyzed, introspected and revalidated, which for large contracts costs more than hashing it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+
+use crate::checksum::Checksum;
+use crate::errors::VmResult;
+use crate::wasm_module::WasmModule;
+
+/// Code hashes beyond this evict the least recently used one
+const MAX_PARSED_MODULES: usize = 64;
+
+struct ParsedModule {
+    module: Arc<WasmModule>,
+    last_used: u64,
+}
+
+struct ParsedModules {
+    modules: HashMap<Checksum, ParsedModule>,
+    capacity: usize,
+    /// Incremented on every use, orders the entries by recency
+    clock: u64,
+}
+
+impl ParsedModules {
+    fn new(capacity: usize) -> Self {
+        ParsedModules {
+            modules: HashMap::new(),
+            capacity,
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, checksum: &Checksum) -> Option<Arc<WasmModule>> {
+        self.clock += 1;
+        let entry = self.modules.get_mut(checksum)?;
+        entry.last_used = self.clock;
+        Some(entry.module.clone())
+    }
+
+    fn insert(&mut self, checksum: Checksum, module: Arc<WasmModule>) {
+        self.clock += 1;
+        if !self.modules.contains_key(&checksum) && self.modules.len() >= self.capacity {
+            let oldest = self
+                .modules
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(checksum, _)| *checksum);
+            if let Some(oldest) = oldest {
+                self.modules.remove(&oldest);
+            }
+        }
+        self.modules.insert(
+            checksum,
+            ParsedModule {
+                module,
+                last_used: self.clock,
+            },
+        );
+    }
+}
+
+lazy_static! {
+    static ref PARSED_MODULES: Mutex<ParsedModules> =
+        Mutex::new(ParsedModules::new(MAX_PARSED_MODULES));
+}
+
+/// Like `WasmModule::from_bytes`, reusing the module parsed from the same code if it is still
+/// cached. Code that fails to parse is not cached.
+pub(crate) fn parse_module(wasm: &[u8]) -> VmResult<Arc<WasmModule>> {
+    parse_module_with_checksum(&Checksum::generate(wasm), wasm)
+}
+
+/// Like `parse_module`, for callers that already know the checksum of `wasm`
+pub(crate) fn parse_module_with_checksum(
+    checksum: &Checksum,
+    wasm: &[u8],
+) -> VmResult<Arc<WasmModule>> {
+    if let Some(module) = PARSED_MODULES.lock().get(checksum) {
+        return Ok(module);
+    }
+    // parsed without holding the lock, contracts are validated in parallel
+    let module = Arc::new(WasmModule::from_bytes(wasm)?);
+    PARSED_MODULES.lock().insert(*checksum, module.clone());
+    Ok(module)
+}
+
+/// Drops the module parsed from the code of `checksum`, once the code was removed
+pub(crate) fn forget_parsed_module(checksum: &Checksum) {
+    PARSED_MODULES.lock().modules.remove(checksum);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wabt::wat2wasm;
+
+    #[test]
+    fn parse_module_works() {
+        let wasm = wat2wasm(
+            r#"(module
+                (memory 1)
+                (export "parse_module_works" (memory 0))
+            )"#,
+        )
+        .unwrap();
+        let first = parse_module(&wasm).unwrap();
+        assert_eq!(first.exports()[0].name, "parse_module_works");
+        let second = parse_module(&wasm).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // errors are not cached
+        let invalid = b"parse_module_works";
+        parse_module(invalid).unwrap_err();
+        assert!(PARSED_MODULES
+            .lock()
+            .get(&Checksum::generate(invalid))
+            .is_none());
+
+        forget_parsed_module(&Checksum::generate(&wasm));
+        let third = parse_module(&wasm).unwrap();
+        assert!(!Arc::ptr_eq(&first, &third));
+    }
+
+    #[test]
+    fn parsed_modules_evicts_least_recently_used() {
+        let checksums: Vec<Checksum> = (0u8..3).map(|i| Checksum::generate(&[i])).collect();
+        let mut modules = ParsedModules::new(2);
+        modules.insert(checksums[0], Arc::new(WasmModule::default()));
+        modules.insert(checksums[1], Arc::new(WasmModule::default()));
+        assert!(modules.get(&checksums[0]).is_some());
+
+        modules.insert(checksums[2], Arc::new(WasmModule::default()));
+        assert!(modules.get(&checksums[0]).is_some());
+        assert!(modules.get(&checksums[1]).is_none());
+        assert!(modules.get(&checksums[2]).is_some());
+        assert_eq!(modules.modules.len(), 2);
+    }
+}
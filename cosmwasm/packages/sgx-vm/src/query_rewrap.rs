@@ -0,0 +1,108 @@
+use sgx_types::*;
+
+use log::debug;
+
+use crate::checksum::Checksum;
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_rewrap_query(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        contract_address: *const u8,
+        contract_address_len: usize,
+        previous_code_hash: *const u8,
+        og_contract_key: *const u8,
+        previous_current_contract_key: *const u8,
+        previous_current_contract_key_len: usize,
+        current_code_hash: *const u8,
+        current_contract_key: *const u8,
+        migrator: *const u8,
+        migrator_len: usize,
+        migration_height: u64,
+        current_height: u64,
+        grace_blocks: u64,
+        msg: *const u8,
+        msg_len: usize,
+        rewrapped_msg: *mut u8,
+    ) -> sgx_status_t;
+}
+
+/// Asks the enclave to re-wrap an encrypted query addressed to the previous code of a migrated
+/// contract, so that it can run against the current code.
+///
+/// `og_contract_key` and `previous_current_contract_key` are the keys the contract had when it ran
+/// `previous_code_hash`: its original key, and its key and migration proof if it had already been
+/// migrated then. `current_contract_key` is the key and migration proof `migrator` generated when
+/// it migrated the contract at `migration_height`. Fails with `SGX_ERROR_INVALID_PARAMETER` if
+/// `msg` did not opt in or is not addressed to the previous code, or if the migration is more than
+/// `grace_blocks` before `current_height`, which enclaves verifying blocks take from the last one.
+#[allow(clippy::too_many_arguments)]
+pub fn untrusted_rewrap_query(
+    contract_address: &[u8],
+    previous_code_hash: &Checksum,
+    og_contract_key: &[u8; 64],
+    previous_current_contract_key: Option<(&[u8; 64], &[u8; 32])>,
+    current_code_hash: &Checksum,
+    current_contract_key: (&[u8; 64], &[u8; 32]),
+    migrator: &[u8],
+    migration_height: u64,
+    current_height: u64,
+    grace_blocks: u64,
+    msg: &[u8],
+) -> SgxResult<Vec<u8>> {
+    let previous_current = match previous_current_contract_key {
+        Some((key, proof)) => [&key[..], &proof[..]].concat(),
+        None => vec![],
+    };
+    let current = [&current_contract_key.0[..], &current_contract_key.1[..]].concat();
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut rewrapped_msg = vec![0u8; msg.len()];
+    let status = unsafe {
+        ecall_rewrap_query(
+            eid,
+            &mut retval,
+            contract_address.as_ptr(),
+            contract_address.len(),
+            previous_code_hash.as_slice().as_ptr(),
+            og_contract_key.as_ptr(),
+            previous_current.as_ptr(),
+            previous_current.len(),
+            current_code_hash.as_slice().as_ptr(),
+            current.as_ptr(),
+            migrator.as_ptr(),
+            migrator.len(),
+            migration_height,
+            current_height,
+            grace_blocks,
+            msg.as_ptr(),
+            msg.len(),
+            rewrapped_msg.as_mut_ptr(),
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!(
+        "Enclave re-wrapped a query to {} for code hash {}",
+        hex::encode(contract_address),
+        current_code_hash
+    );
+
+    Ok(rewrapped_msg)
+}
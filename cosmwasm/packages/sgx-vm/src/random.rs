@@ -2,7 +2,7 @@ use sgx_types::*;
 
 use log::{debug, error, warn};
 
-use crate::enclave::ENCLAVE_DOORBELL;
+use crate::enclave::doorbell_for_height;
 
 extern "C" {
     pub fn ecall_submit_block_signatures(
@@ -24,7 +24,10 @@ extern "C" {
     ) -> sgx_status_t;
 }
 
+/// `height` is the height of the block in `header`. It selects the enclave that executes the
+/// block, which is the one that needs its random seed.
 pub fn untrusted_submit_block_signatures(
+    height: u64,
     header: &[u8],
     commit: &[u8],
     txs: &[u8],
@@ -39,7 +42,7 @@ pub fn untrusted_submit_block_signatures(
     // this is here so we can
     loop {
         let (retval, decrypted, status) =
-            submit_block_signature_impl(header, commit, txs, encrypted_random)?;
+            submit_block_signature_impl(height, header, commit, txs, encrypted_random)?;
         if status != sgx_status_t::SGX_SUCCESS {
             return Err(status);
         } else if retval != sgx_status_t::SGX_SUCCESS {
@@ -65,6 +68,7 @@ pub fn untrusted_submit_block_signatures(
 }
 
 fn submit_block_signature_impl(
+    height: u64,
     header: &[u8],
     commit: &[u8],
     txs: &[u8],
@@ -72,7 +76,7 @@ fn submit_block_signature_impl(
 ) -> SgxResult<(sgx_status_t, [u8; 32], sgx_status_t)> {
     // Bind the token to a local variable to ensure its
     // destructor runs in the end of the function
-    let enclave_access_token = ENCLAVE_DOORBELL
+    let enclave_access_token = doorbell_for_height(height)
         .get_access(1) // This can never be recursive
         .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
     let enclave = (*enclave_access_token)?;
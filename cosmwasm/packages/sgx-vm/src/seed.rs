@@ -1,6 +1,7 @@
 use enclave_ffi_types::{
-    HealthCheckResult, INPUT_ENCRYPTED_SEED_SIZE, NEWLY_FORMED_DOUBLE_ENCRYPTED_SEED_SIZE,
-    NEWLY_FORMED_SINGLE_ENCRYPTED_SEED_SIZE,
+    HealthCheckResult, INPUT_ENCRYPTED_SEED_SIZE, MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE,
+    NEWLY_FORMED_DOUBLE_ENCRYPTED_SEED_SIZE, NEWLY_FORMED_SINGLE_ENCRYPTED_SEED_SIZE,
+    SINGLE_ENCRYPTED_SEED_SIZE,
 };
 use sgx_types::*;
 
@@ -20,6 +21,15 @@ extern "C" {
         api_key_len: u32,
     ) -> sgx_status_t;
 
+    pub fn ecall_init_previous_seeds(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        master_key: *const u8,
+        master_key_len: u32,
+        encrypted_seeds: *const u8,
+        encrypted_seeds_len: u32,
+    ) -> sgx_status_t;
+
     pub fn ecall_init_bootstrap(
         eid: sgx_enclave_id_t,
         retval: *mut sgx_status_t,
@@ -130,6 +140,48 @@ pub fn untrusted_init_node(
     Ok(())
 }
 
+/// Seals the seeds that were current before each seed rotation, as shared with the node when it
+/// registered. Called after `untrusted_init_node`, only if the node got any.
+pub fn untrusted_init_previous_seeds(master_key: &[u8], encrypted_seeds: &[u8]) -> SgxResult<()> {
+    if encrypted_seeds.len() % SINGLE_ENCRYPTED_SEED_SIZE != 0
+        || encrypted_seeds.len() > MAX_OUTPUT_ENCRYPTED_PREVIOUS_SEEDS_SIZE
+    {
+        error!("Received previous seeds with wrong length");
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut ret = sgx_status_t::SGX_SUCCESS;
+
+    let status = unsafe {
+        ecall_init_previous_seeds(
+            eid,
+            &mut ret,
+            master_key.as_ptr(),
+            master_key.len() as u32,
+            encrypted_seeds.as_ptr(),
+            encrypted_seeds.len() as u32,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if ret != sgx_status_t::SGX_SUCCESS {
+        return Err(ret);
+    }
+
+    Ok(())
+}
+
 pub fn untrusted_key_gen() -> SgxResult<[u8; 32]> {
     info!("Initializing enclave..");
 
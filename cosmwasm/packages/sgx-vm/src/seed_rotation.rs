@@ -0,0 +1,440 @@
+use std::collections::BTreeMap;
+
+use enclave_ffi_types::{
+    NodeAuthResult, PUBLIC_KEY_SIZE, SEED_ROTATION_APPROVAL_SIZE, SINGLE_ENCRYPTED_SEED_SIZE,
+};
+use sgx_types::*;
+
+use log::{debug, info, warn};
+use sha2::{Digest, Sha256};
+
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_seed_rotation_generate_candidate(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        activation_height: u64,
+        encrypted_candidate: &mut [u8; SINGLE_ENCRYPTED_SEED_SIZE],
+        hash: &mut [u8; 32],
+    ) -> sgx_status_t;
+
+    pub fn ecall_seed_rotation_approve(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        encrypted_candidate: *const u8,
+        activation_height: u64,
+        approval: &mut [u8; SEED_ROTATION_APPROVAL_SIZE],
+    ) -> sgx_status_t;
+
+    pub fn ecall_seed_rotation_verify_approval(
+        eid: sgx_enclave_id_t,
+        retval: *mut NodeAuthResult,
+        encrypted_candidate: *const u8,
+        activation_height: u64,
+        approval: *const u8,
+        cert: *const u8,
+        cert_len: u32,
+    ) -> sgx_status_t;
+
+    pub fn ecall_seed_rotation_activate(
+        eid: sgx_enclave_id_t,
+        retval: *mut NodeAuthResult,
+        encrypted_candidate: *const u8,
+        activation_height: u64,
+        current_height: u64,
+        threshold: u32,
+        approvals: *const u8,
+        approvals_len: u32,
+        certs: *const u8,
+        certs_len: u32,
+        restart_required: *mut u8,
+    ) -> sgx_status_t;
+}
+
+/// Generates a candidate for the next consensus seed. Returns the encrypted candidate and its hash,
+/// which is how the candidate is referred to during the ceremony.
+pub fn untrusted_seed_rotation_generate_candidate(
+    activation_height: u64,
+) -> SgxResult<([u8; SINGLE_ENCRYPTED_SEED_SIZE], [u8; 32])> {
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut encrypted_candidate = [0u8; SINGLE_ENCRYPTED_SEED_SIZE];
+    let mut hash = [0u8; 32];
+    let status = unsafe {
+        ecall_seed_rotation_generate_candidate(
+            eid,
+            &mut retval,
+            activation_height,
+            &mut encrypted_candidate,
+            &mut hash,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    Ok((encrypted_candidate, hash))
+}
+
+/// Asks this node's enclave to approve a candidate
+pub fn untrusted_seed_rotation_approve(
+    encrypted_candidate: &[u8],
+    activation_height: u64,
+) -> SgxResult<[u8; SEED_ROTATION_APPROVAL_SIZE]> {
+    if encrypted_candidate.len() != SINGLE_ENCRYPTED_SEED_SIZE {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut approval = [0u8; SEED_ROTATION_APPROVAL_SIZE];
+    let status = unsafe {
+        ecall_seed_rotation_approve(
+            eid,
+            &mut retval,
+            encrypted_candidate.as_ptr(),
+            activation_height,
+            &mut approval,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    Ok(approval)
+}
+
+/// Verifies that `approval` was made for this candidate and height by the enclave that `cert`, its
+/// attestation certificate, belongs to
+pub fn untrusted_seed_rotation_verify_approval(
+    encrypted_candidate: &[u8],
+    activation_height: u64,
+    approval: &[u8],
+    cert: &[u8],
+) -> SgxResult<Result<(), NodeAuthResult>> {
+    if encrypted_candidate.len() != SINGLE_ENCRYPTED_SEED_SIZE
+        || approval.len() != SEED_ROTATION_APPROVAL_SIZE
+        || cert.len() > u32::MAX as usize
+    {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = NodeAuthResult::Success;
+    let status = unsafe {
+        ecall_seed_rotation_verify_approval(
+            eid,
+            &mut retval,
+            encrypted_candidate.as_ptr(),
+            activation_height,
+            approval.as_ptr(),
+            cert.as_ptr(),
+            cert.len() as u32,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != NodeAuthResult::Success {
+        debug!("Error from seed rotation approval verification, bad NodeAuthResult");
+        return Ok(Err(retval));
+    }
+
+    Ok(Ok(()))
+}
+
+/// Seals the candidate as the node's current consensus seed, if at least `threshold` distinct
+/// attested nodes approved it. `approvals` are concatenated approvals, and `certs` the attestation
+/// certificates of the nodes that made them, in the same order. The enclave refuses to activate
+/// before `activation_height`.
+///
+/// Returns whether the node must be restarted to start using the new seed.
+pub fn untrusted_seed_rotation_activate(
+    encrypted_candidate: &[u8],
+    activation_height: u64,
+    current_height: u64,
+    threshold: u32,
+    approvals: &[u8],
+    certs: &[&[u8]],
+) -> SgxResult<Result<bool, NodeAuthResult>> {
+    if encrypted_candidate.len() != SINGLE_ENCRYPTED_SEED_SIZE
+        || approvals.len() != certs.len() * SEED_ROTATION_APPROVAL_SIZE
+        || approvals.len() > u32::MAX as usize
+    {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    let mut certs_bytes = vec![];
+    for cert in certs {
+        if cert.len() > u32::MAX as usize {
+            return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+        }
+        certs_bytes.extend_from_slice(&(cert.len() as u32).to_be_bytes());
+        certs_bytes.extend_from_slice(cert);
+    }
+    if certs_bytes.len() > u32::MAX as usize {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = NodeAuthResult::Success;
+    let mut restart_required = 0u8;
+    let status = unsafe {
+        ecall_seed_rotation_activate(
+            eid,
+            &mut retval,
+            encrypted_candidate.as_ptr(),
+            activation_height,
+            current_height,
+            threshold,
+            approvals.as_ptr(),
+            approvals.len() as u32,
+            certs_bytes.as_ptr(),
+            certs_bytes.len() as u32,
+            &mut restart_required,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != NodeAuthResult::Success {
+        debug!("Error from seed rotation activation, bad NodeAuthResult");
+        return Ok(Err(retval));
+    }
+
+    Ok(Ok(restart_required != 0))
+}
+
+/// Tracks a single seed rotation candidate through the ceremony: collecting approvals from
+/// validators' enclaves until `threshold` distinct nodes approved it, then activating it.
+#[derive(Clone, Debug)]
+pub struct SeedRotationCeremony {
+    encrypted_candidate: [u8; SINGLE_ENCRYPTED_SEED_SIZE],
+    activation_height: u64,
+    threshold: usize,
+    /// Verified approvals and the attestation certificates of the nodes that made them, keyed by
+    /// the approving node's public key
+    approvals: BTreeMap<[u8; PUBLIC_KEY_SIZE], ([u8; SEED_ROTATION_APPROVAL_SIZE], Vec<u8>)>,
+}
+
+impl SeedRotationCeremony {
+    pub fn new(
+        encrypted_candidate: [u8; SINGLE_ENCRYPTED_SEED_SIZE],
+        activation_height: u64,
+        threshold: usize,
+    ) -> Self {
+        SeedRotationCeremony {
+            encrypted_candidate,
+            activation_height,
+            threshold,
+            approvals: BTreeMap::new(),
+        }
+    }
+
+    pub fn encrypted_candidate(&self) -> &[u8; SINGLE_ENCRYPTED_SEED_SIZE] {
+        &self.encrypted_candidate
+    }
+
+    pub fn candidate_hash(&self) -> [u8; 32] {
+        Sha256::digest(&self.encrypted_candidate).into()
+    }
+
+    pub fn activation_height(&self) -> u64 {
+        self.activation_height
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Verifies `approval` against the attestation certificate `cert` of the node that made it in
+    /// the enclave, and records it. Returns false if the node already approved this candidate.
+    pub fn add_approval(
+        &mut self,
+        approval: &[u8],
+        cert: &[u8],
+    ) -> SgxResult<Result<bool, NodeAuthResult>> {
+        if let Err(e) = untrusted_seed_rotation_verify_approval(
+            &self.encrypted_candidate,
+            self.activation_height,
+            approval,
+            cert,
+        )? {
+            warn!(
+                "Rejected approval for seed rotation candidate {}: {}",
+                hex::encode(self.candidate_hash()),
+                e
+            );
+            return Ok(Err(e));
+        }
+
+        let mut verified = [0u8; SEED_ROTATION_APPROVAL_SIZE];
+        verified.copy_from_slice(approval);
+        Ok(Ok(self.insert_verified_approval(verified, cert.to_vec())))
+    }
+
+    fn insert_verified_approval(
+        &mut self,
+        approval: [u8; SEED_ROTATION_APPROVAL_SIZE],
+        cert: Vec<u8>,
+    ) -> bool {
+        let mut node = [0u8; PUBLIC_KEY_SIZE];
+        node.copy_from_slice(&approval[..PUBLIC_KEY_SIZE]);
+
+        if self.approvals.contains_key(&node) {
+            return false;
+        }
+        self.approvals.insert(node, (approval, cert));
+
+        info!(
+            "Seed rotation candidate {} approved by node {} ({}/{})",
+            hex::encode(self.candidate_hash()),
+            hex::encode(node),
+            self.approvals.len(),
+            self.threshold
+        );
+        true
+    }
+
+    pub fn approval_count(&self) -> usize {
+        self.approvals.len()
+    }
+
+    /// The public keys of the nodes that approved the candidate, in a stable order for auditing
+    pub fn approvers(&self) -> Vec<[u8; PUBLIC_KEY_SIZE]> {
+        self.approvals.keys().copied().collect()
+    }
+
+    pub fn is_approved(&self) -> bool {
+        self.threshold != 0 && self.approvals.len() >= self.threshold
+    }
+
+    /// Activates the candidate in this node's enclave, once it has enough approvals and the chain
+    /// reached the activation height. The enclave verifies the approvals again and enforces the
+    /// threshold itself. Returns whether the node must be restarted to start using the new seed.
+    pub fn activate(&self, current_height: u64) -> SgxResult<Result<bool, NodeAuthResult>> {
+        if !self.is_approved() {
+            warn!(
+                "Seed rotation candidate {} only has {}/{} approvals",
+                hex::encode(self.candidate_hash()),
+                self.approvals.len(),
+                self.threshold
+            );
+            return Err(sgx_status_t::SGX_ERROR_INVALID_STATE);
+        }
+
+        let mut approvals = vec![];
+        let mut certs = vec![];
+        for (approval, cert) in self.approvals.values() {
+            approvals.extend_from_slice(approval);
+            certs.push(cert.as_slice());
+        }
+        let restart_required = match untrusted_seed_rotation_activate(
+            &self.encrypted_candidate,
+            self.activation_height,
+            current_height,
+            self.threshold as u32,
+            &approvals,
+            &certs,
+        )? {
+            Ok(restart_required) => restart_required,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        info!(
+            "Activated seed rotation candidate {} at height {}",
+            hex::encode(self.candidate_hash()),
+            current_height
+        );
+        Ok(Ok(restart_required))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn approval(node: u8) -> [u8; SEED_ROTATION_APPROVAL_SIZE] {
+        let mut approval = [0u8; SEED_ROTATION_APPROVAL_SIZE];
+        approval[..PUBLIC_KEY_SIZE].copy_from_slice(&[node; PUBLIC_KEY_SIZE]);
+        approval[PUBLIC_KEY_SIZE..].copy_from_slice(&[0xaa; 32]);
+        approval
+    }
+
+    #[test]
+    fn approvals_are_deduplicated_by_node() {
+        let mut ceremony = SeedRotationCeremony::new([0u8; SINGLE_ENCRYPTED_SEED_SIZE], 100, 2);
+
+        assert!(ceremony.insert_verified_approval(approval(1), vec![1]));
+        assert!(!ceremony.insert_verified_approval(approval(1), vec![1]));
+        assert_eq!(ceremony.approval_count(), 1);
+        assert!(!ceremony.is_approved());
+
+        assert!(ceremony.insert_verified_approval(approval(2), vec![2]));
+        assert_eq!(ceremony.approval_count(), 2);
+        assert!(ceremony.is_approved());
+        assert_eq!(
+            ceremony.approvers(),
+            vec![[1u8; PUBLIC_KEY_SIZE], [2u8; PUBLIC_KEY_SIZE]]
+        );
+    }
+
+    #[test]
+    fn zero_threshold_is_never_approved() {
+        let mut ceremony = SeedRotationCeremony::new([0u8; SINGLE_ENCRYPTED_SEED_SIZE], 100, 0);
+        ceremony.insert_verified_approval(approval(1), vec![1]);
+        assert!(!ceremony.is_approved());
+    }
+
+    #[test]
+    fn activate_requires_threshold() {
+        let ceremony = SeedRotationCeremony::new([0u8; SINGLE_ENCRYPTED_SEED_SIZE], 100, 1);
+        assert_eq!(
+            ceremony.activate(100).unwrap_err(),
+            sgx_status_t::SGX_ERROR_INVALID_STATE
+        );
+    }
+}
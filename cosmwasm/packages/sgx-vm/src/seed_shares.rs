@@ -0,0 +1,182 @@
+use enclave_ffi_types::{
+    NodeAuthResult, ENCRYPTED_SEED_SHARE_SIZE, PUBLIC_KEY_SIZE, RESHARED_SEED_SHARE_SIZE,
+};
+use sgx_types::*;
+
+use log::{debug, info};
+
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_split_consensus_seed(
+        eid: sgx_enclave_id_t,
+        retval: *mut NodeAuthResult,
+        threshold: u8,
+        recipient_certs: *const u8,
+        recipient_certs_len: u32,
+        shares: *mut u8,
+        shares_len: u32,
+    ) -> sgx_status_t;
+
+    pub fn ecall_reshare_seed_share(
+        eid: sgx_enclave_id_t,
+        retval: *mut NodeAuthResult,
+        master_key: *const u8,
+        encrypted_share: *const u8,
+        cert: *const u8,
+        cert_len: u32,
+        reshared: &mut [u8; RESHARED_SEED_SHARE_SIZE],
+    ) -> sgx_status_t;
+
+    pub fn ecall_combine_seed_shares(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        master_key: *const u8,
+        reshared: *const u8,
+        reshared_len: u32,
+    ) -> sgx_status_t;
+}
+
+/// Splits the consensus seeds into one encrypted share per recipient, `threshold` of which are
+/// needed to reconstruct them. The recipients are given by their attestation certificates, which
+/// the enclave verifies before encrypting a share to the registration key found in each.
+pub fn untrusted_split_consensus_seed(
+    threshold: u8,
+    recipient_certs: &[&[u8]],
+) -> SgxResult<Result<Vec<Vec<u8>>, NodeAuthResult>> {
+    let mut certs_bytes = vec![];
+    for cert in recipient_certs {
+        if cert.len() > u32::MAX as usize {
+            return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+        }
+        certs_bytes.extend_from_slice(&(cert.len() as u32).to_be_bytes());
+        certs_bytes.extend_from_slice(cert);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = NodeAuthResult::Success;
+    let mut shares = vec![0u8; recipient_certs.len() * ENCRYPTED_SEED_SHARE_SIZE];
+    let status = unsafe {
+        ecall_split_consensus_seed(
+            eid,
+            &mut retval,
+            threshold,
+            certs_bytes.as_ptr(),
+            certs_bytes.len() as u32,
+            shares.as_mut_ptr(),
+            shares.len() as u32,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != NodeAuthResult::Success {
+        debug!("Error from split consensus seed, bad NodeAuthResult");
+        return Ok(Err(retval));
+    }
+
+    info!(
+        "Split the consensus seeds into {} shares with a threshold of {}",
+        recipient_certs.len(),
+        threshold
+    );
+
+    Ok(Ok(shares
+        .chunks_exact(ENCRYPTED_SEED_SHARE_SIZE)
+        .map(|share| share.to_vec())
+        .collect()))
+}
+
+/// Re-encrypts this node's share for the node that owns the attestation certificate `cert`
+pub fn untrusted_reshare_seed_share(
+    master_key: &[u8],
+    encrypted_share: &[u8],
+    cert: &[u8],
+) -> SgxResult<Result<[u8; RESHARED_SEED_SHARE_SIZE], NodeAuthResult>> {
+    if master_key.len() != PUBLIC_KEY_SIZE || encrypted_share.len() != ENCRYPTED_SEED_SHARE_SIZE {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = NodeAuthResult::Success;
+    let mut reshared = [0u8; RESHARED_SEED_SHARE_SIZE];
+    let status = unsafe {
+        ecall_reshare_seed_share(
+            eid,
+            &mut retval,
+            master_key.as_ptr(),
+            encrypted_share.as_ptr(),
+            cert.as_ptr(),
+            cert.len() as u32,
+            &mut reshared,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != NodeAuthResult::Success {
+        debug!("Error from reshare seed share, bad NodeAuthResult");
+        return Ok(Err(retval));
+    }
+
+    Ok(Ok(reshared))
+}
+
+/// Reconstructs and seals the consensus seeds from shares re-encrypted for this node. Fails unless
+/// the seeds match `master_key`, the seed exchange key registered on chain.
+pub fn untrusted_combine_seed_shares(
+    master_key: &[u8],
+    reshared: &[[u8; RESHARED_SEED_SHARE_SIZE]],
+) -> SgxResult<()> {
+    if master_key.len() != PUBLIC_KEY_SIZE {
+        return Err(sgx_status_t::SGX_ERROR_INVALID_PARAMETER);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let reshared_bytes = reshared.concat();
+    let status = unsafe {
+        ecall_combine_seed_shares(
+            eid,
+            &mut retval,
+            master_key.as_ptr(),
+            reshared_bytes.as_ptr(),
+            reshared_bytes.len() as u32,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use sgx_types::*;
+
+use log::debug;
+
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_decrypt_state_keys(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        og_contract_key: *const u8,
+        keys: *const u8,
+        keys_len: usize,
+        features: *const u8,
+        features_len: usize,
+        decrypted_keys: *mut u8,
+    ) -> sgx_status_t;
+}
+
+/// The length the enclave returns for keys it can't decrypt
+const UNDECRYPTABLE_KEY: u32 = u32::MAX;
+
+/// Decrypts the state keys of the contract with `og_contract_key`, as they are stored, and
+/// returns the plaintext of each of them, or `None` for keys in the old format and the keys of
+/// value chunks.
+///
+/// Only enclaves built in software mode with the `state-key-decryption` feature decrypt state
+/// keys, and only if `features`, the active features in the JSON of the env of a call, include
+/// `state_key_decryption`. Others fail with `SGX_ERROR_FEATURE_NOT_SUPPORTED`.
+pub fn untrusted_decrypt_state_keys(
+    og_contract_key: &[u8; 64],
+    keys: &[&[u8]],
+    features: &[u8],
+) -> SgxResult<Vec<Option<Vec<u8>>>> {
+    let mut keys_bytes = vec![];
+    for key in keys {
+        let len =
+            u32::try_from(key.len()).map_err(|_| sgx_status_t::SGX_ERROR_INVALID_PARAMETER)?;
+        keys_bytes.extend_from_slice(&len.to_be_bytes());
+        keys_bytes.extend_from_slice(key);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut decrypted_bytes = vec![0u8; keys_bytes.len()];
+    let status = unsafe {
+        ecall_decrypt_state_keys(
+            eid,
+            &mut retval,
+            og_contract_key.as_ptr(),
+            keys_bytes.as_ptr(),
+            keys_bytes.len(),
+            features.as_ptr(),
+            features.len(),
+            decrypted_bytes.as_mut_ptr(),
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!("Decrypted {} state keys", keys.len());
+
+    // The output is padded with zeros, so only read as many keys as were sent
+    let mut decrypted = Vec::with_capacity(keys.len());
+    let mut rest = decrypted_bytes.as_slice();
+    for _ in keys {
+        if rest.len() < 4 {
+            return Err(sgx_status_t::SGX_ERROR_UNEXPECTED);
+        }
+        let (len, tail) = rest.split_at(4);
+        let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]);
+        if len == UNDECRYPTABLE_KEY {
+            decrypted.push(None);
+            rest = tail;
+            continue;
+        }
+
+        let len = len as usize;
+        if tail.len() < len {
+            return Err(sgx_status_t::SGX_ERROR_UNEXPECTED);
+        }
+        let (key, tail) = tail.split_at(len);
+        decrypted.push(Some(key.to_vec()));
+        rest = tail;
+    }
+    Ok(decrypted)
+}
@@ -0,0 +1,219 @@
+//! Versioned migrations of the files the VM keeps in its data dir.
+//!
+//! Every directory of the data dir the VM owns (a prefix, e.g. `wasm`) records the version of its
+//! layout in a `.store_version` file. A change to that layout is a `StoreMigration` to the next
+//! version, registered in `store_migrations`. Pending migrations are applied when the cache is
+//! opened, which for a coordinated upgrade is when the new binary starts at the upgrade height.
+//!
+//! Only the data dir is covered. The VM owns no prefix of the chain store: registration data and
+//! code metadata are kept by the Go modules, and migrate with their consensus versions.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use log::*;
+
+use crate::errors::{VmError, VmResult};
+
+const VERSION_FILE: &str = ".store_version";
+
+/// A change to the layout of one prefix of the data dir.
+pub struct StoreMigration {
+    /// The directory migrated, relative to the data dir
+    pub prefix: &'static str,
+    /// The version of the prefix after this migration. The first migration of a prefix is
+    /// version 1, and every following one increases it by one.
+    pub version: u32,
+    pub description: &'static str,
+    /// Rewrites the directory of the prefix in place. It gets the directory, which may be empty.
+    pub migrate: fn(&Path) -> VmResult<()>,
+}
+
+#[derive(Default)]
+pub struct MigrationRegistry {
+    migrations: Vec<StoreMigration>,
+}
+
+impl MigrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, migration: StoreMigration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// The version a prefix has once every registered migration is applied
+    pub fn latest_version(&self, prefix: &str) -> u32 {
+        self.migrations_of(prefix)
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn migrations_of<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a StoreMigration> {
+        self.migrations
+            .iter()
+            .filter(move |migration| migration.prefix == prefix)
+    }
+
+    fn prefixes(&self) -> Vec<&'static str> {
+        let mut prefixes: Vec<_> = self.migrations.iter().map(|m| m.prefix).collect();
+        prefixes.sort_unstable();
+        prefixes.dedup();
+        prefixes
+    }
+
+    /// Applies the pending migrations of every prefix under `data_dir`, in version order.
+    ///
+    /// The version of a prefix is recorded after each migration, so a node interrupted in the
+    /// middle resumes from the migration that didn't complete.
+    pub fn run(&self, data_dir: &Path) -> VmResult<()> {
+        for prefix in self.prefixes() {
+            let dir = data_dir.join(prefix);
+            fs::create_dir_all(&dir).map_err(|e| {
+                VmError::cache_err(format!(
+                    "Error creating {} dir for migration: {}",
+                    prefix, e
+                ))
+            })?;
+
+            let current = read_version(&dir)?;
+            let latest = self.latest_version(prefix);
+            if current > latest {
+                return Err(VmError::cache_err(format!(
+                    "The {} store is at version {}, but this node only knows up to version {}",
+                    prefix, current, latest
+                )));
+            }
+
+            let mut pending: Vec<_> = self
+                .migrations_of(prefix)
+                .filter(|migration| migration.version > current)
+                .collect();
+            pending.sort_by_key(|migration| migration.version);
+
+            let mut version = current;
+            for migration in pending {
+                if migration.version != version + 1 {
+                    return Err(VmError::cache_err(format!(
+                        "Missing migration of the {} store from version {} to {}",
+                        prefix,
+                        version,
+                        version + 1
+                    )));
+                }
+                info!(
+                    "Migrating the {} store to version {}: {}",
+                    prefix, migration.version, migration.description
+                );
+                (migration.migrate)(&dir)?;
+                write_version(&dir, migration.version)?;
+                version = migration.version;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The migrations of this build. A prefix without a version file is at version 0.
+pub fn store_migrations() -> MigrationRegistry {
+    MigrationRegistry::new()
+}
+
+fn read_version(dir: &Path) -> VmResult<u32> {
+    let path = dir.join(VERSION_FILE);
+    if !path.exists() {
+        return Ok(0);
+    }
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| VmError::cache_err(format!("Error reading store version: {}", e)))?;
+    contents.trim().parse::<u32>().map_err(|_| {
+        VmError::cache_err(format!(
+            "Invalid store version {:?} in {}",
+            contents,
+            path.display()
+        ))
+    })
+}
+
+/// Writes the version to a temporary file first, so the version file is never left half written
+fn write_version(dir: &Path, version: u32) -> VmResult<()> {
+    let tmp_path = dir.join(format!("{}.tmp", VERSION_FILE));
+    let mut file = File::create(&tmp_path)
+        .map_err(|e| VmError::cache_err(format!("Error writing store version: {}", e)))?;
+    file.write_all(version.to_string().as_bytes())
+        .and_then(|_| file.sync_all())
+        .and_then(|_| fs::rename(&tmp_path, dir.join(VERSION_FILE)))
+        .map_err(|e| VmError::cache_err(format!("Error writing store version: {}", e)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Counts how many times it ran in the `runs` file
+    fn count_run(dir: &Path) -> VmResult<()> {
+        let runs = fs::read(dir.join("runs")).unwrap_or_default();
+        fs::write(dir.join("runs"), [runs, vec![b'.']].concat()).unwrap();
+        Ok(())
+    }
+
+    fn fail(_dir: &Path) -> VmResult<()> {
+        Err(VmError::cache_err("failed"))
+    }
+
+    fn migration(version: u32, migrate: fn(&Path) -> VmResult<()>) -> StoreMigration {
+        StoreMigration {
+            prefix: "wasm",
+            version,
+            description: "test",
+            migrate,
+        }
+    }
+
+    #[test]
+    fn run_applies_pending_migrations_once() {
+        let tmp_dir = TempDir::new().unwrap();
+        let registry = MigrationRegistry::new()
+            .register(migration(2, count_run))
+            .register(migration(1, count_run));
+        assert_eq!(registry.latest_version("wasm"), 2);
+        assert_eq!(registry.latest_version("other"), 0);
+
+        registry.run(tmp_dir.path()).unwrap();
+        let dir = tmp_dir.path().join("wasm");
+        assert_eq!(read_version(&dir).unwrap(), 2);
+        assert_eq!(fs::read(dir.join("runs")).unwrap(), b"..");
+
+        // Nothing is pending anymore
+        registry.run(tmp_dir.path()).unwrap();
+        assert_eq!(fs::read(dir.join("runs")).unwrap(), b"..");
+    }
+
+    #[test]
+    fn run_stops_at_failed_migration() {
+        let tmp_dir = TempDir::new().unwrap();
+        let registry = MigrationRegistry::new()
+            .register(migration(1, count_run))
+            .register(migration(2, fail));
+
+        registry.run(tmp_dir.path()).unwrap_err();
+        assert_eq!(read_version(&tmp_dir.path().join("wasm")).unwrap(), 1);
+    }
+
+    #[test]
+    fn run_rejects_gaps_and_newer_stores() {
+        let tmp_dir = TempDir::new().unwrap();
+        let registry = MigrationRegistry::new().register(migration(2, count_run));
+        registry.run(tmp_dir.path()).unwrap_err();
+
+        let dir = tmp_dir.path().join("wasm");
+        write_version(&dir, 3).unwrap();
+        let registry = registry.register(migration(1, count_run));
+        registry.run(tmp_dir.path()).unwrap_err();
+    }
+}
@@ -4,7 +4,7 @@
 use cosmwasm_std::{Coin, HumanAddr};
 use std::collections::HashSet;
 
-use crate::compatability::check_wasm;
+use crate::compatability::{check_wasm, WasmValidationConfig};
 use crate::features::features_from_csv;
 use crate::instance::Instance;
 use crate::Extern;
@@ -102,7 +102,12 @@ pub fn mock_instance_with_options(
     wasm: &[u8],
     options: MockInstanceOptions,
 ) -> Instance<MockStorage, MockApi, MockQuerier> {
-    check_wasm(wasm, &options.supported_features).unwrap();
+    check_wasm(
+        wasm,
+        &options.supported_features,
+        &WasmValidationConfig::default(),
+    )
+    .unwrap();
     let contract_address = HumanAddr::from(MOCK_CONTRACT_ADDR);
 
     // merge balances
@@ -141,6 +141,20 @@ mod test {
         assert_eq!(None, store.get(b"food").0.unwrap());
     }
 
+    #[test]
+    fn set_multiple() {
+        let mut store = MockStorage::new();
+        let entries = vec![
+            (b"foo".to_vec(), b"bar".to_vec()),
+            (b"food".to_vec(), b"bank".to_vec()),
+            (b"foo".to_vec(), b"baz".to_vec()),
+        ];
+        store.set_multiple(&entries).0.unwrap();
+
+        assert_eq!(Some(b"baz".to_vec()), store.get(b"foo").0.unwrap());
+        assert_eq!(Some(b"bank".to_vec()), store.get(b"food").0.unwrap());
+    }
+
     #[test]
     fn delete() {
         let mut store = MockStorage::new();
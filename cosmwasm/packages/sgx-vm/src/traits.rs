@@ -2,9 +2,7 @@ use cosmwasm_std::{Binary, CanonicalAddr, HumanAddr, StdResult, SystemResult};
 #[cfg(feature = "iterator")]
 use cosmwasm_std::{Order, KV};
 
-#[cfg(feature = "iterator")]
-use crate::ffi::FfiError;
-use crate::ffi::FfiResult;
+use crate::ffi::{FfiError, FfiResult, GasInfo};
 
 /// Holds all external dependencies of the contract.
 /// Designed to allow easy dependency injection at runtime.
@@ -86,11 +84,54 @@ where
 
     fn set(&mut self, key: &[u8], value: &[u8]) -> FfiResult<()>;
 
+    /// Writes `entries` in order, stopping at the first failure.
+    ///
+    /// A contract call flushes all its writes at once, so embedders behind an FFI boundary can
+    /// override this to cross it once per call instead of once per key. The writes of a call can't
+    /// be held back further, e.g. until the end of the block, since they must land in the store of
+    /// their transaction for the embedder to revert them with it.
+    fn set_multiple(&mut self, entries: &[(Vec<u8>, Vec<u8>)]) -> FfiResult<()> {
+        let mut externally_used = 0;
+        for (key, value) in entries {
+            let (result, gas_info) = self.set(key, value);
+            externally_used += gas_info.externally_used;
+            if result.is_err() {
+                return (result, GasInfo::with_externally_used(externally_used));
+            }
+        }
+        (Ok(()), GasInfo::with_externally_used(externally_used))
+    }
+
     /// Removes a database entry at `key`.
     ///
     /// The current interface does not allow to differentiate between a key that existed
     /// before and one that didn't exist. See https://github.com/CosmWasm/cosmwasm/issues/290
     fn remove(&mut self, key: &[u8]) -> FfiResult<()>;
+
+    /// The tier keys missing from this storage are read from, if the embedder moved rarely read
+    /// keys out of it.
+    fn cold_tier(&mut self) -> Option<&mut dyn StorageTier> {
+        None
+    }
+}
+
+/// A cheaper backend to which an embedder moves rarely read keys of a storage, e.g. on archival
+/// nodes with huge state. Contracts read them transparently.
+///
+/// Which keys a node moves is up to the node, so a read must cost the same whichever tier the key
+/// is in: the tier itself charges nothing, and a key found in it is charged by `charge_read` as if
+/// the storage had it. Writes and removals of a key go to the storage and drop the key from the
+/// tier, so that a stale value never shows through.
+pub trait StorageTier {
+    /// Returns Ok(None) when the key isn't in this tier either. Must not consume gas.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, FfiError>;
+
+    /// Charges the gas the storage charges for reading `value` at `key`, on top of what it already
+    /// charged when it didn't find `key`, and reports it as externally used.
+    fn charge_read(&mut self, key: &[u8], value: &[u8]) -> FfiResult<()>;
+
+    /// Drops `key`, once it was written to or removed from the storage. Must not consume gas.
+    fn remove(&mut self, key: &[u8]) -> Result<(), FfiError>;
 }
 
 /// Api are callbacks to system functions defined outside of the wasm modules.
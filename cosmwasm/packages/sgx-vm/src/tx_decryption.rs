@@ -0,0 +1,75 @@
+use enclave_ffi_types::MsgDecryptionStatus;
+use sgx_types::*;
+
+use log::debug;
+
+use crate::enclave::ENCLAVE_DOORBELL;
+
+extern "C" {
+    pub fn ecall_decrypt_tx_msgs(
+        eid: sgx_enclave_id_t,
+        retval: *mut sgx_status_t,
+        msgs: *const u8,
+        msgs_len: usize,
+        statuses: *mut u8,
+        statuses_len: u32,
+        keep_plaintexts: u8,
+    ) -> sgx_status_t;
+}
+
+/// Decrypts the encrypted messages of a transaction in one ecall, and returns whether each of
+/// them could be decrypted.
+///
+/// With `keep_plaintexts`, for transactions being delivered, the enclave keeps the plaintext of the
+/// messages it decrypted, so executing them right after doesn't decrypt them again.
+pub fn untrusted_decrypt_tx_msgs(
+    msgs: &[&[u8]],
+    keep_plaintexts: bool,
+) -> SgxResult<Vec<MsgDecryptionStatus>> {
+    let mut msgs_bytes = vec![];
+    for msg in msgs {
+        let len =
+            u32::try_from(msg.len()).map_err(|_| sgx_status_t::SGX_ERROR_INVALID_PARAMETER)?;
+        msgs_bytes.extend_from_slice(&len.to_be_bytes());
+        msgs_bytes.extend_from_slice(msg);
+    }
+
+    // Bind the token to a local variable to ensure its
+    // destructor runs in the end of the function
+    let enclave_access_token = ENCLAVE_DOORBELL
+        .get_access(1) // This can never be recursive
+        .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
+    let enclave = (*enclave_access_token)?;
+
+    let eid = enclave.geteid();
+    let mut retval = sgx_status_t::SGX_SUCCESS;
+    let mut statuses = vec![0u8; msgs.len()];
+    let status = unsafe {
+        ecall_decrypt_tx_msgs(
+            eid,
+            &mut retval,
+            msgs_bytes.as_ptr(),
+            msgs_bytes.len(),
+            statuses.as_mut_ptr(),
+            statuses.len() as u32,
+            keep_plaintexts as u8,
+        )
+    };
+
+    if status != sgx_status_t::SGX_SUCCESS {
+        return Err(status);
+    }
+
+    if retval != sgx_status_t::SGX_SUCCESS {
+        return Err(retval);
+    }
+
+    debug!("Decrypted the {} messages of a transaction", msgs.len());
+
+    statuses
+        .into_iter()
+        .map(|status| {
+            MsgDecryptionStatus::from_u8(status).ok_or(sgx_status_t::SGX_ERROR_UNEXPECTED)
+        })
+        .collect()
+}
@@ -0,0 +1,182 @@
+//! The heights at which the behavior of the VM changed.
+//!
+//! Replaying a block must give the result it had when it was first executed, so a behavior that
+//! changes in a chain upgrade has to keep its old form for the blocks before the upgrade. Instead
+//! of comparing heights where the behavior is implemented, every such change is a `Behavior`
+//! whose activation height is listed in the `UpgradeSchedule` the node runs with.
+//!
+//! The node passes the schedule to `init_cache`, from its configuration.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use log::*;
+use parking_lot::RwLock;
+use serde::Deserialize;
+
+use crate::errors::{VmError, VmResult};
+
+/// The version of the schedule built by `UpgradeSchedule::from_json`.
+/// Bump it whenever a `Behavior` is added.
+pub const UPGRADE_SCHEDULE_VERSION: u32 = 1;
+
+/// A behavior of the VM that changed at some height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Behavior {
+    /// Contract calls and block signatures go to the current enclave. Before this activates they
+    /// go to the legacy enclave, if the node loaded one (see `load_legacy_enclave`).
+    CurrentEnclave,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Activation {
+    behavior: Behavior,
+    height: u64,
+}
+
+/// The heights at which each `Behavior` activated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeSchedule {
+    version: u32,
+    activations: Vec<Activation>,
+}
+
+impl UpgradeSchedule {
+    /// An empty schedule, in which every behavior is active from genesis.
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            activations: vec![],
+        }
+    }
+
+    /// Returns the schedule with `behavior` activating at `height`, replacing any previous
+    /// activation height of that behavior.
+    pub fn activate(mut self, behavior: Behavior, height: u64) -> Self {
+        self.activations
+            .retain(|activation| activation.behavior != behavior);
+        self.activations.push(Activation { behavior, height });
+        self
+    }
+
+    /// Parses the activation height of each behavior from a JSON object, such as
+    /// `{"current_enclave":100}`. Behaviors it doesn't list are active from genesis, so empty
+    /// input is the default schedule.
+    pub fn from_json(json: &[u8]) -> VmResult<Self> {
+        if json.is_empty() {
+            return Ok(Self::default());
+        }
+        let heights: BTreeMap<Behavior, u64> = serde_json::from_slice(json)
+            .map_err(|e| VmError::parse_err("UpgradeSchedule", e.to_string()))?;
+        Ok(heights.into_iter().fold(
+            Self::new(UPGRADE_SCHEDULE_VERSION),
+            |schedule, (behavior, height)| schedule.activate(behavior, height),
+        ))
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The height at which `behavior` activated, or `None` if it is active from genesis.
+    pub fn activation_height(&self, behavior: Behavior) -> Option<u64> {
+        self.activations
+            .iter()
+            .find(|activation| activation.behavior == behavior)
+            .map(|activation| activation.height)
+    }
+
+    /// Whether `behavior` applies to a call made at `height`.
+    pub fn is_active(&self, behavior: Behavior, height: u64) -> bool {
+        match self.activation_height(behavior) {
+            Some(activation_height) => height >= activation_height,
+            None => true,
+        }
+    }
+}
+
+impl Default for UpgradeSchedule {
+    fn default() -> Self {
+        Self::new(UPGRADE_SCHEDULE_VERSION)
+    }
+}
+
+lazy_static! {
+    static ref UPGRADE_SCHEDULE: RwLock<Arc<UpgradeSchedule>> =
+        RwLock::new(Arc::new(UpgradeSchedule::default()));
+}
+
+/// Sets the schedule the VM runs with. This is done once, when the VM is constructed.
+pub fn configure_upgrade_schedule(schedule: UpgradeSchedule) {
+    info!(
+        "Using upgrade schedule version {}: {:?}",
+        schedule.version, schedule.activations
+    );
+    *UPGRADE_SCHEDULE.write() = Arc::new(schedule);
+}
+
+pub(crate) fn upgrade_schedule() -> Arc<UpgradeSchedule> {
+    UPGRADE_SCHEDULE.read().clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn behaviors_without_activation_are_always_active() {
+        let schedule = UpgradeSchedule::new(1);
+        assert_eq!(schedule.activation_height(Behavior::CurrentEnclave), None);
+        assert!(schedule.is_active(Behavior::CurrentEnclave, 0));
+        assert!(schedule.is_active(Behavior::CurrentEnclave, u64::MAX));
+    }
+
+    #[test]
+    fn behaviors_activate_at_their_height() {
+        let schedule = UpgradeSchedule::new(1).activate(Behavior::CurrentEnclave, 100);
+        assert_eq!(
+            schedule.activation_height(Behavior::CurrentEnclave),
+            Some(100)
+        );
+        assert!(!schedule.is_active(Behavior::CurrentEnclave, 0));
+        assert!(!schedule.is_active(Behavior::CurrentEnclave, 99));
+        assert!(schedule.is_active(Behavior::CurrentEnclave, 100));
+        assert!(schedule.is_active(Behavior::CurrentEnclave, 101));
+    }
+
+    #[test]
+    fn from_json_works() {
+        assert_eq!(
+            UpgradeSchedule::from_json(b"").unwrap(),
+            UpgradeSchedule::default()
+        );
+        assert_eq!(
+            UpgradeSchedule::from_json(b"{}").unwrap(),
+            UpgradeSchedule::default()
+        );
+
+        let schedule = UpgradeSchedule::from_json(br#"{"current_enclave":100}"#).unwrap();
+        assert_eq!(schedule.version(), UPGRADE_SCHEDULE_VERSION);
+        assert_eq!(
+            schedule.activation_height(Behavior::CurrentEnclave),
+            Some(100)
+        );
+
+        assert!(UpgradeSchedule::from_json(br#"{"unknown_behavior":100}"#).is_err());
+        assert!(UpgradeSchedule::from_json(br#"{"current_enclave":"100"}"#).is_err());
+    }
+
+    #[test]
+    fn activate_replaces_previous_height() {
+        let schedule = UpgradeSchedule::new(2)
+            .activate(Behavior::CurrentEnclave, 100)
+            .activate(Behavior::CurrentEnclave, 200);
+        assert_eq!(schedule.version(), 2);
+        assert_eq!(
+            schedule.activation_height(Behavior::CurrentEnclave),
+            Some(200)
+        );
+    }
+}
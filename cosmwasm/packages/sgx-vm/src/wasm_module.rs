@@ -0,0 +1,800 @@
+//! A parsed Wasm module, holding just what the static checks on upload need.
+//!
+//! This hides the parser in use (currently wasmparser) from the rest of the VM.
+//! Error messages of the parser are translated back to the ones parity-wasm,
+//! the previous parser, used to return, since they end up in transaction results.
+
+use std::fmt;
+
+use wasmparser::{
+    BinaryReaderError, ExternalKind, FuncType, FunctionBody, Operator, Parser, Payload, Type,
+    TypeRef, ValType, Validator, WasmFeatures,
+};
+
+use crate::errors::{ValidationFailure, VmError, VmResult};
+
+/// wasmparser message prefixes and the parity-wasm message that replaces them
+const PARITY_WASM_ERROR_MESSAGES: &[(&str, &str)] = &[
+    ("unexpected end-of-file", "Unexpected end of input"),
+    (
+        "magic header not detected",
+        "Invalid magic number at start of file",
+    ),
+    ("unknown binary version", "Unsupported wasm version"),
+    ("section out of order", "Sections out of order"),
+    ("malformed UTF-8 encoding", "Non-UTF-8 string"),
+    (
+        "function and code section have inconsistent lengths",
+        "Number of function body entries and signatures does not match",
+    ),
+    ("illegal opcode", "Unknown opcode"),
+];
+
+/// A Wasm proposal the enclave doesn't support: its name, how to enable it in wasmparser and the
+/// wasm-opt flag enabling it
+type UnsupportedProposal = (&'static str, fn(&mut WasmFeatures), &'static str);
+
+/// The unsupported proposals newer toolchains may use
+const UNSUPPORTED_PROPOSALS: &[UnsupportedProposal] = &[
+    (
+        "bulk memory",
+        |f: &mut WasmFeatures| f.bulk_memory = true,
+        "--enable-bulk-memory",
+    ),
+    (
+        "reference types",
+        |f: &mut WasmFeatures| f.reference_types = true,
+        "--enable-reference-types",
+    ),
+    (
+        "multi-value",
+        |f: &mut WasmFeatures| f.multi_value = true,
+        "--enable-multivalue",
+    ),
+    (
+        "non-trapping float-to-int conversions",
+        |f: &mut WasmFeatures| f.saturating_float_to_int = true,
+        "--enable-nontrapping-float-to-int",
+    ),
+    (
+        "SIMD",
+        |f: &mut WasmFeatures| f.simd = true,
+        "--enable-simd",
+    ),
+    (
+        "tail calls",
+        |f: &mut WasmFeatures| f.tail_call = true,
+        "--enable-tail-call",
+    ),
+    (
+        "multi-memory",
+        |f: &mut WasmFeatures| f.multi_memory = true,
+        "--enable-multimemory",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmImport {
+    pub module: String,
+    pub name: String,
+    pub is_function: bool,
+}
+
+impl WasmImport {
+    /// `module.name`, the form used in the lists of supported imports
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.module, self.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmExport {
+    pub name: String,
+    pub is_function: bool,
+    /// In the index space of its kind
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmMemory {
+    /// In pages
+    pub initial: u64,
+    /// In pages
+    pub maximum: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmValueType {
+    I32,
+    I64,
+    F32,
+    F64,
+    V128,
+    /// `funcref` or `externref`
+    Reference,
+}
+
+impl From<ValType> for WasmValueType {
+    fn from(ty: ValType) -> Self {
+        match ty {
+            ValType::I32 => WasmValueType::I32,
+            ValType::I64 => WasmValueType::I64,
+            ValType::F32 => WasmValueType::F32,
+            ValType::F64 => WasmValueType::F64,
+            ValType::V128 => WasmValueType::V128,
+            _ => WasmValueType::Reference,
+        }
+    }
+}
+
+impl fmt::Display for WasmValueType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            WasmValueType::I32 => "i32",
+            WasmValueType::I64 => "i64",
+            WasmValueType::F32 => "f32",
+            WasmValueType::F64 => "f64",
+            WasmValueType::V128 => "v128",
+            WasmValueType::Reference => "ref",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmFunctionType {
+    pub params: Vec<WasmValueType>,
+    pub results: Vec<WasmValueType>,
+}
+
+impl WasmFunctionType {
+    /// A function taking `params` i32 and returning `results` i32, the only type CosmWasm exports
+    /// use
+    pub fn i32s(params: usize, results: usize) -> Self {
+        WasmFunctionType {
+            params: vec![WasmValueType::I32; params],
+            results: vec![WasmValueType::I32; results],
+        }
+    }
+}
+
+impl From<&FuncType> for WasmFunctionType {
+    fn from(ty: &FuncType) -> Self {
+        WasmFunctionType {
+            params: ty.params().iter().map(|&t| t.into()).collect(),
+            results: ty.results().iter().map(|&t| t.into()).collect(),
+        }
+    }
+}
+
+/// In the notation of the Wasm spec, e.g. `[i32, i32] -> [i32]`
+impl fmt::Display for WasmFunctionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |types: &[WasmValueType]| -> String {
+            let names: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+            names.join(", ")
+        };
+        write!(f, "[{}] -> [{}]", join(&self.params), join(&self.results))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmCustomSection {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WasmModule {
+    imports: Vec<WasmImport>,
+    exports: Vec<WasmExport>,
+    /// `None` if the module has no memory section at all
+    memories: Option<Vec<WasmMemory>>,
+    custom_sections: Vec<WasmCustomSection>,
+    /// The function types of the type section, in order
+    types: Vec<WasmFunctionType>,
+    /// The index in `types` of the type of each function, imports included
+    function_types: Vec<u32>,
+    /// Indices of the functions using floating point instructions, imports included
+    float_functions: Vec<u32>,
+    /// In bytes, of the functions defined by the module, in order
+    function_body_sizes: Vec<usize>,
+    /// The initial sizes of the tables defined by the module, in elements
+    table_sizes: Vec<u32>,
+    /// In bytes, of the data segments, in order
+    data_segment_sizes: Vec<usize>,
+    /// The index of the start function, if the module has a start section
+    start_function: Option<u32>,
+}
+
+impl WasmModule {
+    /// Parses the structure of `wasm`, without validating function bodies.
+    /// See `validate` for that.
+    pub fn from_bytes(wasm: &[u8]) -> VmResult<Self> {
+        Self::parse(wasm).map_err(|err| deserialization_err(wasm, &err, false))
+    }
+
+    fn parse(wasm: &[u8]) -> Result<Self, BinaryReaderError> {
+        let mut module = WasmModule::default();
+        let mut function_index = 0;
+
+        for payload in Parser::new(0).parse_all(wasm) {
+            match payload? {
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import?;
+                        module.imports.push(WasmImport {
+                            module: import.module.to_string(),
+                            name: import.name.to_string(),
+                            is_function: matches!(import.ty, TypeRef::Func(_)),
+                        });
+                        if let TypeRef::Func(type_index) = import.ty {
+                            module.function_types.push(type_index);
+                            function_index += 1;
+                        }
+                    }
+                }
+                Payload::TypeSection(reader) => {
+                    for ty in reader {
+                        match ty? {
+                            Type::Func(func_type) => module.types.push((&func_type).into()),
+                        }
+                    }
+                }
+                Payload::FunctionSection(reader) => {
+                    for type_index in reader {
+                        module.function_types.push(type_index?);
+                    }
+                }
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export?;
+                        module.exports.push(WasmExport {
+                            name: export.name.to_string(),
+                            is_function: export.kind == ExternalKind::Func,
+                            index: export.index,
+                        });
+                    }
+                }
+                Payload::MemorySection(reader) => {
+                    let mut memories = vec![];
+                    for memory in reader {
+                        let memory = memory?;
+                        memories.push(WasmMemory {
+                            initial: memory.initial,
+                            maximum: memory.maximum,
+                        });
+                    }
+                    module.memories = Some(memories);
+                }
+                Payload::TableSection(reader) => {
+                    for table in reader {
+                        module.table_sizes.push(table?.initial);
+                    }
+                }
+                Payload::DataSection(reader) => {
+                    for data in reader {
+                        module.data_segment_sizes.push(data?.data.len());
+                    }
+                }
+                Payload::StartSection { func, .. } => {
+                    module.start_function = Some(func);
+                }
+                Payload::CodeSectionEntry(body) => {
+                    module.function_body_sizes.push(body.range().len());
+                    if uses_floats(&body)? {
+                        module.float_functions.push(function_index);
+                    }
+                    function_index += 1;
+                }
+                Payload::CustomSection(reader) => {
+                    module.custom_sections.push(WasmCustomSection {
+                        name: reader.name().to_string(),
+                        data: reader.data().to_vec(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(module)
+    }
+
+    /// Fully validates `wasm`, which must be the code this module was parsed from.
+    /// Only the MVP plus the sign extension and mutable globals proposals are accepted,
+    /// which is what parity-wasm was built with, and the bulk memory proposal if `bulk_memory`
+    /// is set.
+    pub fn validate(&self, wasm: &[u8], bulk_memory: bool) -> VmResult<()> {
+        Validator::new_with_features(supported_features(bulk_memory))
+            .validate_all(wasm)
+            .map(|_| ())
+            .map_err(|err| deserialization_err(wasm, &err, bulk_memory))
+    }
+
+    pub fn imports(&self) -> &[WasmImport] {
+        &self.imports
+    }
+
+    pub fn exports(&self) -> &[WasmExport] {
+        &self.exports
+    }
+
+    pub fn memories(&self) -> Option<&[WasmMemory]> {
+        self.memories.as_deref()
+    }
+
+    /// Indices of the functions using f32 or f64 instructions, which are not deterministic
+    pub fn float_functions(&self) -> &[u32] {
+        &self.float_functions
+    }
+
+    /// The sizes of the bodies of the functions defined by the module, imports excluded
+    pub fn function_body_sizes(&self) -> &[usize] {
+        &self.function_body_sizes
+    }
+
+    /// The initial sizes of the tables defined by the module, in elements
+    pub fn table_sizes(&self) -> &[u32] {
+        &self.table_sizes
+    }
+
+    /// The sizes of the data segments of the module, in bytes
+    pub fn data_segment_sizes(&self) -> &[usize] {
+        &self.data_segment_sizes
+    }
+
+    /// The function the module runs when it is instantiated, if any
+    pub fn start_function(&self) -> Option<u32> {
+        self.start_function
+    }
+
+    /// The name a function is exported under, if any
+    pub fn function_export_name(&self, index: u32) -> Option<&str> {
+        self.exports
+            .iter()
+            .find(|export| export.is_function && export.index == index)
+            .map(|export| export.name.as_str())
+    }
+
+    /// The type of a function, in the index space of functions, imports included. `None` if the
+    /// index or its type index is out of bounds, which only `validate` rejects.
+    pub fn function_type(&self, index: u32) -> Option<&WasmFunctionType> {
+        let type_index = *self.function_types.get(index as usize)?;
+        self.types.get(type_index as usize)
+    }
+
+    /// The content of the first custom section called `name`
+    pub fn custom_section(&self, name: &str) -> Option<&[u8]> {
+        self.custom_sections
+            .iter()
+            .find(|section| section.name == name)
+            .map(|section| section.data.as_slice())
+    }
+}
+
+fn uses_floats(body: &FunctionBody) -> Result<bool, BinaryReaderError> {
+    let mut reader = body.get_operators_reader()?;
+    while !reader.eof() {
+        if is_float_operator(&reader.read()?) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn is_float_operator(operator: &Operator) -> bool {
+    use Operator::*;
+
+    matches!(
+        operator,
+        F32Load { .. }
+            | F64Load { .. }
+            | F32Store { .. }
+            | F64Store { .. }
+            | F32Const { .. }
+            | F64Const { .. }
+            | F32Eq
+            | F32Ne
+            | F32Lt
+            | F32Gt
+            | F32Le
+            | F32Ge
+            | F64Eq
+            | F64Ne
+            | F64Lt
+            | F64Gt
+            | F64Le
+            | F64Ge
+            | F32Abs
+            | F32Neg
+            | F32Ceil
+            | F32Floor
+            | F32Trunc
+            | F32Nearest
+            | F32Sqrt
+            | F32Add
+            | F32Sub
+            | F32Mul
+            | F32Div
+            | F32Min
+            | F32Max
+            | F32Copysign
+            | F64Abs
+            | F64Neg
+            | F64Ceil
+            | F64Floor
+            | F64Trunc
+            | F64Nearest
+            | F64Sqrt
+            | F64Add
+            | F64Sub
+            | F64Mul
+            | F64Div
+            | F64Min
+            | F64Max
+            | F64Copysign
+            | I32TruncF32S
+            | I32TruncF32U
+            | I32TruncF64S
+            | I32TruncF64U
+            | I64TruncF32S
+            | I64TruncF32U
+            | I64TruncF64S
+            | I64TruncF64U
+            | F32ConvertI32S
+            | F32ConvertI32U
+            | F32ConvertI64S
+            | F32ConvertI64U
+            | F32DemoteF64
+            | F64ConvertI32S
+            | F64ConvertI32U
+            | F64ConvertI64S
+            | F64ConvertI64U
+            | F64PromoteF32
+            | I32ReinterpretF32
+            | I64ReinterpretF64
+            | F32ReinterpretI32
+            | F64ReinterpretI64
+            | I32TruncSatF32S
+            | I32TruncSatF32U
+            | I32TruncSatF64S
+            | I32TruncSatF64U
+            | I64TruncSatF32S
+            | I64TruncSatF32U
+            | I64TruncSatF64S
+            | I64TruncSatF64U
+    )
+}
+
+/// The MVP plus the sign extension and mutable globals proposals, which is what parity-wasm was
+/// built with, and the bulk memory proposal if `bulk_memory` is set
+fn supported_features(bulk_memory: bool) -> WasmFeatures {
+    let mut features = WasmFeatures::default();
+    features.mutable_global = true;
+    features.sign_extension = true;
+    features.saturating_float_to_int = false;
+    features.reference_types = false;
+    features.multi_value = false;
+    features.bulk_memory = bulk_memory;
+    features.simd = false;
+    features.relaxed_simd = false;
+    features.threads = false;
+    features.tail_call = false;
+    features.multi_memory = false;
+    features.exceptions = false;
+    features.memory64 = false;
+    features.extended_const = false;
+    features.component_model = false;
+    features
+}
+
+fn deserialization_err(wasm: &[u8], err: &BinaryReaderError, bulk_memory: bool) -> VmError {
+    let used = unsupported_proposals(wasm, bulk_memory);
+    VmError::static_validation_failure(ValidationFailure::Deserialization {
+        error: parity_wasm_message(err),
+        unsupported_proposals: used.iter().map(|(name, _, _)| name.to_string()).collect(),
+        wasm_opt_flags: used.iter().map(|(_, _, flag)| flag.to_string()).collect(),
+    })
+}
+
+/// The unsupported proposals `wasm` uses, if supporting them would make it valid. Empty if it is
+/// invalid for another reason.
+fn unsupported_proposals(wasm: &[u8], bulk_memory: bool) -> Vec<UnsupportedProposal> {
+    let validates_with = |enabled: &dyn Fn(usize) -> bool| {
+        let mut features = supported_features(bulk_memory);
+        for (i, (_, enable, _)) in UNSUPPORTED_PROPOSALS.iter().enumerate() {
+            if enabled(i) {
+                enable(&mut features);
+            }
+        }
+        Validator::new_with_features(features)
+            .validate_all(wasm)
+            .is_ok()
+    };
+    if !validates_with(&|_| true) {
+        return vec![];
+    }
+
+    // The proposals without which it is invalid even with all the others
+    (0..UNSUPPORTED_PROPOSALS.len())
+        .filter(|&used| !validates_with(&|i| i != used))
+        .map(|i| UNSUPPORTED_PROPOSALS[i])
+        .collect()
+}
+
+fn parity_wasm_message(err: &BinaryReaderError) -> String {
+    let message = err.message();
+    PARITY_WASM_ERROR_MESSAGES
+        .iter()
+        .find(|(prefix, _)| message.starts_with(prefix))
+        .map_or_else(|| message.to_string(), |(_, parity)| parity.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wabt::wat2wasm;
+
+    #[test]
+    fn from_bytes_works() {
+        let wasm = wat2wasm(
+            r#"(module
+            (import "env" "db_read" (func (param i32) (result i32)))
+            (import "env" "some_global" (global i32))
+            (memory 3 5)
+            (table 7 anyfunc)
+            (func (export "allocate") (param i32) (result i32) get_local 0)
+            (global (export "some_global") i32 (i32.const 1))
+            (data (i32.const 0) "abc")
+            (data (i32.const 8) "")
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+
+        assert_eq!(
+            module.imports(),
+            &[
+                WasmImport {
+                    module: "env".to_string(),
+                    name: "db_read".to_string(),
+                    is_function: true,
+                },
+                WasmImport {
+                    module: "env".to_string(),
+                    name: "some_global".to_string(),
+                    is_function: false,
+                },
+            ]
+        );
+        assert_eq!(module.imports()[0].full_name(), "env.db_read");
+        assert_eq!(
+            module.exports(),
+            &[
+                WasmExport {
+                    name: "allocate".to_string(),
+                    is_function: true,
+                    index: 1,
+                },
+                WasmExport {
+                    name: "some_global".to_string(),
+                    is_function: false,
+                    index: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            module.memories(),
+            Some(
+                &[WasmMemory {
+                    initial: 3,
+                    maximum: Some(5)
+                }][..]
+            )
+        );
+        assert_eq!(module.table_sizes(), &[7]);
+        // local count, get_local 0, end
+        assert_eq!(module.function_body_sizes(), &[4]);
+        assert_eq!(module.data_segment_sizes(), &[3, 0]);
+        assert_eq!(module.function_type(0), Some(&WasmFunctionType::i32s(1, 1)));
+        assert_eq!(module.function_type(1), Some(&WasmFunctionType::i32s(1, 1)));
+        assert_eq!(module.function_type(2), None);
+    }
+
+    #[test]
+    fn function_type_display_works() {
+        assert_eq!(
+            WasmFunctionType::i32s(2, 1).to_string(),
+            "[i32, i32] -> [i32]"
+        );
+        let ty = WasmFunctionType {
+            params: vec![WasmValueType::I64, WasmValueType::F32],
+            results: vec![],
+        };
+        assert_eq!(ty.to_string(), "[i64, f32] -> []");
+    }
+
+    #[test]
+    fn from_bytes_finds_float_functions() {
+        let wasm = wat2wasm(
+            r#"(module
+            (import "env" "db_read" (func (param i32) (result i32)))
+            (func (param i32) (result i32) get_local 0)
+            (func (export "to_float") (param i32) (result i32)
+                get_local 0
+                f32.convert_s/i32
+                i32.reinterpret/f32)
+            (func (result f64) f64.const 1.5)
+        )"#,
+        )
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        assert_eq!(module.float_functions(), &[2, 3]);
+        assert_eq!(module.function_body_sizes().len(), 3);
+        assert_eq!(module.function_export_name(2), Some("to_float"));
+        assert_eq!(module.function_export_name(3), None);
+    }
+
+    #[test]
+    fn from_bytes_without_sections() {
+        let module = WasmModule::from_bytes(&wat2wasm("(module)").unwrap()).unwrap();
+        assert!(module.imports().is_empty());
+        assert!(module.exports().is_empty());
+        assert_eq!(module.memories(), None);
+        assert!(module.float_functions().is_empty());
+        assert!(module.function_body_sizes().is_empty());
+        assert!(module.table_sizes().is_empty());
+        assert!(module.data_segment_sizes().is_empty());
+        assert_eq!(module.start_function(), None);
+        assert_eq!(module.custom_section("name"), None);
+    }
+
+    #[test]
+    fn from_bytes_reads_start_function() {
+        let wasm = wat2wasm("(module (func) (func $init) (start $init))").unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        assert_eq!(module.start_function(), Some(1));
+    }
+
+    #[test]
+    fn from_bytes_reads_custom_sections() {
+        let mut wasm = wat2wasm("(module)").unwrap();
+        wasm.extend_from_slice(
+            &hex::decode(concat!(
+                "00",           // section type (custom)
+                "0b",           // section length
+                "04",           // name length
+                "736e6970",     // "snip"
+                "736e69703230", // "snip20"
+            ))
+            .unwrap(),
+        );
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        assert_eq!(module.custom_section("snip"), Some(&b"snip20"[..]));
+        assert_eq!(module.custom_section("other"), None);
+    }
+
+    #[test]
+    fn from_bytes_uses_parity_wasm_messages() {
+        match WasmModule::from_bytes(b"\0asm").unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => assert_eq!(
+                msg,
+                "Wasm bytecode could not be deserialized. Deserialization error: \"Unexpected end of input\""
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        match WasmModule::from_bytes(b"\0wasm\x01\0\0\0").unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => assert_eq!(
+                msg,
+                "Wasm bytecode could not be deserialized. Deserialization error: \"Invalid magic number at start of file\""
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_works() {
+        let wasm =
+            wat2wasm("(module (func (param i32) (result i32) get_local 0 i32.const 1 i32.add))")
+                .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        module.validate(&wasm, false).unwrap();
+    }
+
+    #[test]
+    fn validate_rejects_type_errors() {
+        // (module (func (result i32) i64.const 1)), generated manually because wat2wasm
+        // refuses to create invalid code
+        let wasm = hex::decode(concat!(
+            "0061736d", // magic bytes
+            "01000000", // binary version (uint32)
+            "0105",     // section type (type), section length
+            "01600001", // one function type with no params and one result
+            "7f",       // i32
+            "0302",     // section type (function), section length
+            "0100",     // one function of type 0
+            "0a06",     // section type (code), section length
+            "0104",     // one body of 4 bytes
+            "00",       // no locals
+            "4201",     // i64.const 1
+            "0b",       // end
+        ))
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        match module.validate(&wasm, false).unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => {
+                assert!(msg.starts_with("Wasm bytecode could not be deserialized."));
+                assert!(!msg.contains("proposals"));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_names_unsupported_proposals() {
+        // (module (memory 1) (func i32.const 0 i32.const 0 i32.const 0 memory.fill)), generated
+        // manually because wat2wasm doesn't know bulk memory
+        let wasm = hex::decode(concat!(
+            "0061736d",     // magic bytes
+            "01000000",     // binary version (uint32)
+            "0104",         // section type (type), section length
+            "01600000",     // one function type without params and results
+            "030201",       // section type (function), section length, one function
+            "00",           // of type 0
+            "0503",         // section type (memory), section length
+            "010001",       // one memory, min=1, max=unset
+            "0a0d",         // section type (code), section length
+            "010b",         // one body of 11 bytes
+            "00",           // no locals
+            "410041004100", // i32.const 0 (three times)
+            "fc0b00",       // memory.fill 0
+            "0b",           // end
+        ))
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        match module.validate(&wasm, false).unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => {
+                assert!(msg.starts_with("Wasm bytecode could not be deserialized."));
+                assert!(msg.contains("proposals the enclave doesn't support: bulk memory."));
+                assert!(msg.contains("-C target-cpu=mvp"));
+                assert!(msg.contains("--enable-bulk-memory"));
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_bulk_memory_if_enabled() {
+        // (module (memory 1) (func i32.const 0 i32.const 0 i32.const 0 memory.copy))
+        let wasm = hex::decode(concat!(
+            "0061736d",     // magic bytes
+            "01000000",     // binary version (uint32)
+            "0104",         // section type (type), section length
+            "01600000",     // one function type without params and results
+            "030201",       // section type (function), section length, one function
+            "00",           // of type 0
+            "0503",         // section type (memory), section length
+            "010001",       // one memory, min=1, max=unset
+            "0a0e",         // section type (code), section length
+            "010c",         // one body of 12 bytes
+            "00",           // no locals
+            "410041004100", // i32.const 0 (three times)
+            "fc0a0000",     // memory.copy 0 0
+            "0b",           // end
+        ))
+        .unwrap();
+        let module = WasmModule::from_bytes(&wasm).unwrap();
+        module.validate(&wasm, true).unwrap();
+        match module.validate(&wasm, false).unwrap_err() {
+            VmError::StaticValidationErr { failure, .. } => match failure {
+                ValidationFailure::Deserialization {
+                    unsupported_proposals,
+                    ..
+                } => assert_eq!(unsupported_proposals, vec!["bulk memory"]),
+                f => panic!("Unexpected failure: {:?}", f),
+            },
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
@@ -2,12 +2,15 @@ use std::ffi::c_void;
 
 use sgx_types::SgxResult;
 
-use enclave_ffi_types::{Ctx, EnclaveBuffer, OcallReturn, UntrustedVmError, UserSpaceBuffer};
+use enclave_ffi_types::{
+    AllocatorStats, CallMemoryStats, Ctx, EnclaveBuffer, ExecutionReceipt, ModuleCacheStats,
+    OcallReturn, UntrustedVmError, UserSpaceBuffer,
+};
 
 use cosmwasm_std::{Binary, StdResult, SystemResult};
 
 use crate::context::{with_querier_from_context, with_storage_from_context};
-use crate::{Querier, Storage, VmError, VmResult};
+use crate::{Querier, Storage, VmResult};
 
 #[no_mangle]
 pub extern "C" fn ocall_allocate(buffer: *const u8, length: usize) -> UserSpaceBuffer {
@@ -301,6 +304,33 @@ pub extern "C" fn ocall_write_db(
     // This will happen only when `catch_unwind` returns `Err`, which indicates a caught panic
     .unwrap_or(OcallReturn::Panic)
 }
+
+#[no_mangle]
+pub extern "C" fn ocall_report_allocator_stats(stats: AllocatorStats) {
+    crate::enclave_stats::record_allocator_stats(stats);
+}
+
+#[no_mangle]
+pub extern "C" fn ocall_report_call_memory(stats: CallMemoryStats) {
+    crate::enclave_stats::record_call_memory(stats);
+}
+
+#[no_mangle]
+pub extern "C" fn ocall_report_module_cache_stats(stats: ModuleCacheStats) {
+    crate::enclave_stats::record_module_cache_stats(stats);
+}
+
+#[no_mangle]
+pub extern "C" fn ocall_report_execution_receipt(receipt: ExecutionReceipt) {
+    crate::execution_receipts::record_execution_receipt(receipt);
+}
+
+#[no_mangle]
+pub extern "C" fn ocall_report_observed_events(record: *const u8, record_len: usize) {
+    let record = unsafe { std::slice::from_raw_parts(record, record_len) };
+    crate::observed_events::record_observed_events(record.to_vec());
+}
+
 /// Box the error and return a pointer to it.
 /// This box will be recovered on the side that called the enclave.
 ///
@@ -380,13 +410,48 @@ where
     Q: Querier,
 {
     with_storage_from_context::<S, Q, _, _>(&mut context, |storage: &mut S| {
-        let (ffi_result, gas_info) = storage.get(key);
-        ffi_result
-            .map(|value| (value, gas_info.externally_used))
-            .map_err(Into::into)
+        read_through_cold_tier(storage, key)
     })
 }
 
+/// Reads `key` from the storage, or from its cold tier if the storage doesn't have it, charging the
+/// same gas either way
+fn read_through_cold_tier<S: Storage>(
+    storage: &mut S,
+    key: &[u8],
+) -> VmResult<(Option<Vec<u8>>, u64)> {
+    let (ffi_result, gas_info) = storage.get(key);
+    let value = ffi_result?;
+    let cold_tier = match (&value, storage.cold_tier()) {
+        (None, Some(cold_tier)) => cold_tier,
+        _ => return Ok((value, gas_info.externally_used)),
+    };
+
+    let value = match cold_tier.get(key)? {
+        Some(value) => value,
+        None => return Ok((None, gas_info.externally_used)),
+    };
+    let (ffi_result, read_gas_info) = cold_tier.charge_read(key, &value);
+    ffi_result?;
+    Ok((
+        Some(value),
+        gas_info.externally_used + read_gas_info.externally_used,
+    ))
+}
+
+/// Drops `keys`, which were just written to or removed from the storage, from its cold tier
+fn drop_from_cold_tier<'k, S: Storage>(
+    storage: &mut S,
+    keys: impl IntoIterator<Item = &'k [u8]>,
+) -> VmResult<()> {
+    if let Some(cold_tier) = storage.cold_tier() {
+        for key in keys {
+            cold_tier.remove(key)?;
+        }
+    }
+    Ok(())
+}
+
 fn ocall_query_chain_impl<S, Q>(
     mut context: Ctx,
     query: &[u8],
@@ -412,9 +477,9 @@ where
 {
     with_storage_from_context::<S, Q, _, _>(&mut context, |storage: &mut S| {
         let (ffi_result, gas_info) = storage.remove(key);
-        ffi_result
-            .and(Ok(gas_info.externally_used))
-            .map_err(Into::into)
+        ffi_result?;
+        drop_from_cold_tier(storage, Some(key))?;
+        Ok(gas_info.externally_used)
     })
 }
 
@@ -425,9 +490,9 @@ where
 {
     with_storage_from_context::<S, Q, _, _>(&mut context, |storage: &mut S| {
         let (ffi_result, gas_info) = storage.set(key, value);
-        ffi_result
-            .and(Ok(gas_info.externally_used))
-            .map_err(Into::into)
+        ffi_result?;
+        drop_from_cold_tier(storage, Some(key))?;
+        Ok(gas_info.externally_used)
     })
 }
 
@@ -440,19 +505,9 @@ where
     Q: Querier,
 {
     with_storage_from_context::<S, Q, _, _>(&mut context, |storage: &mut S| {
-        let mut total_gas = 0;
-
-        for (k, v) in keys.into_iter() {
-            let (ffi_result, gas_info) = storage.set(&k, &v);
-            total_gas += gas_info.externally_used;
-
-            if ffi_result.is_err() {
-                return Err(VmError::FfiErr {
-                    source: ffi_result.unwrap_err(),
-                });
-            }
-        }
-
-        Ok(total_gas)
+        let (ffi_result, gas_info) = storage.set_multiple(&keys);
+        ffi_result?;
+        drop_from_cold_tier(storage, keys.iter().map(|(key, _)| key.as_slice()))?;
+        Ok(gas_info.externally_used)
     })
 }
@@ -8,7 +8,7 @@ use enclave_ffi_types::{
     Ctx, EnclaveBuffer, HandleResult, InitResult, MigrateResult, QueryResult, UpdateAdminResult,
 };
 
-use crate::enclave::ENCLAVE_DOORBELL;
+use crate::enclave::current_doorbell;
 
 extern "C" {
     /// Copy a buffer into the enclave memory space, and receive an opaque pointer to it.
@@ -117,15 +117,15 @@ pub(super) fn allocate_enclave_buffer(buffer: &[u8]) -> SgxResult<EnclaveBuffer>
 
     // Bind the token to a local variable to ensure its
     // destructor runs in the end of the function
-    let enclave_access_token = ENCLAVE_DOORBELL
+    // The buffer must be allocated in the enclave that made the ocall
+    let enclave_access_token = current_doorbell()
         // This is always called from an ocall contxt, so we don't want to wait for
         // an new TCS. To do that, we say that our query depth is >1, e.g. 2
         .get_access(2)
         .ok_or(sgx_status_t::SGX_ERROR_BUSY)?;
 
-    let enclave_id = enclave_access_token
-        .expect("If we got here, surely the enclave has been loaded")
-        .geteid();
+    // We can only get here from an ocall, so the enclave must have been loaded
+    let enclave_id = (*enclave_access_token)?.geteid();
 
     trace!(
         target: module_path!(),
@@ -1,6 +1,8 @@
 use super::exports;
 use crate::VmResult;
-use enclave_ffi_types::{HandleResult, InitResult, MigrateResult, QueryResult, UpdateAdminResult};
+use enclave_ffi_types::{
+    HandleResult, InitResult, MigrateResult, QueryResponseSignature, QueryResult, UpdateAdminResult,
+};
 
 /// This struct is returned from module initialization.
 pub struct InitSuccess {
@@ -112,18 +114,27 @@ pub fn handle_result_to_vm_result(other: HandleResult) -> VmResult<HandleSuccess
 pub struct QuerySuccess {
     /// A pointer to the output of the execution
     output: Vec<u8>,
+    signature: QueryResponseSignature,
 }
 
 impl QuerySuccess {
     pub fn into_output(self) -> Vec<u8> {
         self.output
     }
+
+    /// Returns the output and, if the enclave signed or MACed the response, its signature.
+    pub fn into_signed_output(self) -> (Vec<u8>, Option<QueryResponseSignature>) {
+        let signature =
+            Some(self.signature).filter(|signature| signature.signed || signature.has_contract_mac);
+        (self.output, signature)
+    }
 }
 
 pub fn query_result_to_vm_result(other: QueryResult) -> VmResult<QuerySuccess> {
     match other {
-        QueryResult::Success { output } => Ok(QuerySuccess {
+        QueryResult::Success { output, signature } => Ok(QuerySuccess {
             output: unsafe { exports::recover_buffer(output) }.unwrap_or_else(Vec::new),
+            signature,
         }),
         QueryResult::Failure { err } => Err(err.into()),
     }
@@ -4,12 +4,13 @@ use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
 
-use crate::enclave::ENCLAVE_DOORBELL;
+use crate::enclave::{doorbell_for_height, ENCLAVE_DOORBELL};
+use crate::enclave_stats::take_call_memory;
 use crate::errors::{EnclaveError, VmResult};
 use crate::{Querier, Storage, VmError};
 
 use enclave_ffi_types::{
-    Ctx, HandleResult, InitResult, MigrateResult, QueryResult, UpdateAdminResult,
+    CallMemoryStats, Ctx, HandleResult, InitResult, MigrateResult, QueryResult, UpdateAdminResult,
 };
 
 use sgx_types::sgx_status_t;
@@ -35,6 +36,8 @@ where
     bytecode: Vec<u8>,
     gas_limit: u64,
     used_gas: u64,
+    /// The memory footprint of the last contract call
+    call_memory: CallMemoryStats,
     ctx: Ctx,
     finalizer: fn(*mut c_void),
 
@@ -62,6 +65,7 @@ where
             bytecode,
             gas_limit,
             used_gas: 0,
+            call_memory: CallMemoryStats::default(),
             ctx,
             finalizer,
             type_storage: Default::default(),
@@ -90,9 +94,22 @@ where
         self.used_gas
     }
 
+    pub fn call_memory(&self) -> CallMemoryStats {
+        self.call_memory
+    }
+
+    pub fn bytecode(&self) -> &[u8] {
+        &self.bytecode
+    }
+
     // This is here to avoid putting it in the module's scope
     fn busy_enclave_err() -> VmError {
-        VmError::generic_err("The enclave is too busy and can not respond to this query")
+        let policy = ENCLAVE_DOORBELL.policy();
+        VmError::generic_err(format!(
+            "The enclave is too busy and can not respond to this query: all {} enclave threads stayed in use for {} seconds",
+            policy.max_threads(),
+            policy.queue_timeout().as_secs()
+        ))
     }
 
     pub fn migrate(
@@ -115,11 +132,13 @@ where
 
         // Bind the token to a local variable to ensure its
         // destructor runs in the end of the function
-        let enclave_access_token = ENCLAVE_DOORBELL
+        let enclave_access_token = doorbell_for_height(get_block_height(env)?)
             .get_access(1) // This can never be recursive
             .ok_or_else(Self::busy_enclave_err)?;
         let enclave = enclave_access_token.map_err(EnclaveError::sdk_err)?;
 
+        // Forget the footprint of any call this thread made before
+        take_call_memory();
         let status = unsafe {
             imports::ecall_migrate(
                 enclave.geteid(),
@@ -142,10 +161,13 @@ where
             )
         };
 
+        self.call_memory = take_call_memory();
         trace!(
-            "migrate() returned with gas_used: {} (gas_limit: {})",
+            "migrate() returned with gas_used: {} (gas_limit: {}), wasm memory: {} bytes, enclave heap high-watermark: {} bytes",
             used_gas,
-            self.gas_limit
+            self.gas_limit,
+            self.call_memory.wasm_memory_bytes,
+            self.call_memory.heap_high_watermark_bytes
         );
         self.consume_gas(used_gas);
 
@@ -175,7 +197,7 @@ where
 
         // Bind the token to a local variable to ensure its
         // destructor runs in the end of the function
-        let enclave_access_token = ENCLAVE_DOORBELL
+        let enclave_access_token = doorbell_for_height(get_block_height(env)?)
             .get_access(1) // This can never be recursive
             .ok_or_else(Self::busy_enclave_err)?;
         let enclave = enclave_access_token.map_err(EnclaveError::sdk_err)?;
@@ -227,11 +249,13 @@ where
 
         // Bind the token to a local variable to ensure its
         // destructor runs in the end of the function
-        let enclave_access_token = ENCLAVE_DOORBELL
+        let enclave_access_token = doorbell_for_height(get_block_height(env)?)
             .get_access(1) // This can never be recursive
             .ok_or_else(Self::busy_enclave_err)?;
         let enclave = enclave_access_token.map_err(EnclaveError::sdk_err)?;
 
+        // Forget the footprint of any call this thread made before
+        take_call_memory();
         let status = unsafe {
             imports::ecall_init(
                 enclave.geteid(),
@@ -252,10 +276,13 @@ where
             )
         };
 
+        self.call_memory = take_call_memory();
         trace!(
-            "init() returned with gas_used: {} (gas_limit: {})",
+            "init() returned with gas_used: {} (gas_limit: {}), wasm memory: {} bytes, enclave heap high-watermark: {} bytes",
             used_gas,
-            self.gas_limit
+            self.gas_limit,
+            self.call_memory.wasm_memory_bytes,
+            self.call_memory.heap_high_watermark_bytes
         );
         self.consume_gas(used_gas);
 
@@ -287,11 +314,13 @@ where
 
         // Bind the token to a local variable to ensure its
         // destructor runs in the end of the function
-        let enclave_access_token = ENCLAVE_DOORBELL
+        let enclave_access_token = doorbell_for_height(get_block_height(env)?)
             .get_access(1) // This can never be recursive
             .ok_or_else(Self::busy_enclave_err)?;
         let enclave = enclave_access_token.map_err(EnclaveError::sdk_err)?;
 
+        // Forget the footprint of any call this thread made before
+        take_call_memory();
         let status = unsafe {
             imports::ecall_handle(
                 enclave.geteid(),
@@ -311,10 +340,13 @@ where
             )
         };
 
+        self.call_memory = take_call_memory();
         trace!(
-            "handle() returned with gas_used: {} (gas_limit: {})",
+            "handle() returned with gas_used: {} (gas_limit: {}), wasm memory: {} bytes, enclave heap high-watermark: {} bytes",
             used_gas,
-            self.gas_limit
+            self.gas_limit,
+            self.call_memory.wasm_memory_bytes,
+            self.call_memory.heap_high_watermark_bytes
         );
         self.consume_gas(used_gas);
 
@@ -337,7 +369,7 @@ where
         let mut query_result = MaybeUninit::<QueryResult>::uninit();
         let mut used_gas = 0_u64;
 
-        let doorbell = &ENCLAVE_DOORBELL;
+        let doorbell = doorbell_for_height(get_block_height(env)?);
 
         // Bind the token to a local variable to ensure its
         // destructor runs in the end of the function
@@ -346,6 +378,8 @@ where
             .ok_or_else(Self::busy_enclave_err)?;
         let enclave = enclave_access_token.map_err(EnclaveError::sdk_err)?;
 
+        // Forget the footprint of any call this thread made before
+        take_call_memory();
         let status = unsafe {
             imports::ecall_query(
                 // TODO use the _qe variant
@@ -363,10 +397,13 @@ where
             )
         };
 
+        self.call_memory = take_call_memory();
         trace!(
-            "query() returned with gas_used: {} (gas_limit: {})",
+            "query() returned with gas_used: {} (gas_limit: {}), wasm memory: {} bytes, enclave heap high-watermark: {} bytes",
             used_gas,
-            self.gas_limit
+            self.gas_limit,
+            self.call_memory.wasm_memory_bytes,
+            self.call_memory.heap_high_watermark_bytes
         );
         self.consume_gas(used_gas);
 
@@ -396,23 +433,40 @@ where
 }
 
 /// This type is used to extract the `query_depth` field which starts out at 1
-/// and is incremented every time a recursive query is called.
+/// and is incremented every time a recursive query is called, and the height of the block,
+/// which decides the enclave that handles the call.
 /// We do not include the other fields of the Env here
 /// to reduce the need to keep this type in sync with the canonical `Env` type.
 #[derive(Debug, Deserialize)]
 struct Env {
     #[serde(default)]
     query_depth: u32,
+    #[serde(default)]
+    block: Option<BlockInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockInfo {
+    height: u64,
+}
+
+fn parse_env(env: &[u8]) -> VmResult<Env> {
+    serde_json::from_slice::<Env>(env).map_err(|_err| {
+        VmError::generic_err(format!(
+            "could not parse the env parameter: {:?}",
+            String::from_utf8_lossy(env)
+        ))
+    })
 }
 
 /// This function parses the `env` parameter using the type above, and extracts the
 /// `recursive` field from it.
 fn get_query_depth(env: &[u8]) -> VmResult<u32> {
-    match serde_json::from_slice::<Env>(env) {
-        Ok(env) => Ok(env.query_depth),
-        Err(_err) => Err(VmError::generic_err(format!(
-            "could not parse the env parameter: {:?}",
-            String::from_utf8_lossy(env)
-        ))),
-    }
+    Ok(parse_env(env)?.query_depth)
+}
+
+/// This function parses the `env` parameter using the type above, and extracts the height of
+/// the block from it. Calls without a block are handled by the current enclave.
+fn get_block_height(env: &[u8]) -> VmResult<u64> {
+    Ok(parse_env(env)?.block.map_or(u64::MAX, |block| block.height))
 }
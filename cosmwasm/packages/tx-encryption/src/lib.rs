@@ -0,0 +1,208 @@
+//! Client-side encryption of Secret Network tx messages, without an enclave or `std`.
+//!
+//! This is the scheme the enclave decrypts with (see `calc_encryption_key` in the contract
+//! engine): the encryption key is HKDF-SHA256 of `X25519(user_secret, io_pubkey) || nonce`, and
+//! messages are encrypted with AES-128-SIV. Contract outputs are encrypted with the same key, so
+//! the user decrypts them with the nonce of the message that produced them.
+//!
+//! Randomness is the caller's job: the nonce is passed in, and must never be reused with the same
+//! user key.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use aes_siv::aead::generic_array::GenericArray;
+use aes_siv::siv::Aes128Siv;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+pub const KEY_LENGTH: usize = 32;
+pub const NONCE_LENGTH: usize = 32;
+
+/// Starts the header of v2 messages. The last byte is the version.
+pub const ENVELOPE_V2_MAGIC: [u8; 8] = *b"SCRTENV\x02";
+/// Identifies the X25519 key agreement in v2 headers
+pub const KEY_AGREEMENT_X25519: u8 = 1;
+/// Identifies AES-SIV in v2 headers
+pub const AEAD_AES_SIV: u8 = 1;
+
+// Bitcoin halving block hash https://www.blockchain.com/btc/block/000000000000000000024bead8df69990852c202db0e0097c1a12ea637d7e96d
+const KDF_SALT: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x4b, 0xea, 0xd8, 0xdf, 0x69, 0x99,
+    0x08, 0x52, 0xc2, 0x02, 0xdb, 0x0e, 0x00, 0x97, 0xc1, 0xa1, 0x2e, 0xa6, 0x37, 0xd7, 0xe9, 0x6d,
+];
+
+pub type Key = [u8; KEY_LENGTH];
+pub type Nonce = [u8; NONCE_LENGTH];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    EncryptionError,
+    DecryptionError,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EncryptionError => write!(f, "failed to encrypt message"),
+            Error::DecryptionError => write!(f, "failed to decrypt message"),
+        }
+    }
+}
+
+/// The version of the encrypted message format
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Envelope {
+    /// No header. Accepted by every enclave version.
+    V1,
+    /// A header naming the X25519 key agreement and AES-SIV
+    V2,
+}
+
+/// The public key the enclave needs to agree on the same encryption key as `user_secret`
+pub fn public_key(user_secret: &Key) -> Key {
+    let secret = StaticSecret::from(*user_secret);
+    *PublicKey::from(&secret).as_bytes()
+}
+
+/// The AES-SIV key of a message, and of the outputs of the contract call it triggers
+pub fn encryption_key(user_secret: &Key, io_pubkey: &Key, nonce: &Nonce) -> Key {
+    let secret = StaticSecret::from(*user_secret);
+    let shared_secret = secret.diffie_hellman(&PublicKey::from(*io_pubkey));
+
+    let mut ikm = [0u8; KEY_LENGTH + NONCE_LENGTH];
+    ikm[..KEY_LENGTH].copy_from_slice(shared_secret.as_bytes());
+    ikm[KEY_LENGTH..].copy_from_slice(nonce);
+
+    let mut key = [0u8; KEY_LENGTH];
+    // 32 bytes is always a valid HKDF-SHA256 output length
+    Hkdf::<Sha256>::new(Some(&KDF_SALT), &ikm)
+        .expand(&[], &mut key)
+        .unwrap();
+    key
+}
+
+/// Encrypts `plaintext` (e.g. `code_hash || json_msg`) for the enclave. The result is what goes in
+/// the `msg` field of `MsgInstantiateContract`/`MsgExecuteContract`.
+pub fn encrypt_msg(
+    user_secret: &Key,
+    io_pubkey: &Key,
+    nonce: &Nonce,
+    plaintext: &[u8],
+    envelope: Envelope,
+) -> Result<Vec<u8>, Error> {
+    let key = encryption_key(user_secret, io_pubkey, nonce);
+    let ciphertext = aes_siv_encrypt(&key, plaintext)?;
+
+    let mut msg = Vec::with_capacity(
+        ENVELOPE_V2_MAGIC.len() + 2 + NONCE_LENGTH + KEY_LENGTH + ciphertext.len(),
+    );
+    if envelope == Envelope::V2 {
+        msg.extend_from_slice(&ENVELOPE_V2_MAGIC);
+        msg.push(KEY_AGREEMENT_X25519);
+        msg.push(AEAD_AES_SIV);
+    }
+    msg.extend_from_slice(nonce);
+    msg.extend_from_slice(&public_key(user_secret));
+    msg.extend_from_slice(&ciphertext);
+    Ok(msg)
+}
+
+/// Decrypts an encrypted field of a contract output (data, logs, errors), given the nonce of the
+/// message that produced it
+pub fn decrypt_output(
+    user_secret: &Key,
+    io_pubkey: &Key,
+    nonce: &Nonce,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let key = encryption_key(user_secret, io_pubkey, nonce);
+    aes_siv_decrypt(&key, ciphertext)
+}
+
+// The enclave always passes a single empty associated data component
+fn aes_siv_encrypt(key: &Key, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut cipher = Aes128Siv::new(GenericArray::clone_from_slice(key));
+    cipher
+        .encrypt(&[&[]], plaintext)
+        .map_err(|_| Error::EncryptionError)
+}
+
+fn aes_siv_decrypt(key: &Key, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut cipher = Aes128Siv::new(GenericArray::clone_from_slice(key));
+    cipher
+        .decrypt(&[&[]], ciphertext)
+        .map_err(|_| Error::DecryptionError)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const USER_SECRET: Key = [1u8; KEY_LENGTH];
+    const IO_SECRET: Key = [2u8; KEY_LENGTH];
+    const NONCE: Nonce = [3u8; NONCE_LENGTH];
+
+    #[test]
+    fn encryption_key_matches_the_enclave_side() {
+        let io_pubkey = public_key(&IO_SECRET);
+        let user_pubkey = public_key(&USER_SECRET);
+
+        // the enclave agrees on the key from its secret and the user public key
+        assert_eq!(
+            encryption_key(&USER_SECRET, &io_pubkey, &NONCE),
+            encryption_key(&IO_SECRET, &user_pubkey, &NONCE)
+        );
+        assert_ne!(
+            encryption_key(&USER_SECRET, &io_pubkey, &NONCE),
+            encryption_key(&USER_SECRET, &io_pubkey, &[4u8; NONCE_LENGTH])
+        );
+    }
+
+    #[test]
+    fn encrypt_msg_works() {
+        let io_pubkey = public_key(&IO_SECRET);
+        let plaintext = b"{\"increment\":{}}";
+
+        let msg = encrypt_msg(&USER_SECRET, &io_pubkey, &NONCE, plaintext, Envelope::V1).unwrap();
+        assert_eq!(&msg[..NONCE_LENGTH], &NONCE);
+        assert_eq!(
+            &msg[NONCE_LENGTH..NONCE_LENGTH + KEY_LENGTH],
+            &public_key(&USER_SECRET)
+        );
+
+        let key = encryption_key(&IO_SECRET, &public_key(&USER_SECRET), &NONCE);
+        let decrypted = aes_siv_decrypt(&key, &msg[NONCE_LENGTH + KEY_LENGTH..]).unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        let v2 = encrypt_msg(&USER_SECRET, &io_pubkey, &NONCE, plaintext, Envelope::V2).unwrap();
+        assert_eq!(&v2[..ENVELOPE_V2_MAGIC.len()], &ENVELOPE_V2_MAGIC);
+        assert_eq!(
+            &v2[ENVELOPE_V2_MAGIC.len()..ENVELOPE_V2_MAGIC.len() + 2],
+            &[KEY_AGREEMENT_X25519, AEAD_AES_SIV]
+        );
+        assert_eq!(&v2[ENVELOPE_V2_MAGIC.len() + 2..], &msg[..]);
+    }
+
+    #[test]
+    fn decrypt_output_works() {
+        let io_pubkey = public_key(&IO_SECRET);
+        let key = encryption_key(&IO_SECRET, &public_key(&USER_SECRET), &NONCE);
+        let output = aes_siv_encrypt(&key, b"{\"count\":1}").unwrap();
+
+        let decrypted = decrypt_output(&USER_SECRET, &io_pubkey, &NONCE, &output).unwrap();
+        assert_eq!(decrypted, b"{\"count\":1}");
+
+        let mut tampered = output;
+        tampered[0] ^= 1;
+        assert_eq!(
+            decrypt_output(&USER_SECRET, &io_pubkey, &NONCE, &tampered),
+            Err(Error::DecryptionError)
+        );
+    }
+}
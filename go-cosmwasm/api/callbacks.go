@@ -10,6 +10,7 @@ package api
 typedef GoResult (*read_db_fn)(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *val, Buffer *errOut);
 typedef GoResult (*write_db_fn)(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer val, Buffer *errOut);
 typedef GoResult (*remove_db_fn)(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *errOut);
+typedef GoResult (*write_multiple_db_fn)(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer entries, Buffer *errOut);
 typedef GoResult (*scan_db_fn)(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer start, Buffer end, int32_t order, GoIter *out, Buffer *errOut);
 // iterator
 typedef GoResult (*next_db_fn)(iterator_t idx, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer *key, Buffer *val, Buffer *errOut);
@@ -22,6 +23,7 @@ typedef GoResult (*query_external_fn)(querier_t *ptr, uint64_t gas_limit, uint64
 GoResult cGet_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *val, Buffer *errOut);
 GoResult cSet_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer val, Buffer *errOut);
 GoResult cDelete_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *errOut);
+GoResult cSetMultiple_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer entries, Buffer *errOut);
 GoResult cScan_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer start, Buffer end, int32_t order, GoIter *out, Buffer *errOut);
 // iterator
 GoResult cNext_cgo(iterator_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer *key, Buffer *val, Buffer *errOut);
@@ -36,6 +38,7 @@ GoResult cQueryExternal_cgo(querier_t *ptr, uint64_t gas_limit, uint64_t *used_g
 import "C"
 
 import (
+	"encoding/binary"
 	"encoding/json"
 	"fmt"
 	"log"
@@ -109,10 +112,11 @@ type KVStore interface {
 }
 
 var db_vtable = C.DB_vtable{
-	read_db:   (C.read_db_fn)(C.cGet_cgo),
-	write_db:  (C.write_db_fn)(C.cSet_cgo),
-	remove_db: (C.remove_db_fn)(C.cDelete_cgo),
-	scan_db:   (C.scan_db_fn)(C.cScan_cgo),
+	read_db:           (C.read_db_fn)(C.cGet_cgo),
+	write_db:          (C.write_db_fn)(C.cSet_cgo),
+	remove_db:         (C.remove_db_fn)(C.cDelete_cgo),
+	write_multiple_db: (C.write_multiple_db_fn)(C.cSetMultiple_cgo),
+	scan_db:           (C.scan_db_fn)(C.cScan_cgo),
 }
 
 type DBState struct {
@@ -207,6 +211,60 @@ func cSet(ptr *C.db_t, gasMeter *C.gas_meter_t, usedGas *C.uint64_t, key C.Buffe
 	return C.GoResult_Ok
 }
 
+//export cSetMultiple
+func cSetMultiple(ptr *C.db_t, gasMeter *C.gas_meter_t, usedGas *C.uint64_t, entries C.Buffer, errOut *C.Buffer) (ret C.GoResult) {
+	defer recoverPanic(&ret)
+	if ptr == nil || gasMeter == nil || usedGas == nil || errOut == nil {
+		// we received an invalid pointer
+		return C.GoResult_BadArgument
+	}
+
+	gm := *(*GasMeter)(unsafe.Pointer(gasMeter))
+	kv := *(*KVStore)(unsafe.Pointer(ptr))
+	e := receiveSlice(entries)
+
+	gasBefore := gm.GasConsumed()
+	err := setMultiple(kv, e)
+	gasAfter := gm.GasConsumed()
+	*usedGas = (C.uint64_t)(gasAfter - gasBefore)
+	if err != nil {
+		*errOut = allocateRust([]byte(err.Error()))
+		return C.GoResult_Other
+	}
+
+	return C.GoResult_Ok
+}
+
+// setMultiple writes entries encoded as `key length | key | value length | value`, with lengths as
+// big endian uint32, in order
+func setMultiple(kv KVStore, entries []byte) error {
+	for len(entries) > 0 {
+		key, rest, err := readLengthPrefixed(entries)
+		if err != nil {
+			return err
+		}
+		value, rest, err := readLengthPrefixed(rest)
+		if err != nil {
+			return err
+		}
+		kv.Set(key, value)
+		entries = rest
+	}
+	return nil
+}
+
+func readLengthPrefixed(bz []byte) ([]byte, []byte, error) {
+	if len(bz) < 4 {
+		return nil, nil, fmt.Errorf("truncated entry length")
+	}
+	length := binary.BigEndian.Uint32(bz[:4])
+	bz = bz[4:]
+	if uint64(len(bz)) < uint64(length) {
+		return nil, nil, fmt.Errorf("entry of length %d exceeds the %d remaining bytes", length, len(bz))
+	}
+	return bz[:length], bz[length:], nil
+}
+
 //export cDelete
 func cDelete(ptr *C.db_t, gasMeter *C.gas_meter_t, usedGas *C.uint64_t, key C.Buffer, _ *C.Buffer) (ret C.GoResult) {
 	defer recoverPanic(&ret)
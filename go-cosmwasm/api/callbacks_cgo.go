@@ -11,6 +11,7 @@ package api
 GoResult cSet(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer val, Buffer *errOut);
 GoResult cGet(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *val, Buffer *errOut);
 GoResult cDelete(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *errOut);
+GoResult cSetMultiple(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer entries, Buffer *errOut);
 GoResult cScan(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer start, Buffer end, int32_t order, GoIter *out, Buffer *errOut);
 // imports (iterator)
 GoResult cNext(iterator_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer *key, Buffer *val, Buffer *errOut);
@@ -30,6 +31,9 @@ GoResult cSet_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer
 GoResult cDelete_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer key, Buffer *errOut) {
 	return cDelete(ptr, gas_meter, used_gas, key, errOut);
 }
+GoResult cSetMultiple_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer entries, Buffer *errOut) {
+	return cSetMultiple(ptr, gas_meter, used_gas, entries, errOut);
+}
 GoResult cScan_cgo(db_t *ptr, gas_meter_t *gas_meter, uint64_t *used_gas, Buffer start, Buffer end, int32_t order, GoIter *out, Buffer *errOut) {
 	return cScan(ptr, gas_meter, used_gas, start, end, order, out, errOut);
 }
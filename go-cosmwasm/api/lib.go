@@ -8,9 +8,12 @@ package api
 import "C"
 
 import (
+	"encoding/binary"
+	"encoding/json"
 	"fmt"
 	"runtime"
 	"syscall"
+	"unsafe"
 
 	v1types "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types/v1"
 
@@ -45,7 +48,7 @@ func HealthCheck() ([]byte, error) {
 	return receiveVector(res), nil
 }
 
-func SubmitBlockSignatures(header []byte, commit []byte, txs []byte, encRandom []byte /* valSet []byte, nextValSet []byte */) ([]byte, error) {
+func SubmitBlockSignatures(height uint64, header []byte, commit []byte, txs []byte, encRandom []byte /* valSet []byte, nextValSet []byte */) ([]byte, error) {
 	errmsg := C.Buffer{}
 	spidSlice := sendSlice(header)
 	defer freeAfterSend(spidSlice)
@@ -56,7 +59,99 @@ func SubmitBlockSignatures(header []byte, commit []byte, txs []byte, encRandom [
 	txsSlice := sendSlice(txs)
 	defer freeAfterSend(txsSlice)
 
-	res, err := C.submit_block_signatures(spidSlice, apiKeySlice, txsSlice, encRandomSlice /* valSetSlice, nextValSetSlice,*/, &errmsg)
+	res, err := C.submit_block_signatures(u64(height), spidSlice, apiKeySlice, txsSlice, encRandomSlice /* valSetSlice, nextValSetSlice,*/, &errmsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errmsg)
+	}
+	return receiveVector(res), nil
+}
+
+// DecryptTxMsgs decrypts the encrypted messages of a transaction in one ecall, and returns
+// the decryption status of each message
+func DecryptTxMsgs(msgs [][]byte, keepPlaintexts bool) ([]byte, error) {
+	var prefixed []byte
+	for _, msg := range msgs {
+		prefixed = binary.BigEndian.AppendUint32(prefixed, uint32(len(msg)))
+		prefixed = append(prefixed, msg...)
+	}
+
+	errmsg := C.Buffer{}
+	msgsSlice := sendSlice(prefixed)
+	defer freeAfterSend(msgsSlice)
+
+	res, err := C.decrypt_tx_msgs(msgsSlice, cbool(keepPlaintexts), &errmsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errmsg)
+	}
+	return receiveVector(res), nil
+}
+
+// DecryptStateKeys decrypts the stored state keys of the contract with ogContractKey in one ecall,
+// and returns the plaintext keys, length prefixed, with a length of math.MaxUint32 for keys that
+// can't be decrypted. features is the JSON of an env with the active features.
+func DecryptStateKeys(ogContractKey []byte, keys [][]byte, features []byte) ([]byte, error) {
+	var prefixed []byte
+	for _, key := range keys {
+		prefixed = binary.BigEndian.AppendUint32(prefixed, uint32(len(key)))
+		prefixed = append(prefixed, key...)
+	}
+
+	errmsg := C.Buffer{}
+	ogKeySlice := sendSlice(ogContractKey)
+	defer freeAfterSend(ogKeySlice)
+	keysSlice := sendSlice(prefixed)
+	defer freeAfterSend(keysSlice)
+	featuresSlice := sendSlice(features)
+	defer freeAfterSend(featuresSlice)
+
+	res, err := C.decrypt_state_keys(ogKeySlice, keysSlice, featuresSlice, &errmsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errmsg)
+	}
+	return receiveVector(res), nil
+}
+
+// ProveContractKey asks the enclave to validate the keys of a contract and sign a proof of its
+// current key. currentContractKey is empty unless the contract was migrated, in which case it is
+// the current contract key followed by its migration proof.
+func ProveContractKey(contractAddress []byte, codeHash []byte, ogContractKey []byte, currentContractKey []byte) ([]byte, error) {
+	errmsg := C.Buffer{}
+	addressSlice := sendSlice(contractAddress)
+	defer freeAfterSend(addressSlice)
+	codeHashSlice := sendSlice(codeHash)
+	defer freeAfterSend(codeHashSlice)
+	ogKeySlice := sendSlice(ogContractKey)
+	defer freeAfterSend(ogKeySlice)
+	currentKeySlice := sendSlice(currentContractKey)
+	defer freeAfterSend(currentKeySlice)
+
+	res, err := C.prove_contract_key(addressSlice, codeHashSlice, ogKeySlice, currentKeySlice, &errmsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errmsg)
+	}
+	return receiveVector(res), nil
+}
+
+func RewrapQuery(contractAddress []byte, previousCodeHash []byte, ogContractKey []byte, previousCurrentContractKey []byte, currentCodeHash []byte, currentContractKey []byte, migrator []byte, migrationHeight uint64, currentHeight uint64, graceBlocks uint64, msg []byte) ([]byte, error) {
+	errmsg := C.Buffer{}
+	addressSlice := sendSlice(contractAddress)
+	defer freeAfterSend(addressSlice)
+	previousCodeHashSlice := sendSlice(previousCodeHash)
+	defer freeAfterSend(previousCodeHashSlice)
+	ogKeySlice := sendSlice(ogContractKey)
+	defer freeAfterSend(ogKeySlice)
+	previousKeySlice := sendSlice(previousCurrentContractKey)
+	defer freeAfterSend(previousKeySlice)
+	currentCodeHashSlice := sendSlice(currentCodeHash)
+	defer freeAfterSend(currentCodeHashSlice)
+	currentKeySlice := sendSlice(currentContractKey)
+	defer freeAfterSend(currentKeySlice)
+	migratorSlice := sendSlice(migrator)
+	defer freeAfterSend(migratorSlice)
+	msgSlice := sendSlice(msg)
+	defer freeAfterSend(msgSlice)
+
+	res, err := C.rewrap_query(addressSlice, previousCodeHashSlice, ogKeySlice, previousKeySlice, currentCodeHashSlice, currentKeySlice, migratorSlice, u64(migrationHeight), u64(currentHeight), u64(graceBlocks), msgSlice, &errmsg)
 	if err != nil {
 		return nil, errorWithMessage(err, errmsg)
 	}
@@ -93,16 +188,36 @@ func LoadSeedToEnclave(masterKey []byte, seed []byte, apiKey []byte) (bool, erro
 	return true, nil
 }
 
+// LoadPreviousSeedsToEnclave seals the seeds that were current before each seed rotation, as
+// shared with this node when it registered. Must be called after LoadSeedToEnclave.
+func LoadPreviousSeedsToEnclave(masterKey []byte, seeds []byte) (bool, error) {
+	pkSlice := sendSlice(masterKey)
+	defer freeAfterSend(pkSlice)
+	seedsSlice := sendSlice(seeds)
+	defer freeAfterSend(seedsSlice)
+	errmsg := C.Buffer{}
+
+	_, err := C.init_previous_seeds(pkSlice, seedsSlice, &errmsg)
+	if err != nil {
+		return false, errorWithMessage(err, errmsg)
+	}
+	return true, nil
+}
+
 type Querier = types.Querier
 
-func InitCache(dataDir string, supportedFeatures string, cacheSize uint64) (Cache, error) {
+func InitCache(dataDir string, supportedFeatures string, validationConfig []byte, upgradeSchedule []byte, cacheSize uint64) (Cache, error) {
 	dir := sendSlice([]byte(dataDir))
 	defer freeAfterSend(dir)
 	features := sendSlice([]byte(supportedFeatures))
 	defer freeAfterSend(features)
+	config := sendSlice(validationConfig)
+	defer freeAfterSend(config)
+	schedule := sendSlice(upgradeSchedule)
+	defer freeAfterSend(schedule)
 	errmsg := C.Buffer{}
 
-	ptr, err := C.init_cache(dir, features, usize(cacheSize), &errmsg)
+	ptr, err := C.init_cache(dir, features, config, schedule, usize(cacheSize), &errmsg)
 	if err != nil {
 		return Cache{}, errorWithMessage(err, errmsg)
 	}
@@ -113,11 +228,13 @@ func ReleaseCache(cache Cache) {
 	C.release_cache(cache.ptr)
 }
 
-func InitEnclaveRuntime(moduleCacheSize uint16) error {
+func InitEnclaveRuntime(moduleCacheSize uint16, signExecutionReceipts bool, signQueryResponses bool) error {
 	errmsg := C.Buffer{}
 
 	config := C.EnclaveRuntimeConfig{
-		module_cache_size: u32(moduleCacheSize),
+		module_cache_size:       u32(moduleCacheSize),
+		sign_execution_receipts: cbool(signExecutionReceipts),
+		sign_query_responses:    cbool(signQueryResponses),
 	}
 	_, err := C.configure_enclave_runtime(config, &errmsg)
 	if err != nil {
@@ -127,11 +244,73 @@ func InitEnclaveRuntime(moduleCacheSize uint16) error {
 	return nil
 }
 
-func Create(cache Cache, wasm []byte) ([]byte, error) {
+func ValidateEnclaveLaunch(chainID string, allowDebugEnclave bool) error {
+	chainIDBuf := sendSlice([]byte(chainID))
+	defer freeAfterSend(chainIDBuf)
+	errmsg := C.Buffer{}
+
+	_, err := C.validate_enclave_launch(chainIDBuf, cbool(allowDebugEnclave), &errmsg)
+	if err != nil {
+		return errorWithMessage(err, errmsg)
+	}
+	return nil
+}
+
+// LoadLegacyEnclave loads the previous version of the enclave from enclaveFile, next to the current
+// one, for the blocks before the upgrade to it. It must be called before InitEnclaveRuntime.
+func LoadLegacyEnclave(enclaveFile string) error {
+	enclaveFileBuf := sendSlice([]byte(enclaveFile))
+	defer freeAfterSend(enclaveFileBuf)
+	errmsg := C.Buffer{}
+
+	_, err := C.load_legacy_enclave(enclaveFileBuf, &errmsg)
+	if err != nil {
+		return errorWithMessage(err, errmsg)
+	}
+	return nil
+}
+
+// ActivateSeedRotation activates a seed rotation candidate in the enclave, if at least threshold
+// distinct attested nodes approved it. certs are the attestation certificates of the nodes that made
+// the approvals, in the same order. Returns whether the node must be restarted to start using the
+// new seed.
+func ActivateSeedRotation(encryptedCandidate []byte, activationHeight uint64, currentHeight uint64, threshold uint32, approvals [][]byte, certs [][]byte) (bool, error) {
+	if len(approvals) != len(certs) {
+		return false, fmt.Errorf("got %d approvals but %d certificates", len(approvals), len(certs))
+	}
+
+	var approvalBytes []byte
+	var certBytes []byte
+	for i := range approvals {
+		approvalBytes = append(approvalBytes, approvals[i]...)
+		certBytes = binary.BigEndian.AppendUint32(certBytes, uint32(len(certs[i])))
+		certBytes = append(certBytes, certs[i]...)
+	}
+
+	candidateSlice := sendSlice(encryptedCandidate)
+	defer freeAfterSend(candidateSlice)
+	approvalsSlice := sendSlice(approvalBytes)
+	defer freeAfterSend(approvalsSlice)
+	certsSlice := sendSlice(certBytes)
+	defer freeAfterSend(certsSlice)
+	errmsg := C.Buffer{}
+
+	restartRequired, err := C.activate_seed_rotation(candidateSlice, u64(activationHeight), u64(currentHeight), u32(threshold), approvalsSlice, certsSlice, &errmsg)
+	if err != nil {
+		return false, errorWithMessage(err, errmsg)
+	}
+	return bool(restartRequired), nil
+}
+
+func Create(cache Cache, wasm []byte, bulkMemory bool, developerPubKey []byte, developerSignature []byte) ([]byte, error) {
 	code := sendSlice(wasm)
 	defer freeAfterSend(code)
+	pubKey := sendSlice(developerPubKey)
+	defer freeAfterSend(pubKey)
+	signature := sendSlice(developerSignature)
+	defer freeAfterSend(signature)
 	errmsg := C.Buffer{}
-	id, err := C.create(cache.ptr, code, &errmsg)
+	id, err := C.create(cache.ptr, code, cbool(bulkMemory), pubKey, signature, &errmsg)
 	if err != nil {
 		return nil, errorWithMessage(err, errmsg)
 	}
@@ -149,6 +328,56 @@ func GetCode(cache Cache, code_id []byte) ([]byte, error) {
 	return receiveVector(code), nil
 }
 
+func RemoveCode(cache Cache, code_id []byte) (bool, error) {
+	id := sendSlice(code_id)
+	defer freeAfterSend(id)
+	errmsg := C.Buffer{}
+	removed, err := C.remove_code(cache.ptr, id, &errmsg)
+	if err != nil {
+		return false, errorWithMessage(err, errmsg)
+	}
+	return bool(removed), nil
+}
+
+func Pin(cache Cache, code_id []byte) error {
+	id := sendSlice(code_id)
+	defer freeAfterSend(id)
+	errmsg := C.Buffer{}
+	_, err := C.pin(cache.ptr, id, &errmsg)
+	if err != nil {
+		return errorWithMessage(err, errmsg)
+	}
+	return nil
+}
+
+// Prewarm loads and validates the stored code of codeIds on a background thread, and pins it if pin
+// is set. It returns once the thread is started.
+func Prewarm(cache Cache, codeIds [][]byte, pin bool) error {
+	var concatenated []byte
+	for _, codeId := range codeIds {
+		concatenated = append(concatenated, codeId...)
+	}
+	ids := sendSlice(concatenated)
+	defer freeAfterSend(ids)
+	errmsg := C.Buffer{}
+	_, err := C.prewarm(cache.ptr, ids, cbool(pin), &errmsg)
+	if err != nil {
+		return errorWithMessage(err, errmsg)
+	}
+	return nil
+}
+
+func Unpin(cache Cache, code_id []byte) error {
+	id := sendSlice(code_id)
+	defer freeAfterSend(id)
+	errmsg := C.Buffer{}
+	_, err := C.unpin(cache.ptr, id, &errmsg)
+	if err != nil {
+		return errorWithMessage(err, errmsg)
+	}
+	return nil
+}
+
 func Migrate(
 	cache Cache,
 	code_id []byte,
@@ -162,7 +391,7 @@ func Migrate(
 	sigInfo []byte,
 	admin []byte,
 	adminProof []byte,
-) ([]byte, uint64, error) {
+) ([]byte, *types.ExecutionReceipt, uint64, error) {
 	id := sendSlice(code_id)
 	defer freeAfterSend(id)
 	p := sendSlice(params)
@@ -182,6 +411,7 @@ func Migrate(
 	a := buildAPI(api)
 	q := buildQuerier(querier)
 	var gasUsed u64
+	receipt := C.ExecutionReceipt{}
 	errmsg := C.Buffer{}
 
 	adminBuffer := sendSlice(admin)
@@ -195,12 +425,12 @@ func Migrate(
 	//runtime.LockOSThread()
 	//defer runtime.UnlockOSThread()
 
-	res, err := C.migrate(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &errmsg, s, adminBuffer, adminProofBuffer)
+	res, err := C.migrate(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &receipt, &errmsg, s, adminBuffer, adminProofBuffer)
 	if err != nil && err.(syscall.Errno) != C.ErrnoValue_Success {
 		// Depending on the nature of the error, `gasUsed` will either have a meaningful value, or just 0.
-		return nil, uint64(gasUsed), errorWithMessage(err, errmsg)
+		return nil, receiveExecutionReceipt(receipt), uint64(gasUsed), errorWithMessage(err, errmsg)
 	}
-	return receiveVector(res), uint64(gasUsed), nil
+	return receiveVector(res), receiveExecutionReceipt(receipt), uint64(gasUsed), nil
 }
 
 func UpdateAdmin(
@@ -268,7 +498,7 @@ func Instantiate(
 	gasLimit uint64,
 	sigInfo []byte,
 	admin []byte,
-) ([]byte, uint64, error) {
+) ([]byte, *types.ExecutionReceipt, uint64, error) {
 	id := sendSlice(code_id)
 	defer freeAfterSend(id)
 	p := sendSlice(params)
@@ -288,6 +518,7 @@ func Instantiate(
 	a := buildAPI(api)
 	q := buildQuerier(querier)
 	var gasUsed u64
+	receipt := C.ExecutionReceipt{}
 	errmsg := C.Buffer{}
 
 	adminBuffer := sendSlice(admin)
@@ -298,12 +529,12 @@ func Instantiate(
 	//runtime.LockOSThread()
 	//defer runtime.UnlockOSThread()
 
-	res, err := C.instantiate(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &errmsg, s, adminBuffer)
+	res, err := C.instantiate(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &receipt, &errmsg, s, adminBuffer)
 	if err != nil && err.(syscall.Errno) != C.ErrnoValue_Success {
 		// Depending on the nature of the error, `gasUsed` will either have a meaningful value, or just 0.
-		return nil, uint64(gasUsed), errorWithMessage(err, errmsg)
+		return nil, receiveExecutionReceipt(receipt), uint64(gasUsed), errorWithMessage(err, errmsg)
 	}
-	return receiveVector(res), uint64(gasUsed), nil
+	return receiveVector(res), receiveExecutionReceipt(receipt), uint64(gasUsed), nil
 }
 
 func Handle(
@@ -318,7 +549,7 @@ func Handle(
 	gasLimit uint64,
 	sigInfo []byte,
 	handleType types.HandleType,
-) ([]byte, uint64, error) {
+) ([]byte, *types.ExecutionReceipt, uint64, error) {
 	id := sendSlice(code_id)
 	defer freeAfterSend(id)
 	p := sendSlice(params)
@@ -337,6 +568,7 @@ func Handle(
 	a := buildAPI(api)
 	q := buildQuerier(querier)
 	var gasUsed u64
+	receipt := C.ExecutionReceipt{}
 	errmsg := C.Buffer{}
 
 	//// This is done in order to ensure that goroutines don't
@@ -344,12 +576,12 @@ func Handle(
 	//runtime.LockOSThread()
 	//defer runtime.UnlockOSThread()
 
-	res, err := C.handle(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &errmsg, s, u8(handleType))
+	res, err := C.handle(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &receipt, &errmsg, s, u8(handleType))
 	if err != nil && err.(syscall.Errno) != C.ErrnoValue_Success {
 		// Depending on the nature of the error, `gasUsed` will either have a meaningful value, or just 0.
-		return nil, uint64(gasUsed), errorWithMessage(err, errmsg)
+		return nil, receiveExecutionReceipt(receipt), uint64(gasUsed), errorWithMessage(err, errmsg)
 	}
-	return receiveVector(res), uint64(gasUsed), nil
+	return receiveVector(res), receiveExecutionReceipt(receipt), uint64(gasUsed), nil
 }
 
 func Query(
@@ -362,7 +594,7 @@ func Query(
 	api *GoAPI,
 	querier *Querier,
 	gasLimit uint64,
-) ([]byte, uint64, error) {
+) ([]byte, *types.QueryResponseSignature, uint64, error) {
 	id := sendSlice(code_id)
 	defer freeAfterSend(id)
 	p := sendSlice(params)
@@ -386,12 +618,30 @@ func Query(
 	//runtime.LockOSThread()
 	//defer runtime.UnlockOSThread()
 
-	res, err := C.query(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &errmsg)
+	signature := C.QueryResponseSignature{}
+	res, err := C.query(cache.ptr, id, p, m, db, a, q, u64(gasLimit), &gasUsed, &signature, &errmsg)
 	if err != nil && err.(syscall.Errno) != C.ErrnoValue_Success {
 		// Depending on the nature of the error, `gasUsed` will either have a meaningful value, or just 0.
-		return nil, uint64(gasUsed), errorWithMessage(err, errmsg)
+		return nil, nil, uint64(gasUsed), errorWithMessage(err, errmsg)
 	}
-	return receiveVector(res), uint64(gasUsed), nil
+	data := receiveVector(res)
+	if !bool(signature.signed) && !bool(signature.has_contract_mac) {
+		return data, nil, uint64(gasUsed), nil
+	}
+	responseSignature := &types.QueryResponseSignature{
+		QueryHash:    C.GoBytes(unsafe.Pointer(&signature.query_hash[0]), 32),
+		ResultHash:   C.GoBytes(unsafe.Pointer(&signature.result_hash[0]), 32),
+		SignedResult: data,
+	}
+	if bool(signature.signed) {
+		responseSignature.BlockHeight = uint64(signature.block_height)
+		responseSignature.SignerPublicKey = C.GoBytes(unsafe.Pointer(&signature.signer_public_key[0]), 32)
+		responseSignature.Signature = C.GoBytes(unsafe.Pointer(&signature.signature[0]), 64)
+	}
+	if bool(signature.has_contract_mac) {
+		responseSignature.ContractMAC = C.GoBytes(unsafe.Pointer(&signature.contract_mac[0]), 32)
+	}
+	return data, responseSignature, uint64(gasUsed), nil
 }
 
 func AnalyzeCode(
@@ -406,12 +656,185 @@ func AnalyzeCode(
 		return nil, errorWithMessage(err, errMsg)
 	}
 	res := v1types.AnalysisReport{
+		HasIBCEntryPoints:     bool(report.has_ibc_entry_points),
+		RequiredFeatures:      string(receiveVector(report.required_features)),
+		IBCEntryPoints:        string(receiveVector(report.ibc_entry_points)),
+		MissingIBCEntryPoints: string(receiveVector(report.missing_ibc_entry_points)),
+	}
+	return &res, nil
+}
+
+// Introspect reads the public information of a stored contract without running it
+func Introspect(
+	cache Cache,
+	codeHash []byte,
+) (*v1types.IntrospectionReport, error) {
+	cs := sendSlice(codeHash)
+	defer runtime.KeepAlive(codeHash)
+	errMsg := C.Buffer{}
+	report, err := C.introspect(cache.ptr, cs, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+	res := v1types.IntrospectionReport{
+		InterfaceVersion:  string(receiveVector(report.interface_version)),
 		HasIBCEntryPoints: bool(report.has_ibc_entry_points),
-		RequiredFeatures:  string(receiveVector(report.required_features)),
+		SnipStandards:     string(receiveVector(report.snip_standards)),
 	}
 	return &res, nil
 }
 
+// VmCapabilities describes the contracts this node accepts and what it offers them
+func VmCapabilities(cache Cache) (*types.VmCapabilities, error) {
+	errMsg := C.Buffer{}
+	res, err := C.vm_capabilities(cache.ptr, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+
+	var capabilities types.VmCapabilities
+	if err := json.Unmarshal(receiveVector(res), &capabilities); err != nil {
+		return nil, err
+	}
+	return &capabilities, nil
+}
+
+func RevalidateStoredCode(cache Cache, supportedFeatures string, validationConfig []byte) (*types.RevalidationReport, error) {
+	features := sendSlice([]byte(supportedFeatures))
+	defer freeAfterSend(features)
+	config := sendSlice(validationConfig)
+	defer freeAfterSend(config)
+	errMsg := C.Buffer{}
+
+	res, err := C.revalidate_stored_code(cache.ptr, features, config, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+
+	var report types.RevalidationReport
+	if err := json.Unmarshal(receiveVector(res), &report); err != nil {
+		return nil, err
+	}
+	return &report, nil
+}
+
+func ContractPanicStats() ([]types.ContractPanicStats, error) {
+	errMsg := C.Buffer{}
+	res, err := C.contract_panic_stats(&errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+
+	var stats []types.ContractPanicStats
+	if err := json.Unmarshal(receiveVector(res), &stats); err != nil {
+		return nil, err
+	}
+	return stats, nil
+}
+
+func ConfigureAutoPinning(cache Cache, topK uint32, epochLength uint64) error {
+	errmsg := C.Buffer{}
+	_, err := C.configure_auto_pinning(cache.ptr, u32(topK), u64(epochLength), &errmsg)
+	if err != nil {
+		return errorWithMessage(err, errmsg)
+	}
+	return nil
+}
+
+func AutoPinMetrics(cache Cache) (*types.AutoPinMetrics, error) {
+	errMsg := C.Buffer{}
+	res, err := C.auto_pin_metrics(cache.ptr, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+
+	var metrics types.AutoPinMetrics
+	if err := json.Unmarshal(receiveVector(res), &metrics); err != nil {
+		return nil, err
+	}
+	return &metrics, nil
+}
+
+func GetMetrics(cache Cache) (*types.Metrics, error) {
+	errMsg := C.Buffer{}
+	res, err := C.cache_metrics(cache.ptr, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+
+	var metrics types.Metrics
+	if err := json.Unmarshal(receiveVector(res), &metrics); err != nil {
+		return nil, err
+	}
+	return &metrics, nil
+}
+
+// InstanceStats returns the executions of a contract recorded by this node, or nil if it never
+// executed it
+func InstanceStats(cache Cache, contractAddress string) (*types.InstanceStats, error) {
+	addr := sendSlice([]byte(contractAddress))
+	defer freeAfterSend(addr)
+	errMsg := C.Buffer{}
+
+	res, err := C.instance_stats(cache.ptr, addr, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+
+	var stats *types.InstanceStats
+	if err := json.Unmarshal(receiveVector(res), &stats); err != nil {
+		return nil, err
+	}
+	return stats, nil
+}
+
+// AnalyzeWasm reads the capabilities of a contract from its Wasm, which doesn't need to be stored
+func AnalyzeWasm(code []byte) (*v1types.ContractAnalysis, error) {
+	w := sendSlice(code)
+	defer runtime.KeepAlive(code)
+	errMsg := C.Buffer{}
+	analysis, err := C.analyze_wasm(w, &errMsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errMsg)
+	}
+	res := v1types.ContractAnalysis{
+		InterfaceVersion:   string(receiveVector(analysis.interface_version)),
+		RequiredFeatures:   string(receiveVector(analysis.required_features)),
+		HasIBCEntryPoints:  bool(analysis.has_ibc_entry_points),
+		HasMigrate:         bool(analysis.has_migrate),
+		HasReply:           bool(analysis.has_reply),
+		HasSudo:            bool(analysis.has_sudo),
+		MemoryInitialPages: uint64(analysis.memory_initial_pages),
+	}
+	if analysis.has_memory_maximum {
+		maximum := uint64(analysis.memory_maximum_pages)
+		res.MemoryMaximumPages = &maximum
+	}
+	return &res, nil
+}
+
+// receiveExecutionReceipt converts the receipt the enclave signed for a call, if it signed one
+func receiveExecutionReceipt(receipt C.ExecutionReceipt) *types.ExecutionReceipt {
+	if !bool(receipt.signed) {
+		return nil
+	}
+	return &types.ExecutionReceipt{
+		BlockHeight:     uint64(receipt.block_height),
+		CodeHash:        C.GoBytes(unsafe.Pointer(&receipt.code_hash[0]), 32),
+		MsgHash:         C.GoBytes(unsafe.Pointer(&receipt.msg_hash[0]), 32),
+		ResultHash:      C.GoBytes(unsafe.Pointer(&receipt.result_hash[0]), 32),
+		GasUsed:         uint64(receipt.gas_used),
+		SignerPublicKey: C.GoBytes(unsafe.Pointer(&receipt.signer_public_key[0]), 32),
+		Signature:       C.GoBytes(unsafe.Pointer(&receipt.signature[0]), 64),
+	}
+}
+
+// TakeObservedEvents returns the events the enclave encrypted to contract observers since the
+// last call, one json record per line
+func TakeObservedEvents() []byte {
+	return receiveVector(C.take_observed_events())
+}
+
 // KeyGen Send KeyGen request to enclave
 func KeyGen() ([]byte, error) {
 	errmsg := C.Buffer{}
@@ -446,6 +869,19 @@ func GetEncryptedSeed(cert []byte) ([]byte, error) {
 	return receiveVector(res), nil
 }
 
+// GetEncryptedPreviousSeeds returns the seeds that were current before each seed rotation,
+// oldest first, encrypted for the node of cert. It is empty if the seed was never rotated.
+func GetEncryptedPreviousSeeds(cert []byte) ([]byte, error) {
+	errmsg := C.Buffer{}
+	certSlice := sendSlice(cert)
+	defer freeAfterSend(certSlice)
+	res, err := C.get_encrypted_previous_seeds(certSlice, &errmsg)
+	if err != nil {
+		return nil, errorWithMessage(err, errmsg)
+	}
+	return receiveVector(res), nil
+}
+
 func GetEncryptedGenesisSeed(pk []byte) ([]byte, error) {
 	errmsg := C.Buffer{}
 	pkSlice := sendSlice(pk)
@@ -468,5 +904,11 @@ func errorWithMessage(err error, b C.Buffer) error {
 	if msg == nil {
 		return err
 	}
+	if errno, ok := err.(syscall.Errno); ok && errno == C.ErrnoValue_ValidationFailure {
+		var validationErr types.ValidationError
+		if json.Unmarshal(msg, &validationErr) == nil {
+			return validationErr
+		}
+	}
 	return fmt.Errorf("%s", string(msg))
 }
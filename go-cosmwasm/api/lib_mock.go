@@ -39,24 +39,44 @@ func InitBootstrap(spid []byte, apiKey []byte) ([]byte, error) {
 	return nil, nil
 }
 
-func SubmitBlockSignatures(header []byte, commit []byte, txs []byte, random []byte /* valSet []byte, nextValSet []byte*/) ([]byte, error) {
+func SubmitBlockSignatures(height uint64, header []byte, commit []byte, txs []byte, random []byte /* valSet []byte, nextValSet []byte*/) ([]byte, error) {
 	return nil, nil
 }
 
+func DecryptTxMsgs(msgs [][]byte, keepPlaintexts bool) ([]byte, error) {
+	return nil, nil
+}
+
+func DecryptStateKeys(ogContractKey []byte, keys [][]byte, features []byte) ([]byte, error) {
+	return nil, nil
+}
+
+func ProveContractKey(contractAddress []byte, codeHash []byte, ogContractKey []byte, currentContractKey []byte) ([]byte, error) {
+	return nil, nil
+}
+
+func RewrapQuery(contractAddress []byte, previousCodeHash []byte, ogContractKey []byte, previousCurrentContractKey []byte, currentCodeHash []byte, currentContractKey []byte, migrator []byte, migrationHeight uint64, currentHeight uint64, graceBlocks uint64, msg []byte) ([]byte, error) {
+	return msg, nil
+}
+
 func LoadSeedToEnclave(masterKey []byte, seed []byte, apiKey []byte) (bool, error) {
 	return true, nil
 }
 
+func LoadPreviousSeedsToEnclave(masterKey []byte, seeds []byte) (bool, error) {
+	return true, nil
+}
+
 type Querier = types.Querier
 
-func InitCache(dataDir string, supportedFeatures string, cacheSize uint64) (Cache, error) {
+func InitCache(dataDir string, supportedFeatures string, validationConfig []byte, upgradeSchedule []byte, cacheSize uint64) (Cache, error) {
 	//dir := sendSlice([]byte(dataDir))
 	//defer freeAfterSend(dir)
 	//features := sendSlice([]byte(supportedFeatures))
 	//defer freeAfterSend(features)
 	//errmsg := C.Buffer{}
 	//
-	//ptr, err := C.init_cache(dir, features, usize(cacheSize), &errmsg)
+	//ptr, err := C.init_cache(dir, features, config, usize(cacheSize), &errmsg)
 	//if err != nil {
 	//	return Cache{}, errorWithMessage(err, errmsg)
 	//}
@@ -67,15 +87,27 @@ func ReleaseCache(cache Cache) {
 	// C.release_cache(cache.ptr)
 }
 
-func InitEnclaveRuntime(ModuleCacheSize uint16) error {
+func InitEnclaveRuntime(ModuleCacheSize uint16, SignExecutionReceipts bool, SignQueryResponses bool) error {
+	return nil
+}
+
+func ValidateEnclaveLaunch(chainID string, allowDebugEnclave bool) error {
+	return nil
+}
+
+func LoadLegacyEnclave(enclaveFile string) error {
 	return nil
 }
 
-func Create(cache Cache, wasm []byte) ([]byte, error) {
+func ActivateSeedRotation(encryptedCandidate []byte, activationHeight uint64, currentHeight uint64, threshold uint32, approvals [][]byte, certs [][]byte) (bool, error) {
+	return false, nil
+}
+
+func Create(cache Cache, wasm []byte, bulkMemory bool, developerPubKey []byte, developerSignature []byte) ([]byte, error) {
 	//code := sendSlice(wasm)
 	//defer freeAfterSend(code)
 	//errmsg := C.Buffer{}
-	//id, err := C.create(cache.ptr, code, &errmsg)
+	//id, err := C.create(cache.ptr, code, cbool(bulkMemory), pubKey, signature, &errmsg)
 	//if err != nil {
 	//	return nil, errorWithMessage(err, errmsg)
 	//}
@@ -95,6 +127,30 @@ func GetCode(cache Cache, code_id []byte) ([]byte, error) {
 	return nil, nil
 }
 
+func RemoveCode(cache Cache, code_id []byte) (bool, error) {
+	//id := sendSlice(code_id)
+	//defer freeAfterSend(id)
+	//errmsg := C.Buffer{}
+	//removed, err := C.remove_code(cache.ptr, id, &errmsg)
+	//if err != nil {
+	//	return false, errorWithMessage(err, errmsg)
+	//}
+	//return bool(removed), nil
+	return false, nil
+}
+
+func Pin(cache Cache, code_id []byte) error {
+	return nil
+}
+
+func Prewarm(cache Cache, codeIds [][]byte, pin bool) error {
+	return nil
+}
+
+func Unpin(cache Cache, code_id []byte) error {
+	return nil
+}
+
 func Migrate(
 	cache Cache,
 	code_id []byte,
@@ -108,8 +164,8 @@ func Migrate(
 	sigInfo []byte,
 	admin []byte,
 	adminProof []byte,
-) ([]byte, uint64, error) {
-	return nil, 0, nil
+) ([]byte, *types.ExecutionReceipt, uint64, error) {
+	return nil, nil, 0, nil
 }
 
 func UpdateAdmin(
@@ -141,7 +197,7 @@ func Instantiate(
 	gasLimit uint64,
 	sigInfo []byte,
 	admin []byte,
-) ([]byte, uint64, error) {
+) ([]byte, *types.ExecutionReceipt, uint64, error) {
 	//id := sendSlice(code_id)
 	//defer freeAfterSend(id)
 	//p := sendSlice(params)
@@ -159,7 +215,7 @@ func Instantiate(
 	//	return nil, uint64(gasUsed), errorWithMessage(err, errmsg)
 	//}
 	//return receiveVector(res), uint64(gasUsed), nil
-	return nil, 0, nil
+	return nil, nil, 0, nil
 }
 
 func Handle(
@@ -174,7 +230,7 @@ func Handle(
 	gasLimit uint64,
 	sigInfo []byte,
 	handleType types.HandleType,
-) ([]byte, uint64, error) {
+) ([]byte, *types.ExecutionReceipt, uint64, error) {
 	//id := sendSlice(code_id)
 	//defer freeAfterSend(id)
 	//p := sendSlice(params)
@@ -192,7 +248,7 @@ func Handle(
 	//	return nil, uint64(gasUsed), errorWithMessage(err, errmsg)
 	//}
 	//return receiveVector(res), uint64(gasUsed), nil
-	return nil, 0, nil
+	return nil, nil, 0, nil
 }
 
 func Query(
@@ -205,7 +261,7 @@ func Query(
 	api *GoAPI,
 	querier *Querier,
 	gasLimit uint64,
-) ([]byte, uint64, error) {
+) ([]byte, *types.QueryResponseSignature, uint64, error) {
 	//id := sendSlice(code_id)
 	//defer freeAfterSend(id)
 	//m := sendSlice(msg)
@@ -244,6 +300,49 @@ func AnalyzeCode(
 	return nil, nil
 }
 
+func Introspect(
+	cache Cache,
+	codeHash []byte,
+) (*v1types.IntrospectionReport, error) {
+	return nil, nil
+}
+
+func VmCapabilities(cache Cache) (*types.VmCapabilities, error) {
+	return nil, nil
+}
+
+func RevalidateStoredCode(cache Cache, supportedFeatures string, validationConfig []byte) (*types.RevalidationReport, error) {
+	return nil, nil
+}
+
+func ContractPanicStats() ([]types.ContractPanicStats, error) {
+	return nil, nil
+}
+
+func ConfigureAutoPinning(cache Cache, topK uint32, epochLength uint64) error {
+	return nil
+}
+
+func AutoPinMetrics(cache Cache) (*types.AutoPinMetrics, error) {
+	return nil, nil
+}
+
+func GetMetrics(cache Cache) (*types.Metrics, error) {
+	return nil, nil
+}
+
+func InstanceStats(cache Cache, contractAddress string) (*types.InstanceStats, error) {
+	return nil, nil
+}
+
+func AnalyzeWasm(code []byte) (*v1types.ContractAnalysis, error) {
+	return nil, nil
+}
+
+func TakeObservedEvents() []byte {
+	return nil
+}
+
 // KeyGen Send KeyGen request to enclave
 func KeyGen() ([]byte, error) {
 	//errmsg := C.Buffer{}
@@ -277,6 +376,10 @@ func GetEncryptedSeed(cert []byte) ([]byte, error) {
 	return nil, nil
 }
 
+func GetEncryptedPreviousSeeds(cert []byte) ([]byte, error) {
+	return nil, nil
+}
+
 func GetEncryptedGenesisSeed(cert []byte) ([]byte, error) {
 	return nil, nil
 }
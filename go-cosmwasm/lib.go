@@ -1,8 +1,10 @@
 package cosmwasm
 
 import (
+	"encoding/binary"
 	"encoding/json"
 	"fmt"
+	"math"
 
 	"github.com/scrtlabs/SecretNetwork/go-cosmwasm/api"
 	types "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
@@ -40,12 +42,35 @@ type Wasmer struct {
 // cacheSize sets the size of an optional in-memory LRU cache for prepared VMs.
 // They allow popular contracts to be executed very rapidly (no loading overhead),
 // but require ~32-64MB each in memory usage.
-func NewWasmer(dataDir string, supportedFeatures string, cacheSize uint64, moduleCacheSize uint16) (*Wasmer, error) {
-	cache, err := api.InitCache(dataDir, supportedFeatures, cacheSize)
+// signExecutionReceipts makes the enclave sign a receipt of every instantiate, execute and migrate,
+// which they return. signQueryResponses makes it sign the responses to queries sent
+// directly to this node, see Query.
+// validationConfig adjusts the validation of stored contracts, nil keeps the defaults.
+// upgradeSchedule sets the heights the behaviors of the VM changed at, nil activates all of them
+// from genesis.
+func NewWasmer(dataDir string, supportedFeatures string, validationConfig *types.ValidationConfig, upgradeSchedule *types.UpgradeSchedule, cacheSize uint64, moduleCacheSize uint16, signExecutionReceipts bool, signQueryResponses bool) (*Wasmer, error) {
+	var validationConfigBz []byte
+	if validationConfig != nil {
+		var err error
+		validationConfigBz, err = json.Marshal(validationConfig)
+		if err != nil {
+			return nil, err
+		}
+	}
+	var upgradeScheduleBz []byte
+	if upgradeSchedule != nil {
+		var err error
+		upgradeScheduleBz, err = json.Marshal(upgradeSchedule)
+		if err != nil {
+			return nil, err
+		}
+	}
+
+	cache, err := api.InitCache(dataDir, supportedFeatures, validationConfigBz, upgradeScheduleBz, cacheSize)
 	if err != nil {
 		return nil, err
 	}
-	err = api.InitEnclaveRuntime(moduleCacheSize)
+	err = api.InitEnclaveRuntime(moduleCacheSize, signExecutionReceipts, signQueryResponses)
 	if err != nil {
 		return nil, err
 	}
@@ -53,6 +78,28 @@ func NewWasmer(dataDir string, supportedFeatures string, cacheSize uint64, modul
 	return &Wasmer{cache: cache}, nil
 }
 
+// ValidateEnclaveLaunch checks the attributes the enclave was launched with, and refuses
+// a debug-signed enclave on mainnet chain-ids unless allowDebugEnclave is set.
+// It should be called before the node starts taking part in consensus.
+func ValidateEnclaveLaunch(chainID string, allowDebugEnclave bool) error {
+	return api.ValidateEnclaveLaunch(chainID, allowDebugEnclave)
+}
+
+// LoadLegacyEnclave loads the previous version of the enclave from enclaveFile, next to the current
+// one, to execute the blocks before the upgrade to the current enclave. It refuses an enclave built
+// for another version of the interface with the node, and must be called before NewWasmer.
+func LoadLegacyEnclave(enclaveFile string) error {
+	return api.LoadLegacyEnclave(enclaveFile)
+}
+
+// ActivateSeedRotation activates a seed rotation candidate in the enclave, once the chain reached
+// its activation height and at least threshold distinct attested nodes approved it. certs are the
+// attestation certificates of the nodes that made the approvals, in the same order. It returns
+// whether the node must be restarted to start using the new seed.
+func ActivateSeedRotation(encryptedCandidate []byte, activationHeight uint64, currentHeight uint64, threshold uint32, approvals [][]byte, certs [][]byte) (bool, error) {
+	return api.ActivateSeedRotation(encryptedCandidate, activationHeight, currentHeight, threshold, approvals, certs)
+}
+
 // Cleanup should be called when no longer using this to free resources on the rust-side
 func (w *Wasmer) Cleanup() {
 	api.ReleaseCache(w.cache)
@@ -69,7 +116,20 @@ func (w *Wasmer) Cleanup() {
 //
 // TODO: return gas cost? Add gas limit??? there is no metering here...
 func (w *Wasmer) Create(code WasmCode) (CodeHash, error) {
-	return api.Create(w.cache, code)
+	return api.Create(w.cache, code, true, nil, nil)
+}
+
+// CreateWithBulkMemory is like Create, only accepting contracts using the bulk memory proposal if
+// bulkMemory is set. It must be decided by the chain, so all the nodes accept the same contracts.
+func (w *Wasmer) CreateWithBulkMemory(code WasmCode, bulkMemory bool) (CodeHash, error) {
+	return api.Create(w.cache, code, bulkMemory, nil, nil)
+}
+
+// CreateSigned is like CreateWithBulkMemory, also failing unless developerSignature is a secp256k1
+// signature of code, exactly as given, by developerPubKey. The check is part of the validation of
+// the wasm, so all the nodes accept the same contracts.
+func (w *Wasmer) CreateSigned(code WasmCode, bulkMemory bool, developerPubKey []byte, developerSignature []byte) (CodeHash, error) {
+	return api.Create(w.cache, code, bulkMemory, developerPubKey, developerSignature)
 }
 
 // GetCode will load the original wasm code for the given code id.
@@ -83,6 +143,156 @@ func (w *Wasmer) GetCode(code CodeHash) (WasmCode, error) {
 	return api.GetCode(w.cache, code)
 }
 
+// RemoveCode deletes the wasm code stored for the given code id, to reclaim disk
+// space once no contract uses it anymore, and has the enclave drop the module it built
+// from it. The code can't be loaded or instantiated afterwards, unless it is created
+// again. It returns false if no such code was stored, and an error if the code is pinned.
+func (w *Wasmer) RemoveCode(code CodeHash) (bool, error) {
+	return api.RemoveCode(w.cache, code)
+}
+
+// Pin keeps the wasm code stored for the given code id in memory, and has the enclave keep the
+// module it builds from it across executions, so the contracts of hot code don't load and parse it
+// on every call. Auto pinning doesn't unpin it. Pins don't survive restarts, and the enclave
+// refuses to pin more than a few dozen codes.
+func (w *Wasmer) Pin(code CodeHash) error {
+	return api.Pin(w.cache, code)
+}
+
+// Prewarm loads and validates the wasm code of the given code hashes on a background thread, pins
+// it if pin is set, and has the enclave build its module, so the first block after a restart
+// doesn't pay for loading and building it. It returns once the thread is started, code that can't
+// be loaded is skipped.
+func (w *Wasmer) Prewarm(codes []CodeHash, pin bool) error {
+	codeIds := make([][]byte, len(codes))
+	for i, code := range codes {
+		codeIds[i] = code
+	}
+	return api.Prewarm(w.cache, codeIds, pin)
+}
+
+// Unpin undoes Pin, or a pin of auto pinning. Unpinning code that isn't pinned does nothing.
+func (w *Wasmer) Unpin(code CodeHash) error {
+	return api.Unpin(w.cache, code)
+}
+
+// DecryptTxMsgs decrypts the encrypted messages of a transaction in a single enclave call, and
+// returns whether each of them could be decrypted. With keepPlaintexts the enclave keeps their
+// plaintext, so executing them afterwards doesn't decrypt them again.
+func (w *Wasmer) DecryptTxMsgs(msgs [][]byte, keepPlaintexts bool) ([]types.MsgDecryptionStatus, error) {
+	res, err := api.DecryptTxMsgs(msgs, keepPlaintexts)
+	if err != nil {
+		return nil, err
+	}
+	if len(res) != len(msgs) {
+		return nil, fmt.Errorf("got %d decryption statuses for %d messages", len(res), len(msgs))
+	}
+
+	statuses := make([]types.MsgDecryptionStatus, len(res))
+	for i, status := range res {
+		statuses[i] = types.MsgDecryptionStatus(status)
+	}
+	return statuses, nil
+}
+
+// DecryptStateKeys decrypts the stored state keys of the contract with ogContractKey in a single
+// enclave call. The plaintext of keys that can't be decrypted, i.e. keys in the old format and the
+// keys of value chunks, is nil. Only enclaves built in software mode for migration tooling decrypt
+// state keys, if activeFeatures include it, other enclaves return an error.
+func (w *Wasmer) DecryptStateKeys(ogContractKey []byte, keys [][]byte, activeFeatures []string) ([][]byte, error) {
+	features, err := json.Marshal(types.Env{ActiveFeatures: activeFeatures})
+	if err != nil {
+		return nil, err
+	}
+	res, err := api.DecryptStateKeys(ogContractKey, keys, features)
+	if err != nil {
+		return nil, err
+	}
+
+	decrypted := make([][]byte, 0, len(keys))
+	for len(res) > 0 {
+		if len(res) < 4 {
+			return nil, fmt.Errorf("truncated decrypted state keys")
+		}
+		length := binary.BigEndian.Uint32(res)
+		res = res[4:]
+		if length == math.MaxUint32 {
+			decrypted = append(decrypted, nil)
+			continue
+		}
+		if uint32(len(res)) < length {
+			return nil, fmt.Errorf("truncated decrypted state keys")
+		}
+		decrypted = append(decrypted, res[:length])
+		res = res[length:]
+	}
+	if len(decrypted) != len(keys) {
+		return nil, fmt.Errorf("got %d decrypted state keys for %d keys", len(decrypted), len(keys))
+	}
+	return decrypted, nil
+}
+
+// ProveContractKey asks the enclave to validate the keys of a contract and sign a proof of its
+// current contract key, which clients can verify without trusting this node. currentContractKey
+// and currentContractKeyProof are nil unless the contract was migrated.
+func (w *Wasmer) ProveContractKey(
+	contractAddress []byte,
+	codeHash []byte,
+	ogContractKey []byte,
+	currentContractKey []byte,
+	currentContractKeyProof []byte,
+) (*types.ContractKeyProof, error) {
+	current := append(append([]byte{}, currentContractKey...), currentContractKeyProof...)
+	res, err := api.ProveContractKey(contractAddress, codeHash, ogContractKey, current)
+	if err != nil {
+		return nil, err
+	}
+	if len(res) != 160 {
+		return nil, fmt.Errorf("got a contract key proof of %d bytes, expected 160", len(res))
+	}
+
+	contractKey := ogContractKey
+	if len(currentContractKey) > 0 {
+		contractKey = currentContractKey
+	}
+	return &types.ContractKeyProof{
+		Version:             types.ContractKeyProofVersion,
+		ContractAddress:     contractAddress,
+		CodeHash:            codeHash,
+		ContractKey:         contractKey,
+		NodePublicKey:       res[0:32],
+		EncryptionPublicKey: res[32:64],
+		SignerPublicKey:     res[64:96],
+		Signature:           res[96:160],
+	}, nil
+}
+
+// RewrapQuery asks the enclave to re-wrap an encrypted query addressed to previousCodeHash, the
+// code a contract ran before its last migration, so that it runs against currentCodeHash. The
+// previous keys are the keys of the contract when it ran the previous code, and the current ones
+// the keys migrator generated when it migrated the contract at migrationHeight. Fails if the query
+// did not opt in or is not addressed to the previous code, or if the migration is more than
+// graceBlocks before currentHeight.
+func (w *Wasmer) RewrapQuery(
+	contractAddress []byte,
+	previousCodeHash []byte,
+	ogContractKey []byte,
+	previousCurrentContractKey []byte,
+	previousCurrentContractKeyProof []byte,
+	currentCodeHash []byte,
+	currentContractKey []byte,
+	currentContractKeyProof []byte,
+	migrator []byte,
+	migrationHeight uint64,
+	currentHeight uint64,
+	graceBlocks uint64,
+	msg []byte,
+) ([]byte, error) {
+	previousCurrent := append(append([]byte{}, previousCurrentContractKey...), previousCurrentContractKeyProof...)
+	current := append(append([]byte{}, currentContractKey...), currentContractKeyProof...)
+	return api.RewrapQuery(contractAddress, previousCodeHash, ogContractKey, previousCurrent, currentCodeHash, current, migrator, migrationHeight, currentHeight, graceBlocks, msg)
+}
+
 // This struct helps us to distinguish between v0.10 contract response and v1 contract response
 type ContractExecResponse struct {
 	V1                     *V1ContractExecResponse       `json:"v1,omitempty"`
@@ -92,6 +302,7 @@ type ContractExecResponse struct {
 	IBCBasic               *v1types.IBCBasicResult       `json:"ibc_basic,omitempty"`
 	IBCPacketReceive       *v1types.IBCReceiveResult     `json:"ibc_packet_receive,omitempty"`
 	IBCChannelOpen         *v1types.IBCOpenChannelResult `json:"ibc_open_channel,omitempty"`
+	ContractInfo           *types.ContractVersion        `json:"contract_info,omitempty"`
 }
 
 type V010ContractExecResponse struct {
@@ -109,6 +320,7 @@ type V010orV1ContractInitResponse struct {
 	V010                   *V010ContractInitResponse `json:"v010,omitempty"`
 	InternaReplyEnclaveSig []byte                    `json:"internal_reply_enclave_sig"`
 	InternalMsgId          []byte                    `json:"internal_msg_id"`
+	ContractInfo           *types.ContractVersion    `json:"contract_info,omitempty"`
 }
 
 type V010ContractInitResponse struct {
@@ -140,21 +352,21 @@ func (w *Wasmer) Instantiate(
 	gasLimit uint64,
 	sigInfo types.SigInfo,
 	admin []byte,
-	// data, contractKey, adminProof, gasUsed, error
-) (interface{}, []byte, []byte, uint64, error) {
+	// data, contractKey, adminProof, contractVersion, receipt, gasUsed, error
+) (interface{}, []byte, []byte, *types.ContractVersion, uint64, error) {
 	paramBin, err := json.Marshal(env)
 	if err != nil {
-		return nil, nil, nil, 0, err
+		return nil, nil, nil, nil, nil, 0, err
 	}
 
 	sigInfoBin, err := json.Marshal(sigInfo)
 	if err != nil {
-		return nil, nil, nil, 0, err
+		return nil, nil, nil, nil, nil, 0, err
 	}
 
-	data, gasUsed, err := api.Instantiate(w.cache, codeId, paramBin, initMsg, &gasMeter, store, &goapi, &querier, gasLimit, sigInfoBin, admin)
+	data, receipt, gasUsed, err := api.Instantiate(w.cache, codeId, paramBin, initMsg, &gasMeter, store, &goapi, &querier, gasLimit, sigInfoBin, admin)
 	if err != nil {
-		return nil, nil, nil, gasUsed, err
+		return nil, nil, nil, nil, receipt, gasUsed, err
 	}
 
 	key := data[0:64]
@@ -166,7 +378,7 @@ func (w *Wasmer) Instantiate(
 
 	if err != nil {
 		// unidentified response 🤷
-		return nil, nil, nil, gasUsed, fmt.Errorf("instantiate: cannot parse response from json: %w", err)
+		return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("instantiate: cannot parse response from json: %w", err)
 	}
 
 	isOutputAddressedToReply := len(respV010orV1.InternaReplyEnclaveSig) > 0 && len(respV010orV1.InternalMsgId) > 0
@@ -178,18 +390,18 @@ func (w *Wasmer) Instantiate(
 				InternalMsgId:          respV010orV1.InternalMsgId,
 				InternaReplyEnclaveSig: respV010orV1.InternaReplyEnclaveSig,
 				Data:                   []byte(respV010orV1.V010.Err.GenericErr.Msg),
-			}, nil, nil, gasUsed, fmt.Errorf("%+v", respV010orV1.V010.Err)
+			}, nil, nil, nil, receipt, gasUsed, *respV010orV1.V010.Err
 		}
 
 		if respV010orV1.V010.Ok != nil {
 			if isOutputAddressedToReply {
 				respV010orV1.V010.Ok.Data, err = AppendReplyInternalDataToData(respV010orV1.V010.Ok.Data, respV010orV1.InternaReplyEnclaveSig, respV010orV1.InternalMsgId)
 				if err != nil {
-					return nil, nil, nil, gasUsed, fmt.Errorf("cannot serialize v0.10 DataWithInternalReplyInfo into binary : %w", err)
+					return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("cannot serialize v0.10 DataWithInternalReplyInfo into binary : %w", err)
 				}
 			}
 
-			return respV010orV1.V010.Ok, key, adminProof, gasUsed, nil
+			return respV010orV1.V010.Ok, key, adminProof, respV010orV1.ContractInfo, receipt, gasUsed, nil
 		}
 	}
 
@@ -200,22 +412,22 @@ func (w *Wasmer) Instantiate(
 				InternalMsgId:          respV010orV1.InternalMsgId,
 				InternaReplyEnclaveSig: respV010orV1.InternaReplyEnclaveSig,
 				Data:                   []byte(respV010orV1.V1.Err.GenericErr.Msg),
-			}, nil, nil, gasUsed, fmt.Errorf("%+v", respV010orV1.V1.Err)
+			}, nil, nil, nil, receipt, gasUsed, *respV010orV1.V1.Err
 		}
 
 		if respV010orV1.V1.Ok != nil {
 			if isOutputAddressedToReply {
 				respV010orV1.V1.Ok.Data, err = AppendReplyInternalDataToData(respV010orV1.V1.Ok.Data, respV010orV1.InternaReplyEnclaveSig, respV010orV1.InternalMsgId)
 				if err != nil {
-					return nil, nil, nil, gasUsed, fmt.Errorf("cannot serialize v1 DataWithInternalReplyInfo into binary: %w", err)
+					return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("cannot serialize v1 DataWithInternalReplyInfo into binary: %w", err)
 				}
 			}
 
-			return respV010orV1.V1.Ok, key, adminProof, gasUsed, nil
+			return respV010orV1.V1.Ok, key, adminProof, respV010orV1.ContractInfo, receipt, gasUsed, nil
 		}
 	}
 
-	return nil, nil, nil, gasUsed, fmt.Errorf("instantiate: cannot detect response type (v0.10 or v1)")
+	return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("instantiate: cannot detect response type (v0.10 or v1)")
 }
 
 func AppendReplyInternalDataToData(data []byte, internaReplyEnclaveSig []byte, internalMsgId []byte) ([]byte, error) {
@@ -245,19 +457,20 @@ func (w *Wasmer) Execute(
 	gasLimit uint64,
 	sigInfo types.SigInfo,
 	handleType types.HandleType,
-) (interface{}, uint64, error) {
+	// data, receipt, gasUsed, error
+) (interface{}, *types.ExecutionReceipt, uint64, error) {
 	paramBin, err := json.Marshal(env)
 	if err != nil {
-		return nil, 0, err
+		return nil, nil, 0, err
 	}
 	sigInfoBin, err := json.Marshal(sigInfo)
 	if err != nil {
-		return nil, 0, err
+		return nil, nil, 0, err
 	}
 
-	data, gasUsed, err := api.Handle(w.cache, code, paramBin, executeMsg, &gasMeter, store, &goapi, &querier, gasLimit, sigInfoBin, handleType)
+	data, receipt, gasUsed, err := api.Handle(w.cache, code, paramBin, executeMsg, &gasMeter, store, &goapi, &querier, gasLimit, sigInfoBin, handleType)
 	if err != nil {
-		return nil, gasUsed, err
+		return nil, receipt, gasUsed, err
 	}
 
 	var resp ContractExecResponse
@@ -265,7 +478,7 @@ func (w *Wasmer) Execute(
 
 	if err != nil {
 		// unidentified response 🤷
-		return nil, gasUsed, fmt.Errorf("handle: cannot parse response from json: %w", err)
+		return nil, receipt, gasUsed, fmt.Errorf("handle: cannot parse response from json: %w", err)
 	}
 
 	isOutputAddressedToReply := len(resp.InternaReplyEnclaveSig) > 0 && len(resp.InternalMsgId) > 0
@@ -277,17 +490,17 @@ func (w *Wasmer) Execute(
 				InternalMsgId:          resp.InternalMsgId,
 				InternaReplyEnclaveSig: resp.InternaReplyEnclaveSig,
 				Data:                   []byte(resp.V010.Err.GenericErr.Msg),
-			}, gasUsed, fmt.Errorf("%+v", resp.V010.Err)
+			}, receipt, gasUsed, *resp.V010.Err
 		} else if resp.V010.Ok != nil {
 			if isOutputAddressedToReply {
 				resp.V010.Ok.Data, err = AppendReplyInternalDataToData(resp.V010.Ok.Data, resp.InternaReplyEnclaveSig, resp.InternalMsgId)
 				if err != nil {
-					return nil, gasUsed, fmt.Errorf("cannot serialize v0.10 DataWithInternalReplyInfo into binary : %w", err)
+					return nil, receipt, gasUsed, fmt.Errorf("cannot serialize v0.10 DataWithInternalReplyInfo into binary : %w", err)
 				}
 			}
-			return resp.V010.Ok, gasUsed, nil
+			return resp.V010.Ok, receipt, gasUsed, nil
 		} else {
-			return nil, gasUsed, fmt.Errorf("cannot parse v0.10 handle response: %+v", resp)
+			return nil, receipt, gasUsed, fmt.Errorf("cannot parse v0.10 handle response: %+v", resp)
 		}
 	}
 
@@ -298,57 +511,59 @@ func (w *Wasmer) Execute(
 				InternalMsgId:          resp.InternalMsgId,
 				InternaReplyEnclaveSig: resp.InternaReplyEnclaveSig,
 				Data:                   []byte(resp.V1.Err.GenericErr.Msg),
-			}, gasUsed, fmt.Errorf("%+v", resp.V1.Err)
+			}, receipt, gasUsed, *resp.V1.Err
 		} else if resp.V1.Ok != nil {
 			if isOutputAddressedToReply {
 				resp.V1.Ok.Data, err = AppendReplyInternalDataToData(resp.V1.Ok.Data, resp.InternaReplyEnclaveSig, resp.InternalMsgId)
 				if err != nil {
-					return nil, gasUsed, fmt.Errorf("cannot serialize v1 DataWithInternalReplyInfo into binary: %w", err)
+					return nil, receipt, gasUsed, fmt.Errorf("cannot serialize v1 DataWithInternalReplyInfo into binary: %w", err)
 				}
 			}
-			return resp.V1.Ok, gasUsed, nil
+			return resp.V1.Ok, receipt, gasUsed, nil
 		} else {
-			return nil, gasUsed, fmt.Errorf("cannot parse v1 handle response: %+v", resp)
+			return nil, receipt, gasUsed, fmt.Errorf("cannot parse v1 handle response: %+v", resp)
 		}
 	}
 
 	if resp.IBCBasic != nil {
 		if resp.IBCBasic.Err != nil { //nolint:gocritic
-			return nil, gasUsed, fmt.Errorf("%+v", resp.IBCBasic.Err)
+			return nil, receipt, gasUsed, *resp.IBCBasic.Err
 		} else if resp.IBCBasic.Ok != nil {
-			return resp.IBCBasic.Ok, gasUsed, nil
+			return resp.IBCBasic.Ok, receipt, gasUsed, nil
 		} else {
-			return nil, gasUsed, fmt.Errorf("cannot parse IBCBasic response: %+v", resp)
+			return nil, receipt, gasUsed, fmt.Errorf("cannot parse IBCBasic response: %+v", resp)
 		}
 	}
 
 	if resp.IBCPacketReceive != nil {
 		if resp.IBCPacketReceive.Err != nil { //nolint:gocritic
-			return nil, gasUsed, fmt.Errorf("%+v", resp.IBCPacketReceive.Err)
+			return nil, receipt, gasUsed, *resp.IBCPacketReceive.Err
 		} else if resp.IBCPacketReceive.Ok != nil {
-			return resp.IBCPacketReceive.Ok, gasUsed, nil
+			return resp.IBCPacketReceive.Ok, receipt, gasUsed, nil
 		} else {
-			return nil, gasUsed, fmt.Errorf("cannot parse IBCPacketReceive response: %+v", resp)
+			return nil, receipt, gasUsed, fmt.Errorf("cannot parse IBCPacketReceive response: %+v", resp)
 		}
 	}
 
 	if resp.IBCChannelOpen != nil {
 		if resp.IBCChannelOpen.Err != nil { //nolint:gocritic
-			return nil, gasUsed, fmt.Errorf("%+v", resp.IBCChannelOpen.Err)
+			return nil, receipt, gasUsed, *resp.IBCChannelOpen.Err
 		} else if resp.IBCChannelOpen.Ok != nil {
 			// ibc_channel_open actually returns no data
-			return resp.IBCChannelOpen.Ok, gasUsed, nil
+			return resp.IBCChannelOpen.Ok, receipt, gasUsed, nil
 		} else {
-			return nil, gasUsed, fmt.Errorf("cannot parse IBCChannelOpen response: %+v", resp)
+			return nil, receipt, gasUsed, fmt.Errorf("cannot parse IBCChannelOpen response: %+v", resp)
 		}
 	}
 
-	return nil, gasUsed, fmt.Errorf("handle: cannot detect response type (v0.10 or v1)")
+	return nil, receipt, gasUsed, fmt.Errorf("handle: cannot detect response type (v0.10 or v1)")
 }
 
 // Query allows a client to execute a contract-specific query. If the result is not empty, it should be
 // valid json-encoded data to return to the client.
 // The meaning of path and data can be determined by the code. Path is the suffix of the abci.QueryRequest.Path
+// The signature is nil unless the node signs query responses (see NewWasmer) and this is not a
+// query made by a contract.
 func (w *Wasmer) Query(
 	code CodeHash,
 	env types.Env,
@@ -358,35 +573,35 @@ func (w *Wasmer) Query(
 	querier Querier,
 	gasMeter GasMeter,
 	gasLimit uint64,
-) ([]byte, uint64, error) {
+) ([]byte, *types.QueryResponseSignature, uint64, error) {
 	paramBin, err := json.Marshal(env)
 	if err != nil {
-		return nil, 0, err
+		return nil, nil, 0, err
 	}
-	data, gasUsed, err := api.Query(w.cache, code, paramBin, queryMsg, &gasMeter, store, &goapi, &querier, gasLimit)
+	data, signature, gasUsed, err := api.Query(w.cache, code, paramBin, queryMsg, &gasMeter, store, &goapi, &querier, gasLimit)
 	if err != nil {
-		return nil, gasUsed, err
+		return nil, nil, gasUsed, err
 	}
 
 	var resp types.ContractQueryResponse
 	err = json.Unmarshal(data, &resp)
 	if err != nil {
-		return nil, gasUsed, err
+		return nil, nil, gasUsed, err
 	}
 
 	if resp.Query == nil {
-		return nil, gasUsed, fmt.Errorf("query: cannot detect response type")
+		return nil, nil, gasUsed, fmt.Errorf("query: cannot detect response type")
 	}
 
 	if resp.Query.Err != nil {
-		return nil, gasUsed, fmt.Errorf("%v", resp.Query.Err)
+		return nil, nil, gasUsed, fmt.Errorf("%v", resp.Query.Err)
 	}
 
 	if resp.Query.Ok != nil {
-		return resp.Query.Ok, gasUsed, nil
+		return resp.Query.Ok, signature, gasUsed, nil
 	}
 
-	return nil, gasUsed, fmt.Errorf("query: cannot detect response type")
+	return nil, nil, gasUsed, fmt.Errorf("query: cannot detect response type")
 }
 
 // AnalyzeCode returns a report of static analysis of the wasm contract (uncompiled).
@@ -398,6 +613,86 @@ func (w *Wasmer) AnalyzeCode(
 	return api.AnalyzeCode(w.cache, codeHash)
 }
 
+// Introspect returns the public information of a contract (uncompiled), for wallets and explorers.
+// This contract must have been stored in the cache previously (via Create).
+// It does not run the contract, so it uses no gas.
+func (w *Wasmer) Introspect(
+	codeHash []byte,
+) (*v1types.IntrospectionReport, error) {
+	return api.Introspect(w.cache, codeHash)
+}
+
+// VmCapabilities describes the contracts this node accepts and what it offers them: the interface
+// versions and host functions of contracts, the supported features, the validation limits, the
+// version of the gas schedule and the MRENCLAVE of the enclave, for clients and contract tooling to
+// adapt to the node.
+func (w *Wasmer) VmCapabilities() (*types.VmCapabilities, error) {
+	return api.VmCapabilities(w.cache)
+}
+
+// RevalidateStoredCode reports the stored contracts that would fail validation with the given
+// supported features and validation config (nil for the defaults), e.g. those of an upcoming
+// upgrade, so the breakage can be assessed before it activates. It reads every stored contract and
+// changes nothing.
+func (w *Wasmer) RevalidateStoredCode(supportedFeatures string, validationConfig *types.ValidationConfig) (*types.RevalidationReport, error) {
+	var validationConfigBz []byte
+	if validationConfig != nil {
+		var err error
+		validationConfigBz, err = json.Marshal(validationConfig)
+		if err != nil {
+			return nil, err
+		}
+	}
+	return api.RevalidateStoredCode(w.cache, supportedFeatures, validationConfigBz)
+}
+
+// ContractPanicStats returns how often the contracts of each code hash trapped since the node
+// started, most frequent first, with a recent trap message. Only the code hashes whose contracts
+// trapped most recently are kept.
+func (w *Wasmer) ContractPanicStats() ([]types.ContractPanicStats, error) {
+	return api.ContractPanicStats()
+}
+
+// ConfigureAutoPinning pins the topK code hashes whose contracts were executed the most in each
+// epoch of epochLength blocks, and unpins the ones that dropped out of the top. A topK of zero
+// disables it. Pinned code is kept in memory instead of being read from disk on every call.
+func (w *Wasmer) ConfigureAutoPinning(topK uint32, epochLength uint64) error {
+	return api.ConfigureAutoPinning(w.cache, topK, epochLength)
+}
+
+// AutoPinMetrics returns what auto pinning did since the node started
+func (w *Wasmer) AutoPinMetrics() (*types.AutoPinMetrics, error) {
+	return api.AutoPinMetrics(w.cache)
+}
+
+// GetMetrics returns the hits and misses of the cache and the time it spent loading and parsing
+// Wasm since the node started, with the module cache statistics the enclave reported after its
+// last contract call
+func (w *Wasmer) GetMetrics() (*types.Metrics, error) {
+	return api.GetMetrics(w.cache)
+}
+
+// InstanceStats returns how many times this node executed a contract, at which height it last
+// did and the gas the executions used in total, or nil if it never executed it. The stats are kept
+// by the VM across restarts and are not part of consensus.
+func (w *Wasmer) InstanceStats(contractAddress string) (*types.InstanceStats, error) {
+	return api.InstanceStats(w.cache, contractAddress)
+}
+
+// AnalyzeWasm returns the capabilities of a contract, read from its Wasm without storing or running it,
+// e.g. to describe a contract in its ContractInfo before it is instantiated.
+func (w *Wasmer) AnalyzeWasm(
+	code []byte,
+) (*v1types.ContractAnalysis, error) {
+	return api.AnalyzeWasm(code)
+}
+
+// TakeObservedEvents returns the events the enclave encrypted to the observers of contracts since
+// the last call, one json record per line. Only enclaves built with observer mode produce any.
+func (w *Wasmer) TakeObservedEvents() []byte {
+	return api.TakeObservedEvents()
+}
+
 // Migrate will migrate an existing contract to a new code binary.
 // This takes storage of the data from the original contract and the CodeID of the new contract that should
 // replace it. This allows it to run a migration step if needed, or return an error if unable to migrate
@@ -416,21 +711,21 @@ func (w *Wasmer) Migrate(
 	sigInfo types.SigInfo,
 	admin []byte,
 	adminProof []byte,
-	// data, contractKey, adminProof, gasUsed, error
-) (interface{}, []byte, []byte, uint64, error) {
+	// data, contractKey, adminProof, contractVersion, receipt, gasUsed, error
+) (interface{}, []byte, []byte, *types.ContractVersion, uint64, error) {
 	paramBin, err := json.Marshal(env)
 	if err != nil {
-		return nil, nil, nil, 0, err
+		return nil, nil, nil, nil, nil, 0, err
 	}
 
 	sigInfoBin, err := json.Marshal(sigInfo)
 	if err != nil {
-		return nil, nil, nil, 0, err
+		return nil, nil, nil, nil, nil, 0, err
 	}
 
-	data, gasUsed, err := api.Migrate(w.cache, newCodeId, paramBin, migrateMsg, &gasMeter, store, &goapi, &querier, gasLimit, sigInfoBin, admin, adminProof)
+	data, receipt, gasUsed, err := api.Migrate(w.cache, newCodeId, paramBin, migrateMsg, &gasMeter, store, &goapi, &querier, gasLimit, sigInfoBin, admin, adminProof)
 	if err != nil {
-		return nil, nil, nil, gasUsed, err
+		return nil, nil, nil, nil, receipt, gasUsed, err
 	}
 
 	newContractKey := data[0:64]
@@ -442,7 +737,7 @@ func (w *Wasmer) Migrate(
 
 	if err != nil {
 		// unidentified response 🤷
-		return nil, nil, nil, gasUsed, fmt.Errorf("migrate: cannot parse response from json: %w", err)
+		return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("migrate: cannot parse response from json: %w", err)
 	}
 
 	isOutputAddressedToReply := len(respV010orV1.InternaReplyEnclaveSig) > 0 && len(respV010orV1.InternalMsgId) > 0
@@ -454,18 +749,18 @@ func (w *Wasmer) Migrate(
 				InternalMsgId:          respV010orV1.InternalMsgId,
 				InternaReplyEnclaveSig: respV010orV1.InternaReplyEnclaveSig,
 				Data:                   []byte(respV010orV1.V010.Err.GenericErr.Msg),
-			}, nil, nil, gasUsed, fmt.Errorf("%+v", respV010orV1.V010.Err)
+			}, nil, nil, nil, receipt, gasUsed, *respV010orV1.V010.Err
 		}
 
 		if respV010orV1.V010.Ok != nil {
 			if isOutputAddressedToReply {
 				respV010orV1.V010.Ok.Data, err = AppendReplyInternalDataToData(respV010orV1.V010.Ok.Data, respV010orV1.InternaReplyEnclaveSig, respV010orV1.InternalMsgId)
 				if err != nil {
-					return nil, nil, nil, gasUsed, fmt.Errorf("cannot serialize v0.10 DataWithInternalReplyInfo into binary : %w", err)
+					return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("cannot serialize v0.10 DataWithInternalReplyInfo into binary : %w", err)
 				}
 			}
 
-			return respV010orV1.V010.Ok, newContractKey, proof, gasUsed, nil
+			return respV010orV1.V010.Ok, newContractKey, proof, respV010orV1.ContractInfo, receipt, gasUsed, nil
 		}
 	}
 
@@ -476,22 +771,22 @@ func (w *Wasmer) Migrate(
 				InternalMsgId:          respV010orV1.InternalMsgId,
 				InternaReplyEnclaveSig: respV010orV1.InternaReplyEnclaveSig,
 				Data:                   []byte(respV010orV1.V1.Err.GenericErr.Msg),
-			}, nil, nil, gasUsed, fmt.Errorf("%+v", respV010orV1.V1.Err)
+			}, nil, nil, nil, receipt, gasUsed, *respV010orV1.V1.Err
 		}
 
 		if respV010orV1.V1.Ok != nil {
 			if isOutputAddressedToReply {
 				respV010orV1.V1.Ok.Data, err = AppendReplyInternalDataToData(respV010orV1.V1.Ok.Data, respV010orV1.InternaReplyEnclaveSig, respV010orV1.InternalMsgId)
 				if err != nil {
-					return nil, nil, nil, gasUsed, fmt.Errorf("cannot serialize v1 DataWithInternalReplyInfo into binary: %w", err)
+					return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("cannot serialize v1 DataWithInternalReplyInfo into binary: %w", err)
 				}
 			}
 
-			return respV010orV1.V1.Ok, newContractKey, proof, gasUsed, nil
+			return respV010orV1.V1.Ok, newContractKey, proof, respV010orV1.ContractInfo, receipt, gasUsed, nil
 		}
 	}
 
-	return nil, nil, nil, gasUsed, fmt.Errorf("migrate: cannot detect response type (v0.10 or v1)")
+	return nil, nil, nil, nil, receipt, gasUsed, fmt.Errorf("migrate: cannot detect response type (v0.10 or v1)")
 }
 
 // UpdateAdmin will update or clear a contract admin.
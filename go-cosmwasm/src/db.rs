@@ -26,6 +26,9 @@ pub struct DB_vtable {
     pub write_db:
         extern "C" fn(*mut db_t, *mut gas_meter_t, *mut u64, Buffer, Buffer, *mut Buffer) -> i32,
     pub remove_db: extern "C" fn(*mut db_t, *mut gas_meter_t, *mut u64, Buffer, *mut Buffer) -> i32,
+    // entries encoded by `encode_entries`, written in order
+    pub write_multiple_db:
+        extern "C" fn(*mut db_t, *mut gas_meter_t, *mut u64, Buffer, *mut Buffer) -> i32,
     // order -> Ascending = 1, Descending = 2
     // Note: we cannot set gas_meter on the returned GoIter due to cgo memory safety.
     // Since we have the pointer in rust already, we must set that manually
@@ -173,6 +176,29 @@ impl Storage for DB {
         (Ok(()), gas_info)
     }
 
+    fn set_multiple(&mut self, entries: &[(Vec<u8>, Vec<u8>)]) -> FfiResult<()> {
+        let entries_buf = Buffer::from_vec(encode_entries(entries));
+        let mut err = Buffer::default();
+        let mut used_gas = 0_u64;
+        let go_result: GoResult = (self.vtable.write_multiple_db)(
+            self.state,
+            self.gas_meter,
+            &mut used_gas as *mut u64,
+            entries_buf,
+            &mut err as *mut Buffer,
+        )
+        .into();
+        let gas_info = GasInfo::with_externally_used(used_gas);
+        let _entries = unsafe { entries_buf.consume() };
+        let default = || format!("Failed to set {} keys in the db", entries.len());
+        unsafe {
+            if let Err(err) = go_result.into_ffi_result(err, default) {
+                return (Err(err), gas_info);
+            }
+        }
+        (Ok(()), gas_info)
+    }
+
     fn remove(&mut self, key: &[u8]) -> FfiResult<()> {
         let key_buf = Buffer::from_vec(key.to_vec());
         let mut err = Buffer::default();
@@ -201,3 +227,20 @@ impl Storage for DB {
         (Ok(()), gas_info)
     }
 }
+
+/// Encodes entries as `key length | key | value length | value` for each entry, with lengths as
+/// big endian u32
+fn encode_entries(entries: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+    let size = entries
+        .iter()
+        .map(|(key, value)| 8 + key.len() + value.len())
+        .sum();
+    let mut encoded = Vec::with_capacity(size);
+    for (key, value) in entries {
+        encoded.extend_from_slice(&(key.len() as u32).to_be_bytes());
+        encoded.extend_from_slice(key);
+        encoded.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        encoded.extend_from_slice(value);
+    }
+    encoded
+}
@@ -1,6 +1,6 @@
 use errno::{set_errno, Errno};
 
-use cosmwasm_sgx_vm::VmError;
+use cosmwasm_sgx_vm::{ValidationFailure, VmError};
 use snafu::Snafu;
 
 use crate::memory::Buffer;
@@ -35,6 +35,14 @@ pub enum Error {
         #[cfg(feature = "backtraces")]
         backtrace: snafu::Backtrace,
     },
+    /// A `VmErr` of static validation, which keeps why the contract was rejected
+    #[snafu(display("Execution error: {}", msg))]
+    ValidationErr {
+        msg: String,
+        failure: ValidationFailure,
+        #[cfg(feature = "backtraces")]
+        backtrace: snafu::Backtrace,
+    },
     #[snafu(display("{}", msg))]
     GoCwEnclaveError {
         msg: String,
@@ -66,6 +74,14 @@ impl Error {
         .build()
     }
 
+    pub fn validation_err<S: ToString>(msg: S, failure: ValidationFailure) -> Self {
+        ValidationErr {
+            msg: msg.to_string(),
+            failure,
+        }
+        .build()
+    }
+
     pub fn enclave_err<S: ToString>(msg: S) -> Self {
         GoCwEnclaveError {
             msg: msg.to_string(),
@@ -80,6 +96,9 @@ impl Error {
 
 impl From<VmError> for Error {
     fn from(source: VmError) -> Self {
+        if let Some(failure) = source.validation_failure().cloned() {
+            return Error::validation_err(source, failure);
+        }
         match source {
             VmError::GasDepletion => Error::out_of_gas(),
             _ => Error::vm_err(source),
@@ -105,6 +124,8 @@ enum ErrnoValue {
     Success = 0,
     Other = 1,
     OutOfGas = 2,
+    /// The error is a JSON object with the message, the code and the `ValidationFailure`
+    ValidationFailure = 3,
 }
 
 pub fn clear_error() {
@@ -112,12 +133,21 @@ pub fn clear_error() {
 }
 
 pub fn set_error(err: Error, errout: Option<&mut Buffer>) {
-    let msg = err.to_string();
+    let msg = match &err {
+        Error::ValidationErr { failure, .. } => serde_json::json!({
+            "msg": err.to_string(),
+            "code": failure.code(),
+            "failure": failure,
+        })
+        .to_string(),
+        _ => err.to_string(),
+    };
     if let Some(mb) = errout {
         *mb = Buffer::from_vec(msg.into_bytes());
     }
     let errno = match err {
         Error::OutOfGas { .. } => ErrnoValue::OutOfGas,
+        Error::ValidationErr { .. } => ErrnoValue::ValidationFailure,
         _ => ErrnoValue::Other,
     } as i32;
     set_errno(Errno(errno));
@@ -233,6 +263,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_error_serializes_validation_failures() {
+        let error = Error::validation_err(
+            "Error during static Wasm validation: Wasm contract must not have a start function",
+            ValidationFailure::StartFunction,
+        );
+        let mut errout = Buffer::default();
+        set_error(error, Some(&mut errout));
+
+        assert_eq!(errno::errno().0, ErrnoValue::ValidationFailure as i32);
+        let msg: serde_json::Value = serde_json::from_slice(&unsafe { errout.consume() }).unwrap();
+        assert_eq!(
+            msg,
+            serde_json::json!({
+                "msg": "Execution error: Error during static Wasm validation: Wasm contract must not have a start function",
+                "code": "start_function",
+                "failure": {"code": "start_function"},
+            })
+        );
+    }
+
     // Tests of `impl From<X> for Error` converters
 
     #[test]
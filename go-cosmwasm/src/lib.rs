@@ -21,12 +21,16 @@ use crate::error::{clear_error, handle_c_error, handle_c_error_default, set_erro
 
 use cosmwasm_sgx_vm::untrusted_init_bootstrap;
 use cosmwasm_sgx_vm::{
-    call_handle_raw, call_init_raw, call_migrate_raw, call_query_raw, call_update_admin_raw,
-    features_from_csv, Checksum, CosmCache, Extern,
+    call_handle_raw, call_init_raw, call_migrate_raw, call_query_raw_signed, call_update_admin_raw,
+    features_from_csv, AutoPinConfig, Checksum, CosmCache, DeveloperSignature, Extern,
+    WasmValidationConfig,
 };
 use cosmwasm_sgx_vm::{
-    create_attestation_report_u, untrusted_get_encrypted_genesis_seed,
-    untrusted_get_encrypted_seed, untrusted_health_check, untrusted_init_node, untrusted_key_gen,
+    create_attestation_report_u, untrusted_decrypt_state_keys, untrusted_decrypt_tx_msgs,
+    untrusted_get_encrypted_genesis_seed, untrusted_get_encrypted_previous_seeds,
+    untrusted_get_encrypted_seed, untrusted_health_check, untrusted_init_node,
+    untrusted_init_previous_seeds, untrusted_key_gen, untrusted_prove_contract_key,
+    untrusted_rewrap_query, untrusted_seed_rotation_activate,
 };
 
 use ctor::ctor;
@@ -93,6 +97,27 @@ pub extern "C" fn get_encrypted_seed(cert: Buffer, err: Option<&mut Buffer>) ->
     }
 }
 
+/// The seeds that were current before each seed rotation, encrypted for the node of `cert`. Empty if
+/// the seed was never rotated.
+#[no_mangle]
+pub extern "C" fn get_encrypted_previous_seeds(cert: Buffer, err: Option<&mut Buffer>) -> Buffer {
+    let r = catch_unwind(|| do_get_encrypted_previous_seeds(cert))
+        .unwrap_or_else(|_| Err(Error::panic()));
+    let data = handle_c_error(r, err);
+    Buffer::from_vec(data)
+}
+
+fn do_get_encrypted_previous_seeds(cert: Buffer) -> Result<Vec<u8>, Error> {
+    let cert_slice = unsafe { cert.read() }.ok_or_else(|| Error::empty_arg("attestation_cert"))?;
+    match untrusted_get_encrypted_previous_seeds(cert_slice) {
+        // An error happened in the SGX sdk.
+        Err(e) => Err(Error::enclave_err(e.to_string())),
+        // An error was returned from the enclave.
+        Ok(Err(e)) => Err(Error::enclave_err(e.to_string())),
+        Ok(Ok(seeds)) => Ok(seeds),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn get_encrypted_genesis_seed(pk: Buffer, err: Option<&mut Buffer>) -> Buffer {
     trace!("Called get_encrypted_genesis_seed");
@@ -194,6 +219,27 @@ pub extern "C" fn init_node(
     }
 }
 
+/// Seals the seeds that were current before each seed rotation, as shared with the node when it
+/// registered. Called after `init_node`, only if the node got any.
+#[no_mangle]
+pub extern "C" fn init_previous_seeds(
+    master_key: Buffer,
+    encrypted_seeds: Buffer,
+    err: Option<&mut Buffer>,
+) -> bool {
+    let r = catch_unwind(|| do_init_previous_seeds(master_key, encrypted_seeds))
+        .unwrap_or_else(|_| Err(Error::panic()));
+    handle_c_error_default(r.map(|()| true), err)
+}
+
+fn do_init_previous_seeds(master_key: Buffer, encrypted_seeds: Buffer) -> Result<(), Error> {
+    let pk_slice = unsafe { master_key.read() }.ok_or_else(|| Error::empty_arg("master_key"))?;
+    let encrypted_seeds_slice =
+        unsafe { encrypted_seeds.read() }.ok_or_else(|| Error::empty_arg("encrypted_seeds"))?;
+    untrusted_init_previous_seeds(pk_slice, encrypted_seeds_slice)
+        .map_err(|e| Error::enclave_err(e.to_string()))
+}
+
 #[no_mangle]
 pub extern "C" fn create_attestation_report(api_key: Buffer, err: Option<&mut Buffer>) -> bool {
     let api_key_slice = match unsafe { api_key.read() } {
@@ -224,12 +270,23 @@ fn to_extern(storage: DB, api: GoApi, querier: GoQuerier) -> Extern<DB, GoApi, G
 pub extern "C" fn init_cache(
     data_dir: Buffer,
     supported_features: Buffer,
+    // JSON of a WasmValidationConfig, empty for the default
+    validation_config: Buffer,
+    // JSON of an UpgradeSchedule, empty for the default
+    upgrade_schedule: Buffer,
     // TODO: remove unused cache size
     _cache_size: usize,
     err: Option<&mut Buffer>,
 ) -> *mut cache_t {
-    let r = catch_unwind(|| do_init_cache(data_dir, supported_features))
-        .unwrap_or_else(|_| Err(Error::panic()));
+    let r = catch_unwind(|| {
+        do_init_cache(
+            data_dir,
+            supported_features,
+            validation_config,
+            upgrade_schedule,
+        )
+    })
+    .unwrap_or_else(|_| Err(Error::panic()));
     match r {
         Ok(t) => {
             clear_error();
@@ -244,6 +301,7 @@ pub extern "C" fn init_cache(
 
 #[no_mangle]
 pub extern "C" fn submit_block_signatures(
+    height: u64,
     header: Buffer,
     commit: Buffer,
     txs: Buffer,
@@ -302,6 +360,7 @@ pub extern "C" fn submit_block_signatures(
     // };
 
     match cosmwasm_sgx_vm::untrusted_submit_block_signatures(
+        height,
         header_slice,
         commit_slice,
         txs_slice,
@@ -320,9 +379,261 @@ pub extern "C" fn submit_block_signatures(
     }
 }
 
+/// `msgs` holds the encrypted messages of a transaction, each prefixed by its length as a big
+/// endian u32. Returns the decryption status of each message, one byte per message. The enclave
+/// keeps their plaintext for their execution if `keep_plaintexts` is set.
+#[no_mangle]
+pub extern "C" fn decrypt_tx_msgs(
+    msgs: Buffer,
+    keep_plaintexts: bool,
+    err: Option<&mut Buffer>,
+) -> Buffer {
+    let r = catch_unwind(|| do_decrypt_tx_msgs(msgs, keep_plaintexts))
+        .unwrap_or_else(|_| Err(Error::panic()));
+    let data = handle_c_error(r, err);
+    Buffer::from_vec(data)
+}
+
+fn do_decrypt_tx_msgs(msgs: Buffer, keep_plaintexts: bool) -> Result<Vec<u8>, Error> {
+    let msgs = unsafe { msgs.read() }.ok_or_else(|| Error::empty_arg(MSG_ARG))?;
+    let msgs = split_length_prefixed(msgs).ok_or_else(|| Error::vm_err("truncated message"))?;
+
+    let statuses = untrusted_decrypt_tx_msgs(&msgs, keep_plaintexts)
+        .map_err(|e| Error::enclave_err(e.to_string()))?;
+    Ok(statuses.into_iter().map(|status| status as u8).collect())
+}
+
+/// `current_contract_key` is empty unless the contract was migrated, in which case it holds the
+/// current contract key followed by its migration proof. Returns the node public key, the contract
+/// encryption public key, the signer public key and the signature, concatenated.
+#[no_mangle]
+pub extern "C" fn prove_contract_key(
+    contract_address: Buffer,
+    code_hash: Buffer,
+    og_contract_key: Buffer,
+    current_contract_key: Buffer,
+    err: Option<&mut Buffer>,
+) -> Buffer {
+    let r = catch_unwind(|| {
+        do_prove_contract_key(
+            contract_address,
+            code_hash,
+            og_contract_key,
+            current_contract_key,
+        )
+    })
+    .unwrap_or_else(|_| Err(Error::panic()));
+    let data = handle_c_error(r, err);
+    Buffer::from_vec(data)
+}
+
+fn do_prove_contract_key(
+    contract_address: Buffer,
+    code_hash: Buffer,
+    og_contract_key: Buffer,
+    current_contract_key: Buffer,
+) -> Result<Vec<u8>, Error> {
+    let contract_address =
+        unsafe { contract_address.read() }.ok_or_else(|| Error::empty_arg(CONTRACT_ADDRESS_ARG))?;
+    let code_hash: Checksum = unsafe { code_hash.read() }
+        .ok_or_else(|| Error::empty_arg(CODE_HASH_ARG))?
+        .try_into()?;
+    let og_contract_key: &[u8; 64] = unsafe { og_contract_key.read() }
+        .ok_or_else(|| Error::empty_arg(CONTRACT_KEY_ARG))?
+        .try_into()
+        .map_err(|_| Error::vm_err("invalid contract key length"))?;
+    let current_contract_key = read_current_contract_key(&current_contract_key)?;
+
+    let proof = untrusted_prove_contract_key(
+        contract_address,
+        &code_hash,
+        og_contract_key,
+        current_contract_key,
+    )
+    .map_err(|e| Error::enclave_err(e.to_string()))?;
+
+    Ok([
+        &proof.node_public_key[..],
+        &proof.encryption_public_key[..],
+        &proof.signer_public_key[..],
+        &proof.signature[..],
+    ]
+    .concat())
+}
+
+/// Splits the current contract key of a migrated contract from its migration proof. Empty if the
+/// contract was never migrated.
+fn read_current_contract_key(
+    current_contract_key: &Buffer,
+) -> Result<Option<(&[u8; 64], &[u8; 32])>, Error> {
+    match unsafe { current_contract_key.read() } {
+        None | Some([]) => Ok(None),
+        Some(current) if current.len() == 96 => {
+            let (key, proof) = current.split_at(64);
+            Ok(Some((key.try_into().unwrap(), proof.try_into().unwrap())))
+        }
+        Some(_) => Err(Error::vm_err("invalid current contract key length")),
+    }
+}
+
+/// Re-wraps an encrypted query addressed to `previous_code_hash`, the code a contract ran before
+/// its last migration, for `current_code_hash`. `og_contract_key` and
+/// `previous_current_contract_key` are the keys of the contract when it ran the previous code, in
+/// the format of `prove_contract_key`, and `current_contract_key` the key and proof `migrator`
+/// generated at `migration_height`. The enclave re-wraps queries for `grace_blocks` blocks after
+/// the migration.
+#[no_mangle]
+pub extern "C" fn rewrap_query(
+    contract_address: Buffer,
+    previous_code_hash: Buffer,
+    og_contract_key: Buffer,
+    previous_current_contract_key: Buffer,
+    current_code_hash: Buffer,
+    current_contract_key: Buffer,
+    migrator: Buffer,
+    migration_height: u64,
+    current_height: u64,
+    grace_blocks: u64,
+    msg: Buffer,
+    err: Option<&mut Buffer>,
+) -> Buffer {
+    let r = catch_unwind(|| {
+        do_rewrap_query(
+            contract_address,
+            previous_code_hash,
+            og_contract_key,
+            previous_current_contract_key,
+            current_code_hash,
+            current_contract_key,
+            migrator,
+            migration_height,
+            current_height,
+            grace_blocks,
+            msg,
+        )
+    })
+    .unwrap_or_else(|_| Err(Error::panic()));
+    let data = handle_c_error(r, err);
+    Buffer::from_vec(data)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn do_rewrap_query(
+    contract_address: Buffer,
+    previous_code_hash: Buffer,
+    og_contract_key: Buffer,
+    previous_current_contract_key: Buffer,
+    current_code_hash: Buffer,
+    current_contract_key: Buffer,
+    migrator: Buffer,
+    migration_height: u64,
+    current_height: u64,
+    grace_blocks: u64,
+    msg: Buffer,
+) -> Result<Vec<u8>, Error> {
+    let contract_address =
+        unsafe { contract_address.read() }.ok_or_else(|| Error::empty_arg(CONTRACT_ADDRESS_ARG))?;
+    let previous_code_hash: Checksum = unsafe { previous_code_hash.read() }
+        .ok_or_else(|| Error::empty_arg(CODE_HASH_ARG))?
+        .try_into()?;
+    let og_contract_key: &[u8; 64] = unsafe { og_contract_key.read() }
+        .ok_or_else(|| Error::empty_arg(CONTRACT_KEY_ARG))?
+        .try_into()
+        .map_err(|_| Error::vm_err("invalid contract key length"))?;
+    let previous_current_contract_key = read_current_contract_key(&previous_current_contract_key)?;
+    let current_code_hash: Checksum = unsafe { current_code_hash.read() }
+        .ok_or_else(|| Error::empty_arg(CODE_HASH_ARG))?
+        .try_into()?;
+    let current_contract_key = read_current_contract_key(&current_contract_key)?
+        .ok_or_else(|| Error::empty_arg(CURRENT_CONTRACT_KEY_ARG))?;
+    let migrator = unsafe { migrator.read() }.ok_or_else(|| Error::empty_arg(MIGRATOR_ARG))?;
+    let msg = unsafe { msg.read() }.ok_or_else(|| Error::empty_arg(MSG_ARG))?;
+
+    untrusted_rewrap_query(
+        contract_address,
+        &previous_code_hash,
+        og_contract_key,
+        previous_current_contract_key,
+        &current_code_hash,
+        current_contract_key,
+        migrator,
+        migration_height,
+        current_height,
+        grace_blocks,
+        msg,
+    )
+    .map_err(|e| Error::enclave_err(e.to_string()))
+}
+
+/// Decrypts the length prefixed stored state keys of the contract with `og_contract_key`. Returns
+/// the plaintext keys, length prefixed the same way, with a length of `u32::MAX` for keys that
+/// can't be decrypted. Only enclaves built in software mode for migration tooling support it, once
+/// governance activates it. `features` holds the active features like the env of a call.
+#[no_mangle]
+pub extern "C" fn decrypt_state_keys(
+    og_contract_key: Buffer,
+    keys: Buffer,
+    features: Buffer,
+    err: Option<&mut Buffer>,
+) -> Buffer {
+    let r = catch_unwind(|| do_decrypt_state_keys(og_contract_key, keys, features))
+        .unwrap_or_else(|_| Err(Error::panic()));
+    let data = handle_c_error(r, err);
+    Buffer::from_vec(data)
+}
+
+fn do_decrypt_state_keys(
+    og_contract_key: Buffer,
+    keys: Buffer,
+    features: Buffer,
+) -> Result<Vec<u8>, Error> {
+    let og_contract_key: &[u8; 64] = unsafe { og_contract_key.read() }
+        .ok_or_else(|| Error::empty_arg(CONTRACT_KEY_ARG))?
+        .try_into()
+        .map_err(|_| Error::vm_err("invalid contract key length"))?;
+    let keys = unsafe { keys.read() }.ok_or_else(|| Error::empty_arg(KEYS_ARG))?;
+    let keys = split_length_prefixed(keys).ok_or_else(|| Error::vm_err("truncated state keys"))?;
+    let features =
+        unsafe { features.read() }.ok_or_else(|| Error::empty_arg(ACTIVE_FEATURES_ARG))?;
+
+    let decrypted = untrusted_decrypt_state_keys(og_contract_key, &keys, features)
+        .map_err(|e| Error::enclave_err(e.to_string()))?;
+
+    let mut prefixed = vec![];
+    for key in decrypted {
+        match key {
+            Some(key) => {
+                prefixed.extend_from_slice(&(key.len() as u32).to_be_bytes());
+                prefixed.extend_from_slice(&key);
+            }
+            None => prefixed.extend_from_slice(&u32::MAX.to_be_bytes()),
+        }
+    }
+    Ok(prefixed)
+}
+
+fn split_length_prefixed(mut bytes: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut split = vec![];
+    while !bytes.is_empty() {
+        if bytes.len() < 4 {
+            return None;
+        }
+        let (len, rest) = bytes.split_at(4);
+        let len = u32::from_be_bytes(len.try_into().ok()?) as usize;
+        if rest.len() < len {
+            return None;
+        }
+        let (msg, rest) = rest.split_at(len);
+        split.push(msg);
+        bytes = rest;
+    }
+    Some(split)
+}
+
 // store some common string for argument names
 static DATA_DIR_ARG: &str = "data_dir";
 static FEATURES_ARG: &str = "supported_features";
+static ACTIVE_FEATURES_ARG: &str = "active_features";
 static CACHE_ARG: &str = "cache";
 static WASM_ARG: &str = "wasm";
 static CODE_ID_ARG: &str = "code_id";
@@ -332,10 +643,20 @@ static GAS_USED_ARG: &str = "gas_used";
 static SIG_INFO_ARG: &str = "sig_info";
 static CURRENT_ADMIN_ARG: &str = "current_admin";
 static CURRENT_ADMIN_PROOF_ARG: &str = "current_admin_proof";
+static CHAIN_ID_ARG: &str = "chain_id";
+static CONTRACT_ADDRESS_ARG: &str = "contract_address";
+static CODE_HASH_ARG: &str = "code_hash";
+static CONTRACT_KEY_ARG: &str = "og_contract_key";
+static CURRENT_CONTRACT_KEY_ARG: &str = "current_contract_key";
+static MIGRATOR_ARG: &str = "migrator";
+static KEYS_ARG: &str = "keys";
+static ENCLAVE_FILE_ARG: &str = "enclave_file";
 
 fn do_init_cache(
     data_dir: Buffer,
     supported_features: Buffer,
+    validation_config: Buffer,
+    upgrade_schedule: Buffer,
 ) -> Result<*mut CosmCache<DB, GoApi, GoQuerier>, Error> {
     let dir = unsafe { data_dir.read() }.ok_or_else(|| Error::empty_arg(DATA_DIR_ARG))?;
     let dir_str = from_utf8(dir)?;
@@ -344,7 +665,13 @@ fn do_init_cache(
         unsafe { supported_features.read() }.ok_or_else(|| Error::empty_arg(FEATURES_ARG))?;
     let features_str = from_utf8(features_bin)?;
     let features = features_from_csv(features_str);
-    let cache = unsafe { CosmCache::new(dir_str, features) }?;
+    let validation_config =
+        WasmValidationConfig::from_json(unsafe { validation_config.read() }.unwrap_or_default())?;
+    // The schedule must be in place before the first call reaches an enclave
+    cosmwasm_sgx_vm::configure_upgrade_schedule(cosmwasm_sgx_vm::UpgradeSchedule::from_json(
+        unsafe { upgrade_schedule.read() }.unwrap_or_default(),
+    )?);
+    let cache = unsafe { CosmCache::new(dir_str, features, validation_config) }?;
     let out = Box::new(cache);
     Ok(Box::into_raw(out))
 }
@@ -366,12 +693,16 @@ pub extern "C" fn release_cache(cache: *mut cache_t) {
 #[repr(C)]
 pub struct EnclaveRuntimeConfig {
     pub module_cache_size: u32,
+    pub sign_execution_receipts: bool,
+    pub sign_query_responses: bool,
 }
 
 impl EnclaveRuntimeConfig {
     fn to_sgx_vm(&self) -> cosmwasm_sgx_vm::EnclaveRuntimeConfig {
         cosmwasm_sgx_vm::EnclaveRuntimeConfig {
             module_cache_size: self.module_cache_size,
+            sign_execution_receipts: self.sign_execution_receipts,
+            sign_query_responses: self.sign_query_responses,
         }
     }
 }
@@ -392,19 +723,169 @@ pub extern "C" fn configure_enclave_runtime(
 }
 
 #[no_mangle]
-pub extern "C" fn create(cache: *mut cache_t, wasm: Buffer, err: Option<&mut Buffer>) -> Buffer {
+pub extern "C" fn validate_enclave_launch(
+    chain_id: Buffer,
+    allow_debug_enclave: bool,
+    err: Option<&mut Buffer>,
+) {
+    let r = catch_unwind(|| do_validate_enclave_launch(chain_id, allow_debug_enclave))
+        .unwrap_or_else(|_| Err(Error::panic()));
+
+    if let Err(e) = r {
+        set_error(e, err);
+    } else {
+        clear_error();
+    }
+}
+
+fn do_validate_enclave_launch(chain_id: Buffer, allow_debug_enclave: bool) -> Result<(), Error> {
+    let chain_id = unsafe { chain_id.read() }.ok_or_else(|| Error::empty_arg(CHAIN_ID_ARG))?;
+    let chain_id = from_utf8(chain_id)?;
+    cosmwasm_sgx_vm::validate_enclave_launch(chain_id, allow_debug_enclave)?;
+    Ok(())
+}
+
+/// Loads the previous version of the enclave next to the current one, for the blocks before the
+/// upgrade to it. Must be called before the enclave runtime is configured.
+#[no_mangle]
+pub extern "C" fn load_legacy_enclave(enclave_file: Buffer, err: Option<&mut Buffer>) {
+    let r = catch_unwind(|| do_load_legacy_enclave(enclave_file))
+        .unwrap_or_else(|_| Err(Error::panic()));
+
+    if let Err(e) = r {
+        set_error(e, err);
+    } else {
+        clear_error();
+    }
+}
+
+fn do_load_legacy_enclave(enclave_file: Buffer) -> Result<(), Error> {
+    let enclave_file =
+        unsafe { enclave_file.read() }.ok_or_else(|| Error::empty_arg(ENCLAVE_FILE_ARG))?;
+    let enclave_file = from_utf8(enclave_file)?;
+    cosmwasm_sgx_vm::load_legacy_enclave(enclave_file)?;
+    Ok(())
+}
+
+/// Activates a seed rotation candidate in the enclave. `approvals` are concatenated approvals, and
+/// `certs` the attestation certificates of the nodes that made them, in the same order, each
+/// prefixed with its length as a big endian u32. Returns whether the node must be restarted to
+/// start using the new seed.
+#[no_mangle]
+pub extern "C" fn activate_seed_rotation(
+    encrypted_candidate: Buffer,
+    activation_height: u64,
+    current_height: u64,
+    threshold: u32,
+    approvals: Buffer,
+    certs: Buffer,
+    err: Option<&mut Buffer>,
+) -> bool {
+    let r = catch_unwind(|| {
+        do_activate_seed_rotation(
+            encrypted_candidate,
+            activation_height,
+            current_height,
+            threshold,
+            approvals,
+            certs,
+        )
+    })
+    .unwrap_or_else(|_| Err(Error::panic()));
+
+    match r {
+        Ok(restart_required) => {
+            clear_error();
+            restart_required
+        }
+        Err(e) => {
+            set_error(e, err);
+            false
+        }
+    }
+}
+
+fn do_activate_seed_rotation(
+    encrypted_candidate: Buffer,
+    activation_height: u64,
+    current_height: u64,
+    threshold: u32,
+    approvals: Buffer,
+    certs: Buffer,
+) -> Result<bool, Error> {
+    let encrypted_candidate = unsafe { encrypted_candidate.read() }
+        .ok_or_else(|| Error::empty_arg("encrypted_candidate"))?;
+    let approvals = unsafe { approvals.read() }.ok_or_else(|| Error::empty_arg("approvals"))?;
+    let mut certs = unsafe { certs.read() }.ok_or_else(|| Error::empty_arg("certs"))?;
+
+    let mut cert_list = vec![];
+    while !certs.is_empty() {
+        if certs.len() < 4 {
+            return Err(Error::vm_err("certificate length is truncated"));
+        }
+        let len = u32::from_be_bytes(certs[..4].try_into().unwrap()) as usize;
+        if certs.len() - 4 < len {
+            return Err(Error::vm_err("certificate is truncated"));
+        }
+        cert_list.push(&certs[4..4 + len]);
+        certs = &certs[4 + len..];
+    }
+
+    match untrusted_seed_rotation_activate(
+        encrypted_candidate,
+        activation_height,
+        current_height,
+        threshold,
+        approvals,
+        &cert_list,
+    ) {
+        Err(e) => Err(Error::enclave_err(e.to_string())),
+        Ok(Err(e)) => Err(Error::enclave_err(e.to_string())),
+        Ok(Ok(restart_required)) => Ok(restart_required),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn create(
+    cache: *mut cache_t,
+    wasm: Buffer,
+    // whether the chain accepts contracts using the bulk memory proposal
+    bulk_memory: bool,
+    // the developer key and signature of `wasm`, both empty if it isn't signed
+    developer_pub_key: Buffer,
+    developer_signature: Buffer,
+    err: Option<&mut Buffer>,
+) -> Buffer {
     let r = match to_cache(cache) {
-        Some(c) => catch_unwind(AssertUnwindSafe(move || do_create(c, wasm)))
-            .unwrap_or_else(|_| Err(Error::panic())),
+        Some(c) => catch_unwind(AssertUnwindSafe(move || {
+            do_create(c, wasm, bulk_memory, developer_pub_key, developer_signature)
+        }))
+        .unwrap_or_else(|_| Err(Error::panic())),
         None => Err(Error::empty_arg(CACHE_ARG)),
     };
     let data = handle_c_error(r, err);
     Buffer::from_vec(data)
 }
 
-fn do_create(cache: &mut CosmCache<DB, GoApi, GoQuerier>, wasm: Buffer) -> Result<Checksum, Error> {
+fn do_create(
+    cache: &mut CosmCache<DB, GoApi, GoQuerier>,
+    wasm: Buffer,
+    bulk_memory: bool,
+    developer_pub_key: Buffer,
+    developer_signature: Buffer,
+) -> Result<Checksum, Error> {
     let wasm = unsafe { wasm.read() }.ok_or_else(|| Error::empty_arg(WASM_ARG))?;
-    let checksum = cache.save_wasm(wasm)?;
+    let public_key = unsafe { developer_pub_key.read() }.unwrap_or_default();
+    let signature = unsafe { developer_signature.read() }.unwrap_or_default();
+    let developer_signature = if public_key.is_empty() && signature.is_empty() {
+        None
+    } else {
+        Some(DeveloperSignature {
+            public_key,
+            signature,
+        })
+    };
+    let checksum = cache.save_signed_wasm(wasm, bulk_memory, developer_signature)?;
     Ok(checksum)
 }
 
@@ -427,6 +908,130 @@ fn do_get_code(cache: &mut CosmCache<DB, GoApi, GoQuerier>, id: Buffer) -> Resul
     Ok(wasm)
 }
 
+#[no_mangle]
+pub extern "C" fn remove_code(cache: *mut cache_t, id: Buffer, err: Option<&mut Buffer>) -> bool {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || do_remove_code(c, id)))
+            .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+    handle_c_error_default(r, err)
+}
+
+fn do_remove_code(cache: &mut CosmCache<DB, GoApi, GoQuerier>, id: Buffer) -> Result<bool, Error> {
+    let id: Checksum = unsafe { id.read() }
+        .ok_or_else(|| Error::empty_arg(CACHE_ARG))?
+        .try_into()?;
+    let removed = cache.remove_wasm(&id)?;
+    Ok(removed)
+}
+
+/// Keeps a stored code in memory and its module in the enclave across executions, see
+/// `CosmCache::pin`
+#[no_mangle]
+pub extern "C" fn pin(cache: *mut cache_t, checksum: Buffer, error_msg: Option<&mut Buffer>) {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || do_pin(c, checksum, true)))
+            .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+    handle_c_error_default(r, error_msg)
+}
+
+#[no_mangle]
+pub extern "C" fn unpin(cache: *mut cache_t, checksum: Buffer, error_msg: Option<&mut Buffer>) {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || do_pin(c, checksum, false)))
+            .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_pin(
+    cache: &mut CosmCache<DB, GoApi, GoQuerier>,
+    checksum: Buffer,
+    pinned: bool,
+) -> Result<(), Error> {
+    let checksum: Checksum = unsafe { checksum.read() }
+        .ok_or_else(|| Error::empty_arg(CODE_ID_ARG))?
+        .try_into()?;
+    if pinned {
+        cache.pin(&checksum)?;
+    } else {
+        cache.unpin(&checksum)?;
+    }
+    Ok(())
+}
+
+/// Loads and validates the stored code of `checksums`, concatenated, on a background thread, and pins
+/// it if `pin` is set, see `CosmCache::prewarm`. Returns once the thread is started.
+#[no_mangle]
+pub extern "C" fn prewarm(
+    cache: *mut cache_t,
+    checksums: Buffer,
+    pin: bool,
+    error_msg: Option<&mut Buffer>,
+) {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || do_prewarm(c, checksums, pin)))
+            .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_prewarm(
+    cache: &mut CosmCache<DB, GoApi, GoQuerier>,
+    checksums: Buffer,
+    pin: bool,
+) -> Result<(), Error> {
+    let checksums = unsafe { checksums.read() }.ok_or_else(|| Error::empty_arg(CODE_ID_ARG))?;
+    let checksums = checksums
+        .chunks(32)
+        .map(|checksum| checksum.try_into())
+        .collect::<Result<Vec<Checksum>, _>>()?;
+    // the thread is detached, it only warms the cache
+    cache.prewarm(&checksums, pin)?;
+    Ok(())
+}
+
+/// The receipt the enclave signed for an instantiate, execute or migrate, written by the FFI
+/// functions of these calls.
+///
+/// `signed` is false when the enclave didn't sign a receipt, in which case the other fields are
+/// left untouched.
+#[repr(C)]
+pub struct ExecutionReceipt {
+    pub signed: bool,
+    pub block_height: u64,
+    pub code_hash: [u8; 32],
+    pub msg_hash: [u8; 32],
+    pub result_hash: [u8; 32],
+    pub gas_used: u64,
+    pub signer_public_key: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+/// Writes the receipt of the call that just returned on this thread, if the enclave signed one
+fn write_execution_receipt(receipt: Option<&mut ExecutionReceipt>) {
+    // Callers that don't care about the receipt may pass null
+    if let (Some(receipt), Some(signed_receipt)) =
+        (receipt, cosmwasm_sgx_vm::take_execution_receipt())
+    {
+        *receipt = ExecutionReceipt {
+            signed: true,
+            block_height: signed_receipt.block_height,
+            code_hash: signed_receipt.code_hash,
+            msg_hash: signed_receipt.msg_hash,
+            result_hash: signed_receipt.result_hash,
+            gas_used: signed_receipt.gas_used,
+            signer_public_key: signed_receipt.signer_public_key,
+            signature: signed_receipt.signature,
+        };
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn instantiate(
     cache: *mut cache_t,
@@ -438,6 +1043,7 @@ pub extern "C" fn instantiate(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    receipt: Option<&mut ExecutionReceipt>,
     err: Option<&mut Buffer>,
     sig_info: Buffer,
     admin: Buffer,
@@ -454,6 +1060,7 @@ pub extern "C" fn instantiate(
                 querier,
                 gas_limit,
                 gas_used,
+                receipt,
                 sig_info,
                 admin,
             )
@@ -476,6 +1083,7 @@ fn do_init(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    receipt: Option<&mut ExecutionReceipt>,
     sig_info: Buffer,
     admin: Buffer,
 ) -> Result<Vec<u8>, Error> {
@@ -490,10 +1098,14 @@ fn do_init(
 
     let deps = to_extern(db, api, querier);
     let mut instance = cache.get_instance(&code_id, deps, gas_limit)?;
+    // A receipt no call took would otherwise be mistaken for the one of this call
+    cosmwasm_sgx_vm::take_execution_receipt();
     // We only check this result after reporting gas usage and returning the instance into the cache.
     let res = call_init_raw(&mut instance, params, msg, sig_info, admin);
     *gas_used = instance.create_gas_report().used_internally;
     instance.recycle();
+    write_execution_receipt(receipt);
+    record_execution(cache, &code_id, params, *gas_used);
     Ok(res?)
 }
 
@@ -508,6 +1120,7 @@ pub extern "C" fn migrate(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    receipt: Option<&mut ExecutionReceipt>,
     err: Option<&mut Buffer>,
     sig_info: Buffer,
     admin: Buffer,
@@ -525,6 +1138,7 @@ pub extern "C" fn migrate(
                 querier,
                 gas_limit,
                 gas_used,
+                receipt,
                 sig_info,
                 admin,
                 admin_proof,
@@ -548,6 +1162,7 @@ fn do_migrate(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    receipt: Option<&mut ExecutionReceipt>,
     sig_info: Buffer,
     admin: Buffer,
     admin_proof: Buffer,
@@ -565,10 +1180,14 @@ fn do_migrate(
 
     let deps = to_extern(db, api, querier);
     let mut instance = cache.get_instance(&code_id, deps, gas_limit)?;
+    // A receipt no call took would otherwise be mistaken for the one of this call
+    cosmwasm_sgx_vm::take_execution_receipt();
     // We only check this result after reporting gas usage and returning the instance into the cache.
     let res = call_migrate_raw(&mut instance, params, msg, sig_info, admin, admin_proof);
     *gas_used = instance.create_gas_report().used_internally;
     instance.recycle();
+    write_execution_receipt(receipt);
+    record_execution(cache, &code_id, params, *gas_used);
     Ok(res?)
 }
 
@@ -661,6 +1280,7 @@ pub extern "C" fn handle(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    receipt: Option<&mut ExecutionReceipt>,
     err: Option<&mut Buffer>,
     sig_info: Buffer,
     handle_type: u8,
@@ -677,6 +1297,7 @@ pub extern "C" fn handle(
                 querier,
                 gas_limit,
                 gas_used,
+                receipt,
                 sig_info,
                 handle_type,
             )
@@ -699,6 +1320,7 @@ fn do_handle(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    receipt: Option<&mut ExecutionReceipt>,
     sig_info: Buffer,
     handle_type: u8,
 ) -> Result<Vec<u8>, Error> {
@@ -712,13 +1334,48 @@ fn do_handle(
 
     let deps = to_extern(db, api, querier);
     let mut instance = cache.get_instance(&code_id, deps, gas_limit)?;
+    // A receipt no call took would otherwise be mistaken for the one of this call
+    cosmwasm_sgx_vm::take_execution_receipt();
     // We only check this result after reporting gas usage and returning the instance into the cache.
     let res = call_handle_raw(&mut instance, params, msg, sig_info, handle_type);
     *gas_used = instance.create_gas_report().used_internally;
     instance.recycle();
+    write_execution_receipt(receipt);
+    record_execution(cache, &code_id, params, *gas_used);
     Ok(res?)
 }
 
+fn record_execution(
+    cache: &CosmCache<DB, GoApi, GoQuerier>,
+    code_id: &Checksum,
+    params: &[u8],
+    gas_used: u64,
+) {
+    // The stats are local to the node, failing to record them mustn't fail the tx
+    if let Err(e) = cache.record_execution(code_id, params, gas_used) {
+        warn!(
+            "Failed to record the execution in the instance stats: {}",
+            e
+        );
+    }
+}
+
+/// The enclave's signature of a query response, written by the FFI function query.
+///
+/// `signed` is false when the node doesn't sign query responses, and `has_contract_mac` when the
+/// contract didn't opt in to MACed responses. The fields are left untouched when both are false.
+#[repr(C)]
+pub struct QueryResponseSignature {
+    pub signed: bool,
+    pub block_height: u64,
+    pub query_hash: [u8; 32],
+    pub result_hash: [u8; 32],
+    pub signer_public_key: [u8; 32],
+    pub signature: [u8; 64],
+    pub has_contract_mac: bool,
+    pub contract_mac: [u8; 32],
+}
+
 #[no_mangle]
 pub extern "C" fn query(
     cache: *mut cache_t,
@@ -730,12 +1387,13 @@ pub extern "C" fn query(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    signature: Option<&mut QueryResponseSignature>,
     err: Option<&mut Buffer>,
 ) -> Buffer {
     let r = match to_cache(cache) {
         Some(c) => catch_unwind(AssertUnwindSafe(move || {
             do_query(
-                c, code_id, params, msg, db, api, querier, gas_limit, gas_used,
+                c, code_id, params, msg, db, api, querier, gas_limit, gas_used, signature,
             )
         }))
         .unwrap_or_else(|_| Err(Error::panic())),
@@ -755,6 +1413,7 @@ fn do_query(
     querier: GoQuerier,
     gas_limit: u64,
     gas_used: Option<&mut u64>,
+    signature: Option<&mut QueryResponseSignature>,
 ) -> Result<Vec<u8>, Error> {
     let gas_used = gas_used.ok_or_else(|| Error::empty_arg(GAS_USED_ARG))?;
     let code_id: Checksum = unsafe { code_id.read() }
@@ -766,16 +1425,31 @@ fn do_query(
     let deps = to_extern(db, api, querier);
     let mut instance = cache.get_instance(&code_id, deps, gas_limit)?;
     // We only check this result after reporting gas usage and returning the instance into the cache.
-    let res = call_query_raw(&mut instance, params, msg);
+    let res = call_query_raw_signed(&mut instance, params, msg);
     *gas_used = instance.create_gas_report().used_internally;
     instance.recycle();
-    Ok(res?)
+    let (output, response_signature) = res?;
+
+    // Callers that don't care about the signature may pass null
+    if let (Some(signature), Some(response_signature)) = (signature, response_signature) {
+        *signature = QueryResponseSignature {
+            signed: response_signature.signed,
+            block_height: response_signature.block_height,
+            query_hash: response_signature.query_hash,
+            result_hash: response_signature.result_hash,
+            signer_public_key: response_signature.signer_public_key,
+            signature: response_signature.signature,
+            has_contract_mac: response_signature.has_contract_mac,
+            contract_mac: response_signature.contract_mac,
+        };
+    }
+    Ok(output)
 }
 
 /// The result type of the FFI function analyze_code.
 ///
-/// Please note that the unmanaged vector in `required_features`
-/// has to be destroyed exactly once. When calling `analyze_code`
+/// Please note that the unmanaged vectors in `required_features`, `ibc_entry_points` and
+/// `missing_ibc_entry_points` have to be destroyed exactly once. When calling `analyze_code`
 /// from Go this is done via `C.destroy_unmanaged_vector`.
 #[repr(C)]
 #[derive(Clone, Default, Debug, PartialEq)]
@@ -784,6 +1458,12 @@ pub struct AnalysisReport {
     /// An UTF-8 encoded comma separated list of required features.
     /// This is never None/nil.
     pub required_features: Buffer,
+    /// An UTF-8 encoded comma separated list of the IBC entry points the contract exports.
+    /// This is never None/nil.
+    pub ibc_entry_points: Buffer,
+    /// An UTF-8 encoded comma separated list of the IBC entry points the contract doesn't export.
+    /// This is never None/nil.
+    pub missing_ibc_entry_points: Buffer,
 }
 
 #[no_mangle]
@@ -821,9 +1501,283 @@ fn do_analyze_code(
     Ok(AnalysisReport {
         has_ibc_entry_points: report.has_ibc_entry_points,
         required_features: Buffer::from_vec(features_vec),
+        ibc_entry_points: Buffer::from_vec(report.ibc_capabilities.present.join(",").into_bytes()),
+        missing_ibc_entry_points: Buffer::from_vec(
+            report.ibc_capabilities.missing.join(",").into_bytes(),
+        ),
     })
 }
 
+/// The result type of the FFI function introspect.
+///
+/// Please note that the unmanaged vectors in `interface_version` and `snip_standards`
+/// have to be destroyed exactly once. When calling `introspect`
+/// from Go this is done via `C.destroy_unmanaged_vector`.
+#[repr(C)]
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct IntrospectionReport {
+    /// "v1" or "v0.10", empty if unknown.
+    /// This is never None/nil.
+    pub interface_version: Buffer,
+    pub has_ibc_entry_points: bool,
+    /// An UTF-8 encoded comma separated list of the SNIP standards the contract declares.
+    /// This is never None/nil.
+    pub snip_standards: Buffer,
+}
+
+#[no_mangle]
+pub extern "C" fn introspect(
+    cache: *mut cache_t,
+    checksum: Buffer,
+    error_msg: Option<&mut Buffer>,
+) -> IntrospectionReport {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || do_introspect(c, checksum)))
+            .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_introspect(
+    cache: &mut CosmCache<DB, GoApi, GoQuerier>,
+    checksum: Buffer,
+) -> Result<IntrospectionReport, Error> {
+    let checksum: Checksum = unsafe { checksum.read() }
+        .ok_or_else(|| Error::empty_arg(CODE_ID_ARG))?
+        .try_into()?;
+    let report = cache.introspect(&checksum)?;
+    let interface_version = report.interface_version.unwrap_or_default();
+
+    Ok(IntrospectionReport {
+        interface_version: Buffer::from_vec(interface_version.as_bytes().to_vec()),
+        has_ibc_entry_points: report.has_ibc_entry_points,
+        snip_standards: Buffer::from_vec(report.snip_standards.join(",").into_bytes()),
+    })
+}
+
+/// The result type of the FFI function analyze_wasm.
+///
+/// Please note that the unmanaged vectors in `interface_version` and `required_features`
+/// have to be destroyed exactly once. When calling `analyze_wasm`
+/// from Go this is done via `C.destroy_unmanaged_vector`.
+#[repr(C)]
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct ContractAnalysis {
+    /// "v1" or "v0.10", empty if unknown.
+    /// This is never None/nil.
+    pub interface_version: Buffer,
+    /// An UTF-8 encoded comma separated list of required features.
+    /// This is never None/nil.
+    pub required_features: Buffer,
+    pub has_ibc_entry_points: bool,
+    pub has_migrate: bool,
+    pub has_reply: bool,
+    pub has_sudo: bool,
+    /// Zero if the contract has no memory
+    pub memory_initial_pages: u64,
+    pub has_memory_maximum: bool,
+    pub memory_maximum_pages: u64,
+}
+
+/// Reads the capabilities of a contract from its Wasm, before it is stored
+#[no_mangle]
+pub extern "C" fn analyze_wasm(wasm: Buffer, error_msg: Option<&mut Buffer>) -> ContractAnalysis {
+    let r = catch_unwind(AssertUnwindSafe(move || do_analyze_wasm(wasm)))
+        .unwrap_or_else(|_| Err(Error::panic()));
+
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_analyze_wasm(wasm: Buffer) -> Result<ContractAnalysis, Error> {
+    let wasm = unsafe { wasm.read() }.ok_or_else(|| Error::empty_arg(WASM_ARG))?;
+    let analysis = cosmwasm_sgx_vm::analyze_wasm(wasm)?;
+
+    let mut required_features: Vec<&str> = analysis
+        .required_features
+        .iter()
+        .map(String::as_str)
+        .collect();
+    required_features.sort_unstable();
+
+    Ok(ContractAnalysis {
+        interface_version: Buffer::from_vec(
+            analysis
+                .interface_version
+                .unwrap_or_default()
+                .as_bytes()
+                .to_vec(),
+        ),
+        required_features: Buffer::from_vec(required_features.join(",").into_bytes()),
+        has_ibc_entry_points: analysis.has_ibc_entry_points,
+        has_migrate: analysis.has_migrate,
+        has_reply: analysis.has_reply,
+        has_sudo: analysis.has_sudo,
+        memory_initial_pages: analysis.memory_initial_pages.unwrap_or_default(),
+        has_memory_maximum: analysis.memory_maximum_pages.is_some(),
+        memory_maximum_pages: analysis.memory_maximum_pages.unwrap_or_default(),
+    })
+}
+
+/// Describes the contracts this node accepts and what it offers them, as JSON
+#[no_mangle]
+pub extern "C" fn vm_capabilities(cache: *mut cache_t, error_msg: Option<&mut Buffer>) -> Buffer {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || do_vm_capabilities(c)))
+            .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_vm_capabilities(cache: &mut CosmCache<DB, GoApi, GoQuerier>) -> Result<Buffer, Error> {
+    let capabilities =
+        serde_json::to_vec(&cache.vm_capabilities()).map_err(|e| Error::vm_err(e.to_string()))?;
+    Ok(Buffer::from_vec(capabilities))
+}
+
+/// Reports, as JSON, the stored contracts that would fail validation with the given supported
+/// features and validation config (JSON, empty for the default), without changing anything
+#[no_mangle]
+pub extern "C" fn revalidate_stored_code(
+    cache: *mut cache_t,
+    supported_features: Buffer,
+    validation_config: Buffer,
+    error_msg: Option<&mut Buffer>,
+) -> Buffer {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || {
+            do_revalidate_stored_code(c, supported_features, validation_config)
+        }))
+        .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_revalidate_stored_code(
+    cache: &mut CosmCache<DB, GoApi, GoQuerier>,
+    supported_features: Buffer,
+    validation_config: Buffer,
+) -> Result<Buffer, Error> {
+    let features_bin =
+        unsafe { supported_features.read() }.ok_or_else(|| Error::empty_arg(FEATURES_ARG))?;
+    let features = features_from_csv(from_utf8(features_bin)?);
+    let validation_config =
+        WasmValidationConfig::from_json(unsafe { validation_config.read() }.unwrap_or_default())?;
+    let report = cache.revalidate_stored_code(&features, &validation_config)?;
+    let report = serde_json::to_vec(&report).map_err(|e| Error::vm_err(e.to_string()))?;
+    Ok(Buffer::from_vec(report))
+}
+
+/// Returns the executions of a contract recorded by this node as JSON, `null` if it never
+/// executed it
+#[no_mangle]
+pub extern "C" fn instance_stats(
+    cache: *mut cache_t,
+    contract_address: Buffer,
+    error_msg: Option<&mut Buffer>,
+) -> Buffer {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || {
+            do_instance_stats(c, contract_address)
+        }))
+        .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+
+    handle_c_error_default(r, error_msg)
+}
+
+fn do_instance_stats(
+    cache: &mut CosmCache<DB, GoApi, GoQuerier>,
+    contract_address: Buffer,
+) -> Result<Buffer, Error> {
+    let contract_address =
+        unsafe { contract_address.read() }.ok_or_else(|| Error::empty_arg(CONTRACT_ADDRESS_ARG))?;
+    let stats = cache.instance_stats(from_utf8(contract_address)?)?;
+    let stats = serde_json::to_vec(&stats).map_err(|e| Error::vm_err(e.to_string()))?;
+    Ok(Buffer::from_vec(stats))
+}
+
+/// Pins the `top_k` most executed code hashes of every epoch of `epoch_length` blocks, see
+/// `AutoPinConfig`. A `top_k` of zero disables auto pinning.
+#[no_mangle]
+pub extern "C" fn configure_auto_pinning(
+    cache: *mut cache_t,
+    top_k: u32,
+    epoch_length: u64,
+    err: Option<&mut Buffer>,
+) {
+    let r = match to_cache(cache) {
+        Some(c) => catch_unwind(AssertUnwindSafe(move || {
+            c.configure_auto_pinning(AutoPinConfig {
+                top_k: top_k as usize,
+                epoch_length,
+            });
+            Ok(())
+        }))
+        .unwrap_or_else(|_| Err(Error::panic())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+
+    if let Err(e) = r {
+        set_error(e, err);
+    } else {
+        clear_error();
+    }
+}
+
+/// Returns what auto pinning did since the node started, as JSON
+#[no_mangle]
+pub extern "C" fn auto_pin_metrics(cache: *mut cache_t, error_msg: Option<&mut Buffer>) -> Buffer {
+    let r = match to_cache(cache) {
+        Some(c) => serde_json::to_vec(&c.auto_pin_metrics())
+            .map(Buffer::from_vec)
+            .map_err(|e| Error::vm_err(e.to_string())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+    handle_c_error_default(r, error_msg)
+}
+
+/// Returns the hits, misses and time spent loading and parsing Wasm of the cache, and the module
+/// cache statistics of the enclave, as JSON
+#[no_mangle]
+pub extern "C" fn cache_metrics(cache: *mut cache_t, error_msg: Option<&mut Buffer>) -> Buffer {
+    let r = match to_cache(cache) {
+        Some(c) => serde_json::to_vec(&c.metrics())
+            .map(Buffer::from_vec)
+            .map_err(|e| Error::vm_err(e.to_string())),
+        None => Err(Error::empty_arg(CACHE_ARG)),
+    };
+    handle_c_error_default(r, error_msg)
+}
+
+/// Returns the traps of contracts per code hash since the node started, as JSON
+#[no_mangle]
+pub extern "C" fn contract_panic_stats(error_msg: Option<&mut Buffer>) -> Buffer {
+    let r = serde_json::to_vec(&cosmwasm_sgx_vm::contract_panic_stats())
+        .map(Buffer::from_vec)
+        .map_err(|e| Error::vm_err(e.to_string()));
+    handle_c_error_default(r, error_msg)
+}
+
+/// Returns the events the enclave encrypted to contract observers since the last call, as json
+/// lines. Empty unless the enclave is built with the `observer-mode` feature.
+#[no_mangle]
+pub extern "C" fn take_observed_events() -> Buffer {
+    let records = cosmwasm_sgx_vm::take_observed_events();
+    let mut lines = Vec::with_capacity(records.iter().map(|record| record.len() + 1).sum());
+    for record in records {
+        lines.extend_from_slice(&record);
+        lines.push(b'\n');
+    }
+    Buffer::from_vec(lines)
+}
+
 #[no_mangle]
 pub extern "C" fn key_gen(err: Option<&mut Buffer>) -> Buffer {
     match untrusted_key_gen() {
@@ -14,6 +14,23 @@ type Env struct {
 	Key         ContractKey      `json:"contract_key"`
 	QueryDepth  uint32           `json:"query_depth"`
 	Transaction *TransactionInfo `json:"transaction,omitempty"`
+	// InstantiateSalt is set when a contract is instantiated at a predictable address
+	InstantiateSalt []byte `json:"instantiate_salt,omitempty"`
+	// Simulation is set during CheckTx and simulations, where the enclave samples with a
+	// placeholder instead of the block random, which depends on the block the tx ends up in
+	Simulation bool `json:"simulation,omitempty"`
+	// MaxResponseDataSize and MaxQueryResponseSize are the response size limits of the compute
+	// params, zero if unlimited
+	MaxResponseDataSize  uint64 `json:"max_response_data_size,omitempty"`
+	MaxQueryResponseSize uint64 `json:"max_query_response_size,omitempty"`
+	// ActiveFeatures are the enclave features governance activated at the height of the block
+	ActiveFeatures []string `json:"active_features,omitempty"`
+	// Observer is the x25519 public key of the observer governance approved for the contract. The
+	// enclave streams the events of the contract to it only if the code of the contract names it too.
+	Observer []byte `json:"observer,omitempty"`
+	// AccessList are the prefixes of the plaintext keys of its store the contract may touch, if the
+	// execution declared an access list. The enclave fails the execution when it touches another key.
+	AccessList [][]byte `json:"access_list,omitempty"`
 }
 
 type ContractKey struct {
@@ -53,6 +70,10 @@ type MessageInfo struct {
 	Sender HumanAddress `json:"sender"`
 	// amount of funds send to the contract along with this message
 	SentFunds Coins `json:"sent_funds"`
+	// address that paid the fees of the transaction, empty for queries
+	FeePayer HumanAddress `json:"fee_payer,omitempty"`
+	// address that granted the fees of the transaction, empty unless a fee grant was used
+	FeeGranter HumanAddress `json:"fee_granter,omitempty"`
 }
 
 type ContractInfo struct {
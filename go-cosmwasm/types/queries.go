@@ -64,15 +64,16 @@ func ToQuerierResult(response []byte, err error) QuerierResult {
 // QueryRequest is an rust enum and only (exactly) one of the fields should be set
 // Should we do a cleaner approach in Go? (type/data?)
 type QueryRequest struct {
-	Bank     *BankQuery      `json:"bank,omitempty"`
-	Custom   json.RawMessage `json:"custom,omitempty"`
-	Staking  *StakingQuery   `json:"staking,omitempty"`
-	Wasm     *WasmQuery      `json:"wasm,omitempty"`
-	Dist     *DistQuery      `json:"dist,omitempty"`
-	Mint     *MintQuery      `json:"mint,omitempty"`
-	Gov      *GovQuery       `json:"gov,omitempty"`
-	IBC      *IBCQuery       `json:"ibc,omitempty"`
-	Stargate *StargateQuery  `json:"stargate,omitempty"`
+	Bank         *BankQuery         `json:"bank,omitempty"`
+	Custom       json.RawMessage    `json:"custom,omitempty"`
+	Staking      *StakingQuery      `json:"staking,omitempty"`
+	Wasm         *WasmQuery         `json:"wasm,omitempty"`
+	Dist         *DistQuery         `json:"dist,omitempty"`
+	Mint         *MintQuery         `json:"mint,omitempty"`
+	Gov          *GovQuery          `json:"gov,omitempty"`
+	IBC          *IBCQuery          `json:"ibc,omitempty"`
+	Stargate     *StargateQuery     `json:"stargate,omitempty"`
+	TokenFactory *TokenFactoryQuery `json:"token_factory,omitempty"`
 }
 
 type BankQuery struct {
@@ -442,6 +443,58 @@ type MintingBondedRatioResponse struct {
 	BondedRatio string `json:"bonded_ratio"`
 }
 
+// TokenFactoryQuery queries denoms created the token factory way, named
+// factory/{creator address}/{subdenom}. These are regular bank denoms, with
+// metadata and a supply tracked by the bank module.
+type TokenFactoryQuery struct {
+	FullDenom     *FullDenomQuery     `json:"full_denom,omitempty"`
+	DenomMetadata *DenomMetadataQuery `json:"denom_metadata,omitempty"`
+	Supply        *DenomSupplyQuery   `json:"supply,omitempty"`
+}
+
+type FullDenomQuery struct {
+	CreatorAddr string `json:"creator_addr"`
+	Subdenom    string `json:"subdenom"`
+}
+
+// FullDenomResponse is the expected response to FullDenomQuery
+type FullDenomResponse struct {
+	Denom string `json:"denom"`
+}
+
+type DenomMetadataQuery struct {
+	Denom string `json:"denom"`
+}
+
+// DenomMetadataResponse is the expected response to DenomMetadataQuery
+type DenomMetadataResponse struct {
+	Metadata *DenomMetadata `json:"metadata"` // serializes to `null` when the denom has no metadata
+}
+
+type DenomMetadata struct {
+	Description string      `json:"description"`
+	DenomUnits  []DenomUnit `json:"denom_units"`
+	Base        string      `json:"base"`
+	Display     string      `json:"display"`
+	Name        string      `json:"name"`
+	Symbol      string      `json:"symbol"`
+}
+
+type DenomUnit struct {
+	Denom    string   `json:"denom"`
+	Exponent uint32   `json:"exponent"`
+	Aliases  []string `json:"aliases"`
+}
+
+type DenomSupplyQuery struct {
+	Denom string `json:"denom"`
+}
+
+// DenomSupplyResponse is the expected response to DenomSupplyQuery
+type DenomSupplyResponse struct {
+	Amount Coin `json:"amount"`
+}
+
 type ProposalsQuery struct{}
 
 // DelegationResponse is the expected response to DelegationsQuery
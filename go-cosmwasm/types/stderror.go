@@ -1,12 +1,17 @@
 package types
 
 import (
+	"encoding/base64"
+	"encoding/json"
 	"fmt"
 	"reflect"
+	"regexp"
+	"strconv"
 )
 
 // StdError captures all errors returned from the Rust code as StdError.
 // Exactly one of the fields should be set.
+// Contract calls return it as is, so callers can read it back with errors.As.
 type StdError struct {
 	GenericErr    *GenericErr    `json:"generic_err,omitempty"`
 	InvalidBase64 *InvalidBase64 `json:"invalid_base64,omitempty"`
@@ -72,12 +77,81 @@ func (a StdError) IsEmpty() bool {
 
 type GenericErr struct {
 	Msg string `json:"msg,omitempty"`
+	// Revert is set if the contract failed with a structured revert reason, which is public
+	Revert *Revert `json:"revert,omitempty"`
+}
+
+// Revert is the machine-readable reason a contract failed with, by returning an error
+// message of the form {"revert":{"code":42,"data":"<base64>"}}. The encoding of Data,
+// e.g. ABI, is up to the contract.
+type Revert struct {
+	Code uint32 `json:"code"`
+	Data []byte `json:"data"`
+}
+
+func (r Revert) String() string {
+	if len(r.Data) == 0 {
+		return strconv.FormatUint(uint64(r.Code), 10)
+	}
+	return fmt.Sprintf("%d %s", r.Code, base64.StdEncoding.EncodeToString(r.Data))
 }
 
 func (e GenericErr) Error() string {
+	if e.Revert != nil {
+		return fmt.Sprintf("encrypted: %s (revert: %s)", e.Msg, e.Revert)
+	}
 	return fmt.Sprintf("encrypted: %s", e.Msg)
 }
 
+var (
+	encryptedErrorRegex = regexp.MustCompile(`encrypted: ([A-Za-z0-9+/=]+)(?: \(revert: (\d+)(?: ([A-Za-z0-9+/=]+))?\))?`)
+	revertRegex         = regexp.MustCompile(` \(revert: \d+(?: [A-Za-z0-9+/=]+)?\)`)
+)
+
+// ParseEncryptedError finds the error of a contract in errString, as formatted by GenericErr.
+// It returns the base64 ciphertext of the error, which only the tx sender can decrypt.
+func ParseEncryptedError(errString string) (ciphertextB64 string, ok bool) {
+	match := encryptedErrorRegex.FindStringSubmatch(errString)
+	if match == nil {
+		return "", false
+	}
+	return match[1], true
+}
+
+// stdErrorCodes are the StdError variants of cosmwasm-std v0.10, serialized as their snake_case
+// names
+var stdErrorCodes = map[string]bool{
+	"generic_err":    true,
+	"invalid_base64": true,
+	"invalid_utf8":   true,
+	"not_found":      true,
+	"parse_err":      true,
+	"serialize_err":  true,
+	"unauthorized":   true,
+	"underflow":      true,
+}
+
+// ErrorCode returns the kind of a decrypted contract error, e.g. "not_found". The kind is part of
+// the ciphertext, so only the tx sender learns it. Errors that aren't a StdError, like the errors
+// of v1 contracts, which are strings, are "unknown".
+func ErrorCode(decryptedErr []byte) string {
+	var variants map[string]json.RawMessage
+	if err := json.Unmarshal(decryptedErr, &variants); err != nil || len(variants) != 1 {
+		return "unknown"
+	}
+	for kind := range variants {
+		if stdErrorCodes[kind] {
+			return kind
+		}
+	}
+	return "unknown"
+}
+
+// StripRevert removes the public revert reason GenericErr adds after the ciphertext of an error
+func StripRevert(errString string) string {
+	return revertRegex.ReplaceAllString(errString, "")
+}
+
 type InvalidBase64 struct {
 	Msg string `json:"msg,omitempty"`
 }
@@ -0,0 +1,42 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/require"
+)
+
+func TestParseEncryptedError(t *testing.T) {
+	errString := GenericErr{Msg: "c2VjcmV0+/w=="}.Error() + ": execute contract failed"
+	require.Equal(t, "encrypted: c2VjcmV0+/w==: execute contract failed", errString)
+
+	ciphertext, ok := ParseEncryptedError(errString)
+	require.True(t, ok)
+	require.Equal(t, "c2VjcmV0+/w==", ciphertext)
+
+	_, ok = ParseEncryptedError("out of gas")
+	require.False(t, ok)
+}
+
+func TestErrorCode(t *testing.T) {
+	require.Equal(t, "not_found", ErrorCode([]byte(`{"not_found":{"kind":"balance"}}`)))
+	require.Equal(t, "generic_err", ErrorCode([]byte(`{"generic_err":{"msg":"insufficient funds"}}`)))
+	require.Equal(t, "unknown", ErrorCode([]byte(`{"custom_err":{"msg":"x"}}`)))
+	require.Equal(t, "unknown", ErrorCode([]byte(`{"not_found":{},"unauthorized":{}}`)))
+	// the errors of v1 contracts are strings
+	require.Equal(t, "unknown", ErrorCode([]byte(`"insufficient funds"`)))
+}
+
+func TestRevertError(t *testing.T) {
+	withRevert := GenericErr{Msg: "c2VjcmV0", Revert: &Revert{Code: 42, Data: []byte{0, 1, 2}}}
+	errString := withRevert.Error() + ": execute contract failed"
+	require.Equal(t, "encrypted: c2VjcmV0 (revert: 42 AAEC): execute contract failed", errString)
+
+	ciphertext, ok := ParseEncryptedError(errString)
+	require.True(t, ok)
+	require.Equal(t, "c2VjcmV0", ciphertext)
+	require.Equal(t, "encrypted: c2VjcmV0: execute contract failed", StripRevert(errString))
+
+	withoutData := GenericErr{Msg: "c2VjcmV0", Revert: &Revert{Code: 7}}
+	require.Equal(t, "encrypted: c2VjcmV0 (revert: 7)", withoutData.Error())
+}
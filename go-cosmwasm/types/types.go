@@ -1,6 +1,7 @@
 package types
 
 import (
+	"crypto/sha256"
 	"encoding/json"
 	"strconv"
 )
@@ -11,6 +12,47 @@ type HumanAddress = string
 // CanonicalAddress uses standard base64 encoding, just use it as a label for developers
 type CanonicalAddress = []byte
 
+// ValidationConfig adjusts the validation of contracts when they are stored. Unset fields keep the
+// defaults of Secret Network, so chains forking it only set the limits they change.
+// All the nodes of a chain must use the same config.
+type ValidationConfig struct {
+	// MemoryLimit is the largest initial memory of a contract, in pages
+	MemoryLimit uint32 `json:"memory_limit,omitempty"`
+	// AllowMemoryMaximum accepts contracts declaring a memory maximum of at most MemoryLimit pages
+	AllowMemoryMaximum   bool     `json:"allow_memory_maximum,omitempty"`
+	SupportedImportsV010 []string `json:"supported_imports_v010,omitempty"`
+	SupportedImportsV1   []string `json:"supported_imports_v1,omitempty"`
+	RequiredExportsV010  []string `json:"required_exports_v010,omitempty"`
+	RequiredExportsV1    []string `json:"required_exports_v1,omitempty"`
+	// ExtraSupportedImports are host functions the enclave of the chain adds, accepted from contracts
+	// of both interface versions
+	ExtraSupportedImports []string `json:"extra_supported_imports,omitempty"`
+	// FloatInstructions is "flag" (the default) to only log contracts using floating point
+	// instructions, or "reject" to refuse storing them
+	FloatInstructions string `json:"float_instructions,omitempty"`
+	// MaxFunctions, MaxFunctionBodySize (in bytes) and MaxTableSize (in elements) are unlimited if nil
+	MaxFunctions        *uint64 `json:"max_functions,omitempty"`
+	MaxFunctionBodySize *uint64 `json:"max_function_body_size,omitempty"`
+	MaxTableSize        *uint32 `json:"max_table_size,omitempty"`
+	// MaxCodeSize (in bytes, checked before deserializing), MaxDataSegments and MaxDataSize (the
+	// total size of the data segments, in bytes) are unlimited if nil
+	MaxCodeSize     *uint64 `json:"max_code_size,omitempty"`
+	MaxDataSegments *uint64 `json:"max_data_segments,omitempty"`
+	MaxDataSize     *uint64 `json:"max_data_size,omitempty"`
+	// StrictExports logs a warning for contracts exporting functions the enclave never calls, which
+	// are usually misspelled entry points
+	StrictExports bool `json:"strict_exports,omitempty"`
+}
+
+// UpgradeSchedule holds the height at which each behavior of the VM that changed in a chain upgrade
+// activated, so the blocks before it are replayed with the behavior they ran with. Behaviors without
+// a height are active from genesis.
+type UpgradeSchedule struct {
+	// CurrentEnclave is the first height executed by the current enclave, the blocks before it are
+	// executed by the legacy enclave if one is loaded
+	CurrentEnclave *uint64 `json:"current_enclave,omitempty"`
+}
+
 // Coin is a string representation of the sdk.Coin type (more portable than sdk.Int)
 type Coin struct {
 	Denom  string `json:"denom"`  // type, eg. "ATOM"
@@ -58,6 +100,21 @@ func (o OutOfGasError) Error() string {
 	return "Out of gas"
 }
 
+// ValidationError is the error of a contract rejected by static validation when it is stored
+type ValidationError struct {
+	Msg string `json:"msg"`
+	// Code identifies the kind of failure, e.g. "missing_export". Codes are stable, unlike Msg.
+	Code string `json:"code"`
+	// Failure holds the code and the details of the failure, which depend on it
+	Failure json.RawMessage `json:"failure"`
+}
+
+var _ error = ValidationError{}
+
+func (e ValidationError) Error() string {
+	return e.Msg
+}
+
 type SigInfo struct {
 	TxBytes           []byte `json:"tx_bytes"`
 	SignBytes         []byte `json:"sign_bytes"`
@@ -68,6 +125,195 @@ type SigInfo struct {
 	CallbackSignature []byte `json:"callback_sig"` // Optional
 }
 
+// ExecutionReceipt is signed by the enclave for a contract execution, when the node enabled it.
+// The signature is an ed25519 signature by SignerPublicKey over sha256(sha256(chain-id) ||
+// big endian u64 BlockHeight || CodeHash || MsgHash || ResultHash || big endian u64 GasUsed ||
+// SignerPublicKey), where the chain-id and BlockHeight are those of the last block the enclave
+// verified.
+type ExecutionReceipt struct {
+	BlockHeight     uint64 `json:"block_height"`
+	CodeHash        []byte `json:"code_hash"`
+	MsgHash         []byte `json:"msg_hash"`    // sha256 of the message as received by the enclave
+	ResultHash      []byte `json:"result_hash"` // sha256 of the encrypted output
+	GasUsed         uint64 `json:"gas_used"`
+	SignerPublicKey []byte `json:"signer_public_key"`
+	Signature       []byte `json:"signature"`
+}
+
+// VmCapabilities describes the contracts a node accepts and what it offers them
+type VmCapabilities struct {
+	// InterfaceVersions are the CosmWasm versions of the contracts the node runs, "v0.10" and "v1"
+	InterfaceVersions []string `json:"interface_versions"`
+	// HostFunctionsV010 and HostFunctionsV1 are the imports contracts of each version may use, as
+	// "module.name"
+	HostFunctionsV010 []string `json:"host_functions_v010"`
+	HostFunctionsV1   []string `json:"host_functions_v1"`
+	Features          []string `json:"features"`
+	Limits            VmLimits `json:"limits"`
+	// GasScheduleVersion changes whenever the gas the enclave charges for instructions and host
+	// functions does
+	GasScheduleVersion uint32 `json:"gas_schedule_version"`
+	// MrEnclave is hex encoded, and empty if the enclave can't report it, e.g. in software mode
+	MrEnclave string `json:"mr_enclave,omitempty"`
+	// DisclosureSigner is the hex encoded ed25519 public key the enclaves of the network sign
+	// disclosure bundles with, empty before the node has the consensus seed
+	DisclosureSigner string `json:"disclosure_signer,omitempty"`
+}
+
+// VmLimits are the limits contracts must stay within to be stored, nil if unlimited
+type VmLimits struct {
+	// MemoryLimit is in pages
+	MemoryLimit uint32 `json:"memory_limit"`
+	// MaxFunctionBodySize, MaxCodeSize and MaxDataSize are in bytes, MaxTableSize in elements
+	MaxFunctions        *uint64 `json:"max_functions,omitempty"`
+	MaxFunctionBodySize *uint64 `json:"max_function_body_size,omitempty"`
+	MaxTableSize        *uint32 `json:"max_table_size,omitempty"`
+	MaxCodeSize         *uint64 `json:"max_code_size,omitempty"`
+	MaxDataSegments     *uint64 `json:"max_data_segments,omitempty"`
+	MaxDataSize         *uint64 `json:"max_data_size,omitempty"`
+}
+
+// RevalidationReport lists the stored contracts that would fail validation under other supported
+// features or another validation config
+type RevalidationReport struct {
+	// Checked is the number of stored contracts checked
+	Checked  uint64                `json:"checked"`
+	Failures []RevalidationFailure `json:"failures"`
+}
+
+type RevalidationFailure struct {
+	// Checksum is the hex encoded code hash
+	Checksum string `json:"checksum"`
+	Error    string `json:"error"`
+}
+
+// ContractPanicStats are the traps of the contracts of a code hash since the node started
+type ContractPanicStats struct {
+	// CodeHash is hex encoded
+	CodeHash string `json:"code_hash"`
+	Count    uint64 `json:"count"`
+	// LastMessage is a recent trap message, truncated and without control characters
+	LastMessage string `json:"last_message"`
+}
+
+// InstanceStats are the executions of a contract recorded by the VM of this node: its init, the
+// executions and the migrations. Simulations are not counted.
+type InstanceStats struct {
+	Executions         uint64 `json:"executions"`
+	LastExecutedHeight uint64 `json:"last_executed_height"`
+	CumulativeGas      uint64 `json:"cumulative_gas"`
+}
+
+// AutoPinMetrics is what auto pinning did since the node started
+type AutoPinMetrics struct {
+	// Epoch is the epoch executions are currently counted in
+	Epoch uint64 `json:"epoch"`
+	// Pinned is the number of code hashes auto pinning pinned now
+	Pinned uint64 `json:"pinned"`
+	Pins   uint64 `json:"pins"`
+	Unpins uint64 `json:"unpins"`
+}
+
+// Metrics is how the cache of the VM performed since the node started
+type Metrics struct {
+	// HitsPinned counts the instances built from pinned Wasm, kept in memory
+	HitsPinned uint64 `json:"hits_pinned"`
+	// Misses counts the instances of code that isn't pinned, including those that failed to load
+	Misses uint64 `json:"misses"`
+	// ElementsPinned is the number of pinned code hashes
+	ElementsPinned uint64 `json:"elements_pinned"`
+	// UnsealTimeNs is the time spent loading stored Wasm and checking its integrity, in nanoseconds
+	UnsealTimeNs uint64 `json:"unseal_time_ns"`
+	// ParseTimeNs is the time spent validating and parsing Wasm outside of the enclave, in nanoseconds
+	ParseTimeNs uint64 `json:"parse_time_ns"`
+	// EnclaveModuleCache is how the module cache of the enclave performed
+	EnclaveModuleCache EnclaveModuleCacheStats `json:"enclave_module_cache"`
+}
+
+// EnclaveModuleCacheStats is how the module cache of the enclave performed since the node started
+type EnclaveModuleCacheStats struct {
+	// Capacity is the most modules the cache keeps
+	Capacity uint64 `json:"capacity"`
+	// Size is the number of modules in the cache
+	Size uint64 `json:"size"`
+	// Pinned is the number of pinned code hashes, whose modules are kept outside of the cache
+	Pinned uint64 `json:"pinned"`
+	// Hits counts the modules found in the cache
+	Hits uint64 `json:"hits"`
+	// PinnedHits counts the modules found pinned
+	PinnedHits uint64 `json:"pinned_hits"`
+	// Misses counts the modules built for a call
+	Misses uint64 `json:"misses"`
+	// Evictions counts the modules dropped from the cache to make room for others
+	Evictions uint64 `json:"evictions"`
+}
+
+// ContractVersion is the name and version a contract stores under the cw2 `contract_info` key.
+// The enclave returns it after instantiate and migrate, as the node can't read contract state.
+type ContractVersion struct {
+	Contract string `json:"contract"`
+	Version  string `json:"version"`
+}
+
+// QueryResponseSignature binds a query to the chain and block height it ran at and to its result.
+// It is signed by the enclave of the node that served the query, with the same key as
+// ExecutionReceipt. The signature is an ed25519 signature by SignerPublicKey over
+// sha256(sha256(chain-id) || big endian u64 BlockHeight || QueryHash || ResultHash ||
+// SignerPublicKey), where the chain-id and BlockHeight are those of the last block the enclave
+// verified.
+//
+// Contracts opting in to MACed query responses also get ContractMAC, an HMAC-SHA256 over
+// sha256(chain-id) || big endian u64 BlockHeight || QueryHash || ResultHash only the querier can
+// check. SignerPublicKey and Signature are empty when the node doesn't sign query responses.
+type QueryResponseSignature struct {
+	BlockHeight uint64 `json:"block_height"`
+	// sha256 of the canonical address of the contract followed by the encrypted query
+	QueryHash  []byte `json:"query_hash"`
+	ResultHash []byte `json:"result_hash"` // sha256 of SignedResult
+	// SignedResult is the raw output of the enclave, which contains the encrypted response
+	SignedResult    []byte `json:"signed_result"`
+	SignerPublicKey []byte `json:"signer_public_key"`
+	Signature       []byte `json:"signature"`
+	ContractMAC     []byte `json:"contract_mac,omitempty"`
+}
+
+// ContractKeyProofVersion is the version of the ContractKeyProof format
+const ContractKeyProofVersion uint8 = 1
+
+// ContractKeyProof is signed by the enclave of a node to prove that ContractKey and
+// EncryptionPublicKey were derived by a genuine enclave for the contract at ContractAddress running
+// the code with CodeHash. The attestation certificate of NodePublicKey, which the registration
+// module holds, commits to SignerPublicKey in its report data.
+type ContractKeyProof struct {
+	Version         uint8            `json:"version"`
+	ContractAddress CanonicalAddress `json:"contract_address"`
+	CodeHash        []byte           `json:"code_hash"`
+	// ContractKey is the current contract key, which differs from the original one once the
+	// contract was migrated
+	ContractKey []byte `json:"contract_key"`
+	// EncryptionPublicKey is the x25519 key other contracts encrypt data to the contract with. It
+	// changes when the consensus seed is rotated.
+	EncryptionPublicKey []byte `json:"encryption_public_key"`
+	NodePublicKey       []byte `json:"node_public_key"`
+	SignerPublicKey     []byte `json:"signer_public_key"`
+	Signature           []byte `json:"signature"`
+}
+
+// SigningBytes returns the bytes covered by the ed25519 signature of the proof:
+// sha256(Version || CodeHash || ContractKey || EncryptionPublicKey || NodePublicKey ||
+// SignerPublicKey || ContractAddress)
+func (p ContractKeyProof) SigningBytes() []byte {
+	data := []byte{p.Version}
+	data = append(data, p.CodeHash...)
+	data = append(data, p.ContractKey...)
+	data = append(data, p.EncryptionPublicKey...)
+	data = append(data, p.NodePublicKey...)
+	data = append(data, p.SignerPublicKey...)
+	data = append(data, p.ContractAddress...)
+	hash := sha256.Sum256(data)
+	return hash[:]
+}
+
 type HandleType int
 
 const (
@@ -90,3 +336,14 @@ const (
 	CosmosMsgVersionV010 CosmosMsgVersion = iota
 	CosmosMsgVersionV1
 )
+
+// MsgDecryptionStatus is what the enclave did with a message passed to DecryptTxMsgs
+type MsgDecryptionStatus byte
+
+const (
+	MsgDecrypted MsgDecryptionStatus = iota
+	// MsgMalformed means the message is too short to be an encrypted message
+	MsgMalformed
+	// MsgDecryptionFailed means the message can't be decrypted with the key of its nonce and public key
+	MsgDecryptionFailed
+)
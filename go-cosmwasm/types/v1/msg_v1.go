@@ -81,6 +81,7 @@ type CosmosMsg struct {
 	Stargate     *StargateMsg     `json:"stargate,omitempty"`
 	Wasm         *WasmMsg         `json:"wasm,omitempty"`
 	FinalizeTx   *Empty           `json:"finalize_tx,omitempty"`
+	Secret       *SecretMsg       `json:"secret,omitempty"`
 }
 
 type BankMsg struct {
@@ -222,3 +223,28 @@ type WasmMsg struct {
 	UpdateAdmin *v010msgtypes.UpdateAdminMsg `json:"update_admin,omitempty"`
 	ClearAdmin  *v010msgtypes.ClearAdminMsg  `json:"clear_admin,omitempty"`
 }
+
+// SecretMsg is an operation of the chain's own privacy features, as opposed to those of contracts.
+// Variants can be added before the chain supports them; the default encoder rejects all of them.
+type SecretMsg struct {
+	Shield       *ShieldMsg       `json:"shield,omitempty"`
+	Unshield     *UnshieldMsg     `json:"unshield,omitempty"`
+	ShieldedSend *ShieldedSendMsg `json:"shielded_send,omitempty"`
+}
+
+// ShieldMsg moves coins from the contract's bank balance to its shielded balance.
+type ShieldMsg struct {
+	Amount types.Coins `json:"amount"`
+}
+
+// UnshieldMsg moves coins from the contract's shielded balance back to its bank balance.
+type UnshieldMsg struct {
+	Amount types.Coins `json:"amount"`
+}
+
+// ShieldedSendMsg sends coins from the contract's shielded balance to the shielded balance of
+// ToAddress, without revealing the amount on chain.
+type ShieldedSendMsg struct {
+	ToAddress string      `json:"to_address"`
+	Amount    types.Coins `json:"amount"`
+}
@@ -5,4 +5,34 @@ package v1types
 type AnalysisReport struct {
 	HasIBCEntryPoints bool
 	RequiredFeatures  string
+	// Comma separated lists of the IBC entry points the contract exports and doesn't export.
+	// HasIBCEntryPoints is set if it exports all of them.
+	IBCEntryPoints        string
+	MissingIBCEntryPoints string
+}
+
+// Public information about a contract, read without running it.
+// This type is returned by VM.Introspect().
+type IntrospectionReport struct {
+	// "v1" or "v0.10", empty if unknown
+	InterfaceVersion  string
+	HasIBCEntryPoints bool
+	// Comma separated list of the SNIP standards the contract declares. These are not verified.
+	SnipStandards string
+}
+
+// The capabilities of a contract, read from its Wasm before it is stored.
+// This type is returned by VM.AnalyzeWasm().
+type ContractAnalysis struct {
+	// "v1" or "v0.10", empty if unknown
+	InterfaceVersion  string
+	RequiredFeatures  string
+	HasIBCEntryPoints bool
+	HasMigrate        bool
+	HasReply          bool
+	HasSudo           bool
+	// Zero if the contract has no memory
+	MemoryInitialPages uint64
+	// Nil if the contract doesn't declare a maximum
+	MemoryMaximumPages *uint64
 }
@@ -48,11 +48,16 @@ var (
 	NewEnv                    = types.NewEnv
 	NewWasmCoins              = types.NewWasmCoins
 	DefaultWasmConfig         = types.DefaultWasmConfig
+	DefaultParams             = types.DefaultParams
+	ParamKeyTable             = types.ParamKeyTable
 	IsEncryptedError          = types.IsEncryptedErrorCode
 	ErrContainsQueryError     = types.ErrContainsQueryError
 	GetConfig                 = types.GetConfig
 	InitGenesis               = keeper.InitGenesis
 	ExportGenesis             = keeper.ExportGenesis
+	ExportState               = keeper.ExportState
+	ImportState               = keeper.ImportState
+	NewStateExport            = types.NewStateExport
 	NewMessageHandler         = keeper.NewMessageHandler
 	DefaultEncoders           = keeper.DefaultEncoders
 	EncodeBankMsg             = keeper.EncodeBankMsg
@@ -76,7 +81,11 @@ var (
 	NewWasmSnapshotter        = keeper.NewWasmSnapshotter
 	ContractFromPortID        = keeper.ContractFromPortID
 	NewCountTXDecorator       = keeper.NewCountTXDecorator
+	NewFeeInfoDecorator       = keeper.NewFeeInfoDecorator
+	NewDecryptTxMsgsDecorator = keeper.NewDecryptTxMsgsDecorator
 	NewMsgServerImpl          = keeper.NewMsgServerImpl
+	NewProposalHandler        = keeper.NewProposalHandler
+	NewRemoveCodeProposal     = types.NewRemoveCodeProposal
 
 	// variable aliases
 	ModuleCdc            = types.ModuleCdc
@@ -101,6 +110,8 @@ var (
 type (
 	// ProposalType            = types.ProposalType
 	GenesisState               = types.GenesisState
+	Params                     = types.Params
+	StateExport                = types.StateExport
 	Code                       = types.Code
 	Contract                   = types.Contract
 	MsgStoreCode               = types.MsgStoreCode
@@ -110,6 +121,7 @@ type (
 	MsgMigrateContract         = types.MsgMigrateContract
 	MsgUpdateAdmin             = types.MsgUpdateAdmin
 	MsgClearAdmin              = types.MsgClearAdmin
+	RemoveCodeProposal         = types.RemoveCodeProposal
 	Model                      = types.Model
 	CodeInfo                   = types.CodeInfo
 	ContractInfo               = types.ContractInfo
@@ -1,8 +1,26 @@
 package cli
 
-import "fmt"
+import (
+	"bytes"
+	"fmt"
+
+	wasmUtils "github.com/scrtlabs/SecretNetwork/x/compute/client/utils"
+)
 
 func parseEncryptedBlob(blob []byte) ([]byte, []byte, []byte, error) {
+	// v2 envelopes start with a header naming their cipher suite. The only one so far is the
+	// suite of v1 envelopes, which have no header.
+	if bytes.HasPrefix(blob, wasmUtils.EnvelopeV2Magic) {
+		if len(blob) < wasmUtils.EnvelopeV2HeaderLength {
+			return nil, nil, nil, fmt.Errorf("input has a truncated envelope header")
+		}
+		keyAgreement, aead := blob[len(wasmUtils.EnvelopeV2Magic)], blob[len(wasmUtils.EnvelopeV2Magic)+1]
+		if keyAgreement != wasmUtils.KeyAgreementX25519 || aead != wasmUtils.AeadAesSiv {
+			return nil, nil, nil, fmt.Errorf("unsupported cipher suite: key agreement %d aead %d", keyAgreement, aead)
+		}
+		blob = blob[wasmUtils.EnvelopeV2HeaderLength:]
+	}
+
 	if len(blob) < 64 {
 		return nil, nil, nil, fmt.Errorf("input must be > 64 bytes. Got %d", len(blob))
 	}
@@ -26,6 +26,7 @@ import (
 	sdk "github.com/cosmos/cosmos-sdk/types"
 
 	wasmUtils "github.com/scrtlabs/SecretNetwork/x/compute/client/utils"
+	regtypes "github.com/scrtlabs/SecretNetwork/x/registration"
 
 	"github.com/scrtlabs/SecretNetwork/x/compute/internal/keeper"
 	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
@@ -52,6 +53,11 @@ func GetQueryCmd() *cobra.Command {
 		GetCmdCodeHashByCodeID(),
 		CmdDecryptText(),
 		GetCmdGetContractHistory(),
+		GetCmdContractKeyProof(),
+		GetCmdContractStorageStats(),
+		GetCmdContractVersion(),
+		GetCmdContractInstanceStats(),
+		GetCmdEnclaveModuleCache(),
 	)
 	return queryCmd
 }
@@ -152,6 +158,160 @@ func GetCmdCodeHashByContractAddress() *cobra.Command {
 	return cmd
 }
 
+// GetCmdContractStorageStats returns the number and size of the entries a contract keeps in its store
+func GetCmdContractStorageStats() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "contract-storage-stats [address]",
+		Short: "Return the number and size of the entries in the store of a contract",
+		Long:  "Return the number of keys of a contract, and the total size of its keys and of its encrypted values",
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientQueryContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			route := fmt.Sprintf("custom/%s/%s/%s", types.QuerierRoute, keeper.QueryContractStorageStats, args[0])
+			res, _, err := clientCtx.Query(route)
+			if err != nil {
+				return fmt.Errorf("error querying contract storage stats: %s", err)
+			}
+
+			return clientCtx.PrintBytes(res)
+		},
+	}
+
+	flags.AddQueryFlagsToCmd(cmd)
+	return cmd
+}
+
+// GetCmdContractVersion returns the cw2 name and version of a contract
+func GetCmdContractVersion() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "contract-version [address]",
+		Short: "Return the cw2 name and version of a contract",
+		Long:  "Return the name and version a contract stores under the cw2 contract_info key, as of its last instantiate or migrate. Prints nothing for contracts not following the convention.",
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientQueryContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			route := fmt.Sprintf("custom/%s/%s/%s", types.QuerierRoute, keeper.QueryContractVersion, args[0])
+			res, _, err := clientCtx.Query(route)
+			if err != nil {
+				return fmt.Errorf("error querying contract version: %s", err)
+			}
+
+			return clientCtx.PrintBytes(res)
+		},
+	}
+
+	flags.AddQueryFlagsToCmd(cmd)
+	return cmd
+}
+
+// GetCmdContractInstanceStats returns how many times the queried node executed a contract
+func GetCmdContractInstanceStats() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "contract-instance-stats [address]",
+		Short: "Return how many times the node executed a contract",
+		Long:  "Return the number of executions of a contract recorded by the queried node, the height of the last one and the gas they used in total. These are local to the node and not part of consensus. Prints nothing if the node never executed the contract.",
+		Args:  cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientQueryContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			route := fmt.Sprintf("custom/%s/%s/%s", types.QuerierRoute, keeper.QueryContractInstanceStats, args[0])
+			res, _, err := clientCtx.Query(route)
+			if err != nil {
+				return fmt.Errorf("error querying contract instance stats: %s", err)
+			}
+
+			return clientCtx.PrintBytes(res)
+		},
+	}
+
+	flags.AddQueryFlagsToCmd(cmd)
+	return cmd
+}
+
+// GetCmdEnclaveModuleCache returns how the module cache of the enclave of the queried node performed
+func GetCmdEnclaveModuleCache() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "enclave-module-cache",
+		Short: "Return the module cache statistics of the enclave of the node",
+		Long:  "Return the capacity and size of the module cache of the enclave of the queried node, and its hits, misses and evictions since the node started. These are local to the node and not part of consensus.",
+		Args:  cobra.NoArgs,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientQueryContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			route := fmt.Sprintf("custom/%s/%s", types.QuerierRoute, keeper.QueryEnclaveModuleCache)
+			res, _, err := clientCtx.Query(route)
+			if err != nil {
+				return fmt.Errorf("error querying enclave module cache stats: %s", err)
+			}
+
+			return clientCtx.PrintBytes(res)
+		},
+	}
+
+	flags.AddQueryFlagsToCmd(cmd)
+	return cmd
+}
+
+// GetCmdContractKeyProof fetches a proof of the contract key of a contract and verifies it against
+// the attestation certificate of the node that signed it
+func GetCmdContractKeyProof() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "contract-key-proof [address]",
+		Short: "Return a verified proof of the contract key of a contract",
+		Long: "Return a proof, signed by the enclave of the queried node, of the contract key and encryption public key of a contract. " +
+			"The proof is verified against the attestation certificate the node registered with before it is printed",
+		Args: cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientQueryContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			route := fmt.Sprintf("custom/%s/%s/%s", types.QuerierRoute, keeper.QueryContractKeyProof, args[0])
+			res, _, err := clientCtx.Query(route)
+			if err != nil {
+				return fmt.Errorf("error querying contract key proof: %s", err)
+			}
+
+			var proof cosmwasmTypes.ContractKeyProof
+			err = json.Unmarshal(res, &proof)
+			if err != nil {
+				return fmt.Errorf("error unmarshalling contract key proof: %s", err)
+			}
+
+			route = fmt.Sprintf("custom/%s/%s/%s", regtypes.QuerierRoute, regtypes.QueryNodeCertificate, hex.EncodeToString(proof.NodePublicKey))
+			cert, _, err := clientCtx.Query(route)
+			if err != nil {
+				return fmt.Errorf("error querying node certificate: %s", err)
+			}
+
+			err = wasmUtils.VerifyContractKeyProof(&proof, cert)
+			if err != nil {
+				return fmt.Errorf("contract key proof did not verify: %s", err)
+			}
+
+			return clientCtx.PrintBytes(res)
+		},
+	}
+
+	flags.AddQueryFlagsToCmd(cmd)
+	return cmd
+}
+
 // GetCmdCodeHashByID return the code hash of a contract by ID
 func GetCmdCodeHashByCodeID() *cobra.Command {
 	cmd := &cobra.Command{
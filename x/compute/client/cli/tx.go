@@ -33,6 +33,10 @@ const (
 	flagIoMasterKey            = "enclave-key"
 	flagCodeHash               = "code-hash"
 	flagAdmin                  = "admin"
+	flagAccessList             = "access-list"
+	flagDeveloperPubKey        = "developer-pubkey"
+	flagDeveloperSignature     = "developer-signature"
+	flagSalt                   = "salt"
 )
 
 // GetTxCmd returns the transaction commands for this module
@@ -47,6 +51,7 @@ func GetTxCmd() *cobra.Command {
 	}
 	txCmd.AddCommand(
 		StoreCodeCmd(),
+		SignCodeCmd(),
 		InstantiateContractCmd(),
 		ExecuteContractCmd(),
 		MigrateContractCmd(),
@@ -84,11 +89,61 @@ func StoreCodeCmd() *cobra.Command {
 	cmd.Flags().String(flagBuilder, "", "A valid docker tag for the build system, optional")
 	cmd.Flags().String(flagInstantiateByEverybody, "", "Everybody can instantiate a contract from the code, optional")
 	cmd.Flags().String(flagInstantiateByAddress, "", "Only this address can instantiate a contract instance from the code, optional")
+	cmd.Flags().String(flagDeveloperPubKey, "", "Hex encoded public key of the developer who signed the code, see sign-code, optional")
+	cmd.Flags().String(flagDeveloperSignature, "", "Hex encoded signature of the developer over the code, see sign-code, optional")
 	flags.AddTxFlagsToCmd(cmd)
 
 	return cmd
 }
 
+// SignCodeCmd signs a WASM binary with a key of the keyring, for the code to be uploaded with the signature of its
+// developer by anyone.
+func SignCodeCmd() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "sign-code [wasm file] --from [key]",
+		Short: "Sign a WASM binary as its developer",
+		Long: `Sign a WASM binary as its developer. The signature and public key are printed, pass them to store
+with --developer-signature and --developer-pubkey to record in the code info that the developer signed the code.
+Sign the exact uncompressed binary that is stored: it is the binary the code hash is computed over, whatever the
+node strips from it when it runs it. Storing signed code is possible once governance sets developer_signature_height.`,
+		Args: cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientTxContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			wasm, err := os.ReadFile(args[0])
+			if err != nil {
+				return err
+			}
+			if !wasmUtils.IsWasm(wasm) {
+				return fmt.Errorf("invalid input file. Use an uncompressed wasm binary")
+			}
+
+			signature, pubKey, err := clientCtx.Keyring.Sign(clientCtx.GetFromName(), wasm)
+			if err != nil {
+				return err
+			}
+
+			out, err := json.MarshalIndent(map[string]string{
+				"developer_pub_key":   hex.EncodeToString(pubKey.Bytes()),
+				"developer_signature": hex.EncodeToString(signature),
+			}, "", "  ")
+			if err != nil {
+				return err
+			}
+			return clientCtx.PrintString(string(out) + "\n")
+		},
+	}
+
+	cmd.Flags().String(flags.FlagFrom, "", "Name or address of the key to sign with")
+	cmd.Flags().String(flags.FlagKeyringBackend, flags.DefaultKeyringBackend, "Select keyring's backend (os|file|kwallet|pass|test|memory)")
+	cmd.Flags().String(flags.FlagKeyringDir, "", "The client Keyring directory; if omitted, the default 'home' directory will be used")
+
+	return cmd
+}
+
 func parseStoreCodeArgs(args []string, cliCtx client.Context, flags *flag.FlagSet) (types.MsgStoreCode, error) {
 	wasm, err := os.ReadFile(args[0])
 	if err != nil {
@@ -114,13 +169,31 @@ func parseStoreCodeArgs(args []string, cliCtx client.Context, flags *flag.FlagSe
 	if err != nil {
 		return types.MsgStoreCode{}, fmt.Errorf("builder: %s", err)
 	}
+	developerPubKeyStr, err := flags.GetString(flagDeveloperPubKey)
+	if err != nil {
+		return types.MsgStoreCode{}, fmt.Errorf("developer pubkey: %s", err)
+	}
+	developerPubKey, err := hex.DecodeString(developerPubKeyStr)
+	if err != nil {
+		return types.MsgStoreCode{}, fmt.Errorf("developer pubkey: %s", err)
+	}
+	developerSignatureStr, err := flags.GetString(flagDeveloperSignature)
+	if err != nil {
+		return types.MsgStoreCode{}, fmt.Errorf("developer signature: %s", err)
+	}
+	developerSignature, err := hex.DecodeString(developerSignatureStr)
+	if err != nil {
+		return types.MsgStoreCode{}, fmt.Errorf("developer signature: %s", err)
+	}
 
 	// build and sign the transaction, then broadcast to Tendermint
 	msg := types.MsgStoreCode{
-		Sender:       cliCtx.GetFromAddress(),
-		WASMByteCode: wasm,
-		Source:       source,
-		Builder:      builder,
+		Sender:             cliCtx.GetFromAddress(),
+		WASMByteCode:       wasm,
+		Source:             source,
+		Builder:            builder,
+		DeveloperPubKey:    developerPubKey,
+		DeveloperSignature: developerSignature,
 	}
 	return msg, nil
 }
@@ -128,7 +201,7 @@ func parseStoreCodeArgs(args []string, cliCtx client.Context, flags *flag.FlagSe
 // InstantiateContractCmd will instantiate a contract from previously uploaded code.
 func InstantiateContractCmd() *cobra.Command {
 	cmd := &cobra.Command{
-		Use:     "instantiate [code_id_int64] [json_encoded_init_args] --label [text] --amount [coins,optional] --admin [admin_addr_bech32,optional]",
+		Use:     "instantiate [code_id_int64] [json_encoded_init_args] --label [text] --amount [coins,optional] --admin [admin_addr_bech32,optional] --salt [hex,optional]",
 		Short:   "Instantiate a wasm contract",
 		Aliases: []string{"init"},
 		Args:    cobra.ExactArgs(2),
@@ -155,6 +228,7 @@ func InstantiateContractCmd() *cobra.Command {
 	cmd.Flags().String(flagAmount, "", "Coins to send to the contract during instantiation")
 	cmd.Flags().String(flagLabel, "", "A human-readable name for this contract in lists")
 	cmd.Flags().String(flagAdmin, "", "Optional: Bech32 address of the admin of the contract")
+	cmd.Flags().String(flagSalt, "", "Optional: hex encoded salt, instantiates the contract at an address that can be computed in advance")
 	flags.AddTxFlagsToCmd(cmd)
 	return cmd
 }
@@ -260,6 +334,17 @@ func parseInstantiateArgs(args []string, cliCtx client.Context, initFlags *flag.
 		msg.Admin = admin
 	}
 
+	salt, err := initFlags.GetString(flagSalt)
+	if err != nil {
+		return types.MsgInstantiateContract{}, fmt.Errorf("salt: %s", err)
+	}
+	if salt != "" {
+		msg.Salt, err = hex.DecodeString(salt)
+		if err != nil {
+			return types.MsgInstantiateContract{}, fmt.Errorf("salt is not hex encoded: %s", err)
+		}
+	}
+
 	return msg, nil
 }
 
@@ -351,10 +436,33 @@ func ExecuteContractCmd() *cobra.Command {
 		"io-master-key.txt file, which you can get using the command `secretcli q register secret-network-params` ")
 	cmd.Flags().String(flagAmount, "", "Coins to send to the contract along with command")
 	cmd.Flags().String(flagLabel, "", "A human-readable name for this contract in lists")
+	cmd.Flags().StringSlice(flagAccessList, nil, "Optional: hex encoded prefixes of the plaintext keys of the "+
+		"contract store the execution will read or write. The execution fails if it touches any other key. "+
+		"The prefixes are public")
 	flags.AddTxFlagsToCmd(cmd)
 	return cmd
 }
 
+// parseAccessList returns the prefixes of the access list flag, if the command has it
+func parseAccessList(flags *flag.FlagSet) ([][]byte, error) {
+	if flags.Lookup(flagAccessList) == nil {
+		return nil, nil
+	}
+	hexKeys, err := flags.GetStringSlice(flagAccessList)
+	if err != nil {
+		return nil, err
+	}
+	keys := make([][]byte, 0, len(hexKeys))
+	for _, hexKey := range hexKeys {
+		key, err := hex.DecodeString(hexKey)
+		if err != nil {
+			return nil, fmt.Errorf("access list key %s is not hex: %s", hexKey, err)
+		}
+		keys = append(keys, key)
+	}
+	return keys, nil
+}
+
 func ExecuteWithData(cmd *cobra.Command, contractAddress sdk.AccAddress, msg []byte, amount string, genOnly bool, ioMasterKeyPath string, codeHash string, cliCtx client.Context) error {
 	wasmCtx := wasmUtils.WASMContext{CLIContext: cliCtx}
 	execMsg := types.SecretMsg{}
@@ -366,6 +474,11 @@ func ExecuteWithData(cmd *cobra.Command, contractAddress sdk.AccAddress, msg []b
 		return err
 	}
 
+	accessList, err := parseAccessList(cmd.Flags())
+	if err != nil {
+		return err
+	}
+
 	var encryptedMsg []byte
 	if genOnly {
 		execMsg.CodeHash = []byte(codeHash)
@@ -388,6 +501,7 @@ func ExecuteWithData(cmd *cobra.Command, contractAddress sdk.AccAddress, msg []b
 		CallbackCodeHash: "",
 		SentFunds:        coins,
 		Msg:              encryptedMsg,
+		AccessList:       accessList,
 	}
 	return tx.GenerateOrBroadcastTxCLI(cliCtx, cmd.Flags(), &msgExec)
 }
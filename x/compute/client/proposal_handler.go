@@ -0,0 +1,24 @@
+package client
+
+import (
+	"net/http"
+
+	"github.com/cosmos/cosmos-sdk/client"
+	"github.com/cosmos/cosmos-sdk/types/rest"
+	govclient "github.com/cosmos/cosmos-sdk/x/gov/client"
+	govrest "github.com/cosmos/cosmos-sdk/x/gov/client/rest"
+	"github.com/scrtlabs/SecretNetwork/x/compute/client/cli"
+)
+
+// RemoveCodeProposalHandler is the gov client handler of the proposals removing a code
+var RemoveCodeProposalHandler = govclient.NewProposalHandler(cli.RemoveCodeProposalCmd, emptyRestHandler)
+
+// emptyRestHandler is needed by the gov client, compute proposals are only submitted with the CLI or gRPC
+func emptyRestHandler(client.Context) govrest.ProposalRESTHandler {
+	return govrest.ProposalRESTHandler{
+		SubRoute: "unsupported-compute",
+		Handler: func(w http.ResponseWriter, r *http.Request) {
+			rest.WriteErrorResponse(w, http.StatusBadRequest, "Legacy REST Routes are not supported for compute proposals")
+		},
+	}
+}
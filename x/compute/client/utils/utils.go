@@ -3,9 +3,12 @@ package utils
 import (
 	"bytes"
 	"compress/gzip"
+	"crypto/ed25519"
+	"crypto/hmac"
 	"crypto/rand"
 	"crypto/sha256"
 	"encoding/base64"
+	"encoding/binary"
 	"encoding/hex"
 	"encoding/json"
 	"fmt"
@@ -13,10 +16,11 @@ import (
 	"log"
 	"os"
 	"path/filepath"
-	"regexp"
 
 	"github.com/cosmos/cosmos-sdk/client"
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
 	regtypes "github.com/scrtlabs/SecretNetwork/x/registration"
+	ra "github.com/scrtlabs/SecretNetwork/x/registration/remote_attestation"
 	"google.golang.org/grpc/encoding"
 	"google.golang.org/grpc/encoding/proto"
 
@@ -30,6 +34,22 @@ var (
 	wasmIdent = []byte("\x00\x61\x73\x6D")
 )
 
+// Encrypted messages are sent in a v1 envelope, nonce(32) || wallet_pubkey(32) || ciphertext, which
+// uses X25519 and AES-SIV. A v2 envelope prepends EnvelopeV2Magic || key_agreement(1) || aead(1),
+// so that other cipher suites can be introduced without breaking clients sending v1 envelopes.
+var EnvelopeV2Magic = []byte("SCRTENV\x02")
+
+const (
+	EnvelopeV2HeaderLength = 10
+
+	KeyAgreementX25519 byte = 1
+	AeadAesSiv         byte = 1
+
+	// KeyAgreementX25519Kyber768 is only accepted by enclaves built with the experimental pq-hybrid
+	// feature. Its header is followed by a Kyber768 ciphertext for the key of `tx-key-pq`.
+	KeyAgreementX25519Kyber768 byte = 2
+)
+
 // IsGzip returns checks if the file contents are gzip compressed
 func IsGzip(input []byte) bool {
 	return bytes.Equal(input[:3], gzipIdent)
@@ -259,26 +279,32 @@ func (ctx WASMContext) Decrypt(ciphertext []byte, nonce []byte) ([]byte, error)
 	return cipher.Open(nil, ciphertext, []byte{})
 }
 
-var re = regexp.MustCompile("encrypted: (.+?):")
-
+// DecryptError decrypts the contract error in errString, which was encrypted for the tx sender
+// with nonce
 func (ctx WASMContext) DecryptError(errString string, nonce []byte) (json.RawMessage, error) {
-	regexMatch := re.FindStringSubmatch(errString)
-	if len(regexMatch) != 2 {
-		return nil, fmt.Errorf("got an error finding base64 of the error: regexMatch '%v' should have a length of 2. error: %v", regexMatch, errString)
+	errorPlainBz, _, err := ctx.DecryptErrorWithCode(errString, nonce)
+	return errorPlainBz, err
+}
+
+// DecryptErrorWithCode is like DecryptError, and also returns the kind of the error, e.g.
+// "not_found", which is only known to the tx sender
+func (ctx WASMContext) DecryptErrorWithCode(errString string, nonce []byte) (json.RawMessage, string, error) {
+	errorCipherB64, ok := wasmTypes.ParseEncryptedError(errString)
+	if !ok {
+		return nil, "", fmt.Errorf("got an error finding base64 of the error: %v", errString)
 	}
-	errorCipherB64 := regexMatch[1]
 
 	errorCipherBz, err := base64.StdEncoding.DecodeString(errorCipherB64)
 	if err != nil {
-		return nil, fmt.Errorf("got an error decoding base64 of the error: %w", err)
+		return nil, "", fmt.Errorf("got an error decoding base64 of the error: %w", err)
 	}
 
 	errorPlainBz, err := ctx.Decrypt(errorCipherBz, nonce)
 	if err != nil {
-		return nil, fmt.Errorf("got an error decrypting the error: %w", err)
+		return nil, "", fmt.Errorf("got an error decrypting the error: %w", err)
 	}
 
-	return errorPlainBz, nil
+	return errorPlainBz, wasmTypes.ErrorCode(errorPlainBz), nil
 }
 
 func encryptData(aesEncryptionKey []byte, txSenderPubKey []byte, plaintext []byte, nonce []byte) ([]byte, error) {
@@ -315,3 +341,79 @@ func GetTxEncryptionKeyOffline(pubkey []byte, txSenderPrivKey []byte, nonce []by
 
 	return txEncryptionKey, nil
 }
+
+// queryResponseMacKeyLabel separates the MAC key of query responses from other uses of the tx
+// encryption key, as in the enclave
+var queryResponseMacKeyLabel = []byte("secret_query_response_mac_key")
+
+// VerifyContractQueryMAC checks the MAC of the response to a query of a contract that opted in to
+// MACed responses, and that the enclave answered it at minHeight or later on chainID.
+// txEncryptionKey is the key the query was encrypted with (see GetTxEncryptionKeyOffline),
+// contractAddress the canonical address of the contract and encryptedQuery the query as sent to
+// the node. A node or RPC relaying the response doesn't know txEncryptionKey, so it can't forge
+// the MAC of a tampered or stale response.
+func VerifyContractQueryMAC(txEncryptionKey []byte, chainID string, minHeight uint64, contractAddress []byte, encryptedQuery []byte, response *wasmTypes.QueryResponseSignature) error {
+	if len(response.ContractMAC) == 0 {
+		return fmt.Errorf("query response has no contract MAC")
+	}
+	if response.BlockHeight < minHeight {
+		return fmt.Errorf("query response is from height %d, before %d", response.BlockHeight, minHeight)
+	}
+
+	queryHash := sha256.Sum256(append(append([]byte{}, contractAddress...), encryptedQuery...))
+	if !bytes.Equal(queryHash[:], response.QueryHash) {
+		return fmt.Errorf("query response is for another query")
+	}
+	resultHash := sha256.Sum256(response.SignedResult)
+	if !bytes.Equal(resultHash[:], response.ResultHash) {
+		return fmt.Errorf("query response doesn't match its result hash")
+	}
+
+	macKey := sha256.Sum256(append(append([]byte{}, queryResponseMacKeyLabel...), txEncryptionKey...))
+	chainIDHash := sha256.Sum256([]byte(chainID))
+	height := make([]byte, 8)
+	binary.BigEndian.PutUint64(height, response.BlockHeight)
+	mac := hmac.New(sha256.New, macKey[:])
+	mac.Write(chainIDHash[:])
+	mac.Write(height)
+	mac.Write(response.QueryHash)
+	mac.Write(response.ResultHash)
+	if !hmac.Equal(mac.Sum(nil), response.ContractMAC) {
+		return fmt.Errorf("invalid contract MAC")
+	}
+	return nil
+}
+
+// VerifyContractKeyProof checks that a contract key proof was signed by a genuine enclave. nodeCert
+// is the attestation certificate the registration module stored for proof.NodePublicKey.
+func VerifyContractKeyProof(proof *wasmTypes.ContractKeyProof, nodeCert []byte) error {
+	reportData, err := ra.VerifyRaCertReportData(nodeCert)
+	if err != nil {
+		return fmt.Errorf("invalid node certificate: %w", err)
+	}
+
+	return verifyContractKeyProofWithReportData(proof, reportData)
+}
+
+func verifyContractKeyProofWithReportData(proof *wasmTypes.ContractKeyProof, reportData []byte) error {
+	if proof.Version != wasmTypes.ContractKeyProofVersion {
+		return fmt.Errorf("unsupported contract key proof version %d", proof.Version)
+	}
+	if len(proof.SignerPublicKey) != ed25519.PublicKeySize || len(proof.Signature) != ed25519.SignatureSize {
+		return fmt.Errorf("malformed contract key proof")
+	}
+	if !bytes.Equal(reportData[0:32], proof.NodePublicKey) {
+		return fmt.Errorf("node certificate is not of node %s", hex.EncodeToString(proof.NodePublicKey))
+	}
+
+	// the node commits to its signing key in the rest of the report data, after the seed service challenge
+	signerHash := sha256.Sum256(proof.SignerPublicKey)
+	if !bytes.Equal(reportData[36:64], signerHash[:28]) {
+		return fmt.Errorf("node certificate does not commit to signer %s", hex.EncodeToString(proof.SignerPublicKey))
+	}
+
+	if !ed25519.Verify(proof.SignerPublicKey, proof.SigningBytes(), proof.Signature) {
+		return fmt.Errorf("invalid contract key proof signature")
+	}
+	return nil
+}
@@ -1,10 +1,15 @@
 package utils
 
 import (
+	"bytes"
+	"crypto/ed25519"
+	"crypto/hmac"
+	"crypto/sha256"
 	"os"
 	"path/filepath"
 	"testing"
 
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
 	"github.com/scrtlabs/SecretNetwork/x/compute/internal/keeper"
 
 	"github.com/stretchr/testify/require"
@@ -67,3 +72,91 @@ func TestGzipIt(t *testing.T) {
 	require.NoError(t, err)
 	require.Equal(t, originalGzipData, strToGzip)
 }
+
+func TestVerifyContractKeyProofWithReportData(t *testing.T) {
+	signerPub, signerPriv, err := ed25519.GenerateKey(nil)
+	require.NoError(t, err)
+
+	proof := &wasmTypes.ContractKeyProof{
+		Version:             wasmTypes.ContractKeyProofVersion,
+		ContractAddress:     bytes.Repeat([]byte{1}, 20),
+		CodeHash:            bytes.Repeat([]byte{2}, 32),
+		ContractKey:         bytes.Repeat([]byte{3}, 64),
+		EncryptionPublicKey: bytes.Repeat([]byte{4}, 32),
+		NodePublicKey:       bytes.Repeat([]byte{5}, 32),
+		SignerPublicKey:     signerPub,
+	}
+	proof.Signature = ed25519.Sign(signerPriv, proof.SigningBytes())
+
+	signerHash := sha256.Sum256(signerPub)
+	reportData := append(append(append([]byte{}, proof.NodePublicKey...), 0, 0, 0, 0), signerHash[:28]...)
+	require.NoError(t, verifyContractKeyProofWithReportData(proof, reportData))
+
+	t.Log("a proof of another contract key should not verify")
+	forged := *proof
+	forged.ContractKey = bytes.Repeat([]byte{6}, 64)
+	require.Error(t, verifyContractKeyProofWithReportData(&forged, reportData))
+
+	t.Log("a certificate of another node should not verify")
+	otherNode := append([]byte{}, reportData...)
+	otherNode[0] ^= 1
+	require.Error(t, verifyContractKeyProofWithReportData(proof, otherNode))
+
+	t.Log("a certificate that doesn't commit to the signer should not verify")
+	otherSigner := append([]byte{}, reportData...)
+	otherSigner[63] ^= 1
+	require.Error(t, verifyContractKeyProofWithReportData(proof, otherSigner))
+}
+
+func TestVerifyContractQueryMAC(t *testing.T) {
+	txEncryptionKey := bytes.Repeat([]byte{1}, 32)
+	contractAddress := bytes.Repeat([]byte{3}, 20)
+	query := []byte("encrypted query")
+	result := []byte("encrypted result")
+
+	queryHash := sha256.Sum256(append(append([]byte{}, contractAddress...), query...))
+	resultHash := sha256.Sum256(result)
+	macKey := sha256.Sum256(append([]byte("secret_query_response_mac_key"), txEncryptionKey...))
+	chainIDHash := sha256.Sum256([]byte("secret-4"))
+	mac := hmac.New(sha256.New, macKey[:])
+	mac.Write(chainIDHash[:])
+	mac.Write([]byte{0, 0, 0, 0, 0, 0, 0, 42})
+	mac.Write(queryHash[:])
+	mac.Write(resultHash[:])
+
+	response := &wasmTypes.QueryResponseSignature{
+		BlockHeight:  42,
+		QueryHash:    queryHash[:],
+		ResultHash:   resultHash[:],
+		SignedResult: result,
+		ContractMAC:  mac.Sum(nil),
+	}
+	require.NoError(t, VerifyContractQueryMAC(txEncryptionKey, "secret-4", 40, contractAddress, query, response))
+
+	t.Log("a tampered result should not verify")
+	tampered := *response
+	tampered.SignedResult = []byte("tampered result")
+	require.Error(t, VerifyContractQueryMAC(txEncryptionKey, "secret-4", 40, contractAddress, query, &tampered))
+
+	t.Log("a MAC of another encryption key should not verify")
+	require.Error(t, VerifyContractQueryMAC(bytes.Repeat([]byte{4}, 32), "secret-4", 40, contractAddress, query, response))
+
+	t.Log("a MAC of another chain should not verify")
+	require.Error(t, VerifyContractQueryMAC(txEncryptionKey, "pulsar-3", 40, contractAddress, query, response))
+
+	t.Log("a response claiming another height should not verify")
+	otherHeight := *response
+	otherHeight.BlockHeight = 43
+	require.Error(t, VerifyContractQueryMAC(txEncryptionKey, "secret-4", 40, contractAddress, query, &otherHeight))
+
+	t.Log("a response older than the minimum height should not verify")
+	require.Error(t, VerifyContractQueryMAC(txEncryptionKey, "secret-4", 43, contractAddress, query, response))
+
+	t.Log("a response to another query should not verify")
+	require.Error(t, VerifyContractQueryMAC(txEncryptionKey, "secret-4", 40, contractAddress, []byte("other query"), response))
+
+	t.Log("a response without MAC should not verify")
+	unmaced := *response
+	unmaced.ContractMAC = nil
+	require.Error(t, VerifyContractQueryMAC(txEncryptionKey, "secret-4", 40, contractAddress, query, &unmaced))
+}
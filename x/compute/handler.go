@@ -59,7 +59,7 @@ func handleStoreCode(ctx sdk.Context, k Keeper, msg *MsgStoreCode) (*sdk.Result,
 		return nil, err
 	}
 
-	codeID, err := k.Create(ctx, msg.Sender, msg.WASMByteCode, msg.Source, msg.Builder)
+	codeID, err := k.CreateSigned(ctx, msg.Sender, msg.WASMByteCode, msg.Source, msg.Builder, msg.DeveloperPubKey, msg.DeveloperSignature)
 	if err != nil {
 		return nil, err
 	}
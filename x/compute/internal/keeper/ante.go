@@ -4,6 +4,8 @@ import (
 	"encoding/binary"
 
 	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
 	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
 )
 
@@ -43,6 +45,90 @@ func (a CountTXDecorator) AnteHandle(ctx sdk.Context, tx sdk.Tx, simulate bool,
 	return next(types.WithTXCounter(ctx, txCounter), tx, simulate)
 }
 
+// FeeInfoDecorator ante handler to pass who paid the fees of a tx to contracts.
+type FeeInfoDecorator struct{}
+
+// NewFeeInfoDecorator constructor
+func NewFeeInfoDecorator() *FeeInfoDecorator {
+	return &FeeInfoDecorator{}
+}
+
+// AnteHandle handler passes the fee payer and granter of the tx via sdk.Context upstream, from where they are
+// added to the MessageInfo of contracts. See `types.TXFeeInfo(ctx)` to read the value.
+// Simulations don't get fee info, like they don't get a tx counter.
+func (a FeeInfoDecorator) AnteHandle(ctx sdk.Context, tx sdk.Tx, simulate bool, next sdk.AnteHandler) (sdk.Context, error) {
+	feeTx, ok := tx.(sdk.FeeTx)
+	if simulate || !ok {
+		return next(ctx, tx, simulate)
+	}
+
+	feeInfo := types.FeeInfo{
+		Payer:   feeTx.FeePayer(),
+		Granter: feeTx.FeeGranter(),
+	}
+	return next(types.WithFeeInfo(ctx, feeInfo), tx, simulate)
+}
+
+// DecryptTxMsgsDecorator ante handler to decrypt the contract messages of a tx in a single enclave call.
+type DecryptTxMsgsDecorator struct {
+	keeper *Keeper
+}
+
+// NewDecryptTxMsgsDecorator constructor
+func NewDecryptTxMsgsDecorator(keeper *Keeper) *DecryptTxMsgsDecorator {
+	return &DecryptTxMsgsDecorator{keeper: keeper}
+}
+
+// AnteHandle handler decrypts the encrypted messages of txs with several contract messages before they're executed,
+// so that the enclave doesn't decrypt each of them in the call executing it.
+// The enclave call is charged per message and per byte.
+// CheckTx rejects a tx with a message that can't be decrypted, without executing any of its messages.
+// DeliverTx ignores the decryption statuses, a message that can't be decrypted fails when executed as before,
+// and has the enclave keep the plaintexts for the execution of the messages.
+func (a DecryptTxMsgsDecorator) AnteHandle(ctx sdk.Context, tx sdk.Tx, simulate bool, next sdk.AnteHandler) (sdk.Context, error) {
+	if simulate {
+		return next(ctx, tx, simulate)
+	}
+
+	var encryptedMsgs [][]byte
+	var encryptedSize uint64
+	for _, msg := range tx.GetMsgs() {
+		switch msg := msg.(type) {
+		case *types.MsgInstantiateContract:
+			encryptedMsgs = append(encryptedMsgs, msg.InitMsg)
+		case *types.MsgExecuteContract:
+			encryptedMsgs = append(encryptedMsgs, msg.Msg)
+		case *types.MsgMigrateContract:
+			encryptedMsgs = append(encryptedMsgs, msg.Msg)
+		}
+	}
+	if len(encryptedMsgs) < 2 {
+		return next(ctx, tx, simulate)
+	}
+
+	for _, msg := range encryptedMsgs {
+		encryptedSize += uint64(len(msg))
+	}
+	decryptionGas := types.DecryptTxMsgCost*uint64(len(encryptedMsgs)) + types.DecryptTxMsgByteCost*encryptedSize
+	ctx.GasMeter().ConsumeGas(decryptionGas, "Decrypt tx msgs")
+
+	statuses, err := a.keeper.wasmer.DecryptTxMsgs(encryptedMsgs, !ctx.IsCheckTx())
+	if err != nil {
+		ctx.Logger().Error("decrypt tx msgs", "error", err.Error())
+		return next(ctx, tx, simulate)
+	}
+
+	if ctx.IsCheckTx() {
+		for i, status := range statuses {
+			if status != wasmTypes.MsgDecrypted {
+				return ctx, sdkerrors.Wrapf(types.ErrInvalid, "contract message %d of the tx can't be decrypted", i)
+			}
+		}
+	}
+
+	return next(ctx, tx, simulate)
+}
+
 func encodeHeightCounter(height int64, counter uint32) []byte {
 	b := make([]byte, 4)
 	binary.BigEndian.PutUint32(b, counter)
@@ -0,0 +1,23 @@
+package keeper
+
+import (
+	"github.com/cosmos/cosmos-sdk/telemetry"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+)
+
+// ReportAutoPinning sets gauges with the number of code hashes auto pinning keeps pinned and the
+// number of pins and unpins since the node started. It is local to the node and not part of
+// consensus.
+func (k Keeper) ReportAutoPinning(ctx sdk.Context) {
+	metrics, err := k.wasmer.AutoPinMetrics()
+	if err != nil {
+		moduleLogger(ctx).Error("failed to read auto pinning metrics", "error", err)
+		return
+	}
+	if metrics == nil {
+		return
+	}
+	telemetry.SetGauge(float32(metrics.Pinned), "compute", "auto_pin", "pinned")
+	telemetry.SetGauge(float32(metrics.Pins), "compute", "auto_pin", "pins")
+	telemetry.SetGauge(float32(metrics.Unpins), "compute", "auto_pin", "unpins")
+}
@@ -0,0 +1,21 @@
+package keeper
+
+import (
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// BulkMemoryEnabled reports whether contracts using the bulk memory proposal can be stored at the
+// height of ctx, which is decided by the BulkMemoryHeight param. The enclave runs them from the same
+// height, see FeatureBulkMemory.
+func (k Keeper) BulkMemoryEnabled(ctx sdk.Context) bool {
+	return k.GetParams(ctx).IsActive(types.FeatureBulkMemory, ctx.BlockHeight())
+}
+
+// DeveloperSignaturesEnabled reports whether code can be stored signed by its developer at the height of ctx,
+// which is decided by the DeveloperSignatureHeight param.
+func (k Keeper) DeveloperSignaturesEnabled(ctx sdk.Context) bool {
+	height := k.GetParams(ctx).DeveloperSignatureHeight
+	return height > 0 && ctx.BlockHeight() >= height
+}
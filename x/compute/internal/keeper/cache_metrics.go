@@ -0,0 +1,47 @@
+package keeper
+
+import (
+	"github.com/cosmos/cosmos-sdk/telemetry"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+)
+
+// ReportCacheMetrics sets gauges with the hits and misses of the contract cache of the VM, the time
+// it spent loading and parsing Wasm since the node started and how the module cache of the enclave
+// performed, for operators to size it with contract-memory-enclave-cache-size. It is local to the
+// node and not part of consensus.
+func (k Keeper) ReportCacheMetrics(ctx sdk.Context) {
+	metrics, err := k.wasmer.GetMetrics()
+	if err != nil {
+		moduleLogger(ctx).Error("failed to read cache metrics", "error", err)
+		return
+	}
+	if metrics == nil {
+		return
+	}
+	telemetry.SetGauge(float32(metrics.HitsPinned), "compute", "cache", "hits_pinned")
+	telemetry.SetGauge(float32(metrics.Misses), "compute", "cache", "misses")
+	telemetry.SetGauge(float32(metrics.ElementsPinned), "compute", "cache", "elements_pinned")
+	telemetry.SetGauge(float32(metrics.UnsealTimeNs)/1e6, "compute", "cache", "unseal_time_ms")
+	telemetry.SetGauge(float32(metrics.ParseTimeNs)/1e6, "compute", "cache", "parse_time_ms")
+
+	enclave := metrics.EnclaveModuleCache
+	telemetry.SetGauge(float32(enclave.Capacity), "compute", "enclave_module_cache", "capacity")
+	telemetry.SetGauge(float32(enclave.Size), "compute", "enclave_module_cache", "size")
+	telemetry.SetGauge(float32(enclave.Pinned), "compute", "enclave_module_cache", "pinned")
+	telemetry.SetGauge(float32(enclave.Hits), "compute", "enclave_module_cache", "hits")
+	telemetry.SetGauge(float32(enclave.PinnedHits), "compute", "enclave_module_cache", "pinned_hits")
+	telemetry.SetGauge(float32(enclave.Misses), "compute", "enclave_module_cache", "misses")
+	telemetry.SetGauge(float32(enclave.Evictions), "compute", "enclave_module_cache", "evictions")
+}
+
+// EnclaveModuleCache returns how the module cache of the enclave performed since the node started,
+// as reported after its last contract call. It is local to the node and not part of consensus.
+func (k Keeper) EnclaveModuleCache() (*wasmTypes.EnclaveModuleCacheStats, error) {
+	metrics, err := k.wasmer.GetMetrics()
+	if err != nil || metrics == nil {
+		return nil, err
+	}
+	return &metrics.EnclaveModuleCache, nil
+}
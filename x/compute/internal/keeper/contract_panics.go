@@ -0,0 +1,34 @@
+package keeper
+
+import (
+	"github.com/armon/go-metrics"
+	"github.com/cosmos/cosmos-sdk/telemetry"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+)
+
+// ContractPanics returns how often the contracts of each code hash trapped since the node started,
+// as counted by the VM. It is local to the node and not part of consensus.
+func (k Keeper) ContractPanics() ([]wasmTypes.ContractPanicStats, error) {
+	return k.wasmer.ContractPanicStats()
+}
+
+// ReportContractPanics sets the compute_contract_panics gauge, labeled with the code hash, to the
+// number of times the contracts of that code hash trapped since the node started, for operators to
+// spot a malfunctioning contract. The VM keeps a bounded number of code hashes, so the number of
+// label values doesn't grow with the number of contracts on chain.
+func (k Keeper) ReportContractPanics(ctx sdk.Context) {
+	stats, err := k.ContractPanics()
+	if err != nil {
+		moduleLogger(ctx).Error("failed to read contract panic stats", "error", err)
+		return
+	}
+	for _, s := range stats {
+		telemetry.SetGaugeWithLabels(
+			[]string{"compute", "contract", "panics"},
+			float32(s.Count),
+			[]metrics.Label{telemetry.NewLabel("code_hash", s.CodeHash)},
+		)
+	}
+}
@@ -0,0 +1,43 @@
+package keeper
+
+import (
+	"encoding/json"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// setContractVersion stores the cw2 version the enclave read from a contract after it was
+// instantiated or migrated. A nil version, of a contract not following the convention, removes it.
+func (k Keeper) setContractVersion(ctx sdk.Context, contractAddress sdk.AccAddress, version *wasmTypes.ContractVersion) {
+	store := ctx.KVStore(k.storeKey)
+	if version == nil {
+		store.Delete(types.GetContractVersionKey(contractAddress))
+		return
+	}
+	bz, err := json.Marshal(version)
+	if err != nil {
+		panic(err)
+	}
+	store.Set(types.GetContractVersionKey(contractAddress), bz)
+}
+
+// GetContractVersion returns the cw2 name and version of a contract, or nil if it doesn't store one
+func (k Keeper) GetContractVersion(ctx sdk.Context, contractAddress sdk.AccAddress) (*wasmTypes.ContractVersion, error) {
+	if k.GetContractInfo(ctx, contractAddress) == nil {
+		return nil, sdkerrors.Wrap(types.ErrNotFound, "contract")
+	}
+
+	bz := ctx.KVStore(k.storeKey).Get(types.GetContractVersionKey(contractAddress))
+	if bz == nil {
+		return nil, nil
+	}
+	var version wasmTypes.ContractVersion
+	if err := json.Unmarshal(bz, &version); err != nil {
+		return nil, err
+	}
+	return &version, nil
+}
@@ -0,0 +1,107 @@
+package keeper
+
+import (
+	"encoding/json"
+	"os"
+	"strings"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+)
+
+// determinismCorpus is a sequence of messages, executed in order on every test contract. It
+// should cover every host function and error path whose result or gas could differ between node
+// configurations: SW and HW enclaves, and the supported wasm engines.
+//
+// Results that depend on the randomness of a test run (contract and wallet addresses, nonces,
+// event order) aren't recorded, so messages that return them shouldn't be added here.
+var determinismCorpus = []struct {
+	msg              string
+	isErrorEncrypted bool
+	gas              uint64
+}{
+	{`{"nop":{}}`, true, defaultGasForTests},
+	{`{"empty_data":{}}`, true, defaultGasForTests},
+	{`{"no_data":{}}`, true, defaultGasForTests},
+	{`{"unicode_data":{}}`, true, defaultGasForTests},
+	{`{"contract_error":{"error_type":"generic_err"}}`, true, defaultGasForTests},
+	{`{"panic":{}}`, false, defaultGasForTests},
+	{`{"allocate_on_heap":{"bytes":13631488}}`, false, defaultGasForTests},
+	{`{"allocate_on_heap":{"bytes":1073741824}}`, false, 9_000_000},
+	{`{"secp256k1_verify":{"iterations":1,"pubkey":"A0ZGrlBHMWtCMNAIbIrOxofwCxzZ0dxjT2yzWKwKmo//","sig":"/hZeEYHs9trj+Akeb+7p3UAtXjcDNYP9/D/hj/ALIUAG9bfrJltxkfpMz/9Jn5K3c5QjLuvaNT2jgr7P/AEW8A==","msg_hash":"ARp3VEHssUlDEwoW8AzdQYGKg90ENy8yWePKcjfjzao="}}`, true, defaultGasForTests},
+	{`{"secp256k1_recover_pubkey":{"iterations":1,"recovery_param":0,"sig":"/hZeEYHs9trj+Akeb+7p3UAtXjcDNYP9/D/hj/ALIUAG9bfrJltxkfpMz/9Jn5K3c5QjLuvaNT2jgr7P/AEW8A==","msg_hash":"ARp3VEHssUlDEwoW8AzdQYGKg90ENy8yWePKcjfjzao="}}`, true, defaultGasForTests},
+	{`{"ed25519_verify":{"iterations":1,"pubkey":"LO2+Bt+/FIjomSaPB+I++LXkxgxwfnrKHLyvCic72rA=","sig":"8O7nwhM71/B9srKwe8Ps39z5lAsLMMs6LxdvoPk0HXjEM97TNhKbdU6gEePT2MaaIUSiMEmoG28HIZMgMRTCDg==","msg":"YXNzYWYgd2FzIGhlcmU="}}`, true, defaultGasForTests},
+	{`{"ed25519_batch_verify":{"iterations":1,"pubkeys":["LO2+Bt+/FIjomSaPB+I++LXkxgxwfnrKHLyvCic72rA="],"sigs":["8O7nwhM71/B9srKwe8Ps39z5lAsLMMs6LxdvoPk0HXjEM97TNhKbdU6gEePT2MaaIUSiMEmoG28HIZMgMRTCDg=="],"msgs":["YXNzYWYgd2FzIGhlcmU="]}}`, true, defaultGasForTests},
+	{`{"test_canonicalize_address_errors":{}}`, true, defaultGasForTests},
+	// calls the contract itself
+	{`{"a":{"contract_addr":"$contract_addr","code_hash":"$code_hash","x":2,"y":3}}`, true, defaultGasForTests},
+}
+
+// determinismRecord is what a message of the corpus returned. Nodes running different
+// configurations only agree on a block if these are identical for every transaction.
+type determinismRecord struct {
+	Contract string `json:"contract"`
+	Msg      int    `json:"msg"`
+	Output   string `json:"output"`
+	GasUsed  uint64 `json:"gas_used"`
+	Error    string `json:"error"`
+}
+
+func runDeterminismCorpus(t *testing.T) []determinismRecord {
+	var records []determinismRecord
+	for _, testContract := range testContracts {
+		ctx, keeper, codeID, codeHash, walletA, privKeyA, _, _ := setupTest(t, testContract.WasmFilePath, sdk.NewCoins())
+
+		_, initCtx, contractAddress, _, initErr := initHelper(t, keeper, ctx, codeID, walletA, nil, privKeyA, `{"nop":{}}`, true, testContract.IsCosmWasmV1, defaultGasForTests)
+		require.Empty(t, initErr)
+		records = append(records, determinismRecord{
+			Contract: testContract.CosmWasmVersion,
+			Msg:      -1,
+			GasUsed:  initCtx.GasMeter().GasConsumed(),
+		})
+
+		for i, corpusMsg := range determinismCorpus {
+			execMsg := strings.NewReplacer("$contract_addr", contractAddress.String(), "$code_hash", codeHash).Replace(corpusMsg.msg)
+			_, _, data, _, gasUsed, err := execHelper(t, keeper, ctx, contractAddress, walletA, privKeyA, execMsg, corpusMsg.isErrorEncrypted, testContract.IsCosmWasmV1, corpusMsg.gas, 0)
+
+			record := determinismRecord{
+				Contract: testContract.CosmWasmVersion,
+				Msg:      i,
+				Output:   string(data),
+				GasUsed:  gasUsed,
+			}
+			if !err.IsEmpty() {
+				record.Error = err.Error()
+			}
+			records = append(records, record)
+		}
+	}
+	return records
+}
+
+// TestDeterminismCorpus runs the corpus twice and requires identical results.
+//
+// To compare node configurations, set SCRT_DETERMINISM_RECORD to write the results of a run to a
+// file, and SCRT_DETERMINISM_EXPECTED to require the results of a run to match such a file.
+// `make determinism-tests` does this for SW and HW enclaves, and for enclaves built with other
+// features such as an alternative wasm engine, as part of release qualification.
+func TestDeterminismCorpus(t *testing.T) {
+	records := runDeterminismCorpus(t)
+	require.Equal(t, records, runDeterminismCorpus(t), "results differ between two runs of the same node")
+
+	if path := os.Getenv("SCRT_DETERMINISM_RECORD"); path != "" {
+		bz, err := json.MarshalIndent(records, "", "  ")
+		require.NoError(t, err)
+		require.NoError(t, os.WriteFile(path, bz, 0o644))
+	}
+
+	if path := os.Getenv("SCRT_DETERMINISM_EXPECTED"); path != "" {
+		bz, err := os.ReadFile(path)
+		require.NoError(t, err)
+		var expected []determinismRecord
+		require.NoError(t, json.Unmarshal(bz, &expected))
+		require.Equal(t, expected, records, "results differ from %s", path)
+	}
+}
@@ -0,0 +1,44 @@
+package keeper
+
+import (
+	"encoding/json"
+	"os"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+)
+
+// executionReceiptRecord is a line of the execution receipts file
+type executionReceiptRecord struct {
+	ChainID string `json:"chain_id"`
+	*wasmTypes.ExecutionReceipt
+}
+
+// recordExecutionReceipt appends the receipt the enclave signed for the last contract call to the
+// execution receipts file, so it can be handed to off-chain parties. Receipts of calls running in
+// CheckTx or simulations are not recorded.
+//
+// Failing to write it only logs, as receipts are not part of consensus.
+func (k Keeper) recordExecutionReceipt(ctx sdk.Context, receipt *wasmTypes.ExecutionReceipt) {
+	if receipt == nil || k.executionReceiptsFile == "" || ctx.IsCheckTx() {
+		return
+	}
+
+	record, err := json.Marshal(executionReceiptRecord{ChainID: ctx.ChainID(), ExecutionReceipt: receipt})
+	if err != nil {
+		moduleLogger(ctx).Error("failed to encode an execution receipt", "error", err)
+		return
+	}
+
+	file, err := os.OpenFile(k.executionReceiptsFile, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0o600)
+	if err != nil {
+		moduleLogger(ctx).Error("failed to open the execution receipts file", "error", err)
+		return
+	}
+	defer file.Close()
+
+	if _, err := file.Write(append(record, '\n')); err != nil {
+		moduleLogger(ctx).Error("failed to write an execution receipt", "error", err)
+	}
+}
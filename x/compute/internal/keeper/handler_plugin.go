@@ -163,6 +163,7 @@ type (
 	DistributionEncoder func(sender sdk.AccAddress, msg *v1wasmTypes.DistributionMsg) ([]sdk.Msg, error)
 	GovEncoder          func(sender sdk.AccAddress, msg *v1wasmTypes.GovMsg) ([]sdk.Msg, error)
 	IBCEncoder          func(ctx sdk.Context, sender sdk.AccAddress, contractIBCPortID string, msg *v1wasmTypes.IBCMsg) ([]sdk.Msg, error)
+	SecretEncoder       func(sender sdk.AccAddress, msg *v1wasmTypes.SecretMsg) ([]sdk.Msg, error)
 	StakingEncoder      func(sender sdk.AccAddress, msg *v1wasmTypes.StakingMsg) ([]sdk.Msg, error)
 	StargateEncoder     func(sender sdk.AccAddress, msg *v1wasmTypes.StargateMsg) ([]sdk.Msg, error)
 	WasmEncoder         func(sender sdk.AccAddress, msg *v1wasmTypes.WasmMsg) ([]sdk.Msg, error)
@@ -174,6 +175,7 @@ type MessageEncoders struct {
 	Distribution DistributionEncoder
 	Gov          GovEncoder
 	IBC          IBCEncoder
+	Secret       SecretEncoder
 	Staking      StakingEncoder
 	Stargate     StargateEncoder
 	Wasm         WasmEncoder
@@ -186,6 +188,7 @@ func DefaultEncoders(portSource types.ICS20TransferPortSource, unpacker codectyp
 		Distribution: EncodeDistributionMsg,
 		Gov:          EncodeGovMsg,
 		IBC:          EncodeIBCMsg(portSource),
+		Secret:       NoSecretMsg,
 		Staking:      EncodeStakingMsg,
 		Stargate:     EncodeStargateMsg(unpacker),
 		Wasm:         EncodeWasmMsg,
@@ -211,6 +214,9 @@ func (e MessageEncoders) Merge(o *MessageEncoders) MessageEncoders {
 	if o.Gov != nil {
 		e.Gov = o.Gov
 	}
+	if o.Secret != nil {
+		e.Secret = o.Secret
+	}
 	return e
 }
 
@@ -267,6 +273,8 @@ func (e MessageEncoders) Encode(ctx sdk.Context, contractAddr sdk.AccAddress, co
 		return e.Gov(contractAddr, msg.Gov)
 	case msg.IBC != nil:
 		return e.IBC(ctx, contractAddr, contractIBCPortID, msg.IBC)
+	case msg.Secret != nil:
+		return e.Secret(contractAddr, msg.Secret)
 	case msg.Staking != nil:
 		return e.Staking(contractAddr, msg.Staking)
 	case msg.Stargate != nil:
@@ -365,6 +373,16 @@ func NoCustomMsg(_ sdk.AccAddress, _ json.RawMessage) ([]sdk.Msg, error) {
 	return nil, sdkerrors.Wrap(types.ErrInvalidMsg, "Custom variant not supported")
 }
 
+// NoSecretMsg is the Secret encoder of a chain without shielded balances. A chain that adds them
+// sets its own encoder through the custom encoders of the keeper.
+func NoSecretMsg(_ sdk.AccAddress, msg *v1wasmTypes.SecretMsg) ([]sdk.Msg, error) {
+	switch {
+	case msg.Shield != nil, msg.Unshield != nil, msg.ShieldedSend != nil:
+		return nil, sdkerrors.Wrap(types.ErrInvalidMsg, "Secret messages are not supported by this chain")
+	}
+	return nil, sdkerrors.Wrap(types.ErrInvalidMsg, "Unknown variant of Secret")
+}
+
 func EncodeDistributionMsg(sender sdk.AccAddress, msg *v1wasmTypes.DistributionMsg) ([]sdk.Msg, error) {
 	switch {
 	case msg.SetWithdrawAddress != nil:
@@ -8,12 +8,14 @@ import (
 	"github.com/stretchr/testify/require"
 
 	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
 	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
 	distributiontypes "github.com/cosmos/cosmos-sdk/x/distribution/types"
 	stakingtypes "github.com/cosmos/cosmos-sdk/x/staking/types"
 
 	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
 	v010wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types/v010"
+	v1wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types/v1"
 
 	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
 )
@@ -283,3 +285,93 @@ func TestEncoding(t *testing.T) {
 		})
 	}
 }
+
+func TestEncodeSecretMsg(t *testing.T) {
+	_, _, addr1 := keyPubAddr()
+	_, _, addr2 := keyPubAddr()
+	msg := v1wasmTypes.CosmosMsg{
+		Secret: &v1wasmTypes.SecretMsg{
+			ShieldedSend: &v1wasmTypes.ShieldedSendMsg{
+				ToAddress: addr2.String(),
+				Amount:    []wasmTypes.Coin{wasmTypes.NewCoin(1, "uscrt")},
+			},
+		},
+	}
+
+	var ctx sdk.Context
+	encodingConfig := MakeEncodingConfig()
+	encoders := DefaultEncoders(nil, encodingConfig.Marshaler)
+	_, err := encoders.Encode(ctx, addr1, "", msg)
+	require.True(t, types.ErrInvalidMsg.Is(err), err)
+
+	// a chain with shielded balances plugs in its own encoder
+	sendMsg := &banktypes.MsgSend{FromAddress: addr1.String(), ToAddress: addr2.String()}
+	encoders = encoders.Merge(&MessageEncoders{
+		Secret: func(sender sdk.AccAddress, msg *v1wasmTypes.SecretMsg) ([]sdk.Msg, error) {
+			require.Equal(t, addr1, sender)
+			require.NotNil(t, msg.ShieldedSend)
+			return []sdk.Msg{sendMsg}, nil
+		},
+	})
+	res, err := encoders.Encode(ctx, addr1, "", msg)
+	require.NoError(t, err)
+	require.Equal(t, []sdk.Msg{sendMsg}, res)
+}
+
+func TestNormalizeMsgFunds(t *testing.T) {
+	_, _, addr := keyPubAddr()
+
+	specs := map[string]struct {
+		input  wasmTypes.Coins
+		output wasmTypes.Coins
+		expErr bool
+	}{
+		"normalized": {
+			input:  wasmTypes.Coins{wasmTypes.NewCoin(1, "uatom"), wasmTypes.NewCoin(2, "usdt")},
+			output: wasmTypes.Coins{wasmTypes.NewCoin(1, "uatom"), wasmTypes.NewCoin(2, "usdt")},
+		},
+		"unsorted with repeated and zero amounts": {
+			input: wasmTypes.Coins{
+				wasmTypes.NewCoin(2, "usdt"),
+				wasmTypes.NewCoin(0, "ueth"),
+				wasmTypes.NewCoin(1, "uatom"),
+				wasmTypes.NewCoin(3, "usdt"),
+			},
+			output: wasmTypes.Coins{wasmTypes.NewCoin(1, "uatom"), wasmTypes.NewCoin(5, "usdt")},
+		},
+		"negative amount": {
+			input:  wasmTypes.Coins{{Denom: "uatom", Amount: "-1"}},
+			expErr: true,
+		},
+		"invalid denom": {
+			input:  wasmTypes.Coins{wasmTypes.NewCoin(1, "1atom")},
+			expErr: true,
+		},
+	}
+	for name, spec := range specs {
+		t.Run(name, func(t *testing.T) {
+			send := v1wasmTypes.CosmosMsg{Bank: &v1wasmTypes.BankMsg{Send: &v1wasmTypes.SendMsg{
+				ToAddress: addr.String(),
+				Amount:    spec.input,
+			}}}
+			execute := v1wasmTypes.CosmosMsg{Wasm: &v1wasmTypes.WasmMsg{Execute: &v010wasmTypes.ExecuteMsg{
+				ContractAddr: addr.String(),
+				Send:         spec.input,
+			}}}
+
+			normalizedSend, err := normalizeMsgFunds(send)
+			if spec.expErr {
+				require.True(t, sdkerrors.ErrInvalidCoins.Is(err), err)
+				return
+			}
+			require.NoError(t, err)
+			require.Equal(t, spec.output, normalizedSend.Bank.Send.Amount)
+			require.Equal(t, spec.input, send.Bank.Send.Amount, "the original message is unchanged")
+
+			normalizedExecute, err := normalizeMsgFunds(execute)
+			require.NoError(t, err)
+			require.Equal(t, spec.output, normalizedExecute.Wasm.Execute.Send)
+			require.Equal(t, spec.input, execute.Wasm.Execute.Send, "the original message is unchanged")
+		})
+	}
+}
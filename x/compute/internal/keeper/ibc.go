@@ -6,6 +6,7 @@ import (
 	sdk "github.com/cosmos/cosmos-sdk/types"
 	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
 	capabilitytypes "github.com/cosmos/cosmos-sdk/x/capability/types"
+	v1wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types/v1"
 	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
 
 	host "github.com/cosmos/ibc-go/v4/modules/core/24-host"
@@ -31,6 +32,15 @@ func (k Keeper) ensureIbcPort(ctx sdk.Context, contractAddr sdk.AccAddress) (str
 	return portID, k.bindIbcPort(ctx, portID)
 }
 
+// checkIbcEntryPoints rejects contracts exporting some of the IBC entry points but not all: they
+// can't take part in IBC, and the enclave would fail calling the ones they miss
+func checkIbcEntryPoints(report *v1wasmTypes.AnalysisReport) error {
+	if report.HasIBCEntryPoints || report.IBCEntryPoints == "" {
+		return nil
+	}
+	return sdkerrors.Wrapf(types.ErrInvalid, "contract exports the ibc entry points %s but not %s", report.IBCEntryPoints, report.MissingIBCEntryPoints)
+}
+
 const portIDPrefix = "wasm."
 
 func ContractFromPortID(portID string) (sdk.AccAddress, error) {
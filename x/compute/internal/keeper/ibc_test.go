@@ -1272,3 +1272,20 @@ func TestIBCPacketTimeout(t *testing.T) {
 		})
 	}
 }
+
+func TestCheckIbcEntryPoints(t *testing.T) {
+	require.NoError(t, checkIbcEntryPoints(&v1types.AnalysisReport{
+		MissingIBCEntryPoints: "ibc_channel_open,ibc_channel_connect,ibc_channel_close,ibc_packet_receive,ibc_packet_ack,ibc_packet_timeout",
+	}))
+	require.NoError(t, checkIbcEntryPoints(&v1types.AnalysisReport{
+		HasIBCEntryPoints: true,
+		IBCEntryPoints:    "ibc_channel_open,ibc_channel_connect,ibc_channel_close,ibc_packet_receive,ibc_packet_ack,ibc_packet_timeout",
+	}))
+
+	err := checkIbcEntryPoints(&v1types.AnalysisReport{
+		IBCEntryPoints:        "ibc_channel_open,ibc_packet_receive",
+		MissingIBCEntryPoints: "ibc_channel_connect,ibc_channel_close,ibc_packet_ack,ibc_packet_timeout",
+	})
+	require.ErrorIs(t, err, types.ErrInvalid)
+	require.Contains(t, err.Error(), "contract exports the ibc entry points ibc_channel_open,ibc_packet_receive but not ibc_channel_connect,ibc_channel_close,ibc_packet_ack,ibc_packet_timeout")
+}
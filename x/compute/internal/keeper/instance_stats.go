@@ -0,0 +1,15 @@
+package keeper
+
+import (
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+)
+
+// InstanceStats returns how many times this node executed a contract, at which height it last did
+// and the gas the executions used in total, or nil if it never executed it. Instantiations and
+// migrations count as executions. The VM keeps them across restarts, but they are local to the node
+// and not part of consensus.
+func (k Keeper) InstanceStats(contractAddress sdk.AccAddress) (*wasmTypes.InstanceStats, error) {
+	return k.wasmer.InstanceStats(contractAddress.String())
+}
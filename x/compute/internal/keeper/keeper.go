@@ -8,6 +8,7 @@ import (
 	"encoding/json"
 	"fmt"
 	"path/filepath"
+	"slices"
 	"strconv"
 	"strings"
 	"time"
@@ -31,6 +32,7 @@ import (
 	distrkeeper "github.com/cosmos/cosmos-sdk/x/distribution/keeper"
 	govkeeper "github.com/cosmos/cosmos-sdk/x/gov/keeper"
 	mintkeeper "github.com/cosmos/cosmos-sdk/x/mint/keeper"
+	paramtypes "github.com/cosmos/cosmos-sdk/x/params/types"
 	stakingkeeper "github.com/cosmos/cosmos-sdk/x/staking/keeper"
 	"github.com/tendermint/tendermint/libs/log"
 
@@ -68,6 +70,7 @@ type ResponseHandler interface {
 // Keeper will have a reference to Wasmer with it's own data directory.
 type Keeper struct {
 	storeKey         sdk.StoreKey
+	paramSpace       paramtypes.Subspace
 	cdc              codec.BinaryCodec
 	legacyAmino      codec.LegacyAmino
 	accountKeeper    authkeeper.AccountKeeper
@@ -79,9 +82,14 @@ type Keeper struct {
 	messenger        Messenger
 	// queryGasLimit is the max wasm gas that can be spent on executing a query with a contract
 	queryGasLimit uint64
-	HomeDir       string
+	// observedEventsFile is the file events encrypted to contract observers are appended to
+	observedEventsFile string
+	// executionReceiptsFile is the file the receipts the enclave signs are appended to
+	executionReceiptsFile string
+	// consensusConfigHash identifies the part of the config all nodes of the network must share
+	consensusConfigHash []byte
+	HomeDir             string
 	// authZPolicy   AuthorizationPolicy
-	// paramSpace    subspace.Subspace
 	LastMsgManager *baseapp.LastMsgMarkerContainer
 }
 
@@ -98,6 +106,7 @@ func NewKeeper(
 	cdc codec.Codec,
 	legacyAmino codec.LegacyAmino,
 	storeKey sdk.StoreKey,
+	paramSpace paramtypes.Subspace,
 	accountKeeper authkeeper.AccountKeeper,
 	bankKeeper bankkeeper.Keeper,
 	govKeeper govkeeper.Keeper,
@@ -119,13 +128,61 @@ func NewKeeper(
 	customPlugins *QueryPlugins,
 	lastMsgManager *baseapp.LastMsgMarkerContainer,
 ) Keeper {
-	wasmer, err := wasm.NewWasmer(filepath.Join(homeDir, "wasm"), supportedFeatures, wasmConfig.CacheSize, wasmConfig.EnclaveCacheSize)
+	// before the enclave is used, so a node never takes part in consensus with an enclave it
+	// shouldn't run
+	err := wasm.ValidateEnclaveLaunch(wasmConfig.ChainID, wasmConfig.AllowDebugEnclave)
 	if err != nil {
 		panic(err)
 	}
+	if wasmConfig.LegacyEnclaveFile != "" {
+		err = wasm.LoadLegacyEnclave(wasmConfig.LegacyEnclaveFile)
+		if err != nil {
+			panic(err)
+		}
+	}
+
+	wasmer, err := wasm.NewWasmer(
+		filepath.Join(homeDir, "wasm"),
+		supportedFeatures,
+		// the validation of Secret Network, chains forking it can set their own
+		nil,
+		wasmConfig.UpgradeSchedule(),
+		wasmConfig.CacheSize,
+		wasmConfig.EnclaveCacheSize,
+		wasmConfig.SignExecutionReceipts,
+		wasmConfig.SignQueryResponses,
+	)
+	if err != nil {
+		panic(err)
+	}
+	if wasmConfig.AutoPinTopK > 0 {
+		err = wasmer.ConfigureAutoPinning(wasmConfig.AutoPinTopK, wasmConfig.AutoPinEpochLength)
+		if err != nil {
+			panic(err)
+		}
+	}
+	if len(wasmConfig.PrewarmCodeHashes) > 0 {
+		codeHashes := make([]wasm.CodeHash, len(wasmConfig.PrewarmCodeHashes))
+		for i, codeHash := range wasmConfig.PrewarmCodeHashes {
+			codeHashes[i], err = hex.DecodeString(codeHash)
+			if err != nil {
+				panic(fmt.Errorf("invalid code hash %q to prewarm: %w", codeHash, err))
+			}
+		}
+		// runs in the background, code that can't be loaded is only logged
+		err = wasmer.Prewarm(codeHashes, wasmConfig.PrewarmPin)
+		if err != nil {
+			panic(err)
+		}
+	}
+
+	if !paramSpace.HasKeyTable() {
+		paramSpace = paramSpace.WithKeyTable(types.ParamKeyTable())
+	}
 
 	keeper := Keeper{
 		storeKey:         storeKey,
+		paramSpace:       paramSpace,
 		cdc:              cdc,
 		legacyAmino:      legacyAmino,
 		wasmer:           *wasmer,
@@ -143,35 +200,81 @@ func NewKeeper(
 			portSource,
 			cdc,
 		),
-		queryGasLimit:  wasmConfig.SmartQueryGasLimit,
-		HomeDir:        homeDir,
-		LastMsgManager: lastMsgManager,
+		queryGasLimit:         wasmConfig.SmartQueryGasLimit,
+		observedEventsFile:    homePath(homeDir, wasmConfig.ObservedEventsFile),
+		executionReceiptsFile: homePath(homeDir, wasmConfig.ExecutionReceiptsFile),
+		consensusConfigHash:   types.ConsensusHash(supportedFeatures),
+		HomeDir:               homeDir,
+		LastMsgManager:        lastMsgManager,
 	}
 	keeper.queryPlugins = DefaultQueryPlugins(govKeeper, distKeeper, mintKeeper, bankKeeper, stakingKeeper, queryRouter, &keeper, channelKeeper).Merge(customPlugins)
 
 	return keeper
 }
 
+// checkFundsDenoms fails if funds sent to a contract include a denom the allowlist of the params
+// doesn't
+func (k Keeper) checkFundsDenoms(ctx sdk.Context, funds sdk.Coins) error {
+	allowlist := k.GetParams(ctx).FundsDenomAllowlist
+	if len(allowlist) == 0 {
+		return nil
+	}
+	for _, coin := range funds {
+		if !slices.Contains(allowlist, coin.Denom) {
+			return sdkerrors.Wrapf(sdkerrors.ErrInvalidCoins, "denom %s can't be sent to contracts", coin.Denom)
+		}
+	}
+	return nil
+}
+
 func (k Keeper) GetLastMsgMarkerContainer() *baseapp.LastMsgMarkerContainer {
 	return k.LastMsgManager
 }
 
 // Create uploads and compiles a WASM contract, returning a short identifier for the contract
 func (k Keeper) Create(ctx sdk.Context, creator sdk.AccAddress, wasmCode []byte, source string, builder string) (codeID uint64, err error) {
+	return k.CreateSigned(ctx, creator, wasmCode, source, builder, nil, nil)
+}
+
+// CreateSigned is like Create, only the code can be signed by its developer once the DeveloperSignatureHeight param
+// is reached. When developerPubKey is set, developerSignature must be its signature over the raw (uncompressed) code
+// exactly as uploaded, which is the code returned by GetWasm and hashed into the code hash, and the key is recorded in
+// the code info for users to check who built the code. The signature is verified by the wasm validation of the node.
+func (k Keeper) CreateSigned(ctx sdk.Context, creator sdk.AccAddress, wasmCode []byte, source string, builder string, developerPubKey []byte, developerSignature []byte) (codeID uint64, err error) {
 	wasmCode, err = uncompress(wasmCode)
 	if err != nil {
 		return 0, sdkerrors.Wrap(types.ErrCreateFailed, err.Error())
 	}
+	signed := len(developerPubKey) != 0 || len(developerSignature) != 0
+	if signed {
+		if !k.DeveloperSignaturesEnabled(ctx) {
+			return 0, sdkerrors.Wrap(types.ErrInvalid, "developer signatures are not enabled yet")
+		}
+		ctx.GasMeter().ConsumeGas(k.accountKeeper.GetParams(ctx).SigVerifyCostSecp256k1, "Verifying developer signature")
+		ctx.GasMeter().ConsumeGas(types.DeveloperSignatureHashCostPerByte*uint64(len(wasmCode)), "Hashing signed WASM Bytecode")
+	}
 	ctx.GasMeter().ConsumeGas(types.CompileCost*uint64(len(wasmCode)), "Compiling WASM Bytecode")
 
-	codeHash, err := k.wasmer.Create(wasmCode)
+	codeHash, err := k.wasmer.CreateSigned(wasmCode, k.BulkMemoryEnabled(ctx), developerPubKey, developerSignature)
 	if err != nil {
+		if signed && strings.Contains(err.Error(), "not signed by the developer key") {
+			return 0, sdkerrors.Wrap(types.ErrInvalidDeveloperSignature, err.Error())
+		}
 		return 0, sdkerrors.Wrap(types.ErrCreateFailed, err.Error())
 	}
+	if k.featureActive(ctx, types.FeatureIBCEntryPoints) {
+		report, err := k.wasmer.AnalyzeCode(codeHash)
+		if err == nil {
+			err = checkIbcEntryPoints(report)
+		}
+		if err != nil {
+			return 0, sdkerrors.Wrap(types.ErrCreateFailed, err.Error())
+		}
+	}
 	store := ctx.KVStore(k.storeKey)
 	codeID = k.autoIncrementID(ctx, types.KeyLastCodeID)
 
-	codeInfo := types.NewCodeInfo(codeHash, creator, source, builder)
+	codeInfo := types.NewCodeInfo(codeHash, creator, source, builder, developerPubKey)
 	// 0x01 | codeID (uint64) -> ContractInfo
 	store.Set(types.GetCodeKey(codeID), k.cdc.MustMarshal(&codeInfo))
 
@@ -404,6 +507,19 @@ func V010MsgsToV1SubMsgs(contractAddr string, msgs []v010wasmTypes.CosmosMsg) ([
 
 // Instantiate creates an instance of a WASM contract
 func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.AccAddress, initMsg []byte, label string, deposit sdk.Coins, callbackSig []byte) (sdk.AccAddress, []byte, error) {
+	return k.instantiate(ctx, codeID, creator, admin, initMsg, label, deposit, callbackSig, nil)
+}
+
+// Instantiate2 creates an instance of a WASM contract at an address derived from its code hash, its
+// creator and the salt, which can be computed before the contract exists with PredictableContractAddress
+func (k Keeper) Instantiate2(ctx sdk.Context, codeID uint64, creator, admin sdk.AccAddress, initMsg []byte, label string, deposit sdk.Coins, salt []byte, callbackSig []byte) (sdk.AccAddress, []byte, error) {
+	if len(salt) == 0 || len(salt) > types.MaxInstantiateSaltLength {
+		return nil, nil, sdkerrors.Wrapf(types.ErrInvalid, "salt must be 1 to %d bytes long", types.MaxInstantiateSaltLength)
+	}
+	return k.instantiate(ctx, codeID, creator, admin, initMsg, label, deposit, callbackSig, salt)
+}
+
+func (k Keeper) instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.AccAddress, initMsg []byte, label string, deposit sdk.Coins, callbackSig []byte, salt []byte) (sdk.AccAddress, []byte, error) {
 	defer telemetry.MeasureSince(time.Now(), "compute", "keeper", "instantiate")
 
 	ctx.GasMeter().ConsumeGas(types.InstanceCost, "Loading CosmWasm module: init")
@@ -434,12 +550,31 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 		return nil, nil, sdkerrors.Wrap(types.ErrAccountExists, label)
 	}
 
-	contractAddress := k.generateContractAddress(ctx, codeID, creator)
+	// get contact info
+	bz := store.Get(types.GetCodeKey(codeID))
+	if bz == nil {
+		return nil, nil, sdkerrors.Wrap(types.ErrNotFound, "code")
+	}
+	var codeInfo types.CodeInfo
+	k.cdc.MustUnmarshal(bz, &codeInfo)
+
+	var contractAddress sdk.AccAddress
+	if salt == nil {
+		contractAddress = k.generateContractAddress(ctx, codeID, creator)
+	} else {
+		// the instance counter still advances, as InitGenesis checks it against the number of contracts
+		k.autoIncrementID(ctx, types.KeyLastInstanceID)
+		contractAddress = PredictableContractAddress(codeInfo.CodeHash, creator, salt)
+	}
 	existingAcct := k.accountKeeper.GetAccount(ctx, contractAddress)
 	if existingAcct != nil {
 		return nil, nil, sdkerrors.Wrap(types.ErrAccountExists, existingAcct.GetAddress().String())
 	}
 
+	if err := k.checkFundsDenoms(ctx, deposit); err != nil {
+		return nil, nil, err
+	}
+
 	// deposit initial contract funds
 	if !deposit.IsZero() {
 		if k.bankKeeper.BlockedAddr(creator) {
@@ -456,18 +591,10 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 		k.accountKeeper.SetAccount(ctx, contractAccount)
 	}
 
-	// get contact info
-	bz := store.Get(types.GetCodeKey(codeID))
-	if bz == nil {
-		return nil, nil, sdkerrors.Wrap(types.ErrNotFound, "code")
-	}
-	var codeInfo types.CodeInfo
-	k.cdc.MustUnmarshal(bz, &codeInfo)
-
 	random := k.GetRandomSeed(ctx, ctx.BlockHeight())
 
 	// prepare env for contract instantiate call
-	env := types.NewEnv(ctx,
+	env := k.newEnv(ctx,
 		creator,
 		deposit,
 		contractAddress,
@@ -478,11 +605,12 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 		},
 		random,
 	)
+	// the enclave checks the address against the salt before binding the contract key to it
+	env.InstantiateSalt = salt
 
 	// create prefixed data store
 	// 0x03 | contractAddress (sdk.AccAddress)
-	prefixStoreKey := types.GetContractStorePrefixKey(contractAddress)
-	prefixStore := prefix.NewStore(ctx.KVStore(k.storeKey), prefixStoreKey)
+	prefixStore := k.contractStore(ctx, contractAddress)
 
 	// prepare querier
 	querier := QueryHandler{
@@ -491,8 +619,10 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 		Caller:  contractAddress,
 	}
 
-	response, ogContractKey, adminProof, gasUsed, initError := k.wasmer.Instantiate(codeInfo.CodeHash, env, initMsg, prefixStore, cosmwasmAPI, querier, ctx.GasMeter(), gasForContract(ctx), sigInfo, admin)
+	response, ogContractKey, adminProof, contractVersion, receipt, gasUsed, initError := k.wasmer.Instantiate(codeInfo.CodeHash, env, initMsg, prefixStore, cosmwasmAPI, querier, ctx.GasMeter(), gasForContract(ctx), sigInfo, admin)
 	consumeGas(ctx, gasUsed)
+	k.streamObservedEvents(ctx)
+	k.recordExecutionReceipt(ctx, receipt)
 
 	if initError != nil {
 		switch res := response.(type) { //nolint:gocritic
@@ -502,10 +632,10 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 				return nil, nil, sdkerrors.Wrap(jsonError, "couldn't marshal internal reply info")
 			}
 
-			return contractAddress, result, sdkerrors.Wrap(types.ErrInstantiateFailed, initError.Error())
+			return contractAddress, result, contractError(contractAddress, types.ErrInstantiateFailed, initError)
 		}
 
-		return contractAddress, nil, sdkerrors.Wrap(types.ErrInstantiateFailed, initError.Error())
+		return contractAddress, nil, contractError(contractAddress, types.ErrInstantiateFailed, initError)
 	}
 
 	switch res := response.(type) {
@@ -522,6 +652,9 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 		k.appendToContractHistory(ctx, contractAddress, historyEntry)
 
 		k.setContractInfo(ctx, contractAddress, &contractInfo)
+		if k.featureActive(ctx, types.FeatureContractVersion) {
+			k.setContractVersion(ctx, contractAddress, contractVersion)
+		}
 		k.SetContractKey(ctx, contractAddress, &types.ContractKey{
 			OgContractKey:           ogContractKey,
 			CurrentContractKey:      nil,
@@ -557,6 +690,7 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 				return nil, nil, err
 			}
 			contractInfo.IBCPortID = ibcPort
+			contractInfo.IBCEnabled = true
 		}
 
 		ctx.EventManager().EmitEvent(sdk.NewEvent(
@@ -572,6 +706,9 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 
 		// persist instance
 		k.setContractInfo(ctx, contractAddress, &contractInfo)
+		if k.featureActive(ctx, types.FeatureContractVersion) {
+			k.setContractVersion(ctx, contractAddress, contractVersion)
+		}
 		k.SetContractKey(ctx, contractAddress, &types.ContractKey{
 			OgContractKey:           ogContractKey,
 			CurrentContractKey:      nil,
@@ -592,6 +729,17 @@ func (k Keeper) Instantiate(ctx sdk.Context, codeID uint64, creator, admin sdk.A
 
 // Execute executes the contract instance
 func (k Keeper) Execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller sdk.AccAddress, msg []byte, coins sdk.Coins, callbackSig []byte, handleType wasmTypes.HandleType) (*sdk.Result, error) {
+	return k.execute(ctx, contractAddress, caller, msg, coins, callbackSig, handleType, nil)
+}
+
+// ExecuteWithAccessList executes the contract like Execute, failing if the contract touches a key of its
+// store outside the prefixes accessList declares. The enclave checks the keys before encrypting them. Only the
+// contract itself is constrained, not the messages it dispatches.
+func (k Keeper) ExecuteWithAccessList(ctx sdk.Context, contractAddress sdk.AccAddress, caller sdk.AccAddress, msg []byte, coins sdk.Coins, callbackSig []byte, accessList types.AccessList) (*sdk.Result, error) {
+	return k.execute(ctx, contractAddress, caller, msg, coins, callbackSig, wasmTypes.HandleTypeExecute, &accessList)
+}
+
+func (k Keeper) execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller sdk.AccAddress, msg []byte, coins sdk.Coins, callbackSig []byte, handleType wasmTypes.HandleType, accessList *types.AccessList) (*sdk.Result, error) {
 	defer telemetry.MeasureSince(time.Now(), "compute", "keeper", "execute")
 
 	ctx.GasMeter().ConsumeGas(types.InstanceCost, "Loading Compute module: execute")
@@ -618,6 +766,10 @@ func (k Keeper) Execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 		return nil, err
 	}
 
+	if err := k.checkFundsDenoms(ctx, coins); err != nil {
+		return nil, err
+	}
+
 	// add more funds
 	if !coins.IsZero() {
 		if k.bankKeeper.BlockedAddr(caller) {
@@ -637,7 +789,10 @@ func (k Keeper) Execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 		return nil, err
 	}
 
-	env := types.NewEnv(ctx, caller, coins, contractAddress, contractKey, random)
+	env := k.newEnv(ctx, caller, coins, contractAddress, contractKey, random)
+	if accessList != nil {
+		env.AccessList = accessList.Prefixes()
+	}
 
 	// prepare querier
 	querier := QueryHandler{
@@ -646,8 +801,10 @@ func (k Keeper) Execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 		Caller:  contractAddress,
 	}
 
-	response, gasUsed, execErr := k.wasmer.Execute(codeInfo.CodeHash, env, msg, prefixStore, cosmwasmAPI, querier, gasMeter(ctx), gasForContract(ctx), sigInfo, handleType)
+	response, receipt, gasUsed, execErr := k.wasmer.Execute(codeInfo.CodeHash, env, msg, prefixStore, cosmwasmAPI, querier, gasMeter(ctx), gasForContract(ctx), sigInfo, handleType)
 	consumeGas(ctx, gasUsed)
+	k.streamObservedEvents(ctx)
+	k.recordExecutionReceipt(ctx, receipt)
 
 	if execErr != nil {
 		var result sdk.Result
@@ -660,7 +817,7 @@ func (k Keeper) Execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 			}
 		}
 
-		return &result, sdkerrors.Wrap(types.ErrExecuteFailed, execErr.Error())
+		return &result, contractError(contractAddress, types.ErrExecuteFailed, execErr)
 	}
 
 	switch res := response.(type) {
@@ -699,15 +856,23 @@ func (k Keeper) Execute(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 
 // QuerySmart queries the smart contract itself.
 func (k Keeper) QuerySmart(ctx sdk.Context, contractAddr sdk.AccAddress, req []byte, useDefaultGasLimit bool) ([]byte, error) {
+	res, _, err := k.querySmartImpl(ctx, contractAddr, req, useDefaultGasLimit, 1)
+	return res, err
+}
+
+// QuerySmartSigned queries the smart contract itself, and also returns the enclave's signature of the response.
+// The signature is nil unless the node enabled signing query responses.
+func (k Keeper) QuerySmartSigned(ctx sdk.Context, contractAddr sdk.AccAddress, req []byte, useDefaultGasLimit bool) ([]byte, *wasmTypes.QueryResponseSignature, error) {
 	return k.querySmartImpl(ctx, contractAddr, req, useDefaultGasLimit, 1)
 }
 
 // QuerySmartRecursive queries the smart contract itself. This should only be called when running inside another query recursively.
 func (k Keeper) querySmartRecursive(ctx sdk.Context, contractAddr sdk.AccAddress, req []byte, queryDepth uint32, useDefaultGasLimit bool) ([]byte, error) {
-	return k.querySmartImpl(ctx, contractAddr, req, useDefaultGasLimit, queryDepth)
+	res, _, err := k.querySmartImpl(ctx, contractAddr, req, useDefaultGasLimit, queryDepth)
+	return res, err
 }
 
-func (k Keeper) querySmartImpl(ctx sdk.Context, contractAddress sdk.AccAddress, req []byte, useDefaultGasLimit bool, queryDepth uint32) ([]byte, error) {
+func (k Keeper) querySmartImpl(ctx sdk.Context, contractAddress sdk.AccAddress, req []byte, useDefaultGasLimit bool, queryDepth uint32) ([]byte, *wasmTypes.QueryResponseSignature, error) {
 	defer telemetry.MeasureSince(time.Now(), "compute", "keeper", "query")
 
 	if useDefaultGasLimit {
@@ -718,7 +883,7 @@ func (k Keeper) querySmartImpl(ctx sdk.Context, contractAddress sdk.AccAddress,
 
 	_, codeInfo, prefixStore, err := k.contractInstance(ctx, contractAddress)
 	if err != nil {
-		return nil, err
+		return nil, nil, err
 	}
 
 	// prepare querier
@@ -730,10 +895,10 @@ func (k Keeper) querySmartImpl(ctx sdk.Context, contractAddress sdk.AccAddress,
 
 	contractKey, err := k.GetContractKey(ctx, contractAddress)
 	if err != nil {
-		return nil, err
+		return nil, nil, err
 	}
 
-	params := types.NewEnv(
+	params := k.newEnv(
 		ctx,
 		sdk.AccAddress{}, /* empty because it's unused in queries */
 		sdk.NewCoins(),   /* empty because it's unused in queries */
@@ -743,15 +908,85 @@ func (k Keeper) querySmartImpl(ctx sdk.Context, contractAddress sdk.AccAddress,
 	)
 	params.QueryDepth = queryDepth
 
-	queryResult, gasUsed, qErr := k.wasmer.Query(codeInfo.CodeHash, params, req, prefixStore, cosmwasmAPI, querier, gasMeter(ctx), gasForContract(ctx))
+	queryResult, signature, gasUsed, qErr := k.wasmer.Query(codeInfo.CodeHash, params, req, prefixStore, cosmwasmAPI, querier, gasMeter(ctx), gasForContract(ctx))
 	consumeGas(ctx, gasUsed)
 
 	telemetry.SetGauge(float32(gasUsed), "compute", "keeper", "query", contractAddress.String(), "gasUsed")
 
 	if qErr != nil {
-		return nil, sdkerrors.Wrap(types.ErrQueryFailed, qErr.Error())
+		return nil, nil, sdkerrors.Wrap(types.ErrQueryFailed, qErr.Error())
 	}
-	return queryResult, nil
+	return queryResult, signature, nil
+}
+
+// RewrapPreviousCodeQuery has the enclave re-wrap a query addressed to the code a contract ran
+// before a migration in the last QueryRewrapGraceBlocks blocks, for its current code. Clients that
+// built the query before they saw the migration keep working, if they opted in inside the
+// encrypted query. Other queries are returned as is.
+func (k Keeper) RewrapPreviousCodeQuery(ctx sdk.Context, contractAddress sdk.AccAddress, req []byte) []byte {
+	graceBlocks := k.GetParams(ctx).QueryRewrapGraceBlocks
+	if graceBlocks == 0 {
+		return req
+	}
+
+	history := k.GetContractHistory(ctx, contractAddress)
+	if len(history) < 2 {
+		return req
+	}
+
+	// the enclave checks the window too, this only spares the call
+	migration := history[len(history)-1]
+	if migration.Operation != types.ContractCodeHistoryOperationTypeMigrate || migration.Updated == nil ||
+		uint64(ctx.BlockHeight()-migration.Updated.BlockHeight) > graceBlocks {
+		return req
+	}
+
+	contractInfo := k.GetContractInfo(ctx, contractAddress)
+	if contractInfo == nil {
+		return req
+	}
+	codeInfo, err := k.GetCodeInfo(ctx, contractInfo.CodeID)
+	if err != nil {
+		return req
+	}
+	codeHash := codeInfo.CodeHash
+
+	contractKey, err := k.GetContractKey(ctx, contractAddress)
+	if err != nil {
+		return req
+	}
+	previousKey, migrator, err := k.getPreviousContractKey(ctx, contractAddress)
+	if err != nil {
+		// the contract was migrated before QueryRewrapHeight
+		return req
+	}
+	previousCodeInfo, err := k.GetCodeInfo(ctx, history[len(history)-2].CodeID)
+	if err != nil || bytes.Equal(previousCodeInfo.CodeHash, codeHash) {
+		return req
+	}
+
+	rewrapped, err := k.wasmer.RewrapQuery(
+		contractAddress,
+		previousCodeInfo.CodeHash,
+		previousKey.OgContractKey,
+		previousKey.CurrentContractKey,
+		previousKey.CurrentContractKeyProof,
+		codeHash,
+		contractKey.CurrentContractKey,
+		contractKey.CurrentContractKeyProof,
+		migrator,
+		uint64(migration.Updated.BlockHeight),
+		uint64(ctx.BlockHeight()),
+		graceBlocks,
+		req,
+	)
+	if err != nil {
+		// most queries are addressed to the current code already
+		return req
+	}
+
+	moduleLogger(ctx).Debug("re-wrapped query for the previous code of a contract", "contract", contractAddress.String())
+	return rewrapped
 }
 
 // We don't use this function since we have an encrypted state. It's here for upstream compatibility
@@ -773,25 +1008,23 @@ func (k Keeper) QueryRaw(ctx sdk.Context, contractAddress sdk.AccAddress, key []
 	return result
 }
 
-func (k Keeper) contractInstance(ctx sdk.Context, contractAddress sdk.AccAddress) (types.ContractInfo, types.CodeInfo, prefix.Store, error) {
+func (k Keeper) contractInstance(ctx sdk.Context, contractAddress sdk.AccAddress) (types.ContractInfo, types.CodeInfo, sdk.KVStore, error) {
 	store := ctx.KVStore(k.storeKey)
 
 	contractBz := store.Get(types.GetContractAddressKey(contractAddress))
 	if contractBz == nil {
-		return types.ContractInfo{}, types.CodeInfo{}, prefix.Store{}, sdkerrors.Wrap(types.ErrNotFound, "contract")
+		return types.ContractInfo{}, types.CodeInfo{}, nil, sdkerrors.Wrap(types.ErrNotFound, "contract")
 	}
 	var contract types.ContractInfo
 	k.cdc.MustUnmarshal(contractBz, &contract)
 
 	contractInfoBz := store.Get(types.GetCodeKey(contract.CodeID))
 	if contractInfoBz == nil {
-		return types.ContractInfo{}, types.CodeInfo{}, prefix.Store{}, sdkerrors.Wrap(types.ErrNotFound, "contract info")
+		return types.ContractInfo{}, types.CodeInfo{}, nil, sdkerrors.Wrap(types.ErrNotFound, "contract info")
 	}
 	var codeInfo types.CodeInfo
 	k.cdc.MustUnmarshal(contractInfoBz, &codeInfo)
-	prefixStoreKey := types.GetContractStorePrefixKey(contractAddress)
-	prefixStore := prefix.NewStore(ctx.KVStore(k.storeKey), prefixStoreKey)
-	return contract, codeInfo, prefixStore, nil
+	return contract, codeInfo, k.contractStore(ctx, contractAddress), nil
 }
 
 func (k Keeper) GetContractKey(ctx sdk.Context, contractAddress sdk.AccAddress) (types.ContractKey, error) {
@@ -819,6 +1052,32 @@ func (k Keeper) SetContractKey(ctx sdk.Context, contractAddress sdk.AccAddress,
 	store.Set(types.GetContractEnclaveKey(contractAddress), contractKeyBz)
 }
 
+// setPreviousContractKey keeps the keys a contract had before a migration and the address that
+// migrated it, so that the enclave can check queries addressed to its previous code and the
+// height of the migration.
+func (k Keeper) setPreviousContractKey(ctx sdk.Context, contractAddress sdk.AccAddress, contractKey *types.ContractKey, migrator sdk.AccAddress) {
+	store := ctx.KVStore(k.storeKey)
+
+	contractKeyBz := k.cdc.MustMarshal(contractKey)
+	store.Set(types.GetPreviousContractEnclaveKey(contractAddress), contractKeyBz)
+	store.Set(types.GetContractMigratorKey(contractAddress), migrator)
+}
+
+func (k Keeper) getPreviousContractKey(ctx sdk.Context, contractAddress sdk.AccAddress) (types.ContractKey, sdk.AccAddress, error) {
+	store := ctx.KVStore(k.storeKey)
+
+	var contractKey types.ContractKey
+	contractKeyBz := store.Get(types.GetPreviousContractEnclaveKey(contractAddress))
+	migrator := store.Get(types.GetContractMigratorKey(contractAddress))
+
+	if contractKeyBz == nil || migrator == nil {
+		return types.ContractKey{}, nil, sdkerrors.Wrap(types.ErrNotFound, "previous contract key")
+	}
+
+	err := k.cdc.Unmarshal(contractKeyBz, &contractKey)
+	return contractKey, migrator, err
+}
+
 func (k Keeper) GetRandomSeed(ctx sdk.Context, height int64) []byte {
 	store := ctx.KVStore(k.storeKey)
 
@@ -860,6 +1119,75 @@ func (k Keeper) GetContractHash(ctx sdk.Context, contractAddress sdk.AccAddress)
 	return codeInfo.CodeHash, nil
 }
 
+// IntrospectContract returns the public information of a contract without running it, so it consumes no gas.
+// Wallets and explorers use it to discover what a contract supports.
+func (k Keeper) IntrospectContract(ctx sdk.Context, contractAddress sdk.AccAddress) (*types.ContractIntrospection, error) {
+	codeHash, err := k.GetContractHash(ctx, contractAddress)
+	if err != nil {
+		return nil, err
+	}
+
+	report, err := k.wasmer.Introspect(codeHash)
+	if err != nil {
+		return nil, err
+	}
+
+	snipStandards := []string{}
+	if report.SnipStandards != "" {
+		snipStandards = strings.Split(report.SnipStandards, ",")
+	}
+
+	return &types.ContractIntrospection{
+		CodeHash:         hex.EncodeToString(codeHash),
+		InterfaceVersion: report.InterfaceVersion,
+		IBCEnabled:       report.HasIBCEntryPoints,
+		SnipStandards:    snipStandards,
+	}, nil
+}
+
+// ProveContractKey returns a proof, signed by the enclave of this node, that the contract key and the
+// encryption public key of a contract were derived by a genuine enclave for its code. Clients that get
+// the proof from a node they don't trust verify it against the node's attestation certificate.
+func (k Keeper) ProveContractKey(ctx sdk.Context, contractAddress sdk.AccAddress) (*wasmTypes.ContractKeyProof, error) {
+	codeHash, err := k.GetContractHash(ctx, contractAddress)
+	if err != nil {
+		return nil, err
+	}
+
+	contractKey, err := k.GetContractKey(ctx, contractAddress)
+	if err != nil {
+		return nil, err
+	}
+
+	return k.wasmer.ProveContractKey(
+		contractAddress,
+		codeHash,
+		contractKey.OgContractKey,
+		contractKey.CurrentContractKey,
+		contractKey.CurrentContractKeyProof,
+	)
+}
+
+// ConsensusConfigHash returns the hash of the config all the nodes of the network must share, for
+// operators to compare with the one recorded in the params
+func (k Keeper) ConsensusConfigHash() []byte {
+	return k.consensusConfigHash
+}
+
+// VmCapabilities describes the contracts this node accepts and what it offers them, for clients and
+// contract tooling to adapt to the node. It depends on the configuration and enclave of the node,
+// not on the state of the chain.
+func (k Keeper) VmCapabilities() (*wasmTypes.VmCapabilities, error) {
+	return k.wasmer.VmCapabilities()
+}
+
+// RevalidateStoredCode reports the stored contracts that would no longer validate with the given
+// supported features and validation config, for planning upgrades that change them. It is a
+// maintenance entry point reading every stored contract, not reachable from queries.
+func (k Keeper) RevalidateStoredCode(supportedFeatures string, validationConfig *wasmTypes.ValidationConfig) (*wasmTypes.RevalidationReport, error) {
+	return k.wasmer.RevalidateStoredCode(supportedFeatures, validationConfig)
+}
+
 func (k Keeper) GetContractInfo(ctx sdk.Context, contractAddress sdk.AccAddress) *types.ContractInfo {
 	store := ctx.KVStore(k.storeKey)
 	var contract types.ContractInfo
@@ -922,8 +1250,7 @@ func (k Keeper) GetContractState(ctx sdk.Context, contractAddress sdk.AccAddress
 }
 
 func (k Keeper) importContractState(ctx sdk.Context, contractAddress sdk.AccAddress, models []types.Model) error {
-	prefixStoreKey := types.GetContractStorePrefixKey(contractAddress)
-	prefixStore := prefix.NewStore(ctx.KVStore(k.storeKey), prefixStoreKey)
+	prefixStore := k.contractStore(ctx, contractAddress)
 	for _, model := range models {
 		if model.Value == nil {
 			model.Value = []byte{}
@@ -1029,6 +1356,9 @@ func consumeGas(ctx sdk.Context, gas uint64) {
 	}
 }
 
+// separates predictable contract addresses from other hashes
+const predictableAddressDomain = "secret_instantiate2"
+
 // generates a contract address from codeID + instanceID
 func (k Keeper) generateContractAddress(ctx sdk.Context, codeID uint64, creator sdk.AccAddress) sdk.AccAddress {
 	instanceID := k.autoIncrementID(ctx, types.KeyLastInstanceID)
@@ -1048,6 +1378,22 @@ func contractAddress(codeID, instanceID uint64, creator sdk.AccAddress) sdk.AccA
 	return sdk.AccAddress(hasherRIPEMD160.Sum(nil))
 }
 
+// PredictableContractAddress returns the address of a contract instantiated with Instantiate2. It is
+// the first 20 bytes of the sha256 of "secret_instantiate2" followed by the code hash, the creator
+// and the salt, each prefixed with its length as a big endian uint64. The enclave derives it the same way.
+func PredictableContractAddress(codeHash []byte, creator sdk.AccAddress, salt []byte) sdk.AccAddress {
+	hashSourceBytes := []byte(predictableAddressDomain)
+	for _, part := range [][]byte{codeHash, creator, salt} {
+		length := make([]byte, 8)
+		binary.BigEndian.PutUint64(length, uint64(len(part)))
+		hashSourceBytes = append(hashSourceBytes, length...)
+		hashSourceBytes = append(hashSourceBytes, part...)
+	}
+
+	sha := sha256.Sum256(hashSourceBytes)
+	return sdk.AccAddress(sha[:20])
+}
+
 func (k Keeper) GetNextCodeID(ctx sdk.Context) uint64 {
 	store := ctx.KVStore(k.storeKey)
 	bz := store.Get(types.KeyLastCodeID)
@@ -1167,7 +1513,7 @@ func (k Keeper) reply(ctx sdk.Context, contractAddress sdk.AccAddress, reply v1w
 
 	random := k.GetRandomSeed(ctx, ctx.BlockHeight())
 
-	env := types.NewEnv(ctx, contractAddress, sdk.Coins{}, contractAddress, contractKey, random)
+	env := k.newEnv(ctx, contractAddress, sdk.Coins{}, contractAddress, contractKey, random)
 
 	// prepare querier
 	querier := QueryHandler{
@@ -1183,11 +1529,13 @@ func (k Keeper) reply(ctx sdk.Context, contractAddress sdk.AccAddress, reply v1w
 		return nil, err
 	}
 
-	response, gasUsed, execErr := k.wasmer.Execute(codeInfo.CodeHash, env, marshaledReply, prefixStore, cosmwasmAPI, querier, ctx.GasMeter(), gasForContract(ctx), ogSigInfo, wasmTypes.HandleTypeReply)
+	response, receipt, gasUsed, execErr := k.wasmer.Execute(codeInfo.CodeHash, env, marshaledReply, prefixStore, cosmwasmAPI, querier, ctx.GasMeter(), gasForContract(ctx), ogSigInfo, wasmTypes.HandleTypeReply)
 	consumeGas(ctx, gasUsed)
+	k.streamObservedEvents(ctx)
+	k.recordExecutionReceipt(ctx, receipt)
 
 	if execErr != nil {
-		return nil, sdkerrors.Wrap(types.ErrReplyFailed, execErr.Error())
+		return nil, contractError(contractAddress, types.ErrReplyFailed, execErr)
 	}
 
 	switch res := response.(type) {
@@ -1245,7 +1593,7 @@ func (k Keeper) UpdateContractAdmin(ctx sdk.Context, contractAddress, caller, ne
 		return err
 	}
 
-	env := types.NewEnv(ctx, caller, sdk.Coins{}, contractAddress, contractKey, nil)
+	env := k.newEnv(ctx, caller, sdk.Coins{}, contractAddress, contractKey, nil)
 
 	currentAdminAddress, err := sdk.AccAddressFromBech32(contractInfo.Admin)
 	if err != nil {
@@ -1315,7 +1663,8 @@ func (k Keeper) Migrate(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 	}
 
 	// check for IBC flag
-	switch report, err := k.wasmer.AnalyzeCode(newCodeInfo.CodeHash); {
+	report, err := k.wasmer.AnalyzeCode(newCodeInfo.CodeHash)
+	switch {
 	case err != nil:
 		return nil, sdkerrors.Wrap(types.ErrMigrationFailed, err.Error())
 	case !report.HasIBCEntryPoints && contractInfo.IBCPortID != "":
@@ -1329,6 +1678,7 @@ func (k Keeper) Migrate(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 		}
 		contractInfo.IBCPortID = ibcPort
 	}
+	contractInfo.IBCEnabled = report.HasIBCEntryPoints
 
 	contractKey, err := k.GetContractKey(ctx, contractAddress)
 	if err != nil {
@@ -1341,7 +1691,7 @@ func (k Keeper) Migrate(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 
 	random := k.GetRandomSeed(ctx, ctx.BlockHeight())
 
-	env := types.NewEnv(ctx, caller, sdk.Coins{}, contractAddress, contractKey, random)
+	env := k.newEnv(ctx, caller, sdk.Coins{}, contractAddress, contractKey, random)
 
 	adminProof := contractInfo.AdminProof
 	admin := contractInfo.Admin
@@ -1358,8 +1708,9 @@ func (k Keeper) Migrate(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 		Caller:  contractAddress,
 	}
 
-	response, newContractKey, newContractKeyProof, gasUsed, migrateErr := k.wasmer.Migrate(newCodeInfo.CodeHash, env, msg, prefixStore, cosmwasmAPI, querier, gasMeter(ctx), gasForContract(ctx), sigInfo, adminAddr, adminProof)
+	response, newContractKey, newContractKeyProof, contractVersion, receipt, gasUsed, migrateErr := k.wasmer.Migrate(newCodeInfo.CodeHash, env, msg, prefixStore, cosmwasmAPI, querier, gasMeter(ctx), gasForContract(ctx), sigInfo, adminAddr, adminProof)
 	consumeGas(ctx, gasUsed)
+	k.recordExecutionReceipt(ctx, receipt)
 
 	if migrateErr != nil {
 		var result []byte
@@ -1372,10 +1723,13 @@ func (k Keeper) Migrate(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 			}
 		}
 
-		return result, sdkerrors.Wrap(types.ErrMigrationFailed, migrateErr.Error())
+		return result, contractError(contractAddress, types.ErrMigrationFailed, migrateErr)
 	}
 
 	// update contract key with new one
+	if k.featureActive(ctx, types.FeatureQueryRewrap) {
+		k.setPreviousContractKey(ctx, contractAddress, &contractKey, caller)
+	}
 	k.SetContractKey(ctx, contractAddress, &types.ContractKey{
 		OgContractKey:           contractKey.OgContractKey,
 		CurrentContractKey:      newContractKey,
@@ -1391,6 +1745,9 @@ func (k Keeper) Migrate(ctx sdk.Context, contractAddress sdk.AccAddress, caller
 
 	contractInfo.CodeID = newCodeID
 	k.setContractInfo(ctx, contractAddress, &contractInfo)
+	if k.featureActive(ctx, types.FeatureContractVersion) {
+		k.setContractVersion(ctx, contractAddress, contractVersion)
+	}
 
 	ctx.EventManager().EmitEvent(sdk.NewEvent(
 		types.EventTypeMigrate,
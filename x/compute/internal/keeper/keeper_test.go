@@ -1,6 +1,7 @@
 package keeper
 
 import (
+	"bytes"
 	"crypto/sha1"
 	"encoding/base64"
 	"encoding/hex"
@@ -14,8 +15,10 @@ import (
 	"github.com/stretchr/testify/assert"
 	"github.com/stretchr/testify/require"
 
+	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
 	stypes "github.com/cosmos/cosmos-sdk/store/types"
 	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
 	authante "github.com/cosmos/cosmos-sdk/x/auth/ante"
 	"github.com/scrtlabs/SecretNetwork/go-cosmwasm/api"
 	wasmtypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
@@ -72,6 +75,24 @@ func TestNewKeeper(t *testing.T) {
 	require.NotNil(t, keepers.WasmKeeper)
 }
 
+func TestVerifyConsensusConfigHash(t *testing.T) {
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, nil, nil)
+	keeper := keepers.WasmKeeper
+
+	t.Log("nothing recorded yet")
+	require.NoError(t, keeper.VerifyConsensusConfigHash(ctx))
+
+	keeper.RecordConsensusConfigHash(ctx)
+	require.Equal(t, keeper.ConsensusConfigHash(), keeper.GetParams(ctx).ConsensusConfigHash)
+	require.NoError(t, keeper.VerifyConsensusConfigHash(ctx))
+
+	t.Log("the network runs with another consensus config")
+	params := keeper.GetParams(ctx)
+	params.ConsensusConfigHash = types.ConsensusHash("staking")
+	keeper.SetParams(ctx, params)
+	require.Error(t, keeper.VerifyConsensusConfigHash(ctx))
+}
+
 func TestCreate(t *testing.T) {
 	encodingConfig := MakeEncodingConfig()
 	var transferPortSource types.ICS20TransferPortSource
@@ -194,6 +215,66 @@ func TestIsSimulationMode(t *testing.T) {
 	}
 }
 
+func TestCreateSigned(t *testing.T) {
+	encodingConfig := MakeEncodingConfig()
+	var transferPortSource types.ICS20TransferPortSource
+	transferPortSource = MockIBCTransferKeeper{GetPortFn: func(ctx sdk.Context) string {
+		return "myTransferPort"
+	}}
+	encoders := DefaultEncoders(transferPortSource, encodingConfig.Marshaler)
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, &encoders, nil)
+	accKeeper, keeper := keepers.AccountKeeper, keepers.WasmKeeper
+
+	deposit := sdk.NewCoins(sdk.NewInt64Coin("denom", 100000))
+	creator, _ := CreateFakeFundedAccount(ctx, accKeeper, keeper.bankKeeper, deposit)
+
+	wasmCode, err := os.ReadFile(TestContractPaths[hackAtomContract])
+	require.NoError(t, err)
+
+	developerKey := secp256k1.GenPrivKey()
+	signature, err := developerKey.Sign(wasmCode)
+	require.NoError(t, err)
+	otherSignature, err := secp256k1.GenPrivKey().Sign(wasmCode)
+	require.NoError(t, err)
+
+	// not enabled yet
+	_, err = keeper.CreateSigned(ctx, creator, wasmCode, "", "", developerKey.PubKey().Bytes(), signature)
+	require.True(t, types.ErrInvalid.Is(err), err)
+
+	params := keeper.GetParams(ctx)
+	params.DeveloperSignatureHeight = ctx.BlockHeight()
+	keeper.SetParams(ctx, params)
+
+	// signed by another key
+	_, err = keeper.CreateSigned(ctx, creator, wasmCode, "", "", developerKey.PubKey().Bytes(), otherSignature)
+	require.True(t, types.ErrInvalidDeveloperSignature.Is(err), err)
+
+	// the signature is charged for
+	gasBefore := ctx.GasMeter().GasConsumed()
+	_, err = keeper.Create(ctx, creator, wasmCode, "", "")
+	require.NoError(t, err)
+	unsignedGas := ctx.GasMeter().GasConsumed() - gasBefore
+	gasBefore = ctx.GasMeter().GasConsumed()
+	_, err = keeper.CreateSigned(ctx, creator, wasmCode, "", "", developerKey.PubKey().Bytes(), signature)
+	require.NoError(t, err)
+	require.Greater(t, ctx.GasMeter().GasConsumed()-gasBefore, unsignedGas)
+
+	// the signature is over the raw code, whether it is uploaded compressed or not
+	gzippedCode, err := wasmUtils.GzipIt(wasmCode)
+	require.NoError(t, err)
+	codeID, err := keeper.CreateSigned(ctx, creator, gzippedCode, "", "", developerKey.PubKey().Bytes(), signature)
+	require.NoError(t, err)
+	codeInfo, err := keeper.GetCodeInfo(ctx, codeID)
+	require.NoError(t, err)
+	require.Equal(t, developerKey.PubKey().Bytes(), codeInfo.DeveloperPubKey)
+
+	codeID, err = keeper.Create(ctx, creator, wasmCode, "", "")
+	require.NoError(t, err)
+	codeInfo, err = keeper.GetCodeInfo(ctx, codeID)
+	require.NoError(t, err)
+	require.Empty(t, codeInfo.DeveloperPubKey)
+}
+
 func TestCreateWithGzippedPayload(t *testing.T) {
 	encodingConfig := MakeEncodingConfig()
 	var transferPortSource types.ICS20TransferPortSource
@@ -418,6 +499,38 @@ func TestInstantiateWithNonExistingCodeID(t *testing.T) {
 	require.Nil(t, addr)
 }
 
+func TestPredictableContractAddress(t *testing.T) {
+	codeHash := bytes.Repeat([]byte{1}, 32)
+	creator := sdk.AccAddress(bytes.Repeat([]byte{2}, 20))
+
+	addr := PredictableContractAddress(codeHash, creator, []byte("salt"))
+	// must match the address derived by the enclave for the same inputs
+	require.Equal(t, "a7c05addb72eefb6e2ab807e6335a76426c2eb79", hex.EncodeToString(addr))
+
+	require.NotEqual(t, addr, PredictableContractAddress(codeHash, creator, []byte("other salt")))
+	require.NotEqual(t, addr, PredictableContractAddress(bytes.Repeat([]byte{3}, 32), creator, []byte("salt")))
+}
+
+func TestInstantiate2WithInvalidSalt(t *testing.T) {
+	encodingConfig := MakeEncodingConfig()
+	var transferPortSource types.ICS20TransferPortSource
+	transferPortSource = MockIBCTransferKeeper{GetPortFn: func(ctx sdk.Context) string {
+		return "myTransferPort"
+	}}
+	encoders := DefaultEncoders(transferPortSource, encodingConfig.Marshaler)
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, &encoders, nil)
+	accKeeper, keeper := keepers.AccountKeeper, keepers.WasmKeeper
+
+	deposit := sdk.NewCoins(sdk.NewInt64Coin("denom", 100000))
+	creator, _ := CreateFakeFundedAccount(ctx, accKeeper, keeper.bankKeeper, deposit)
+
+	for _, salt := range [][]byte{{}, bytes.Repeat([]byte{1}, types.MaxInstantiateSaltLength+1)} {
+		addr, _, err := keeper.Instantiate2(ctx, 1, creator, nil, []byte{}, "demo contract", nil, salt, nil)
+		require.True(t, types.ErrInvalid.Is(err), err)
+		require.Nil(t, addr)
+	}
+}
+
 func TestExecute(t *testing.T) {
 	encodingConfig := MakeEncodingConfig()
 	var transferPortSource types.ICS20TransferPortSource
@@ -941,3 +1054,44 @@ type InstantiateMsg struct {
 	Counter uint64 `json:"counter"`
 	Expires uint64 `json:"expires"`
 }
+
+func TestCheckFundsDenoms(t *testing.T) {
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, nil, nil)
+	keeper := keepers.WasmKeeper
+
+	specs := map[string]struct {
+		allowlist []string
+		funds     sdk.Coins
+		expError  bool
+	}{
+		"no allowlist": {
+			funds: sdk.NewCoins(sdk.NewInt64Coin("denom", 1)),
+		},
+		"allowed denoms": {
+			allowlist: []string{"denom", "uscrt"},
+			funds:     sdk.NewCoins(sdk.NewInt64Coin("denom", 1), sdk.NewInt64Coin("uscrt", 1)),
+		},
+		"no funds": {
+			allowlist: []string{"uscrt"},
+		},
+		"denom not allowed": {
+			allowlist: []string{"uscrt"},
+			funds:     sdk.NewCoins(sdk.NewInt64Coin("denom", 1), sdk.NewInt64Coin("uscrt", 1)),
+			expError:  true,
+		},
+	}
+	for msg, spec := range specs {
+		t.Run(msg, func(t *testing.T) {
+			params := keeper.GetParams(ctx)
+			params.FundsDenomAllowlist = spec.allowlist
+			keeper.SetParams(ctx, params)
+
+			err := keeper.checkFundsDenoms(ctx, spec.funds)
+			if spec.expError {
+				require.True(t, sdkerrors.ErrInvalidCoins.Is(err), err)
+				return
+			}
+			require.NoError(t, err)
+		})
+	}
+}
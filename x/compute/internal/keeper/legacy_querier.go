@@ -1,6 +1,7 @@
 package keeper
 
 import (
+	"encoding/hex"
 	"encoding/json"
 	"fmt"
 	"reflect"
@@ -13,15 +14,26 @@ import (
 )
 
 const (
-	QueryListContractByCode   = "list-contracts-by-code"
-	QueryGetContract          = "contract-info"
-	QueryGetContractState     = "contract-state"
-	QueryGetCode              = "code"
-	QueryListCode             = "list-code"
-	QueryContractAddress      = "label"
-	QueryContractKey          = "contract-key"
-	QueryContractHash         = "contract-hash"
-	QueryContractHashByCodeID = "contract-hash-by-id"
+	QueryListContractByCode     = "list-contracts-by-code"
+	QueryGetContract            = "contract-info"
+	QueryGetContractState       = "contract-state"
+	QueryGetCode                = "code"
+	QueryListCode               = "list-code"
+	QueryContractAddress        = "label"
+	QueryContractKey            = "contract-key"
+	QueryContractHash           = "contract-hash"
+	QueryContractHashByCodeID   = "contract-hash-by-id"
+	QueryContractIntrospect     = "contract-introspect"
+	QueryContractStateSigned    = "contract-state-signed"
+	QueryPredictContractAddress = "predict-contract-address"
+	QueryContractKeyProof       = "contract-key-proof"
+	QueryContractStorageStats   = "contract-storage-stats"
+	QueryVmCapabilities         = "vm-capabilities"
+	QueryConsensusConfigHash    = "consensus-config-hash"
+	QueryContractPanics         = "contract-panics"
+	QueryContractVersion        = "contract-version"
+	QueryContractInstanceStats  = "contract-instance-stats"
+	QueryEnclaveModuleCache     = "enclave-module-cache"
 )
 
 const QueryMethodContractStateSmart = "smart"
@@ -58,6 +70,11 @@ func NewLegacyQuerier(keeper Keeper) sdk.Querier {
 				return nil, sdkerrors.Wrap(sdkerrors.ErrUnknownRequest, fmt.Sprintf("%s too few arguments (wanted at least 2): %v", QueryGetContractState, path))
 			}
 			return queryContractState(ctx, path[1], "unused" /* path[2] */, req.Data, keeper)
+		case QueryContractStateSigned:
+			if len(path) < 2 {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrUnknownRequest, fmt.Sprintf("%s too few arguments (wanted at least 2): %v", QueryContractStateSigned, path))
+			}
+			rsp, err = queryContractStateSigned(ctx, path[1], req.Data, keeper)
 		case QueryGetCode:
 			codeID, err := strconv.ParseUint(path[1], 10, 64)
 			if err != nil {
@@ -99,6 +116,64 @@ func NewLegacyQuerier(keeper Keeper) sdk.Querier {
 			if err != nil {
 				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
 			}
+		case QueryContractIntrospect:
+			addr, err := sdk.AccAddressFromBech32(path[1])
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+			}
+			rsp, err = keeper.IntrospectContract(ctx, addr)
+			if err != nil {
+				return nil, sdkerrors.Wrapf(types.ErrInvalid, "contract: %s", err.Error())
+			}
+		case QueryContractKeyProof:
+			addr, err := sdk.AccAddressFromBech32(path[1])
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+			}
+			rsp, err = keeper.ProveContractKey(ctx, addr)
+			if err != nil {
+				return nil, sdkerrors.Wrapf(types.ErrInvalid, "contract: %s", err.Error())
+			}
+		case QueryContractStorageStats:
+			addr, err := sdk.AccAddressFromBech32(path[1])
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+			}
+			rsp, err = keeper.GetContractStorageStats(ctx, addr)
+			if err != nil {
+				return nil, sdkerrors.Wrapf(types.ErrInvalid, "contract: %s", err.Error())
+			}
+		case QueryContractVersion:
+			addr, err := sdk.AccAddressFromBech32(path[1])
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+			}
+			rsp, err = keeper.GetContractVersion(ctx, addr)
+			if err != nil {
+				return nil, sdkerrors.Wrapf(types.ErrInvalid, "contract: %s", err.Error())
+			}
+		case QueryContractInstanceStats:
+			addr, err := sdk.AccAddressFromBech32(path[1])
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+			}
+			rsp, err = keeper.InstanceStats(addr)
+			if err != nil {
+				return nil, sdkerrors.Wrapf(types.ErrInvalid, "contract: %s", err.Error())
+			}
+		case QueryVmCapabilities:
+			rsp, err = keeper.VmCapabilities()
+		case QueryConsensusConfigHash:
+			bz = []byte(hex.EncodeToString(keeper.ConsensusConfigHash()))
+		case QueryContractPanics:
+			rsp, err = keeper.ContractPanics()
+		case QueryEnclaveModuleCache:
+			rsp, err = keeper.EnclaveModuleCache()
+		case QueryPredictContractAddress:
+			if len(path) < 4 {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrUnknownRequest, fmt.Sprintf("%s too few arguments (wanted at least 4): %v", QueryPredictContractAddress, path))
+			}
+			bz, err = queryPredictContractAddress(ctx, path[1], path[2], path[3], keeper)
 		default:
 			return nil, sdkerrors.Wrap(sdkerrors.ErrUnknownRequest, fmt.Sprintf("unknown data query endpoint %s", path[0]))
 		}
@@ -134,6 +209,44 @@ func queryContractState(ctx sdk.Context, bech, queryMethod string, data []byte,
 	return keeper.QuerySmart(ctx, contractAddr, data, false)
 }
 
+func queryContractStateSigned(ctx sdk.Context, bech string, data []byte, keeper Keeper) (*types.SignedContractQueryResponse, error) {
+	contractAddr, err := sdk.AccAddressFromBech32(bech)
+	if err != nil {
+		return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, bech)
+	}
+
+	// we enforce a subjective gas limit on all queries to avoid infinite loops
+	ctx = ctx.WithGasMeter(sdk.NewGasMeter(keeper.queryGasLimit))
+	res, signature, err := keeper.QuerySmartSigned(ctx, contractAddr, data, false)
+	if err != nil {
+		return nil, err
+	}
+	return &types.SignedContractQueryResponse{Data: res, Signature: signature}, nil
+}
+
+// queryPredictContractAddress returns the address Instantiate2 would give a contract of codeID
+// instantiated by creator with the hex encoded salt
+func queryPredictContractAddress(ctx sdk.Context, codeIDStr, creatorBech, saltHex string, keeper Keeper) ([]byte, error) {
+	codeID, err := strconv.ParseUint(codeIDStr, 10, 64)
+	if err != nil {
+		return nil, sdkerrors.Wrapf(types.ErrInvalid, "code id: %s", err.Error())
+	}
+	creator, err := sdk.AccAddressFromBech32(creatorBech)
+	if err != nil {
+		return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+	}
+	salt, err := hex.DecodeString(saltHex)
+	if err != nil || len(salt) == 0 || len(salt) > types.MaxInstantiateSaltLength {
+		return nil, sdkerrors.Wrapf(types.ErrInvalid, "salt must be 1 to %d hex encoded bytes", types.MaxInstantiateSaltLength)
+	}
+	codeInfo, err := keeper.GetCodeInfo(ctx, codeID)
+	if err != nil {
+		return nil, sdkerrors.Wrap(types.ErrNotFound, err.Error())
+	}
+
+	return PredictableContractAddress(codeInfo.CodeHash, creator, salt), nil
+}
+
 func queryContractKey(ctx sdk.Context, address sdk.AccAddress, keeper Keeper) ([]byte, error) {
 	res, err := keeper.GetContractKey(ctx, address)
 	if err != nil {
@@ -134,6 +134,28 @@ func (m Migrator) Migrate4to5(ctx sdk.Context) error {
 	return nil
 }
 
+// Migrate5to6 migrates from version 5 to 6. The migration computes the storage statistics of every
+// contract, which are then kept up to date on every write.
+func (m Migrator) Migrate5to6(ctx sdk.Context) error {
+	iter := prefix.NewStore(ctx.KVStore(m.keeper.storeKey), types.ContractKeyPrefix).Iterator(nil, nil)
+	defer iter.Close()
+
+	formatter := message.NewPrinter(language.English)
+	migratedContracts := uint64(0)
+	totalContracts := m.keeper.peekAutoIncrementID(ctx, types.KeyLastInstanceID) - 1
+	previousTime := time.Now().UnixNano()
+
+	for ; iter.Valid(); iter.Next() {
+		var contractAddress sdk.AccAddress = iter.Key()
+		m.keeper.recomputeContractStorageStats(ctx, contractAddress)
+
+		migratedContracts++
+		logMigrationProgress(ctx, formatter, migratedContracts, totalContracts, previousTime)
+		previousTime = time.Now().UnixNano()
+	}
+	return nil
+}
+
 const progressPartSize = 1000
 
 func logMigrationProgress(ctx sdk.Context, formatter *message.Printer, migratedContracts uint64, totalContracts uint64, previousTime int64) {
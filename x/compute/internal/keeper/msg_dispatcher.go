@@ -2,7 +2,9 @@ package keeper
 
 import (
 	"bytes"
+	"encoding/base64"
 	"encoding/json"
+	"errors"
 	"fmt"
 	"os"
 	"sort"
@@ -30,6 +32,7 @@ type Messenger interface {
 type Replyer interface {
 	reply(ctx sdk.Context, contractAddress sdk.AccAddress, reply v1wasmTypes.Reply, ogTx []byte, ogSigInfo wasmTypes.SigInfo) ([]byte, error)
 	GetLastMsgMarkerContainer() *baseapp.LastMsgMarkerContainer
+	featureActive(ctx sdk.Context, feature string) bool
 }
 
 // MessageDispatcher coordinates message sending and submessage reply/ state commits
@@ -94,7 +97,7 @@ func (d MessageDispatcher) dispatchMsgWithGasLimit(ctx sdk.Context, contractAddr
 			err = sdkerrors.Wrap(sdkerrors.ErrOutOfGas, "SubMsg hit gas limit")
 		}
 	}()
-	events, data, err = d.messenger.DispatchMsg(subCtx, contractAddr, ibcPort, msg)
+	events, data, err = d.dispatchMsg(subCtx, contractAddr, ibcPort, msg)
 
 	// make sure we charge the parent what was spent
 	spent := subCtx.GasMeter().GasConsumed()
@@ -103,6 +106,70 @@ func (d MessageDispatcher) dispatchMsgWithGasLimit(ctx sdk.Context, contractAddr
 	return events, data, err
 }
 
+// dispatchMsg dispatches a message once the funds it sends are normalized, if funds normalization
+// is active. The enclave signed the callback of a wasm message over the same normalized funds.
+func (d MessageDispatcher) dispatchMsg(ctx sdk.Context, contractAddr sdk.AccAddress, ibcPort string, msg v1wasmTypes.CosmosMsg) ([]sdk.Event, [][]byte, error) {
+	if d.keeper.featureActive(ctx, types.FeatureFundsNormalization) {
+		var err error
+		if msg, err = normalizeMsgFunds(msg); err != nil {
+			return nil, nil, err
+		}
+	}
+	return d.messenger.DispatchMsg(ctx, contractAddr, ibcPort, msg)
+}
+
+// normalizeMsgFunds returns msg with the funds it sends sorted by denom, the amounts of a repeated
+// denom summed and zero amounts dropped, failing on invalid funds. msg itself is left unchanged.
+func normalizeMsgFunds(msg v1wasmTypes.CosmosMsg) (v1wasmTypes.CosmosMsg, error) {
+	switch {
+	case msg.Bank != nil && msg.Bank.Send != nil:
+		send := *msg.Bank.Send
+		amount, err := normalizeWasmCoins(send.Amount)
+		if err != nil {
+			return msg, err
+		}
+		send.Amount = amount
+		msg.Bank = &v1wasmTypes.BankMsg{Send: &send}
+	case msg.Wasm != nil && msg.Wasm.Execute != nil:
+		execute := *msg.Wasm.Execute
+		funds, err := normalizeWasmCoins(execute.Send)
+		if err != nil {
+			return msg, err
+		}
+		execute.Send = funds
+		msg.Wasm = &v1wasmTypes.WasmMsg{Execute: &execute}
+	case msg.Wasm != nil && msg.Wasm.Instantiate != nil:
+		instantiate := *msg.Wasm.Instantiate
+		funds, err := normalizeWasmCoins(instantiate.Send)
+		if err != nil {
+			return msg, err
+		}
+		instantiate.Send = funds
+		msg.Wasm = &v1wasmTypes.WasmMsg{Instantiate: &instantiate}
+	}
+	return msg, nil
+}
+
+func normalizeWasmCoins(coins wasmTypes.Coins) (wasmTypes.Coins, error) {
+	var normalized sdk.Coins
+	for _, coin := range coins {
+		amount, ok := sdk.NewIntFromString(coin.Amount)
+		if !ok || amount.IsNegative() {
+			return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidCoins, coin.Amount+coin.Denom)
+		}
+		if err := sdk.ValidateDenom(coin.Denom); err != nil {
+			return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidCoins, err.Error())
+		}
+		normalized = normalized.Add(sdk.Coin{Denom: coin.Denom, Amount: amount})
+	}
+
+	res := make(wasmTypes.Coins, len(normalized))
+	for i, coin := range normalized {
+		res[i] = wasmTypes.Coin{Denom: coin.Denom, Amount: coin.Amount.String()}
+	}
+	return res, nil
+}
+
 type InvalidRequest struct {
 	Err     string `json:"error"`
 	Request []byte `json:"request"`
@@ -154,6 +221,11 @@ func isReplyEncrypted(msg v1wasmTypes.SubMsg) bool {
 
 // Issue #759 - we don't return error string for worries of non-determinism
 func redactError(err error) (bool, error) {
+	// the contract a revert is attributed to is emitted with it, not passed on to the reply
+	if revertErr, ok := err.(*types.ContractRevertError); ok {
+		err = revertErr.Err
+	}
+
 	// Do not redact encrypted wasm contract errors
 	if strings.HasPrefix(err.Error(), "encrypted:") {
 		// remove encrypted sign
@@ -161,6 +233,8 @@ func redactError(err error) (bool, error) {
 		e = strings.ReplaceAll(e, ": execute contract failed", "")
 		e = strings.ReplaceAll(e, ": instantiate contract failed", "")
 		e = strings.ReplaceAll(e, ": migrate contract failed", "")
+		// the public revert reason of the error is not part of its ciphertext
+		e = wasmTypes.StripRevert(e)
 		return false, fmt.Errorf("%s", e)
 	}
 
@@ -224,7 +298,7 @@ func (d MessageDispatcher) DispatchSubmessages(ctx sdk.Context, contractAddr sdk
 		if limitGas {
 			events, data, err = d.dispatchMsgWithGasLimit(subCtx, contractAddr, ibcPort, msg.Msg, *msg.GasLimit)
 		} else {
-			events, data, err = d.messenger.DispatchMsg(subCtx, contractAddr, ibcPort, msg.Msg)
+			events, data, err = d.dispatchMsg(subCtx, contractAddr, ibcPort, msg.Msg)
 		}
 
 		// if it succeeds, commit state changes from submessage, and pass on events to Event Manager
@@ -278,6 +352,18 @@ func (d MessageDispatcher) DispatchSubmessages(ctx sdk.Context, contractAddr sdk
 				},
 			}
 		} else {
+			// the revert reason of a contract is public, and would be lost with the events of the submessage.
+			// It is attributed to the contract that failed, which may be called deeper than the submessage.
+			var revertErr *types.ContractRevertError
+			if d.keeper.featureActive(ctx, types.FeatureRevertReasons) && errors.As(err, &revertErr) {
+				ctx.EventManager().EmitEvent(sdk.NewEvent(
+					types.EventTypeRevert,
+					sdk.NewAttribute(types.AttributeKeyContractAddr, revertErr.Contract.String()),
+					sdk.NewAttribute(types.AttributeKeyRevertCode, fmt.Sprint(revertErr.Revert.Code)),
+					sdk.NewAttribute(types.AttributeKeyRevertData, base64.StdEncoding.EncodeToString(revertErr.Revert.Data)),
+				))
+			}
+
 			// Issue #759 - we don't return error string for worries of non-determinism
 			moduleLogger(ctx).Info("Redacting submessage error", "cause", err)
 			isSdkError, redactedErr = redactError(err)
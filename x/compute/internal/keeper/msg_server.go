@@ -31,7 +31,7 @@ func (m msgServer) StoreCode(goCtx context.Context, msg *types.MsgStoreCode) (*t
 		sdk.NewAttribute(types.AttributeKeySigner, msg.Sender.String()),
 	))
 
-	codeID, err := m.keeper.Create(ctx, msg.Sender, msg.WASMByteCode, msg.Source, msg.Builder)
+	codeID, err := m.keeper.CreateSigned(ctx, msg.Sender, msg.WASMByteCode, msg.Source, msg.Builder, msg.DeveloperPubKey, msg.DeveloperSignature)
 	if err != nil {
 		return nil, err
 	}
@@ -59,7 +59,13 @@ func (m msgServer) InstantiateContract(goCtx context.Context, msg *types.MsgInst
 		}
 	}
 
-	contractAddr, data, err := m.keeper.Instantiate(ctx, msg.CodeID, msg.Sender, adminAddr, msg.InitMsg, msg.Label, msg.InitFunds, msg.CallbackSig)
+	var contractAddr sdk.AccAddress
+	var data []byte
+	if len(msg.Salt) != 0 {
+		contractAddr, data, err = m.keeper.Instantiate2(ctx, msg.CodeID, msg.Sender, adminAddr, msg.InitMsg, msg.Label, msg.InitFunds, msg.Salt, msg.CallbackSig)
+	} else {
+		contractAddr, data, err = m.keeper.Instantiate(ctx, msg.CodeID, msg.Sender, adminAddr, msg.InitMsg, msg.Label, msg.InitFunds, msg.CallbackSig)
+	}
 	if err != nil {
 		return nil, err
 	}
@@ -89,7 +95,16 @@ func (m msgServer) ExecuteContract(goCtx context.Context, msg *types.MsgExecuteC
 		sdk.NewAttribute(types.AttributeKeyContractAddr, msg.Contract.String()),
 	))
 
-	data, err := m.keeper.Execute(ctx, msg.Contract, msg.Sender, msg.Msg, msg.SentFunds, msg.CallbackSig, wasmtypes.HandleTypeExecute)
+	var data *sdk.Result
+	var err error
+	if accessList, ok := types.NewAccessList(msg); ok {
+		if !m.keeper.featureActive(ctx, types.FeatureAccessList) {
+			return nil, sdkerrors.Wrap(types.ErrInvalid, "access lists are not enabled")
+		}
+		data, err = m.keeper.ExecuteWithAccessList(ctx, msg.Contract, msg.Sender, msg.Msg, msg.SentFunds, msg.CallbackSig, accessList)
+	} else {
+		data, err = m.keeper.Execute(ctx, msg.Contract, msg.Sender, msg.Msg, msg.SentFunds, msg.CallbackSig, wasmtypes.HandleTypeExecute)
+	}
 	if err != nil {
 		return nil, err
 	}
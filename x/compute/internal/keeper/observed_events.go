@@ -0,0 +1,39 @@
+package keeper
+
+import (
+	"os"
+	"path/filepath"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+)
+
+// homePath resolves a file configured for the module against the node home
+func homePath(homeDir string, file string) string {
+	if file == "" || filepath.IsAbs(file) {
+		return file
+	}
+	return filepath.Join(homeDir, file)
+}
+
+// streamObservedEvents drains the events the enclave encrypted to contract observers during the
+// last contract call and appends them to the observed events file. They are drained even when the
+// node doesn't stream them or the call runs in CheckTx, so they don't pile up.
+//
+// Failing to write them only logs, as they are not part of consensus.
+func (k Keeper) streamObservedEvents(ctx sdk.Context) {
+	records := k.wasmer.TakeObservedEvents()
+	if len(records) == 0 || k.observedEventsFile == "" || ctx.IsCheckTx() {
+		return
+	}
+
+	file, err := os.OpenFile(k.observedEventsFile, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0o600)
+	if err != nil {
+		moduleLogger(ctx).Error("failed to open the observed events file", "error", err)
+		return
+	}
+	defer file.Close()
+
+	if _, err := file.Write(records); err != nil {
+		moduleLogger(ctx).Error("failed to write observed events", "error", err)
+	}
+}
@@ -0,0 +1,121 @@
+package keeper
+
+import (
+	"bytes"
+	"encoding/hex"
+
+	"github.com/cosmos/cosmos-sdk/store/prefix"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// OrphanedCodeIDs returns, in code id order, the codes that no contract currently runs, e.g.
+// because all their contracts were migrated to another code.
+//
+// Codes uploaded more than once share their wasm, so a code is only orphaned if no contract runs
+// any code with the same code hash.
+func (k Keeper) OrphanedCodeIDs(ctx sdk.Context) []uint64 {
+	var codeIDs []uint64
+	usedCodeHashes := make(map[string]struct{})
+	k.IterateCodeInfos(ctx, func(codeID uint64, info types.CodeInfo) bool {
+		if k.hasContracts(ctx, codeID) {
+			usedCodeHashes[string(info.CodeHash)] = struct{}{}
+		} else {
+			codeIDs = append(codeIDs, codeID)
+		}
+		return false
+	})
+
+	var orphaned []uint64
+	for _, codeID := range codeIDs {
+		info, err := k.GetCodeInfo(ctx, codeID)
+		if err != nil {
+			panic(err)
+		}
+		if _, used := usedCodeHashes[string(info.CodeHash)]; !used {
+			orphaned = append(orphaned, codeID)
+		}
+	}
+	return orphaned
+}
+
+// GarbageCollectOrphanedCode deletes the wasm of the codes returned by OrphanedCodeIDs from the
+// node's data dir, and returns their ids.
+//
+// This only reclaims disk space on this node and doesn't touch the chain state: the codes stay
+// registered, but this node can't instantiate them or return their wasm anymore. It's meant for
+// archival nodes that don't need to, and must not run as part of block processing.
+func (k Keeper) GarbageCollectOrphanedCode(ctx sdk.Context) ([]uint64, error) {
+	orphaned := k.OrphanedCodeIDs(ctx)
+	removedCodeHashes := make(map[string]struct{})
+	for _, codeID := range orphaned {
+		info, err := k.GetCodeInfo(ctx, codeID)
+		if err != nil {
+			return nil, err
+		}
+		// codes with the same hash are removed at once
+		if _, removed := removedCodeHashes[string(info.CodeHash)]; removed {
+			continue
+		}
+		if _, err := k.wasmer.RemoveCode(info.CodeHash); err != nil {
+			return nil, sdkerrors.Wrapf(err, "removing code %d", codeID)
+		}
+		removedCodeHashes[string(info.CodeHash)] = struct{}{}
+	}
+	return orphaned, nil
+}
+
+// RemoveCode removes a code that no contract runs from the chain, once governance voted to, so
+// that no contract can be instantiated from it anymore. Its wasm is deleted from the node's data
+// dir and dropped by the enclave at the end of the block, see RemovePendingCodes.
+func (k Keeper) RemoveCode(ctx sdk.Context, codeID uint64) error {
+	info, err := k.GetCodeInfo(ctx, codeID)
+	if err != nil {
+		return sdkerrors.Wrap(types.ErrNotFound, err.Error())
+	}
+	if k.hasContracts(ctx, codeID) {
+		return sdkerrors.Wrapf(types.ErrInvalid, "code %d is still used by contracts", codeID)
+	}
+	store := ctx.KVStore(k.storeKey)
+	store.Delete(types.GetCodeKey(codeID))
+	store.Set(types.GetPendingCodeRemovalKey(info.CodeHash), []byte{1})
+	return nil
+}
+
+// RemovePendingCodes deletes the wasm of the codes removed during the block, unless another code
+// has the same code hash. It runs at the end of the block, as deleting a wasm can't be undone if
+// the tx removing its code fails. A failure to delete it only leaves it on this node's disk.
+func (k Keeper) RemovePendingCodes(ctx sdk.Context) {
+	store := prefix.NewStore(ctx.KVStore(k.storeKey), types.PendingCodeRemovalPrefix)
+	iter := store.Iterator(nil, nil)
+	var codeHashes [][]byte
+	for ; iter.Valid(); iter.Next() {
+		codeHashes = append(codeHashes, iter.Key())
+	}
+	iter.Close()
+
+	for _, codeHash := range codeHashes {
+		store.Delete(codeHash)
+
+		shared := false
+		k.IterateCodeInfos(ctx, func(_ uint64, other types.CodeInfo) bool {
+			shared = bytes.Equal(other.CodeHash, codeHash)
+			return shared
+		})
+		if shared {
+			continue
+		}
+		if _, err := k.wasmer.RemoveCode(codeHash); err != nil {
+			moduleLogger(ctx).Error("failed to remove wasm of removed code", "code_hash", hex.EncodeToString(codeHash), "error", err.Error())
+		}
+	}
+}
+
+// hasContracts returns whether any contract currently runs the code
+func (k Keeper) hasContracts(ctx sdk.Context, codeID uint64) bool {
+	prefixStore := prefix.NewStore(ctx.KVStore(k.storeKey), types.GetContractByCodeIDSecondaryIndexPrefix(codeID))
+	iter := prefixStore.Iterator(nil, nil)
+	defer iter.Close()
+	return iter.Valid()
+}
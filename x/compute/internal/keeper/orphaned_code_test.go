@@ -0,0 +1,103 @@
+package keeper
+
+import (
+	"os"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+func TestGarbageCollectOrphanedCode(t *testing.T) {
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, nil, nil)
+	accKeeper, keeper := keepers.AccountKeeper, keepers.WasmKeeper
+
+	deposit := sdk.NewCoins(sdk.NewInt64Coin("denom", 100000))
+	creator, _ := CreateFakeFundedAccount(ctx, accKeeper, keeper.bankKeeper, deposit)
+
+	usedCode, err := os.ReadFile(TestContractPaths[v1Contract])
+	require.NoError(t, err)
+	orphanedCode, err := os.ReadFile(TestContractPaths[v1MigratedContract])
+	require.NoError(t, err)
+
+	usedCodeID, err := keeper.Create(ctx, creator, usedCode, "", "")
+	require.NoError(t, err)
+	orphanedCodeID, err := keeper.Create(ctx, creator, orphanedCode, "", "")
+	require.NoError(t, err)
+	// same wasm as a code with a contract
+	duplicateCodeID, err := keeper.Create(ctx, creator, usedCode, "", "")
+	require.NoError(t, err)
+
+	require.Equal(t, []uint64{usedCodeID, orphanedCodeID, duplicateCodeID}, keeper.OrphanedCodeIDs(ctx))
+
+	_, _, contractAddr := keyPubAddr()
+	keeper.addToContractCodeSecondaryIndex(ctx, contractAddr, types.ContractCodeHistoryEntry{
+		CodeID:  usedCodeID,
+		Updated: types.NewAbsoluteTxPosition(ctx),
+	})
+	require.Equal(t, []uint64{orphanedCodeID}, keeper.OrphanedCodeIDs(ctx))
+
+	removed, err := keeper.GarbageCollectOrphanedCode(ctx)
+	require.NoError(t, err)
+	require.Equal(t, []uint64{orphanedCodeID}, removed)
+
+	_, err = keeper.GetWasm(ctx, orphanedCodeID)
+	require.Error(t, err)
+	storedCode, err := keeper.GetWasm(ctx, duplicateCodeID)
+	require.NoError(t, err)
+	require.Equal(t, usedCode, storedCode)
+
+	// the code info stays, so the code is still reported but has nothing left to remove
+	removed, err = keeper.GarbageCollectOrphanedCode(ctx)
+	require.NoError(t, err)
+	require.Equal(t, []uint64{orphanedCodeID}, removed)
+}
+
+func TestRemoveCode(t *testing.T) {
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, nil, nil)
+	accKeeper, keeper := keepers.AccountKeeper, keepers.WasmKeeper
+
+	deposit := sdk.NewCoins(sdk.NewInt64Coin("denom", 100000))
+	creator, _ := CreateFakeFundedAccount(ctx, accKeeper, keeper.bankKeeper, deposit)
+
+	wasmCode, err := os.ReadFile(TestContractPaths[v1Contract])
+	require.NoError(t, err)
+
+	usedCodeID, err := keeper.Create(ctx, creator, wasmCode, "", "")
+	require.NoError(t, err)
+	duplicateCodeID, err := keeper.Create(ctx, creator, wasmCode, "", "")
+	require.NoError(t, err)
+
+	_, _, contractAddr := keyPubAddr()
+	keeper.addToContractCodeSecondaryIndex(ctx, contractAddr, types.ContractCodeHistoryEntry{
+		CodeID:  usedCodeID,
+		Updated: types.NewAbsoluteTxPosition(ctx),
+	})
+	handler := NewProposalHandler(keeper)
+	err = handler(ctx, types.NewRemoveCodeProposal("remove", "used code", usedCodeID))
+	require.True(t, types.ErrInvalid.Is(err), err)
+
+	require.NoError(t, handler(ctx, types.NewRemoveCodeProposal("remove", "duplicate code", duplicateCodeID)))
+	_, err = keeper.GetCodeInfo(ctx, duplicateCodeID)
+	require.Error(t, err)
+	require.True(t, ctx.KVStore(keeper.storeKey).Has(types.GetPendingCodeRemovalKey(wasmCodeHash(t, keeper, ctx, usedCodeID))))
+
+	// the wasm is shared with the code that is still used
+	keeper.RemovePendingCodes(ctx)
+	require.False(t, ctx.KVStore(keeper.storeKey).Has(types.GetPendingCodeRemovalKey(wasmCodeHash(t, keeper, ctx, usedCodeID))))
+	storedCode, err := keeper.GetWasm(ctx, usedCodeID)
+	require.NoError(t, err)
+	require.Equal(t, wasmCode, storedCode)
+
+	err = keeper.RemoveCode(ctx, duplicateCodeID)
+	require.True(t, types.ErrNotFound.Is(err), err)
+}
+
+func wasmCodeHash(t *testing.T, keeper Keeper, ctx sdk.Context, codeID uint64) []byte {
+	info, err := keeper.GetCodeInfo(ctx, codeID)
+	require.NoError(t, err)
+	return info.CodeHash
+}
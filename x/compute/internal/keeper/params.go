@@ -0,0 +1,63 @@
+package keeper
+
+import (
+	"bytes"
+	"encoding/hex"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// GetParams returns the params of the module. The ones that were never set, like all of them on a
+// chain that upgraded from a version without params, keep their default.
+func (k Keeper) GetParams(ctx sdk.Context) types.Params {
+	params := types.DefaultParams()
+	for _, pair := range params.ParamSetPairs() {
+		k.paramSpace.GetIfExists(ctx, pair.Key, pair.Value)
+	}
+	return params
+}
+
+// SetParams sets the params of the module
+func (k Keeper) SetParams(ctx sdk.Context, params types.Params) {
+	k.paramSpace.SetParamSet(ctx, &params)
+}
+
+// newEnv is types.NewEnv with the params the enclave enforces
+func (k Keeper) newEnv(ctx sdk.Context, creator sdk.AccAddress, deposit sdk.Coins, contractAddr sdk.AccAddress, contractKey types.ContractKey, random []byte) wasmTypes.Env {
+	env := types.NewEnv(ctx, creator, deposit, contractAddr, contractKey, random)
+
+	params := k.GetParams(ctx)
+	env.MaxResponseDataSize = params.MaxResponseDataSize
+	env.MaxQueryResponseSize = params.MaxQueryResponseSize
+	env.ActiveFeatures = params.ActiveFeatures(ctx.BlockHeight())
+	env.Observer = params.ObserverOf(contractAddr)
+	return env
+}
+
+// featureActive reports whether an enclave feature is activated at the height of ctx
+func (k Keeper) featureActive(ctx sdk.Context, feature string) bool {
+	return k.GetParams(ctx).IsActive(feature, ctx.BlockHeight())
+}
+
+// RecordConsensusConfigHash records the consensus config hash of this node as the one of the
+// network. It runs at genesis and in the upgrade handlers of releases that change the contract
+// features, governance updates it when the network changes its consensus config.
+func (k Keeper) RecordConsensusConfigHash(ctx sdk.Context) {
+	params := k.GetParams(ctx)
+	params.ConsensusConfigHash = k.consensusConfigHash
+	k.SetParams(ctx, params)
+}
+
+// VerifyConsensusConfigHash returns an error if the consensus config of this node isn't the one
+// recorded for the network, whose transactions this node would run differently
+func (k Keeper) VerifyConsensusConfigHash(ctx sdk.Context) error {
+	expected := k.GetParams(ctx).ConsensusConfigHash
+	if len(expected) == 0 || bytes.Equal(expected, k.consensusConfigHash) {
+		return nil
+	}
+	return sdkerrors.Wrapf(types.ErrInvalid, "wasm consensus config hash is %s, the network runs with %s",
+		hex.EncodeToString(k.consensusConfigHash), hex.EncodeToString(expected))
+}
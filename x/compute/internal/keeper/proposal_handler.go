@@ -0,0 +1,27 @@
+package keeper
+
+import (
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	govtypes "github.com/cosmos/cosmos-sdk/x/gov/types"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// NewProposalHandler returns the handler of the governance proposals of the compute module
+func NewProposalHandler(k Keeper) govtypes.Handler {
+	return func(ctx sdk.Context, content govtypes.Content) error {
+		switch c := content.(type) {
+		case *types.RemoveCodeProposal:
+			return handleRemoveCodeProposal(ctx, k, c)
+		default:
+			return sdkerrors.Wrapf(sdkerrors.ErrUnknownRequest, "unrecognized compute proposal content type: %T", c)
+		}
+	}
+}
+
+func handleRemoveCodeProposal(ctx sdk.Context, k Keeper, p *types.RemoveCodeProposal) error {
+	if err := p.ValidateBasic(); err != nil {
+		return err
+	}
+	return k.RemoveCode(ctx, p.CodeID)
+}
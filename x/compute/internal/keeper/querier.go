@@ -79,7 +79,9 @@ func (q GrpcQuerier) QuerySecretContract(c context.Context, req *types.QuerySecr
 
 	ctx := sdk.UnwrapSDKContext(c).WithGasMeter(sdk.NewGasMeter(q.keeper.queryGasLimit))
 
-	response, err := q.keeper.QuerySmart(ctx, contractAddress, req.Query, false)
+	query := q.keeper.RewrapPreviousCodeQuery(ctx, contractAddress, req.Query)
+
+	response, err := q.keeper.QuerySmart(ctx, contractAddress, query, false)
 	switch {
 	case err != nil:
 		return nil, err
@@ -248,11 +250,12 @@ func queryCode(ctx sdk.Context, codeId uint64, keeper Keeper) (*types.QueryCodeR
 	}
 
 	info := types.CodeInfoResponse{
-		CodeId:   codeId,
-		Creator:  codeInfo.Creator.String(),
-		CodeHash: hex.EncodeToString(codeInfo.CodeHash),
-		Source:   codeInfo.Source,
-		Builder:  codeInfo.Builder,
+		CodeId:          codeId,
+		Creator:         codeInfo.Creator.String(),
+		CodeHash:        hex.EncodeToString(codeInfo.CodeHash),
+		Source:          codeInfo.Source,
+		Builder:         codeInfo.Builder,
+		DeveloperPubKey: hex.EncodeToString(codeInfo.DeveloperPubKey),
 	}
 
 	wasmBz, err := keeper.GetWasm(ctx, codeId)
@@ -270,11 +273,12 @@ func queryCodeList(ctx sdk.Context, keeper Keeper) ([]types.CodeInfoResponse, er
 	var info []types.CodeInfoResponse
 	keeper.IterateCodeInfos(ctx, func(codeId uint64, res types.CodeInfo) bool {
 		info = append(info, types.CodeInfoResponse{
-			CodeId:   codeId,
-			Creator:  res.Creator.String(),
-			CodeHash: hex.EncodeToString(res.CodeHash),
-			Source:   res.Source,
-			Builder:  res.Builder,
+			CodeId:          codeId,
+			Creator:         res.Creator.String(),
+			CodeHash:        hex.EncodeToString(res.CodeHash),
+			Source:          res.Source,
+			Builder:         res.Builder,
+			DeveloperPubKey: hex.EncodeToString(res.DeveloperPubKey),
 		})
 		return false
 	})
@@ -238,6 +238,11 @@ func TestQueryContractState(t *testing.T) {
 			expModelLen: 0,
 			expErr:      types.ErrNotFound,
 		},
+		"query signed with unknown address": {
+			srcPath:     []string{QueryContractStateSigned, anyAddr.String()},
+			expModelLen: 0,
+			expErr:      types.ErrNotFound,
+		},
 	}
 
 	for msg, spec := range specs {
@@ -11,6 +11,7 @@ import (
 	"github.com/cosmos/cosmos-sdk/baseapp"
 	"github.com/cosmos/cosmos-sdk/codec"
 	bankkeeper "github.com/cosmos/cosmos-sdk/x/bank/keeper"
+	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
 	distrkeeper "github.com/cosmos/cosmos-sdk/x/distribution/keeper"
 	govkeeper "github.com/cosmos/cosmos-sdk/x/gov/keeper"
 	govtypes "github.com/cosmos/cosmos-sdk/x/gov/types"
@@ -75,6 +76,9 @@ func (q QueryHandler) Query(request wasmTypes.QueryRequest, queryDepth uint32, g
 	if request.Stargate != nil {
 		return q.Plugins.Stargate(q.Ctx, request.Stargate)
 	}
+	if request.TokenFactory != nil {
+		return q.Plugins.TokenFactory(subctx, request.TokenFactory)
+	}
 	return nil, wasmTypes.Unknown{}
 }
 
@@ -85,28 +89,30 @@ func (q QueryHandler) GasConsumed() uint64 {
 type CustomQuerier func(ctx sdk.Context, request json.RawMessage) ([]byte, error)
 
 type QueryPlugins struct {
-	Bank     func(ctx sdk.Context, request *wasmTypes.BankQuery) ([]byte, error)
-	Custom   CustomQuerier
-	Staking  func(ctx sdk.Context, request *wasmTypes.StakingQuery) ([]byte, error)
-	Wasm     func(ctx sdk.Context, request *wasmTypes.WasmQuery, queryDepth uint32) ([]byte, error)
-	Dist     func(ctx sdk.Context, request *wasmTypes.DistQuery) ([]byte, error)
-	Mint     func(ctx sdk.Context, request *wasmTypes.MintQuery) ([]byte, error)
-	Gov      func(ctx sdk.Context, request *wasmTypes.GovQuery) ([]byte, error)
-	IBC      func(ctx sdk.Context, caller sdk.AccAddress, request *wasmTypes.IBCQuery) ([]byte, error)
-	Stargate func(ctx sdk.Context, request *wasmTypes.StargateQuery) ([]byte, error)
+	Bank         func(ctx sdk.Context, request *wasmTypes.BankQuery) ([]byte, error)
+	Custom       CustomQuerier
+	Staking      func(ctx sdk.Context, request *wasmTypes.StakingQuery) ([]byte, error)
+	Wasm         func(ctx sdk.Context, request *wasmTypes.WasmQuery, queryDepth uint32) ([]byte, error)
+	Dist         func(ctx sdk.Context, request *wasmTypes.DistQuery) ([]byte, error)
+	Mint         func(ctx sdk.Context, request *wasmTypes.MintQuery) ([]byte, error)
+	Gov          func(ctx sdk.Context, request *wasmTypes.GovQuery) ([]byte, error)
+	IBC          func(ctx sdk.Context, caller sdk.AccAddress, request *wasmTypes.IBCQuery) ([]byte, error)
+	Stargate     func(ctx sdk.Context, request *wasmTypes.StargateQuery) ([]byte, error)
+	TokenFactory func(ctx sdk.Context, request *wasmTypes.TokenFactoryQuery) ([]byte, error)
 }
 
 func DefaultQueryPlugins(gov govkeeper.Keeper, dist distrkeeper.Keeper, mint mintkeeper.Keeper, bank bankkeeper.Keeper, staking stakingkeeper.Keeper, stargateQueryRouter GRPCQueryRouter, wasm *Keeper, channelKeeper types.ChannelKeeper) QueryPlugins {
 	return QueryPlugins{
-		Bank:     BankQuerier(bank),
-		Custom:   NoCustomQuerier,
-		Staking:  StakingQuerier(staking, dist),
-		Wasm:     WasmQuerier(wasm),
-		Dist:     DistQuerier(dist),
-		Mint:     MintQuerier(mint),
-		Gov:      GovQuerier(gov),
-		Stargate: StargateQuerier(stargateQueryRouter),
-		IBC:      IBCQuerier(wasm, channelKeeper),
+		Bank:         BankQuerier(bank),
+		Custom:       NoCustomQuerier,
+		Staking:      StakingQuerier(staking, dist),
+		Wasm:         WasmQuerier(wasm),
+		Dist:         DistQuerier(dist),
+		Mint:         MintQuerier(mint),
+		Gov:          GovQuerier(gov),
+		Stargate:     StargateQuerier(stargateQueryRouter),
+		IBC:          IBCQuerier(wasm, channelKeeper),
+		TokenFactory: TokenFactoryQuerier(bank),
 	}
 }
 
@@ -142,6 +148,9 @@ func (e QueryPlugins) Merge(o *QueryPlugins) QueryPlugins {
 	if o.Stargate != nil {
 		e.Stargate = o.Stargate
 	}
+	if o.TokenFactory != nil {
+		e.TokenFactory = o.TokenFactory
+	}
 	return e
 }
 
@@ -440,6 +449,66 @@ func BankQuerier(bankKeeper bankkeeper.ViewKeeper) func(ctx sdk.Context, request
 	}
 }
 
+// tokenFactoryDenomPrefix is the namespace of denoms created the token factory way
+const tokenFactoryDenomPrefix = "factory"
+
+func TokenFactoryQuerier(bankKeeper bankkeeper.Keeper) func(ctx sdk.Context, request *wasmTypes.TokenFactoryQuery) ([]byte, error) {
+	return func(ctx sdk.Context, request *wasmTypes.TokenFactoryQuery) ([]byte, error) {
+		if request.FullDenom != nil {
+			creator, err := sdk.AccAddressFromBech32(request.FullDenom.CreatorAddr)
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, request.FullDenom.CreatorAddr)
+			}
+			denom := strings.Join([]string{tokenFactoryDenomPrefix, creator.String(), request.FullDenom.Subdenom}, "/")
+			if err := sdk.ValidateDenom(denom); err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidCoins, err.Error())
+			}
+			return json.Marshal(wasmTypes.FullDenomResponse{Denom: denom})
+		}
+		if request.DenomMetadata != nil {
+			res := wasmTypes.DenomMetadataResponse{}
+			if metadata, found := bankKeeper.GetDenomMetaData(ctx, request.DenomMetadata.Denom); found {
+				res.Metadata = convertSdkMetadataToWasmMetadata(metadata)
+			}
+			return json.Marshal(res)
+		}
+		if request.Supply != nil {
+			supply := bankKeeper.GetSupply(ctx, request.Supply.Denom)
+			res := wasmTypes.DenomSupplyResponse{
+				Amount: wasmTypes.Coin{
+					Denom:  request.Supply.Denom,
+					Amount: supply.Amount.String(),
+				},
+			}
+			return json.Marshal(res)
+		}
+		return nil, wasmTypes.UnsupportedRequest{Kind: "unknown TokenFactoryQuery variant"}
+	}
+}
+
+func convertSdkMetadataToWasmMetadata(metadata banktypes.Metadata) *wasmTypes.DenomMetadata {
+	units := make([]wasmTypes.DenomUnit, len(metadata.DenomUnits))
+	for i, unit := range metadata.DenomUnits {
+		aliases := unit.Aliases
+		if aliases == nil {
+			aliases = []string{}
+		}
+		units[i] = wasmTypes.DenomUnit{
+			Denom:    unit.Denom,
+			Exponent: unit.Exponent,
+			Aliases:  aliases,
+		}
+	}
+	return &wasmTypes.DenomMetadata{
+		Description: metadata.Description,
+		DenomUnits:  units,
+		Base:        metadata.Base,
+		Display:     metadata.Display,
+		Name:        metadata.Name,
+		Symbol:      metadata.Symbol,
+	}
+}
+
 func NoCustomQuerier(sdk.Context, json.RawMessage) ([]byte, error) {
 	return nil, wasmTypes.UnsupportedRequest{Kind: "custom"}
 }
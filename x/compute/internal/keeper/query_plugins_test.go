@@ -0,0 +1,74 @@
+package keeper
+
+import (
+	"encoding/json"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+)
+
+func TestTokenFactoryQuerier(t *testing.T) {
+	ctx, keepers := CreateTestInput(t, false, SupportedFeatures, nil, nil)
+	accKeeper, bankKeeper := keepers.AccountKeeper, keepers.BankKeeper
+	querier := TokenFactoryQuerier(bankKeeper)
+
+	_, _, creator := keyPubAddr()
+	denom := "factory/" + creator.String() + "/foo"
+
+	// full denom
+	bz, err := querier(ctx, &wasmTypes.TokenFactoryQuery{
+		FullDenom: &wasmTypes.FullDenomQuery{CreatorAddr: creator.String(), Subdenom: "foo"},
+	})
+	require.NoError(t, err)
+	require.JSONEq(t, `{"denom":"`+denom+`"}`, string(bz))
+
+	_, err = querier(ctx, &wasmTypes.TokenFactoryQuery{
+		FullDenom: &wasmTypes.FullDenomQuery{CreatorAddr: "invalid", Subdenom: "foo"},
+	})
+	require.Error(t, err)
+
+	// metadata
+	metadataQuery := &wasmTypes.TokenFactoryQuery{
+		DenomMetadata: &wasmTypes.DenomMetadataQuery{Denom: denom},
+	}
+	bz, err = querier(ctx, metadataQuery)
+	require.NoError(t, err)
+	require.JSONEq(t, `{"metadata":null}`, string(bz))
+
+	bankKeeper.SetDenomMetaData(ctx, banktypes.Metadata{
+		Description: "a factory denom",
+		DenomUnits:  []*banktypes.DenomUnit{{Denom: denom, Exponent: 0}},
+		Base:        denom,
+		Display:     denom,
+		Name:        "Foo",
+		Symbol:      "FOO",
+	})
+	bz, err = querier(ctx, metadataQuery)
+	require.NoError(t, err)
+	var metadataRes wasmTypes.DenomMetadataResponse
+	require.NoError(t, json.Unmarshal(bz, &metadataRes))
+	require.Equal(t, &wasmTypes.DenomMetadata{
+		Description: "a factory denom",
+		DenomUnits:  []wasmTypes.DenomUnit{{Denom: denom, Exponent: 0, Aliases: []string{}}},
+		Base:        denom,
+		Display:     denom,
+		Name:        "Foo",
+		Symbol:      "FOO",
+	}, metadataRes.Metadata)
+
+	// supply
+	CreateFakeFundedAccount(ctx, accKeeper, bankKeeper, sdk.NewCoins(sdk.NewInt64Coin(denom, 1234)))
+	bz, err = querier(ctx, &wasmTypes.TokenFactoryQuery{
+		Supply: &wasmTypes.DenomSupplyQuery{Denom: denom},
+	})
+	require.NoError(t, err)
+	require.JSONEq(t, `{"amount":{"denom":"`+denom+`","amount":"1234"}}`, string(bz))
+
+	_, err = querier(ctx, &wasmTypes.TokenFactoryQuery{})
+	require.Error(t, err)
+}
@@ -40,7 +40,7 @@ func (k Keeper) ibcContractCall(ctx sdk.Context,
 	}
 	random := k.GetRandomSeed(ctx, ctx.BlockHeight())
 
-	env := types.NewEnv(
+	env := k.newEnv(
 		ctx,
 		sdk.AccAddress{}, /* there's no MessageInfo for IBC contract calls */
 		sdk.NewCoins(),   /* there's no MessageInfo for IBC contract calls */
@@ -56,8 +56,9 @@ func (k Keeper) ibcContractCall(ctx sdk.Context,
 	}
 
 	gas := gasForContract(ctx)
-	res, gasUsed, err := k.wasmer.Execute(codeInfo.CodeHash, env, msgBz, prefixStore, cosmwasmAPI, querier, ctx.GasMeter(), gas, sigInfo, callType)
+	res, receipt, gasUsed, err := k.wasmer.Execute(codeInfo.CodeHash, env, msgBz, prefixStore, cosmwasmAPI, querier, ctx.GasMeter(), gas, sigInfo, callType)
 	consumeGas(ctx, gasUsed)
+	k.recordExecutionReceipt(ctx, receipt)
 
 	return res, err
 }
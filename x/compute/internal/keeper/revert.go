@@ -0,0 +1,28 @@
+package keeper
+
+import (
+	"errors"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// contractError wraps the error the contract at contractAddress failed with in kind. If the
+// contract failed with a structured revert reason, the error keeps it with the address of the
+// contract, see types.ContractRevertError.
+func contractError(contractAddress sdk.AccAddress, kind *sdkerrors.Error, err error) error {
+	wrapped := sdkerrors.Wrap(kind, err.Error())
+
+	var stdErr wasmTypes.StdError
+	if !errors.As(err, &stdErr) || stdErr.GenericErr == nil || stdErr.GenericErr.Revert == nil {
+		return wrapped
+	}
+	return &types.ContractRevertError{
+		Contract: contractAddress,
+		Revert:   *stdErr.GenericErr.Revert,
+		Err:      wrapped,
+	}
+}
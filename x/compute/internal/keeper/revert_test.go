@@ -0,0 +1,42 @@
+package keeper
+
+import (
+	"errors"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+func TestContractError(t *testing.T) {
+	contract := sdk.AccAddress([]byte("contract____________"))
+	revert := wasmTypes.Revert{Code: 42, Data: []byte{0, 1, 2}}
+
+	err := contractError(contract, types.ErrExecuteFailed, wasmTypes.StdError{
+		GenericErr: &wasmTypes.GenericErr{Msg: "c2VjcmV0", Revert: &revert},
+	})
+	// handled up the call chain
+	err = sdkerrors.Wrap(sdkerrors.Wrap(err, "submessages"), "dispatch")
+
+	var revertErr *types.ContractRevertError
+	require.True(t, errors.As(err, &revertErr))
+	require.Equal(t, contract, revertErr.Contract)
+	require.Equal(t, revert, revertErr.Revert)
+	require.True(t, types.ErrExecuteFailed.Is(sdkerrors.Cause(err)))
+	require.Contains(t, err.Error(), "reverted by "+contract.String())
+
+	// the reply only gets the encrypted error
+	_, redacted := redactError(revertErr)
+	require.Equal(t, "c2VjcmV0", redacted.Error())
+
+	withoutRevert := contractError(contract, types.ErrExecuteFailed, wasmTypes.StdError{
+		GenericErr: &wasmTypes.GenericErr{Msg: "c2VjcmV0"},
+	})
+	require.False(t, errors.As(withoutRevert, &revertErr))
+	require.Equal(t, "encrypted: c2VjcmV0: execute contract failed", withoutRevert.Error())
+}
@@ -150,6 +150,35 @@ func TestState(t *testing.T) {
 	}
 }
 
+func TestReadYourWrites(t *testing.T) {
+	ctx, keeper, codeID, _, walletA, privKeyA, _, _ := setupTest(t, TestContractPaths[v1Contract], sdk.NewCoins())
+
+	_, _, contractAddress, _, initErr := initHelper(t, keeper, ctx, codeID, walletA, nil, privKeyA, `{"nop":{}}`, true, true, defaultGasForTests)
+	require.Empty(t, initErr)
+
+	_, _, _, _, _, execErr := execHelper(t, keeper, ctx, contractAddress, walletA, privKeyA, `{"set_state":{"key":"committed","value":"0"}}`, true, true, defaultGasForTests, 0)
+	require.Empty(t, execErr)
+
+	_, _, _, _, _, execErr = execHelper(t, keeper, ctx, contractAddress, walletA, privKeyA, `{"read_your_writes":{}}`, true, true, defaultGasForTests, 0)
+	require.Empty(t, execErr)
+
+	// the writes of the call were flushed as they were last read
+	for key, value := range map[string]string{"fresh": "3", "committed": "2", "never_written": ""} {
+		_, _, data, _, _, execErr := execHelper(t, keeper, ctx, contractAddress, walletA, privKeyA, fmt.Sprintf(`{"get_state":{"key":"%s"}}`, key), true, true, defaultGasForTests, 0)
+		require.Empty(t, execErr)
+		require.Equal(t, value, string(data), key)
+	}
+
+	_, _, _, _, _, execErr = execHelper(t, keeper, ctx, contractAddress, walletA, privKeyA, `{"read_your_writes_with_submessage":{}}`, true, true, defaultGasForTests, 0)
+	require.Empty(t, execErr)
+
+	for key, value := range map[string]string{"parent": "", "child": "2", "reply": "3"} {
+		_, _, data, _, _, execErr := execHelper(t, keeper, ctx, contractAddress, walletA, privKeyA, fmt.Sprintf(`{"get_state":{"key":"%s"}}`, key), true, true, defaultGasForTests, 0)
+		require.Empty(t, execErr)
+		require.Equal(t, value, string(data), key)
+	}
+}
+
 func TestAddrValidateFunction(t *testing.T) {
 	ctx, keeper, codeID, _, walletA, privKeyA, _, _ := setupTest(t, TestContractPaths[v1Contract], sdk.NewCoins())
 
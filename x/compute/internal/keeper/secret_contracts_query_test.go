@@ -723,3 +723,28 @@ func TestV1QueryV010Contract(t *testing.T) {
 	require.Empty(t, err)
 	require.Equal(t, uint32(190), binary.BigEndian.Uint32(data))
 }
+
+func TestIntrospectContract(t *testing.T) {
+	for _, testContract := range testContracts {
+		t.Run(testContract.CosmWasmVersion, func(t *testing.T) {
+			ctx, keeper, codeID, codeHash, walletA, privKeyA, _, _ := setupTest(t, testContract.WasmFilePath, sdk.NewCoins())
+
+			_, _, contractAddr, _, initErr := initHelper(t, keeper, ctx, codeID, walletA, nil, privKeyA, `{"nop":{}}`, true, testContract.IsCosmWasmV1, defaultGasForTests)
+			require.Empty(t, initErr)
+
+			gasBefore := ctx.GasMeter().GasConsumed()
+			info, err := keeper.IntrospectContract(ctx, contractAddr)
+			require.NoError(t, err)
+			require.Equal(t, gasBefore, ctx.GasMeter().GasConsumed())
+
+			require.Equal(t, codeHash, info.CodeHash)
+			if testContract.IsCosmWasmV1 {
+				require.Equal(t, "v1", info.InterfaceVersion)
+			} else {
+				require.Equal(t, "v0.10", info.InterfaceVersion)
+			}
+			require.False(t, info.IBCEnabled)
+			require.Empty(t, info.SnipStandards)
+		})
+	}
+}
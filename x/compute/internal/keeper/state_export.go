@@ -0,0 +1,28 @@
+package keeper
+
+import (
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// ExportState exports all contract code and state at the current height, for building the
+// genesis of a hard fork.
+//
+// Codes are walked in code id order and contracts in address order, as they are laid out in the
+// store, so every node exporting the same state produces the same export and commitment.
+func ExportState(ctx sdk.Context, keeper Keeper) (types.StateExport, error) {
+	return types.NewStateExport(ctx.BlockHeight(), *ExportGenesis(ctx, keeper))
+}
+
+// ImportState checks an export against its commitment and imports it. It is the counterpart of
+// ExportState and must run on a store without any code or contract, as InitGenesis does.
+func ImportState(ctx sdk.Context, keeper Keeper, export types.StateExport) error {
+	if err := export.Verify(); err != nil {
+		return err
+	}
+	if err := InitGenesis(ctx, keeper, export.Genesis); err != nil {
+		return sdkerrors.Wrapf(err, "state export at height %d", export.Height)
+	}
+	return nil
+}
@@ -0,0 +1,71 @@
+package keeper
+
+import (
+	"bytes"
+	"sort"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// DecryptedStateEntry is an entry of the store of a contract along with the plaintext of its key
+type DecryptedStateEntry struct {
+	Key       []byte
+	StoredKey []byte
+	Value     []byte
+}
+
+// IterateContractStateInKeyOrder calls cb with the entries of the store of a contract in the order
+// of their plaintext keys, the order the contract itself iterates them in, until cb returns true.
+// It returns the number of entries whose key couldn't be decrypted, which are skipped: keys in the
+// old format and the keys of value chunks.
+//
+// This is meant for state migration and analytics tooling. Only enclaves built in software mode
+// for it decrypt state keys, once governance sets StateKeyDecryptionHeight, the enclave of any
+// other node returns an error. It is not reachable from transactions or queries.
+func (k Keeper) IterateContractStateInKeyOrder(ctx sdk.Context, contractAddress sdk.AccAddress, cb func(entry DecryptedStateEntry) bool) (int, error) {
+	if k.GetContractInfo(ctx, contractAddress) == nil {
+		return 0, sdkerrors.Wrap(types.ErrNotFound, "contract")
+	}
+	contractKey, err := k.GetContractKey(ctx, contractAddress)
+	if err != nil {
+		return 0, err
+	}
+
+	var entries []DecryptedStateEntry
+	var storedKeys [][]byte
+	iter := k.GetContractState(ctx, contractAddress)
+	defer iter.Close()
+	for ; iter.Valid(); iter.Next() {
+		entries = append(entries, DecryptedStateEntry{StoredKey: iter.Key(), Value: iter.Value()})
+		storedKeys = append(storedKeys, iter.Key())
+	}
+
+	keys, err := k.wasmer.DecryptStateKeys(contractKey.OgContractKey, storedKeys, k.GetParams(ctx).ActiveFeatures(ctx.BlockHeight()))
+	if err != nil {
+		return 0, sdkerrors.Wrap(types.ErrInvalid, err.Error())
+	}
+
+	decrypted := entries[:0]
+	skipped := 0
+	for i, key := range keys {
+		if key == nil {
+			skipped++
+			continue
+		}
+		entries[i].Key = key
+		decrypted = append(decrypted, entries[i])
+	}
+	sort.Slice(decrypted, func(i, j int) bool {
+		return bytes.Compare(decrypted[i].Key, decrypted[j].Key) < 0
+	})
+
+	for _, entry := range decrypted {
+		if cb(entry) {
+			break
+		}
+	}
+	return skipped, nil
+}
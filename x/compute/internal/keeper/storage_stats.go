@@ -0,0 +1,88 @@
+package keeper
+
+import (
+	"github.com/cosmos/cosmos-sdk/store/prefix"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+
+	"github.com/scrtlabs/SecretNetwork/x/compute/internal/types"
+)
+
+// contractStore is the store of a contract, which keeps the storage statistics of the contract up to
+// date as the contract writes to it.
+//
+// The statistics are read and written without consuming gas, so that keeping them doesn't change the
+// gas of contracts.
+type contractStore struct {
+	prefix.Store
+	// the same entries, without gas metering
+	untracked prefix.Store
+	stats     sdk.KVStore
+	statsKey  []byte
+}
+
+var _ sdk.KVStore = contractStore{}
+
+func (k Keeper) contractStore(ctx sdk.Context, contractAddress sdk.AccAddress) contractStore {
+	prefixStoreKey := types.GetContractStorePrefixKey(contractAddress)
+	untrackedStore := ctx.MultiStore().GetKVStore(k.storeKey)
+	return contractStore{
+		Store:     prefix.NewStore(ctx.KVStore(k.storeKey), prefixStoreKey),
+		untracked: prefix.NewStore(untrackedStore, prefixStoreKey),
+		stats:     untrackedStore,
+		statsKey:  types.GetContractStorageStatsKey(contractAddress),
+	}
+}
+
+func (s contractStore) loadStats() types.ContractStorageStats {
+	stats, err := types.UnmarshalContractStorageStats(s.stats.Get(s.statsKey))
+	if err != nil {
+		panic(err)
+	}
+	return stats
+}
+
+func (s contractStore) Set(key, value []byte) {
+	stats := s.loadStats()
+	stats.AddEntry(key, s.untracked.Get(key), value)
+	s.stats.Set(s.statsKey, stats.Marshal())
+
+	s.Store.Set(key, value)
+}
+
+func (s contractStore) Delete(key []byte) {
+	if oldValue := s.untracked.Get(key); oldValue != nil {
+		stats := s.loadStats()
+		stats.RemoveEntry(key, oldValue)
+		s.stats.Set(s.statsKey, stats.Marshal())
+	}
+
+	s.Store.Delete(key)
+}
+
+// GetContractStorageStats returns the number and size of the entries in the store of a contract
+func (k Keeper) GetContractStorageStats(ctx sdk.Context, contractAddress sdk.AccAddress) (*types.ContractStorageStats, error) {
+	if k.GetContractInfo(ctx, contractAddress) == nil {
+		return nil, sdkerrors.Wrap(types.ErrNotFound, "contract")
+	}
+
+	stats, err := types.UnmarshalContractStorageStats(ctx.KVStore(k.storeKey).Get(types.GetContractStorageStatsKey(contractAddress)))
+	if err != nil {
+		return nil, err
+	}
+	return &stats, nil
+}
+
+// recomputeContractStorageStats scans the store of a contract to set its statistics, for contracts
+// created before the statistics were kept
+func (k Keeper) recomputeContractStorageStats(ctx sdk.Context, contractAddress sdk.AccAddress) {
+	var stats types.ContractStorageStats
+
+	iter := k.GetContractState(ctx, contractAddress)
+	defer iter.Close()
+	for ; iter.Valid(); iter.Next() {
+		stats.AddEntry(iter.Key(), nil, iter.Value())
+	}
+
+	ctx.KVStore(k.storeKey).Set(types.GetContractStorageStatsKey(contractAddress), stats.Marshal())
+}
@@ -346,6 +346,7 @@ func CreateTestInput(t *testing.T, isCheckTx bool, supportedFeatures string, enc
 	paramsKeeper.Subspace(slashingtypes.ModuleName)
 	paramsKeeper.Subspace(crisistypes.ModuleName)
 	paramsKeeper.Subspace(ibchost.ModuleName)
+	paramsKeeper.Subspace(wasmtypes.ModuleName)
 
 	// this is also used to initialize module accounts (so nil is meaningful here)
 	maccPerms := map[string][]string{
@@ -545,10 +546,12 @@ func CreateTestInput(t *testing.T, isCheckTx bool, supportedFeatures string, enc
 
 	bappTxMngr := baseapp.LastMsgMarkerContainer{}
 
+	wasmSubsp, _ := paramsKeeper.GetSubspace(wasmtypes.ModuleName)
 	keeper := NewKeeper(
 		encodingConfig.Marshaler,
 		*encodingConfig.Amino,
 		keys[wasmtypes.StoreKey],
+		wasmSubsp,
 		authKeeper,
 		bankKeeper,
 		govKeeper,
@@ -1027,7 +1030,7 @@ func updateLightClientHelper(t *testing.T, ctx sdk.Context) {
 
 	randomAndProofBz := append(random, proof...) //nolint:all
 
-	_, err = api.SubmitBlockSignatures(headerBz, commitBz, dataBz, randomAndProofBz)
+	_, err = api.SubmitBlockSignatures(uint64(blockHeader.Height), headerBz, commitBz, dataBz, randomAndProofBz)
 	require.NoError(t, err)
 }
 
@@ -0,0 +1,28 @@
+package types
+
+import (
+	sdk "github.com/cosmos/cosmos-sdk/types"
+)
+
+// AccessList is the set of prefixes of keys of a contract store an execution declared it would read or
+// write. The keys are the plaintext keys the contract uses, which the enclave checks before encrypting
+// them, so declaring them reveals which keys of its store the execution touches.
+type AccessList struct {
+	Contract sdk.AccAddress
+	prefixes [][]byte
+}
+
+// NewAccessList returns the access list declared by an execute message, and false if it declared none
+func NewAccessList(msg *MsgExecuteContract) (AccessList, bool) {
+	if len(msg.AccessList) == 0 {
+		return AccessList{}, false
+	}
+	prefixes := make([][]byte, len(msg.AccessList))
+	copy(prefixes, msg.AccessList)
+	return AccessList{Contract: msg.Contract, prefixes: prefixes}, true
+}
+
+// Prefixes returns the declared prefixes, as passed to the enclave in the env of the execution
+func (a AccessList) Prefixes() [][]byte {
+	return a.prefixes
+}
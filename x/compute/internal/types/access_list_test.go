@@ -0,0 +1,20 @@
+package types
+
+import (
+	"testing"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	"github.com/stretchr/testify/require"
+)
+
+func TestAccessList(t *testing.T) {
+	contract := sdk.AccAddress(make([]byte, 20))
+
+	_, ok := NewAccessList(&MsgExecuteContract{Contract: contract})
+	require.False(t, ok)
+
+	a, ok := NewAccessList(&MsgExecuteContract{Contract: contract, AccessList: [][]byte{[]byte("a"), []byte("b")}})
+	require.True(t, ok)
+	require.Equal(t, contract, a.Contract)
+	require.Equal(t, [][]byte{[]byte("a"), []byte("b")}, a.Prefixes())
+}
@@ -9,6 +9,7 @@ type contextKey int
 const (
 	// private type creates an interface key for Context that cannot be accessed by any other package
 	contextKeyTXCount contextKey = iota
+	contextKeyFeeInfo
 )
 
 // WithTXCounter stores a transaction counter value in the context
@@ -22,3 +23,22 @@ func TXCounter(ctx sdk.Context) (uint32, bool) {
 	val, ok := ctx.Value(contextKeyTXCount).(uint32)
 	return val, ok
 }
+
+// FeeInfo is who paid the fees of a transaction
+type FeeInfo struct {
+	Payer sdk.AccAddress
+	// Granter is empty unless the fees were paid through a fee grant
+	Granter sdk.AccAddress
+}
+
+// WithFeeInfo stores the fee payer and granter of the transaction in the context
+func WithFeeInfo(ctx sdk.Context, feeInfo FeeInfo) sdk.Context {
+	return ctx.WithValue(contextKeyFeeInfo, feeInfo)
+}
+
+// TXFeeInfo returns the fee payer and granter and found bool from the context.
+// The result will be (FeeInfo{}, false) for external queries or simulations.
+func TXFeeInfo(ctx sdk.Context) (FeeInfo, bool) {
+	val, ok := ctx.Value(contextKeyFeeInfo).(FeeInfo)
+	return val, ok
+}
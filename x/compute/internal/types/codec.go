@@ -6,6 +6,7 @@ import (
 	"github.com/cosmos/cosmos-sdk/codec/types"
 	cryptocodec "github.com/cosmos/cosmos-sdk/crypto/codec"
 	sdk "github.com/cosmos/cosmos-sdk/types"
+	govtypes "github.com/cosmos/cosmos-sdk/x/gov/types"
 )
 
 // RegisterCodec registers the account types and interface
@@ -16,6 +17,7 @@ func RegisterLegacyAminoCodec(cdc *codec.LegacyAmino) {
 	cdc.RegisterConcrete(&MsgMigrateContract{}, "wasm/MsgMigrateContract", nil)
 	cdc.RegisterConcrete(&MsgUpdateAdmin{}, "wasm/MsgUpdateAdmin", nil)
 	cdc.RegisterConcrete(&MsgClearAdmin{}, "wasm/MsgClearAdmin", nil)
+	cdc.RegisterConcrete(&RemoveCodeProposal{}, "wasm/RemoveCodeProposal", nil)
 }
 
 func RegisterInterfaces(registry types.InterfaceRegistry) {
@@ -28,6 +30,10 @@ func RegisterInterfaces(registry types.InterfaceRegistry) {
 		&MsgUpdateAdmin{},
 		&MsgClearAdmin{},
 	)
+	registry.RegisterImplementations(
+		(*govtypes.Content)(nil),
+		&RemoveCodeProposal{},
+	)
 }
 
 // ModuleCdc generic sealed codec to be used throughout module
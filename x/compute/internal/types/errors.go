@@ -1,9 +1,13 @@
 package types
 
 import (
+	"fmt"
 	"strings"
 
+	sdk "github.com/cosmos/cosmos-sdk/types"
 	sdkErrors "github.com/cosmos/cosmos-sdk/types/errors"
+
+	wasmTypes "github.com/scrtlabs/SecretNetwork/go-cosmwasm/types"
 )
 
 // Codes for wasm contract errors
@@ -72,8 +76,34 @@ var (
 
 	// ErrMaxIBCChannels error for maximum number of ibc channels reached
 	ErrMaxIBCChannels = sdkErrors.Register(DefaultCodespace, 22, "max transfer channels")
+
+	// ErrInvalidDeveloperSignature error when the developer signature of uploaded code doesn't verify
+	ErrInvalidDeveloperSignature = sdkErrors.Register(DefaultCodespace, 24, "invalid developer signature")
 )
 
+// ContractRevertError is the error of a contract that failed with a structured revert reason. It
+// keeps the address of that contract, so that the revert is attributed to it when the error is
+// handled by a contract up the call chain. A failed tx drops its events, so the address is also
+// part of the error log.
+type ContractRevertError struct {
+	Contract sdk.AccAddress
+	Revert   wasmTypes.Revert
+	Err      error
+}
+
+func (e *ContractRevertError) Error() string {
+	return fmt.Sprintf("%s: reverted by %s", e.Err, e.Contract)
+}
+
+func (e *ContractRevertError) Unwrap() error {
+	return e.Err
+}
+
+// Cause lets sdkErrors.ABCIInfo find the code of the wrapped error
+func (e *ContractRevertError) Cause() error {
+	return e.Err
+}
+
 func IsEncryptedErrorCode(code uint32) bool {
 	return 5 >= code && code > 1
 }
@@ -15,6 +15,7 @@ const (
 	EventTypeSudo                = "sudo"
 	EventTypeReply               = "reply"
 	EventTypeUpdateContractAdmin = "update_contract_admin"
+	EventTypeRevert              = "revert"
 )
 
 // event attributes returned from contract execution
@@ -25,4 +26,6 @@ const (
 	AttributeKeyCodeID       = "code_id"
 	AttributeKeySigner       = "signer"
 	AttributeKeyNewAdmin     = "new_admin_address"
+	AttributeKeyRevertCode   = "revert_code"
+	AttributeKeyRevertData   = "revert_data"
 )
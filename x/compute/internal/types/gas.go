@@ -19,3 +19,14 @@ const InstanceCost uint64 = 20_000
 
 // CompileCost is how much SDK gas we charge *per byte* for compiling WASM code.
 const CompileCost uint64 = 2
+
+// DeveloperSignatureHashCostPerByte is how much SDK gas we charge per byte of WASM code hashed to verify the
+// signature of its developer, on top of the auth module's cost of verifying a secp256k1 signature.
+const DeveloperSignatureHashCostPerByte uint64 = 1
+
+// DecryptTxMsgCost is how much SDK gas we charge per contract message decrypted ahead of its execution,
+// and DecryptTxMsgByteCost per byte of it.
+const (
+	DecryptTxMsgCost     uint64 = 1_000
+	DecryptTxMsgByteCost uint64 = 1
+)
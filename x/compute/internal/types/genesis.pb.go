@@ -26,7 +26,7 @@ const _ = proto.GoGoProtoPackageIsVersion3 // please upgrade the proto package
 
 // GenesisState - genesis state of x/wasm
 type GenesisState struct {
-	//    Params params = 1 [(gogoproto.nullable) = false];
+	Params    Params     `protobuf:"bytes,1,opt,name=params,proto3" json:"params"`
 	Codes     []Code     `protobuf:"bytes,2,rep,name=codes,proto3" json:"codes,omitempty"`
 	Contracts []Contract `protobuf:"bytes,3,rep,name=contracts,proto3" json:"contracts,omitempty"`
 	Sequences []Sequence `protobuf:"bytes,4,rep,name=sequences,proto3" json:"sequences,omitempty"`
@@ -281,41 +281,42 @@ func init() {
 }
 
 var fileDescriptor_e737d858048ffc2a = []byte{
-	// 542 bytes of a gzipped FileDescriptorProto
-	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0x84, 0x93, 0x4d, 0x6f, 0x12, 0x41,
-	0x18, 0xc7, 0x59, 0xba, 0x20, 0x1d, 0xd1, 0x9a, 0x91, 0x28, 0xa9, 0x76, 0x21, 0xd8, 0x03, 0x31,
-	0x96, 0x0d, 0x7a, 0xf3, 0xd6, 0xa5, 0x89, 0x41, 0xa2, 0x26, 0x8b, 0x27, 0x6d, 0x42, 0x96, 0x99,
-	0xa7, 0xb8, 0x01, 0x76, 0x70, 0x67, 0xa8, 0xee, 0xa7, 0xd0, 0x8b, 0xdf, 0xa9, 0xc7, 0x1e, 0x3d,
-	0x11, 0xb3, 0xdc, 0xfc, 0x08, 0x9e, 0xcc, 0xbc, 0xb0, 0xdd, 0x44, 0x69, 0x4f, 0xb0, 0xcf, 0xfc,
-	0x9f, 0xdf, 0xfc, 0x9f, 0x97, 0x41, 0x87, 0x1c, 0x48, 0x0c, 0xc2, 0x25, 0x6c, 0xbe, 0x58, 0x0a,
-	0x70, 0xcf, 0xbb, 0x63, 0x10, 0x41, 0xd7, 0x9d, 0x40, 0x04, 0x3c, 0xe4, 0x9d, 0x45, 0xcc, 0x04,
-	0xc3, 0x0f, 0xb4, 0xaa, 0x63, 0x54, 0x1d, 0xa3, 0xda, 0xaf, 0x4d, 0xd8, 0x84, 0x29, 0x89, 0x2b,
-	0xff, 0x69, 0xf5, 0x7e, 0x6b, 0x0b, 0x53, 0x24, 0x0b, 0x30, 0xc4, 0xd6, 0x8f, 0x22, 0xaa, 0xbe,
-	0xd2, 0x77, 0x0c, 0x45, 0x20, 0x00, 0x0f, 0x50, 0x89, 0x30, 0x0a, 0xbc, 0x5e, 0x6c, 0xee, 0xb4,
-	0x6f, 0x3f, 0x7f, 0xdc, 0xf9, 0xff, 0x95, 0x9d, 0x1e, 0xa3, 0xe0, 0x3d, 0xbc, 0x58, 0x35, 0x0a,
-	0xbf, 0x57, 0x8d, 0x3d, 0x95, 0xf2, 0x8c, 0xcd, 0x43, 0x01, 0xf3, 0x85, 0x48, 0x7c, 0xcd, 0xc0,
-	0x1f, 0xd1, 0x2e, 0x61, 0x91, 0x88, 0x03, 0x22, 0x78, 0x7d, 0x47, 0x01, 0x9b, 0xdb, 0x81, 0x5a,
-	0xe8, 0x3d, 0x32, 0xd0, 0xfb, 0x59, 0x6a, 0x0e, 0x7c, 0xc5, 0x93, 0x70, 0x0e, 0x9f, 0x97, 0x10,
-	0x11, 0xe0, 0x75, 0xfb, 0x7a, 0xf8, 0xd0, 0x08, 0xaf, 0xe0, 0x59, 0x6a, 0x1e, 0x9e, 0x05, 0x5b,
-	0xdf, 0x2c, 0x64, 0xcb, 0x12, 0xf1, 0x13, 0x74, 0x4b, 0xd6, 0x32, 0x0a, 0x69, 0xdd, 0x6a, 0x5a,
-	0x6d, 0xdb, 0x43, 0xe9, 0xaa, 0x51, 0x96, 0x47, 0xfd, 0x13, 0xbf, 0x2c, 0x8f, 0xfa, 0x14, 0xf7,
-	0x64, 0x9d, 0x52, 0x14, 0x9d, 0xb1, 0x7a, 0xb1, 0x69, 0x5d, 0x5f, 0x27, 0x85, 0x7e, 0x74, 0xc6,
-	0x3c, 0x5b, 0x5a, 0xf1, 0x2b, 0xc4, 0x7c, 0xe3, 0x03, 0x84, 0x14, 0x64, 0x9c, 0x08, 0x90, 0xdd,
-	0xb2, 0xda, 0x55, 0x5f, 0x61, 0x3d, 0x19, 0x68, 0xad, 0x8b, 0xa8, 0xb2, 0xe9, 0x11, 0x3e, 0x45,
-	0xf7, 0x36, 0x8d, 0x18, 0x05, 0x94, 0xc6, 0xc0, 0xb9, 0xb2, 0x57, 0xf5, 0xba, 0x7f, 0x56, 0x8d,
-	0xa3, 0x49, 0x28, 0x3e, 0x2d, 0xc7, 0xf2, 0x6a, 0x97, 0x30, 0x3e, 0x67, 0xdc, 0xfc, 0x1c, 0x71,
-	0x3a, 0x35, 0xe3, 0x3f, 0x26, 0xe4, 0x58, 0x27, 0xfa, 0x7b, 0x1b, 0x94, 0x09, 0xe0, 0x77, 0xe8,
-	0x4e, 0x46, 0xcf, 0x95, 0x74, 0x78, 0xd3, 0xe8, 0x72, 0x65, 0x55, 0x49, 0x2e, 0x86, 0x5f, 0xa3,
-	0xbb, 0x19, 0x90, 0xcb, 0x35, 0x33, 0xcb, 0x70, 0xb0, 0x8d, 0xf8, 0x86, 0x51, 0x98, 0x19, 0x54,
-	0xe6, 0x45, 0x2f, 0xe8, 0x29, 0xaa, 0x65, 0x2c, 0xb2, 0xe4, 0x82, 0xcd, 0xb5, 0x47, 0x5b, 0x79,
-	0x7c, 0x7a, 0x93, 0xc7, 0x9e, 0x4a, 0x91, 0xae, 0x7c, 0x4c, 0xfe, 0x89, 0xb5, 0x3c, 0x54, 0xd9,
-	0xec, 0x0a, 0x6e, 0xa2, 0x72, 0x48, 0x47, 0x53, 0x48, 0x4c, 0x6b, 0x77, 0xd3, 0x55, 0xa3, 0xd4,
-	0x3f, 0x19, 0x40, 0xe2, 0x97, 0x42, 0x3a, 0x80, 0x04, 0xd7, 0x50, 0xe9, 0x3c, 0x98, 0x2d, 0x41,
-	0x35, 0xc8, 0xf6, 0xf5, 0x87, 0xf7, 0xfe, 0x22, 0x75, 0xac, 0xcb, 0xd4, 0xb1, 0x7e, 0xa5, 0x8e,
-	0xf5, 0x7d, 0xed, 0x14, 0x2e, 0xd7, 0x4e, 0xe1, 0xe7, 0xda, 0x29, 0x7c, 0x78, 0x99, 0x1b, 0x0c,
-	0x27, 0xb1, 0x98, 0x05, 0x63, 0xee, 0x0e, 0x95, 0xe1, 0xb7, 0x20, 0xbe, 0xb0, 0x78, 0xea, 0x7e,
-	0xcd, 0x9e, 0x6b, 0x18, 0x09, 0x88, 0xa3, 0x60, 0xa6, 0x07, 0x36, 0x2e, 0xab, 0x07, 0xfb, 0xe2,
-	0x6f, 0x00, 0x00, 0x00, 0xff, 0xff, 0xaa, 0xfb, 0xbf, 0x15, 0x2a, 0x04, 0x00, 0x00,
+	// 552 bytes of a gzipped FileDescriptorProto
+	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0x85, 0x93, 0xcb, 0x6e, 0xd3, 0x40,
+	0x14, 0x86, 0xeb, 0xd4, 0x31, 0xe9, 0x10, 0x28, 0x1a, 0x22, 0x88, 0x0a, 0x4d, 0x22, 0xb7, 0x8b,
+	0x0a, 0x51, 0x5b, 0x81, 0x1d, 0x62, 0x53, 0xa7, 0x12, 0x0a, 0x15, 0x17, 0x39, 0xac, 0xa0, 0x52,
+	0x64, 0x8f, 0x87, 0x60, 0x25, 0xf6, 0x18, 0xcf, 0xb8, 0x90, 0xa7, 0x80, 0xc7, 0xea, 0xb2, 0x12,
+	0x1b, 0x56, 0x11, 0x6a, 0x77, 0x3c, 0x02, 0x2b, 0xe6, 0x16, 0xd7, 0x12, 0xa4, 0x5d, 0x8c, 0x6c,
+	0x1f, 0xff, 0xff, 0x37, 0xe7, 0x9c, 0x39, 0x03, 0x76, 0x29, 0x46, 0x39, 0x66, 0x2e, 0x22, 0x49,
+	0x56, 0x30, 0xec, 0x9e, 0xf4, 0x43, 0xcc, 0x82, 0xbe, 0x3b, 0xc1, 0x29, 0xa6, 0x31, 0x75, 0xb2,
+	0x9c, 0x30, 0x02, 0xef, 0x29, 0x95, 0xa3, 0x55, 0x8e, 0x56, 0x6d, 0xb5, 0x26, 0x64, 0x42, 0xa4,
+	0xc4, 0x15, 0x6f, 0x4a, 0xbd, 0x65, 0xaf, 0x60, 0xb2, 0x79, 0x86, 0x35, 0xd1, 0xfe, 0x51, 0x03,
+	0xcd, 0x17, 0x6a, 0x8f, 0x11, 0x0b, 0x18, 0x86, 0xcf, 0x81, 0x95, 0x05, 0x79, 0x90, 0xd0, 0xb6,
+	0xd1, 0x33, 0xf6, 0x6e, 0x3e, 0xe9, 0x38, 0xff, 0xdf, 0xd3, 0x79, 0x2b, 0x55, 0x9e, 0x79, 0xba,
+	0xe8, 0xae, 0xf9, 0xda, 0x03, 0x8f, 0x40, 0x1d, 0x91, 0x08, 0xd3, 0x76, 0xad, 0xb7, 0xce, 0xcd,
+	0x0f, 0x57, 0x99, 0x07, 0x5c, 0xe4, 0xdd, 0x17, 0xd6, 0xdf, 0x8b, 0xee, 0xa6, 0xb4, 0x3c, 0x26,
+	0x49, 0xcc, 0x70, 0x92, 0xb1, 0xb9, 0xaf, 0x18, 0xf0, 0x03, 0xd8, 0x40, 0x24, 0x65, 0x79, 0x80,
+	0x18, 0x6d, 0xaf, 0x4b, 0x60, 0x6f, 0x35, 0x50, 0x09, 0xbd, 0x07, 0x1a, 0x7a, 0xb7, 0xb4, 0x56,
+	0xc0, 0x97, 0x3c, 0x01, 0xa7, 0xf8, 0x73, 0x81, 0x53, 0xc4, 0xb3, 0x35, 0xaf, 0x86, 0x8f, 0xb4,
+	0xf0, 0x12, 0x5e, 0x5a, 0xab, 0xf0, 0x32, 0x68, 0x7f, 0x33, 0x80, 0x29, 0x4a, 0x84, 0x3b, 0xe0,
+	0x86, 0xa8, 0x65, 0x1c, 0x47, 0xb2, 0x9d, 0xa6, 0x07, 0xce, 0x17, 0x5d, 0x4b, 0xfc, 0x1a, 0x1e,
+	0xfa, 0x96, 0xf8, 0x35, 0x8c, 0xe0, 0x40, 0xd4, 0x29, 0x44, 0xe9, 0x47, 0xc2, 0x1b, 0x67, 0x5c,
+	0x5d, 0x27, 0xb7, 0x70, 0x9d, 0xee, 0x7b, 0x03, 0xe9, 0x6f, 0xb8, 0x0d, 0x80, 0x84, 0x84, 0x73,
+	0x86, 0x45, 0xb7, 0x8c, 0xbd, 0xa6, 0x2f, 0xb1, 0x9e, 0x08, 0xd8, 0x17, 0x35, 0xd0, 0x58, 0xf6,
+	0x08, 0x1e, 0x83, 0x3b, 0xcb, 0x46, 0x8c, 0x83, 0x28, 0xca, 0x31, 0x55, 0xa7, 0xdd, 0xf4, 0xfa,
+	0x7f, 0x16, 0xdd, 0xfd, 0x49, 0xcc, 0x3e, 0x15, 0xa1, 0xd8, 0x9a, 0x8f, 0x0e, 0x4d, 0x08, 0xd5,
+	0x8f, 0x7d, 0x1a, 0x4d, 0xf5, 0xf0, 0x1c, 0x20, 0x74, 0xa0, 0x8c, 0xfe, 0xe6, 0x12, 0xa5, 0x03,
+	0xf0, 0x0d, 0xb8, 0x55, 0xd2, 0x2b, 0x25, 0xed, 0x5e, 0x77, 0x74, 0x95, 0xb2, 0x9a, 0xa8, 0x12,
+	0x83, 0x2f, 0xc1, 0xed, 0x12, 0x48, 0xc5, 0x90, 0xea, 0x61, 0xd8, 0x5e, 0x45, 0x7c, 0xc5, 0xcb,
+	0x9e, 0x69, 0x54, 0x99, 0x8b, 0x1a, 0xef, 0x63, 0xd0, 0x2a, 0x59, 0xa8, 0xa0, 0x8c, 0x24, 0x2a,
+	0x47, 0x53, 0xe6, 0xf8, 0xe8, 0xba, 0x1c, 0x07, 0xd2, 0x22, 0xb2, 0xf2, 0x21, 0xfa, 0x27, 0x66,
+	0x7b, 0xa0, 0xb1, 0x9c, 0x15, 0xd8, 0x03, 0x56, 0x1c, 0x8d, 0xa7, 0x78, 0xae, 0x5b, 0xbb, 0xc1,
+	0x4f, 0xbe, 0x3e, 0x3c, 0x3c, 0xc2, 0x7c, 0xbe, 0xe3, 0x88, 0x3f, 0x60, 0x0b, 0xd4, 0x4f, 0x82,
+	0x59, 0x81, 0x65, 0x83, 0x4c, 0x5f, 0x7d, 0x78, 0xef, 0x4e, 0xcf, 0x3b, 0xc6, 0x19, 0x5f, 0xbf,
+	0xf8, 0xfa, 0x7e, 0xd1, 0x59, 0x3b, 0xe3, 0xeb, 0x27, 0x5f, 0xef, 0x9f, 0x55, 0x0e, 0x86, 0xa2,
+	0x9c, 0xcd, 0x82, 0x90, 0xba, 0x23, 0x99, 0xf0, 0x6b, 0xcc, 0xbe, 0x90, 0x7c, 0xea, 0x7e, 0x2d,
+	0x2f, 0x7b, 0x9c, 0x32, 0x9c, 0xa7, 0xc1, 0x4c, 0x1d, 0x58, 0x68, 0xc9, 0xeb, 0xfe, 0xf4, 0x2f,
+	0x77, 0x32, 0xea, 0x1f, 0x68, 0x04, 0x00, 0x00,
 }
 
 func (m *GenesisState) Marshal() (dAtA []byte, err error) {
@@ -380,6 +381,16 @@ func (m *GenesisState) MarshalToSizedBuffer(dAtA []byte) (int, error) {
 			dAtA[i] = 0x12
 		}
 	}
+	{
+		size, err := m.Params.MarshalToSizedBuffer(dAtA[:i])
+		if err != nil {
+			return 0, err
+		}
+		i -= size
+		i = encodeVarintGenesis(dAtA, i, uint64(size))
+	}
+	i--
+	dAtA[i] = 0xa
 	return len(dAtA) - i, nil
 }
 
@@ -546,6 +557,8 @@ func (m *GenesisState) Size() (n int) {
 	}
 	var l int
 	_ = l
+	l = m.Params.Size()
+	n += 1 + l + sovGenesis(uint64(l))
 	if len(m.Codes) > 0 {
 		for _, e := range m.Codes {
 			l = e.Size()
@@ -661,6 +674,39 @@ func (m *GenesisState) Unmarshal(dAtA []byte) error {
 			return fmt.Errorf("proto: GenesisState: illegal tag %d (wire type %d)", fieldNum, wire)
 		}
 		switch fieldNum {
+		case 1:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field Params", wireType)
+			}
+			var msglen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowGenesis
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				msglen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if msglen < 0 {
+				return ErrInvalidLengthGenesis
+			}
+			postIndex := iNdEx + msglen
+			if postIndex < 0 {
+				return ErrInvalidLengthGenesis
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			if err := m.Params.Unmarshal(dAtA[iNdEx:postIndex]); err != nil {
+				return err
+			}
+			iNdEx = postIndex
 		case 2:
 			if wireType != 2 {
 				return fmt.Errorf("proto: wrong wireType = %d for field Codes", wireType)
@@ -33,6 +33,11 @@ var (
 	TXCounterPrefix                                = []byte{0x08}
 	ContractCodeHistoryElementPrefix               = []byte{0x09}
 	ContractByCodeIDAndCreatedSecondaryIndexPrefix = []byte{0x0A}
+	PreviousContractEnclaveIdPrefix                = []byte{0x0B}
+	ContractStorageStatsPrefix                     = []byte{0x0C}
+	ContractVersionPrefix                          = []byte{0x0D}
+	PendingCodeRemovalPrefix                       = []byte{0x0E}
+	ContractMigratorPrefix                         = []byte{0x0F}
 	RandomPrefix                                   = []byte{0xFF}
 
 	KeyLastCodeID     = append(SequenceKeyPrefix, []byte("lastCodeId")...)
@@ -66,11 +71,31 @@ func GetContractEnclaveKey(addr sdk.AccAddress) []byte {
 	return append(ContractEnclaveIdPrefix, addr...)
 }
 
+// GetPreviousContractEnclaveKey returns the key for the keys a contract had before its last migration
+func GetPreviousContractEnclaveKey(addr sdk.AccAddress) []byte {
+	return append(PreviousContractEnclaveIdPrefix, addr...)
+}
+
+// GetContractMigratorKey returns the key for the address that last migrated a contract
+func GetContractMigratorKey(addr sdk.AccAddress) []byte {
+	return append(ContractMigratorPrefix, addr...)
+}
+
 // GetContractStorePrefixKey returns the store prefix for the WASM contract instance
 func GetContractStorePrefixKey(addr sdk.AccAddress) []byte {
 	return append(ContractStorePrefix, addr...)
 }
 
+// GetContractStorageStatsKey returns the key for the storage statistics of the WASM contract instance
+func GetContractStorageStatsKey(addr sdk.AccAddress) []byte {
+	return append(ContractStorageStatsPrefix, addr...)
+}
+
+// GetContractVersionKey returns the key for the cw2 version of the WASM contract instance
+func GetContractVersionKey(addr sdk.AccAddress) []byte {
+	return append(ContractVersionPrefix, addr...)
+}
+
 // GetContractStorePrefixKey returns the store prefix for the WASM contract instance
 func GetContractLabelPrefix(addr string) []byte {
 	return append(ContractLabelPrefix, []byte(addr)...)
@@ -118,3 +143,8 @@ func GetContractCodeHistoryElementKey(contractAddr sdk.AccAddress, pos uint64) [
 	copy(r[prefixLen:], sdk.Uint64ToBigEndian(pos))
 	return r
 }
+
+// GetPendingCodeRemovalKey constructs the key of a code hash whose wasm is deleted at the end of the block
+func GetPendingCodeRemovalKey(codeHash []byte) []byte {
+	return append(PendingCodeRemovalPrefix, codeHash...)
+}
@@ -32,6 +32,10 @@ func (msg MsgStoreCode) ValidateBasic() error {
 		return sdkerrors.Wrapf(sdkerrors.ErrInvalidRequest, "builder %s", err.Error())
 	}
 
+	if err := validateDeveloperSignature(msg.DeveloperPubKey, msg.DeveloperSignature); err != nil {
+		return sdkerrors.Wrapf(sdkerrors.ErrInvalidRequest, "developer signature %s", err.Error())
+	}
+
 	return nil
 }
 
@@ -68,6 +72,10 @@ func (msg MsgInstantiateContract) ValidateBasic() error {
 		return sdkerrors.ErrInvalidCoins
 	}
 
+	if len(msg.Salt) > MaxInstantiateSaltLength {
+		return sdkerrors.Wrapf(ErrInvalid, "salt must be at most %d bytes long", MaxInstantiateSaltLength)
+	}
+
 	return nil
 }
 
@@ -99,6 +107,10 @@ func (msg MsgExecuteContract) ValidateBasic() error {
 		return sdkerrors.Wrap(sdkerrors.ErrInvalidCoins, "sentFunds")
 	}
 
+	if err := validateAccessList(msg.AccessList); err != nil {
+		return sdkerrors.Wrap(err, "access list")
+	}
+
 	return nil
 }
 
@@ -39,6 +39,10 @@ type MsgStoreCode struct {
 	Source string `protobuf:"bytes,3,opt,name=source,proto3" json:"source,omitempty"`
 	// Builder is a valid docker image name with tag, optional
 	Builder string `protobuf:"bytes,4,opt,name=builder,proto3" json:"builder,omitempty"`
+	// DeveloperPubKey is the compressed secp256k1 public key of the developer who signed the code, optional
+	DeveloperPubKey []byte `protobuf:"bytes,5,opt,name=developer_pub_key,json=developerPubKey,proto3" json:"developer_pub_key,omitempty"`
+	// DeveloperSignature is the signature of the developer over the raw (uncompressed) code exactly as uploaded, which is the code the code hash is computed over, required with DeveloperPubKey
+	DeveloperSignature []byte `protobuf:"bytes,6,opt,name=developer_signature,json=developerSignature,proto3" json:"developer_signature,omitempty"`
 }
 
 func (m *MsgStoreCode) Reset()         { *m = MsgStoreCode{} }
@@ -133,6 +137,8 @@ type MsgInstantiateContract struct {
 	CallbackSig []byte `protobuf:"bytes,7,opt,name=callback_sig,json=callbackSig,proto3" json:"callback_sig,omitempty"`
 	// Admin is an optional address that can execute migrations
 	Admin string `protobuf:"bytes,8,opt,name=admin,proto3" json:"admin,omitempty"`
+	// Salt, when set, instantiates the contract at an address derived from the code hash, the sender and the salt, which can be computed before the contract exists
+	Salt []byte `protobuf:"bytes,9,opt,name=salt,proto3" json:"salt,omitempty"`
 }
 
 func (m *MsgInstantiateContract) Reset()         { *m = MsgInstantiateContract{} }
@@ -235,6 +241,9 @@ type MsgExecuteContract struct {
 	SentFunds        github_com_cosmos_cosmos_sdk_types.Coins `protobuf:"bytes,5,rep,name=sent_funds,json=sentFunds,proto3,castrepeated=github.com/cosmos/cosmos-sdk/types.Coins" json:"sent_funds"`
 	// used internally for encryption, should always be empty in a signed transaction
 	CallbackSig []byte `protobuf:"bytes,6,opt,name=callback_sig,json=callbackSig,proto3" json:"callback_sig,omitempty"`
+	// access_list optionally declares the prefixes of the plaintext keys of the contract store the execution
+	// will read or write, an execution touching any other key fails. The prefixes are public.
+	AccessList [][]byte `protobuf:"bytes,7,rep,name=access_list,json=accessList,proto3" json:"access_list,omitempty"`
 }
 
 func (m *MsgExecuteContract) Reset()         { *m = MsgExecuteContract{} }
@@ -683,62 +692,66 @@ func init() {
 func init() { proto.RegisterFile("secret/compute/v1beta1/msg.proto", fileDescriptor_6815433faf72a133) }
 
 var fileDescriptor_6815433faf72a133 = []byte{
-	// 873 bytes of a gzipped FileDescriptorProto
-	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xb4, 0x56, 0xbf, 0x8f, 0xe3, 0x44,
-	0x14, 0x8e, 0x71, 0x36, 0x59, 0xbf, 0x0d, 0x77, 0x2b, 0xb3, 0x04, 0x9f, 0x91, 0x9c, 0x28, 0xfc,
-	0x50, 0x84, 0x6e, 0xed, 0xdb, 0x20, 0x5d, 0x71, 0x54, 0x49, 0x00, 0x91, 0xc2, 0x57, 0x38, 0x20,
-	0x24, 0x9a, 0x68, 0x6c, 0x0f, 0x8e, 0x6f, 0x1d, 0x3b, 0x78, 0x26, 0xe4, 0xb6, 0xa0, 0xa7, 0xa4,
-	0x00, 0x6a, 0x24, 0x3a, 0xfe, 0x92, 0xa3, 0xbb, 0x92, 0x2a, 0x40, 0xf6, 0xbf, 0xa0, 0x42, 0x33,
-	0xfe, 0x11, 0x5f, 0x48, 0x4c, 0x6e, 0x75, 0x5b, 0xc5, 0x2f, 0xf3, 0xf9, 0x7b, 0xef, 0x7d, 0xdf,
-	0x9b, 0x19, 0x43, 0x9b, 0x60, 0x27, 0xc6, 0xd4, 0x70, 0xa2, 0xd9, 0x7c, 0x41, 0xb1, 0xf1, 0xed,
-	0x85, 0x8d, 0x29, 0xba, 0x30, 0x66, 0xc4, 0xd3, 0xe7, 0x71, 0x44, 0x23, 0xb9, 0x99, 0x20, 0xf4,
-	0x14, 0xa1, 0xa7, 0x08, 0xf5, 0xcc, 0x8b, 0xbc, 0x88, 0x43, 0x0c, 0xf6, 0x94, 0xa0, 0x55, 0xcd,
-	0x89, 0xc8, 0x2c, 0x22, 0x86, 0x8d, 0xc8, 0x86, 0xcc, 0x89, 0xfc, 0x30, 0x59, 0xef, 0xfc, 0x2e,
-	0x40, 0xc3, 0x24, 0xde, 0x98, 0x46, 0x31, 0x1e, 0x46, 0x2e, 0x96, 0x47, 0x50, 0x23, 0x38, 0x74,
-	0x71, 0xac, 0x08, 0x6d, 0xa1, 0xdb, 0x18, 0x5c, 0xfc, 0xb3, 0x6a, 0x9d, 0x7b, 0x3e, 0x9d, 0x2e,
-	0x6c, 0x96, 0xd2, 0x48, 0xf9, 0x92, 0x9f, 0x73, 0xe2, 0x5e, 0x1a, 0xf4, 0x6a, 0x8e, 0x89, 0xde,
-	0x77, 0x9c, 0xbe, 0xeb, 0xc6, 0x98, 0x10, 0x2b, 0x25, 0x90, 0x1f, 0xc2, 0x9d, 0x25, 0x22, 0xb3,
-	0x89, 0x7d, 0x45, 0xf1, 0xc4, 0x89, 0x5c, 0xac, 0xbc, 0xc6, 0x29, 0x4f, 0xd7, 0xab, 0x56, 0xe3,
-	0xcb, 0xfe, 0xd8, 0x1c, 0x5c, 0x51, 0x9e, 0xd4, 0x6a, 0x30, 0x5c, 0x16, 0xc9, 0x4d, 0xa8, 0x91,
-	0x68, 0x11, 0x3b, 0x58, 0x11, 0xdb, 0x42, 0x57, 0xb2, 0xd2, 0x48, 0x56, 0xa0, 0x6e, 0x2f, 0xfc,
-	0x80, 0xd5, 0x56, 0xe5, 0x0b, 0x59, 0xf8, 0xa8, 0xfa, 0xfd, 0x2f, 0xad, 0x4a, 0xe7, 0x23, 0x38,
-	0x2b, 0xb6, 0x62, 0x61, 0x32, 0x8f, 0x42, 0x82, 0xe5, 0x77, 0xa0, 0xce, 0xb2, 0x4f, 0x7c, 0x97,
-	0xf7, 0x54, 0x1d, 0xc0, 0x7a, 0xd5, 0xaa, 0x31, 0xc8, 0xe8, 0x63, 0xab, 0xc6, 0x96, 0x46, 0x6e,
-	0xe7, 0x57, 0x11, 0x9a, 0x26, 0xf1, 0x46, 0x21, 0xa1, 0x28, 0xa4, 0x3e, 0x62, 0xb5, 0x84, 0x34,
-	0x46, 0x0e, 0x7d, 0x95, 0x92, 0xdc, 0x07, 0xd9, 0x41, 0x41, 0x60, 0x23, 0xe7, 0x92, 0x2b, 0x32,
-	0x99, 0x22, 0x32, 0xe5, 0xb2, 0x48, 0xd6, 0x69, 0xb6, 0xc2, 0x2a, 0xfb, 0x0c, 0x91, 0x69, 0xb1,
-	0x70, 0x71, 0x5f, 0xe1, 0xf2, 0x19, 0x1c, 0x05, 0xc8, 0xc6, 0x41, 0xaa, 0x49, 0x12, 0xc8, 0xf7,
-	0xe0, 0xd8, 0x0f, 0x7d, 0x3a, 0x99, 0x11, 0x4f, 0x39, 0x62, 0x55, 0x5b, 0x75, 0x16, 0x9b, 0xc4,
-	0x93, 0x9f, 0x00, 0xf0, 0xa5, 0xaf, 0x17, 0xa1, 0x4b, 0x94, 0x5a, 0x5b, 0xec, 0x9e, 0xf4, 0xee,
-	0xe9, 0x49, 0xf5, 0x3a, 0x9b, 0x93, 0x6c, 0xa4, 0xf4, 0x61, 0xe4, 0x87, 0x83, 0x07, 0xcf, 0x56,
-	0xad, 0xca, 0x6f, 0x7f, 0xb6, 0xba, 0x07, 0x74, 0xcc, 0x5e, 0x20, 0x96, 0xc4, 0xe8, 0x3f, 0x65,
-	0xec, 0x72, 0x0f, 0x1a, 0x79, 0xbf, 0xc4, 0xf7, 0x94, 0x3a, 0x17, 0xf0, 0xee, 0x7a, 0xd5, 0x3a,
-	0x19, 0xa6, 0xff, 0x8f, 0x7d, 0xcf, 0x3a, 0x71, 0x36, 0x01, 0x6b, 0x08, 0xb9, 0x33, 0x3f, 0x54,
-	0x8e, 0x93, 0x86, 0x78, 0x90, 0x5a, 0xfc, 0x18, 0xb4, 0xdd, 0x26, 0xe5, 0x66, 0x2b, 0x50, 0x47,
-	0x89, 0xe8, 0xdc, 0x2d, 0xc9, 0xca, 0x42, 0x59, 0x86, 0xaa, 0x8b, 0x28, 0x4a, 0x86, 0xd0, 0xe2,
-	0xcf, 0x9d, 0x1f, 0x45, 0x90, 0x4d, 0xe2, 0x7d, 0xf2, 0x14, 0x3b, 0x8b, 0xdb, 0x71, 0xdc, 0x84,
-	0x63, 0x27, 0xa5, 0x4d, 0xc7, 0xff, 0x06, 0x64, 0x39, 0x85, 0x7c, 0x0a, 0x22, 0xb3, 0x54, 0xe4,
-	0x3d, 0xb0, 0xc7, 0x3d, 0x23, 0x55, 0xdd, 0x33, 0x52, 0x4f, 0x00, 0x08, 0x0e, 0x33, 0xf3, 0x8f,
-	0x6e, 0xc1, 0x7c, 0x46, 0xbf, 0xdb, 0xfc, 0xda, 0xff, 0x9b, 0x9f, 0xda, 0xfc, 0x00, 0xd4, 0xff,
-	0xba, 0x92, 0x5b, 0x9c, 0x19, 0x29, 0x14, 0x8c, 0xfc, 0x5b, 0xe0, 0x46, 0x9a, 0xbe, 0x17, 0x17,
-	0xb7, 0x6e, 0xf3, 0x05, 0x23, 0xa5, 0xdc, 0x15, 0x75, 0xcb, 0x15, 0xa9, 0x20, 0xf1, 0x41, 0xbb,
-	0x2e, 0xf5, 0xa1, 0xba, 0xf1, 0xe1, 0x26, 0xa3, 0xbe, 0xdb, 0xbb, 0xe3, 0xdd, 0xde, 0xa5, 0xaa,
-	0x6c, 0xb5, 0x58, 0xaa, 0xca, 0x4f, 0x02, 0xdc, 0x31, 0x89, 0xf7, 0xc5, 0xdc, 0x45, 0x14, 0xf7,
-	0xd9, 0x3e, 0xda, 0xab, 0xc8, 0xdb, 0x20, 0x85, 0x78, 0x39, 0x49, 0x76, 0x5e, 0x2a, 0x49, 0x88,
-	0x97, 0xc9, 0x4b, 0x45, 0xb9, 0xc4, 0x2d, 0xb9, 0x6e, 0xd0, 0x77, 0x47, 0xe1, 0x67, 0x6d, 0xa1,
-	0xac, 0xac, 0x8b, 0xce, 0x12, 0x5e, 0x37, 0x89, 0x37, 0x0c, 0x30, 0x8a, 0xcb, 0xeb, 0x7d, 0xd5,
-	0x25, 0xbd, 0x05, 0x6f, 0xbe, 0x90, 0x38, 0xab, 0xa8, 0xf7, 0xf3, 0x11, 0x88, 0xec, 0xd8, 0x9c,
-	0x80, 0xb4, 0xb9, 0x25, 0xdf, 0xd5, 0x77, 0xdf, 0xc2, 0x7a, 0xf1, 0x02, 0x52, 0xef, 0x1f, 0x82,
-	0xca, 0x0d, 0xfc, 0x0e, 0xde, 0xd8, 0x75, 0xfb, 0xe8, 0x25, 0x24, 0x3b, 0xf0, 0xea, 0xc3, 0x97,
-	0xc3, 0xe7, 0xe9, 0xbf, 0x81, 0xbb, 0xdb, 0xc7, 0xe0, 0x07, 0x25, 0x54, 0x5b, 0x58, 0xb5, 0x77,
-	0x38, 0xb6, 0x98, 0x72, 0x7b, 0xc3, 0x96, 0xa5, 0xdc, 0xc2, 0x96, 0xa6, 0xdc, 0xb7, 0x4b, 0x30,
-	0x9c, 0x14, 0x77, 0xc3, 0xfb, 0x25, 0x14, 0x05, 0x9c, 0xaa, 0x1f, 0x86, 0xcb, 0xd3, 0xd8, 0x00,
-	0x85, 0x19, 0x7e, 0xaf, 0xe4, 0xed, 0x0d, 0x4c, 0x3d, 0x3f, 0x08, 0x96, 0xe5, 0x18, 0x7c, 0xfe,
-	0x6c, 0xad, 0x09, 0xcf, 0xd7, 0x9a, 0xf0, 0xd7, 0x5a, 0x13, 0x7e, 0xb8, 0xd6, 0x2a, 0xcf, 0xaf,
-	0xb5, 0xca, 0x1f, 0xd7, 0x5a, 0xe5, 0xab, 0x47, 0x85, 0xd3, 0x9a, 0x38, 0x31, 0x0d, 0x90, 0x4d,
-	0x8c, 0x31, 0xe7, 0x7e, 0x8c, 0xe9, 0x32, 0x8a, 0x2f, 0x8d, 0xa7, 0xf9, 0x17, 0xa6, 0x1f, 0x52,
-	0x1c, 0x87, 0x28, 0x48, 0x4e, 0x71, 0xbb, 0xc6, 0xbf, 0x0b, 0x3f, 0xfc, 0x37, 0x00, 0x00, 0xff,
-	0xff, 0x8d, 0x4c, 0x08, 0x48, 0x89, 0x0a, 0x00, 0x00,
+	// 940 bytes of a gzipped FileDescriptorProto
+	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xb5, 0x56, 0x4d, 0x6f, 0xd3, 0x48,
+	0x18, 0x6e, 0x70, 0x9a, 0x36, 0x6f, 0x02, 0x2d, 0x43, 0x29, 0xc6, 0x2b, 0x35, 0x28, 0x7c, 0xa8,
+	0x42, 0xd4, 0xa6, 0x45, 0xe2, 0xc0, 0x9e, 0x9a, 0x2e, 0xab, 0xad, 0x96, 0x22, 0xe4, 0x82, 0x90,
+	0xb8, 0x58, 0xe3, 0xf1, 0x90, 0x9a, 0x3a, 0x76, 0xf0, 0x8c, 0x29, 0x3d, 0x70, 0xe7, 0xc8, 0x85,
+	0x3d, 0x73, 0xda, 0xc3, 0xde, 0x57, 0xfb, 0x17, 0x38, 0x72, 0xdc, 0x53, 0xf9, 0xd8, 0x7f, 0xb1,
+	0xa7, 0x9d, 0x19, 0x7f, 0xc4, 0x84, 0x24, 0xca, 0x56, 0xf4, 0x60, 0xc5, 0x33, 0xef, 0x33, 0xef,
+	0xd7, 0xf3, 0xbc, 0x13, 0xc3, 0x25, 0x46, 0x49, 0x4c, 0xb9, 0x45, 0xa2, 0x5e, 0x3f, 0xe1, 0xd4,
+	0x7a, 0xb1, 0xee, 0x52, 0x8e, 0xd7, 0xad, 0x1e, 0xeb, 0x9a, 0xfd, 0x38, 0xe2, 0x11, 0x5a, 0x4e,
+	0x11, 0x66, 0x86, 0x30, 0x33, 0x84, 0xb1, 0xd4, 0x8d, 0xba, 0x91, 0x82, 0x58, 0xf2, 0x2d, 0x45,
+	0x1b, 0x2b, 0x24, 0x62, 0xbd, 0x88, 0x59, 0x2e, 0x66, 0x03, 0x67, 0x24, 0xf2, 0xc3, 0xd4, 0xde,
+	0xfe, 0xfd, 0x14, 0x34, 0x77, 0x58, 0x77, 0x97, 0x47, 0x31, 0xdd, 0x8a, 0x3c, 0x8a, 0xb6, 0xa1,
+	0xc6, 0x68, 0xe8, 0xd1, 0x58, 0xaf, 0x5c, 0xaa, 0xac, 0x36, 0x3b, 0xeb, 0xff, 0x1e, 0xb5, 0xd6,
+	0xba, 0x3e, 0xdf, 0x4b, 0x5c, 0x19, 0xd2, 0xca, 0xfc, 0xa5, 0x3f, 0x6b, 0xcc, 0xdb, 0xb7, 0xf8,
+	0x61, 0x9f, 0x32, 0x73, 0x93, 0x90, 0x4d, 0xcf, 0x8b, 0x29, 0x63, 0x76, 0xe6, 0x00, 0xdd, 0x86,
+	0x33, 0x07, 0x98, 0xf5, 0x1c, 0xf7, 0x90, 0x53, 0x87, 0x08, 0xe7, 0xfa, 0x29, 0xe5, 0x72, 0xf1,
+	0xcb, 0x51, 0xab, 0xf9, 0x78, 0x73, 0x77, 0xa7, 0x23, 0x0c, 0x32, 0xa8, 0xdd, 0x94, 0xb8, 0x7c,
+	0x85, 0x96, 0x45, 0x0a, 0x51, 0x12, 0x13, 0xaa, 0x6b, 0x02, 0x5f, 0xb7, 0xb3, 0x15, 0xd2, 0x61,
+	0xce, 0x4d, 0xfc, 0x40, 0xe6, 0x56, 0x55, 0x86, 0x7c, 0x89, 0xae, 0xc3, 0x59, 0x8f, 0xbe, 0xa0,
+	0x41, 0xd4, 0xa7, 0xb1, 0xd3, 0x4f, 0x5c, 0x67, 0x9f, 0x1e, 0xea, 0xb3, 0x32, 0x98, 0xbd, 0x50,
+	0x18, 0x1e, 0x24, 0xee, 0xaf, 0xf4, 0x10, 0x59, 0x70, 0x6e, 0x80, 0x65, 0x7e, 0x37, 0xc4, 0x3c,
+	0x89, 0xa9, 0x5e, 0x53, 0x68, 0x54, 0x98, 0x76, 0x73, 0xcb, 0x9d, 0xea, 0xeb, 0x77, 0xad, 0x99,
+	0xf6, 0x8f, 0xb0, 0x54, 0xee, 0x93, 0x4d, 0x59, 0x3f, 0x0a, 0x19, 0x45, 0x97, 0x61, 0x4e, 0x96,
+	0xe6, 0xf8, 0x9e, 0x6a, 0x58, 0xb5, 0x03, 0xa2, 0xba, 0x9a, 0x84, 0x6c, 0xff, 0x64, 0xd7, 0xa4,
+	0x69, 0xdb, 0x6b, 0xff, 0xa5, 0xc1, 0xb2, 0x38, 0xbd, 0x1d, 0x32, 0x8e, 0x43, 0xee, 0x63, 0x59,
+	0x68, 0xc8, 0x63, 0x4c, 0xf8, 0xf7, 0xec, 0xf7, 0x0d, 0x40, 0x04, 0x07, 0x81, 0x8b, 0xc9, 0xbe,
+	0x6a, 0xb7, 0xb3, 0x87, 0xd9, 0x9e, 0xea, 0x79, 0xdd, 0x5e, 0xcc, 0x2d, 0x32, 0xb3, 0x5f, 0xc4,
+	0x7e, 0x39, 0x71, 0x6d, 0x5c, 0xe2, 0x68, 0x09, 0x66, 0x03, 0xec, 0xd2, 0x20, 0x6b, 0x78, 0xba,
+	0x40, 0x17, 0x61, 0xde, 0x0f, 0x7d, 0xee, 0x08, 0x51, 0x66, 0x5d, 0x9e, 0x93, 0x6b, 0x51, 0x21,
+	0x7a, 0x06, 0xa0, 0x4c, 0x4f, 0x93, 0xd0, 0x63, 0xa2, 0xa9, 0xda, 0x6a, 0x63, 0xe3, 0xa2, 0x99,
+	0x66, 0x6f, 0x4a, 0x11, 0xe6, 0x7a, 0x35, 0xb7, 0x84, 0x08, 0x3b, 0x37, 0xdf, 0x1f, 0xb5, 0x66,
+	0xfe, 0xf8, 0xd8, 0x5a, 0x9d, 0xa2, 0x62, 0x79, 0x80, 0xd9, 0x75, 0xe9, 0xfe, 0x67, 0xe9, 0x1d,
+	0x6d, 0x40, 0xb3, 0xa8, 0x57, 0x10, 0xa9, 0xcf, 0xa9, 0x06, 0x2e, 0x88, 0x32, 0x1a, 0x5b, 0xd9,
+	0xbe, 0x60, 0xd1, 0x6e, 0x90, 0xc1, 0x42, 0x16, 0x84, 0xbd, 0x9e, 0x1f, 0xea, 0xf3, 0x69, 0x41,
+	0x6a, 0x81, 0x10, 0x54, 0x19, 0x0e, 0xb8, 0x5e, 0x57, 0xc5, 0xa8, 0xf7, 0x8c, 0xf6, 0xfb, 0xb0,
+	0x32, 0x9a, 0xb8, 0x42, 0x00, 0x42, 0x95, 0x38, 0x25, 0x42, 0x31, 0x28, 0x54, 0x99, 0x2d, 0xa5,
+	0x57, 0x0f, 0x73, 0x9c, 0xaa, 0xde, 0x56, 0xef, 0xed, 0x3f, 0x35, 0x40, 0xc2, 0xe1, 0xdd, 0x97,
+	0x94, 0x24, 0x27, 0xa3, 0x82, 0x1d, 0x98, 0x27, 0x99, 0xdb, 0x6c, 0xde, 0x8e, 0xe1, 0xac, 0x70,
+	0x81, 0x16, 0x41, 0x93, 0x34, 0x6b, 0xaa, 0x06, 0xf9, 0x3a, 0x46, 0x66, 0xd5, 0x31, 0x32, 0x13,
+	0x82, 0x10, 0x89, 0xe5, 0x82, 0x98, 0x3d, 0x01, 0x41, 0x48, 0xf7, 0xa3, 0x05, 0x51, 0x9b, 0x42,
+	0x10, 0x2d, 0x68, 0x60, 0x42, 0x44, 0xcd, 0x4e, 0xe0, 0x33, 0x2e, 0x34, 0xa4, 0x89, 0x3a, 0x21,
+	0xdd, 0xba, 0x27, 0x76, 0x32, 0x1d, 0xdc, 0x04, 0xe3, 0x5b, 0xda, 0x0a, 0x0d, 0xe4, 0x4c, 0x57,
+	0x4a, 0x4c, 0x7f, 0xae, 0x28, 0xa6, 0x77, 0xfc, 0x6e, 0x5c, 0x9e, 0xf7, 0xe5, 0xaf, 0x98, 0xae,
+	0x17, 0xb4, 0x19, 0x43, 0xb4, 0xd5, 0x4b, 0x1c, 0x4c, 0x35, 0xaa, 0x19, 0x51, 0xd5, 0x01, 0x51,
+	0xc7, 0x99, 0x8f, 0xd1, 0xe4, 0xce, 0x8f, 0x26, 0x37, 0xeb, 0xca, 0x50, 0x89, 0x13, 0xbb, 0xf2,
+	0xb6, 0x02, 0x67, 0xc4, 0x91, 0x47, 0x7d, 0xb1, 0xa2, 0x9b, 0x6a, 0xf8, 0xc6, 0x75, 0xe4, 0x07,
+	0xa8, 0x87, 0xf4, 0xc0, 0x49, 0xc7, 0x35, 0x6b, 0x89, 0xd8, 0x48, 0x0f, 0x95, 0xdb, 0xa5, 0x0d,
+	0xb5, 0xeb, 0x18, 0x75, 0xb7, 0x75, 0x75, 0x41, 0x97, 0xd2, 0xca, 0xab, 0x68, 0x1f, 0xc0, 0x69,
+	0x61, 0xd9, 0x0a, 0x28, 0x8e, 0x27, 0xe7, 0xfb, 0xbd, 0x53, 0xba, 0x00, 0xe7, 0xbf, 0x0a, 0x9c,
+	0x67, 0xb4, 0xf1, 0xdb, 0x2c, 0x68, 0xf2, 0xae, 0x75, 0xa0, 0x3e, 0xf8, 0xdf, 0xbe, 0x62, 0x8e,
+	0xfe, 0x2e, 0x30, 0xcb, 0xff, 0x5a, 0xc6, 0x8d, 0x69, 0x50, 0x05, 0x81, 0xaf, 0xe0, 0xdc, 0xa8,
+	0xbf, 0x2c, 0x73, 0x82, 0x93, 0x11, 0x78, 0xe3, 0xf6, 0xff, 0xc3, 0x17, 0xe1, 0x9f, 0xc3, 0xc2,
+	0xf0, 0x3d, 0x79, 0x7d, 0x82, 0xab, 0x21, 0xac, 0xb1, 0x31, 0x3d, 0xb6, 0x1c, 0x72, 0x78, 0x60,
+	0x27, 0x85, 0x1c, 0xc2, 0x4e, 0x0c, 0x39, 0x6e, 0x4a, 0x28, 0x34, 0xca, 0xd3, 0x70, 0x6d, 0x82,
+	0x8b, 0x12, 0xce, 0x30, 0xa7, 0xc3, 0x15, 0x61, 0x5c, 0x80, 0x92, 0x86, 0xaf, 0x4e, 0x38, 0x3d,
+	0x80, 0x19, 0x6b, 0x53, 0xc1, 0xf2, 0x18, 0x9d, 0x87, 0xef, 0xbf, 0xac, 0x54, 0x3e, 0x88, 0xe7,
+	0x93, 0x78, 0xde, 0xfc, 0xb3, 0x32, 0xf3, 0x41, 0x3c, 0x7f, 0x8b, 0xe7, 0xc9, 0x9d, 0xd2, 0x75,
+	0xce, 0x48, 0xcc, 0xc5, 0x77, 0x04, 0xb3, 0x76, 0x95, 0xef, 0xfb, 0x94, 0x1f, 0x44, 0xf1, 0xbe,
+	0xf5, 0xb2, 0xf8, 0xe6, 0xf5, 0x43, 0x4e, 0xe3, 0x10, 0x07, 0xe9, 0x35, 0xef, 0xd6, 0xd4, 0x97,
+	0xea, 0xad, 0xff, 0x00, 0x0b, 0x0d, 0x47, 0x1c, 0x1b, 0x0b, 0x00, 0x00,
 }
 
 // Reference imports to suppress errors if they are not otherwise used.
@@ -1033,6 +1046,20 @@ func (m *MsgStoreCode) MarshalToSizedBuffer(dAtA []byte) (int, error) {
 	_ = i
 	var l int
 	_ = l
+	if len(m.DeveloperSignature) > 0 {
+		i -= len(m.DeveloperSignature)
+		copy(dAtA[i:], m.DeveloperSignature)
+		i = encodeVarintMsg(dAtA, i, uint64(len(m.DeveloperSignature)))
+		i--
+		dAtA[i] = 0x32
+	}
+	if len(m.DeveloperPubKey) > 0 {
+		i -= len(m.DeveloperPubKey)
+		copy(dAtA[i:], m.DeveloperPubKey)
+		i = encodeVarintMsg(dAtA, i, uint64(len(m.DeveloperPubKey)))
+		i--
+		dAtA[i] = 0x2a
+	}
 	if len(m.Builder) > 0 {
 		i -= len(m.Builder)
 		copy(dAtA[i:], m.Builder)
@@ -1112,6 +1139,13 @@ func (m *MsgInstantiateContract) MarshalToSizedBuffer(dAtA []byte) (int, error)
 	_ = i
 	var l int
 	_ = l
+	if len(m.Salt) > 0 {
+		i -= len(m.Salt)
+		copy(dAtA[i:], m.Salt)
+		i = encodeVarintMsg(dAtA, i, uint64(len(m.Salt)))
+		i--
+		dAtA[i] = 0x4a
+	}
 	if len(m.Admin) > 0 {
 		i -= len(m.Admin)
 		copy(dAtA[i:], m.Admin)
@@ -1233,6 +1267,15 @@ func (m *MsgExecuteContract) MarshalToSizedBuffer(dAtA []byte) (int, error) {
 	_ = i
 	var l int
 	_ = l
+	if len(m.AccessList) > 0 {
+		for iNdEx := len(m.AccessList) - 1; iNdEx >= 0; iNdEx-- {
+			i -= len(m.AccessList[iNdEx])
+			copy(dAtA[i:], m.AccessList[iNdEx])
+			i = encodeVarintMsg(dAtA, i, uint64(len(m.AccessList[iNdEx])))
+			i--
+			dAtA[i] = 0x3a
+		}
+	}
 	if len(m.CallbackSig) > 0 {
 		i -= len(m.CallbackSig)
 		copy(dAtA[i:], m.CallbackSig)
@@ -1582,6 +1625,14 @@ func (m *MsgStoreCode) Size() (n int) {
 	if l > 0 {
 		n += 1 + l + sovMsg(uint64(l))
 	}
+	l = len(m.DeveloperPubKey)
+	if l > 0 {
+		n += 1 + l + sovMsg(uint64(l))
+	}
+	l = len(m.DeveloperSignature)
+	if l > 0 {
+		n += 1 + l + sovMsg(uint64(l))
+	}
 	return n
 }
 
@@ -1636,6 +1687,10 @@ func (m *MsgInstantiateContract) Size() (n int) {
 	if l > 0 {
 		n += 1 + l + sovMsg(uint64(l))
 	}
+	l = len(m.Salt)
+	if l > 0 {
+		n += 1 + l + sovMsg(uint64(l))
+	}
 	return n
 }
 
@@ -1688,6 +1743,12 @@ func (m *MsgExecuteContract) Size() (n int) {
 	if l > 0 {
 		n += 1 + l + sovMsg(uint64(l))
 	}
+	if len(m.AccessList) > 0 {
+		for _, b := range m.AccessList {
+			l = len(b)
+			n += 1 + l + sovMsg(uint64(l))
+		}
+	}
 	return n
 }
 
@@ -1980,6 +2041,74 @@ func (m *MsgStoreCode) Unmarshal(dAtA []byte) error {
 			}
 			m.Builder = string(dAtA[iNdEx:postIndex])
 			iNdEx = postIndex
+		case 5:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field DeveloperPubKey", wireType)
+			}
+			var byteLen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowMsg
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				byteLen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if byteLen < 0 {
+				return ErrInvalidLengthMsg
+			}
+			postIndex := iNdEx + byteLen
+			if postIndex < 0 {
+				return ErrInvalidLengthMsg
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.DeveloperPubKey = append(m.DeveloperPubKey[:0], dAtA[iNdEx:postIndex]...)
+			if m.DeveloperPubKey == nil {
+				m.DeveloperPubKey = []byte{}
+			}
+			iNdEx = postIndex
+		case 6:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field DeveloperSignature", wireType)
+			}
+			var byteLen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowMsg
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				byteLen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if byteLen < 0 {
+				return ErrInvalidLengthMsg
+			}
+			postIndex := iNdEx + byteLen
+			if postIndex < 0 {
+				return ErrInvalidLengthMsg
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.DeveloperSignature = append(m.DeveloperSignature[:0], dAtA[iNdEx:postIndex]...)
+			if m.DeveloperSignature == nil {
+				m.DeveloperSignature = []byte{}
+			}
+			iNdEx = postIndex
 		default:
 			iNdEx = preIndex
 			skippy, err := skipMsg(dAtA[iNdEx:])
@@ -2350,6 +2479,40 @@ func (m *MsgInstantiateContract) Unmarshal(dAtA []byte) error {
 			}
 			m.Admin = string(dAtA[iNdEx:postIndex])
 			iNdEx = postIndex
+		case 9:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field Salt", wireType)
+			}
+			var byteLen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowMsg
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				byteLen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if byteLen < 0 {
+				return ErrInvalidLengthMsg
+			}
+			postIndex := iNdEx + byteLen
+			if postIndex < 0 {
+				return ErrInvalidLengthMsg
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.Salt = append(m.Salt[:0], dAtA[iNdEx:postIndex]...)
+			if m.Salt == nil {
+				m.Salt = []byte{}
+			}
+			iNdEx = postIndex
 		default:
 			iNdEx = preIndex
 			skippy, err := skipMsg(dAtA[iNdEx:])
@@ -2718,6 +2881,38 @@ func (m *MsgExecuteContract) Unmarshal(dAtA []byte) error {
 				m.CallbackSig = []byte{}
 			}
 			iNdEx = postIndex
+		case 7:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field AccessList", wireType)
+			}
+			var byteLen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowMsg
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				byteLen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if byteLen < 0 {
+				return ErrInvalidLengthMsg
+			}
+			postIndex := iNdEx + byteLen
+			if postIndex < 0 {
+				return ErrInvalidLengthMsg
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.AccessList = append(m.AccessList, make([]byte, postIndex-iNdEx))
+			copy(m.AccessList[len(m.AccessList)-1], dAtA[iNdEx:postIndex])
+			iNdEx = postIndex
 		default:
 			iNdEx = preIndex
 			skippy, err := skipMsg(dAtA[iNdEx:])
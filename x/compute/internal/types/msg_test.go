@@ -1,6 +1,7 @@
 package types
 
 import (
+	"bytes"
 	"strings"
 	"testing"
 
@@ -104,6 +105,40 @@ func TestStoreCodeValidation(t *testing.T) {
 			},
 			valid: false,
 		},
+		"developer signature": {
+			msg: MsgStoreCode{
+				Sender:             goodAddress,
+				WASMByteCode:       []byte("foo"),
+				DeveloperPubKey:    make([]byte, 33),
+				DeveloperSignature: make([]byte, 64),
+			},
+			valid: true,
+		},
+		"developer key without signature": {
+			msg: MsgStoreCode{
+				Sender:          goodAddress,
+				WASMByteCode:    []byte("foo"),
+				DeveloperPubKey: make([]byte, 33),
+			},
+			valid: false,
+		},
+		"developer signature without key": {
+			msg: MsgStoreCode{
+				Sender:             goodAddress,
+				WASMByteCode:       []byte("foo"),
+				DeveloperSignature: make([]byte, 64),
+			},
+			valid: false,
+		},
+		"invalid developer key": {
+			msg: MsgStoreCode{
+				Sender:             goodAddress,
+				WASMByteCode:       []byte("foo"),
+				DeveloperPubKey:    make([]byte, 32),
+				DeveloperSignature: make([]byte, 64),
+			},
+			valid: false,
+		},
 		/*
 			"invalid InstantiatePermission": {
 				msg: MsgStoreCode{
@@ -203,6 +238,26 @@ func TestInstantiateContractValidation(t *testing.T) {
 			},
 			valid: false,
 		},
+		"with salt": {
+			msg: MsgInstantiateContract{
+				Sender:  goodAddress,
+				CodeID:  1,
+				Label:   "foo",
+				InitMsg: []byte("{}"),
+				Salt:    bytes.Repeat([]byte{1}, MaxInstantiateSaltLength),
+			},
+			valid: true,
+		},
+		"salt too long": {
+			msg: MsgInstantiateContract{
+				Sender:  goodAddress,
+				CodeID:  1,
+				Label:   "foo",
+				InitMsg: []byte("{}"),
+				Salt:    bytes.Repeat([]byte{1}, MaxInstantiateSaltLength+1),
+			},
+			valid: false,
+		},
 		/*
 			"non json init msg": {
 				msg: MsgInstantiateContract{
@@ -315,6 +370,42 @@ func TestExecuteContractValidation(t *testing.T) {
 			},
 			valid: false,
 		},
+		"access list": {
+			msg: MsgExecuteContract{
+				Sender:     goodAddress,
+				Contract:   goodAddress,
+				Msg:        []byte(`{"some": "data"}`),
+				AccessList: [][]byte{[]byte("a"), []byte("b")},
+			},
+			valid: true,
+		},
+		"empty access list key": {
+			msg: MsgExecuteContract{
+				Sender:     goodAddress,
+				Contract:   goodAddress,
+				Msg:        []byte(`{"some": "data"}`),
+				AccessList: [][]byte{[]byte("a"), {}},
+			},
+			valid: false,
+		},
+		"duplicate access list key": {
+			msg: MsgExecuteContract{
+				Sender:     goodAddress,
+				Contract:   goodAddress,
+				Msg:        []byte(`{"some": "data"}`),
+				AccessList: [][]byte{[]byte("a"), []byte("a")},
+			},
+			valid: false,
+		},
+		"too many access list keys": {
+			msg: MsgExecuteContract{
+				Sender:     goodAddress,
+				Contract:   goodAddress,
+				Msg:        []byte(`{"some": "data"}`),
+				AccessList: make([][]byte, MaxAccessListKeys+1),
+			},
+			valid: false,
+		},
 		/*
 			"non json msg": {
 				msg: MsgExecuteContract{
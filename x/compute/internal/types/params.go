@@ -0,0 +1,275 @@
+package types
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"strings"
+
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	paramtypes "github.com/cosmos/cosmos-sdk/x/params/types"
+)
+
+var (
+	KeyMaxResponseDataSize      = []byte("MaxResponseDataSize")
+	KeyMaxQueryResponseSize     = []byte("MaxQueryResponseSize")
+	KeyBulkMemoryHeight         = []byte("BulkMemoryHeight")
+	KeyContractVersionHeight    = []byte("ContractVersionHeight")
+	KeyConsensusConfigHash      = []byte("ConsensusConfigHash")
+	KeyFundsDenomAllowlist      = []byte("FundsDenomAllowlist")
+	KeyFundsNormalizationHeight = []byte("FundsNormalizationHeight")
+	KeyIBCEntryPointsHeight     = []byte("IBCEntryPointsHeight")
+	KeyAccessListHeight         = []byte("AccessListHeight")
+	KeyQueryRewrapHeight        = []byte("QueryRewrapHeight")
+	KeyEventValidationHeight    = []byte("EventValidationHeight")
+	KeyStateCompressionHeight   = []byte("StateCompressionHeight")
+	KeyStackHeightLimitHeight   = []byte("StackHeightLimitHeight")
+	KeyMemoryGrowthLimitHeight  = []byte("MemoryGrowthLimitHeight")
+	KeyGasForwardingHeight      = []byte("GasForwardingHeight")
+	KeyContractObservers        = []byte("ContractObservers")
+	KeyDeveloperSignatureHeight = []byte("DeveloperSignatureHeight")
+	KeyRevertReasonsHeight      = []byte("RevertReasonsHeight")
+	KeyQueryRewrapGraceBlocks   = []byte("QueryRewrapGraceBlocks")
+	KeyStateKeyDecryptionHeight = []byte("StateKeyDecryptionHeight")
+)
+
+// The changes of the module and the enclave that alter the results of transactions. The keeper
+// passes the ones active at the height of the block to the enclave, see Params.ActiveFeatures.
+const (
+	FeatureContractVersion    = "contract_version"
+	FeatureFundsNormalization = "funds_normalization"
+	FeatureIBCEntryPoints     = "ibc_entry_points"
+	FeatureAccessList         = "access_list"
+	FeatureQueryRewrap        = "query_rewrap"
+	FeatureEventValidation    = "event_validation"
+	FeatureStateCompression   = "state_compression"
+	FeatureStackHeightLimit   = "stack_height_limit"
+	FeatureMemoryGrowthLimit  = "memory_growth_limit"
+	FeatureGasForwarding      = "gas_forwarding"
+	FeatureRevertReasons      = "revert_reasons"
+	FeatureBulkMemory         = "bulk_memory"
+	FeatureStateKeyDecryption = "state_key_decryption"
+)
+
+var _ paramtypes.ParamSet = &Params{}
+
+// ParamKeyTable returns the key table of the compute params
+func ParamKeyTable() paramtypes.KeyTable {
+	return paramtypes.NewKeyTable().RegisterParamSet(&Params{})
+}
+
+// DefaultParams disables all the limits, so a chain upgrading to a version with params runs its
+// contracts like before until governance sets them
+func DefaultParams() Params {
+	return Params{}
+}
+
+// ValidateBasic checks the params are valid
+func (p Params) ValidateBasic() error {
+	if err := validateUint64(p.MaxResponseDataSize); err != nil {
+		return err
+	}
+	if err := validateUint64(p.MaxQueryResponseSize); err != nil {
+		return err
+	}
+	if err := validateHeight(p.BulkMemoryHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.ContractVersionHeight); err != nil {
+		return err
+	}
+	if err := validateConsensusConfigHash(p.ConsensusConfigHash); err != nil {
+		return err
+	}
+	if err := validateDenoms(p.FundsDenomAllowlist); err != nil {
+		return err
+	}
+	if err := validateHeight(p.FundsNormalizationHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.IBCEntryPointsHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.AccessListHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.QueryRewrapHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.EventValidationHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.StateCompressionHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.StackHeightLimitHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.MemoryGrowthLimitHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.GasForwardingHeight); err != nil {
+		return err
+	}
+	if err := validateContractObservers(p.ContractObservers); err != nil {
+		return err
+	}
+	if err := validateHeight(p.DeveloperSignatureHeight); err != nil {
+		return err
+	}
+	if err := validateHeight(p.RevertReasonsHeight); err != nil {
+		return err
+	}
+	if err := validateUint64(p.QueryRewrapGraceBlocks); err != nil {
+		return err
+	}
+	return validateHeight(p.StateKeyDecryptionHeight)
+}
+
+// ParamSetPairs implements params.ParamSet
+func (p *Params) ParamSetPairs() paramtypes.ParamSetPairs {
+	return paramtypes.ParamSetPairs{
+		paramtypes.NewParamSetPair(KeyMaxResponseDataSize, &p.MaxResponseDataSize, validateUint64),
+		paramtypes.NewParamSetPair(KeyMaxQueryResponseSize, &p.MaxQueryResponseSize, validateUint64),
+		paramtypes.NewParamSetPair(KeyBulkMemoryHeight, &p.BulkMemoryHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyContractVersionHeight, &p.ContractVersionHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyConsensusConfigHash, &p.ConsensusConfigHash, validateConsensusConfigHash),
+		paramtypes.NewParamSetPair(KeyFundsDenomAllowlist, &p.FundsDenomAllowlist, validateDenoms),
+		paramtypes.NewParamSetPair(KeyFundsNormalizationHeight, &p.FundsNormalizationHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyIBCEntryPointsHeight, &p.IBCEntryPointsHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyAccessListHeight, &p.AccessListHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyQueryRewrapHeight, &p.QueryRewrapHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyEventValidationHeight, &p.EventValidationHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyStateCompressionHeight, &p.StateCompressionHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyStackHeightLimitHeight, &p.StackHeightLimitHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyMemoryGrowthLimitHeight, &p.MemoryGrowthLimitHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyGasForwardingHeight, &p.GasForwardingHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyContractObservers, &p.ContractObservers, validateContractObservers),
+		paramtypes.NewParamSetPair(KeyDeveloperSignatureHeight, &p.DeveloperSignatureHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyRevertReasonsHeight, &p.RevertReasonsHeight, validateHeight),
+		paramtypes.NewParamSetPair(KeyQueryRewrapGraceBlocks, &p.QueryRewrapGraceBlocks, validateUint64),
+		paramtypes.NewParamSetPair(KeyStateKeyDecryptionHeight, &p.StateKeyDecryptionHeight, validateHeight),
+	}
+}
+
+// ActiveFeatures returns the enclave features activated at or before height. A feature whose
+// height is zero is never activated.
+func (p Params) ActiveFeatures(height int64) []string {
+	var features []string
+	for _, feature := range []struct {
+		name   string
+		height int64
+	}{
+		{FeatureContractVersion, p.ContractVersionHeight},
+		{FeatureFundsNormalization, p.FundsNormalizationHeight},
+		{FeatureIBCEntryPoints, p.IBCEntryPointsHeight},
+		{FeatureAccessList, p.AccessListHeight},
+		{FeatureQueryRewrap, p.QueryRewrapHeight},
+		{FeatureEventValidation, p.EventValidationHeight},
+		{FeatureStateCompression, p.StateCompressionHeight},
+		{FeatureStackHeightLimit, p.StackHeightLimitHeight},
+		{FeatureMemoryGrowthLimit, p.MemoryGrowthLimitHeight},
+		{FeatureGasForwarding, p.GasForwardingHeight},
+		{FeatureRevertReasons, p.RevertReasonsHeight},
+		{FeatureBulkMemory, p.BulkMemoryHeight},
+		{FeatureStateKeyDecryption, p.StateKeyDecryptionHeight},
+	} {
+		if feature.height > 0 && height >= feature.height {
+			features = append(features, feature.name)
+		}
+	}
+	return features
+}
+
+// IsActive reports whether an enclave feature is activated at height
+func (p Params) IsActive(feature string, height int64) bool {
+	for _, active := range p.ActiveFeatures(height) {
+		if active == feature {
+			return true
+		}
+	}
+	return false
+}
+
+// ObserverOf returns the x25519 public key of the observer governance approved for a contract, or
+// nil if it has none
+func (p Params) ObserverOf(contractAddress sdk.AccAddress) []byte {
+	for _, entry := range p.ContractObservers {
+		address, observer, err := parseContractObserver(entry)
+		if err == nil && address.Equals(contractAddress) {
+			return observer
+		}
+	}
+	return nil
+}
+
+func parseContractObserver(entry string) (sdk.AccAddress, []byte, error) {
+	parts := strings.Split(entry, ":")
+	if len(parts) != 2 {
+		return nil, nil, fmt.Errorf("contract observer must be <contract address>:<public key>: %s", entry)
+	}
+	address, err := sdk.AccAddressFromBech32(parts[0])
+	if err != nil {
+		return nil, nil, fmt.Errorf("invalid contract address of observer %s: %w", entry, err)
+	}
+	observer, err := hex.DecodeString(parts[1])
+	if err != nil || len(observer) != 32 {
+		return nil, nil, fmt.Errorf("observer public key must be 32 hex encoded bytes: %s", entry)
+	}
+	return address, observer, nil
+}
+
+func validateUint64(i interface{}) error {
+	if _, ok := i.(uint64); !ok {
+		return fmt.Errorf("invalid parameter type: %T", i)
+	}
+	return nil
+}
+
+func validateHeight(i interface{}) error {
+	height, ok := i.(int64)
+	if !ok {
+		return fmt.Errorf("invalid parameter type: %T", i)
+	}
+	if height < 0 {
+		return fmt.Errorf("height must not be negative: %d", height)
+	}
+	return nil
+}
+
+func validateConsensusConfigHash(i interface{}) error {
+	hash, ok := i.([]byte)
+	if !ok {
+		return fmt.Errorf("invalid parameter type: %T", i)
+	}
+	if len(hash) != 0 && len(hash) != sha256.Size {
+		return fmt.Errorf("consensus config hash must be %d bytes: %d", sha256.Size, len(hash))
+	}
+	return nil
+}
+
+func validateDenoms(i interface{}) error {
+	denoms, ok := i.([]string)
+	if !ok {
+		return fmt.Errorf("invalid parameter type: %T", i)
+	}
+	for _, denom := range denoms {
+		if err := sdk.ValidateDenom(denom); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func validateContractObservers(i interface{}) error {
+	observers, ok := i.([]string)
+	if !ok {
+		return fmt.Errorf("invalid parameter type: %T", i)
+	}
+	for _, entry := range observers {
+		if _, _, err := parseContractObserver(entry); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,43 @@
+package types
+
+import (
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	govtypes "github.com/cosmos/cosmos-sdk/x/gov/types"
+)
+
+// ProposalTypeRemoveCode is the type of the proposals removing a code
+const ProposalTypeRemoveCode = "RemoveCode"
+
+func init() {
+	govtypes.RegisterProposalType(ProposalTypeRemoveCode)
+}
+
+var _ govtypes.Content = &RemoveCodeProposal{}
+
+// NewRemoveCodeProposal constructor
+func NewRemoveCodeProposal(title, description string, codeID uint64) *RemoveCodeProposal {
+	return &RemoveCodeProposal{Title: title, Description: description, CodeID: codeID}
+}
+
+// GetTitle returns the title of the proposal
+func (p RemoveCodeProposal) GetTitle() string { return p.Title }
+
+// GetDescription returns the description of the proposal
+func (p RemoveCodeProposal) GetDescription() string { return p.Description }
+
+// ProposalRoute returns the routing key of the proposal
+func (p RemoveCodeProposal) ProposalRoute() string { return RouterKey }
+
+// ProposalType returns the type of the proposal
+func (p RemoveCodeProposal) ProposalType() string { return ProposalTypeRemoveCode }
+
+// ValidateBasic validates the proposal
+func (p RemoveCodeProposal) ValidateBasic() error {
+	if err := govtypes.ValidateAbstract(p); err != nil {
+		return err
+	}
+	if p.CodeID == 0 {
+		return sdkerrors.Wrap(ErrInvalid, "code id is required")
+	}
+	return nil
+}
@@ -345,6 +345,8 @@ type CodeInfoResponse struct {
 	CodeHash string `protobuf:"bytes,3,opt,name=code_hash,json=codeHash,proto3" json:"code_hash,omitempty"`
 	Source   string `protobuf:"bytes,4,opt,name=source,proto3" json:"source,omitempty"`
 	Builder  string `protobuf:"bytes,5,opt,name=builder,proto3" json:"builder,omitempty"`
+	// developer_pub_key is the hex encoded key of the developer who signed the code, if it was signed
+	DeveloperPubKey string `protobuf:"bytes,6,opt,name=developer_pub_key,json=developerPubKey,proto3" json:"developer_pub_key,omitempty"`
 }
 
 func (m *CodeInfoResponse) Reset()         { *m = CodeInfoResponse{} }
@@ -753,85 +755,86 @@ func init() {
 }
 
 var fileDescriptor_7735281c5fa969d4 = []byte{
-	// 1245 bytes of a gzipped FileDescriptorProto
-	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xc4, 0x57, 0xcf, 0x6f, 0x1b, 0xc5,
-	0x17, 0xf7, 0xa4, 0x4e, 0xd2, 0x4c, 0x7e, 0x76, 0xbe, 0x69, 0xea, 0x3a, 0xfd, 0x3a, 0xed, 0x52,
-	0xc8, 0xaf, 0xe2, 0xad, 0x9d, 0x50, 0xa4, 0x8a, 0x4b, 0x92, 0x46, 0x6a, 0x50, 0x28, 0xe0, 0x1c,
-	0x90, 0x50, 0x91, 0x35, 0x5e, 0x4f, 0xec, 0x55, 0x9d, 0x9d, 0xed, 0xce, 0x38, 0x89, 0x85, 0xc2,
-	0x81, 0x13, 0x47, 0x24, 0xe0, 0x80, 0x7a, 0xe1, 0x04, 0x15, 0x07, 0x24, 0xae, 0xfc, 0x05, 0x39,
-	0x70, 0x88, 0xc4, 0x85, 0x53, 0x05, 0x09, 0x07, 0xc4, 0x9d, 0x3b, 0xda, 0x37, 0xb3, 0x9b, 0xb5,
-	0xbd, 0x8e, 0xed, 0x72, 0xe0, 0xb6, 0x33, 0xf3, 0xe6, 0x7d, 0x3e, 0xf3, 0x79, 0x6f, 0xde, 0x9b,
-	0xc5, 0x86, 0x60, 0x96, 0xc7, 0xa4, 0x69, 0xf1, 0x3d, 0xb7, 0x2e, 0x99, 0xb9, 0x9f, 0x2b, 0x31,
-	0x49, 0x73, 0xe6, 0xd3, 0x3a, 0xf3, 0x1a, 0x59, 0xd7, 0xe3, 0x92, 0x93, 0x19, 0x65, 0x93, 0xd5,
-	0x36, 0x59, 0x6d, 0x93, 0x9e, 0xae, 0xf0, 0x0a, 0x07, 0x13, 0xd3, 0xff, 0x52, 0xd6, 0xe9, 0x4e,
-	0x1e, 0x65, 0xc3, 0x65, 0x42, 0xdb, 0xcc, 0x56, 0x38, 0xaf, 0xd4, 0x98, 0x09, 0xa3, 0x52, 0x7d,
-	0xd7, 0x64, 0x7b, 0xae, 0xd4, 0x70, 0xe9, 0x1b, 0x7a, 0x91, 0xba, 0xb6, 0x49, 0x1d, 0x87, 0x4b,
-	0x2a, 0x6d, 0xee, 0x04, 0x5b, 0x5f, 0xb1, 0xb8, 0xd8, 0xe3, 0xc2, 0x2c, 0x51, 0xc1, 0x4c, 0x5a,
-	0xb2, 0xec, 0x10, 0xc0, 0x1f, 0x68, 0xa3, 0xa5, 0xa8, 0x11, 0x1c, 0x25, 0xb4, 0x72, 0x69, 0xc5,
-	0x76, 0xc0, 0xa3, 0xb2, 0x35, 0x3e, 0xc2, 0xe9, 0xf7, 0x7d, 0x8b, 0x1d, 0xa0, 0xbd, 0xc1, 0x1d,
-	0xe9, 0x51, 0x4b, 0x16, 0xd8, 0xd3, 0x3a, 0x13, 0x92, 0x2c, 0xe2, 0x29, 0x4b, 0x4f, 0x15, 0x69,
-	0xb9, 0xec, 0x31, 0x21, 0x52, 0xe8, 0x26, 0x5a, 0x18, 0x29, 0x4c, 0x06, 0xf3, 0x6b, 0x6a, 0x9a,
-	0x4c, 0xe3, 0x41, 0x80, 0x4a, 0x0d, 0xdc, 0x44, 0x0b, 0x63, 0x05, 0x35, 0x30, 0x96, 0xf1, 0xff,
-	0xc0, 0xfd, 0x7a, 0x63, 0x9b, 0x96, 0x58, 0x2d, 0xf0, 0x3b, 0x8d, 0x07, 0x6b, 0xfe, 0x58, 0x3b,
-	0x53, 0x03, 0xe3, 0x6d, 0xfc, 0x7f, 0x6d, 0xbc, 0xd1, 0xec, 0xbc, 0x7f, 0x3a, 0x86, 0x89, 0xa7,
-	0x43, 0x5f, 0x65, 0xb6, 0x55, 0x0e, 0x5c, 0x5c, 0xc3, 0xc3, 0x16, 0x2f, 0xb3, 0xa2, 0x5d, 0x86,
-	0x9d, 0xc9, 0xc2, 0x90, 0x05, 0xeb, 0x46, 0x0e, 0xcf, 0xc6, 0x0a, 0x21, 0x5c, 0xee, 0x08, 0x46,
-	0x08, 0x4e, 0x96, 0xa9, 0xa4, 0xb0, 0x69, 0xac, 0x00, 0xdf, 0xc6, 0x33, 0x84, 0xaf, 0xc3, 0x9e,
-	0xc0, 0x7a, 0xcb, 0xd9, 0xe5, 0xe1, 0x8e, 0x3e, 0xb4, 0xdb, 0xc1, 0xe3, 0xa1, 0xa9, 0xed, 0xec,
-	0x72, 0xd0, 0x70, 0x34, 0x7f, 0x3b, 0x1b, 0x9f, 0x7a, 0xd9, 0x28, 0xde, 0xfa, 0xe5, 0x93, 0x17,
-	0x73, 0xe8, 0xaf, 0x17, 0x73, 0x89, 0xc2, 0x98, 0x15, 0x99, 0x37, 0xbe, 0x46, 0xf8, 0x5a, 0xd4,
-	0xf0, 0x03, 0x5b, 0x56, 0x03, 0xc0, 0xff, 0x9a, 0xdb, 0x27, 0x38, 0xd3, 0x24, 0x9c, 0x38, 0x0f,
-	0x93, 0x56, 0xef, 0x31, 0x9e, 0x68, 0x82, 0xf5, 0xf9, 0x5d, 0x5a, 0x18, 0xcd, 0x9b, 0xbd, 0xe0,
-	0x46, 0x8e, 0xba, 0x9e, 0x3c, 0xf6, 0xe1, 0xc7, 0xa3, 0xf0, 0xc2, 0xf8, 0x12, 0xe1, 0x29, 0x00,
-	0x8c, 0x06, 0xac, 0x53, 0x6a, 0x90, 0x14, 0x1e, 0xb6, 0x3c, 0x46, 0x25, 0xf7, 0xe0, 0xf0, 0x23,
-	0x85, 0x60, 0x48, 0x66, 0xf1, 0x08, 0x6c, 0xa9, 0x52, 0x51, 0x4d, 0x5d, 0x82, 0xb5, 0xcb, 0xfe,
-	0xc4, 0x43, 0x2a, 0xaa, 0x64, 0x06, 0x0f, 0x09, 0x5e, 0xf7, 0x2c, 0x96, 0x4a, 0xc2, 0x8a, 0x1e,
-	0xf9, 0xee, 0x4a, 0x75, 0xbb, 0x56, 0x66, 0x5e, 0x6a, 0x50, 0xb9, 0xd3, 0x43, 0xe3, 0x10, 0x5f,
-	0xd1, 0xb2, 0x94, 0x59, 0x48, 0xeb, 0x5d, 0x8d, 0x01, 0xe2, 0x23, 0x10, 0x7f, 0xa1, 0xb3, 0x08,
-	0xcd, 0x67, 0x8a, 0x04, 0x00, 0x78, 0xf9, 0x6b, 0x7e, 0x2a, 0x1f, 0x50, 0xb1, 0xa7, 0x2f, 0x2a,
-	0x7c, 0x1b, 0x16, 0x26, 0x21, 0xb2, 0x08, 0xa1, 0xdf, 0xc1, 0x38, 0x84, 0x0e, 0x02, 0xd0, 0x3b,
-	0xb6, 0x52, 0x7e, 0x24, 0xc0, 0x15, 0xc6, 0x16, 0xbe, 0xd1, 0x14, 0xf5, 0xf0, 0x76, 0xf7, 0x7d,
-	0x63, 0x8c, 0xbc, 0x2e, 0x5b, 0x81, 0x2b, 0x5d, 0x5d, 0xb4, 0xa3, 0xf8, 0xf2, 0xb2, 0x8a, 0xaf,
-	0x86, 0x67, 0xf4, 0x03, 0x14, 0x9a, 0x37, 0x45, 0x11, 0x35, 0x47, 0xd1, 0xf8, 0x0a, 0xe1, 0xc9,
-	0x07, 0xcc, 0xf2, 0x1a, 0xae, 0x64, 0xe5, 0x35, 0x47, 0x1c, 0x30, 0xcf, 0x57, 0xd0, 0xaf, 0xe7,
-	0xda, 0x16, 0xbe, 0x7d, 0x4c, 0xdb, 0x71, 0xeb, 0x52, 0xa7, 0x88, 0x1a, 0x90, 0x39, 0x3c, 0xca,
-	0xeb, 0xd2, 0xad, 0xcb, 0x22, 0x54, 0x0f, 0x95, 0x22, 0x58, 0x4d, 0x3d, 0xa0, 0x92, 0x92, 0x1c,
-	0xbe, 0x1a, 0x31, 0x28, 0x52, 0x51, 0x14, 0xd2, 0xb3, 0x9d, 0x8a, 0xce, 0x19, 0x72, 0x6e, 0xba,
-	0x26, 0x76, 0x60, 0xe5, 0x7e, 0xf2, 0xcf, 0x6f, 0xe6, 0x12, 0xc6, 0xdf, 0x08, 0x4f, 0xb5, 0xf0,
-	0x12, 0x64, 0x0d, 0x0f, 0x53, 0xf5, 0xa9, 0xa3, 0x35, 0xdf, 0x29, 0x5a, 0x2d, 0x5b, 0x0b, 0xc1,
-	0x3e, 0xb2, 0x1d, 0x32, 0xae, 0xf1, 0x8a, 0x48, 0x0d, 0x80, 0x9b, 0x57, 0xb3, 0xaa, 0xa5, 0x64,
-	0xfd, 0x96, 0x92, 0x85, 0x56, 0x13, 0x38, 0x52, 0xa4, 0x36, 0xf7, 0x99, 0x23, 0x75, 0xc4, 0xf5,
-	0xf1, 0xb6, 0x79, 0x45, 0x90, 0x5b, 0x78, 0x4c, 0x7b, 0x63, 0x9e, 0xc7, 0x3d, 0x2d, 0x80, 0x46,
-	0xd8, 0xf4, 0xa7, 0xc8, 0x3c, 0x9e, 0x74, 0x6b, 0xd4, 0x76, 0x24, 0x3b, 0x0c, 0xac, 0xd4, 0xd9,
-	0x27, 0xc2, 0x69, 0x30, 0xd4, 0xe7, 0x7e, 0xa4, 0xeb, 0x74, 0x10, 0xf9, 0x87, 0xb6, 0x90, 0xdc,
-	0x6b, 0xf4, 0xdf, 0x22, 0xb4, 0xbf, 0xfd, 0x96, 0xa4, 0x0c, 0xfd, 0xe9, 0xe4, 0x78, 0x0f, 0x0f,
-	0x33, 0x47, 0x7a, 0x36, 0x0b, 0x24, 0xbd, 0xdb, 0xad, 0x02, 0x41, 0x7e, 0x29, 0x2f, 0x9b, 0x8e,
-	0xf4, 0x1a, 0x5a, 0x96, 0xc0, 0x8d, 0xc2, 0xcd, 0x3f, 0x1b, 0xc7, 0x83, 0x00, 0x4c, 0xbe, 0x47,
-	0x78, 0x2c, 0x5a, 0xbd, 0xc8, 0x1b, 0x9d, 0x10, 0x2e, 0xec, 0x8e, 0xe9, 0xdc, 0x85, 0xdb, 0xe2,
-	0x7a, 0x94, 0x71, 0xf7, 0xd3, 0x5f, 0xfe, 0xf8, 0x62, 0x60, 0x89, 0x2c, 0xb4, 0xbd, 0x57, 0xfc,
-	0x2b, 0x6f, 0x7e, 0xdc, 0x2a, 0xe5, 0x11, 0xf9, 0x0e, 0xe1, 0x2b, 0x6d, 0x55, 0x9b, 0xdc, 0xe9,
-	0xca, 0x38, 0xd2, 0x83, 0xd3, 0xf7, 0x7a, 0x22, 0xda, 0xd6, 0x13, 0x8c, 0x3b, 0xc0, 0xf6, 0x35,
-	0x72, 0xbb, 0x8d, 0x6d, 0xc0, 0x53, 0xf8, 0x94, 0xa1, 0x84, 0x1f, 0x91, 0x1f, 0x91, 0x7e, 0x7b,
-	0x34, 0x77, 0x74, 0x92, 0xbf, 0x10, 0x3d, 0xf6, 0x1d, 0x94, 0x5e, 0xe9, 0x6b, 0x8f, 0xa6, 0x9b,
-	0x03, 0xba, 0xcb, 0x64, 0x31, 0xfe, 0x79, 0x19, 0xa7, 0xee, 0x67, 0x08, 0x27, 0xfd, 0x43, 0xf7,
-	0x29, 0xe8, 0x62, 0x17, 0x41, 0xcf, 0xbb, 0x89, 0x31, 0x0f, 0xa4, 0x6e, 0x91, 0xb9, 0x18, 0x0d,
-	0xcb, 0x2c, 0x22, 0xdf, 0x13, 0x3c, 0x08, 0xcd, 0x80, 0xcc, 0x64, 0xd5, 0x8b, 0x34, 0x1b, 0x3c,
-	0x57, 0xb3, 0x9b, 0xfe, 0x73, 0x35, 0xbd, 0xd4, 0x15, 0x34, 0xac, 0xec, 0x46, 0x06, 0x50, 0x53,
-	0x64, 0x26, 0x16, 0x55, 0x90, 0x9f, 0x11, 0xbe, 0x1e, 0x94, 0xe5, 0xb6, 0xfc, 0x7e, 0xd9, 0xfb,
-	0xf0, 0x7a, 0x57, 0x82, 0xd1, 0x2e, 0x60, 0x6c, 0x01, 0xc7, 0x0d, 0xb2, 0x16, 0xcb, 0x11, 0x9a,
-	0x83, 0x59, 0x6a, 0x14, 0x5b, 0x83, 0x16, 0x17, 0xc6, 0xe7, 0xfa, 0x79, 0x11, 0x1c, 0xe7, 0x25,
-	0xee, 0x48, 0x9f, 0xe4, 0xdf, 0x04, 0xf2, 0x39, 0x62, 0x76, 0x23, 0x0f, 0xd1, 0x8d, 0x84, 0xf9,
-	0x07, 0x84, 0x27, 0xa0, 0x79, 0xae, 0x37, 0xfe, 0xa5, 0xdc, 0xf9, 0x9e, 0x6e, 0x75, 0x53, 0xa3,
-	0xbe, 0xe0, 0x8a, 0x40, 0xcb, 0x8e, 0xd3, 0xf6, 0x5b, 0x84, 0x27, 0x82, 0xb7, 0x9d, 0xfa, 0xa9,
-	0x20, 0xcb, 0x5d, 0x08, 0x47, 0x7f, 0x3d, 0xd2, 0xab, 0x3d, 0xd1, 0x6c, 0x79, 0x9a, 0x5c, 0x40,
-	0xb4, 0x3d, 0x1f, 0x80, 0xfa, 0x11, 0xf9, 0x09, 0xe1, 0xc9, 0x96, 0xa6, 0x42, 0x56, 0x7a, 0x02,
-	0x6f, 0x6e, 0x69, 0x3d, 0x32, 0x6e, 0xe9, 0x5b, 0xc6, 0x5b, 0xc0, 0xf8, 0x1e, 0x59, 0xed, 0xcc,
-	0xb8, 0xaa, 0xb6, 0xc4, 0xa8, 0xbc, 0xfe, 0xf8, 0xf8, 0xf7, 0x4c, 0xe2, 0xf9, 0x69, 0x06, 0x1d,
-	0x9f, 0x66, 0xd0, 0xc9, 0x69, 0x06, 0xfd, 0x76, 0x9a, 0x41, 0x9f, 0x9f, 0x65, 0x12, 0x27, 0x67,
-	0x99, 0xc4, 0xaf, 0x67, 0x99, 0xc4, 0x87, 0xf7, 0x2b, 0xb6, 0xac, 0xd6, 0x4b, 0x3e, 0x29, 0x53,
-	0x58, 0x9e, 0xac, 0xd1, 0x92, 0x30, 0x55, 0x25, 0x7c, 0xc4, 0xe4, 0x01, 0xf7, 0x9e, 0x98, 0x87,
-	0x21, 0xb4, 0xdf, 0xc6, 0x3d, 0x87, 0xd6, 0xd4, 0x6f, 0x70, 0x69, 0x08, 0x4a, 0xc9, 0xca, 0x3f,
-	0x01, 0x00, 0x00, 0xff, 0xff, 0x66, 0x4a, 0xf1, 0xe3, 0x7f, 0x0f, 0x00, 0x00,
+	// 1257 bytes of a gzipped FileDescriptorProto
+	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xc5, 0x57, 0x3d, 0x6c, 0x1c, 0x45,
+	0x14, 0xf6, 0x26, 0x67, 0x3b, 0x9e, 0xf8, 0x27, 0x1e, 0x1c, 0xe7, 0x38, 0x07, 0x9b, 0x2c, 0x01,
+	0xff, 0x85, 0xdd, 0x9c, 0x6d, 0x82, 0x14, 0xd1, 0xd8, 0x8e, 0xa5, 0x38, 0x98, 0x10, 0xce, 0x05,
+	0x12, 0x0a, 0x3a, 0xed, 0xcf, 0xe4, 0xbc, 0xf2, 0x79, 0x77, 0xd9, 0xd9, 0xb3, 0x7d, 0x42, 0xa6,
+	0xa0, 0xa2, 0x44, 0x42, 0x14, 0x28, 0x0d, 0x15, 0x44, 0x14, 0x48, 0xb4, 0x54, 0x94, 0x2e, 0x28,
+	0x2c, 0xd1, 0x50, 0x45, 0x40, 0x28, 0x10, 0x3d, 0x3d, 0x33, 0x6f, 0x66, 0xd6, 0x7b, 0x77, 0x7b,
+	0xbe, 0xbb, 0x50, 0x50, 0xac, 0x34, 0x3f, 0x6f, 0xde, 0xf7, 0xbd, 0xf7, 0x66, 0xde, 0x7b, 0x8b,
+	0x74, 0x4a, 0x9c, 0x88, 0xc4, 0xa6, 0x13, 0xec, 0x85, 0xb5, 0x98, 0x98, 0xfb, 0x45, 0x9b, 0xc4,
+	0x56, 0xd1, 0xfc, 0xa8, 0x46, 0xa2, 0xba, 0x11, 0x46, 0x41, 0x1c, 0xe0, 0x49, 0x21, 0x63, 0x48,
+	0x19, 0x43, 0xca, 0x14, 0x26, 0x2a, 0x41, 0x25, 0x00, 0x11, 0x93, 0x8f, 0x84, 0x74, 0xa1, 0x9d,
+	0xc6, 0xb8, 0x1e, 0x12, 0x2a, 0x65, 0xa6, 0x2a, 0x41, 0x50, 0xa9, 0x12, 0x13, 0x66, 0x76, 0xed,
+	0x91, 0x49, 0xf6, 0xc2, 0x58, 0xc2, 0x15, 0xae, 0xca, 0x4d, 0x2b, 0xf4, 0x4c, 0xcb, 0xf7, 0x83,
+	0xd8, 0x8a, 0xbd, 0xc0, 0x57, 0x47, 0x5f, 0x71, 0x02, 0xba, 0x17, 0x50, 0xd3, 0xb6, 0x28, 0x13,
+	0xb1, 0x1d, 0x2f, 0x01, 0xe0, 0x13, 0x29, 0xb4, 0x90, 0x16, 0x02, 0x53, 0x12, 0xa9, 0xd0, 0xaa,
+	0x78, 0x3e, 0x68, 0x14, 0xb2, 0xfa, 0x87, 0xa8, 0xf0, 0x1e, 0x97, 0xd8, 0x06, 0xda, 0xeb, 0x81,
+	0x1f, 0x47, 0x96, 0x13, 0x97, 0x08, 0x3b, 0x46, 0x63, 0x3c, 0x8f, 0x2e, 0x39, 0x72, 0xa9, 0x6c,
+	0xb9, 0x6e, 0x44, 0x28, 0xcd, 0x6b, 0x2f, 0x6b, 0x73, 0x43, 0xa5, 0x31, 0xb5, 0xbe, 0x2a, 0x96,
+	0xf1, 0x04, 0xea, 0x07, 0xa8, 0xfc, 0x39, 0xb6, 0x3f, 0x5c, 0x12, 0x13, 0x7d, 0x11, 0xbd, 0x00,
+	0xea, 0xd7, 0xea, 0x5b, 0x96, 0x4d, 0xaa, 0x4a, 0x2f, 0x13, 0xae, 0xf2, 0xb9, 0x54, 0x26, 0x26,
+	0xfa, 0x3d, 0xf4, 0x92, 0x14, 0x5e, 0x6f, 0x54, 0xde, 0x3b, 0x1d, 0xdd, 0x44, 0x13, 0x89, 0x2e,
+	0x97, 0x6c, 0xba, 0x4a, 0xc5, 0x15, 0x34, 0xe8, 0xb0, 0x85, 0xb2, 0xe7, 0xc2, 0xc9, 0x5c, 0x69,
+	0xc0, 0x81, 0x7d, 0xbd, 0x88, 0xa6, 0x32, 0x1d, 0x41, 0x43, 0xe6, 0x7d, 0x82, 0x31, 0xca, 0xb9,
+	0x56, 0x6c, 0xc1, 0xa1, 0xe1, 0x12, 0x8c, 0xf5, 0xc7, 0x1a, 0x7a, 0x11, 0xce, 0x28, 0xe9, 0x4d,
+	0xff, 0x51, 0x90, 0x9c, 0xe8, 0xc1, 0x77, 0xdb, 0x68, 0x24, 0x11, 0xf5, 0x98, 0x0e, 0xf0, 0xe1,
+	0xc5, 0xa5, 0xeb, 0x46, 0xf6, 0xd5, 0x33, 0xd2, 0x78, 0x6b, 0x17, 0x4e, 0x9e, 0xce, 0x68, 0x7f,
+	0x3f, 0x9d, 0xe9, 0x2b, 0x0d, 0x3b, 0xa9, 0x75, 0xfd, 0x2b, 0x0d, 0x5d, 0x49, 0x0b, 0xbe, 0xef,
+	0xc5, 0x3b, 0x0a, 0xf0, 0xff, 0xe6, 0xf6, 0x09, 0x9a, 0x6e, 0x70, 0x1c, 0x3d, 0x0d, 0x93, 0xf4,
+	0xde, 0x43, 0x34, 0xda, 0x00, 0xcb, 0xf9, 0x9d, 0x67, 0xb8, 0x66, 0x37, 0xb8, 0x29, 0x53, 0xd7,
+	0x72, 0xc7, 0x1c, 0x7e, 0x24, 0x0d, 0x4f, 0xf5, 0x9f, 0x34, 0x74, 0x09, 0x00, 0xd3, 0x01, 0x6b,
+	0x77, 0x35, 0x70, 0x9e, 0x6d, 0x44, 0xc4, 0x8a, 0x83, 0x08, 0x8c, 0x1f, 0x2a, 0xa9, 0x29, 0x9e,
+	0x42, 0x43, 0x70, 0x64, 0xc7, 0xa2, 0x3b, 0xf9, 0xf3, 0xb0, 0x77, 0x81, 0x2f, 0xdc, 0x65, 0x73,
+	0x3c, 0x89, 0x06, 0x68, 0x50, 0x8b, 0x1c, 0x92, 0xcf, 0xc1, 0x8e, 0x9c, 0x71, 0x75, 0x76, 0xcd,
+	0xab, 0xba, 0x24, 0xca, 0xf7, 0x0b, 0x75, 0x72, 0x8a, 0x17, 0xd0, 0xb8, 0x4b, 0xf6, 0x49, 0x35,
+	0x08, 0x49, 0x54, 0x0e, 0x6b, 0x76, 0x79, 0x97, 0xd4, 0xf3, 0x03, 0x22, 0x2e, 0xc9, 0xc6, 0x83,
+	0x9a, 0xfd, 0x36, 0xa9, 0xeb, 0x87, 0x68, 0x5c, 0xba, 0xd0, 0x25, 0x89, 0x09, 0xef, 0x4a, 0x3e,
+	0x10, 0x28, 0x0d, 0x02, 0x35, 0xd7, 0xde, 0x61, 0x8d, 0xf6, 0xa7, 0x82, 0x05, 0x36, 0xf0, 0x3d,
+	0x7e, 0xed, 0x0f, 0x2c, 0xba, 0x27, 0x1f, 0x35, 0x8c, 0x75, 0x07, 0xe1, 0x04, 0x99, 0x26, 0xd0,
+	0xef, 0x20, 0x94, 0x40, 0xab, 0x60, 0x75, 0x8f, 0x2d, 0xa2, 0x34, 0xa4, 0x70, 0xa9, 0xbe, 0x89,
+	0xae, 0x36, 0xdc, 0x90, 0x24, 0x13, 0xf4, 0xfc, 0xba, 0xf4, 0x25, 0x99, 0xe2, 0x94, 0x2a, 0x99,
+	0x89, 0xa4, 0xa2, 0xec, 0x54, 0xb4, 0x82, 0x2e, 0x27, 0x36, 0xf2, 0x60, 0x26, 0xe2, 0x0d, 0x11,
+	0xd7, 0x1a, 0x23, 0xae, 0x7f, 0xa9, 0xa1, 0xb1, 0x3b, 0xcc, 0xe2, 0x7a, 0x18, 0x13, 0x77, 0xd5,
+	0xa7, 0x07, 0x2c, 0xa6, 0xcc, 0x83, 0x3c, 0xf7, 0x4b, 0x59, 0x18, 0x73, 0x4c, 0xcf, 0x67, 0x0e,
+	0x91, 0xd7, 0x49, 0x4c, 0xf0, 0x0c, 0xba, 0x18, 0xd4, 0x62, 0x36, 0x2a, 0x43, 0xa6, 0x11, 0xd7,
+	0x09, 0x89, 0xa5, 0x3b, 0x6c, 0x05, 0x17, 0xd1, 0xe5, 0x94, 0x40, 0xd9, 0xa2, 0x65, 0x1a, 0x47,
+	0x9e, 0x5f, 0x91, 0xf7, 0x0b, 0x9f, 0x8a, 0xae, 0xd2, 0x6d, 0xd8, 0xb9, 0x9d, 0xfb, 0xeb, 0xeb,
+	0x99, 0x3e, 0xfd, 0x1f, 0x76, 0xdd, 0x9b, 0x78, 0x51, 0xbc, 0x8a, 0x06, 0x2d, 0x31, 0x94, 0xd1,
+	0x9a, 0x6d, 0x17, 0xad, 0xa6, 0xa3, 0x25, 0x75, 0x0e, 0x6f, 0x25, 0x8c, 0xab, 0x41, 0x85, 0x32,
+	0x6b, 0xb8, 0x9a, 0x57, 0x0d, 0x51, 0x7e, 0x0c, 0x5e, 0x7e, 0x0c, 0x28, 0x4b, 0x4a, 0x91, 0x20,
+	0xb5, 0xb1, 0x4f, 0xfc, 0x58, 0x46, 0x5c, 0x9a, 0xb7, 0xc5, 0x8e, 0xe3, 0x6b, 0x68, 0x58, 0x6a,
+	0x23, 0x51, 0xc4, 0xde, 0x9a, 0x70, 0x80, 0x44, 0xd8, 0xe0, 0x4b, 0x78, 0x16, 0x8d, 0x85, 0x55,
+	0xcb, 0xf3, 0x63, 0x72, 0xa8, 0xa4, 0x84, 0xed, 0xa3, 0xc9, 0x32, 0x08, 0x4a, 0xbb, 0xef, 0xcb,
+	0x9c, 0xae, 0x22, 0x7f, 0xd7, 0xa3, 0xec, 0xd5, 0xd6, 0x7b, 0x2f, 0x27, 0x52, 0xdf, 0x7e, 0xd3,
+	0xa5, 0x4c, 0xf4, 0xc9, 0xcb, 0xf1, 0x00, 0x0d, 0x32, 0xd3, 0x22, 0x8f, 0x28, 0x97, 0xde, 0xec,
+	0x94, 0xad, 0xe0, 0x7e, 0x09, 0x2d, 0x1b, 0x6c, 0xa5, 0x2e, 0xdd, 0xa2, 0xd4, 0x08, 0xdc, 0xa5,
+	0xc7, 0x23, 0xa8, 0x1f, 0x80, 0xf1, 0x77, 0x1a, 0x1a, 0x4e, 0x67, 0x3a, 0xfc, 0x46, 0x3b, 0x84,
+	0x33, 0x2b, 0x69, 0xa1, 0x78, 0xe6, 0xb1, 0xac, 0x7a, 0xa6, 0xdf, 0xfc, 0xf4, 0x97, 0x3f, 0xbf,
+	0x38, 0xb7, 0x80, 0xe7, 0x5a, 0x7a, 0x1b, 0xfe, 0xe4, 0xcd, 0x8f, 0x9b, 0x5d, 0x79, 0x84, 0xbf,
+	0xd5, 0xd0, 0x78, 0x4b, 0x86, 0xc7, 0x37, 0x3a, 0x32, 0x4e, 0xd5, 0xeb, 0xc2, 0xad, 0xae, 0x88,
+	0xb6, 0xd4, 0x0f, 0xfd, 0x06, 0xb0, 0x7d, 0x0d, 0x5f, 0x6f, 0x61, 0xab, 0x78, 0x52, 0x4e, 0x19,
+	0xd2, 0xfd, 0x11, 0xfe, 0x41, 0x93, 0x7d, 0x4a, 0x63, 0xf5, 0xc7, 0x4b, 0x67, 0xa2, 0x67, 0xf6,
+	0x4c, 0x85, 0xe5, 0x9e, 0xce, 0x48, 0xba, 0x45, 0xa0, 0xbb, 0x88, 0xe7, 0xb3, 0x5b, 0xd1, 0x2c,
+	0xef, 0x7e, 0xa6, 0xa1, 0x1c, 0x37, 0xba, 0x47, 0x87, 0xce, 0x77, 0x70, 0xe8, 0x69, 0x35, 0xd1,
+	0x67, 0x81, 0xd4, 0x35, 0x3c, 0x93, 0xe1, 0x43, 0x97, 0xa4, 0xdc, 0xb7, 0x8b, 0xfa, 0xa1, 0x18,
+	0xe0, 0x49, 0x43, 0x74, 0xaf, 0x86, 0x6a, 0x6d, 0x8d, 0x0d, 0xde, 0xda, 0x16, 0x16, 0x3a, 0x82,
+	0x26, 0x99, 0x5d, 0x9f, 0x06, 0xd4, 0x3c, 0x9e, 0xcc, 0x44, 0xa5, 0xf8, 0x67, 0xd6, 0x75, 0xa9,
+	0xb4, 0xdc, 0x72, 0xbf, 0x9f, 0xf7, 0x3d, 0xbc, 0xde, 0x91, 0x60, 0xba, 0x0a, 0xe8, 0x9b, 0xc0,
+	0x71, 0x1d, 0xaf, 0x66, 0x72, 0x84, 0xe2, 0x60, 0xda, 0xf5, 0x72, 0x73, 0xd0, 0xb2, 0xc2, 0xf8,
+	0x44, 0xb6, 0x22, 0xca, 0x9c, 0xe7, 0x78, 0x23, 0x3d, 0x92, 0x7f, 0x13, 0xc8, 0x17, 0xb1, 0xd9,
+	0x89, 0x3c, 0x44, 0x37, 0x15, 0xe6, 0xef, 0x35, 0x34, 0x0a, 0xc5, 0x73, 0xad, 0xfe, 0x1f, 0xdd,
+	0xbd, 0xd4, 0xd5, 0xab, 0x6e, 0x28, 0xd4, 0x67, 0x3c, 0x11, 0x28, 0xd9, 0x59, 0xbe, 0xfd, 0x86,
+	0x11, 0x56, 0x7d, 0xa0, 0xf8, 0x01, 0xc1, 0x8b, 0x1d, 0x08, 0xa7, 0x7f, 0x53, 0x0a, 0x2b, 0x5d,
+	0xd1, 0x6c, 0x6a, 0x4d, 0xce, 0x20, 0xda, 0x7a, 0x1f, 0x80, 0xfa, 0x11, 0xfe, 0x91, 0x35, 0x0e,
+	0x4d, 0x45, 0x05, 0x2f, 0x77, 0x05, 0xde, 0x58, 0xd2, 0xba, 0x64, 0xdc, 0x54, 0xb7, 0xf4, 0xb7,
+	0x80, 0xf1, 0x2d, 0xbc, 0xd2, 0x9e, 0xf1, 0x8e, 0x38, 0x92, 0xe1, 0xe5, 0xb5, 0x87, 0xc7, 0xbf,
+	0x4f, 0xf7, 0x3d, 0xf9, 0x63, 0x5a, 0x3b, 0x66, 0xdf, 0x09, 0xfb, 0x7e, 0x63, 0xdf, 0xe7, 0xcf,
+	0xa6, 0xfb, 0x4e, 0xd8, 0xf7, 0x2b, 0xfb, 0x3e, 0xb8, 0x5d, 0x61, 0x0d, 0x79, 0xcd, 0xe6, 0xa4,
+	0x4c, 0xea, 0x44, 0x31, 0xb3, 0x9a, 0x9a, 0x22, 0x13, 0xde, 0x27, 0xf1, 0x41, 0x10, 0xed, 0x9a,
+	0x87, 0x09, 0x34, 0x2f, 0xe3, 0x91, 0x6f, 0x55, 0xc5, 0x2f, 0xb3, 0x3d, 0x00, 0xa9, 0x64, 0xf9,
+	0x5f, 0x20, 0x2f, 0x47, 0xda, 0xab, 0x0f, 0x00, 0x00,
 }
 
 func (this *QuerySecretContractRequest) Equal(that interface{}) bool {
@@ -1925,6 +1928,13 @@ func (m *CodeInfoResponse) MarshalToSizedBuffer(dAtA []byte) (int, error) {
 	_ = i
 	var l int
 	_ = l
+	if len(m.DeveloperPubKey) > 0 {
+		i -= len(m.DeveloperPubKey)
+		copy(dAtA[i:], m.DeveloperPubKey)
+		i = encodeVarintQuery(dAtA, i, uint64(len(m.DeveloperPubKey)))
+		i--
+		dAtA[i] = 0x32
+	}
 	if len(m.Builder) > 0 {
 		i -= len(m.Builder)
 		copy(dAtA[i:], m.Builder)
@@ -2466,6 +2476,10 @@ func (m *CodeInfoResponse) Size() (n int) {
 	if l > 0 {
 		n += 1 + l + sovQuery(uint64(l))
 	}
+	l = len(m.DeveloperPubKey)
+	if l > 0 {
+		n += 1 + l + sovQuery(uint64(l))
+	}
 	return n
 }
 
@@ -3557,6 +3571,38 @@ func (m *CodeInfoResponse) Unmarshal(dAtA []byte) error {
 			}
 			m.Builder = string(dAtA[iNdEx:postIndex])
 			iNdEx = postIndex
+		case 6:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field DeveloperPubKey", wireType)
+			}
+			var stringLen uint64
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowQuery
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				stringLen |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			intStringLen := int(stringLen)
+			if intStringLen < 0 {
+				return ErrInvalidLengthQuery
+			}
+			postIndex := iNdEx + intStringLen
+			if postIndex < 0 {
+				return ErrInvalidLengthQuery
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.DeveloperPubKey = string(dAtA[iNdEx:postIndex])
+			iNdEx = postIndex
 		default:
 			iNdEx = preIndex
 			skippy, err := skipQuery(dAtA[iNdEx:])
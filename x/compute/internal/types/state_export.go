@@ -0,0 +1,69 @@
+package types
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"encoding/binary"
+
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+)
+
+// stateExportDomain separates state export commitments from other hashes
+const stateExportDomain = "secret-compute-state-export-v1"
+
+// StateExport is the compute state of a chain at some height, meant to seed the genesis of a hard
+// fork. Contract state is exported as stored, so it stays encrypted with the contract keys.
+//
+// The commitment is a hash of the height and the state. Validators of the fork compare it to make
+// sure they all start from the same contracts, without comparing the whole export.
+type StateExport struct {
+	Height     int64        `json:"height"`
+	Genesis    GenesisState `json:"genesis"`
+	Commitment []byte       `json:"commitment"`
+}
+
+// NewStateExport commits to the given state.
+func NewStateExport(height int64, genesis GenesisState) (StateExport, error) {
+	commitment, err := StateCommitment(height, genesis)
+	if err != nil {
+		return StateExport{}, err
+	}
+	return StateExport{
+		Height:     height,
+		Genesis:    genesis,
+		Commitment: commitment,
+	}, nil
+}
+
+// StateCommitment returns the hash committing to the state exported at height. The protobuf
+// encoding of the state is deterministic, as it has no maps, so equal states give equal
+// commitments as long as they list codes and contracts in the same order.
+func StateCommitment(height int64, genesis GenesisState) ([]byte, error) {
+	bz, err := genesis.Marshal()
+	if err != nil {
+		return nil, sdkerrors.Wrap(err, "state export")
+	}
+
+	hasher := sha256.New()
+	hasher.Write([]byte(stateExportDomain))
+	heightBytes := make([]byte, 8)
+	binary.BigEndian.PutUint64(heightBytes, uint64(height))
+	hasher.Write(heightBytes)
+	hasher.Write(bz)
+	return hasher.Sum(nil), nil
+}
+
+// Verify checks that the export is valid and matches its commitment.
+func (e StateExport) Verify() error {
+	if err := e.Genesis.ValidateBasic(); err != nil {
+		return sdkerrors.Wrap(err, "state export")
+	}
+	commitment, err := StateCommitment(e.Height, e.Genesis)
+	if err != nil {
+		return err
+	}
+	if !bytes.Equal(commitment, e.Commitment) {
+		return sdkerrors.Wrapf(ErrInvalid, "state export commitment %X doesn't match the exported state (%X)", e.Commitment, commitment)
+	}
+	return nil
+}
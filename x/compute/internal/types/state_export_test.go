@@ -0,0 +1,78 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/require"
+)
+
+func TestStateExportCommitment(t *testing.T) {
+	genesis := GenesisFixture()
+
+	export, err := NewStateExport(100, genesis)
+	require.NoError(t, err)
+	require.NoError(t, export.Verify())
+
+	again, err := NewStateExport(100, genesis)
+	require.NoError(t, err)
+	require.Equal(t, export.Commitment, again.Commitment)
+
+	otherHeight, err := NewStateExport(101, genesis)
+	require.NoError(t, err)
+	require.NotEqual(t, export.Commitment, otherHeight.Commitment)
+}
+
+func TestStateExportVerify(t *testing.T) {
+	specs := map[string]struct {
+		srcMutator func(*StateExport)
+		expError   bool
+	}{
+		"all good": {
+			srcMutator: func(e *StateExport) {},
+		},
+		"height changed": {
+			srcMutator: func(e *StateExport) {
+				e.Height++
+			},
+			expError: true,
+		},
+		"contract state changed": {
+			srcMutator: func(e *StateExport) {
+				e.Genesis.Contracts[0].ContractState[0].Value = []byte("tampered")
+			},
+			expError: true,
+		},
+		"codes reordered": {
+			srcMutator: func(e *StateExport) {
+				codes := e.Genesis.Codes
+				codes[0], codes[1] = codes[1], codes[0]
+			},
+			expError: true,
+		},
+		"commitment missing": {
+			srcMutator: func(e *StateExport) {
+				e.Commitment = nil
+			},
+			expError: true,
+		},
+		"genesis invalid": {
+			srcMutator: func(e *StateExport) {
+				e.Genesis.Codes[0].CodeID = 0
+			},
+			expError: true,
+		},
+	}
+	for msg, spec := range specs {
+		t.Run(msg, func(t *testing.T) {
+			export, err := NewStateExport(100, GenesisFixture())
+			require.NoError(t, err)
+			spec.srcMutator(&export)
+			got := export.Verify()
+			if spec.expError {
+				require.Error(t, got)
+				return
+			}
+			require.NoError(t, got)
+		})
+	}
+}
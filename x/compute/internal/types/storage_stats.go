@@ -0,0 +1,68 @@
+package types
+
+import (
+	"encoding/binary"
+	"fmt"
+)
+
+const contractStorageStatsLen = 24
+
+// ContractStorageStats counts the entries a contract keeps in its store. It is updated on every write
+// of the contract, so reading it doesn't scan the store.
+type ContractStorageStats struct {
+	KeyCount   uint64 `json:"key_count"`
+	KeyBytes   uint64 `json:"key_bytes"`
+	ValueBytes uint64 `json:"value_bytes"`
+}
+
+// AddEntry accounts for a new entry, or for the new value of an existing entry if oldValue isn't nil
+func (s *ContractStorageStats) AddEntry(key, oldValue, value []byte) {
+	if oldValue == nil {
+		s.KeyCount++
+		s.KeyBytes += uint64(len(key))
+	} else {
+		s.ValueBytes = subSaturating(s.ValueBytes, uint64(len(oldValue)))
+	}
+	s.ValueBytes += uint64(len(value))
+}
+
+// RemoveEntry accounts for the removal of an existing entry
+func (s *ContractStorageStats) RemoveEntry(key, oldValue []byte) {
+	s.KeyCount = subSaturating(s.KeyCount, 1)
+	s.KeyBytes = subSaturating(s.KeyBytes, uint64(len(key)))
+	s.ValueBytes = subSaturating(s.ValueBytes, uint64(len(oldValue)))
+}
+
+// subSaturating returns a - b, or 0 instead of wrapping around when the stats of a contract are
+// behind its store, e.g. entries written before the stats were computed
+func subSaturating(a, b uint64) uint64 {
+	if b > a {
+		return 0
+	}
+	return a - b
+}
+
+// Marshal encodes the stats as three big endian uint64
+func (s ContractStorageStats) Marshal() []byte {
+	bz := make([]byte, contractStorageStatsLen)
+	binary.BigEndian.PutUint64(bz[0:8], s.KeyCount)
+	binary.BigEndian.PutUint64(bz[8:16], s.KeyBytes)
+	binary.BigEndian.PutUint64(bz[16:24], s.ValueBytes)
+	return bz
+}
+
+// UnmarshalContractStorageStats decodes stats encoded by Marshal. Missing stats are empty.
+func UnmarshalContractStorageStats(bz []byte) (ContractStorageStats, error) {
+	if bz == nil {
+		return ContractStorageStats{}, nil
+	}
+	if len(bz) != contractStorageStatsLen {
+		return ContractStorageStats{}, fmt.Errorf("invalid contract storage stats length %d", len(bz))
+	}
+
+	return ContractStorageStats{
+		KeyCount:   binary.BigEndian.Uint64(bz[0:8]),
+		KeyBytes:   binary.BigEndian.Uint64(bz[8:16]),
+		ValueBytes: binary.BigEndian.Uint64(bz[16:24]),
+	}, nil
+}
@@ -0,0 +1,39 @@
+package types
+
+import (
+	"testing"
+
+	"github.com/stretchr/testify/require"
+)
+
+func TestContractStorageStats(t *testing.T) {
+	var stats ContractStorageStats
+
+	stats.AddEntry([]byte("key"), nil, []byte("value"))
+	stats.AddEntry([]byte("other"), nil, []byte("1"))
+	require.Equal(t, ContractStorageStats{KeyCount: 2, KeyBytes: 8, ValueBytes: 6}, stats)
+
+	stats.AddEntry([]byte("key"), []byte("value"), []byte("longer value"))
+	require.Equal(t, ContractStorageStats{KeyCount: 2, KeyBytes: 8, ValueBytes: 13}, stats)
+
+	stats.RemoveEntry([]byte("other"), []byte("1"))
+	require.Equal(t, ContractStorageStats{KeyCount: 1, KeyBytes: 3, ValueBytes: 12}, stats)
+
+	// stats behind the store never wrap around
+	var behind ContractStorageStats
+	behind.RemoveEntry([]byte("key"), []byte("value"))
+	require.Equal(t, ContractStorageStats{}, behind)
+	behind.AddEntry([]byte("key"), []byte("value"), []byte("v"))
+	require.Equal(t, ContractStorageStats{ValueBytes: 1}, behind)
+
+	decoded, err := UnmarshalContractStorageStats(stats.Marshal())
+	require.NoError(t, err)
+	require.Equal(t, stats, decoded)
+
+	empty, err := UnmarshalContractStorageStats(nil)
+	require.NoError(t, err)
+	require.Equal(t, ContractStorageStats{}, empty)
+
+	_, err = UnmarshalContractStorageStats([]byte{1, 2, 3})
+	require.Error(t, err)
+}
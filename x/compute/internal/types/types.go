@@ -3,9 +3,12 @@ package types
 import (
 	"crypto/sha256"
 	"encoding/hex"
+	"encoding/json"
 	fmt "fmt"
+	"sort"
 	"strings"
 
+	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
 	servertypes "github.com/cosmos/cosmos-sdk/server/types"
 	sdk "github.com/cosmos/cosmos-sdk/types"
 	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
@@ -20,6 +23,9 @@ const (
 	defaultLRUCacheSize        = uint64(0)
 	defaultEnclaveLRUCacheSize = uint16(100)
 	defaultQueryGasLimit       = uint64(10_000_000)
+	// defaultAutoPinTopK of zero disables auto pinning
+	defaultAutoPinTopK        = uint32(0)
+	defaultAutoPinEpochLength = uint64(100)
 )
 
 func (m Model) ValidateBasic() error {
@@ -42,17 +48,21 @@ func (c CodeInfo) ValidateBasic() error {
 	if err := validateBuilder(c.Builder); err != nil {
 		return sdkerrors.Wrap(err, "builder")
 	}
+	if len(c.DeveloperPubKey) != 0 && len(c.DeveloperPubKey) != secp256k1.PubKeySize {
+		return sdkerrors.Wrap(ErrInvalid, "developer public key")
+	}
 
 	return nil
 }
 
 // NewCodeInfo fills a new Contract struct
-func NewCodeInfo(codeHash []byte, creator sdk.AccAddress, source string, builder string) CodeInfo {
+func NewCodeInfo(codeHash []byte, creator sdk.AccAddress, source string, builder string, developerPubKey []byte) CodeInfo {
 	return CodeInfo{
-		CodeHash: codeHash,
-		Creator:  creator,
-		Source:   source,
-		Builder:  builder,
+		CodeHash:        codeHash,
+		Creator:         creator,
+		Source:          source,
+		Builder:         builder,
+		DeveloperPubKey: developerPubKey,
 		// InstantiateConfig: instantiatePermission,
 	}
 }
@@ -146,6 +156,8 @@ func NewEnv(ctx sdk.Context, creator sdk.AccAddress, deposit sdk.Coins, contract
 			Address: contractAddr.String(),
 		},
 		QueryDepth: 1,
+		// simulations run with the check state too
+		Simulation: ctx.IsCheckTx(),
 	}
 
 	env.Key = wasmTypes.ContractKey{
@@ -154,6 +166,13 @@ func NewEnv(ctx sdk.Context, creator sdk.AccAddress, deposit sdk.Coins, contract
 		CurrentContractKeyProof: contractKey.CurrentContractKeyProof,
 	}
 
+	if feeInfo, ok := TXFeeInfo(ctx); ok {
+		env.Message.FeePayer = feeInfo.Payer.String()
+		if !feeInfo.Granter.Empty() {
+			env.Message.FeeGranter = feeInfo.Granter.String()
+		}
+	}
+
 	if txCounter, ok := TXCounter(ctx); ok {
 		txhashBz := sha256.Sum256(ctx.TxBytes())
 		txhash := hex.EncodeToString(txhashBz[:])
@@ -239,11 +258,64 @@ func contractSDKEventAttributes(customAttributes []wasmTypesV010.LogAttribute, c
 	return attrs, nil
 }
 
-// WasmConfig is the extra config required for wasm
+// ContractIntrospection is the public information of a contract, read from its code without running it
+type ContractIntrospection struct {
+	CodeHash string `json:"code_hash"`
+	// InterfaceVersion is "v1" or "v0.10", empty if unknown
+	InterfaceVersion string `json:"interface_version"`
+	IBCEnabled       bool   `json:"ibc_enabled"`
+	// SnipStandards are the SNIP standards the contract declares implementing. They are not verified.
+	SnipStandards []string `json:"snip_standards"`
+}
+
+// SignedContractQueryResponse is the response of a contract query along with the enclave's signature of it
+type SignedContractQueryResponse struct {
+	Data []byte `json:"data"`
+	// Signature is nil if the node doesn't sign query responses and the contract doesn't MAC them
+	Signature *wasmTypes.QueryResponseSignature `json:"signature"`
+}
+
+// WasmConfig is the extra config required for wasm. It only changes how this node runs contracts
+// and serves queries: what changes the results of transactions is in the params of the module.
 type WasmConfig struct {
 	SmartQueryGasLimit uint64
 	CacheSize          uint64
 	EnclaveCacheSize   uint16
+	// SignExecutionReceipts makes the enclave sign a receipt of every instantiate, execute and
+	// migrate, which the node can hand to off-chain parties. Receipts are not part of consensus.
+	SignExecutionReceipts bool
+	// SignQueryResponses makes the enclave sign the responses to queries sent to this node over RPC,
+	// with the same key as execution receipts, so light clients can trust them.
+	SignQueryResponses bool
+	// AutoPinTopK is the number of code hashes, the most executed of each epoch, the node keeps
+	// pinned in memory. Zero disables auto pinning.
+	AutoPinTopK uint32
+	// AutoPinEpochLength is the number of blocks executions are counted over before the pinned
+	// code hashes are chosen again
+	AutoPinEpochLength uint64
+	// PrewarmCodeHashes are the hex encoded code hashes loaded, validated and built by the enclave in
+	// the background when the node starts, so the first blocks don't pay for loading the hot
+	// contracts of the network.
+	PrewarmCodeHashes []string
+	// PrewarmPin also pins the prewarmed code hashes, until they are unpinned.
+	PrewarmPin bool
+	// ObservedEventsFile is the file, relative to the node home if not absolute, the events an
+	// observer mode enclave encrypts to contract observers are appended to. Empty discards them.
+	ObservedEventsFile string
+	// ExecutionReceiptsFile is the file, relative to the node home if not absolute, the receipts the
+	// enclave signs are appended to. Empty discards them.
+	ExecutionReceiptsFile string
+	// ChainID is the chain-id of the genesis of the node, the enclave launch is validated against it
+	// before the enclave is used.
+	ChainID string
+	// AllowDebugEnclave lets a debug-signed enclave run on a mainnet chain-id, for testnets using one.
+	AllowDebugEnclave bool
+	// LegacyEnclaveFile is the file of the previous version of the enclave, loaded next to the current
+	// one during a coordinated upgrade to execute the blocks before it. Empty loads none.
+	LegacyEnclaveFile string
+	// EnclaveUpgradeHeight is the first height executed by the current enclave rather than the
+	// legacy one. Zero executes every block with the current enclave.
+	EnclaveUpgradeHeight uint64
 }
 
 // DefaultWasmConfig returns the default settings for WasmConfig
@@ -252,7 +324,40 @@ func DefaultWasmConfig() *WasmConfig {
 		SmartQueryGasLimit: defaultQueryGasLimit,
 		CacheSize:          defaultLRUCacheSize,
 		EnclaveCacheSize:   defaultEnclaveLRUCacheSize,
+		AutoPinTopK:        defaultAutoPinTopK,
+		AutoPinEpochLength: defaultAutoPinEpochLength,
+	}
+}
+
+// UpgradeSchedule returns the heights the behaviors of the VM changed at on this network, nil if
+// all of them are active from genesis.
+func (c WasmConfig) UpgradeSchedule() *wasmTypes.UpgradeSchedule {
+	if c.EnclaveUpgradeHeight == 0 {
+		return nil
 	}
+	height := c.EnclaveUpgradeHeight
+	return &wasmTypes.UpgradeSchedule{CurrentEnclave: &height}
+}
+
+// ConsensusHash identifies the contract features a node supports, which decide what contracts can
+// be stored, so all the nodes of a network must support the same. It doesn't depend on their order.
+func ConsensusHash(supportedFeatures string) []byte {
+	var features []string
+	for _, feature := range strings.Split(supportedFeatures, ",") {
+		if feature = strings.TrimSpace(feature); feature != "" {
+			features = append(features, feature)
+		}
+	}
+	sort.Strings(features)
+
+	bz, err := json.Marshal(struct {
+		SupportedFeatures []string `json:"supported_features"`
+	}{features})
+	if err != nil {
+		panic(err)
+	}
+	hash := sha256.Sum256(bz)
+	return hash[:]
 }
 
 type SecretMsg struct {
@@ -305,6 +410,25 @@ func GetConfig(appOpts servertypes.AppOptions) *WasmConfig {
 		config.EnclaveCacheSize = enclaveCacheSize
 	}
 
+	config.SignExecutionReceipts = cast.ToBool(appOpts.Get("wasm.contract-sign-execution-receipts"))
+	config.SignQueryResponses = cast.ToBool(appOpts.Get("wasm.contract-sign-query-responses"))
+
+	config.AutoPinTopK = cast.ToUint32(appOpts.Get("wasm.contract-auto-pin-top-k"))
+	autoPinEpochLength := cast.ToUint64(appOpts.Get("wasm.contract-auto-pin-epoch-length"))
+	if autoPinEpochLength > 0 {
+		config.AutoPinEpochLength = autoPinEpochLength
+	}
+
+	config.PrewarmCodeHashes = cast.ToStringSlice(appOpts.Get("wasm.contract-prewarm-code-hashes"))
+	config.PrewarmPin = cast.ToBool(appOpts.Get("wasm.contract-prewarm-pin"))
+
+	config.ObservedEventsFile = cast.ToString(appOpts.Get("wasm.contract-observed-events-file"))
+	config.ExecutionReceiptsFile = cast.ToString(appOpts.Get("wasm.contract-execution-receipts-file"))
+
+	config.AllowDebugEnclave = cast.ToBool(appOpts.Get("wasm.allow-debug-enclave"))
+	config.LegacyEnclaveFile = cast.ToString(appOpts.Get("wasm.legacy-enclave-file"))
+	config.EnclaveUpgradeHeight = cast.ToUint64(appOpts.Get("wasm.enclave-upgrade-height"))
+
 	return config
 }
 
@@ -321,6 +445,48 @@ contract-memory-cache-size = "{{ .WASMConfig.CacheSize }}"
 
 # The WASM VM memory cache size in number of cached modules. Can safely go up to 15, but not recommended for validators
 contract-memory-enclave-cache-size = "{{ .WASMConfig.EnclaveCacheSize }}"
+
+# Have the enclave sign a receipt (code hash, message hash, result hash and gas used) of every
+# contract execution. Receipts are signed with a key unique to this node
+contract-sign-execution-receipts = {{ .WASMConfig.SignExecutionReceipts }}
+
+# File the signed execution receipts are appended to, one json record per line with the chain-id,
+# relative to the node home if not absolute. Empty discards them
+contract-execution-receipts-file = "{{ .WASMConfig.ExecutionReceiptsFile }}"
+
+# Have the enclave sign the responses to contract queries sent to this node, binding the query,
+# the block height and the result, so light clients can trust them. Uses the same key as receipts
+contract-sign-query-responses = {{ .WASMConfig.SignQueryResponses }}
+
+# Number of code hashes, the most executed in the last epoch, kept pinned in memory so their
+# contracts aren't read from disk on every call. They are chosen again at the end of each epoch of
+# contract-auto-pin-epoch-length blocks, unpinning the ones that went dormant. 0 disables it
+contract-auto-pin-top-k = "{{ .WASMConfig.AutoPinTopK }}"
+contract-auto-pin-epoch-length = "{{ .WASMConfig.AutoPinEpochLength }}"
+
+# Hex encoded code hashes loaded, validated and built by the enclave in the background when the
+# node starts, so the first blocks after a restart don't pay for loading popular contracts. With contract-prewarm-pin
+# they are also kept pinned in memory, like the ones of auto pinning but never unpinned by it
+contract-prewarm-code-hashes = [{{ range .WASMConfig.PrewarmCodeHashes }}{{ printf "%q, " . }}{{end}}]
+contract-prewarm-pin = {{ .WASMConfig.PrewarmPin }}
+
+# File the events of observed contracts are appended to, one json record per line, relative to the
+# node home if not absolute. Only nodes running an enclave built with observer mode produce any, and
+# only for the contracts it has an observer for. Empty discards them
+contract-observed-events-file = "{{ .WASMConfig.ObservedEventsFile }}"
+
+# Start even if the enclave is debug-signed while the chain-id is the one of a mainnet. A debug
+# enclave offers no confidentiality, never set this on a production network
+allow-debug-enclave = {{ .WASMConfig.AllowDebugEnclave }}
+
+# The previous version of the enclave, looked up like the current one, loaded next to it during a
+# coordinated upgrade so the blocks before the upgrade are replayed with the logic they ran with.
+# It must be built for the same node version. Empty loads none
+legacy-enclave-file = "{{ .WASMConfig.LegacyEnclaveFile }}"
+
+# The first height executed by the current enclave, the blocks before it go to the legacy enclave.
+# Every node of the network must use the same height. 0 executes every block with the current enclave
+enclave-upgrade-height = "{{ .WASMConfig.EnclaveUpgradeHeight }}"
 `
 
 // ZeroSender is a valid 20 byte canonical address that's used to bypass the x/compute checks
@@ -132,6 +132,8 @@ type CodeInfo struct {
 	Creator  github_com_cosmos_cosmos_sdk_types.AccAddress `protobuf:"bytes,2,opt,name=creator,proto3,casttype=github.com/cosmos/cosmos-sdk/types.AccAddress" json:"creator,omitempty"`
 	Source   string                                        `protobuf:"bytes,3,opt,name=source,proto3" json:"source,omitempty"`
 	Builder  string                                        `protobuf:"bytes,4,opt,name=builder,proto3" json:"builder,omitempty"`
+	// DeveloperPubKey is the key of the developer who signed the code, if it was signed
+	DeveloperPubKey []byte `protobuf:"bytes,5,opt,name=developer_pub_key,json=developerPubKey,proto3" json:"developer_pub_key,omitempty"`
 }
 
 func (m *CodeInfo) Reset()         { *m = CodeInfo{} }
@@ -259,6 +261,8 @@ type ContractInfo struct {
 	Admin string `protobuf:"bytes,7,opt,name=admin,proto3" json:"admin,omitempty"`
 	// Proof that enclave executed the instantiate command
 	AdminProof []byte `protobuf:"bytes,8,opt,name=admin_proof,json=adminProof,proto3" json:"admin_proof,omitempty"`
+	// IBCEnabled is set if the code the contract was instantiated or last migrated with exports all the IBC entry points
+	IBCEnabled bool `protobuf:"varint,9,opt,name=ibc_enabled,json=ibcEnabled,proto3" json:"ibc_enabled,omitempty"`
 }
 
 func (m *ContractInfo) Reset()         { *m = ContractInfo{} }
@@ -419,6 +423,126 @@ func (m *ContractCodeHistoryEntry) XXX_DiscardUnknown() {
 
 var xxx_messageInfo_ContractCodeHistoryEntry proto.InternalMessageInfo
 
+// Params are the parameters of the compute module, set by governance
+type Params struct {
+	// MaxResponseDataSize is the largest data field in bytes a contract may return from instantiate, execute or migrate. Zero disables the limit.
+	MaxResponseDataSize uint64 `protobuf:"varint,1,opt,name=max_response_data_size,json=maxResponseDataSize,proto3" json:"max_response_data_size,omitempty"`
+	// MaxQueryResponseSize is the largest response in bytes to a query sent to a node. Zero disables the limit.
+	MaxQueryResponseSize uint64 `protobuf:"varint,2,opt,name=max_query_response_size,json=maxQueryResponseSize,proto3" json:"max_query_response_size,omitempty"`
+	// BulkMemoryHeight is the height from which contracts using the bulk memory proposal can be stored. Zero never activates it.
+	BulkMemoryHeight int64 `protobuf:"varint,3,opt,name=bulk_memory_height,json=bulkMemoryHeight,proto3" json:"bulk_memory_height,omitempty"`
+	// ContractVersionHeight is the height from which the cw2 versions of contracts are read on instantiate and migrate. Zero never activates it.
+	ContractVersionHeight int64 `protobuf:"varint,4,opt,name=contract_version_height,json=contractVersionHeight,proto3" json:"contract_version_height,omitempty"`
+	// ConsensusConfigHash is the hash of the contract features every node of the network must support. Nodes with other ones halt.
+	ConsensusConfigHash []byte `protobuf:"bytes,5,opt,name=consensus_config_hash,json=consensusConfigHash,proto3" json:"consensus_config_hash,omitempty"`
+	// FundsDenomAllowlist lists the denoms that can be sent to contracts. Empty allows any denom.
+	FundsDenomAllowlist []string `protobuf:"bytes,6,rep,name=funds_denom_allowlist,json=fundsDenomAllowlist,proto3" json:"funds_denom_allowlist,omitempty"`
+	// FundsNormalizationHeight is the height from which the funds contracts send are sorted by denom, with repeated denoms merged and zero amounts dropped. Zero never activates it.
+	FundsNormalizationHeight int64 `protobuf:"varint,7,opt,name=funds_normalization_height,json=fundsNormalizationHeight,proto3" json:"funds_normalization_height,omitempty"`
+	// IBCEntryPointsHeight is the height from which code exporting some of the IBC entry points but not all can't be stored. Zero never activates it.
+	IBCEntryPointsHeight int64 `protobuf:"varint,8,opt,name=ibc_entry_points_height,json=ibcEntryPointsHeight,proto3" json:"ibc_entry_points_height,omitempty"`
+	// AccessListHeight is the height from which executions may declare an access list. Zero never activates it.
+	AccessListHeight int64 `protobuf:"varint,9,opt,name=access_list_height,json=accessListHeight,proto3" json:"access_list_height,omitempty"`
+	// QueryRewrapHeight is the height from which migrations keep the previous keys of contracts, for queries addressed to their previous code to be re-wrapped. Zero never activates it.
+	QueryRewrapHeight int64 `protobuf:"varint,10,opt,name=query_rewrap_height,json=queryRewrapHeight,proto3" json:"query_rewrap_height,omitempty"`
+	// EventValidationHeight is the height from which the event types and attribute keys contracts emit are checked against the ones reserved for the chain. Zero never activates it.
+	EventValidationHeight int64 `protobuf:"varint,11,opt,name=event_validation_height,json=eventValidationHeight,proto3" json:"event_validation_height,omitempty"`
+	// StateCompressionHeight is the height from which the state of contracts that opted in is compressed. Zero never activates it.
+	StateCompressionHeight int64 `protobuf:"varint,12,opt,name=state_compression_height,json=stateCompressionHeight,proto3" json:"state_compression_height,omitempty"`
+	// StackHeightLimitHeight is the height from which contracts recursing deeper than the stack height limit fail. Zero never activates it.
+	StackHeightLimitHeight int64 `protobuf:"varint,13,opt,name=stack_height_limit_height,json=stackHeightLimitHeight,proto3" json:"stack_height_limit_height,omitempty"`
+	// MemoryGrowthLimitHeight is the height from which a call growing its memory by more than the memory growth limit fails. Zero never activates it.
+	MemoryGrowthLimitHeight int64 `protobuf:"varint,14,opt,name=memory_growth_limit_height,json=memoryGrowthLimitHeight,proto3" json:"memory_growth_limit_height,omitempty"`
+	// GasForwardingHeight is the height from which contracts forward 63/64 of their remaining gas to the contracts they query instead of all of it. Zero never activates it.
+	GasForwardingHeight int64 `protobuf:"varint,15,opt,name=gas_forwarding_height,json=gasForwardingHeight,proto3" json:"gas_forwarding_height,omitempty"`
+	// ContractObservers are the observers governance approved, as "<contract address>:<hex x25519 public key>". An observer mode enclave streams the events of a contract to its observer if the code of the contract names the same one.
+	ContractObservers []string `protobuf:"bytes,16,rep,name=contract_observers,json=contractObservers,proto3" json:"contract_observers,omitempty"`
+	// DeveloperSignatureHeight is the height from which code can be stored with a developer signature. Zero never activates it.
+	DeveloperSignatureHeight int64 `protobuf:"varint,17,opt,name=developer_signature_height,json=developerSignatureHeight,proto3" json:"developer_signature_height,omitempty"`
+	// RevertReasonsHeight is the height from which the structured revert reasons contracts fail with are published in plaintext next to their encrypted errors. Zero never activates it.
+	RevertReasonsHeight int64 `protobuf:"varint,18,opt,name=revert_reasons_height,json=revertReasonsHeight,proto3" json:"revert_reasons_height,omitempty"`
+	// QueryRewrapGraceBlocks is the number of blocks after a migration during which the queries that opted in are re-wrapped from the previous code of the contract to its current one. Zero re-wraps none.
+	QueryRewrapGraceBlocks uint64 `protobuf:"varint,19,opt,name=query_rewrap_grace_blocks,json=queryRewrapGraceBlocks,proto3" json:"query_rewrap_grace_blocks,omitempty"`
+	// StateKeyDecryptionHeight is the height from which enclaves built for state migration tooling decrypt the state keys of contracts. Zero never activates it.
+	StateKeyDecryptionHeight int64 `protobuf:"varint,20,opt,name=state_key_decryption_height,json=stateKeyDecryptionHeight,proto3" json:"state_key_decryption_height,omitempty"`
+}
+
+func (m *Params) Reset()         { *m = Params{} }
+func (m *Params) String() string { return proto.CompactTextString(m) }
+func (*Params) ProtoMessage()    {}
+func (*Params) Descriptor() ([]byte, []int) {
+	return fileDescriptor_8ba7f40a6d1951b3, []int{8}
+}
+func (m *Params) XXX_Unmarshal(b []byte) error {
+	return m.Unmarshal(b)
+}
+func (m *Params) XXX_Marshal(b []byte, deterministic bool) ([]byte, error) {
+	if deterministic {
+		return xxx_messageInfo_Params.Marshal(b, m, deterministic)
+	} else {
+		b = b[:cap(b)]
+		n, err := m.MarshalToSizedBuffer(b)
+		if err != nil {
+			return nil, err
+		}
+		return b[:n], nil
+	}
+}
+func (m *Params) XXX_Merge(src proto.Message) {
+	xxx_messageInfo_Params.Merge(m, src)
+}
+func (m *Params) XXX_Size() int {
+	return m.Size()
+}
+func (m *Params) XXX_DiscardUnknown() {
+	xxx_messageInfo_Params.DiscardUnknown(m)
+}
+
+var xxx_messageInfo_Params proto.InternalMessageInfo
+
+// RemoveCodeProposal is a governance proposal to remove a code that no contract runs
+type RemoveCodeProposal struct {
+	// Title is a short summary of the proposal
+	Title string `protobuf:"bytes,1,opt,name=title,proto3" json:"title,omitempty"`
+	// Description is a human readable text of the proposal
+	Description string `protobuf:"bytes,2,opt,name=description,proto3" json:"description,omitempty"`
+	// CodeID is the id of the code to remove
+	CodeID uint64 `protobuf:"varint,3,opt,name=code_id,json=codeId,proto3" json:"code_id,omitempty"`
+}
+
+func (m *RemoveCodeProposal) Reset()         { *m = RemoveCodeProposal{} }
+func (m *RemoveCodeProposal) String() string { return proto.CompactTextString(m) }
+func (*RemoveCodeProposal) ProtoMessage()    {}
+func (*RemoveCodeProposal) Descriptor() ([]byte, []int) {
+	return fileDescriptor_8ba7f40a6d1951b3, []int{9}
+}
+func (m *RemoveCodeProposal) XXX_Unmarshal(b []byte) error {
+	return m.Unmarshal(b)
+}
+func (m *RemoveCodeProposal) XXX_Marshal(b []byte, deterministic bool) ([]byte, error) {
+	if deterministic {
+		return xxx_messageInfo_RemoveCodeProposal.Marshal(b, m, deterministic)
+	} else {
+		b = b[:cap(b)]
+		n, err := m.MarshalToSizedBuffer(b)
+		if err != nil {
+			return nil, err
+		}
+		return b[:n], nil
+	}
+}
+func (m *RemoveCodeProposal) XXX_Merge(src proto.Message) {
+	xxx_messageInfo_RemoveCodeProposal.Merge(m, src)
+}
+func (m *RemoveCodeProposal) XXX_Size() int {
+	return m.Size()
+}
+func (m *RemoveCodeProposal) XXX_DiscardUnknown() {
+	xxx_messageInfo_RemoveCodeProposal.DiscardUnknown(m)
+}
+
+var xxx_messageInfo_RemoveCodeProposal proto.InternalMessageInfo
+
 func init() {
 	proto.RegisterEnum("secret.compute.v1beta1.AccessType", AccessType_name, AccessType_value)
 	proto.RegisterEnum("secret.compute.v1beta1.ContractCodeHistoryOperationType", ContractCodeHistoryOperationType_name, ContractCodeHistoryOperationType_value)
@@ -430,6 +554,8 @@ func init() {
 	proto.RegisterType((*AbsoluteTxPosition)(nil), "secret.compute.v1beta1.AbsoluteTxPosition")
 	proto.RegisterType((*Model)(nil), "secret.compute.v1beta1.Model")
 	proto.RegisterType((*ContractCodeHistoryEntry)(nil), "secret.compute.v1beta1.ContractCodeHistoryEntry")
+	proto.RegisterType((*Params)(nil), "secret.compute.v1beta1.Params")
+	proto.RegisterType((*RemoveCodeProposal)(nil), "secret.compute.v1beta1.RemoveCodeProposal")
 }
 
 func init() {
@@ -437,73 +563,107 @@ func init() {
 }
 
 var fileDescriptor_8ba7f40a6d1951b3 = []byte{
-	// 1053 bytes of a gzipped FileDescriptorProto
-	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xac, 0x56, 0x4d, 0x6f, 0x1a, 0x47,
-	0x1f, 0x67, 0x0d, 0x06, 0x33, 0x90, 0x04, 0xcd, 0xe3, 0x27, 0x21, 0x54, 0x02, 0xba, 0xa9, 0x52,
-	0xd7, 0xae, 0x21, 0x4e, 0x7b, 0x88, 0xdc, 0x13, 0x2f, 0x1b, 0x7b, 0xe3, 0x7a, 0x41, 0x03, 0x76,
-	0xe5, 0xaa, 0xd5, 0x6a, 0x5f, 0xc6, 0xb0, 0xf2, 0xb2, 0x83, 0x66, 0x06, 0x97, 0xbd, 0xf5, 0x58,
-	0x71, 0xea, 0xb1, 0x17, 0xa4, 0x4a, 0x8d, 0xa2, 0x7c, 0x81, 0x7e, 0x81, 0x9e, 0x72, 0xcc, 0xb1,
-	0x27, 0xd4, 0xe2, 0x0f, 0x50, 0xa9, 0xc7, 0x9c, 0xaa, 0x1d, 0x16, 0x43, 0x9b, 0x58, 0x76, 0xa5,
-	0x9e, 0xfc, 0x7f, 0xfd, 0xfd, 0xdf, 0x7e, 0x3b, 0x06, 0xc8, 0x0c, 0x5b, 0x14, 0xf3, 0xb2, 0x45,
-	0x7a, 0xfd, 0x01, 0xc7, 0xe5, 0xf3, 0x1d, 0x13, 0x73, 0x63, 0xa7, 0xcc, 0xfd, 0x3e, 0x66, 0xa5,
-	0x3e, 0x25, 0x9c, 0xc0, 0xbb, 0xb3, 0x98, 0x52, 0x18, 0x53, 0x0a, 0x63, 0x72, 0xeb, 0x1d, 0xd2,
-	0x21, 0x22, 0xa4, 0x1c, 0x48, 0xb3, 0x68, 0xd9, 0x02, 0x77, 0x2a, 0x96, 0x85, 0x19, 0x6b, 0xfb,
-	0x7d, 0xdc, 0x34, 0xa8, 0xd1, 0x83, 0xcf, 0xc0, 0xea, 0xb9, 0xe1, 0x0e, 0x70, 0x56, 0x2a, 0x4a,
-	0x1b, 0xb7, 0x1f, 0xcb, 0xa5, 0x77, 0x03, 0x96, 0x16, 0x79, 0xd5, 0xcc, 0x9f, 0x93, 0x42, 0xda,
-	0x37, 0x7a, 0xee, 0xae, 0x2c, 0x52, 0x65, 0x34, 0x83, 0xd8, 0x8d, 0xfd, 0xf0, 0x63, 0x41, 0x92,
-	0x5f, 0x48, 0x60, 0xad, 0x46, 0x6c, 0xac, 0x7a, 0xa7, 0x04, 0xbe, 0x07, 0x92, 0x16, 0xb1, 0xb1,
-	0xde, 0x35, 0x58, 0x57, 0x94, 0x48, 0xa3, 0xb5, 0xc0, 0xb0, 0x6f, 0xb0, 0x2e, 0x3c, 0x00, 0x09,
-	0x8b, 0x62, 0x83, 0x13, 0x9a, 0x5d, 0x09, 0x5c, 0xd5, 0x9d, 0x37, 0x93, 0xc2, 0x76, 0xc7, 0xe1,
-	0xdd, 0x81, 0x19, 0x34, 0x50, 0xb6, 0x08, 0xeb, 0x11, 0x16, 0xfe, 0xd9, 0x66, 0xf6, 0x59, 0x38,
-	0x7b, 0xc5, 0xb2, 0x2a, 0xb6, 0x4d, 0x31, 0x63, 0x68, 0x8e, 0x00, 0xef, 0x82, 0x38, 0x23, 0x03,
-	0x6a, 0xe1, 0x6c, 0xb4, 0x28, 0x6d, 0x24, 0x51, 0xa8, 0xc1, 0x2c, 0x48, 0x98, 0x03, 0xc7, 0xb5,
-	0x31, 0xcd, 0xc6, 0x84, 0x63, 0xae, 0xca, 0xcf, 0x25, 0x90, 0xaa, 0x11, 0x8f, 0x53, 0xc3, 0xe2,
-	0x07, 0xd8, 0x87, 0x0f, 0xc1, 0x1d, 0xd2, 0xd1, 0xad, 0xd0, 0xa2, 0x9f, 0x61, 0x3f, 0xec, 0xf8,
-	0x16, 0xe9, 0x2c, 0xc7, 0x3d, 0x02, 0xeb, 0xd6, 0x80, 0x52, 0xec, 0xf1, 0xbf, 0x07, 0x8b, 0x19,
-	0x10, 0x0c, 0x7d, 0xcb, 0x19, 0x9f, 0x81, 0xdc, 0xbb, 0x32, 0xf4, 0x3e, 0x25, 0xe4, 0x54, 0xf4,
-	0x9b, 0x46, 0xf7, 0xde, 0xce, 0x6b, 0x06, 0x6e, 0xf9, 0x5b, 0x09, 0xc0, 0xb9, 0xb1, 0x36, 0x60,
-	0x9c, 0xf4, 0xc4, 0x66, 0xdb, 0x20, 0x85, 0x3d, 0xcb, 0x35, 0xce, 0xf1, 0x65, 0xa7, 0xa9, 0xc7,
-	0x0f, 0xae, 0x3a, 0xdf, 0x12, 0x6a, 0xf5, 0xf6, 0x74, 0x52, 0x00, 0xca, 0x2c, 0xf7, 0x00, 0xfb,
-	0x08, 0xe0, 0x4b, 0x19, 0xae, 0x83, 0x55, 0xd7, 0x30, 0xb1, 0x2b, 0x86, 0x49, 0xa2, 0x99, 0x22,
-	0xff, 0xb2, 0x02, 0xd2, 0x73, 0x04, 0x51, 0xfc, 0x01, 0x48, 0x88, 0xb3, 0x3a, 0xb6, 0x28, 0x1c,
-	0xab, 0x82, 0xe9, 0xa4, 0x10, 0x17, 0x57, 0xaf, 0xa3, 0x78, 0xe0, 0x52, 0xed, 0xff, 0xf6, 0xbc,
-	0x97, 0x8d, 0xc5, 0x96, 0x1a, 0x83, 0xf5, 0xb0, 0x04, 0xb6, 0xb3, 0xab, 0x62, 0x01, 0x9b, 0x57,
-	0xf2, 0xd7, 0x64, 0xc4, 0x1d, 0x70, 0xdc, 0x1e, 0x36, 0x09, 0x73, 0xb8, 0x43, 0x3c, 0x34, 0x4f,
-	0x85, 0xdb, 0x20, 0xe5, 0x98, 0x96, 0xde, 0x27, 0x94, 0x07, 0x13, 0xc5, 0x83, 0x0a, 0xd5, 0x5b,
-	0xd3, 0x49, 0x21, 0xa9, 0x56, 0x6b, 0x4d, 0x42, 0xb9, 0x5a, 0x47, 0x49, 0xc7, 0xb4, 0x84, 0x68,
-	0x07, 0xad, 0x18, 0x76, 0xcf, 0xf1, 0xb2, 0x89, 0x59, 0x2b, 0x42, 0x81, 0x05, 0x90, 0x12, 0x42,
-	0x78, 0xd4, 0x35, 0x71, 0x54, 0x20, 0x4c, 0xb3, 0x3b, 0x22, 0x00, 0xdf, 0x6e, 0x02, 0xbe, 0x0f,
-	0xd2, 0xa6, 0x4b, 0xac, 0x33, 0xbd, 0x8b, 0x9d, 0x4e, 0x97, 0x8b, 0x75, 0x46, 0x51, 0x4a, 0xd8,
-	0xf6, 0x85, 0x09, 0xde, 0x07, 0x6b, 0x7c, 0xa8, 0x3b, 0x9e, 0x8d, 0x87, 0x62, 0x91, 0x31, 0x94,
-	0xe0, 0x43, 0x35, 0x50, 0x65, 0x07, 0xac, 0x1e, 0x12, 0x1b, 0xbb, 0xf0, 0x19, 0x88, 0x1e, 0xcc,
-	0xf9, 0x5a, 0x7d, 0xf2, 0x66, 0x52, 0xf8, 0x74, 0x69, 0xcf, 0x1c, 0x7b, 0x36, 0xa6, 0x3d, 0xc7,
-	0xe3, 0xcb, 0xa2, 0xeb, 0x98, 0xac, 0x6c, 0xfa, 0x1c, 0xb3, 0xd2, 0x3e, 0x1e, 0x56, 0x03, 0x01,
-	0x45, 0x43, 0x0e, 0x1c, 0x8b, 0x27, 0x61, 0x46, 0xe8, 0x99, 0x22, 0xff, 0x21, 0x81, 0xec, 0x25,
-	0x0d, 0x83, 0x2f, 0xd8, 0x61, 0x9c, 0x50, 0x5f, 0xf1, 0x38, 0xf5, 0xe1, 0x31, 0x48, 0x92, 0x3e,
-	0xa6, 0x46, 0x30, 0x52, 0xf8, 0x92, 0x3c, 0xb9, 0x8e, 0x8a, 0x4b, 0x20, 0x8d, 0x79, 0x6e, 0xf0,
-	0xbe, 0xa0, 0x05, 0xd4, 0x32, 0xcf, 0x56, 0xae, 0xe4, 0x59, 0x1d, 0x24, 0x06, 0x7d, 0x5b, 0x90,
-	0x20, 0xfa, 0xef, 0x49, 0x10, 0xa6, 0xc2, 0x0c, 0x88, 0xf6, 0x58, 0x47, 0xd0, 0x2b, 0x8d, 0x02,
-	0x71, 0xf3, 0x67, 0x09, 0x80, 0xc5, 0xb3, 0x07, 0x1f, 0x82, 0xe4, 0x91, 0x56, 0x57, 0x9e, 0xaa,
-	0x9a, 0x52, 0xcf, 0x44, 0x72, 0xf7, 0x46, 0xe3, 0xe2, 0xff, 0x16, 0xee, 0x23, 0xcf, 0xc6, 0xa7,
-	0x8e, 0x87, 0x6d, 0x58, 0x04, 0x71, 0xad, 0x51, 0x6d, 0xd4, 0x4f, 0x32, 0x52, 0x6e, 0x7d, 0x34,
-	0x2e, 0x66, 0x16, 0x41, 0x1a, 0x31, 0x89, 0xed, 0xc3, 0x2d, 0x90, 0x6e, 0x68, 0x9f, 0x9f, 0xe8,
-	0x95, 0x7a, 0x1d, 0x29, 0xad, 0x56, 0x66, 0x25, 0x77, 0x7f, 0x34, 0x2e, 0xfe, 0x7f, 0x11, 0xd7,
-	0xf0, 0x5c, 0x3f, 0xfc, 0x02, 0x82, 0xb2, 0xca, 0xb1, 0x82, 0x4e, 0x04, 0x62, 0xf4, 0x9f, 0x65,
-	0x95, 0x73, 0x4c, 0xfd, 0x00, 0x34, 0xb7, 0xf6, 0xdd, 0x4f, 0xf9, 0xc8, 0xcb, 0xe7, 0xf9, 0xc8,
-	0xe6, 0x8b, 0x28, 0x28, 0x5e, 0xb7, 0x64, 0x88, 0xc1, 0xa3, 0x5a, 0x43, 0x6b, 0xa3, 0x4a, 0xad,
-	0xad, 0xd7, 0x1a, 0x75, 0x45, 0xdf, 0x57, 0x5b, 0xed, 0x06, 0x3a, 0xd1, 0x1b, 0x4d, 0x05, 0x55,
-	0xda, 0x6a, 0x43, 0xd3, 0xdb, 0x27, 0x4d, 0x45, 0x3f, 0xd2, 0x5a, 0x4d, 0xa5, 0xa6, 0x3e, 0x55,
-	0xc5, 0xd0, 0xe5, 0xd1, 0xb8, 0xb8, 0x75, 0x1d, 0xf6, 0x91, 0xc7, 0xfa, 0xd8, 0x72, 0x4e, 0x1d,
-	0x6c, 0xc3, 0x2f, 0xc0, 0x47, 0x37, 0x2a, 0xa3, 0x6a, 0x6a, 0x3b, 0x23, 0xe5, 0x36, 0x46, 0xe3,
-	0xe2, 0x07, 0xd7, 0xe1, 0xab, 0x9e, 0xc3, 0xe1, 0xd7, 0xe0, 0xe3, 0x1b, 0x01, 0x1f, 0xaa, 0x7b,
-	0xa8, 0xd2, 0x56, 0x32, 0x2b, 0xb9, 0xad, 0xd1, 0xb8, 0xf8, 0xe1, 0x75, 0xd8, 0x87, 0x4e, 0x87,
-	0x1a, 0x1c, 0xdf, 0x18, 0x7e, 0x4f, 0xd1, 0x94, 0x96, 0xda, 0xca, 0x44, 0x6f, 0x06, 0xbf, 0x87,
-	0x3d, 0xcc, 0x1c, 0x96, 0x8b, 0x05, 0xc7, 0xaa, 0x7e, 0xf5, 0xea, 0xf7, 0x7c, 0xe4, 0xe5, 0x34,
-	0x2f, 0xbd, 0x9a, 0xe6, 0xa5, 0xd7, 0xd3, 0xbc, 0xf4, 0xdb, 0x34, 0x2f, 0x7d, 0x7f, 0x91, 0x8f,
-	0xbc, 0xbe, 0xc8, 0x47, 0x7e, 0xbd, 0xc8, 0x47, 0xbe, 0xdc, 0x5d, 0xfa, 0x8a, 0x99, 0x45, 0xb9,
-	0x6b, 0x98, 0xac, 0xdc, 0x12, 0xe4, 0xd6, 0x30, 0xff, 0x86, 0xd0, 0xb3, 0xf2, 0xf0, 0xf2, 0xf7,
-	0x81, 0xe3, 0x71, 0x4c, 0x3d, 0xc3, 0x9d, 0xbd, 0xa2, 0x66, 0x5c, 0xfc, 0xcf, 0xff, 0xe4, 0xaf,
-	0x00, 0x00, 0x00, 0xff, 0xff, 0xa3, 0x45, 0x65, 0xf6, 0x47, 0x08, 0x00, 0x00,
+	// 1592 bytes of a gzipped FileDescriptorProto
+	0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xff, 0xad, 0x57, 0xcf, 0x6f, 0x1b, 0x45,
+	0x14, 0x8e, 0x63, 0x27, 0xb1, 0xc7, 0x6e, 0xe3, 0x4c, 0xd2, 0xc4, 0x75, 0xa5, 0x24, 0xb8, 0x08,
+	0x4a, 0xda, 0xc4, 0x6d, 0x0a, 0xa8, 0x14, 0x38, 0xc4, 0xf6, 0x36, 0x31, 0x6d, 0x6c, 0xb3, 0x76,
+	0x82, 0x82, 0x40, 0xab, 0x5d, 0xef, 0xc4, 0x59, 0x65, 0xbd, 0xe3, 0xee, 0xac, 0x93, 0xb8, 0x27,
+	0x8e, 0x88, 0x13, 0x47, 0x2e, 0x48, 0x48, 0x54, 0x88, 0x7f, 0x80, 0xff, 0xa1, 0x27, 0x84, 0x38,
+	0x71, 0xaa, 0xa0, 0xfd, 0x03, 0x90, 0x38, 0x72, 0xe2, 0xcd, 0xdb, 0x1f, 0x76, 0x68, 0xab, 0x04,
+	0x89, 0xc3, 0xca, 0x33, 0xf3, 0xbd, 0xef, 0xcd, 0x7b, 0xf3, 0xbe, 0x7d, 0xb3, 0x26, 0x05, 0xc1,
+	0xda, 0x2e, 0xf3, 0x8a, 0x6d, 0xde, 0xed, 0xf5, 0x3d, 0x56, 0x3c, 0xba, 0x65, 0x30, 0x4f, 0xbf,
+	0x55, 0xf4, 0x06, 0x3d, 0x26, 0xd6, 0x7a, 0x2e, 0xf7, 0x38, 0x9d, 0xf7, 0x6d, 0xd6, 0x02, 0x9b,
+	0xb5, 0xc0, 0x26, 0x3f, 0xd7, 0xe1, 0x1d, 0x8e, 0x26, 0x45, 0x39, 0xf2, 0xad, 0x0b, 0x6d, 0x32,
+	0xbd, 0xd1, 0x6e, 0x33, 0x21, 0x5a, 0xe0, 0xa2, 0xa1, 0xbb, 0x7a, 0x97, 0x7e, 0x44, 0x26, 0x8e,
+	0x74, 0xbb, 0xcf, 0x72, 0xb1, 0xe5, 0xd8, 0xb5, 0x8b, 0xeb, 0x85, 0xb5, 0x97, 0x3b, 0x5c, 0x1b,
+	0xf2, 0x4a, 0xd9, 0xbf, 0x9e, 0x2e, 0x65, 0x06, 0x7a, 0xd7, 0xbe, 0x5b, 0x40, 0x6a, 0x41, 0xf5,
+	0x5d, 0xdc, 0x4d, 0x7c, 0xf3, 0xdd, 0x52, 0xac, 0xf0, 0x6b, 0x8c, 0x24, 0xcb, 0xdc, 0x64, 0x55,
+	0x67, 0x9f, 0xd3, 0x2b, 0x24, 0xd5, 0x86, 0xb1, 0x76, 0xa0, 0x8b, 0x03, 0xdc, 0x22, 0xa3, 0x26,
+	0xe5, 0xc2, 0x16, 0xcc, 0xe9, 0x7d, 0x32, 0x05, 0x7b, 0xe9, 0x1e, 0x77, 0x73, 0xe3, 0x12, 0x2a,
+	0xdd, 0xfa, 0xfb, 0xe9, 0xd2, 0x6a, 0xc7, 0xf2, 0x0e, 0xfa, 0x86, 0x0c, 0x00, 0x32, 0x17, 0x5d,
+	0x2e, 0x82, 0x9f, 0x55, 0x61, 0x1e, 0x06, 0xb9, 0x43, 0x30, 0x1b, 0xa6, 0xe9, 0x42, 0x40, 0x6a,
+	0xe8, 0x81, 0xce, 0x93, 0x49, 0xc1, 0xfb, 0x6e, 0x9b, 0xe5, 0xe2, 0xe0, 0x2b, 0xa5, 0x06, 0x33,
+	0x9a, 0x23, 0x53, 0x46, 0xdf, 0xb2, 0x4d, 0xe6, 0xe6, 0x12, 0x08, 0x84, 0x53, 0xba, 0x42, 0x66,
+	0x4c, 0x76, 0xc4, 0x6c, 0xde, 0x63, 0xae, 0xd6, 0xeb, 0x1b, 0xda, 0x21, 0x1b, 0xe4, 0x26, 0x30,
+	0xc6, 0xe9, 0x08, 0x68, 0xf4, 0x8d, 0xfb, 0x6c, 0x50, 0x78, 0x1c, 0x23, 0xe9, 0x32, 0x77, 0x3c,
+	0x57, 0x6f, 0x7b, 0x30, 0xa7, 0x6f, 0x90, 0x69, 0xde, 0xd1, 0xda, 0xc1, 0x0a, 0x32, 0xfd, 0xec,
+	0x2e, 0xf0, 0xce, 0xa8, 0xdd, 0x4d, 0x32, 0xd7, 0xee, 0xbb, 0x2e, 0x73, 0xbc, 0xd3, 0xc6, 0x98,
+	0xaf, 0x4a, 0x03, 0x6c, 0x94, 0xf1, 0x3e, 0xc9, 0xbf, 0x8c, 0xa1, 0x41, 0x05, 0xf9, 0x3e, 0xe6,
+	0x96, 0x51, 0x17, 0x5e, 0xe4, 0x35, 0x24, 0x5c, 0xf8, 0x22, 0x46, 0x68, 0xb8, 0x58, 0xee, 0x0b,
+	0x8f, 0x77, 0xb1, 0x0a, 0x2d, 0x92, 0x66, 0x4e, 0xdb, 0xd6, 0x8f, 0x58, 0x14, 0x69, 0x7a, 0xfd,
+	0xea, 0xab, 0x4a, 0x3d, 0xe2, 0xb5, 0x74, 0xf1, 0xd9, 0xd3, 0x25, 0xa2, 0xf8, 0x5c, 0x98, 0xab,
+	0x84, 0x45, 0x63, 0x3a, 0x47, 0x26, 0x6c, 0xdd, 0x60, 0x36, 0x26, 0x93, 0x52, 0xfd, 0x49, 0xe1,
+	0xf9, 0x38, 0xc9, 0x84, 0x1e, 0x70, 0xf3, 0xab, 0x50, 0x65, 0x29, 0x01, 0xcb, 0xc4, 0x8d, 0x13,
+	0x25, 0x02, 0x3e, 0x27, 0x51, 0x21, 0x15, 0x75, 0x52, 0x42, 0x55, 0xf3, 0xff, 0x95, 0x42, 0x14,
+	0x58, 0x62, 0x24, 0x30, 0x5a, 0x09, 0xb6, 0x60, 0x26, 0x16, 0x39, 0xbd, 0xbe, 0xf2, 0x4a, 0xad,
+	0x1b, 0x82, 0xdb, 0xb0, 0xd0, 0x3a, 0x69, 0x70, 0x61, 0x79, 0x16, 0x77, 0xd4, 0x90, 0x4a, 0x57,
+	0x49, 0xda, 0x32, 0xda, 0x5a, 0x8f, 0xbb, 0x9e, 0xcc, 0x68, 0x52, 0xee, 0x50, 0xba, 0x00, 0x19,
+	0xa5, 0xaa, 0xa5, 0x72, 0x03, 0x56, 0x21, 0xa9, 0x14, 0x58, 0xe0, 0xd0, 0x94, 0xa1, 0xe8, 0x66,
+	0xd7, 0x72, 0x72, 0x53, 0x7e, 0x28, 0x38, 0xa1, 0x4b, 0x24, 0x8d, 0x83, 0xa0, 0xa8, 0x49, 0x2c,
+	0x2a, 0xc1, 0x25, 0xac, 0xa3, 0x34, 0x90, 0xbb, 0x30, 0x47, 0x37, 0x6c, 0x88, 0x37, 0x05, 0x06,
+	0x49, 0x95, 0xc0, 0x92, 0xe2, 0xaf, 0x14, 0x54, 0x42, 0x5f, 0x8c, 0x92, 0xbe, 0x46, 0x32, 0x86,
+	0xcd, 0xdb, 0x87, 0xda, 0x01, 0xb3, 0x3a, 0x07, 0x1e, 0x9e, 0x77, 0x5c, 0x4d, 0xe3, 0xda, 0x16,
+	0x2e, 0xd1, 0xcb, 0x24, 0xe9, 0x9d, 0x68, 0x96, 0x63, 0xb2, 0x13, 0x3c, 0xe9, 0x84, 0x3a, 0xe5,
+	0x9d, 0x54, 0xe5, 0xb4, 0x60, 0x91, 0x89, 0x6d, 0xa8, 0x86, 0x0d, 0x3d, 0x21, 0x7e, 0x3f, 0x14,
+	0x74, 0xe9, 0x0e, 0x14, 0xe2, 0xed, 0x91, 0x42, 0x78, 0x0c, 0x8c, 0x5d, 0x88, 0xd4, 0x1b, 0x1d,
+	0xda, 0x96, 0x21, 0x8a, 0xc6, 0xc0, 0x83, 0x8a, 0x6c, 0xb1, 0x93, 0x92, 0x1c, 0xa8, 0xf1, 0x40,
+	0x24, 0xbb, 0xd8, 0x5f, 0x7c, 0xc5, 0xfb, 0x93, 0xc2, 0x9f, 0x31, 0x92, 0x8b, 0x74, 0x2a, 0xdb,
+	0x81, 0x05, 0x5a, 0x75, 0x07, 0x0a, 0xac, 0x0c, 0xe8, 0x2e, 0x49, 0xc9, 0x37, 0x4f, 0x97, 0x29,
+	0x05, 0x6d, 0xe9, 0xce, 0x59, 0x5a, 0x1d, 0x71, 0x52, 0x0f, 0xb9, 0xb2, 0x59, 0xa9, 0x43, 0x57,
+	0xa3, 0x42, 0x1c, 0x7f, 0xa5, 0x10, 0x41, 0x25, 0xfd, 0x9e, 0x89, 0x2a, 0x89, 0xff, 0x77, 0x95,
+	0x04, 0x54, 0x9a, 0x25, 0xf1, 0xae, 0xe8, 0xa0, 0xfe, 0x32, 0xaa, 0x1c, 0x16, 0x7e, 0x4e, 0x92,
+	0x49, 0xec, 0xb8, 0x82, 0xde, 0x26, 0xf3, 0x5d, 0xfd, 0x44, 0x03, 0xcd, 0xf6, 0xb8, 0x23, 0x98,
+	0x06, 0x14, 0x5d, 0x13, 0xd6, 0x23, 0xbf, 0x07, 0x27, 0xd4, 0x59, 0x40, 0xd5, 0x00, 0xac, 0x00,
+	0xd6, 0x04, 0x88, 0xbe, 0x43, 0x16, 0x24, 0xe9, 0x61, 0x9f, 0xb9, 0x83, 0x21, 0x15, 0x59, 0x7e,
+	0x19, 0xe7, 0x00, 0xfe, 0x58, 0xa2, 0x21, 0x15, 0x69, 0x37, 0x08, 0x35, 0xfa, 0xf6, 0xa1, 0xd6,
+	0x65, 0x5d, 0x38, 0x9a, 0x50, 0x17, 0x71, 0xd4, 0x45, 0x56, 0x22, 0xdb, 0x08, 0x04, 0xe2, 0x78,
+	0x97, 0x2c, 0x44, 0x3d, 0xe7, 0x88, 0xb9, 0x02, 0x72, 0x0a, 0x29, 0x09, 0xa4, 0x5c, 0x0a, 0xe1,
+	0x5d, 0x1f, 0x0d, 0x78, 0xeb, 0x44, 0x02, 0x82, 0x39, 0xa2, 0x2f, 0x64, 0xd7, 0xda, 0xb7, 0x3a,
+	0x7e, 0xc7, 0xf7, 0xbb, 0xe9, 0x6c, 0x04, 0x96, 0x11, 0xc3, 0xe6, 0x0f, 0x9c, 0xfd, 0xbe, 0x63,
+	0x0a, 0xcd, 0x64, 0x0e, 0xef, 0x6a, 0xba, 0x6d, 0xf3, 0x63, 0x1b, 0x2a, 0x08, 0xaf, 0x54, 0x1c,
+	0xde, 0x94, 0x59, 0x04, 0x2b, 0x12, 0xdb, 0x08, 0x21, 0xfa, 0x01, 0xc9, 0xfb, 0x1c, 0x87, 0xbb,
+	0x5d, 0xdd, 0xb6, 0x1e, 0x61, 0x61, 0xc3, 0x10, 0xa7, 0x30, 0xc4, 0x1c, 0x5a, 0xd4, 0x46, 0x0d,
+	0x82, 0x28, 0xe1, 0x08, 0xfd, 0x97, 0x0a, 0x44, 0x06, 0x2f, 0x30, 0xc8, 0x56, 0x84, 0xd4, 0x24,
+	0x52, 0xe7, 0xf0, 0x05, 0x03, 0xb4, 0x81, 0x60, 0x40, 0x83, 0x23, 0xd4, 0xf1, 0xf2, 0xd3, 0x64,
+	0x0c, 0x21, 0x23, 0xe5, 0x1f, 0xa1, 0x8f, 0x3c, 0x00, 0x20, 0xb0, 0x5e, 0x23, 0xb3, 0x61, 0x8d,
+	0x8e, 0x5d, 0xbd, 0x17, 0x9a, 0x13, 0x34, 0x9f, 0x79, 0xe8, 0x17, 0x48, 0x22, 0xc3, 0x23, 0x87,
+	0xab, 0x06, 0x9a, 0x3d, 0x5c, 0xa1, 0x96, 0x79, 0x2a, 0x9f, 0xb4, 0x7f, 0xe4, 0x08, 0xef, 0x46,
+	0x68, 0xc0, 0xbb, 0x43, 0x72, 0xc2, 0x03, 0xad, 0x69, 0x52, 0x96, 0xb2, 0xfd, 0x8d, 0x10, 0x33,
+	0x48, 0x9c, 0x47, 0xbc, 0x3c, 0x84, 0x03, 0xe6, 0x7b, 0xe4, 0x32, 0x20, 0x51, 0x93, 0x80, 0xac,
+	0xba, 0x56, 0x94, 0xd6, 0x85, 0x88, 0x1a, 0x76, 0x8c, 0x07, 0x12, 0x0e, 0xa8, 0x70, 0x37, 0x05,
+	0x42, 0xea, 0xb8, 0xfc, 0xd8, 0x3b, 0x38, 0xcd, 0xbd, 0x88, 0xdc, 0x05, 0xdf, 0x62, 0x13, 0x0d,
+	0x46, 0xc9, 0x50, 0xf0, 0x8e, 0x2e, 0xb4, 0x7d, 0xee, 0x1e, 0xeb, 0xae, 0x69, 0x39, 0x9d, 0x90,
+	0x37, 0x8d, 0xbc, 0x59, 0x00, 0xef, 0x45, 0x58, 0xc0, 0x59, 0x25, 0x34, 0x12, 0x24, 0x37, 0x04,
+	0x73, 0xa5, 0x2a, 0x73, 0x59, 0x54, 0xc8, 0x4c, 0x88, 0xd4, 0x43, 0x40, 0xea, 0x63, 0x78, 0xa3,
+	0x0b, 0xab, 0xe3, 0xe8, 0x5e, 0xdf, 0x65, 0xe1, 0x3e, 0x33, 0xbe, 0x3e, 0x22, 0x8b, 0x66, 0x68,
+	0x30, 0x0c, 0xd0, 0x05, 0x0c, 0x1a, 0x3b, 0xf4, 0x7a, 0x01, 0x92, 0x0d, 0x89, 0xd4, 0x0f, 0xd0,
+	0x07, 0x55, 0x1f, 0x1b, 0x1e, 0xe6, 0xa9, 0x72, 0x77, 0x20, 0x20, 0xa6, 0x61, 0xc3, 0x15, 0xb9,
+	0x59, 0x7c, 0x31, 0xe7, 0x47, 0x8a, 0xbe, 0x29, 0xe1, 0x12, 0xa2, 0xf4, 0x43, 0x72, 0xc5, 0xaf,
+	0xa0, 0xbc, 0xdd, 0x4d, 0xe8, 0x31, 0x83, 0xde, 0x68, 0xf5, 0xe7, 0xfc, 0x68, 0xd1, 0x04, 0x1a,
+	0x69, 0x25, 0x32, 0xf0, 0x77, 0x2e, 0x30, 0x42, 0x55, 0x38, 0xe9, 0x23, 0x26, 0x1b, 0x18, 0xdc,
+	0x1a, 0x3d, 0x2e, 0x74, 0x5b, 0xb6, 0x5b, 0xcf, 0xf2, 0x6c, 0xbf, 0x95, 0xc0, 0x7d, 0x83, 0x13,
+	0xba, 0x4c, 0xd2, 0x26, 0x13, 0x6d, 0xd7, 0x42, 0x07, 0xc1, 0x7d, 0x3d, 0xba, 0x44, 0x17, 0x86,
+	0xbd, 0x31, 0x8e, 0x51, 0x07, 0xfd, 0x70, 0xe5, 0xa7, 0x18, 0x21, 0xc3, 0x6f, 0x3f, 0xf8, 0xee,
+	0x49, 0xed, 0xd4, 0x2a, 0xca, 0xbd, 0x6a, 0x4d, 0xa9, 0x64, 0xc7, 0xf2, 0x0b, 0x5f, 0x7d, 0xbb,
+	0x3c, 0x3b, 0x84, 0x77, 0xe0, 0x3e, 0xd8, 0xb7, 0x1c, 0x68, 0x80, 0xcb, 0x64, 0xb2, 0x56, 0x2f,
+	0xd5, 0x2b, 0x7b, 0xd9, 0x58, 0x7e, 0x0e, 0x8c, 0xb2, 0x43, 0xa3, 0x1a, 0x37, 0xb8, 0x39, 0xa0,
+	0xd7, 0x49, 0xa6, 0x5e, 0x7b, 0xb0, 0xa7, 0x6d, 0x54, 0x2a, 0xaa, 0xd2, 0x6c, 0x66, 0xc7, 0xf3,
+	0x97, 0xc1, 0xee, 0xd2, 0xd0, 0xae, 0xee, 0xd8, 0x83, 0xe0, 0x6a, 0x97, 0xdb, 0x2a, 0xbb, 0x8a,
+	0xba, 0x87, 0x1e, 0xe3, 0xff, 0xde, 0x56, 0x81, 0xba, 0x0c, 0xa4, 0xd3, 0x7c, 0xf2, 0xcb, 0xef,
+	0x17, 0xc7, 0x7e, 0x7c, 0xbc, 0x38, 0xb6, 0xf2, 0x43, 0x9c, 0x2c, 0x9f, 0x75, 0x39, 0x50, 0x46,
+	0x6e, 0x96, 0xeb, 0xb5, 0x96, 0xba, 0x51, 0x6e, 0x69, 0xe5, 0x7a, 0x45, 0xd1, 0xb6, 0xaa, 0xcd,
+	0x56, 0x5d, 0xdd, 0xd3, 0xea, 0x0d, 0x45, 0xdd, 0x68, 0x55, 0xeb, 0x35, 0xad, 0xb5, 0xd7, 0x50,
+	0xb4, 0x9d, 0x5a, 0xb3, 0xa1, 0x94, 0xab, 0xf7, 0xaa, 0x98, 0x74, 0x11, 0x76, 0xbf, 0x7e, 0x96,
+	0xef, 0x1d, 0x47, 0xf4, 0x58, 0xdb, 0xda, 0xb7, 0xe0, 0x30, 0x3e, 0x21, 0x6f, 0x9d, 0x6b, 0x9b,
+	0x6a, 0xad, 0xda, 0x82, 0xf3, 0xba, 0x06, 0xfe, 0x5f, 0x3f, 0xcb, 0x7f, 0xd5, 0xb1, 0x3c, 0xfa,
+	0x39, 0xb9, 0x71, 0x2e, 0xc7, 0xdb, 0xd5, 0x4d, 0x98, 0x2a, 0x70, 0xc6, 0xd7, 0xc1, 0xf7, 0x9b,
+	0x67, 0xf9, 0xde, 0xb6, 0x40, 0xc6, 0x1e, 0x3b, 0xb7, 0xfb, 0x4d, 0xa5, 0xa6, 0x34, 0xab, 0x4d,
+	0x28, 0xcc, 0xb9, 0xdc, 0x6f, 0x32, 0x87, 0x09, 0x4b, 0xe4, 0x13, 0xb2, 0x58, 0xa5, 0xcf, 0x9e,
+	0xfc, 0x01, 0x25, 0x7b, 0xb6, 0x18, 0x7b, 0x02, 0xcf, 0x2f, 0xf0, 0xfc, 0x0e, 0xcf, 0xd7, 0xcf,
+	0x17, 0xc7, 0x7e, 0x81, 0xe7, 0x37, 0x78, 0x3e, 0xbd, 0x3b, 0xf2, 0xf5, 0x01, 0x8a, 0xf5, 0xe0,
+	0x73, 0x4e, 0x14, 0x9b, 0x78, 0x29, 0xd7, 0x98, 0x77, 0xcc, 0xdd, 0xc3, 0xe2, 0x49, 0xf4, 0x27,
+	0x09, 0x1a, 0x37, 0x73, 0x1d, 0xdd, 0xf6, 0x3f, 0x0f, 0x8d, 0x49, 0xfc, 0xe3, 0x73, 0xfb, 0x1f,
+	0xfa, 0xf7, 0xd3, 0x7f, 0x4c, 0x0d, 0x00, 0x00,
 }
 
 func (this *AccessTypeParam) Equal(that interface{}) bool {
@@ -561,6 +721,9 @@ func (this *CodeInfo) Equal(that interface{}) bool {
 	if this.Builder != that1.Builder {
 		return false
 	}
+	if !bytes.Equal(this.DeveloperPubKey, that1.DeveloperPubKey) {
+		return false
+	}
 	return true
 }
 func (this *ContractKey) Equal(that interface{}) bool {
@@ -660,6 +823,9 @@ func (this *ContractInfo) Equal(that interface{}) bool {
 	if !bytes.Equal(this.AdminProof, that1.AdminProof) {
 		return false
 	}
+	if this.IBCEnabled != that1.IBCEnabled {
+		return false
+	}
 	return true
 }
 func (this *AbsoluteTxPosition) Equal(that interface{}) bool {
@@ -749,6 +915,127 @@ func (this *ContractCodeHistoryEntry) Equal(that interface{}) bool {
 	}
 	return true
 }
+func (this *Params) Equal(that interface{}) bool {
+	if that == nil {
+		return this == nil
+	}
+
+	that1, ok := that.(*Params)
+	if !ok {
+		that2, ok := that.(Params)
+		if ok {
+			that1 = &that2
+		} else {
+			return false
+		}
+	}
+	if that1 == nil {
+		return this == nil
+	} else if this == nil {
+		return false
+	}
+	if this.MaxResponseDataSize != that1.MaxResponseDataSize {
+		return false
+	}
+	if this.MaxQueryResponseSize != that1.MaxQueryResponseSize {
+		return false
+	}
+	if this.BulkMemoryHeight != that1.BulkMemoryHeight {
+		return false
+	}
+	if this.ContractVersionHeight != that1.ContractVersionHeight {
+		return false
+	}
+	if !bytes.Equal(this.ConsensusConfigHash, that1.ConsensusConfigHash) {
+		return false
+	}
+	if len(this.FundsDenomAllowlist) != len(that1.FundsDenomAllowlist) {
+		return false
+	}
+	for i := range this.FundsDenomAllowlist {
+		if this.FundsDenomAllowlist[i] != that1.FundsDenomAllowlist[i] {
+			return false
+		}
+	}
+	if this.FundsNormalizationHeight != that1.FundsNormalizationHeight {
+		return false
+	}
+	if this.IBCEntryPointsHeight != that1.IBCEntryPointsHeight {
+		return false
+	}
+	if this.AccessListHeight != that1.AccessListHeight {
+		return false
+	}
+	if this.QueryRewrapHeight != that1.QueryRewrapHeight {
+		return false
+	}
+	if this.EventValidationHeight != that1.EventValidationHeight {
+		return false
+	}
+	if this.StateCompressionHeight != that1.StateCompressionHeight {
+		return false
+	}
+	if this.StackHeightLimitHeight != that1.StackHeightLimitHeight {
+		return false
+	}
+	if this.MemoryGrowthLimitHeight != that1.MemoryGrowthLimitHeight {
+		return false
+	}
+	if this.GasForwardingHeight != that1.GasForwardingHeight {
+		return false
+	}
+	if len(this.ContractObservers) != len(that1.ContractObservers) {
+		return false
+	}
+	for i := range this.ContractObservers {
+		if this.ContractObservers[i] != that1.ContractObservers[i] {
+			return false
+		}
+	}
+	if this.DeveloperSignatureHeight != that1.DeveloperSignatureHeight {
+		return false
+	}
+	if this.RevertReasonsHeight != that1.RevertReasonsHeight {
+		return false
+	}
+	if this.QueryRewrapGraceBlocks != that1.QueryRewrapGraceBlocks {
+		return false
+	}
+	if this.StateKeyDecryptionHeight != that1.StateKeyDecryptionHeight {
+		return false
+	}
+	return true
+}
+func (this *RemoveCodeProposal) Equal(that interface{}) bool {
+	if that == nil {
+		return this == nil
+	}
+
+	that1, ok := that.(*RemoveCodeProposal)
+	if !ok {
+		that2, ok := that.(RemoveCodeProposal)
+		if ok {
+			that1 = &that2
+		} else {
+			return false
+		}
+	}
+	if that1 == nil {
+		return this == nil
+	} else if this == nil {
+		return false
+	}
+	if this.Title != that1.Title {
+		return false
+	}
+	if this.Description != that1.Description {
+		return false
+	}
+	if this.CodeID != that1.CodeID {
+		return false
+	}
+	return true
+}
 func (m *AccessTypeParam) Marshal() (dAtA []byte, err error) {
 	size := m.Size()
 	dAtA = make([]byte, size)
@@ -797,6 +1084,13 @@ func (m *CodeInfo) MarshalToSizedBuffer(dAtA []byte) (int, error) {
 	_ = i
 	var l int
 	_ = l
+	if len(m.DeveloperPubKey) > 0 {
+		i -= len(m.DeveloperPubKey)
+		copy(dAtA[i:], m.DeveloperPubKey)
+		i = encodeVarintTypes(dAtA, i, uint64(len(m.DeveloperPubKey)))
+		i--
+		dAtA[i] = 0x2a
+	}
 	if len(m.Builder) > 0 {
 		i -= len(m.Builder)
 		copy(dAtA[i:], m.Builder)
@@ -934,6 +1228,16 @@ func (m *ContractInfo) MarshalToSizedBuffer(dAtA []byte) (int, error) {
 	_ = i
 	var l int
 	_ = l
+	if m.IBCEnabled {
+		i--
+		if m.IBCEnabled {
+			dAtA[i] = 1
+		} else {
+			dAtA[i] = 0
+		}
+		i--
+		dAtA[i] = 0x48
+	}
 	if len(m.AdminProof) > 0 {
 		i -= len(m.AdminProof)
 		copy(dAtA[i:], m.AdminProof)
@@ -1111,6 +1415,191 @@ func (m *ContractCodeHistoryEntry) MarshalToSizedBuffer(dAtA []byte) (int, error
 	return len(dAtA) - i, nil
 }
 
+func (m *Params) Marshal() (dAtA []byte, err error) {
+	size := m.Size()
+	dAtA = make([]byte, size)
+	n, err := m.MarshalToSizedBuffer(dAtA[:size])
+	if err != nil {
+		return nil, err
+	}
+	return dAtA[:n], nil
+}
+
+func (m *Params) MarshalTo(dAtA []byte) (int, error) {
+	size := m.Size()
+	return m.MarshalToSizedBuffer(dAtA[:size])
+}
+
+func (m *Params) MarshalToSizedBuffer(dAtA []byte) (int, error) {
+	i := len(dAtA)
+	_ = i
+	var l int
+	_ = l
+	if m.StateKeyDecryptionHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.StateKeyDecryptionHeight))
+		i--
+		dAtA[i] = 0x1
+		i--
+		dAtA[i] = 0xa0
+	}
+	if m.QueryRewrapGraceBlocks != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.QueryRewrapGraceBlocks))
+		i--
+		dAtA[i] = 0x1
+		i--
+		dAtA[i] = 0x98
+	}
+	if m.RevertReasonsHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.RevertReasonsHeight))
+		i--
+		dAtA[i] = 0x1
+		i--
+		dAtA[i] = 0x90
+	}
+	if m.DeveloperSignatureHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.DeveloperSignatureHeight))
+		i--
+		dAtA[i] = 0x1
+		i--
+		dAtA[i] = 0x88
+	}
+	if len(m.ContractObservers) > 0 {
+		for iNdEx := len(m.ContractObservers) - 1; iNdEx >= 0; iNdEx-- {
+			i -= len(m.ContractObservers[iNdEx])
+			copy(dAtA[i:], m.ContractObservers[iNdEx])
+			i = encodeVarintTypes(dAtA, i, uint64(len(m.ContractObservers[iNdEx])))
+			i--
+			dAtA[i] = 0x1
+			i--
+			dAtA[i] = 0x82
+		}
+	}
+	if m.GasForwardingHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.GasForwardingHeight))
+		i--
+		dAtA[i] = 0x78
+	}
+	if m.MemoryGrowthLimitHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.MemoryGrowthLimitHeight))
+		i--
+		dAtA[i] = 0x70
+	}
+	if m.StackHeightLimitHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.StackHeightLimitHeight))
+		i--
+		dAtA[i] = 0x68
+	}
+	if m.StateCompressionHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.StateCompressionHeight))
+		i--
+		dAtA[i] = 0x60
+	}
+	if m.EventValidationHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.EventValidationHeight))
+		i--
+		dAtA[i] = 0x58
+	}
+	if m.QueryRewrapHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.QueryRewrapHeight))
+		i--
+		dAtA[i] = 0x50
+	}
+	if m.AccessListHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.AccessListHeight))
+		i--
+		dAtA[i] = 0x48
+	}
+	if m.IBCEntryPointsHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.IBCEntryPointsHeight))
+		i--
+		dAtA[i] = 0x40
+	}
+	if m.FundsNormalizationHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.FundsNormalizationHeight))
+		i--
+		dAtA[i] = 0x38
+	}
+	if len(m.FundsDenomAllowlist) > 0 {
+		for iNdEx := len(m.FundsDenomAllowlist) - 1; iNdEx >= 0; iNdEx-- {
+			i -= len(m.FundsDenomAllowlist[iNdEx])
+			copy(dAtA[i:], m.FundsDenomAllowlist[iNdEx])
+			i = encodeVarintTypes(dAtA, i, uint64(len(m.FundsDenomAllowlist[iNdEx])))
+			i--
+			dAtA[i] = 0x32
+		}
+	}
+	if len(m.ConsensusConfigHash) > 0 {
+		i -= len(m.ConsensusConfigHash)
+		copy(dAtA[i:], m.ConsensusConfigHash)
+		i = encodeVarintTypes(dAtA, i, uint64(len(m.ConsensusConfigHash)))
+		i--
+		dAtA[i] = 0x2a
+	}
+	if m.ContractVersionHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.ContractVersionHeight))
+		i--
+		dAtA[i] = 0x20
+	}
+	if m.BulkMemoryHeight != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.BulkMemoryHeight))
+		i--
+		dAtA[i] = 0x18
+	}
+	if m.MaxQueryResponseSize != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.MaxQueryResponseSize))
+		i--
+		dAtA[i] = 0x10
+	}
+	if m.MaxResponseDataSize != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.MaxResponseDataSize))
+		i--
+		dAtA[i] = 0x8
+	}
+	return len(dAtA) - i, nil
+}
+
+func (m *RemoveCodeProposal) Marshal() (dAtA []byte, err error) {
+	size := m.Size()
+	dAtA = make([]byte, size)
+	n, err := m.MarshalToSizedBuffer(dAtA[:size])
+	if err != nil {
+		return nil, err
+	}
+	return dAtA[:n], nil
+}
+
+func (m *RemoveCodeProposal) MarshalTo(dAtA []byte) (int, error) {
+	size := m.Size()
+	return m.MarshalToSizedBuffer(dAtA[:size])
+}
+
+func (m *RemoveCodeProposal) MarshalToSizedBuffer(dAtA []byte) (int, error) {
+	i := len(dAtA)
+	_ = i
+	var l int
+	_ = l
+	if m.CodeID != 0 {
+		i = encodeVarintTypes(dAtA, i, uint64(m.CodeID))
+		i--
+		dAtA[i] = 0x18
+	}
+	if len(m.Description) > 0 {
+		i -= len(m.Description)
+		copy(dAtA[i:], m.Description)
+		i = encodeVarintTypes(dAtA, i, uint64(len(m.Description)))
+		i--
+		dAtA[i] = 0x12
+	}
+	if len(m.Title) > 0 {
+		i -= len(m.Title)
+		copy(dAtA[i:], m.Title)
+		i = encodeVarintTypes(dAtA, i, uint64(len(m.Title)))
+		i--
+		dAtA[i] = 0xa
+	}
+	return len(dAtA) - i, nil
+}
+
 func encodeVarintTypes(dAtA []byte, offset int, v uint64) int {
 	offset -= sovTypes(v)
 	base := offset
@@ -1156,6 +1645,10 @@ func (m *CodeInfo) Size() (n int) {
 	if l > 0 {
 		n += 1 + l + sovTypes(uint64(l))
 	}
+	l = len(m.DeveloperPubKey)
+	if l > 0 {
+		n += 1 + l + sovTypes(uint64(l))
+	}
 	return n
 }
 
@@ -1230,6 +1723,9 @@ func (m *ContractInfo) Size() (n int) {
 	if l > 0 {
 		n += 1 + l + sovTypes(uint64(l))
 	}
+	if m.IBCEnabled {
+		n += 2
+	}
 	return n
 }
 
@@ -1288,23 +1784,119 @@ func (m *ContractCodeHistoryEntry) Size() (n int) {
 	return n
 }
 
-func sovTypes(x uint64) (n int) {
-	return (math_bits.Len64(x|1) + 6) / 7
-}
-func sozTypes(x uint64) (n int) {
-	return sovTypes(uint64((x << 1) ^ uint64((int64(x) >> 63))))
-}
-func (m *AccessTypeParam) Unmarshal(dAtA []byte) error {
-	l := len(dAtA)
-	iNdEx := 0
-	for iNdEx < l {
-		preIndex := iNdEx
-		var wire uint64
-		for shift := uint(0); ; shift += 7 {
-			if shift >= 64 {
-				return ErrIntOverflowTypes
-			}
-			if iNdEx >= l {
+func (m *Params) Size() (n int) {
+	if m == nil {
+		return 0
+	}
+	var l int
+	_ = l
+	if m.MaxResponseDataSize != 0 {
+		n += 1 + sovTypes(uint64(m.MaxResponseDataSize))
+	}
+	if m.MaxQueryResponseSize != 0 {
+		n += 1 + sovTypes(uint64(m.MaxQueryResponseSize))
+	}
+	if m.BulkMemoryHeight != 0 {
+		n += 1 + sovTypes(uint64(m.BulkMemoryHeight))
+	}
+	if m.ContractVersionHeight != 0 {
+		n += 1 + sovTypes(uint64(m.ContractVersionHeight))
+	}
+	l = len(m.ConsensusConfigHash)
+	if l > 0 {
+		n += 1 + l + sovTypes(uint64(l))
+	}
+	if len(m.FundsDenomAllowlist) > 0 {
+		for _, s := range m.FundsDenomAllowlist {
+			l = len(s)
+			n += 1 + l + sovTypes(uint64(l))
+		}
+	}
+	if m.FundsNormalizationHeight != 0 {
+		n += 1 + sovTypes(uint64(m.FundsNormalizationHeight))
+	}
+	if m.IBCEntryPointsHeight != 0 {
+		n += 1 + sovTypes(uint64(m.IBCEntryPointsHeight))
+	}
+	if m.AccessListHeight != 0 {
+		n += 1 + sovTypes(uint64(m.AccessListHeight))
+	}
+	if m.QueryRewrapHeight != 0 {
+		n += 1 + sovTypes(uint64(m.QueryRewrapHeight))
+	}
+	if m.EventValidationHeight != 0 {
+		n += 1 + sovTypes(uint64(m.EventValidationHeight))
+	}
+	if m.StateCompressionHeight != 0 {
+		n += 1 + sovTypes(uint64(m.StateCompressionHeight))
+	}
+	if m.StackHeightLimitHeight != 0 {
+		n += 1 + sovTypes(uint64(m.StackHeightLimitHeight))
+	}
+	if m.MemoryGrowthLimitHeight != 0 {
+		n += 1 + sovTypes(uint64(m.MemoryGrowthLimitHeight))
+	}
+	if m.GasForwardingHeight != 0 {
+		n += 1 + sovTypes(uint64(m.GasForwardingHeight))
+	}
+	if len(m.ContractObservers) > 0 {
+		for _, s := range m.ContractObservers {
+			l = len(s)
+			n += 2 + l + sovTypes(uint64(l))
+		}
+	}
+	if m.DeveloperSignatureHeight != 0 {
+		n += 2 + sovTypes(uint64(m.DeveloperSignatureHeight))
+	}
+	if m.RevertReasonsHeight != 0 {
+		n += 2 + sovTypes(uint64(m.RevertReasonsHeight))
+	}
+	if m.QueryRewrapGraceBlocks != 0 {
+		n += 2 + sovTypes(uint64(m.QueryRewrapGraceBlocks))
+	}
+	if m.StateKeyDecryptionHeight != 0 {
+		n += 2 + sovTypes(uint64(m.StateKeyDecryptionHeight))
+	}
+	return n
+}
+
+func (m *RemoveCodeProposal) Size() (n int) {
+	if m == nil {
+		return 0
+	}
+	var l int
+	_ = l
+	l = len(m.Title)
+	if l > 0 {
+		n += 1 + l + sovTypes(uint64(l))
+	}
+	l = len(m.Description)
+	if l > 0 {
+		n += 1 + l + sovTypes(uint64(l))
+	}
+	if m.CodeID != 0 {
+		n += 1 + sovTypes(uint64(m.CodeID))
+	}
+	return n
+}
+
+func sovTypes(x uint64) (n int) {
+	return (math_bits.Len64(x|1) + 6) / 7
+}
+func sozTypes(x uint64) (n int) {
+	return sovTypes(uint64((x << 1) ^ uint64((int64(x) >> 63))))
+}
+func (m *AccessTypeParam) Unmarshal(dAtA []byte) error {
+	l := len(dAtA)
+	iNdEx := 0
+	for iNdEx < l {
+		preIndex := iNdEx
+		var wire uint64
+		for shift := uint(0); ; shift += 7 {
+			if shift >= 64 {
+				return ErrIntOverflowTypes
+			}
+			if iNdEx >= l {
 				return io.ErrUnexpectedEOF
 			}
 			b := dAtA[iNdEx]
@@ -1524,6 +2116,40 @@ func (m *CodeInfo) Unmarshal(dAtA []byte) error {
 			}
 			m.Builder = string(dAtA[iNdEx:postIndex])
 			iNdEx = postIndex
+		case 5:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field DeveloperPubKey", wireType)
+			}
+			var byteLen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				byteLen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if byteLen < 0 {
+				return ErrInvalidLengthTypes
+			}
+			postIndex := iNdEx + byteLen
+			if postIndex < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.DeveloperPubKey = append(m.DeveloperPubKey[:0], dAtA[iNdEx:postIndex]...)
+			if m.DeveloperPubKey == nil {
+				m.DeveloperPubKey = []byte{}
+			}
+			iNdEx = postIndex
 		default:
 			iNdEx = preIndex
 			skippy, err := skipTypes(dAtA[iNdEx:])
@@ -2063,6 +2689,26 @@ func (m *ContractInfo) Unmarshal(dAtA []byte) error {
 				m.AdminProof = []byte{}
 			}
 			iNdEx = postIndex
+		case 9:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field IBCEnabled", wireType)
+			}
+			var v int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				v |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			m.IBCEnabled = bool(v != 0)
 		default:
 			iNdEx = preIndex
 			skippy, err := skipTypes(dAtA[iNdEx:])
@@ -2448,6 +3094,610 @@ func (m *ContractCodeHistoryEntry) Unmarshal(dAtA []byte) error {
 	}
 	return nil
 }
+func (m *Params) Unmarshal(dAtA []byte) error {
+	l := len(dAtA)
+	iNdEx := 0
+	for iNdEx < l {
+		preIndex := iNdEx
+		var wire uint64
+		for shift := uint(0); ; shift += 7 {
+			if shift >= 64 {
+				return ErrIntOverflowTypes
+			}
+			if iNdEx >= l {
+				return io.ErrUnexpectedEOF
+			}
+			b := dAtA[iNdEx]
+			iNdEx++
+			wire |= uint64(b&0x7F) << shift
+			if b < 0x80 {
+				break
+			}
+		}
+		fieldNum := int32(wire >> 3)
+		wireType := int(wire & 0x7)
+		if wireType == 4 {
+			return fmt.Errorf("proto: Params: wiretype end group for non-group")
+		}
+		if fieldNum <= 0 {
+			return fmt.Errorf("proto: Params: illegal tag %d (wire type %d)", fieldNum, wire)
+		}
+		switch fieldNum {
+		case 1:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field MaxResponseDataSize", wireType)
+			}
+			m.MaxResponseDataSize = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.MaxResponseDataSize |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 2:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field MaxQueryResponseSize", wireType)
+			}
+			m.MaxQueryResponseSize = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.MaxQueryResponseSize |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 3:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field BulkMemoryHeight", wireType)
+			}
+			m.BulkMemoryHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.BulkMemoryHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 4:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field ContractVersionHeight", wireType)
+			}
+			m.ContractVersionHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.ContractVersionHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 5:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field ConsensusConfigHash", wireType)
+			}
+			var byteLen int
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				byteLen |= int(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			if byteLen < 0 {
+				return ErrInvalidLengthTypes
+			}
+			postIndex := iNdEx + byteLen
+			if postIndex < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.ConsensusConfigHash = append(m.ConsensusConfigHash[:0], dAtA[iNdEx:postIndex]...)
+			if m.ConsensusConfigHash == nil {
+				m.ConsensusConfigHash = []byte{}
+			}
+			iNdEx = postIndex
+		case 6:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field FundsDenomAllowlist", wireType)
+			}
+			var stringLen uint64
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				stringLen |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			intStringLen := int(stringLen)
+			if intStringLen < 0 {
+				return ErrInvalidLengthTypes
+			}
+			postIndex := iNdEx + intStringLen
+			if postIndex < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.FundsDenomAllowlist = append(m.FundsDenomAllowlist, string(dAtA[iNdEx:postIndex]))
+			iNdEx = postIndex
+		case 7:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field FundsNormalizationHeight", wireType)
+			}
+			m.FundsNormalizationHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.FundsNormalizationHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 8:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field IBCEntryPointsHeight", wireType)
+			}
+			m.IBCEntryPointsHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.IBCEntryPointsHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 9:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field AccessListHeight", wireType)
+			}
+			m.AccessListHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.AccessListHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 10:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field QueryRewrapHeight", wireType)
+			}
+			m.QueryRewrapHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.QueryRewrapHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 11:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field EventValidationHeight", wireType)
+			}
+			m.EventValidationHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.EventValidationHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 12:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field StateCompressionHeight", wireType)
+			}
+			m.StateCompressionHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.StateCompressionHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 13:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field StackHeightLimitHeight", wireType)
+			}
+			m.StackHeightLimitHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.StackHeightLimitHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 14:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field MemoryGrowthLimitHeight", wireType)
+			}
+			m.MemoryGrowthLimitHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.MemoryGrowthLimitHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 15:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field GasForwardingHeight", wireType)
+			}
+			m.GasForwardingHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.GasForwardingHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 16:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field ContractObservers", wireType)
+			}
+			var stringLen uint64
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				stringLen |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			intStringLen := int(stringLen)
+			if intStringLen < 0 {
+				return ErrInvalidLengthTypes
+			}
+			postIndex := iNdEx + intStringLen
+			if postIndex < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.ContractObservers = append(m.ContractObservers, string(dAtA[iNdEx:postIndex]))
+			iNdEx = postIndex
+		case 17:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field DeveloperSignatureHeight", wireType)
+			}
+			m.DeveloperSignatureHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.DeveloperSignatureHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 18:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field RevertReasonsHeight", wireType)
+			}
+			m.RevertReasonsHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.RevertReasonsHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 19:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field QueryRewrapGraceBlocks", wireType)
+			}
+			m.QueryRewrapGraceBlocks = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.QueryRewrapGraceBlocks |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		case 20:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field StateKeyDecryptionHeight", wireType)
+			}
+			m.StateKeyDecryptionHeight = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.StateKeyDecryptionHeight |= int64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		default:
+			iNdEx = preIndex
+			skippy, err := skipTypes(dAtA[iNdEx:])
+			if err != nil {
+				return err
+			}
+			if (skippy < 0) || (iNdEx+skippy) < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if (iNdEx + skippy) > l {
+				return io.ErrUnexpectedEOF
+			}
+			iNdEx += skippy
+		}
+	}
+
+	if iNdEx > l {
+		return io.ErrUnexpectedEOF
+	}
+	return nil
+}
+func (m *RemoveCodeProposal) Unmarshal(dAtA []byte) error {
+	l := len(dAtA)
+	iNdEx := 0
+	for iNdEx < l {
+		preIndex := iNdEx
+		var wire uint64
+		for shift := uint(0); ; shift += 7 {
+			if shift >= 64 {
+				return ErrIntOverflowTypes
+			}
+			if iNdEx >= l {
+				return io.ErrUnexpectedEOF
+			}
+			b := dAtA[iNdEx]
+			iNdEx++
+			wire |= uint64(b&0x7F) << shift
+			if b < 0x80 {
+				break
+			}
+		}
+		fieldNum := int32(wire >> 3)
+		wireType := int(wire & 0x7)
+		if wireType == 4 {
+			return fmt.Errorf("proto: RemoveCodeProposal: wiretype end group for non-group")
+		}
+		if fieldNum <= 0 {
+			return fmt.Errorf("proto: RemoveCodeProposal: illegal tag %d (wire type %d)", fieldNum, wire)
+		}
+		switch fieldNum {
+		case 1:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field Title", wireType)
+			}
+			var stringLen uint64
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				stringLen |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			intStringLen := int(stringLen)
+			if intStringLen < 0 {
+				return ErrInvalidLengthTypes
+			}
+			postIndex := iNdEx + intStringLen
+			if postIndex < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.Title = string(dAtA[iNdEx:postIndex])
+			iNdEx = postIndex
+		case 2:
+			if wireType != 2 {
+				return fmt.Errorf("proto: wrong wireType = %d for field Description", wireType)
+			}
+			var stringLen uint64
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				stringLen |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+			intStringLen := int(stringLen)
+			if intStringLen < 0 {
+				return ErrInvalidLengthTypes
+			}
+			postIndex := iNdEx + intStringLen
+			if postIndex < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if postIndex > l {
+				return io.ErrUnexpectedEOF
+			}
+			m.Description = string(dAtA[iNdEx:postIndex])
+			iNdEx = postIndex
+		case 3:
+			if wireType != 0 {
+				return fmt.Errorf("proto: wrong wireType = %d for field CodeID", wireType)
+			}
+			m.CodeID = 0
+			for shift := uint(0); ; shift += 7 {
+				if shift >= 64 {
+					return ErrIntOverflowTypes
+				}
+				if iNdEx >= l {
+					return io.ErrUnexpectedEOF
+				}
+				b := dAtA[iNdEx]
+				iNdEx++
+				m.CodeID |= uint64(b&0x7F) << shift
+				if b < 0x80 {
+					break
+				}
+			}
+		default:
+			iNdEx = preIndex
+			skippy, err := skipTypes(dAtA[iNdEx:])
+			if err != nil {
+				return err
+			}
+			if (skippy < 0) || (iNdEx+skippy) < 0 {
+				return ErrInvalidLengthTypes
+			}
+			if (iNdEx + skippy) > l {
+				return io.ErrUnexpectedEOF
+			}
+			iNdEx += skippy
+		}
+	}
+
+	if iNdEx > l {
+		return io.ErrUnexpectedEOF
+	}
+	return nil
+}
 func skipTypes(dAtA []byte) (n int, err error) {
 	l := len(dAtA)
 	iNdEx := 0
@@ -3,8 +3,11 @@ package types
 import (
 	"strings"
 	"testing"
+	"time"
 
+	sdk "github.com/cosmos/cosmos-sdk/types"
 	"github.com/stretchr/testify/require"
+	tmproto "github.com/tendermint/tendermint/proto/tendermint/types"
 )
 
 func TestContractInfoValidateBasic(t *testing.T) {
@@ -92,6 +95,13 @@ func TestCodeInfoValidateBasic(t *testing.T) {
 			srcMutator: func(c *CodeInfo) { c.Builder = "invalid" },
 			expError:   true,
 		},
+		"developer key": {
+			srcMutator: func(c *CodeInfo) { c.DeveloperPubKey = make([]byte, 33) },
+		},
+		"developer key invalid": {
+			srcMutator: func(c *CodeInfo) { c.DeveloperPubKey = make([]byte, 32) },
+			expError:   true,
+		},
 		/*
 			"Instantiate config invalid": {
 				srcMutator: func(c *CodeInfo) { c.InstantiateConfig = AccessConfig{} },
@@ -111,3 +121,56 @@ func TestCodeInfoValidateBasic(t *testing.T) {
 		})
 	}
 }
+
+func TestNewEnvFeeInfo(t *testing.T) {
+	ctx := sdk.NewContext(nil, tmproto.Header{Height: 1, Time: time.Now()}, false, nil)
+	creator := sdk.AccAddress(make([]byte, 20))
+	contract := sdk.AccAddress(make([]byte, 20))
+
+	env := NewEnv(ctx, creator, nil, contract, ContractKey{}, nil)
+	require.Empty(t, env.Message.FeePayer)
+	require.Empty(t, env.Message.FeeGranter)
+
+	payer := sdk.AccAddress([]byte("payer_______________"))
+	ctx = WithFeeInfo(ctx, FeeInfo{Payer: payer})
+	env = NewEnv(ctx, creator, nil, contract, ContractKey{}, nil)
+	require.Equal(t, payer.String(), env.Message.FeePayer)
+	require.Empty(t, env.Message.FeeGranter)
+
+	granter := sdk.AccAddress([]byte("granter_____________"))
+	ctx = WithFeeInfo(ctx, FeeInfo{Payer: payer, Granter: granter})
+	env = NewEnv(ctx, creator, nil, contract, ContractKey{}, nil)
+	require.Equal(t, payer.String(), env.Message.FeePayer)
+	require.Equal(t, granter.String(), env.Message.FeeGranter)
+}
+
+func TestNewEnvSimulation(t *testing.T) {
+	creator := sdk.AccAddress(make([]byte, 20))
+	contract := sdk.AccAddress(make([]byte, 20))
+
+	ctx := sdk.NewContext(nil, tmproto.Header{Height: 1, Time: time.Now()}, false, nil)
+	require.False(t, NewEnv(ctx, creator, nil, contract, ContractKey{}, nil).Simulation)
+
+	ctx = sdk.NewContext(nil, tmproto.Header{Height: 1, Time: time.Now()}, true, nil)
+	require.True(t, NewEnv(ctx, creator, nil, contract, ContractKey{}, nil).Simulation)
+}
+
+func TestConsensusHash(t *testing.T) {
+	hash := ConsensusHash("staking,stargate,ibc3")
+
+	t.Log("the order of features doesn't matter")
+	require.Equal(t, hash, ConsensusHash("ibc3, stargate,staking"))
+
+	t.Log("the features do")
+	require.NotEqual(t, hash, ConsensusHash("staking,stargate"))
+}
+
+func TestWasmConfigUpgradeSchedule(t *testing.T) {
+	config := DefaultWasmConfig()
+	require.Nil(t, config.UpgradeSchedule())
+
+	config.EnclaveUpgradeHeight = 100
+	schedule := config.UpgradeSchedule()
+	require.NotNil(t, schedule.CurrentEnclave)
+	require.Equal(t, uint64(100), *schedule.CurrentEnclave)
+}
@@ -4,6 +4,7 @@ import (
 	"net/url"
 	"regexp"
 
+	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
 	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
 )
 
@@ -13,6 +14,12 @@ const (
 	// MaxLabelSize is the longest label that can be used when Instantiating a contract
 	MaxLabelSize = 512
 
+	// MaxInstantiateSaltLength is the longest salt that can be used with Instantiate2
+	MaxInstantiateSaltLength = 64
+
+	// MaxAccessListKeys is the most keys the access list of an execute message can declare
+	MaxAccessListKeys = 256
+
 	// BuildTagRegexp is a docker image regexp.
 	// We only support max 128 characters, with at least one organization name (subset of all legal names).
 	//
@@ -78,3 +85,36 @@ func validateLabel(label string) error {
 	}
 	return nil
 }
+
+func validateAccessList(keys [][]byte) error {
+	if len(keys) > MaxAccessListKeys {
+		return sdkerrors.Wrapf(ErrLimit, "cannot declare more than %d keys", MaxAccessListKeys)
+	}
+	seen := make(map[string]bool, len(keys))
+	for _, key := range keys {
+		if len(key) == 0 {
+			return sdkerrors.Wrap(ErrEmpty, "key")
+		}
+		if seen[string(key)] {
+			return sdkerrors.Wrapf(ErrDuplicate, "key %X", key)
+		}
+		seen[string(key)] = true
+	}
+	return nil
+}
+
+func validateDeveloperSignature(pubKey []byte, signature []byte) error {
+	if len(pubKey) == 0 {
+		if len(signature) != 0 {
+			return sdkerrors.Wrap(ErrEmpty, "public key is required with a signature")
+		}
+		return nil
+	}
+	if len(pubKey) != secp256k1.PubKeySize {
+		return sdkerrors.Wrapf(ErrInvalid, "public key must be %d bytes", secp256k1.PubKeySize)
+	}
+	if len(signature) == 0 {
+		return sdkerrors.Wrap(ErrEmpty, "signature is required with a public key")
+	}
+	return nil
+}
@@ -52,7 +52,7 @@ func (AppModuleBasic) Name() string {
 // module.
 func (AppModuleBasic) DefaultGenesis(cdc codec.JSONCodec) json.RawMessage {
 	return cdc.MustMarshalJSON(&GenesisState{
-		// Params: DefaultParams(),
+		Params: DefaultParams(),
 	})
 }
 
@@ -103,7 +103,7 @@ func NewAppModule(keeper Keeper) AppModule {
 }
 
 // ConsensusVersion implements AppModule/ConsensusVersion.
-func (AppModule) ConsensusVersion() uint64 { return 5 }
+func (AppModule) ConsensusVersion() uint64 { return 6 }
 
 func (am AppModule) RegisterServices(configurator module.Configurator) {
 	types.RegisterMsgServer(configurator.MsgServer(), keeper.NewMsgServerImpl(am.keeper))
@@ -128,6 +128,11 @@ func (am AppModule) RegisterServices(configurator module.Configurator) {
 	if err != nil {
 		panic(err)
 	}
+
+	err = configurator.RegisterMigration(types.ModuleName, 5, m.Migrate5to6)
+	if err != nil {
+		panic(err)
+	}
 }
 
 func (am AppModule) LegacyQuerierHandler(_ *codec.LegacyAmino) sdk.Querier {
@@ -167,6 +172,11 @@ func (am AppModule) ExportGenesis(ctx sdk.Context, cdc codec.JSONCodec) json.Raw
 
 // BeginBlock returns the begin blocker for the compute module.
 func (am AppModule) BeginBlock(ctx sdk.Context, beginBlock abci.RequestBeginBlock) {
+	// halt rather than commit blocks whose transactions ran differently than on the network
+	if err := am.keeper.VerifyConsensusConfigHash(ctx); err != nil {
+		panic(err)
+	}
+
 	header, err := beginBlock.Header.Marshal()
 	if err != nil {
 		ctx.Logger().Error("Failed to marshal header")
@@ -195,7 +205,7 @@ func (am AppModule) BeginBlock(ctx sdk.Context, beginBlock abci.RequestBeginBloc
 
 	if beginBlock.Header.EncryptedRandom != nil {
 		randomAndProof := append(beginBlock.Header.EncryptedRandom.Random, beginBlock.Header.EncryptedRandom.Proof...) //nolint:all
-		random, err := api.SubmitBlockSignatures(header, commit, data, randomAndProof)
+		random, err := api.SubmitBlockSignatures(uint64(beginBlock.Header.Height), header, commit, data, randomAndProof)
 		if err != nil {
 			ctx.Logger().Error("Failed to submit block signatures")
 			panic(err)
@@ -207,9 +217,14 @@ func (am AppModule) BeginBlock(ctx sdk.Context, beginBlock abci.RequestBeginBloc
 	}
 }
 
-// EndBlock returns the end blocker for the compute module. It returns no validator
-// updates.
-func (AppModule) EndBlock(_ sdk.Context, _ abci.RequestEndBlock) []abci.ValidatorUpdate {
+// EndBlock returns the end blocker for the compute module. It deletes the wasm of the codes removed
+// during the block, reports the contract panic, auto pinning and cache metrics and returns no
+// validator updates.
+func (am AppModule) EndBlock(ctx sdk.Context, _ abci.RequestEndBlock) []abci.ValidatorUpdate {
+	am.keeper.RemovePendingCodes(ctx)
+	am.keeper.ReportContractPanics(ctx)
+	am.keeper.ReportAutoPinning(ctx)
+	am.keeper.ReportCacheMetrics(ctx)
 	return []abci.ValidatorUpdate{}
 }
 
@@ -19,6 +19,7 @@ const (
 	EnclaveRegistrationKey     = types.EnclaveRegistrationKey
 	QueryEncryptedSeed         = keeper.QueryEncryptedSeed
 	QueryMasterKey             = keeper.QueryMasterKey
+	QueryNodeCertificate       = keeper.QueryNodeCertificate
 	SecretNodeSeedLegacyConfig = types.SecretNodeSeedLegacyConfig
 	SecretNodeSeedNewConfig    = types.SecretNodeSeedNewConfig
 	SecretNodeCfgFolder        = types.SecretNodeCfgFolder
@@ -46,6 +47,7 @@ var (
 	NewLegacyQuerier            = keeper.NewLegacyQuerier
 	GetGenesisStateFromAppState = keeper.GetGenesisStateFromAppState
 	IsHexString                 = keeper.IsHexString
+	IsValidEncryptedKeyLength   = types.IsValidEncryptedKeyLength
 	GetApiKey                   = types.GetApiKey
 	GetSpid                     = types.GetSpid
 	// variable aliases
@@ -29,6 +29,7 @@ func GetQueryCmd() *cobra.Command {
 	queryCmd.AddCommand(
 		GetCmdEncryptedSeed(),
 		GetCmdMasterParams(),
+		GetCmdIoKyberKey(),
 	)
 	return queryCmd
 }
@@ -107,6 +108,33 @@ func GetCmdMasterParams() *cobra.Command {
 	return cmd
 }
 
+// GetCmdIoKyberKey gets the key of the experimental hybrid tx encryption
+func GetCmdIoKyberKey() *cobra.Command {
+	cmd := &cobra.Command{
+		Use:   "tx-key-pq",
+		Short: "Get the Kyber768 key of the experimental hybrid tx encryption",
+		Long: "Get the Kyber768 public key of the experimental hybrid X25519+Kyber768 tx encryption, from the queried node - " +
+			"writes it to [" + types.IoExchKyberMasterKeyPath + "]",
+		Args: cobra.ExactArgs(0),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			clientCtx, err := client.GetClientQueryContext(cmd)
+			if err != nil {
+				return err
+			}
+
+			route := fmt.Sprintf("custom/%s/%s", types.QuerierRoute, keeper.QueryIoKyberKey)
+			res, _, err := clientCtx.Query(route)
+			if err != nil {
+				return err
+			}
+
+			return os.WriteFile(types.IoExchKyberMasterKeyPath, []byte(base64.StdEncoding.EncodeToString(res)), 0o600)
+		},
+	}
+	flags.AddQueryFlagsToCmd(cmd)
+	return cmd
+}
+
 type argumentDecoder struct {
 	// dec is the default decoder
 	dec                func(string) ([]byte, error)
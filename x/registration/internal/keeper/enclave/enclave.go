@@ -10,6 +10,14 @@ func (Api) LoadSeed(masterKey []byte, seed []byte, apiKey []byte) (bool, error)
 	return api.LoadSeedToEnclave(masterKey, seed, apiKey)
 }
 
+func (Api) LoadPreviousSeeds(masterKey []byte, seeds []byte) (bool, error) {
+	return api.LoadPreviousSeedsToEnclave(masterKey, seeds)
+}
+
+func (Api) GetEncryptedPreviousSeeds(masterCert []byte) ([]byte, error) {
+	return api.GetEncryptedPreviousSeeds(masterCert)
+}
+
 func (Api) GetEncryptedSeed(masterCert []byte) ([]byte, error) {
 	return api.GetEncryptedSeed(masterCert)
 }
@@ -17,3 +25,7 @@ func (Api) GetEncryptedSeed(masterCert []byte) ([]byte, error) {
 func (Api) GetEncryptedGenesisSeed(pk []byte) ([]byte, error) {
 	return api.GetEncryptedGenesisSeed(pk)
 }
+
+func (Api) ActivateSeedRotation(encryptedCandidate []byte, activationHeight uint64, currentHeight uint64, threshold uint32, approvals [][]byte, certs [][]byte) (bool, error) {
+	return api.ActivateSeedRotation(encryptedCandidate, activationHeight, currentHeight, threshold, approvals, certs)
+}
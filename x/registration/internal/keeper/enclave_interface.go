@@ -2,6 +2,9 @@ package keeper
 
 type EnclaveInterface interface {
 	LoadSeed(masterKey []byte, seed []byte, apiKey []byte) (bool, error)
+	LoadPreviousSeeds(masterKey []byte, seeds []byte) (bool, error)
 	GetEncryptedSeed(masterCert []byte) ([]byte, error)
+	GetEncryptedPreviousSeeds(masterCert []byte) ([]byte, error)
 	GetEncryptedGenesisSeed(pk []byte) ([]byte, error)
+	ActivateSeedRotation(encryptedCandidate []byte, activationHeight uint64, currentHeight uint64, threshold uint32, approvals [][]byte, certs [][]byte) (bool, error)
 }
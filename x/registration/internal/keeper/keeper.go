@@ -1,6 +1,7 @@
 package keeper
 
 import (
+	"encoding/base64"
 	"encoding/binary"
 	"encoding/hex"
 	"encoding/json"
@@ -18,10 +19,13 @@ import (
 
 // Keeper will have a reference to Wasmer with it's own data directory.
 type Keeper struct {
-	storeKey sdk.StoreKey
-	cdc      codec.BinaryCodec
-	enclave  EnclaveInterface
-	router   sdk.Router
+	storeKey   sdk.StoreKey
+	cdc        codec.BinaryCodec
+	enclave    EnclaveInterface
+	router     sdk.Router
+	// ioKyberKey is the Kyber768 public key of the experimental hybrid tx encryption, written by
+	// enclaves built with it. It isn't part of the chain state, see GetIoKyberKey.
+	ioKyberKey []byte
 }
 
 // NewKeeper creates a new contract Keeper instance
@@ -31,13 +35,40 @@ func NewKeeper(cdc codec.BinaryCodec, storeKey sdk.StoreKey, router sdk.Router,
 	}
 
 	return Keeper{
-		storeKey: storeKey,
-		cdc:      cdc,
-		router:   router,
-		enclave:  enclave,
+		storeKey:   storeKey,
+		cdc:        cdc,
+		router:     router,
+		enclave:    enclave,
+		ioKyberKey: readIoKyberKey(),
 	}
 }
 
+func readIoKyberKey() []byte {
+	if !fileExists(types.IoExchKyberMasterKeyPath) {
+		return nil
+	}
+
+	key, err := os.ReadFile(types.IoExchKyberMasterKeyPath)
+	if err != nil {
+		return nil
+	}
+	keyBz, err := base64.StdEncoding.DecodeString(string(key))
+	if err != nil {
+		return nil
+	}
+	return keyBz
+}
+
+// GetIoKyberKey returns the Kyber768 public key of the experimental hybrid tx encryption, or nil if
+// the enclave of this node wasn't built with it.
+//
+// Every enclave derives the same key from the consensus seed, but it's served by the queried node
+// instead of the chain state. A node serving a wrong key can't decrypt the txs encrypted with it, as
+// they are also encrypted with the X25519 tx key, it can only make them lose the Kyber protection.
+func (k Keeper) GetIoKyberKey() []byte {
+	return k.ioKyberKey
+}
+
 func getSizedEncSeed(seed []byte) []byte {
 	// Add size indicator infront of the seed
 	// Size can always be represented by 1 byte as it can contain 2 seeds at most
@@ -50,6 +81,15 @@ func getSizedEncSeed(seed []byte) []byte {
 	return newEnc
 }
 
+// splitPreviousSeeds splits the seeds replaced by seed rotations, that RegisterNode appends to the
+// registration seed, off of it.
+func splitPreviousSeeds(seed []byte) ([]byte, []byte) {
+	if len(seed) <= types.EncryptedKeyLength/2 {
+		return seed, nil
+	}
+	return seed[:types.EncryptedKeyLength/2], seed[types.EncryptedKeyLength/2:]
+}
+
 func getNewSeedParams(path string) ([]byte, []byte) {
 	jsonContent, err := getFile(path)
 	if err != nil {
@@ -147,19 +187,26 @@ func InitializeNode(homeDir string, enclave EnclaveInterface) {
 		encSeed, pk = getNewSeedParams(seedPath)
 	}
 
-	sizedEndSeed := getSizedEncSeed(encSeed)
-	err = validateEncryptedSeed(hex.EncodeToString(sizedEndSeed))
+	err = validateEncryptedSeed(hex.EncodeToString(encSeed))
 	if err != nil {
 		panic(sdkerrors.Wrap(types.ErrSeedInitFailed, err.Error()))
 	}
+	encSeed, encPreviousSeeds := splitPreviousSeeds(encSeed)
 
 	// On upgrade LoadSeed will write the new seed to "SeedPath -- seed.txt" which then will be parsed by the upgrade handler to create new_seed.json
 	// On registration both seed.jsםn and new_seed.json will be created by 'secretd q register secret-network-params' on manual flow or by auto-registration flow"
-	_, err = enclave.LoadSeed(pk, sizedEndSeed, apiKey)
+	_, err = enclave.LoadSeed(pk, getSizedEncSeed(encSeed), apiKey)
 	if err != nil {
 		panic(sdkerrors.Wrap(types.ErrSeedInitFailed, err.Error()))
 	}
 
+	if len(encPreviousSeeds) > 0 {
+		_, err = enclave.LoadPreviousSeeds(pk, encPreviousSeeds)
+		if err != nil {
+			panic(sdkerrors.Wrap(types.ErrSeedInitFailed, err.Error()))
+		}
+	}
+
 	if !fileExists(legacySeedPath) {
 		sgxSecretsFolder := os.Getenv("SCRT_SGX_STORAGE")
 		if sgxSecretsFolder == "" {
@@ -216,6 +263,13 @@ func (k Keeper) RegisterNode(ctx sdk.Context, certificate ra.Certificate) ([]byt
 			// return 0, sdkerrors.Wrap(err, "cosmwasm create")
 			return nil, sdkerrors.Wrap(types.ErrAuthenticateFailed, err.Error())
 		}
+
+		// The seeds replaced by seed rotations are needed to read the state they encrypted
+		encPreviousSeeds, err := k.enclave.GetEncryptedPreviousSeeds(certificate)
+		if err != nil {
+			return nil, sdkerrors.Wrap(types.ErrAuthenticateFailed, err.Error())
+		}
+		encSeed = append(encSeed, encPreviousSeeds...)
 	}
 	fmt.Println("Done RegisterNode")
 	fmt.Println("Got seed: ", hex.EncodeToString(encSeed))
@@ -285,9 +339,7 @@ func FetchRawPubKeyFromLegacyCert(cert []byte) ([]byte, error) {
 }
 
 func validateEncryptedSeed(encSeed string) error {
-	lenKey := len(encSeed) - 2
-
-	if (lenKey != types.EncryptedKeyLength && lenKey != types.LegacyEncryptedKeyLength) || !IsHexString(encSeed) {
+	if !types.IsValidEncryptedKeyLength(len(encSeed)) || !IsHexString(encSeed) {
 		return sdkerrors.Wrap(types.ErrSeedValidationParams, "Invalid parameter: `seed` in seed parameters. Did you initialize the node?")
 	}
 	return nil
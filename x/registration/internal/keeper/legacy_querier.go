@@ -7,12 +7,15 @@ import (
 
 	sdk "github.com/cosmos/cosmos-sdk/types"
 	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	"github.com/scrtlabs/SecretNetwork/x/registration/internal/types"
 	abci "github.com/tendermint/tendermint/abci/types"
 )
 
 const (
-	QueryEncryptedSeed = "seed"
-	QueryMasterKey     = "master-key"
+	QueryEncryptedSeed   = "seed"
+	QueryMasterKey       = "master-key"
+	QueryIoKyberKey      = "io-kyber-key"
+	QueryNodeCertificate = "node-certificate"
 )
 
 // controls error output on querier - set true when testing/debugging
@@ -37,6 +40,13 @@ func NewLegacyQuerier(keeper Keeper) sdk.Querier {
 				return nil, err
 			}
 			return bz, nil
+		case QueryNodeCertificate:
+			// the certificate is signed by Intel, so it can be served by any node
+			pubKey, err := hex.DecodeString(path[1])
+			if err != nil {
+				return nil, sdkerrors.Wrap(sdkerrors.ErrInvalidAddress, err.Error())
+			}
+			return queryNodeCertificate(ctx, pubKey, keeper)
 		case QueryMasterKey:
 			rsp, err = queryMasterKey(ctx, keeper)
 			if err != nil {
@@ -52,6 +62,12 @@ func NewLegacyQuerier(keeper Keeper) sdk.Querier {
 				return nil, sdkerrors.Wrap(sdkerrors.ErrJSONMarshal, err.Error())
 			}
 			return bz, nil
+		case QueryIoKyberKey:
+			key := keeper.GetIoKyberKey()
+			if key == nil {
+				return nil, sdkerrors.Wrap(types.ErrNotFound, "the enclave of this node doesn't support hybrid tx encryption")
+			}
+			return key, nil
 		default:
 			return nil, sdkerrors.Wrap(sdkerrors.ErrUnknownRequest, "unknown data query endpoint")
 		}
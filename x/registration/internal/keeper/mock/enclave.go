@@ -8,6 +8,14 @@ func (MockEnclaveApi) LoadSeed(_ []byte, _ []byte, _ []byte) (bool, error) {
 	return true, nil
 }
 
+func (MockEnclaveApi) LoadPreviousSeeds(_ []byte, _ []byte) (bool, error) {
+	return true, nil
+}
+
+func (MockEnclaveApi) GetEncryptedPreviousSeeds(_ []byte) ([]byte, error) {
+	return []byte(""), nil
+}
+
 func (MockEnclaveApi) GetEncryptedSeed(_ []byte) ([]byte, error) {
 	return []byte(""), nil
 }
@@ -15,3 +23,7 @@ func (MockEnclaveApi) GetEncryptedSeed(_ []byte) ([]byte, error) {
 func (MockEnclaveApi) GetEncryptedGenesisSeed(_ []byte) ([]byte, error) {
 	return []byte(""), nil
 }
+
+func (MockEnclaveApi) ActivateSeedRotation(_ []byte, _ uint64, _ uint64, _ uint32, _ [][]byte, _ [][]byte) (bool, error) {
+	return false, nil
+}
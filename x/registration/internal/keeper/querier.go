@@ -84,3 +84,12 @@ func queryEncryptedSeed(ctx sdk.Context, pubkeyBytes []byte, keeper Keeper) ([]b
 
 	return seed.EncryptedSeed, nil
 }
+
+func queryNodeCertificate(ctx sdk.Context, pubkeyBytes []byte, keeper Keeper) ([]byte, error) {
+	regInfo := keeper.getRegistrationInfo(ctx, pubkeyBytes)
+	if regInfo == nil {
+		return nil, sdkerrors.Wrap(sdkerrors.ErrUnknownAddress, "Node has not been authenticated yet")
+	}
+
+	return regInfo.Certificate, nil
+}
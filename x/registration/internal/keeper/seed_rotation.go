@@ -0,0 +1,38 @@
+package keeper
+
+import (
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	"github.com/scrtlabs/SecretNetwork/x/registration/internal/types"
+)
+
+// ActivateSeedRotation activates the seed rotation carried by the info of the upgrade plan at
+// upgradeHeight, if there is one. It must be called by the upgrade handler, so that it runs once, in
+// the block at the upgrade height, and the new seed is used from then on. The enclave verifies every
+// approval against its certificate, enforces the threshold and checks that the block height reached
+// the upgrade height itself.
+//
+// It returns whether the node must be restarted for the enclave to use the new seed, which is the
+// case if the enclave already loaded its seeds.
+func (k Keeper) ActivateSeedRotation(ctx sdk.Context, upgradeHeight int64, info string) (bool, error) {
+	plan, err := types.ParseSeedRotationPlan(info)
+	if err != nil {
+		return false, sdkerrors.Wrap(types.ErrInvalid, err.Error())
+	}
+	if plan == nil {
+		return false, nil
+	}
+
+	approvals := make([][]byte, len(plan.Approvals))
+	certs := make([][]byte, len(plan.Approvals))
+	for i, approval := range plan.Approvals {
+		approvals[i] = approval.Approval
+		certs[i] = approval.Certificate
+	}
+
+	restartRequired, err := k.enclave.ActivateSeedRotation(plan.EncryptedCandidate, uint64(upgradeHeight), uint64(ctx.BlockHeight()), plan.Threshold, approvals, certs)
+	if err != nil {
+		return false, sdkerrors.Wrap(types.ErrSeedInitFailed, err.Error())
+	}
+	return restartRequired, nil
+}
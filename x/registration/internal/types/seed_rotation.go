@@ -0,0 +1,73 @@
+package types
+
+import (
+	"encoding/json"
+	"fmt"
+)
+
+const (
+	// SeedRotationCandidateLength is the length of an encrypted seed rotation candidate
+	SeedRotationCandidateLength = 48
+	// SeedRotationApprovalLength is the length of an approval: the approving node's registration
+	// key followed by a MAC over the candidate and activation height
+	SeedRotationApprovalLength = 64
+	// MinSeedRotationThreshold is the fewest approvals a rotation can require. The enclave refuses
+	// to activate rotations with a lower threshold.
+	MinSeedRotationThreshold = 2
+)
+
+// SeedRotationApproval is a node's approval of a seed rotation candidate, and the attestation
+// certificate of the node that made it
+type SeedRotationApproval struct {
+	Approval    []byte `json:"approval"`
+	Certificate []byte `json:"certificate"`
+}
+
+// SeedRotationPlan is a seed rotation carried by a software upgrade plan, as JSON under the
+// "seed_rotation" key of the plan's info. Every node activates the candidate in its enclave when
+// it restarts for the upgrade, so the rotation takes effect at the upgrade height.
+type SeedRotationPlan struct {
+	EncryptedCandidate []byte                 `json:"encrypted_candidate"`
+	Threshold          uint32                 `json:"threshold"`
+	Approvals          []SeedRotationApproval `json:"approvals"`
+}
+
+// ParseSeedRotationPlan returns the seed rotation in the info of an upgrade plan, or nil if the
+// upgrade doesn't rotate the seed
+func ParseSeedRotationPlan(info string) (*SeedRotationPlan, error) {
+	var parsed struct {
+		SeedRotation *SeedRotationPlan `json:"seed_rotation"`
+	}
+	// Plans usually carry a link or binaries in their info, which isn't a rotation
+	if err := json.Unmarshal([]byte(info), &parsed); err != nil || parsed.SeedRotation == nil {
+		return nil, nil //nolint:nilerr
+	}
+
+	if err := parsed.SeedRotation.ValidateBasic(); err != nil {
+		return nil, err
+	}
+	return parsed.SeedRotation, nil
+}
+
+// ValidateBasic checks the lengths of the rotation's fields and that it has enough approvals to
+// be activated
+func (p SeedRotationPlan) ValidateBasic() error {
+	if len(p.EncryptedCandidate) != SeedRotationCandidateLength {
+		return fmt.Errorf("encrypted candidate must be %d bytes long", SeedRotationCandidateLength)
+	}
+	if p.Threshold < MinSeedRotationThreshold {
+		return fmt.Errorf("threshold must be at least %d", MinSeedRotationThreshold)
+	}
+	if len(p.Approvals) < int(p.Threshold) {
+		return fmt.Errorf("%d approvals can't reach a threshold of %d", len(p.Approvals), p.Threshold)
+	}
+	for i, approval := range p.Approvals {
+		if len(approval.Approval) != SeedRotationApprovalLength {
+			return fmt.Errorf("approval %d must be %d bytes long", i, SeedRotationApprovalLength)
+		}
+		if len(approval.Certificate) == 0 {
+			return fmt.Errorf("approval %d has no certificate", i)
+		}
+	}
+	return nil
+}
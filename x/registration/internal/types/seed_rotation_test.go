@@ -0,0 +1,53 @@
+package types
+
+import (
+	"encoding/json"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+)
+
+func seedRotationInfo(t *testing.T, plan SeedRotationPlan) string {
+	bz, err := json.Marshal(map[string]SeedRotationPlan{"seed_rotation": plan})
+	require.NoError(t, err)
+	return string(bz)
+}
+
+func TestParseSeedRotationPlan(t *testing.T) {
+	approval := SeedRotationApproval{
+		Approval:    make([]byte, SeedRotationApprovalLength),
+		Certificate: []byte("cert"),
+	}
+	valid := SeedRotationPlan{
+		EncryptedCandidate: make([]byte, SeedRotationCandidateLength),
+		Threshold:          2,
+		Approvals:          []SeedRotationApproval{approval, approval},
+	}
+
+	plan, err := ParseSeedRotationPlan(seedRotationInfo(t, valid))
+	require.NoError(t, err)
+	require.Equal(t, &valid, plan)
+
+	// plans that don't rotate the seed
+	plan, err = ParseSeedRotationPlan("https://github.com/scrtlabs/SecretNetwork/releases")
+	require.NoError(t, err)
+	require.Nil(t, plan)
+	plan, err = ParseSeedRotationPlan(`{"binaries":{}}`)
+	require.NoError(t, err)
+	require.Nil(t, plan)
+
+	lowThreshold := valid
+	lowThreshold.Threshold = 1
+	_, err = ParseSeedRotationPlan(seedRotationInfo(t, lowThreshold))
+	require.Error(t, err)
+
+	notEnoughApprovals := valid
+	notEnoughApprovals.Approvals = valid.Approvals[:1]
+	_, err = ParseSeedRotationPlan(seedRotationInfo(t, notEnoughApprovals))
+	require.Error(t, err)
+
+	noCert := valid
+	noCert.Approvals = []SeedRotationApproval{approval, {Approval: approval.Approval}}
+	_, err = ParseSeedRotationPlan(seedRotationInfo(t, noCert))
+	require.Error(t, err)
+}
@@ -92,6 +92,31 @@ func UNSAFE_VerifyRaCert(rawCert []byte) ([]byte, error) {
 	return pubK[0:32], nil
 }
 
+// VerifyRaCertReportData verifies a hardware mode certificate like VerifyRaCert, and returns the whole
+// 64 bytes of report data: the public key of the node, the 4 bytes of the seed service challenge, and
+// the commitment to the key the node signs receipts and proofs with. Software mode certificates are
+// not signed by Intel, so they are rejected whatever the mode of this node.
+func VerifyRaCertReportData(rawCert []byte) ([]byte, error) {
+	pubK, payload, err := unmarshalCert(rawCert)
+	if err != nil {
+		return nil, err
+	}
+
+	attnReportRaw, err := verifyCert(payload)
+	if err != nil {
+		return nil, err
+	}
+
+	reportData, err := verifyAttReport(attnReportRaw, pubK)
+	if err != nil {
+		return nil, err
+	}
+	if len(reportData) != 64 {
+		return nil, fmt.Errorf("unexpected report data length %d", len(reportData))
+	}
+	return reportData, nil
+}
+
 func extractAsn1Value(cert []byte, oid []byte) ([]byte, error) {
 	offset := uint(bytes.Index(cert, oid))
 	offset += 12 // 11 + TAG (0x04)